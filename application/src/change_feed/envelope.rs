@@ -0,0 +1,184 @@
+use kernel::interfaces::change_feed::ChangeEnvelope;
+use kernel::interfaces::projection::SeqEvent;
+use kernel::prelude::entity::{
+    Account, AccountEvent, FieldAction, ImageId, Metadata, MetadataEvent, Profile, ProfileEvent,
+};
+use serde_json::{json, Map, Value};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+pub const ACCOUNT_AGGREGATE: &str = "account";
+pub const PROFILE_AGGREGATE: &str = "profile";
+pub const METADATA_AGGREGATE: &str = "metadata";
+
+fn rfc3339(value: &OffsetDateTime) -> Value {
+    value
+        .format(&Rfc3339)
+        .map(Value::String)
+        .unwrap_or(Value::Null)
+}
+
+fn image_id(value: &ImageId) -> Value {
+    Value::String(value.as_ref().to_string())
+}
+
+/// Insert `key` only when the field changed: `Clear` becomes `null`.
+fn insert_field_action<T>(
+    data: &mut Map<String, Value>,
+    key: &str,
+    action: &FieldAction<T>,
+    to_value: impl FnOnce(&T) -> Value,
+) {
+    match action {
+        FieldAction::Unchanged => {}
+        FieldAction::Clear => {
+            data.insert(key.to_string(), Value::Null);
+        }
+        FieldAction::Set(value) => {
+            data.insert(key.to_string(), to_value(value));
+        }
+    }
+}
+
+/// Map a stored account event to its public change feed envelope.
+///
+/// The payload is built field by field so that renaming the internal event
+/// shape never changes the published contract.
+pub fn account_change(event: &SeqEvent<AccountEvent, Account>) -> ChangeEnvelope {
+    let (event_type, data) = match &event.envelope.event {
+        AccountEvent::Created {
+            name,
+            is_bot,
+            nanoid,
+            ..
+        } => (
+            "account.created",
+            json!({
+                "name": name.as_ref(),
+                "is_bot": is_bot.as_ref(),
+                "nanoid": nanoid.as_ref(),
+            }),
+        ),
+        AccountEvent::Updated { is_bot } => {
+            ("account.updated", json!({ "is_bot": is_bot.as_ref() }))
+        }
+        AccountEvent::Deactivated => ("account.deactivated", json!({})),
+        AccountEvent::Suspended {
+            reason,
+            suspended_at,
+            expires_at,
+        } => (
+            "account.suspended",
+            json!({
+                "reason": reason,
+                "suspended_at": rfc3339(suspended_at),
+                "expires_at": expires_at.as_ref().map(rfc3339),
+            }),
+        ),
+        AccountEvent::Unsuspended => ("account.unsuspended", json!({})),
+        AccountEvent::Banned { reason, banned_at } => (
+            "account.banned",
+            json!({
+                "reason": reason,
+                "banned_at": rfc3339(banned_at),
+            }),
+        ),
+        AccountEvent::Unbanned => ("account.unbanned", json!({})),
+        AccountEvent::Reactivated => ("account.reactivated", json!({})),
+    };
+    ChangeEnvelope::new(
+        ACCOUNT_AGGREGATE,
+        *event.envelope.id.as_ref(),
+        *event.envelope.version.as_ref(),
+        event.seq,
+        event_type,
+        data,
+    )
+}
+
+/// Map a stored profile event to its public change feed envelope.
+///
+/// `profile.updated` carries only the changed fields; a cleared field is `null`.
+pub fn profile_change(event: &SeqEvent<ProfileEvent, Profile>) -> ChangeEnvelope {
+    let (event_type, data) = match &event.envelope.event {
+        ProfileEvent::Created {
+            account_id,
+            display_name,
+            summary,
+            icon,
+            banner,
+            nanoid,
+        } => (
+            "profile.created",
+            json!({
+                "account_id": account_id.as_ref().to_string(),
+                "display_name": display_name.as_ref().map(|value| value.as_ref()),
+                "summary": summary.as_ref().map(|value| value.as_ref()),
+                "icon_id": icon.as_ref().map(image_id),
+                "banner_id": banner.as_ref().map(image_id),
+                "nanoid": nanoid.as_ref(),
+            }),
+        ),
+        ProfileEvent::Updated {
+            display_name,
+            summary,
+            icon,
+            banner,
+        } => {
+            let mut data = Map::new();
+            insert_field_action(&mut data, "display_name", display_name, |value| {
+                Value::String(value.as_ref().clone())
+            });
+            insert_field_action(&mut data, "summary", summary, |value| {
+                Value::String(value.as_ref().clone())
+            });
+            insert_field_action(&mut data, "icon_id", icon, image_id);
+            insert_field_action(&mut data, "banner_id", banner, image_id);
+            ("profile.updated", Value::Object(data))
+        }
+    };
+    ChangeEnvelope::new(
+        PROFILE_AGGREGATE,
+        *event.envelope.id.as_ref(),
+        *event.envelope.version.as_ref(),
+        event.seq,
+        event_type,
+        data,
+    )
+}
+
+/// Map a stored metadata event to its public change feed envelope.
+pub fn metadata_change(event: &SeqEvent<MetadataEvent, Metadata>) -> ChangeEnvelope {
+    let (event_type, data) = match &event.envelope.event {
+        MetadataEvent::Created {
+            account_id,
+            label,
+            content,
+            nanoid,
+        } => (
+            "metadata.created",
+            json!({
+                "account_id": account_id.as_ref().to_string(),
+                "label": label.as_ref(),
+                "content": content.as_ref(),
+                "nanoid": nanoid.as_ref(),
+            }),
+        ),
+        MetadataEvent::Updated { label, content } => (
+            "metadata.updated",
+            json!({
+                "label": label.as_ref(),
+                "content": content.as_ref(),
+            }),
+        ),
+        MetadataEvent::Deleted => ("metadata.deleted", json!({})),
+    };
+    ChangeEnvelope::new(
+        METADATA_AGGREGATE,
+        *event.envelope.id.as_ref(),
+        *event.envelope.version.as_ref(),
+        event.seq,
+        event_type,
+        data,
+    )
+}
@@ -0,0 +1,8 @@
+mod envelope;
+mod publisher;
+
+#[cfg(test)]
+mod tests;
+
+pub use envelope::*;
+pub use publisher::*;
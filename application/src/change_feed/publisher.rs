@@ -0,0 +1,200 @@
+use super::envelope::{account_change, metadata_change, profile_change};
+use kernel::interfaces::change_feed::{
+    ChangeEnvelope, ChangeFeedPublisher, DependOnChangeFeedPublisher,
+};
+use kernel::interfaces::database::{DatabaseConnection, DependOnDatabaseConnection};
+use kernel::interfaces::projection::{
+    AccountEventLog, DependOnAccountEventLog, DependOnMetadataEventLog, DependOnProfileEventLog,
+    DependOnProjectionCheckpointStore, MetadataEventLog, ProfileEventLog,
+    ProjectionCheckpointStore,
+};
+use kernel::KernelError;
+use std::collections::BTreeSet;
+use std::future::Future;
+
+pub const ACCOUNT_CHANGE_FEED_NAME: &str = "account_change_feed";
+pub const PROFILE_CHANGE_FEED_NAME: &str = "profile_change_feed";
+pub const METADATA_CHANGE_FEED_NAME: &str = "metadata_change_feed";
+
+/// Window re-read margin (see `ACCOUNT_PROJECTOR_WINDOW`). Events published
+/// on an earlier poll are skipped through `PublishedWindow`.
+pub const CHANGE_FEED_WINDOW: i64 = 100;
+
+/// Upper bound of one poll batch. Must exceed `CHANGE_FEED_WINDOW`.
+pub const CHANGE_FEED_BATCH_LIMIT: i64 = 1000;
+
+/// Seqs already published inside the re-read window of one feed.
+///
+/// The window re-read would otherwise republish the same tail on every poll.
+/// The set lives in memory only: after a restart the window is published once
+/// more, which the at-least-once contract allows.
+#[derive(Debug, Default)]
+pub struct PublishedWindow {
+    seqs: BTreeSet<i64>,
+}
+
+impl PublishedWindow {
+    pub fn contains(&self, seq: i64) -> bool {
+        self.seqs.contains(&seq)
+    }
+
+    fn record(&mut self, seqs: impl IntoIterator<Item = i64>, checkpoint: i64) {
+        self.seqs.extend(seqs);
+        self.seqs = self.seqs.split_off(&(checkpoint - CHANGE_FEED_WINDOW + 1));
+    }
+}
+
+/// Change feed publisher (CDC stream) tailing the account, profile and
+/// metadata event logs.
+///
+/// Each feed keeps its own checkpoint in `ProjectionCheckpointStore`. A poll
+/// reads the seq window, publishes the envelopes not yet sent, and only then
+/// advances the checkpoint, so a crash or a publish failure re-sends rather
+/// than drops events (at-least-once).
+pub trait PublishChangeFeedBatch:
+    DependOnDatabaseConnection
+    + DependOnAccountEventLog
+    + DependOnProfileEventLog
+    + DependOnMetadataEventLog
+    + DependOnProjectionCheckpointStore
+    + DependOnChangeFeedPublisher
+{
+    /// Run one poll of the account feed. Returns the checkpoint after the poll.
+    fn publish_account_changes<'a>(
+        &'a self,
+        published: &'a mut PublishedWindow,
+    ) -> impl Future<Output = error_stack::Result<i64, KernelError>> + Send + 'a {
+        async move {
+            let mut executor = self.database_connection().connection().await?;
+            let checkpoint = self
+                .projection_checkpoint_store()
+                .get(&mut executor, ACCOUNT_CHANGE_FEED_NAME)
+                .await?
+                .unwrap_or(0);
+            let events = self
+                .account_event_log()
+                .find_by_seq_window(
+                    &mut executor,
+                    checkpoint - CHANGE_FEED_WINDOW,
+                    CHANGE_FEED_BATCH_LIMIT,
+                )
+                .await?;
+            let envelopes = events.iter().map(account_change).collect();
+            publish_window(
+                self,
+                &mut executor,
+                ACCOUNT_CHANGE_FEED_NAME,
+                checkpoint,
+                envelopes,
+                published,
+            )
+            .await
+        }
+    }
+
+    /// Run one poll of the profile feed. Returns the checkpoint after the poll.
+    fn publish_profile_changes<'a>(
+        &'a self,
+        published: &'a mut PublishedWindow,
+    ) -> impl Future<Output = error_stack::Result<i64, KernelError>> + Send + 'a {
+        async move {
+            let mut executor = self.database_connection().connection().await?;
+            let checkpoint = self
+                .projection_checkpoint_store()
+                .get(&mut executor, PROFILE_CHANGE_FEED_NAME)
+                .await?
+                .unwrap_or(0);
+            let events = self
+                .profile_event_log()
+                .find_by_seq_window(
+                    &mut executor,
+                    checkpoint - CHANGE_FEED_WINDOW,
+                    CHANGE_FEED_BATCH_LIMIT,
+                )
+                .await?;
+            let envelopes = events.iter().map(profile_change).collect();
+            publish_window(
+                self,
+                &mut executor,
+                PROFILE_CHANGE_FEED_NAME,
+                checkpoint,
+                envelopes,
+                published,
+            )
+            .await
+        }
+    }
+
+    /// Run one poll of the metadata feed. Returns the checkpoint after the poll.
+    fn publish_metadata_changes<'a>(
+        &'a self,
+        published: &'a mut PublishedWindow,
+    ) -> impl Future<Output = error_stack::Result<i64, KernelError>> + Send + 'a {
+        async move {
+            let mut executor = self.database_connection().connection().await?;
+            let checkpoint = self
+                .projection_checkpoint_store()
+                .get(&mut executor, METADATA_CHANGE_FEED_NAME)
+                .await?
+                .unwrap_or(0);
+            let events = self
+                .metadata_event_log()
+                .find_by_seq_window(
+                    &mut executor,
+                    checkpoint - CHANGE_FEED_WINDOW,
+                    CHANGE_FEED_BATCH_LIMIT,
+                )
+                .await?;
+            let envelopes = events.iter().map(metadata_change).collect();
+            publish_window(
+                self,
+                &mut executor,
+                METADATA_CHANGE_FEED_NAME,
+                checkpoint,
+                envelopes,
+                published,
+            )
+            .await
+        }
+    }
+}
+
+impl<T> PublishChangeFeedBatch for T where
+    T: DependOnDatabaseConnection
+        + DependOnAccountEventLog
+        + DependOnProfileEventLog
+        + DependOnMetadataEventLog
+        + DependOnProjectionCheckpointStore
+        + DependOnChangeFeedPublisher
+{
+}
+
+async fn publish_window<T>(
+    deps: &T,
+    executor: &mut <T::DatabaseConnection as DatabaseConnection>::Connection,
+    feed_name: &str,
+    checkpoint: i64,
+    envelopes: Vec<ChangeEnvelope>,
+    published: &mut PublishedWindow,
+) -> error_stack::Result<i64, KernelError>
+where
+    T: DependOnProjectionCheckpointStore + DependOnChangeFeedPublisher + ?Sized,
+{
+    let Some(max_seq) = envelopes.last().map(|envelope| envelope.seq) else {
+        return Ok(checkpoint);
+    };
+    let seqs: Vec<i64> = envelopes.iter().map(|envelope| envelope.seq).collect();
+    let pending: Vec<ChangeEnvelope> = envelopes
+        .into_iter()
+        .filter(|envelope| !published.contains(envelope.seq))
+        .collect();
+    if !pending.is_empty() {
+        deps.change_feed_publisher().publish(&pending).await?;
+    }
+    let checkpoint = checkpoint.max(max_seq);
+    published.record(seqs, checkpoint);
+    deps.projection_checkpoint_store()
+        .set(executor, feed_name, checkpoint)
+        .await?;
+    Ok(checkpoint)
+}
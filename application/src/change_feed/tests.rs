@@ -0,0 +1,241 @@
+use super::{
+    account_change, profile_change, PublishChangeFeedBatch, PublishedWindow,
+    ACCOUNT_CHANGE_FEED_NAME,
+};
+use crate::test_utils::{
+    delete_account_events, last_account_event_seq, persist_created_and_suspended,
+    reset_checkpoint_to_head,
+};
+use driver::database::PostgresDatabase;
+use error_stack::Report;
+use kernel::impl_database_delegation;
+use kernel::interfaces::change_feed::{
+    ChangeEnvelope, ChangeFeedPublisher, DependOnChangeFeedPublisher,
+};
+use kernel::interfaces::projection::SeqEvent;
+use kernel::prelude::entity::{
+    Account, AccountEvent, AccountId, AccountIsBot, AccountName, AuthAccountId, EventEnvelope,
+    EventId, EventVersion, FieldAction, ImageId, Nanoid, ProfileDisplayName, ProfileEvent,
+};
+use kernel::KernelError;
+use serde_json::json;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use time::OffsetDateTime;
+
+/// Change feed tests share the `account_change_feed` checkpoint row.
+static CHANGE_FEED_TEST_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+#[derive(Default)]
+struct RecordingPublisher {
+    published: Mutex<Vec<ChangeEnvelope>>,
+    fail: AtomicBool,
+}
+
+impl ChangeFeedPublisher for RecordingPublisher {
+    async fn publish(&self, envelopes: &[ChangeEnvelope]) -> error_stack::Result<(), KernelError> {
+        if self.fail.load(Ordering::SeqCst) {
+            return Err(Report::new(KernelError::Internal).attach_printable("stream unavailable"));
+        }
+        self.published
+            .lock()
+            .unwrap()
+            .extend(envelopes.iter().cloned());
+        Ok(())
+    }
+}
+
+struct ChangeFeedTest {
+    db: PostgresDatabase,
+    publisher: RecordingPublisher,
+}
+
+impl_database_delegation!(ChangeFeedTest, db, PostgresDatabase);
+
+impl DependOnChangeFeedPublisher for ChangeFeedTest {
+    type ChangeFeedPublisher = RecordingPublisher;
+
+    fn change_feed_publisher(&self) -> &Self::ChangeFeedPublisher {
+        &self.publisher
+    }
+}
+
+impl ChangeFeedTest {
+    fn published_for(&self, account_id: &AccountId) -> Vec<ChangeEnvelope> {
+        let aggregate_id = account_id.as_ref().to_string();
+        self.publisher
+            .published
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|envelope| envelope.aggregate_id == aggregate_id)
+            .cloned()
+            .collect()
+    }
+}
+
+fn seq_event<Event, Entity>(
+    seq: i64,
+    id: i64,
+    version: i64,
+    event: Event,
+) -> SeqEvent<Event, Entity> {
+    SeqEvent {
+        seq,
+        envelope: EventEnvelope::new(EventId::new(id), event, EventVersion::new(version)),
+    }
+}
+
+async fn publish_until_checkpoint_covers(
+    feed: &ChangeFeedTest,
+    published: &mut PublishedWindow,
+    account_id: &AccountId,
+) -> error_stack::Result<i64, KernelError> {
+    let target = last_account_event_seq(&feed.db, account_id).await;
+    let mut checkpoint = 0;
+    for _ in 0..50 {
+        checkpoint = feed.publish_account_changes(published).await?;
+        if checkpoint >= target {
+            break;
+        }
+    }
+    Ok(checkpoint)
+}
+
+#[test]
+fn account_suspended_envelope_has_stable_payload() {
+    let envelope = account_change(&seq_event::<AccountEvent, Account>(
+        7,
+        42,
+        2,
+        AccountEvent::Suspended {
+            reason: "spam".to_string(),
+            suspended_at: OffsetDateTime::from_unix_timestamp(1_767_323_045).unwrap(),
+            expires_at: None,
+        },
+    ));
+
+    assert_eq!(
+        serde_json::to_value(&envelope).unwrap(),
+        json!({
+            "schema_version": 1,
+            "event_id": "account:42:2",
+            "event_type": "account.suspended",
+            "aggregate_type": "account",
+            "aggregate_id": "42",
+            "aggregate_version": 2,
+            "seq": 7,
+            "data": {
+                "reason": "spam",
+                "suspended_at": "2026-01-02T03:04:05Z",
+                "expires_at": null,
+            },
+        })
+    );
+}
+
+#[test]
+fn account_created_envelope_omits_auth_account_linkage() {
+    let envelope = account_change(&seq_event::<AccountEvent, Account>(
+        1,
+        42,
+        1,
+        AccountEvent::Created {
+            name: AccountName::new("alice".to_string()),
+            is_bot: AccountIsBot::new(true),
+            nanoid: Nanoid::new("nano-alice"),
+            auth_account_id: AuthAccountId::new(9),
+        },
+    ));
+
+    assert_eq!(envelope.event_type, "account.created");
+    assert_eq!(
+        envelope.data,
+        json!({ "name": "alice", "is_bot": true, "nanoid": "nano-alice" })
+    );
+}
+
+#[test]
+fn profile_updated_envelope_carries_only_changed_fields() {
+    let envelope = profile_change(&seq_event(
+        3,
+        10,
+        4,
+        ProfileEvent::Updated {
+            display_name: FieldAction::Set(ProfileDisplayName::new("Alice".to_string())),
+            summary: FieldAction::Unchanged,
+            icon: FieldAction::Clear,
+            banner: FieldAction::Set(ImageId::new(99)),
+        },
+    ));
+
+    assert_eq!(envelope.event_type, "profile.updated");
+    assert_eq!(
+        envelope.data,
+        json!({ "display_name": "Alice", "icon_id": null, "banner_id": "99" })
+    );
+}
+
+#[test_with::env(DATABASE_URL)]
+#[tokio::test]
+async fn publishes_account_events_once_and_advances_checkpoint() {
+    let _guard = CHANGE_FEED_TEST_LOCK.lock().await;
+    kernel::ensure_generator_initialized();
+    let feed = ChangeFeedTest {
+        db: PostgresDatabase::new().await.unwrap(),
+        publisher: RecordingPublisher::default(),
+    };
+    reset_checkpoint_to_head(&feed.db, ACCOUNT_CHANGE_FEED_NAME).await;
+    let account_id = AccountId::default();
+    persist_created_and_suspended(&feed.db, &account_id).await;
+    let mut published = PublishedWindow::default();
+
+    let checkpoint_1 = publish_until_checkpoint_covers(&feed, &mut published, &account_id)
+        .await
+        .unwrap();
+    let checkpoint_2 = feed.publish_account_changes(&mut published).await.unwrap();
+
+    let envelopes = feed.published_for(&account_id);
+    let event_types: Vec<&str> = envelopes
+        .iter()
+        .map(|envelope| envelope.event_type.as_str())
+        .collect();
+    assert_eq!(event_types, vec!["account.created", "account.suspended"]);
+    assert!(
+        checkpoint_2 >= checkpoint_1,
+        "checkpoint must never regress"
+    );
+    delete_account_events(&feed.db, &account_id).await;
+}
+
+#[test_with::env(DATABASE_URL)]
+#[tokio::test]
+async fn failed_publish_keeps_checkpoint_and_retries_batch() {
+    let _guard = CHANGE_FEED_TEST_LOCK.lock().await;
+    kernel::ensure_generator_initialized();
+    let feed = ChangeFeedTest {
+        db: PostgresDatabase::new().await.unwrap(),
+        publisher: RecordingPublisher::default(),
+    };
+    reset_checkpoint_to_head(&feed.db, ACCOUNT_CHANGE_FEED_NAME).await;
+    let account_id = AccountId::default();
+    persist_created_and_suspended(&feed.db, &account_id).await;
+    let mut published = PublishedWindow::default();
+
+    feed.publisher.fail.store(true, Ordering::SeqCst);
+    let failed = feed.publish_account_changes(&mut published).await;
+    assert!(failed.is_err());
+    assert!(feed.published_for(&account_id).is_empty());
+
+    feed.publisher.fail.store(false, Ordering::SeqCst);
+    publish_until_checkpoint_covers(&feed, &mut published, &account_id)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        feed.published_for(&account_id).len(),
+        2,
+        "the failed batch must be re-sent once the stream recovers"
+    );
+    delete_account_events(&feed.db, &account_id).await;
+}
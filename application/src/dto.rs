@@ -1,7 +1,11 @@
 pub mod account;
 pub mod activitypub;
 pub mod block_mute;
+pub mod custom_role;
 pub mod media;
 pub mod metadata;
 pub mod pagination;
+pub mod personal_access_token;
 pub mod profile;
+pub mod projection;
+pub mod webhook;
@@ -1,4 +1,9 @@
-use kernel::prelude::entity::{Account, AccountStatus, FieldAction};
+use crate::dto::media::{ImageFocusDto, ImageRenditionDto};
+use kernel::interfaces::permission::{AccountRelation, InstanceRole};
+use kernel::prelude::entity::{
+    Account, AccountStatus, FieldAction, Image, ImageVariantKind, InstanceRoleChange,
+    InstanceRoleChangeAction,
+};
 use time::OffsetDateTime;
 
 #[derive(Debug)]
@@ -15,9 +20,53 @@ pub struct UpdateAccountDto {
     pub summary: FieldAction<String>,
     pub icon_url: FieldAction<String>,
     pub banner_url: FieldAction<String>,
+    /// Alt text and focal point of the icon and banner, written to the
+    /// image the slot holds after this update.
+    pub icon_description: FieldAction<String>,
+    pub icon_focus: FieldAction<ImageFocusDto>,
+    pub banner_description: FieldAction<String>,
+    pub banner_focus: FieldAction<ImageFocusDto>,
     pub fields: Option<Vec<AccountFieldDto>>,
 }
 
+/// One relation an auth account holds on an account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountMemberDto {
+    pub auth_account_id: String,
+    pub relation: AccountRelation,
+}
+
+/// An auth account holding an instance role, with the accounts linked to it.
+#[derive(Debug)]
+pub struct InstanceRoleHolderDto {
+    pub auth_account_id: String,
+    pub role: InstanceRole,
+    pub accounts: Vec<AccountDto>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstanceRoleChangeDto {
+    pub id: String,
+    pub role: InstanceRole,
+    pub action: InstanceRoleChangeAction,
+    pub subject_auth_account_id: String,
+    pub actor_auth_account_id: String,
+    pub created_at: OffsetDateTime,
+}
+
+impl From<InstanceRoleChange> for InstanceRoleChangeDto {
+    fn from(change: InstanceRoleChange) -> Self {
+        Self {
+            id: change.id.as_ref().to_string(),
+            role: change.role,
+            action: change.action,
+            subject_auth_account_id: change.subject.as_ref().to_string(),
+            actor_auth_account_id: change.actor.as_ref().to_string(),
+            created_at: change.created_at,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct AccountDto {
     pub nanoid: String,
@@ -41,6 +90,8 @@ pub struct AccountDetailDto {
     pub summary: Option<String>,
     pub icon_url: Option<String>,
     pub banner_url: Option<String>,
+    pub icon: Option<ImageRenditionDto>,
+    pub banner: Option<ImageRenditionDto>,
     pub is_bot: bool,
     pub fields: Vec<AccountFieldDto>,
     pub created_at: OffsetDateTime,
@@ -93,8 +144,8 @@ impl AccountDto {
         self,
         display_name: Option<String>,
         summary: Option<String>,
-        icon_url: Option<String>,
-        banner_url: Option<String>,
+        icon: Option<&Image>,
+        banner: Option<&Image>,
         fields: Vec<AccountFieldDto>,
     ) -> AccountDetailDto {
         AccountDetailDto {
@@ -102,8 +153,10 @@ impl AccountDto {
             name: self.name,
             display_name,
             summary,
-            icon_url,
-            banner_url,
+            icon_url: icon.map(|image| image.url().as_ref().to_string()),
+            banner_url: banner.map(|image| image.url().as_ref().to_string()),
+            icon: icon.map(|image| ImageRenditionDto::of(image, ImageVariantKind::Avatar)),
+            banner: banner.map(|image| ImageRenditionDto::of(image, ImageVariantKind::Header)),
             is_bot: self.is_bot,
             fields,
             created_at: self.created_at,
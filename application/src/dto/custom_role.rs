@@ -0,0 +1,41 @@
+use kernel::interfaces::permission::InstancePermission;
+use kernel::prelude::entity::{AuthAccountId, CustomRole};
+use time::OffsetDateTime;
+
+pub struct CreateCustomRoleDto {
+    pub name: String,
+    pub permissions: Vec<InstancePermission>,
+}
+
+/// Fields left `None` keep their current value.
+pub struct UpdateCustomRoleDto {
+    pub name: Option<String>,
+    pub permissions: Option<Vec<InstancePermission>>,
+}
+
+#[derive(Debug)]
+pub struct CustomRoleDto {
+    pub id: String,
+    pub name: String,
+    pub permissions: Vec<InstancePermission>,
+    /// Auth account ids of the members.
+    pub members: Vec<String>,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+impl CustomRoleDto {
+    pub(crate) fn new(role: CustomRole, members: Vec<AuthAccountId>) -> Self {
+        Self {
+            id: role.id.as_ref().to_string(),
+            name: role.name,
+            permissions: role.permissions,
+            members: members
+                .iter()
+                .map(|member| member.as_ref().to_string())
+                .collect(),
+            created_at: role.created_at,
+            updated_at: role.updated_at,
+        }
+    }
+}
@@ -1,13 +1,240 @@
-#[derive(Debug, Clone, PartialEq, Eq)]
+use crate::service::media::ANIMATED_MEDIA_TYPE;
+use kernel::prelude::entity::{
+    Image, ImageBlocklistEntry, ImageFocus, ImageVariant, ImageVariantKind,
+};
+use time::OffsetDateTime;
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct UploadImageDto {
     pub content_type: String,
     pub bytes: Vec<u8>,
+    pub description: Option<String>,
+    pub focus: Option<ImageFocusDto>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct UploadedImageDto {
     pub id: String,
     pub url: String,
     pub hash: String,
     pub blur_hash: String,
+    pub variants: Vec<ImageVariantDto>,
+    pub animated: bool,
+    pub description: Option<String>,
+    pub focus: Option<ImageFocusDto>,
+    /// Held for moderator review; cannot be used as an icon or banner.
+    pub quarantined: bool,
+}
+
+/// Opens a direct upload of `byte_size` bytes of `content_type`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreateUploadSessionDto {
+    pub content_type: String,
+    pub byte_size: u64,
+    pub description: Option<String>,
+    pub focus: Option<ImageFocusDto>,
+}
+
+/// Where and how to send the bytes: a `method` request to `upload_url`
+/// carrying `headers`, before `expires_at`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadSessionDto {
+    pub id: String,
+    pub upload_url: String,
+    pub method: String,
+    pub headers: Vec<(String, String)>,
+    pub expires_at: OffsetDateTime,
+}
+
+/// Adds an existing image to the blocklist. `action` is `reject` or
+/// `quarantine` and applies to later uploads that match it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockImageDto {
+    pub action: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageBlocklistEntryDto {
+    pub id: String,
+    pub hash: Option<String>,
+    /// 16 hex digits.
+    pub perceptual_hash: Option<String>,
+    pub action: String,
+    pub reason: String,
+    pub created_at: OffsetDateTime,
+}
+
+impl From<ImageBlocklistEntry> for ImageBlocklistEntryDto {
+    fn from(entry: ImageBlocklistEntry) -> Self {
+        Self {
+            id: entry.id.as_ref().to_string(),
+            hash: entry.hash.map(|hash| hash.as_ref().to_string()),
+            perceptual_hash: entry.perceptual_hash.map(|hash| hash.to_hex()),
+            action: entry.action.as_str().to_string(),
+            reason: entry.reason,
+            created_at: entry.created_at,
+        }
+    }
+}
+
+/// Focal point in Mastodon's `focalPoint` coordinates, each in `-1..=1`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImageFocusDto {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl From<&ImageFocus> for ImageFocusDto {
+    fn from(focus: &ImageFocus) -> Self {
+        Self {
+            x: *focus.x(),
+            y: *focus.y(),
+        }
+    }
+}
+
+impl From<ImageFocusDto> for ImageFocus {
+    fn from(dto: ImageFocusDto) -> Self {
+        ImageFocus::new(dto.x, dto.y)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageVariantDto {
+    pub kind: String,
+    pub url: String,
+    pub media_type: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl From<&ImageVariant> for ImageVariantDto {
+    fn from(variant: &ImageVariant) -> Self {
+        Self {
+            kind: variant.kind().as_str().to_string(),
+            url: variant.url().as_ref().to_string(),
+            media_type: variant.media_type().clone(),
+            width: *variant.width(),
+            height: *variant.height(),
+        }
+    }
+}
+
+/// The image to show in one slot (icon, header): the matching rendition
+/// when one exists, otherwise the original without a known size.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageRenditionDto {
+    pub url: String,
+    pub media_type: Option<String>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    /// Still rendition for clients that do not autoplay; only set for
+    /// animated images, which are served as their animated original.
+    pub static_url: Option<String>,
+    pub description: Option<String>,
+    pub focus: Option<ImageFocusDto>,
+}
+
+impl ImageRenditionDto {
+    pub fn of(image: &Image, kind: ImageVariantKind) -> Self {
+        Self {
+            description: image
+                .description()
+                .as_ref()
+                .map(|description| description.as_ref().to_string()),
+            focus: image.focus().as_ref().map(ImageFocusDto::from),
+            ..Self::without_metadata(image, kind)
+        }
+    }
+
+    fn without_metadata(image: &Image, kind: ImageVariantKind) -> Self {
+        if *image.animated() {
+            let still = image
+                .variant(kind)
+                .or_else(|| image.variant(ImageVariantKind::Static));
+            return Self {
+                url: image.url().as_ref().to_string(),
+                media_type: Some(ANIMATED_MEDIA_TYPE.to_string()),
+                width: None,
+                height: None,
+                static_url: still.map(|variant| variant.url().as_ref().to_string()),
+                description: None,
+                focus: None,
+            };
+        }
+        match image.variant(kind) {
+            Some(variant) => Self {
+                url: variant.url().as_ref().to_string(),
+                media_type: Some(variant.media_type().clone()),
+                width: Some(*variant.width()),
+                height: Some(*variant.height()),
+                static_url: None,
+                description: None,
+                focus: None,
+            },
+            None => Self {
+                url: image.url().as_ref().to_string(),
+                media_type: None,
+                width: None,
+                height: None,
+                static_url: None,
+                description: None,
+                focus: None,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kernel::prelude::entity::ImageUrl;
+    use kernel::test_utils::ImageBuilder;
+
+    fn variant(kind: ImageVariantKind) -> ImageVariant {
+        ImageVariant::new(
+            kind,
+            ImageUrl::new(format!("https://media.example/1.{}.webp", kind.as_str())),
+            400,
+            400,
+            "image/webp".to_string(),
+        )
+    }
+
+    #[test]
+    fn animated_images_are_served_whole_with_a_still_fallback() {
+        let image = ImageBuilder::new()
+            .animated(true)
+            .variant(variant(ImageVariantKind::Avatar))
+            .variant(variant(ImageVariantKind::Static))
+            .build();
+
+        let avatar = ImageRenditionDto::of(&image, ImageVariantKind::Avatar);
+        assert_eq!(avatar.url, image.url().as_ref().as_str());
+        assert_eq!(avatar.media_type.as_deref(), Some("image/webp"));
+        assert_eq!(avatar.width, None);
+        assert_eq!(
+            avatar.static_url.as_deref(),
+            Some("https://media.example/1.avatar.webp")
+        );
+
+        let header = ImageRenditionDto::of(&image, ImageVariantKind::Header);
+        assert_eq!(
+            header.static_url.as_deref(),
+            Some("https://media.example/1.static.webp")
+        );
+    }
+
+    #[test]
+    fn still_images_use_the_rendition_without_a_static_url() {
+        let image = ImageBuilder::new()
+            .variant(variant(ImageVariantKind::Avatar))
+            .build();
+
+        let avatar = ImageRenditionDto::of(&image, ImageVariantKind::Avatar);
+        assert_eq!(avatar.url, "https://media.example/1.avatar.webp");
+        assert_eq!(avatar.width, Some(400));
+        assert_eq!(avatar.static_url, None);
+    }
 }
@@ -0,0 +1,37 @@
+use kernel::interfaces::permission::Scope;
+use kernel::prelude::entity::AccountId;
+use time::OffsetDateTime;
+
+pub struct CreatePersonalAccessTokenDto {
+    pub name: String,
+    /// Emumet scope names such as `accounts:read`.
+    pub scopes: Vec<String>,
+    /// Account nanoids the token is limited to; `None` allows every account
+    /// of its owner.
+    pub account_ids: Option<Vec<String>>,
+    pub expires_at: Option<OffsetDateTime>,
+}
+
+pub struct PersonalAccessTokenDto {
+    pub id: String,
+    pub name: String,
+    /// Only returned by the create use case; listings never echo the token.
+    pub token: Option<String>,
+    pub scopes: Vec<String>,
+    /// Account nanoids, `None` when the token is not limited to accounts.
+    pub account_ids: Option<Vec<String>>,
+    pub expires_at: Option<OffsetDateTime>,
+    pub last_used_at: Option<OffsetDateTime>,
+    pub created_at: OffsetDateTime,
+}
+
+/// Who a valid personal access token acts as: the issuer and subject of
+/// its owner's auth account, plus what the token is limited to.
+pub struct PersonalAccessTokenGrantDto {
+    pub token_id: String,
+    pub issuer: String,
+    pub subject: String,
+    pub scopes: Vec<Scope>,
+    pub account_ids: Option<Vec<AccountId>>,
+    pub expires_at: Option<OffsetDateTime>,
+}
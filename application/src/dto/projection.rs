@@ -0,0 +1,80 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use time::OffsetDateTime;
+
+pub struct ProjectorHealthDto {
+    pub name: String,
+    pub checkpoint: i64,
+    /// Highest committed `seq` of the projector's event log.
+    pub head_seq: i64,
+    /// `head_seq - checkpoint`, never negative.
+    pub lag: i64,
+    pub healthy: bool,
+    pub last_success_at: Option<OffsetDateTime>,
+    pub last_error: Option<String>,
+    pub last_error_at: Option<OffsetDateTime>,
+}
+
+/// Read-your-writes token: the event log heads observed after a write.
+///
+/// Serialized as `<account>.<profile>.<metadata>` so it fits in a header.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProjectionToken {
+    pub account_seq: i64,
+    pub profile_seq: i64,
+    pub metadata_seq: i64,
+}
+
+impl Display for ProjectionToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}",
+            self.account_seq, self.profile_seq, self.metadata_seq
+        )
+    }
+}
+
+impl FromStr for ProjectionToken {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid projection token: {s}");
+        let seqs = s
+            .split('.')
+            .map(|part| part.parse::<i64>().ok().filter(|seq| *seq >= 0))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(invalid)?;
+        let [account_seq, profile_seq, metadata_seq] = seqs[..] else {
+            return Err(invalid());
+        };
+        Ok(Self {
+            account_seq,
+            profile_seq,
+            metadata_seq,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProjectionToken;
+
+    #[test]
+    fn projection_token_round_trips() {
+        let token = ProjectionToken {
+            account_seq: 12,
+            profile_seq: 0,
+            metadata_seq: 345,
+        };
+        assert_eq!(token.to_string(), "12.0.345");
+        assert_eq!("12.0.345".parse::<ProjectionToken>(), Ok(token));
+    }
+
+    #[test]
+    fn malformed_projection_tokens_are_rejected() {
+        for raw in ["", "1.2", "1.2.3.4", "1.x.3", "1.-2.3"] {
+            assert!(raw.parse::<ProjectionToken>().is_err(), "{raw}");
+        }
+    }
+}
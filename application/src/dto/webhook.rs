@@ -0,0 +1,68 @@
+use kernel::prelude::entity::{WebhookDelivery, WebhookSubscription};
+use time::OffsetDateTime;
+
+pub struct CreateWebhookSubscriptionDto {
+    pub url: String,
+    pub event_types: Vec<String>,
+    /// Generated when absent.
+    pub secret: Option<String>,
+}
+
+pub struct WebhookSubscriptionDto {
+    pub id: String,
+    pub url: String,
+    pub event_types: Vec<String>,
+    /// Only returned by the create use case; listings never echo the secret.
+    pub secret: Option<String>,
+    pub created_at: OffsetDateTime,
+}
+
+impl WebhookSubscriptionDto {
+    pub(crate) fn without_secret(subscription: WebhookSubscription) -> Self {
+        Self {
+            id: subscription.id.as_ref().to_string(),
+            url: subscription.url,
+            event_types: subscription.event_types,
+            secret: None,
+            created_at: subscription.created_at,
+        }
+    }
+
+    pub(crate) fn with_secret(subscription: WebhookSubscription) -> Self {
+        let secret = subscription.secret.clone();
+        Self {
+            secret: Some(secret),
+            ..Self::without_secret(subscription)
+        }
+    }
+}
+
+pub struct WebhookDeliveryDto {
+    pub id: String,
+    pub event_id: String,
+    pub event_type: String,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: OffsetDateTime,
+    pub last_status_code: Option<i32>,
+    pub last_error: Option<String>,
+    pub created_at: OffsetDateTime,
+    pub delivered_at: Option<OffsetDateTime>,
+}
+
+impl From<WebhookDelivery> for WebhookDeliveryDto {
+    fn from(delivery: WebhookDelivery) -> Self {
+        Self {
+            id: delivery.id.as_ref().to_string(),
+            event_id: delivery.event_id,
+            event_type: delivery.event_type,
+            status: delivery.status.as_str().to_string(),
+            attempts: delivery.attempts,
+            next_attempt_at: delivery.next_attempt_at,
+            last_status_code: delivery.last_status_code,
+            last_error: delivery.last_error,
+            created_at: delivery.created_at,
+            delivered_at: delivery.delivered_at,
+        }
+    }
+}
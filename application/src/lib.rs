@@ -1,5 +1,10 @@
+pub mod change_feed;
 pub mod dto;
 pub mod permission;
 pub mod projection;
 pub mod service;
 pub mod signing_key;
+pub mod telemetry;
+#[cfg(test)]
+mod test_utils;
+pub mod webhook;
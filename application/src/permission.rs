@@ -1,9 +1,108 @@
 use error_stack::Report;
 use kernel::interfaces::permission::{
-    DependOnPermissionChecker, Permission, PermissionChecker, PermissionReq,
+    DependOnPermissionChecker, InstancePermission, Permission, PermissionChecker, PermissionReq,
+    Scope,
 };
 use kernel::prelude::entity::{AccountId, AuthAccountId};
 use kernel::KernelError;
+use std::future::Future;
+
+#[derive(Clone)]
+enum ScopeGrant {
+    Unrestricted,
+    Token(Vec<Scope>),
+}
+
+tokio::task_local! {
+    static GRANTED_SCOPES: ScopeGrant;
+    static TOKEN_ACCOUNTS: Option<Vec<AccountId>>;
+}
+
+/// Run `future` on behalf of an OAuth2 access token that carries `scopes`.
+pub async fn with_granted_scopes<F: Future>(scopes: Vec<Scope>, future: F) -> F::Output {
+    GRANTED_SCOPES
+        .scope(ScopeGrant::Token(scopes), future)
+        .await
+}
+
+/// Run `future` in a trusted context that acts on behalf of no access token
+/// (workers, the inbox), where no scope is required. Outside of this and
+/// [`with_granted_scopes`] no scope is granted.
+pub async fn without_scope_restriction<F: Future>(future: F) -> F::Output {
+    GRANTED_SCOPES.scope(ScopeGrant::Unrestricted, future).await
+}
+
+/// Scopes of the access token the current request was made with, or `None`
+/// in a trusted context.
+pub fn granted_scopes() -> Option<Vec<Scope>> {
+    match GRANTED_SCOPES.try_with(Clone::clone) {
+        Ok(ScopeGrant::Unrestricted) => None,
+        Ok(ScopeGrant::Token(scopes)) => Some(scopes),
+        Err(_) => Some(Vec::new()),
+    }
+}
+
+/// Run `future` on behalf of a personal access token. `account_ids`, when
+/// set, limits its account permissions to those accounts.
+pub async fn with_personal_access_token<F: Future>(
+    account_ids: Option<Vec<AccountId>>,
+    future: F,
+) -> F::Output {
+    TOKEN_ACCOUNTS.scope(account_ids, future).await
+}
+
+pub fn is_personal_access_token() -> bool {
+    TOKEN_ACCOUNTS.try_with(|_| ()).is_ok()
+}
+
+/// Whether the current token may act on `account_id`.
+pub fn token_allows_account(account_id: &AccountId) -> bool {
+    TOKEN_ACCOUNTS
+        .try_with(|accounts| {
+            accounts
+                .as_ref()
+                .is_none_or(|accounts| accounts.contains(account_id))
+        })
+        .unwrap_or(true)
+}
+
+/// Whether the current token is limited to certain accounts.
+pub fn is_account_restricted_token() -> bool {
+    TOKEN_ACCOUNTS.try_with(Option::is_some).unwrap_or(false)
+}
+
+fn ensure_token_accounts(permission: &Permission) -> error_stack::Result<(), KernelError> {
+    let outside = permission.requirements().iter().find_map(|req| match req {
+        PermissionReq::Account { account_id, .. } if !token_allows_account(account_id) => {
+            Some(account_id)
+        }
+        _ => None,
+    });
+    match outside {
+        Some(account_id) => Err(Report::new(KernelError::PermissionDenied).attach_printable(
+            format!(
+                "Access token is not valid for account {}",
+                account_id.as_ref()
+            ),
+        )),
+        None => Ok(()),
+    }
+}
+
+fn ensure_scopes(permission: &Permission) -> error_stack::Result<(), KernelError> {
+    let missing = granted_scopes().and_then(|granted| {
+        permission
+            .requirements()
+            .iter()
+            .map(PermissionReq::required_scope)
+            .find(|scope| !granted.contains(scope))
+    });
+    match missing {
+        Some(scope) => Err(Report::new(KernelError::PermissionDenied)
+            .attach_printable(format!("Access token lacks the {} scope", scope.as_str()))),
+        None => Ok(()),
+    }
+}
 
 pub fn account_view(account_id: &AccountId) -> Permission {
     Permission::new(PermissionReq::account(account_id.clone(), "view"))
@@ -17,16 +116,43 @@ pub fn account_deactivate(account_id: &AccountId) -> Permission {
     Permission::new(PermissionReq::account(account_id.clone(), "deactivate"))
 }
 
+pub fn account_share(account_id: &AccountId) -> Permission {
+    Permission::new(PermissionReq::account(account_id.clone(), "share"))
+}
+
 pub fn account_sign(account_id: &AccountId) -> Permission {
     Permission::new(PermissionReq::account(account_id.clone(), "sign"))
 }
 
 pub fn instance_moderate() -> Permission {
-    Permission::new(PermissionReq::instance("moderate"))
+    Permission::new(PermissionReq::instance(InstancePermission::Moderate))
 }
 
 pub fn instance_administrate() -> Permission {
-    Permission::new(PermissionReq::instance("administrate"))
+    Permission::new(PermissionReq::instance(InstancePermission::Administrate))
+}
+
+pub fn instance_manage_media() -> Permission {
+    Permission::new(PermissionReq::instance(InstancePermission::ManageMedia))
+}
+
+pub fn instance_view_audit_log() -> Permission {
+    Permission::new(PermissionReq::instance(InstancePermission::ViewAuditLog))
+}
+
+pub fn instance_manage_roles() -> Permission {
+    Permission::new(PermissionReq::instance(InstancePermission::ManageRoles))
+}
+
+/// Holding every one of `permissions`.
+pub fn instance_permissions(permissions: &[InstancePermission]) -> Permission {
+    Permission::all(
+        permissions
+            .iter()
+            .copied()
+            .map(PermissionReq::instance)
+            .collect(),
+    )
 }
 
 pub async fn check_permission<T: DependOnPermissionChecker + ?Sized>(
@@ -34,6 +160,8 @@ pub async fn check_permission<T: DependOnPermissionChecker + ?Sized>(
     subject: &AuthAccountId,
     permission: &Permission,
 ) -> error_stack::Result<(), KernelError> {
+    ensure_scopes(permission)?;
+    ensure_token_accounts(permission)?;
     if !deps
         .permission_checker()
         .satisfies(subject, permission)
@@ -45,3 +173,198 @@ pub async fn check_permission<T: DependOnPermissionChecker + ?Sized>(
     }
     Ok(())
 }
+
+/// Which of `permissions` `subject` holds, answered with a single batch
+/// check instead of one check per permission.
+pub async fn check_permissions<T: DependOnPermissionChecker + ?Sized>(
+    deps: &T,
+    subject: &AuthAccountId,
+    permissions: &[Permission],
+) -> error_stack::Result<Vec<bool>, KernelError> {
+    let usable: Vec<bool> = permissions
+        .iter()
+        .map(|permission| {
+            ensure_scopes(permission).is_ok() && ensure_token_accounts(permission).is_ok()
+        })
+        .collect();
+    let reqs: Vec<PermissionReq> = permissions
+        .iter()
+        .zip(&usable)
+        .filter(|(_, usable)| **usable)
+        .flat_map(|(permission, _)| permission.requirements().iter().cloned())
+        .collect();
+    let mut answers = deps
+        .permission_checker()
+        .check_batch(subject, &reqs)
+        .await?
+        .into_iter();
+    Ok(permissions
+        .iter()
+        .zip(usable)
+        .map(|(permission, usable)| {
+            usable
+                && answers
+                    .by_ref()
+                    .take(permission.requirements().len())
+                    .filter(|allowed| !allowed)
+                    .count()
+                    == 0
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kernel::interfaces::permission::{AccountMember, InstanceRole, InstanceRoleHolder};
+    use kernel::prelude::entity::CustomRoleId;
+    use std::sync::Mutex;
+
+    /// Allows viewing account 1 only and records every batch it answers.
+    #[derive(Default)]
+    struct ViewOneChecker {
+        batches: Mutex<Vec<usize>>,
+    }
+
+    impl PermissionChecker for ViewOneChecker {
+        async fn check(
+            &self,
+            _subject: &AuthAccountId,
+            req: &PermissionReq,
+        ) -> error_stack::Result<bool, KernelError> {
+            Ok(req.object_id() == "1" && req.permission_name() == "view")
+        }
+
+        async fn check_batch(
+            &self,
+            subject: &AuthAccountId,
+            reqs: &[PermissionReq],
+        ) -> error_stack::Result<Vec<bool>, KernelError> {
+            self.batches.lock().unwrap().push(reqs.len());
+            let mut allowed = Vec::new();
+            for req in reqs {
+                allowed.push(self.check(subject, req).await?);
+            }
+            Ok(allowed)
+        }
+
+        async fn list_instance_roles(
+            &self,
+            _subject: &AuthAccountId,
+        ) -> error_stack::Result<Vec<InstanceRole>, KernelError> {
+            Ok(Vec::new())
+        }
+
+        async fn list_account_members(
+            &self,
+            _account_id: &AccountId,
+        ) -> error_stack::Result<Vec<AccountMember>, KernelError> {
+            Ok(Vec::new())
+        }
+
+        async fn list_instance_role_holders(
+            &self,
+        ) -> error_stack::Result<Vec<InstanceRoleHolder>, KernelError> {
+            Ok(Vec::new())
+        }
+
+        async fn list_custom_role_members(
+            &self,
+            _role_id: &CustomRoleId,
+        ) -> error_stack::Result<Vec<AuthAccountId>, KernelError> {
+            Ok(Vec::new())
+        }
+    }
+
+    impl DependOnPermissionChecker for ViewOneChecker {
+        type PermissionChecker = Self;
+
+        fn permission_checker(&self) -> &Self::PermissionChecker {
+            self
+        }
+    }
+
+    #[tokio::test]
+    async fn scopes_are_required_outside_trusted_contexts() {
+        let account_id = AccountId::new(1);
+        let outside = ensure_scopes(&account_view(&account_id));
+        assert_eq!(
+            outside.unwrap_err().current_context(),
+            &KernelError::PermissionDenied
+        );
+        without_scope_restriction(async {
+            assert!(ensure_scopes(&account_sign(&account_id)).is_ok());
+        })
+        .await;
+
+        with_granted_scopes(vec![Scope::AccountsRead, Scope::AccountsWrite], async {
+            assert!(ensure_scopes(&account_view(&account_id)).is_ok());
+            assert!(ensure_scopes(&account_edit(&account_id)).is_ok());
+            let denied = ensure_scopes(&(account_view(&account_id) + account_sign(&account_id)));
+            assert_eq!(
+                denied.unwrap_err().current_context(),
+                &KernelError::PermissionDenied
+            );
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn personal_access_tokens_are_limited_to_their_accounts() {
+        let allowed = AccountId::new(1);
+        let other = AccountId::new(2);
+        assert!(!is_personal_access_token());
+        assert!(ensure_token_accounts(&account_edit(&other)).is_ok());
+
+        with_personal_access_token(Some(vec![allowed.clone()]), async {
+            assert!(is_personal_access_token());
+            assert!(is_account_restricted_token());
+            assert!(ensure_token_accounts(&account_edit(&allowed)).is_ok());
+            assert!(ensure_token_accounts(&instance_moderate()).is_ok());
+            let denied = ensure_token_accounts(&(account_view(&allowed) + account_view(&other)));
+            assert_eq!(
+                denied.unwrap_err().current_context(),
+                &KernelError::PermissionDenied
+            );
+        })
+        .await;
+
+        with_personal_access_token(None, async {
+            assert!(is_personal_access_token());
+            assert!(!is_account_restricted_token());
+            assert!(ensure_token_accounts(&account_edit(&other)).is_ok());
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn permissions_are_checked_in_one_batch() {
+        without_scope_restriction(async {
+            let checker = ViewOneChecker::default();
+            let subject = AuthAccountId::new(1);
+            let one = AccountId::new(1);
+            let two = AccountId::new(2);
+            let permissions = [
+                account_view(&one),
+                account_view(&two),
+                account_view(&one) + account_edit(&one),
+                account_view(&one),
+            ];
+
+            let allowed = check_permissions(&checker, &subject, &permissions)
+                .await
+                .unwrap();
+            let token_allowed = with_personal_access_token(
+                Some(vec![two.clone()]),
+                check_permissions(&checker, &subject, &permissions),
+            )
+            .await
+            .unwrap();
+
+            assert_eq!(allowed, [true, false, false, true]);
+            assert_eq!(token_allowed, [false, false, false, false]);
+            assert_eq!(*checker.batches.lock().unwrap(), [5, 1]);
+        })
+        .await;
+    }
+}
@@ -0,0 +1,160 @@
+use crate::dto::projection::{ProjectionToken, ProjectorHealthDto};
+use crate::projection::{ACCOUNT_PROJECTOR_NAME, METADATA_PROJECTOR_NAME, PROFILE_PROJECTOR_NAME};
+use kernel::interfaces::database::{DatabaseConnection, DependOnDatabaseConnection};
+use kernel::interfaces::projection::{
+    AccountEventLog, DependOnAccountEventLog, DependOnMetadataEventLog, DependOnProfileEventLog,
+    DependOnProjectionCheckpointStore, DependOnProjectionRunRegistry, MetadataEventLog,
+    ProfileEventLog, ProjectionCheckpointStore,
+};
+use kernel::KernelError;
+use std::future::Future;
+use std::time::Duration;
+
+/// How often `wait_for_projection` re-reads the checkpoints.
+pub const PROJECTION_WAIT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Per-projector lag (checkpoint versus log head) merged with the in-process
+/// poll outcomes recorded by the projection worker.
+pub trait GetProjectionHealth:
+    DependOnDatabaseConnection
+    + DependOnAccountEventLog
+    + DependOnProfileEventLog
+    + DependOnMetadataEventLog
+    + DependOnProjectionCheckpointStore
+    + DependOnProjectionRunRegistry
+{
+    fn projection_health(
+        &self,
+    ) -> impl Future<Output = error_stack::Result<Vec<ProjectorHealthDto>, KernelError>> + Send
+    {
+        async move {
+            let mut executor = self.database_connection().connection().await?;
+            let heads = [
+                (
+                    ACCOUNT_PROJECTOR_NAME,
+                    self.account_event_log().head_seq(&mut executor).await?,
+                ),
+                (
+                    PROFILE_PROJECTOR_NAME,
+                    self.profile_event_log().head_seq(&mut executor).await?,
+                ),
+                (
+                    METADATA_PROJECTOR_NAME,
+                    self.metadata_event_log().head_seq(&mut executor).await?,
+                ),
+            ];
+            let mut projectors = Vec::with_capacity(heads.len());
+            for (name, head_seq) in heads {
+                let checkpoint = self
+                    .projection_checkpoint_store()
+                    .get(&mut executor, name)
+                    .await?
+                    .unwrap_or(0);
+                let status = self.projection_run_registry().status(name);
+                projectors.push(ProjectorHealthDto {
+                    name: name.to_string(),
+                    checkpoint,
+                    head_seq,
+                    lag: (head_seq - checkpoint).max(0),
+                    healthy: status.is_healthy(),
+                    last_success_at: status.last_success_at,
+                    last_error: status.last_error,
+                    last_error_at: status.last_error_at,
+                });
+            }
+            Ok(projectors)
+        }
+    }
+}
+
+impl<T> GetProjectionHealth for T where
+    T: DependOnDatabaseConnection
+        + DependOnAccountEventLog
+        + DependOnProfileEventLog
+        + DependOnMetadataEventLog
+        + DependOnProjectionCheckpointStore
+        + DependOnProjectionRunRegistry
+{
+}
+
+/// Read-your-writes support for callers of write endpoints.
+///
+/// `projection_token` captures the event log heads after a write has
+/// committed; `wait_for_projection` then waits until every projector
+/// checkpoint has reached them. An event committed out of `seq` order is
+/// applied by the window re-read of the following poll, so waiting can return
+/// up to one poll interval before such a straggler is visible.
+pub trait WaitForProjection:
+    DependOnDatabaseConnection
+    + DependOnAccountEventLog
+    + DependOnProfileEventLog
+    + DependOnMetadataEventLog
+    + DependOnProjectionCheckpointStore
+{
+    fn projection_token(
+        &self,
+    ) -> impl Future<Output = error_stack::Result<ProjectionToken, KernelError>> + Send {
+        async move {
+            let mut executor = self.database_connection().connection().await?;
+            Ok(ProjectionToken {
+                account_seq: self.account_event_log().head_seq(&mut executor).await?,
+                profile_seq: self.profile_event_log().head_seq(&mut executor).await?,
+                metadata_seq: self.metadata_event_log().head_seq(&mut executor).await?,
+            })
+        }
+    }
+
+    /// Returns `false` when `timeout` elapsed before the projections caught up.
+    fn wait_for_projection(
+        &self,
+        token: ProjectionToken,
+        timeout: Duration,
+    ) -> impl Future<Output = error_stack::Result<bool, KernelError>> + Send {
+        async move {
+            let deadline = tokio::time::Instant::now() + timeout;
+            loop {
+                if projection_reached(self, &token).await? {
+                    return Ok(true);
+                }
+                if tokio::time::Instant::now() + PROJECTION_WAIT_POLL_INTERVAL > deadline {
+                    return Ok(false);
+                }
+                tokio::time::sleep(PROJECTION_WAIT_POLL_INTERVAL).await;
+            }
+        }
+    }
+}
+
+impl<T> WaitForProjection for T where
+    T: DependOnDatabaseConnection
+        + DependOnAccountEventLog
+        + DependOnProfileEventLog
+        + DependOnMetadataEventLog
+        + DependOnProjectionCheckpointStore
+{
+}
+
+async fn projection_reached<T>(
+    deps: &T,
+    token: &ProjectionToken,
+) -> error_stack::Result<bool, KernelError>
+where
+    T: DependOnDatabaseConnection + DependOnProjectionCheckpointStore + ?Sized,
+{
+    let mut executor = deps.database_connection().connection().await?;
+    for (name, target) in [
+        (ACCOUNT_PROJECTOR_NAME, token.account_seq),
+        (PROFILE_PROJECTOR_NAME, token.profile_seq),
+        (METADATA_PROJECTOR_NAME, token.metadata_seq),
+    ] {
+        let checkpoint = deps
+            .projection_checkpoint_store()
+            .get(&mut executor, name)
+            .await?
+            .unwrap_or(0);
+        if checkpoint < target {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
@@ -1,4 +1,5 @@
 mod account_projector;
+mod health;
 mod metadata_projector;
 mod profile_projector;
 
@@ -6,5 +7,6 @@ mod profile_projector;
 mod tests;
 
 pub use account_projector::*;
+pub use health::*;
 pub use metadata_projector::*;
 pub use profile_projector::*;
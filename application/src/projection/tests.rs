@@ -1,4 +1,7 @@
-use super::{ProjectAccountBatch, ProjectMetadataBatch, ProjectProfileBatch};
+use super::{
+    GetProjectionHealth, ProjectAccountBatch, ProjectMetadataBatch, ProjectProfileBatch,
+    WaitForProjection, ACCOUNT_PROJECTOR_NAME,
+};
 use driver::database::PostgresDatabase;
 use kernel::impl_database_delegation;
 use kernel::interfaces::database::{
@@ -7,7 +10,10 @@ use kernel::interfaces::database::{
 use kernel::interfaces::event::EventApplier;
 use kernel::interfaces::event_store::{AccountEventStore, DependOnAccountEventStore};
 use kernel::interfaces::permission::{DependOnPermissionWriter, PermissionWriter, RelationTarget};
-use kernel::interfaces::projection::{AccountProjectionWriter, DependOnAccountProjectionWriter};
+use kernel::interfaces::projection::{
+    AccountProjectionWriter, DependOnAccountProjectionWriter, DependOnProjectionRunRegistry,
+    ProjectionRunRegistry,
+};
 use kernel::interfaces::read_model::{AccountReadModel, DependOnAccountReadModel};
 use kernel::prelude::entity::{
     Account, AccountEvent, AccountId, AccountIsBot, AccountName, AuthAccountId, EventEnvelope,
@@ -16,6 +22,9 @@ use kernel::prelude::entity::{
 use kernel::KernelError;
 use serde_json::json;
 use sqlx::PgConnection;
+use std::sync::LazyLock;
+use std::time::Duration;
+use time::OffsetDateTime;
 use tokio::sync::Mutex;
 
 /// Projector tests share the `projection_checkpoints` row and the tailing
@@ -40,6 +49,20 @@ impl PermissionWriter for NoopPermissionWriter {
     ) -> error_stack::Result<(), KernelError> {
         Ok(())
     }
+
+    async fn create_role_grant(
+        &self,
+        _grant: &kernel::interfaces::permission::RoleGrant,
+    ) -> error_stack::Result<(), KernelError> {
+        Ok(())
+    }
+
+    async fn delete_role_grant(
+        &self,
+        _grant: &kernel::interfaces::permission::RoleGrant,
+    ) -> error_stack::Result<(), KernelError> {
+        Ok(())
+    }
 }
 
 struct ProjectorTest {
@@ -56,6 +79,15 @@ impl DependOnPermissionWriter for ProjectorTest {
     }
 }
 
+static PROJECTION_RUN_REGISTRY: LazyLock<ProjectionRunRegistry> =
+    LazyLock::new(ProjectionRunRegistry::default);
+
+impl DependOnProjectionRunRegistry for ProjectorTest {
+    fn projection_run_registry(&self) -> &ProjectionRunRegistry {
+        &PROJECTION_RUN_REGISTRY
+    }
+}
+
 fn fold(envelopes: Vec<EventEnvelope<AccountEvent, Account>>) -> Account {
     let mut entity = None;
     for envelope in envelopes {
@@ -525,6 +557,62 @@ async fn checkpoint_advances_as_events_are_appended() {
     );
 }
 
+#[test_with::env(DATABASE_URL)]
+#[tokio::test]
+async fn projection_health_and_wait_track_checkpoints_against_log_heads() {
+    let _projector_test_guard = PROJECTOR_TEST_LOCK.lock().await;
+    kernel::ensure_generator_initialized();
+    let projector = ProjectorTest {
+        db: PostgresDatabase::new().await.unwrap(),
+    };
+    let account_id = AccountId::default();
+    let auth_account_id = AuthAccountId::default();
+    seed_auth_account(&projector.db, &auth_account_id).await;
+    persist_account_events(&projector.db, &account_id, &auth_account_id, true).await;
+
+    let token = projector.projection_token().await.unwrap();
+    assert!(token.account_seq >= max_seq_for(&projector.db, &account_id).await);
+    PROJECTION_RUN_REGISTRY.record_failure(
+        ACCOUNT_PROJECTOR_NAME,
+        "boom".to_string(),
+        OffsetDateTime::now_utc(),
+    );
+    let health = projector.projection_health().await.unwrap();
+    let account = health
+        .iter()
+        .find(|projector| projector.name == ACCOUNT_PROJECTOR_NAME)
+        .unwrap();
+    assert!(account.head_seq >= token.account_seq);
+    assert_eq!(account.lag, account.head_seq - account.checkpoint);
+    assert!(account.lag > 0, "the new events are not projected yet");
+    assert!(!account.healthy);
+    assert_eq!(account.last_error.as_deref(), Some("boom"));
+    assert!(!projector
+        .wait_for_projection(token, Duration::from_millis(100))
+        .await
+        .unwrap());
+
+    for _ in 0..1000 {
+        if projector.project_batch().await.unwrap() >= token.account_seq {
+            break;
+        }
+    }
+    for _ in 0..1000 {
+        if projector.project_profile_batch().await.unwrap() >= token.profile_seq {
+            break;
+        }
+    }
+    for _ in 0..1000 {
+        if projector.project_metadata_batch().await.unwrap() >= token.metadata_seq {
+            break;
+        }
+    }
+    assert!(projector
+        .wait_for_projection(token, Duration::from_secs(1))
+        .await
+        .unwrap());
+}
+
 mod profile {
     use super::{clear_checkpoint, ProjectAccountBatch, ProjectProfileBatch};
     use driver::database::PostgresDatabase;
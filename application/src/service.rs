@@ -3,9 +3,12 @@ pub mod account_detail;
 pub mod activitypub;
 pub mod auth_account;
 pub mod block;
+pub mod image_moderation;
 pub mod media;
 pub mod mute;
+pub mod personal_access_token;
 pub mod session_context;
+pub mod webhook;
 
 #[cfg(test)]
 mod characterization_tests;
@@ -1,4 +1,5 @@
 use crate::dto::account::{AccountDto, CreateAccountDto};
+use crate::permission::is_account_restricted_token;
 use crate::signing_key::CreateSigningKeyUseCase;
 use error_stack::Report;
 use kernel::interfaces::config::DependOnPublicBaseUrl;
@@ -46,6 +47,10 @@ pub trait CreateAccountUseCase:
         dto: CreateAccountDto,
     ) -> impl Future<Output = error_stack::Result<AccountDto, KernelError>> + Send + '_ {
         async move {
+            if is_account_restricted_token() {
+                return Err(Report::new(KernelError::PermissionDenied)
+                    .attach_printable("Access token is limited to existing accounts"));
+            }
             let account_name = AccountName::new(dto.name);
             let account_is_bot = AccountIsBot::new(dto.is_bot);
             let display_name = ProfileDisplayName::new(account_name.as_ref().to_string());
@@ -0,0 +1,845 @@
+use super::instance_role::target_auth_account_id;
+use crate::dto::custom_role::{CreateCustomRoleDto, CustomRoleDto, UpdateCustomRoleDto};
+use crate::permission::{check_permission, instance_manage_roles, instance_permissions};
+use error_stack::Report;
+use kernel::interfaces::database::DatabaseConnection;
+use kernel::interfaces::permission::{
+    DependOnPermissionChecker, DependOnPermissionWriter, InstancePermission, PermissionChecker,
+    PermissionWriter, RelationTarget, RoleGrant,
+};
+use kernel::interfaces::read_model::DependOnAccountQuery;
+use kernel::interfaces::repository::{CustomRoleRepository, DependOnCustomRoleRepository};
+use kernel::prelude::entity::{AuthAccountId, CustomRole, CustomRoleId};
+use kernel::KernelError;
+use std::future::Future;
+use time::OffsetDateTime;
+
+fn parse_role_id(id: &str) -> error_stack::Result<CustomRoleId, KernelError> {
+    id.parse::<i64>().map(CustomRoleId::new).map_err(|_| {
+        Report::new(KernelError::Validation).attach_printable(format!("Invalid role ID: {id}"))
+    })
+}
+
+/// Validated, deduplicated and in declaration order.
+fn normalize_permissions(
+    permissions: Vec<InstancePermission>,
+) -> error_stack::Result<Vec<InstancePermission>, KernelError> {
+    CustomRole::validate_permissions(&permissions)?;
+    Ok(InstancePermission::ALL
+        .into_iter()
+        .filter(|permission| permissions.contains(permission))
+        .collect())
+}
+
+fn normalize_name(name: String) -> error_stack::Result<String, KernelError> {
+    let name = name.trim().to_string();
+    CustomRole::validate_name(&name)?;
+    Ok(name)
+}
+
+async fn find_role<T>(deps: &T, id: &str) -> error_stack::Result<CustomRole, KernelError>
+where
+    T: DependOnCustomRoleRepository + ?Sized,
+{
+    let id = parse_role_id(id)?;
+    let mut executor = deps.database_connection().connection().await?;
+    deps.custom_role_repository()
+        .find_by_id(&mut executor, &id)
+        .await?
+        .ok_or_else(|| {
+            Report::new(KernelError::NotFound)
+                .attach_printable(format!("Role not found: {}", id.as_ref()))
+        })
+}
+
+async fn ensure_name_available<T>(deps: &T, name: &str) -> error_stack::Result<(), KernelError>
+where
+    T: DependOnCustomRoleRepository + ?Sized,
+{
+    let mut executor = deps.database_connection().connection().await?;
+    if deps
+        .custom_role_repository()
+        .find_by_name(&mut executor, name)
+        .await?
+        .is_some()
+    {
+        return Err(Report::new(KernelError::Rejected)
+            .attach_printable(format!("A role named {name} already exists")));
+    }
+    Ok(())
+}
+
+fn grants(role_id: &CustomRoleId, permissions: &[InstancePermission]) -> Vec<RoleGrant> {
+    permissions
+        .iter()
+        .map(|permission| RoleGrant {
+            role_id: role_id.clone(),
+            permission: *permission,
+        })
+        .collect()
+}
+
+pub trait CreateCustomRoleUseCase:
+    'static
+    + Sync
+    + Send
+    + DependOnCustomRoleRepository
+    + DependOnPermissionChecker
+    + DependOnPermissionWriter
+{
+    /// The caller must hold every permission the role bundles.
+    fn create_custom_role(
+        &self,
+        auth_account_id: &AuthAccountId,
+        dto: CreateCustomRoleDto,
+    ) -> impl Future<Output = error_stack::Result<CustomRoleDto, KernelError>> + Send {
+        async move {
+            check_permission(self, auth_account_id, &instance_manage_roles()).await?;
+            let name = normalize_name(dto.name)?;
+            let permissions = normalize_permissions(dto.permissions)?;
+            check_permission(self, auth_account_id, &instance_permissions(&permissions)).await?;
+            ensure_name_available(self, &name).await?;
+
+            let now = OffsetDateTime::now_utc();
+            let role = CustomRole {
+                id: CustomRoleId::default(),
+                name,
+                permissions,
+                created_at: now,
+                updated_at: now,
+            };
+            let mut executor = self.database_connection().connection().await?;
+            self.custom_role_repository()
+                .create(&mut executor, &role)
+                .await?;
+            for grant in grants(&role.id, &role.permissions) {
+                self.permission_writer().create_role_grant(&grant).await?;
+            }
+            Ok(CustomRoleDto::new(role, Vec::new()))
+        }
+    }
+}
+
+impl<T> CreateCustomRoleUseCase for T where
+    T: 'static
+        + Sync
+        + Send
+        + DependOnCustomRoleRepository
+        + DependOnPermissionChecker
+        + DependOnPermissionWriter
+{
+}
+
+pub trait GetCustomRolesUseCase:
+    'static + Sync + Send + DependOnCustomRoleRepository + DependOnPermissionChecker
+{
+    /// Every role with its members, by name.
+    fn get_custom_roles(
+        &self,
+        auth_account_id: &AuthAccountId,
+    ) -> impl Future<Output = error_stack::Result<Vec<CustomRoleDto>, KernelError>> + Send {
+        async move {
+            check_permission(self, auth_account_id, &instance_manage_roles()).await?;
+            let mut executor = self.database_connection().connection().await?;
+            let roles = self
+                .custom_role_repository()
+                .find_all(&mut executor)
+                .await?;
+            let mut result = Vec::with_capacity(roles.len());
+            for role in roles {
+                let members = self
+                    .permission_checker()
+                    .list_custom_role_members(&role.id)
+                    .await?;
+                result.push(CustomRoleDto::new(role, members));
+            }
+            Ok(result)
+        }
+    }
+}
+
+impl<T> GetCustomRolesUseCase for T where
+    T: 'static + Sync + Send + DependOnCustomRoleRepository + DependOnPermissionChecker
+{
+}
+
+pub trait UpdateCustomRoleUseCase:
+    'static
+    + Sync
+    + Send
+    + DependOnCustomRoleRepository
+    + DependOnPermissionChecker
+    + DependOnPermissionWriter
+{
+    /// The caller must hold every permission the update adds.
+    fn update_custom_role(
+        &self,
+        auth_account_id: &AuthAccountId,
+        role_id: String,
+        dto: UpdateCustomRoleDto,
+    ) -> impl Future<Output = error_stack::Result<CustomRoleDto, KernelError>> + Send {
+        async move {
+            check_permission(self, auth_account_id, &instance_manage_roles()).await?;
+            let current = find_role(self, &role_id).await?;
+
+            let name = dto.name.map(normalize_name).transpose()?;
+            if let Some(name) = name.as_ref().filter(|name| **name != current.name) {
+                ensure_name_available(self, name).await?;
+            }
+            let permissions = match dto.permissions {
+                Some(permissions) => normalize_permissions(permissions)?,
+                None => current.permissions.clone(),
+            };
+            let added: Vec<InstancePermission> = permissions
+                .iter()
+                .copied()
+                .filter(|permission| !current.permissions.contains(permission))
+                .collect();
+            let removed: Vec<InstancePermission> = current
+                .permissions
+                .iter()
+                .copied()
+                .filter(|permission| !permissions.contains(permission))
+                .collect();
+            check_permission(self, auth_account_id, &instance_permissions(&added)).await?;
+
+            let role = CustomRole {
+                name: name.unwrap_or_else(|| current.name.clone()),
+                permissions,
+                updated_at: OffsetDateTime::now_utc(),
+                ..current
+            };
+            let mut executor = self.database_connection().connection().await?;
+            self.custom_role_repository()
+                .update(&mut executor, &role)
+                .await?;
+            for grant in grants(&role.id, &added) {
+                self.permission_writer().create_role_grant(&grant).await?;
+            }
+            for grant in grants(&role.id, &removed) {
+                self.permission_writer().delete_role_grant(&grant).await?;
+            }
+            let members = self
+                .permission_checker()
+                .list_custom_role_members(&role.id)
+                .await?;
+            Ok(CustomRoleDto::new(role, members))
+        }
+    }
+}
+
+impl<T> UpdateCustomRoleUseCase for T where
+    T: 'static
+        + Sync
+        + Send
+        + DependOnCustomRoleRepository
+        + DependOnPermissionChecker
+        + DependOnPermissionWriter
+{
+}
+
+pub trait DeleteCustomRoleUseCase:
+    'static
+    + Sync
+    + Send
+    + DependOnCustomRoleRepository
+    + DependOnPermissionChecker
+    + DependOnPermissionWriter
+{
+    /// Revokes the role from its members before deleting it.
+    fn delete_custom_role(
+        &self,
+        auth_account_id: &AuthAccountId,
+        role_id: String,
+    ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send {
+        async move {
+            check_permission(self, auth_account_id, &instance_manage_roles()).await?;
+            let role = find_role(self, &role_id).await?;
+
+            for grant in grants(&role.id, &role.permissions) {
+                self.permission_writer().delete_role_grant(&grant).await?;
+            }
+            let membership = RelationTarget::CustomRole {
+                role_id: role.id.clone(),
+            };
+            let members = self
+                .permission_checker()
+                .list_custom_role_members(&role.id)
+                .await?;
+            for member in &members {
+                self.permission_writer()
+                    .delete_relation(&membership, member)
+                    .await?;
+            }
+            let mut executor = self.database_connection().connection().await?;
+            self.custom_role_repository()
+                .delete(&mut executor, &role.id)
+                .await?;
+            Ok(())
+        }
+    }
+}
+
+impl<T> DeleteCustomRoleUseCase for T where
+    T: 'static
+        + Sync
+        + Send
+        + DependOnCustomRoleRepository
+        + DependOnPermissionChecker
+        + DependOnPermissionWriter
+{
+}
+
+pub trait AssignCustomRoleUseCase:
+    'static
+    + Sync
+    + Send
+    + DependOnAccountQuery
+    + DependOnCustomRoleRepository
+    + DependOnPermissionChecker
+    + DependOnPermissionWriter
+{
+    /// The caller must hold every permission the role bundles.
+    fn assign_custom_role(
+        &self,
+        auth_account_id: &AuthAccountId,
+        account_id: String,
+        role_id: String,
+    ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send {
+        async move {
+            let role = find_role(self, &role_id).await?;
+            let target_auth_id = target_auth_account_id(
+                self,
+                auth_account_id,
+                account_id,
+                &(instance_manage_roles() + instance_permissions(&role.permissions)),
+            )
+            .await?;
+            self.permission_writer()
+                .create_relation(
+                    &RelationTarget::CustomRole { role_id: role.id },
+                    &target_auth_id,
+                )
+                .await
+        }
+    }
+}
+
+impl<T> AssignCustomRoleUseCase for T where
+    T: 'static
+        + Sync
+        + Send
+        + DependOnAccountQuery
+        + DependOnCustomRoleRepository
+        + DependOnPermissionChecker
+        + DependOnPermissionWriter
+{
+}
+
+pub trait RevokeCustomRoleUseCase:
+    'static
+    + Sync
+    + Send
+    + DependOnAccountQuery
+    + DependOnCustomRoleRepository
+    + DependOnPermissionChecker
+    + DependOnPermissionWriter
+{
+    /// The caller must hold every permission the role bundles.
+    fn revoke_custom_role(
+        &self,
+        auth_account_id: &AuthAccountId,
+        account_id: String,
+        role_id: String,
+    ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send {
+        async move {
+            let role = find_role(self, &role_id).await?;
+            let target_auth_id = target_auth_account_id(
+                self,
+                auth_account_id,
+                account_id,
+                &(instance_manage_roles() + instance_permissions(&role.permissions)),
+            )
+            .await?;
+            self.permission_writer()
+                .delete_relation(
+                    &RelationTarget::CustomRole { role_id: role.id },
+                    &target_auth_id,
+                )
+                .await
+        }
+    }
+}
+
+impl<T> RevokeCustomRoleUseCase for T where
+    T: 'static
+        + Sync
+        + Send
+        + DependOnAccountQuery
+        + DependOnCustomRoleRepository
+        + DependOnPermissionChecker
+        + DependOnPermissionWriter
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::permission::without_scope_restriction;
+    use kernel::interfaces::database::{
+        Connection, DatabaseConnection, DependOnDatabaseConnection,
+    };
+    use kernel::interfaces::permission::{
+        AccountMember, InstanceRole, InstanceRoleHolder, PermissionReq,
+    };
+    use kernel::prelude::entity::AccountId;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone)]
+    struct MockConnection;
+
+    impl Connection for MockConnection {}
+
+    #[derive(Clone)]
+    struct MockDatabaseConnection;
+
+    impl DatabaseConnection for MockDatabaseConnection {
+        type Connection = MockConnection;
+
+        async fn connection(&self) -> error_stack::Result<Self::Connection, KernelError> {
+            Ok(MockConnection)
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockCustomRoleRepository {
+        roles: Arc<Mutex<Vec<CustomRole>>>,
+    }
+
+    impl CustomRoleRepository for MockCustomRoleRepository {
+        type Connection = MockConnection;
+
+        async fn create(
+            &self,
+            _executor: &mut Self::Connection,
+            role: &CustomRole,
+        ) -> error_stack::Result<(), KernelError> {
+            self.roles.lock().unwrap().push(role.clone());
+            Ok(())
+        }
+
+        async fn find_by_id(
+            &self,
+            _executor: &mut Self::Connection,
+            id: &CustomRoleId,
+        ) -> error_stack::Result<Option<CustomRole>, KernelError> {
+            Ok(self
+                .roles
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|role| &role.id == id)
+                .cloned())
+        }
+
+        async fn find_by_name(
+            &self,
+            _executor: &mut Self::Connection,
+            name: &str,
+        ) -> error_stack::Result<Option<CustomRole>, KernelError> {
+            Ok(self
+                .roles
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|role| role.name == name)
+                .cloned())
+        }
+
+        async fn find_all(
+            &self,
+            _executor: &mut Self::Connection,
+        ) -> error_stack::Result<Vec<CustomRole>, KernelError> {
+            Ok(self.roles.lock().unwrap().clone())
+        }
+
+        async fn update(
+            &self,
+            _executor: &mut Self::Connection,
+            role: &CustomRole,
+        ) -> error_stack::Result<(), KernelError> {
+            for stored in self.roles.lock().unwrap().iter_mut() {
+                if stored.id == role.id {
+                    *stored = role.clone();
+                }
+            }
+            Ok(())
+        }
+
+        async fn delete(
+            &self,
+            _executor: &mut Self::Connection,
+            id: &CustomRoleId,
+        ) -> error_stack::Result<bool, KernelError> {
+            let mut roles = self.roles.lock().unwrap();
+            let before = roles.len();
+            roles.retain(|role| &role.id != id);
+            Ok(roles.len() < before)
+        }
+    }
+
+    /// Holds `manage_roles` plus `held`, and reports `members` for every role.
+    #[derive(Clone)]
+    struct MockPermissionChecker {
+        held: Vec<InstancePermission>,
+        members: Vec<AuthAccountId>,
+    }
+
+    impl PermissionChecker for MockPermissionChecker {
+        async fn check(
+            &self,
+            _subject: &AuthAccountId,
+            req: &PermissionReq,
+        ) -> error_stack::Result<bool, KernelError> {
+            Ok(match req {
+                PermissionReq::Instance { permission } => {
+                    *permission == InstancePermission::ManageRoles || self.held.contains(permission)
+                }
+                PermissionReq::Account { .. } => false,
+            })
+        }
+
+        async fn list_instance_roles(
+            &self,
+            _subject: &AuthAccountId,
+        ) -> error_stack::Result<Vec<InstanceRole>, KernelError> {
+            Ok(Vec::new())
+        }
+
+        async fn list_account_members(
+            &self,
+            _account_id: &AccountId,
+        ) -> error_stack::Result<Vec<AccountMember>, KernelError> {
+            Ok(Vec::new())
+        }
+
+        async fn list_instance_role_holders(
+            &self,
+        ) -> error_stack::Result<Vec<InstanceRoleHolder>, KernelError> {
+            Ok(Vec::new())
+        }
+
+        async fn list_custom_role_members(
+            &self,
+            _role_id: &CustomRoleId,
+        ) -> error_stack::Result<Vec<AuthAccountId>, KernelError> {
+            Ok(self.members.clone())
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum WriterCall {
+        Grant(InstancePermission),
+        Ungrant(InstancePermission),
+        RemoveMember(AuthAccountId),
+    }
+
+    #[derive(Clone, Default)]
+    struct MockPermissionWriter {
+        calls: Arc<Mutex<Vec<WriterCall>>>,
+    }
+
+    impl PermissionWriter for MockPermissionWriter {
+        async fn create_relation(
+            &self,
+            _target: &RelationTarget,
+            _subject: &AuthAccountId,
+        ) -> error_stack::Result<(), KernelError> {
+            panic!("custom role management does not add members")
+        }
+
+        async fn delete_relation(
+            &self,
+            target: &RelationTarget,
+            subject: &AuthAccountId,
+        ) -> error_stack::Result<(), KernelError> {
+            assert!(matches!(target, RelationTarget::CustomRole { .. }));
+            self.calls
+                .lock()
+                .unwrap()
+                .push(WriterCall::RemoveMember(subject.clone()));
+            Ok(())
+        }
+
+        async fn create_role_grant(
+            &self,
+            grant: &RoleGrant,
+        ) -> error_stack::Result<(), KernelError> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(WriterCall::Grant(grant.permission));
+            Ok(())
+        }
+
+        async fn delete_role_grant(
+            &self,
+            grant: &RoleGrant,
+        ) -> error_stack::Result<(), KernelError> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(WriterCall::Ungrant(grant.permission));
+            Ok(())
+        }
+    }
+
+    #[derive(Clone)]
+    struct MockModule {
+        database: MockDatabaseConnection,
+        roles: MockCustomRoleRepository,
+        permission_checker: MockPermissionChecker,
+        permission_writer: MockPermissionWriter,
+    }
+
+    impl DependOnDatabaseConnection for MockModule {
+        type DatabaseConnection = MockDatabaseConnection;
+
+        fn database_connection(&self) -> &Self::DatabaseConnection {
+            &self.database
+        }
+    }
+
+    impl DependOnCustomRoleRepository for MockModule {
+        type CustomRoleRepository = MockCustomRoleRepository;
+
+        fn custom_role_repository(&self) -> &Self::CustomRoleRepository {
+            &self.roles
+        }
+    }
+
+    impl DependOnPermissionChecker for MockModule {
+        type PermissionChecker = MockPermissionChecker;
+
+        fn permission_checker(&self) -> &Self::PermissionChecker {
+            &self.permission_checker
+        }
+    }
+
+    impl DependOnPermissionWriter for MockModule {
+        type PermissionWriter = MockPermissionWriter;
+
+        fn permission_writer(&self) -> &Self::PermissionWriter {
+            &self.permission_writer
+        }
+    }
+
+    fn module(held: Vec<InstancePermission>, members: Vec<AuthAccountId>) -> MockModule {
+        kernel::ensure_generator_initialized();
+        MockModule {
+            database: MockDatabaseConnection,
+            roles: MockCustomRoleRepository::default(),
+            permission_checker: MockPermissionChecker { held, members },
+            permission_writer: MockPermissionWriter::default(),
+        }
+    }
+
+    fn calls(module: &MockModule) -> Vec<WriterCall> {
+        module.permission_writer.calls.lock().unwrap().clone()
+    }
+
+    fn create_dto(name: &str, permissions: Vec<InstancePermission>) -> CreateCustomRoleDto {
+        CreateCustomRoleDto {
+            name: name.to_string(),
+            permissions,
+        }
+    }
+
+    #[tokio::test]
+    async fn create_grants_each_permission_once_in_declaration_order() {
+        without_scope_restriction(async {
+            let module = module(
+                vec![
+                    InstancePermission::ManageMedia,
+                    InstancePermission::ViewAuditLog,
+                ],
+                Vec::new(),
+            );
+
+            let role = module
+                .create_custom_role(
+                    &AuthAccountId::default(),
+                    create_dto(
+                        " Media team ",
+                        vec![
+                            InstancePermission::ViewAuditLog,
+                            InstancePermission::ManageMedia,
+                            InstancePermission::ViewAuditLog,
+                        ],
+                    ),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(role.name, "Media team");
+            assert_eq!(
+                role.permissions,
+                vec![
+                    InstancePermission::ManageMedia,
+                    InstancePermission::ViewAuditLog
+                ]
+            );
+            assert_eq!(
+                calls(&module),
+                vec![
+                    WriterCall::Grant(InstancePermission::ManageMedia),
+                    WriterCall::Grant(InstancePermission::ViewAuditLog),
+                ]
+            );
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn create_rejects_permissions_the_caller_lacks_or_roles_cannot_grant() {
+        without_scope_restriction(async {
+            let module = module(vec![InstancePermission::ManageMedia], Vec::new());
+            let caller = AuthAccountId::default();
+
+            let lacking = module
+                .create_custom_role(
+                    &caller,
+                    create_dto("Auditors", vec![InstancePermission::ViewAuditLog]),
+                )
+                .await;
+            let administrate = module
+                .create_custom_role(
+                    &caller,
+                    create_dto("Admins", vec![InstancePermission::Administrate]),
+                )
+                .await;
+
+            assert_eq!(
+                lacking.unwrap_err().current_context(),
+                &KernelError::PermissionDenied
+            );
+            assert_eq!(
+                administrate.unwrap_err().current_context(),
+                &KernelError::Validation
+            );
+            assert!(calls(&module).is_empty());
+            assert!(module.roles.roles.lock().unwrap().is_empty());
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn duplicate_names_are_rejected() {
+        without_scope_restriction(async {
+            let module = module(vec![InstancePermission::ManageMedia], Vec::new());
+            let caller = AuthAccountId::default();
+            module
+                .create_custom_role(
+                    &caller,
+                    create_dto("Media", vec![InstancePermission::ManageMedia]),
+                )
+                .await
+                .unwrap();
+
+            let result = module
+                .create_custom_role(
+                    &caller,
+                    create_dto("Media", vec![InstancePermission::ManageMedia]),
+                )
+                .await;
+
+            assert_eq!(
+                result.unwrap_err().current_context(),
+                &KernelError::Rejected
+            );
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn update_grants_added_and_revokes_removed_permissions() {
+        without_scope_restriction(async {
+            let module = module(
+                vec![
+                    InstancePermission::ManageMedia,
+                    InstancePermission::ManageReports,
+                ],
+                Vec::new(),
+            );
+            let caller = AuthAccountId::default();
+            let role = module
+                .create_custom_role(
+                    &caller,
+                    create_dto("Media", vec![InstancePermission::ManageMedia]),
+                )
+                .await
+                .unwrap();
+            module.permission_writer.calls.lock().unwrap().clear();
+
+            let updated = module
+                .update_custom_role(
+                    &caller,
+                    role.id,
+                    UpdateCustomRoleDto {
+                        name: None,
+                        permissions: Some(vec![InstancePermission::ManageReports]),
+                    },
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(updated.name, "Media");
+            assert_eq!(updated.permissions, vec![InstancePermission::ManageReports]);
+            assert_eq!(
+                calls(&module),
+                vec![
+                    WriterCall::Grant(InstancePermission::ManageReports),
+                    WriterCall::Ungrant(InstancePermission::ManageMedia),
+                ]
+            );
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn delete_revokes_grants_and_memberships() {
+        without_scope_restriction(async {
+            kernel::ensure_generator_initialized();
+            let member = AuthAccountId::default();
+            let module = module(vec![InstancePermission::ManageMedia], vec![member.clone()]);
+            let caller = AuthAccountId::default();
+            let role = module
+                .create_custom_role(
+                    &caller,
+                    create_dto("Media", vec![InstancePermission::ManageMedia]),
+                )
+                .await
+                .unwrap();
+            module.permission_writer.calls.lock().unwrap().clear();
+
+            module
+                .delete_custom_role(&caller, role.id.clone())
+                .await
+                .unwrap();
+
+            assert_eq!(
+                calls(&module),
+                vec![
+                    WriterCall::Ungrant(InstancePermission::ManageMedia),
+                    WriterCall::RemoveMember(member),
+                ]
+            );
+            let result = module.delete_custom_role(&caller, role.id).await;
+            assert_eq!(
+                result.unwrap_err().current_context(),
+                &KernelError::NotFound
+            );
+        })
+        .await;
+    }
+}
@@ -1,14 +1,110 @@
-use crate::permission::{check_permission, instance_administrate};
+use crate::dto::account::{InstanceRoleChangeDto, InstanceRoleHolderDto};
+use crate::permission::{
+    check_permission, instance_administrate, instance_manage_roles, instance_view_audit_log,
+};
 use error_stack::Report;
 use kernel::interfaces::database::DatabaseConnection;
 use kernel::interfaces::permission::{
-    DependOnPermissionChecker, DependOnPermissionWriter, InstanceRole, PermissionWriter,
-    RelationTarget,
+    DependOnPermissionChecker, DependOnPermissionWriter, InstanceRole, InstanceRoleHolder,
+    Permission, PermissionChecker, PermissionWriter, RelationTarget,
 };
 use kernel::interfaces::read_model::{AccountQuery, DependOnAccountQuery};
-use kernel::prelude::entity::{Account, AuthAccountId, Nanoid};
+use kernel::interfaces::repository::{
+    DependOnInstanceRoleChangeRepository, InstanceRoleChangeRepository,
+};
+use kernel::prelude::entity::{
+    Account, AuthAccountId, InstanceRoleChange, InstanceRoleChangeAction, InstanceRoleChangeId,
+    Nanoid,
+};
 use kernel::KernelError;
 use std::future::Future;
+use time::OffsetDateTime;
+
+const MAX_HISTORY_PAGE: usize = 100;
+
+/// Resolve the account's linked auth account after checking that the
+/// caller holds `permission`.
+pub(super) async fn target_auth_account_id<T>(
+    deps: &T,
+    auth_account_id: &AuthAccountId,
+    account_id: String,
+    permission: &Permission,
+) -> error_stack::Result<AuthAccountId, KernelError>
+where
+    T: DependOnAccountQuery + DependOnPermissionChecker + ?Sized,
+{
+    let mut conn = deps.database_connection().connection().await?;
+    let nanoid = Nanoid::<Account>::new(account_id);
+    let projection = deps
+        .account_query()
+        .find_by_nanoid_unfiltered(&mut conn, &nanoid)
+        .await?
+        .ok_or_else(|| {
+            Report::new(KernelError::NotFound).attach_printable(format!(
+                "Account not found with nanoid: {}",
+                nanoid.as_ref()
+            ))
+        })?;
+
+    check_permission(deps, auth_account_id, permission).await?;
+
+    deps.account_query()
+        .find_auth_account_id_by_account_id(&mut conn, projection.id())
+        .await?
+        .ok_or_else(|| {
+            Report::new(KernelError::NotFound).attach_printable(format!(
+                "No auth account linked to account: {:?}",
+                projection.id()
+            ))
+        })
+}
+
+async fn record_change<T>(
+    deps: &T,
+    role: InstanceRole,
+    action: InstanceRoleChangeAction,
+    subject: &AuthAccountId,
+    actor: &AuthAccountId,
+) -> error_stack::Result<(), KernelError>
+where
+    T: DependOnInstanceRoleChangeRepository + ?Sized,
+{
+    let mut conn = deps.database_connection().connection().await?;
+    deps.instance_role_change_repository()
+        .create(
+            &mut conn,
+            &InstanceRoleChange {
+                id: InstanceRoleChangeId::default(),
+                role,
+                action,
+                subject: subject.clone(),
+                actor: actor.clone(),
+                created_at: OffsetDateTime::now_utc(),
+            },
+        )
+        .await
+}
+
+/// Revoking `subject`'s admin role must leave the instance with an admin.
+fn ensure_admin_remains(
+    holders: &[InstanceRoleHolder],
+    subject: &AuthAccountId,
+) -> error_stack::Result<(), KernelError> {
+    let is_admin = |holder: &&InstanceRoleHolder| holder.role == InstanceRole::Admin;
+    let subject_is_admin = holders
+        .iter()
+        .filter(is_admin)
+        .any(|holder| &holder.auth_account_id == subject);
+    let other_admin = holders
+        .iter()
+        .filter(is_admin)
+        .any(|holder| &holder.auth_account_id != subject);
+    if subject_is_admin && !other_admin {
+        return Err(Report::new(KernelError::Rejected)
+            .attach_printable("Cannot revoke the last admin of the instance"));
+    }
+    Ok(())
+}
 
 pub trait AssignInstanceRoleUseCase:
     'static
@@ -16,6 +112,7 @@ pub trait AssignInstanceRoleUseCase:
     + Send
     + Clone
     + DependOnAccountQuery
+    + DependOnInstanceRoleChangeRepository
     + DependOnPermissionChecker
     + DependOnPermissionWriter
 {
@@ -26,35 +123,21 @@ pub trait AssignInstanceRoleUseCase:
         role: InstanceRole,
     ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send + 'a {
         async move {
-            let mut conn = self.database_connection().connection().await?;
-            let nanoid = Nanoid::<Account>::new(account_id);
-            let projection = self
-                .account_query()
-                .find_by_nanoid_unfiltered(&mut conn, &nanoid)
-                .await?
-                .ok_or_else(|| {
-                    Report::new(KernelError::NotFound).attach_printable(format!(
-                        "Account not found with nanoid: {}",
-                        nanoid.as_ref()
-                    ))
-                })?;
-
-            check_permission(self, auth_account_id, &instance_administrate()).await?;
-
-            let target_auth_id = self
-                .account_query()
-                .find_auth_account_id_by_account_id(&mut conn, projection.id())
-                .await?
-                .ok_or_else(|| {
-                    Report::new(KernelError::NotFound).attach_printable(format!(
-                        "No auth account linked to account: {:?}",
-                        projection.id()
-                    ))
-                })?;
+            let target_auth_id =
+                target_auth_account_id(self, auth_account_id, account_id, &instance_administrate())
+                    .await?;
 
             self.permission_writer()
                 .create_relation(&RelationTarget::Instance { role }, &target_auth_id)
-                .await
+                .await?;
+            record_change(
+                self,
+                role,
+                InstanceRoleChangeAction::Granted,
+                &target_auth_id,
+                auth_account_id,
+            )
+            .await
         }
     }
 }
@@ -65,6 +148,7 @@ impl<T> AssignInstanceRoleUseCase for T where
         + Send
         + Clone
         + DependOnAccountQuery
+        + DependOnInstanceRoleChangeRepository
         + DependOnPermissionChecker
         + DependOnPermissionWriter
 {
@@ -76,6 +160,7 @@ pub trait RevokeInstanceRoleUseCase:
     + Send
     + Clone
     + DependOnAccountQuery
+    + DependOnInstanceRoleChangeRepository
     + DependOnPermissionChecker
     + DependOnPermissionWriter
 {
@@ -86,40 +171,33 @@ pub trait RevokeInstanceRoleUseCase:
         role: InstanceRole,
     ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send + 'a {
         async move {
-            let mut conn = self.database_connection().connection().await?;
-            let nanoid = Nanoid::<Account>::new(account_id);
-            let projection = self
-                .account_query()
-                .find_by_nanoid_unfiltered(&mut conn, &nanoid)
-                .await?
-                .ok_or_else(|| {
-                    Report::new(KernelError::NotFound).attach_printable(format!(
-                        "Account not found with nanoid: {}",
-                        nanoid.as_ref()
-                    ))
-                })?;
-
-            check_permission(self, auth_account_id, &instance_administrate()).await?;
-
-            let target_auth_id = self
-                .account_query()
-                .find_auth_account_id_by_account_id(&mut conn, projection.id())
-                .await?
-                .ok_or_else(|| {
-                    Report::new(KernelError::NotFound).attach_printable(format!(
-                        "No auth account linked to account: {:?}",
-                        projection.id()
-                    ))
-                })?;
-
-            if role == InstanceRole::Admin && &target_auth_id == auth_account_id {
-                return Err(Report::new(KernelError::Rejected)
-                    .attach_printable("Cannot revoke your own admin role"));
+            let target_auth_id =
+                target_auth_account_id(self, auth_account_id, account_id, &instance_administrate())
+                    .await?;
+
+            if role == InstanceRole::Admin {
+                if &target_auth_id == auth_account_id {
+                    return Err(Report::new(KernelError::Rejected)
+                        .attach_printable("Cannot revoke your own admin role"));
+                }
+                let holders = self
+                    .permission_checker()
+                    .list_instance_role_holders()
+                    .await?;
+                ensure_admin_remains(&holders, &target_auth_id)?;
             }
 
             self.permission_writer()
                 .delete_relation(&RelationTarget::Instance { role }, &target_auth_id)
-                .await
+                .await?;
+            record_change(
+                self,
+                role,
+                InstanceRoleChangeAction::Revoked,
+                &target_auth_id,
+                auth_account_id,
+            )
+            .await
         }
     }
 }
@@ -130,14 +208,97 @@ impl<T> RevokeInstanceRoleUseCase for T where
         + Send
         + Clone
         + DependOnAccountQuery
+        + DependOnInstanceRoleChangeRepository
         + DependOnPermissionChecker
         + DependOnPermissionWriter
 {
 }
 
+pub trait GetInstanceRoleHoldersUseCase:
+    'static + Sync + Send + DependOnAccountQuery + DependOnPermissionChecker
+{
+    /// Every direct holder of an instance role, with the accounts linked to
+    /// each. Someone holding both roles is listed once per role.
+    fn get_instance_role_holders(
+        &self,
+        auth_account_id: &AuthAccountId,
+    ) -> impl Future<Output = error_stack::Result<Vec<InstanceRoleHolderDto>, KernelError>> + Send
+    {
+        async move {
+            check_permission(self, auth_account_id, &instance_manage_roles()).await?;
+            let holders = self
+                .permission_checker()
+                .list_instance_role_holders()
+                .await?;
+
+            let mut conn = self.database_connection().connection().await?;
+            let mut result = Vec::with_capacity(holders.len());
+            for holder in holders {
+                let accounts = self
+                    .account_query()
+                    .find_by_auth_id(&mut conn, &holder.auth_account_id)
+                    .await?;
+                result.push(InstanceRoleHolderDto {
+                    auth_account_id: holder.auth_account_id.as_ref().to_string(),
+                    role: holder.role,
+                    accounts: accounts.into_iter().map(Into::into).collect(),
+                });
+            }
+            Ok(result)
+        }
+    }
+}
+
+impl<T> GetInstanceRoleHoldersUseCase for T where
+    T: 'static + Sync + Send + DependOnAccountQuery + DependOnPermissionChecker
+{
+}
+
+pub trait GetInstanceRoleHistoryUseCase:
+    'static + Sync + Send + DependOnInstanceRoleChangeRepository + DependOnPermissionChecker
+{
+    /// Role grants and revocations, newest first.
+    fn get_instance_role_history(
+        &self,
+        auth_account_id: &AuthAccountId,
+        limit: Option<u32>,
+        cursor: Option<String>,
+    ) -> impl Future<Output = error_stack::Result<Vec<InstanceRoleChangeDto>, KernelError>> + Send
+    {
+        async move {
+            check_permission(self, auth_account_id, &instance_view_audit_log()).await?;
+            let cursor = cursor
+                .map(|cursor| {
+                    cursor.parse::<i64>().map_err(|_| {
+                        Report::new(KernelError::Validation).attach_printable(format!(
+                            "Invalid instance role history cursor: {cursor}"
+                        ))
+                    })
+                })
+                .transpose()?;
+            let limit = limit
+                .map(|limit| limit as usize)
+                .unwrap_or(20)
+                .clamp(1, MAX_HISTORY_PAGE);
+            let mut conn = self.database_connection().connection().await?;
+            let changes = self
+                .instance_role_change_repository()
+                .find_page(&mut conn, limit, cursor)
+                .await?;
+            Ok(changes.into_iter().map(Into::into).collect())
+        }
+    }
+}
+
+impl<T> GetInstanceRoleHistoryUseCase for T where
+    T: 'static + Sync + Send + DependOnInstanceRoleChangeRepository + DependOnPermissionChecker
+{
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::permission::without_scope_restriction;
     use kernel::interfaces::database::{
         Connection, DatabaseConnection, DependOnDatabaseConnection,
     };
@@ -145,6 +306,7 @@ mod tests {
         PermissionChecker, PermissionReq, PermissionWriter, RelationTarget,
     };
     use kernel::interfaces::read_model::{AccountQuery, DependOnAccountQuery};
+    use kernel::interfaces::repository::InstanceRoleChangeRepository;
     use kernel::prelude::entity::{Account, AccountId, AccountName, Nanoid};
     use kernel::test_utils::AccountBuilder;
     use std::sync::{Arc, Mutex};
@@ -189,9 +351,14 @@ mod tests {
         async fn find_by_auth_id(
             &self,
             _executor: &mut Self::Connection,
-            _auth_id: &AuthAccountId,
+            auth_id: &AuthAccountId,
         ) -> error_stack::Result<Vec<Account>, KernelError> {
-            Ok(Vec::new())
+            Ok(self
+                .account
+                .clone()
+                .filter(|_| self.linked_auth_account_id.as_ref() == Some(auth_id))
+                .into_iter()
+                .collect())
         }
 
         async fn find_auth_account_id_by_account_id(
@@ -294,6 +461,7 @@ mod tests {
     #[derive(Clone)]
     struct MockPermissionChecker {
         allowed: bool,
+        holders: Vec<InstanceRoleHolder>,
     }
 
     impl PermissionChecker for MockPermissionChecker {
@@ -311,6 +479,27 @@ mod tests {
         ) -> error_stack::Result<Vec<InstanceRole>, KernelError> {
             Ok(Vec::new())
         }
+
+        async fn list_account_members(
+            &self,
+            _account_id: &kernel::prelude::entity::AccountId,
+        ) -> error_stack::Result<Vec<kernel::interfaces::permission::AccountMember>, KernelError>
+        {
+            Ok(Vec::new())
+        }
+
+        async fn list_instance_role_holders(
+            &self,
+        ) -> error_stack::Result<Vec<InstanceRoleHolder>, KernelError> {
+            Ok(self.holders.clone())
+        }
+
+        async fn list_custom_role_members(
+            &self,
+            _role_id: &kernel::prelude::entity::CustomRoleId,
+        ) -> error_stack::Result<Vec<AuthAccountId>, KernelError> {
+            Ok(Vec::new())
+        }
     }
 
     #[derive(Clone, Debug, PartialEq)]
@@ -360,6 +549,56 @@ mod tests {
             });
             Ok(())
         }
+
+        async fn create_role_grant(
+            &self,
+            _grant: &kernel::interfaces::permission::RoleGrant,
+        ) -> error_stack::Result<(), KernelError> {
+            Ok(())
+        }
+
+        async fn delete_role_grant(
+            &self,
+            _grant: &kernel::interfaces::permission::RoleGrant,
+        ) -> error_stack::Result<(), KernelError> {
+            Ok(())
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockInstanceRoleChangeRepository {
+        changes: Arc<Mutex<Vec<InstanceRoleChange>>>,
+    }
+
+    impl InstanceRoleChangeRepository for MockInstanceRoleChangeRepository {
+        type Connection = MockConnection;
+
+        async fn create(
+            &self,
+            _executor: &mut Self::Connection,
+            change: &InstanceRoleChange,
+        ) -> error_stack::Result<(), KernelError> {
+            self.changes.lock().unwrap().push(change.clone());
+            Ok(())
+        }
+
+        async fn find_page(
+            &self,
+            _executor: &mut Self::Connection,
+            limit: usize,
+            cursor: Option<i64>,
+        ) -> error_stack::Result<Vec<InstanceRoleChange>, KernelError> {
+            Ok(self
+                .changes
+                .lock()
+                .unwrap()
+                .iter()
+                .rev()
+                .filter(|change| cursor.is_none_or(|cursor| *change.id.as_ref() < cursor))
+                .take(limit)
+                .cloned()
+                .collect())
+        }
     }
 
     #[derive(Clone)]
@@ -368,6 +607,7 @@ mod tests {
         accounts: MockAccountQuery,
         permission_checker: MockPermissionChecker,
         permission_writer: MockPermissionWriter,
+        role_changes: MockInstanceRoleChangeRepository,
     }
 
     impl DependOnDatabaseConnection for MockModule {
@@ -386,6 +626,14 @@ mod tests {
         }
     }
 
+    impl DependOnInstanceRoleChangeRepository for MockModule {
+        type InstanceRoleChangeRepository = MockInstanceRoleChangeRepository;
+
+        fn instance_role_change_repository(&self) -> &Self::InstanceRoleChangeRepository {
+            &self.role_changes
+        }
+    }
+
     impl DependOnPermissionChecker for MockModule {
         type PermissionChecker = MockPermissionChecker;
 
@@ -417,6 +665,13 @@ mod tests {
             .build();
         let operator_id = AuthAccountId::default();
         let target_auth_id = AuthAccountId::default();
+        let holders = [&operator_id, &target_auth_id]
+            .into_iter()
+            .map(|auth_account_id| InstanceRoleHolder {
+                auth_account_id: auth_account_id.clone(),
+                role: InstanceRole::Admin,
+            })
+            .collect();
         Fixture {
             module: MockModule {
                 database: MockDatabaseConnection,
@@ -424,8 +679,9 @@ mod tests {
                     account: account_exists.then_some(account),
                     linked_auth_account_id: linked_auth_exists.then_some(target_auth_id.clone()),
                 },
-                permission_checker: MockPermissionChecker { allowed },
+                permission_checker: MockPermissionChecker { allowed, holders },
                 permission_writer: MockPermissionWriter::default(),
+                role_changes: MockInstanceRoleChangeRepository::default(),
             },
             operator_id,
             target_auth_id,
@@ -437,46 +693,63 @@ mod tests {
         module.permission_writer.calls.lock().unwrap().clone()
     }
 
+    fn recorded_changes(module: &MockModule) -> Vec<(InstanceRole, InstanceRoleChangeAction)> {
+        module
+            .role_changes
+            .changes
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|change| (change.role, change.action))
+            .collect()
+    }
+
     #[tokio::test]
     async fn assign_creates_relation_for_target_auth_account() {
-        let fixture = fixture(true, true, true);
-
-        fixture
-            .module
-            .assign_instance_role(
-                &fixture.operator_id,
-                fixture.nanoid,
-                InstanceRole::Moderator,
-            )
-            .await
-            .unwrap();
+        without_scope_restriction(async {
+            let fixture = fixture(true, true, true);
+
+            fixture
+                .module
+                .assign_instance_role(
+                    &fixture.operator_id,
+                    fixture.nanoid,
+                    InstanceRole::Moderator,
+                )
+                .await
+                .unwrap();
 
-        assert_eq!(
-            calls(&fixture.module),
-            vec![WriterCall::Create {
-                target_role: InstanceRole::Moderator,
-                subject: fixture.target_auth_id,
-            }]
-        );
+            assert_eq!(
+                calls(&fixture.module),
+                vec![WriterCall::Create {
+                    target_role: InstanceRole::Moderator,
+                    subject: fixture.target_auth_id,
+                }]
+            );
+        })
+        .await;
     }
 
     #[tokio::test]
     async fn revoke_deletes_relation_for_target_auth_account() {
-        let fixture = fixture(true, true, true);
+        without_scope_restriction(async {
+            let fixture = fixture(true, true, true);
 
-        fixture
-            .module
-            .revoke_instance_role(&fixture.operator_id, fixture.nanoid, InstanceRole::Admin)
-            .await
-            .unwrap();
+            fixture
+                .module
+                .revoke_instance_role(&fixture.operator_id, fixture.nanoid, InstanceRole::Admin)
+                .await
+                .unwrap();
 
-        assert_eq!(
-            calls(&fixture.module),
-            vec![WriterCall::Delete {
-                target_role: InstanceRole::Admin,
-                subject: fixture.target_auth_id,
-            }]
-        );
+            assert_eq!(
+                calls(&fixture.module),
+                vec![WriterCall::Delete {
+                    target_role: InstanceRole::Admin,
+                    subject: fixture.target_auth_id,
+                }]
+            );
+        })
+        .await;
     }
 
     #[tokio::test]
@@ -513,92 +786,249 @@ mod tests {
 
     #[tokio::test]
     async fn assign_returns_not_found_when_account_has_no_linked_auth_account() {
-        let fixture = fixture(true, false, true);
+        without_scope_restriction(async {
+            let fixture = fixture(true, false, true);
+
+            let result = fixture
+                .module
+                .assign_instance_role(&fixture.operator_id, fixture.nanoid, InstanceRole::Admin)
+                .await;
+
+            assert_eq!(
+                result.unwrap_err().current_context(),
+                &KernelError::NotFound
+            );
+            assert!(calls(&fixture.module).is_empty());
+        })
+        .await;
+    }
 
-        let result = fixture
-            .module
-            .assign_instance_role(&fixture.operator_id, fixture.nanoid, InstanceRole::Admin)
-            .await;
+    #[tokio::test]
+    async fn revoke_rejects_own_admin_role() {
+        without_scope_restriction(async {
+            let mut fixture = fixture(true, true, true);
+            fixture.operator_id = fixture.target_auth_id.clone();
+
+            let result = fixture
+                .module
+                .revoke_instance_role(&fixture.operator_id, fixture.nanoid, InstanceRole::Admin)
+                .await;
+
+            assert_eq!(
+                result.unwrap_err().current_context(),
+                &KernelError::Rejected
+            );
+            assert!(calls(&fixture.module).is_empty());
+        })
+        .await;
+    }
 
-        assert_eq!(
-            result.unwrap_err().current_context(),
-            &KernelError::NotFound
-        );
-        assert!(calls(&fixture.module).is_empty());
+    #[tokio::test]
+    async fn revoke_allows_own_moderator_role() {
+        without_scope_restriction(async {
+            let mut fixture = fixture(true, true, true);
+            fixture.operator_id = fixture.target_auth_id.clone();
+
+            fixture
+                .module
+                .revoke_instance_role(
+                    &fixture.operator_id,
+                    fixture.nanoid,
+                    InstanceRole::Moderator,
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(
+                calls(&fixture.module),
+                vec![WriterCall::Delete {
+                    target_role: InstanceRole::Moderator,
+                    subject: fixture.target_auth_id,
+                }]
+            );
+        })
+        .await;
     }
 
     #[tokio::test]
-    async fn revoke_rejects_own_admin_role() {
-        let mut fixture = fixture(true, true, true);
-        fixture.operator_id = fixture.target_auth_id.clone();
+    async fn assign_twice_creates_relation_twice() {
+        without_scope_restriction(async {
+            let fixture = fixture(true, true, true);
+
+            fixture
+                .module
+                .assign_instance_role(
+                    &fixture.operator_id,
+                    fixture.nanoid.clone(),
+                    InstanceRole::Admin,
+                )
+                .await
+                .unwrap();
+            fixture
+                .module
+                .assign_instance_role(&fixture.operator_id, fixture.nanoid, InstanceRole::Admin)
+                .await
+                .unwrap();
+
+            assert_eq!(
+                calls(&fixture.module),
+                vec![
+                    WriterCall::Create {
+                        target_role: InstanceRole::Admin,
+                        subject: fixture.target_auth_id.clone(),
+                    },
+                    WriterCall::Create {
+                        target_role: InstanceRole::Admin,
+                        subject: fixture.target_auth_id,
+                    },
+                ]
+            );
+        })
+        .await;
+    }
 
-        let result = fixture
-            .module
-            .revoke_instance_role(&fixture.operator_id, fixture.nanoid, InstanceRole::Admin)
-            .await;
+    #[tokio::test]
+    async fn revoke_rejects_last_admin() {
+        without_scope_restriction(async {
+            let mut fixture = fixture(true, true, true);
+            fixture.module.permission_checker.holders = vec![InstanceRoleHolder {
+                auth_account_id: fixture.target_auth_id.clone(),
+                role: InstanceRole::Admin,
+            }];
+
+            let result = fixture
+                .module
+                .revoke_instance_role(&fixture.operator_id, fixture.nanoid, InstanceRole::Admin)
+                .await;
+
+            assert_eq!(
+                result.unwrap_err().current_context(),
+                &KernelError::Rejected
+            );
+            assert!(calls(&fixture.module).is_empty());
+            assert!(recorded_changes(&fixture.module).is_empty());
+        })
+        .await;
+    }
 
-        assert_eq!(
-            result.unwrap_err().current_context(),
-            &KernelError::Rejected
-        );
-        assert!(calls(&fixture.module).is_empty());
+    #[tokio::test]
+    async fn assign_and_revoke_are_recorded_in_history_newest_first() {
+        without_scope_restriction(async {
+            let fixture = fixture(true, true, true);
+
+            fixture
+                .module
+                .assign_instance_role(
+                    &fixture.operator_id,
+                    fixture.nanoid.clone(),
+                    InstanceRole::Moderator,
+                )
+                .await
+                .unwrap();
+            fixture
+                .module
+                .revoke_instance_role(
+                    &fixture.operator_id,
+                    fixture.nanoid,
+                    InstanceRole::Moderator,
+                )
+                .await
+                .unwrap();
+
+            let history = fixture
+                .module
+                .get_instance_role_history(&fixture.operator_id, None, None)
+                .await
+                .unwrap();
+            let operator = fixture.operator_id.as_ref().to_string();
+            let target = fixture.target_auth_id.as_ref().to_string();
+            assert_eq!(
+                history
+                    .iter()
+                    .map(|change| (
+                        change.role,
+                        change.action,
+                        change.subject_auth_account_id.as_str(),
+                        change.actor_auth_account_id.as_str(),
+                    ))
+                    .collect::<Vec<_>>(),
+                vec![
+                    (
+                        InstanceRole::Moderator,
+                        InstanceRoleChangeAction::Revoked,
+                        target.as_str(),
+                        operator.as_str(),
+                    ),
+                    (
+                        InstanceRole::Moderator,
+                        InstanceRoleChangeAction::Granted,
+                        target.as_str(),
+                        operator.as_str(),
+                    ),
+                ]
+            );
+        })
+        .await;
     }
 
     #[tokio::test]
-    async fn revoke_allows_own_moderator_role() {
-        let mut fixture = fixture(true, true, true);
-        fixture.operator_id = fixture.target_auth_id.clone();
+    async fn holders_are_resolved_to_their_accounts() {
+        without_scope_restriction(async {
+            let fixture = fixture(true, true, true);
 
-        fixture
-            .module
-            .revoke_instance_role(
-                &fixture.operator_id,
-                fixture.nanoid,
-                InstanceRole::Moderator,
-            )
-            .await
-            .unwrap();
+            let holders = fixture
+                .module
+                .get_instance_role_holders(&fixture.operator_id)
+                .await
+                .unwrap();
 
-        assert_eq!(
-            calls(&fixture.module),
-            vec![WriterCall::Delete {
-                target_role: InstanceRole::Moderator,
-                subject: fixture.target_auth_id,
-            }]
-        );
+            let resolved: Vec<(String, Vec<String>)> = holders
+                .into_iter()
+                .map(|holder| {
+                    (
+                        holder.auth_account_id,
+                        holder
+                            .accounts
+                            .into_iter()
+                            .map(|account| account.nanoid)
+                            .collect(),
+                    )
+                })
+                .collect();
+            assert_eq!(
+                resolved,
+                vec![
+                    (fixture.operator_id.as_ref().to_string(), Vec::new()),
+                    (
+                        fixture.target_auth_id.as_ref().to_string(),
+                        vec![fixture.nanoid]
+                    ),
+                ]
+            );
+        })
+        .await;
     }
 
     #[tokio::test]
-    async fn assign_twice_creates_relation_twice() {
-        let fixture = fixture(true, true, true);
+    async fn listing_requires_permission() {
+        let fixture = fixture(true, true, false);
 
-        fixture
+        let holders = fixture
             .module
-            .assign_instance_role(
-                &fixture.operator_id,
-                fixture.nanoid.clone(),
-                InstanceRole::Admin,
-            )
-            .await
-            .unwrap();
-        fixture
+            .get_instance_role_holders(&fixture.operator_id)
+            .await;
+        let history = fixture
             .module
-            .assign_instance_role(&fixture.operator_id, fixture.nanoid, InstanceRole::Admin)
-            .await
-            .unwrap();
+            .get_instance_role_history(&fixture.operator_id, None, None)
+            .await;
 
         assert_eq!(
-            calls(&fixture.module),
-            vec![
-                WriterCall::Create {
-                    target_role: InstanceRole::Admin,
-                    subject: fixture.target_auth_id.clone(),
-                },
-                WriterCall::Create {
-                    target_role: InstanceRole::Admin,
-                    subject: fixture.target_auth_id,
-                },
-            ]
+            holders.unwrap_err().current_context(),
+            &KernelError::PermissionDenied
+        );
+        assert_eq!(
+            history.unwrap_err().current_context(),
+            &KernelError::PermissionDenied
         );
     }
 }
@@ -0,0 +1,264 @@
+use crate::dto::account::AccountMemberDto;
+use crate::permission::{account_share, check_permission};
+use error_stack::Report;
+use kernel::interfaces::database::DatabaseConnection;
+use kernel::interfaces::permission::{
+    AccountMember, AccountRelation, DependOnPermissionChecker, DependOnPermissionWriter,
+    PermissionChecker, PermissionWriter, RelationTarget,
+};
+use kernel::interfaces::read_model::{AccountQuery, DependOnAccountQuery};
+use kernel::interfaces::repository::{AuthAccountRepository, DependOnAuthAccountRepository};
+use kernel::prelude::entity::{Account, AccountId, AuthAccountId, Nanoid};
+use kernel::KernelError;
+use std::future::Future;
+
+/// Resolve the account and check that the caller owns it.
+async fn shared_account_id<T>(
+    deps: &T,
+    auth_account_id: &AuthAccountId,
+    account_id: String,
+) -> error_stack::Result<AccountId, KernelError>
+where
+    T: DependOnAccountQuery + DependOnPermissionChecker + ?Sized,
+{
+    let mut executor = deps.database_connection().connection().await?;
+    let nanoid = Nanoid::<Account>::new(account_id);
+    let account = deps
+        .account_query()
+        .find_by_nanoid_unfiltered(&mut executor, &nanoid)
+        .await?
+        .ok_or_else(|| {
+            Report::new(KernelError::NotFound).attach_printable(format!(
+                "Account not found with nanoid: {}",
+                nanoid.as_ref()
+            ))
+        })?;
+    check_permission(deps, auth_account_id, &account_share(account.id())).await?;
+    Ok(account.id().clone())
+}
+
+fn parse_member_id(member_id: &str) -> error_stack::Result<AuthAccountId, KernelError> {
+    member_id
+        .parse::<i64>()
+        .map(AuthAccountId::new)
+        .map_err(|_| {
+            Report::new(KernelError::Validation)
+                .attach_printable(format!("Invalid auth account ID: {member_id}"))
+        })
+}
+
+/// Removing `subject`'s `relation` must leave the account with an owner.
+fn ensure_owner_remains(
+    members: &[AccountMember],
+    subject: &AuthAccountId,
+    relation: AccountRelation,
+) -> error_stack::Result<(), KernelError> {
+    if relation != AccountRelation::Owner {
+        return Ok(());
+    }
+    let other_owner = members.iter().any(|member| {
+        member.relation == AccountRelation::Owner && &member.auth_account_id != subject
+    });
+    if !other_owner {
+        return Err(Report::new(KernelError::Rejected)
+            .attach_printable("Cannot remove the last owner of an account"));
+    }
+    Ok(())
+}
+
+pub trait GetAccountMembersUseCase:
+    'static + Sync + Send + DependOnAccountQuery + DependOnPermissionChecker
+{
+    fn get_account_members(
+        &self,
+        auth_account_id: &AuthAccountId,
+        account_id: String,
+    ) -> impl Future<Output = error_stack::Result<Vec<AccountMemberDto>, KernelError>> + Send {
+        async move {
+            let account_id = shared_account_id(self, auth_account_id, account_id).await?;
+            let members = self
+                .permission_checker()
+                .list_account_members(&account_id)
+                .await?;
+            Ok(members
+                .into_iter()
+                .map(|member| AccountMemberDto {
+                    auth_account_id: AsRef::<i64>::as_ref(&member.auth_account_id).to_string(),
+                    relation: member.relation,
+                })
+                .collect())
+        }
+    }
+}
+
+impl<T> GetAccountMembersUseCase for T where
+    T: 'static + Sync + Send + DependOnAccountQuery + DependOnPermissionChecker
+{
+}
+
+pub trait GrantAccountRelationUseCase:
+    'static
+    + Sync
+    + Send
+    + DependOnAccountQuery
+    + DependOnAuthAccountRepository
+    + DependOnPermissionChecker
+    + DependOnPermissionWriter
+{
+    /// Granting a relation the auth account already holds is a no-op.
+    fn grant_account_relation(
+        &self,
+        auth_account_id: &AuthAccountId,
+        account_id: String,
+        member_id: String,
+        relation: AccountRelation,
+    ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send {
+        async move {
+            let member_id = parse_member_id(&member_id)?;
+            let account_id = shared_account_id(self, auth_account_id, account_id).await?;
+
+            let mut executor = self.database_connection().connection().await?;
+            self.auth_account_repository()
+                .find_by_id(&mut executor, &member_id)
+                .await?
+                .ok_or_else(|| {
+                    Report::new(KernelError::NotFound)
+                        .attach_printable(format!("Auth account not found: {}", member_id.as_ref()))
+                })?;
+
+            self.permission_writer()
+                .create_relation(
+                    &RelationTarget::Account {
+                        account_id,
+                        relation,
+                    },
+                    &member_id,
+                )
+                .await
+        }
+    }
+}
+
+impl<T> GrantAccountRelationUseCase for T where
+    T: 'static
+        + Sync
+        + Send
+        + DependOnAccountQuery
+        + DependOnAuthAccountRepository
+        + DependOnPermissionChecker
+        + DependOnPermissionWriter
+{
+}
+
+pub trait RevokeAccountRelationUseCase:
+    'static + Sync + Send + DependOnAccountQuery + DependOnPermissionChecker + DependOnPermissionWriter
+{
+    /// Owners may remove themselves as long as another owner remains.
+    fn revoke_account_relation(
+        &self,
+        auth_account_id: &AuthAccountId,
+        account_id: String,
+        member_id: String,
+        relation: AccountRelation,
+    ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send {
+        async move {
+            let member_id = parse_member_id(&member_id)?;
+            let account_id = shared_account_id(self, auth_account_id, account_id).await?;
+
+            let members = self
+                .permission_checker()
+                .list_account_members(&account_id)
+                .await?;
+            if !members
+                .iter()
+                .any(|member| member.auth_account_id == member_id && member.relation == relation)
+            {
+                return Err(Report::new(KernelError::NotFound).attach_printable(format!(
+                    "Auth account {} is not one of the account's {}",
+                    member_id.as_ref(),
+                    relation.as_str()
+                )));
+            }
+            ensure_owner_remains(&members, &member_id, relation)?;
+
+            let target = RelationTarget::Account {
+                account_id: account_id.clone(),
+                relation,
+            };
+            self.permission_writer()
+                .delete_relation(&target, &member_id)
+                .await?;
+            if relation != AccountRelation::Owner {
+                return Ok(());
+            }
+            // Two owners removing each other concurrently both pass the check
+            // above. Look again now that ours is gone and put it back if no
+            // owner is left, so at least one of them keeps the account.
+            let members = self
+                .permission_checker()
+                .list_account_members(&account_id)
+                .await?;
+            if let Err(error) = ensure_owner_remains(&members, &member_id, relation) {
+                self.permission_writer()
+                    .create_relation(&target, &member_id)
+                    .await?;
+                return Err(error);
+            }
+            Ok(())
+        }
+    }
+}
+
+impl<T> RevokeAccountRelationUseCase for T where
+    T: 'static
+        + Sync
+        + Send
+        + DependOnAccountQuery
+        + DependOnPermissionChecker
+        + DependOnPermissionWriter
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn member(auth_account_id: &AuthAccountId, relation: AccountRelation) -> AccountMember {
+        AccountMember {
+            auth_account_id: auth_account_id.clone(),
+            relation,
+        }
+    }
+
+    #[test]
+    fn the_last_owner_cannot_be_removed() {
+        kernel::ensure_generator_initialized();
+        let owner = AuthAccountId::default();
+        let editor = AuthAccountId::default();
+        let members = vec![
+            member(&owner, AccountRelation::Owner),
+            member(&editor, AccountRelation::Editor),
+            member(&editor, AccountRelation::Signer),
+        ];
+
+        let result = ensure_owner_remains(&members, &owner, AccountRelation::Owner);
+        assert_eq!(
+            result.unwrap_err().current_context(),
+            &KernelError::Rejected
+        );
+        assert!(ensure_owner_remains(&members, &editor, AccountRelation::Editor).is_ok());
+    }
+
+    #[test]
+    fn an_owner_can_leave_while_another_owner_remains() {
+        kernel::ensure_generator_initialized();
+        let owner = AuthAccountId::default();
+        let co_owner = AuthAccountId::default();
+        let members = vec![
+            member(&owner, AccountRelation::Owner),
+            member(&co_owner, AccountRelation::Owner),
+        ];
+
+        assert!(ensure_owner_remains(&members, &owner, AccountRelation::Owner).is_ok());
+    }
+}
@@ -1,14 +1,26 @@
 mod create;
+mod custom_role;
 mod deactivate;
 mod instance_role;
+mod member;
 mod moderation;
 mod reactivate;
 mod read;
 mod update;
 
 pub use create::CreateAccountUseCase;
+pub use custom_role::{
+    AssignCustomRoleUseCase, CreateCustomRoleUseCase, DeleteCustomRoleUseCase,
+    GetCustomRolesUseCase, RevokeCustomRoleUseCase, UpdateCustomRoleUseCase,
+};
 pub use deactivate::DeactivateAccountUseCase;
-pub use instance_role::{AssignInstanceRoleUseCase, RevokeInstanceRoleUseCase};
+pub use instance_role::{
+    AssignInstanceRoleUseCase, GetInstanceRoleHistoryUseCase, GetInstanceRoleHoldersUseCase,
+    RevokeInstanceRoleUseCase,
+};
+pub use member::{
+    GetAccountMembersUseCase, GrantAccountRelationUseCase, RevokeAccountRelationUseCase,
+};
 pub use moderation::{
     BanAccountUseCase, SuspendAccountUseCase, UnbanAccountUseCase, UnsuspendAccountUseCase,
 };
@@ -320,6 +320,7 @@ impl<T> UnbanAccountUseCase for T where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::permission::without_scope_restriction;
     use kernel::interfaces::database::{
         Connection, DependOnDatabaseConnection, DependOnTransactionManager,
     };
@@ -549,6 +550,28 @@ mod tests {
         ) -> error_stack::Result<Vec<InstanceRole>, KernelError> {
             Ok(Vec::new())
         }
+
+        async fn list_account_members(
+            &self,
+            _account_id: &kernel::prelude::entity::AccountId,
+        ) -> error_stack::Result<Vec<kernel::interfaces::permission::AccountMember>, KernelError>
+        {
+            Ok(Vec::new())
+        }
+
+        async fn list_instance_role_holders(
+            &self,
+        ) -> error_stack::Result<Vec<kernel::interfaces::permission::InstanceRoleHolder>, KernelError>
+        {
+            Ok(Vec::new())
+        }
+
+        async fn list_custom_role_members(
+            &self,
+            _role_id: &kernel::prelude::entity::CustomRoleId,
+        ) -> error_stack::Result<Vec<AuthAccountId>, KernelError> {
+            Ok(Vec::new())
+        }
     }
 
     #[derive(Clone)]
@@ -644,62 +667,71 @@ mod tests {
 
     #[tokio::test]
     async fn unban_saves_unbanned_event_for_banned_account() {
-        let fixture = fixture(
-            Some(account(
-                AccountStatus::Banned {
-                    reason: "x".into(),
-                    banned_at: OffsetDateTime::now_utc(),
-                },
-                None,
-            )),
-            true,
-        );
-
-        fixture
-            .module
-            .unban_account(&fixture.operator_id, fixture.nanoid)
-            .await
-            .unwrap();
-
-        assert_eq!(saved_events(&fixture.module), vec![AccountEvent::Unbanned]);
+        without_scope_restriction(async {
+            let fixture = fixture(
+                Some(account(
+                    AccountStatus::Banned {
+                        reason: "x".into(),
+                        banned_at: OffsetDateTime::now_utc(),
+                    },
+                    None,
+                )),
+                true,
+            );
+
+            fixture
+                .module
+                .unban_account(&fixture.operator_id, fixture.nanoid)
+                .await
+                .unwrap();
+
+            assert_eq!(saved_events(&fixture.module), vec![AccountEvent::Unbanned]);
+        })
+        .await;
     }
 
     #[tokio::test]
     async fn unban_rejects_active_account_without_saving_event() {
-        let fixture = fixture(Some(account(AccountStatus::Active, None)), true);
-
-        let result = fixture
-            .module
-            .unban_account(&fixture.operator_id, fixture.nanoid)
-            .await;
-
-        assert_eq!(
-            result.unwrap_err().current_context(),
-            &KernelError::Rejected
-        );
-        assert!(saved_events(&fixture.module).is_empty());
+        without_scope_restriction(async {
+            let fixture = fixture(Some(account(AccountStatus::Active, None)), true);
+
+            let result = fixture
+                .module
+                .unban_account(&fixture.operator_id, fixture.nanoid)
+                .await;
+
+            assert_eq!(
+                result.unwrap_err().current_context(),
+                &KernelError::Rejected
+            );
+            assert!(saved_events(&fixture.module).is_empty());
+        })
+        .await;
     }
 
     #[tokio::test]
     async fn unban_rejects_deactivated_account_without_saving_event() {
-        let fixture = fixture(
-            Some(account(
-                AccountStatus::Active,
-                Some(DeletedAt::<Account>::now()),
-            )),
-            true,
-        );
-
-        let result = fixture
-            .module
-            .unban_account(&fixture.operator_id, fixture.nanoid)
-            .await;
-
-        assert_eq!(
-            result.unwrap_err().current_context(),
-            &KernelError::Rejected
-        );
-        assert!(saved_events(&fixture.module).is_empty());
+        without_scope_restriction(async {
+            let fixture = fixture(
+                Some(account(
+                    AccountStatus::Active,
+                    Some(DeletedAt::<Account>::now()),
+                )),
+                true,
+            );
+
+            let result = fixture
+                .module
+                .unban_account(&fixture.operator_id, fixture.nanoid)
+                .await;
+
+            assert_eq!(
+                result.unwrap_err().current_context(),
+                &KernelError::Rejected
+            );
+            assert!(saved_events(&fixture.module).is_empty());
+        })
+        .await;
     }
 
     #[tokio::test]
@@ -392,6 +392,20 @@ mod tests {
             self.calls.lock().unwrap().push(WriterCall::Delete);
             Ok(())
         }
+
+        async fn create_role_grant(
+            &self,
+            _grant: &kernel::interfaces::permission::RoleGrant,
+        ) -> error_stack::Result<(), KernelError> {
+            Ok(())
+        }
+
+        async fn delete_role_grant(
+            &self,
+            _grant: &kernel::interfaces::permission::RoleGrant,
+        ) -> error_stack::Result<(), KernelError> {
+            Ok(())
+        }
     }
 
     #[derive(Clone)]
@@ -1,6 +1,6 @@
 use crate::dto::account::AccountDto;
 use crate::dto::pagination::{apply_pagination, Pagination};
-use crate::permission::{account_view, check_permission};
+use crate::permission::{account_view, check_permissions, token_allows_account};
 use kernel::interfaces::database::DatabaseConnection;
 use kernel::interfaces::permission::DependOnPermissionChecker;
 use kernel::interfaces::read_model::{AccountQuery, DependOnAccountQuery};
@@ -12,7 +12,8 @@ pub trait GetAccountUseCase:
     'static + Sync + Send + DependOnAccountQuery + DependOnPermissionChecker
 {
     // find_by_auth_id returns only accounts owned by the authenticated user,
-    // so no additional permission check is needed.
+    // so no additional permission check is needed beyond the accounts a
+    // personal access token is limited to.
     fn get_all_accounts(
         &self,
         auth_account_id: &AuthAccountId,
@@ -28,7 +29,10 @@ pub trait GetAccountUseCase:
             let accounts = self
                 .account_query()
                 .find_by_auth_id(&mut conn, auth_account_id)
-                .await?;
+                .await?
+                .into_iter()
+                .filter(|account| token_allows_account(account.id()))
+                .collect();
             let cursor = if let Some(cursor) = cursor {
                 let id: Nanoid<Account> = Nanoid::new(cursor);
                 self.account_query().find_by_nanoid(&mut conn, &id).await?
@@ -55,17 +59,18 @@ pub trait GetAccountUseCase:
                 .find_by_nanoids(&mut conn, &nanoids)
                 .await?;
 
-            let mut result = Vec::new();
-            for account in accounts {
-                if check_permission(self, auth_account_id, &account_view(account.id()))
-                    .await
-                    .is_ok()
-                {
-                    result.push(AccountDto::from(account));
-                }
-            }
+            let permissions: Vec<_> = accounts
+                .iter()
+                .map(|account| account_view(account.id()))
+                .collect();
+            let allowed = check_permissions(self, auth_account_id, &permissions).await?;
 
-            Ok(result)
+            Ok(accounts
+                .into_iter()
+                .zip(allowed)
+                .filter(|(_, allowed)| *allowed)
+                .map(|(account, _)| AccountDto::from(account))
+                .collect())
         }
     }
 }
@@ -1,6 +1,6 @@
 use crate::dto::account::{AccountDetailDto, AccountDto, AccountFieldDto};
 use crate::dto::pagination::{apply_pagination, Pagination};
-use crate::permission::{account_view, check_permission};
+use crate::permission::{account_view, check_permissions};
 use error_stack::Report;
 use kernel::interfaces::database::DatabaseConnection;
 use kernel::interfaces::permission::DependOnPermissionChecker;
@@ -84,15 +84,17 @@ pub trait GetAccountDetailUseCase:
                 .account_query()
                 .find_by_nanoids(&mut executor, &nanoids)
                 .await?;
-            let mut permitted = Vec::new();
-            for account in accounts {
-                if check_permission(self, auth_account_id, &account_view(account.id()))
-                    .await
-                    .is_ok()
-                {
-                    permitted.push(account);
-                }
-            }
+            let permissions: Vec<_> = accounts
+                .iter()
+                .map(|account| account_view(account.id()))
+                .collect();
+            let allowed = check_permissions(self, auth_account_id, &permissions).await?;
+            let permitted = accounts
+                .into_iter()
+                .zip(allowed)
+                .filter(|(_, allowed)| *allowed)
+                .map(|(account, _)| account)
+                .collect();
             compose_account_details(self, &mut executor, permitted).await
         }
     }
@@ -134,7 +136,7 @@ where
         .find_by_account_ids(executor, &account_ids)
         .await?;
     metadata.sort_by_key(|field| (*field.account_id().as_ref(), *field.id().as_ref()));
-    let image_ids: Vec<_> = profiles
+    let references: Vec<_> = profiles
         .iter()
         .flat_map(|profile| {
             profile
@@ -142,17 +144,17 @@ where
                 .as_ref()
                 .into_iter()
                 .chain(profile.banner().as_ref())
-                .cloned()
+                .map(|image_id| (profile.account_id().clone(), image_id.clone()))
         })
         .collect::<HashSet<_>>()
         .into_iter()
         .collect();
     let images: HashMap<_, _> = deps
         .image_repository()
-        .find_by_ids(executor, &image_ids)
+        .find_profile_images(executor, &references)
         .await?
         .into_iter()
-        .map(|image| (image.id().clone(), image.url().as_ref().to_string()))
+        .map(|(account_id, image)| ((account_id, image.id().clone()), image))
         .collect();
     let profile_map: HashMap<_, _> = profiles
         .into_iter()
@@ -184,11 +186,11 @@ where
                 profile
                     .icon()
                     .as_ref()
-                    .and_then(|id| images.get(id).cloned()),
+                    .and_then(|id| images.get(&(account_id.clone(), id.clone()))),
                 profile
                     .banner()
                     .as_ref()
-                    .and_then(|id| images.get(id).cloned()),
+                    .and_then(|id| images.get(&(account_id.clone(), id.clone()))),
                 metadata_map.remove(&account_id).unwrap_or_default(),
             ))
         })
@@ -1,8 +1,10 @@
 use super::fields::apply_field_updates;
 use super::validate::validate_update_account_dto;
 use crate::dto::account::{AccountDetailDto, AccountDto, AccountFieldDto, UpdateAccountDto};
+use crate::dto::media::ImageFocusDto;
 use crate::permission::{account_edit, check_permission};
 use crate::service::activitypub::DeliverUpdatePersonUseCase;
+use crate::service::media::with_metadata;
 use error_stack::Report;
 use kernel::interfaces::config::DependOnPublicBaseUrl;
 use kernel::interfaces::crypto::{DependOnKeyEncryptor, DependOnPasswordProvider};
@@ -26,8 +28,8 @@ use kernel::interfaces::repository::{
     DependOnRemoteAccountRepository, DependOnSigningKeyRepository, ImageRepository,
 };
 use kernel::prelude::entity::{
-    Account, AccountIsBot, AuthAccountId, FieldAction, ImageId, ImageUrl, Nanoid, Profile,
-    ProfileDisplayName, ProfileSummary,
+    Account, AccountId, AccountIsBot, AuthAccountId, FieldAction, Image, ImageDescription,
+    ImageFocus, ImageId, ImageUrl, Nanoid, Profile, ProfileDisplayName, ProfileSummary,
 };
 use kernel::KernelError;
 use std::collections::HashMap;
@@ -85,6 +87,7 @@ pub trait UpdateAccountDetailUseCase:
             let deps = self.clone();
             let account_nanoid = dto.account_nanoid.clone();
             let delivery_account_id = account_id.clone();
+            let auth_account_id = auth_account_id.clone();
             let (updated, media_changed) = self
                 .transaction_manager()
                 .transaction(move |executor| {
@@ -125,8 +128,30 @@ pub trait UpdateAccountDetailUseCase:
                             resolve_field_action_image_id(&deps, executor, &dto.banner_url).await?;
                         let icon_after = effective_image_id(&icon, profile.icon());
                         let banner_after = effective_image_id(&banner, profile.banner());
-                        let media_changed =
-                            icon_after != *profile.icon() || banner_after != *profile.banner();
+                        let icon_metadata_changed = update_image_metadata(
+                            &deps,
+                            executor,
+                            (&auth_account_id, &account_id),
+                            icon_after.as_ref(),
+                            icon_after != *profile.icon(),
+                            (&dto.icon_description, &dto.icon_focus),
+                            "icon",
+                        )
+                        .await?;
+                        let banner_metadata_changed = update_image_metadata(
+                            &deps,
+                            executor,
+                            (&auth_account_id, &account_id),
+                            banner_after.as_ref(),
+                            banner_after != *profile.banner(),
+                            (&dto.banner_description, &dto.banner_focus),
+                            "banner",
+                        )
+                        .await?;
+                        let media_changed = icon_after != *profile.icon()
+                            || banner_after != *profile.banner()
+                            || icon_metadata_changed
+                            || banner_metadata_changed;
                         if !dto.display_name.is_unchanged()
                             || !dto.summary.is_unchanged()
                             || !icon.is_unchanged()
@@ -216,24 +241,24 @@ pub trait UpdateAccountDetailUseCase:
                                 })
                                 .collect(),
                         };
-                        let image_ids: Vec<_> =
-                            icon_id.iter().chain(banner_id.iter()).cloned().collect();
-                        let images: HashMap<ImageId, String> = if image_ids.is_empty() {
-                            HashMap::new()
-                        } else {
-                            deps.image_repository()
-                                .find_by_ids(executor, &image_ids)
-                                .await?
-                                .into_iter()
-                                .map(|image| (image.id().clone(), image.url().as_ref().to_string()))
-                                .collect()
-                        };
+                        let references: Vec<_> = icon_id
+                            .iter()
+                            .chain(banner_id.iter())
+                            .map(|id| (account_id.clone(), id.clone()))
+                            .collect();
+                        let images: HashMap<ImageId, Image> = deps
+                            .image_repository()
+                            .find_profile_images(executor, &references)
+                            .await?
+                            .into_iter()
+                            .map(|(_, image)| (image.id().clone(), image))
+                            .collect();
                         Ok((
                             AccountDto::from(account).into_detail(
                                 display_name,
                                 summary,
-                                icon_id.as_ref().and_then(|id| images.get(id).cloned()),
-                                banner_id.as_ref().and_then(|id| images.get(id).cloned()),
+                                icon_id.as_ref().and_then(|id| images.get(id)),
+                                banner_id.as_ref().and_then(|id| images.get(id)),
                                 fields,
                             ),
                             media_changed,
@@ -313,9 +338,75 @@ async fn resolve_image_id<T: DependOnImageRepository + ?Sized>(
             Report::new(KernelError::NotFound)
                 .attach_printable(format!("Image not found with URL: {}", url))
         })?;
+    if *image.quarantined() {
+        return Err(Report::new(KernelError::Rejected)
+            .attach_printable("Image is quarantined pending moderator review"));
+    }
     Ok(Some(image.id().clone()))
 }
 
+/// Write the alt text and focal point changes the profile shows with the
+/// image a slot holds after the update. A newly picked image starts from
+/// what the caller sent along with its upload. Returns whether they changed.
+async fn update_image_metadata<T: DependOnImageRepository + ?Sized>(
+    deps: &T,
+    executor: &mut <<T as DependOnDatabaseConnection>::DatabaseConnection as DatabaseConnection>::Connection,
+    (auth_account_id, account_id): (&AuthAccountId, &AccountId),
+    image_id: Option<&ImageId>,
+    picked: bool,
+    (description, focus): (&FieldAction<String>, &FieldAction<ImageFocusDto>),
+    slot: &str,
+) -> error_stack::Result<bool, KernelError> {
+    let explicit = !description.is_unchanged() || !focus.is_unchanged();
+    let image_id = match image_id {
+        Some(image_id) if explicit || picked => image_id,
+        None if explicit => {
+            return Err(
+                Report::new(KernelError::Validation).attach_printable(format!(
+                    "{slot}_description and {slot}_focus need an image in the {slot} slot"
+                )),
+            )
+        }
+        _ => return Ok(false),
+    };
+    let (_, shown) = deps
+        .image_repository()
+        .find_profile_images(executor, &[(account_id.clone(), image_id.clone())])
+        .await?
+        .pop()
+        .ok_or_else(|| Report::new(KernelError::NotFound))?;
+    let uploaded = if picked && shown.description().is_none() && shown.focus().is_none() {
+        deps.image_repository()
+            .find_by_id_for_owner(executor, image_id, auth_account_id)
+            .await?
+    } else {
+        None
+    };
+    let base = uploaded.as_ref().unwrap_or(&shown);
+    let description = match description {
+        FieldAction::Unchanged => base.description().clone(),
+        FieldAction::Clear => None,
+        FieldAction::Set(text) if text.trim().is_empty() => None,
+        FieldAction::Set(text) => Some(ImageDescription::new(text.trim())),
+    };
+    let focus = match focus {
+        FieldAction::Unchanged => *base.focus(),
+        FieldAction::Clear => None,
+        FieldAction::Set(focus) => Some(ImageFocus::from(*focus)),
+    };
+    if description == *shown.description() && focus == *shown.focus() {
+        return Ok(false);
+    }
+    deps.image_repository()
+        .update_profile_metadata(
+            executor,
+            account_id,
+            &with_metadata(shown, description, focus),
+        )
+        .await?;
+    Ok(true)
+}
+
 fn effective_image_id(action: &FieldAction<ImageId>, current: &Option<ImageId>) -> Option<ImageId> {
     match action {
         FieldAction::Unchanged => current.clone(),
@@ -1,6 +1,7 @@
 use crate::dto::account::UpdateAccountDto;
 use kernel::prelude::entity::{
-    FieldAction, MetadataContent, MetadataLabel, ProfileDisplayName, ProfileSummary,
+    FieldAction, ImageDescription, ImageFocus, MetadataContent, MetadataLabel, ProfileDisplayName,
+    ProfileSummary,
 };
 use kernel::KernelError;
 
@@ -13,6 +14,16 @@ pub(super) fn validate_update_account_dto(
     if let FieldAction::Set(value) = &dto.summary {
         ProfileSummary::new(value.as_str()).validate()?;
     }
+    for description in [&dto.icon_description, &dto.banner_description] {
+        if let FieldAction::Set(value) = description {
+            ImageDescription::new(value.as_str()).validate()?;
+        }
+    }
+    for focus in [&dto.icon_focus, &dto.banner_focus] {
+        if let FieldAction::Set(value) = focus {
+            ImageFocus::from(*value).validate()?;
+        }
+    }
     if let Some(fields) = &dto.fields {
         for field in fields {
             MetadataLabel::new(field.label.as_str()).validate()?;
@@ -26,6 +37,7 @@ pub(super) fn validate_update_account_dto(
 mod tests {
     use super::*;
     use crate::dto::account::AccountFieldDto;
+    use crate::dto::media::ImageFocusDto;
 
     fn dto_with(
         display_name: FieldAction<String>,
@@ -39,6 +51,10 @@ mod tests {
             summary,
             icon_url: FieldAction::Unchanged,
             banner_url: FieldAction::Unchanged,
+            icon_description: FieldAction::Unchanged,
+            icon_focus: FieldAction::Unchanged,
+            banner_description: FieldAction::Unchanged,
+            banner_focus: FieldAction::Unchanged,
             fields,
         }
     }
@@ -115,4 +131,15 @@ mod tests {
         assert!(result.is_err());
         assert!(format!("{:?}", result).contains("Field content must not exceed"));
     }
+
+    #[test]
+    fn validate_rejects_image_metadata_out_of_bounds() {
+        let mut dto = dto_with(FieldAction::Unchanged, FieldAction::Unchanged, None);
+        dto.icon_description = FieldAction::Set("a".repeat(ImageDescription::MAX_LENGTH + 1));
+        assert!(validate_update_account_dto(&dto).is_err());
+
+        let mut dto = dto_with(FieldAction::Unchanged, FieldAction::Unchanged, None);
+        dto.banner_focus = FieldAction::Set(ImageFocusDto { x: 0.0, y: 2.0 });
+        assert!(validate_update_account_dto(&dto).is_err());
+    }
 }
@@ -1,6 +1,9 @@
 use crate::dto::activitypub::{GetActorDto, GetWebFingerDto};
+use crate::dto::media::ImageRenditionDto;
 use error_stack::Report;
-use kernel::activitypub::{Actor, ActorImages, ActorUrlBuilder, WebFingerLink, WebFingerResponse};
+use kernel::activitypub::{
+    Actor, ActorImages, ActorUrlBuilder, ImageObject, WebFingerLink, WebFingerResponse,
+};
 use kernel::interfaces::config::DependOnPublicBaseUrl;
 use kernel::interfaces::database::DatabaseConnection;
 use kernel::interfaces::read_model::{AccountQuery, DependOnAccountQuery};
@@ -8,7 +11,7 @@ use kernel::interfaces::read_model::{DependOnProfileReadModel, ProfileReadModel}
 use kernel::interfaces::repository::{
     DependOnImageRepository, DependOnSigningKeyRepository, ImageRepository, SigningKeyRepository,
 };
-use kernel::prelude::entity::{Account, AccountName, ImageId, Nanoid};
+use kernel::prelude::entity::{Account, AccountName, ImageId, ImageVariantKind, Nanoid};
 use kernel::KernelError;
 use std::future::Future;
 
@@ -61,7 +64,7 @@ pub trait GetActorUseCase:
                 .as_ref()
                 .and_then(|profile| profile.summary().as_ref())
                 .map(|summary| summary.as_ref().to_string());
-            let image_ids: Vec<ImageId> = profile
+            let references: Vec<_> = profile
                 .as_ref()
                 .into_iter()
                 .flat_map(|profile| {
@@ -69,37 +72,34 @@ pub trait GetActorUseCase:
                         .icon()
                         .iter()
                         .chain(profile.banner().iter())
-                        .cloned()
+                        .map(|image_id| (account.id().clone(), image_id.clone()))
                 })
                 .collect();
             let images = self
                 .image_repository()
-                .find_by_ids(&mut executor, &image_ids)
+                .find_profile_images(&mut executor, &references)
                 .await?;
-            let image_url = |id: Option<&ImageId>| {
+            let rendition = |id: Option<&ImageId>, kind: ImageVariantKind| {
                 id.and_then(|id| {
                     images
                         .iter()
-                        .find(|image| image.id() == id)
-                        .map(|image| image.url().as_ref())
+                        .find(|(_, image)| image.id() == id)
+                        .map(|(_, image)| image_object(ImageRenditionDto::of(image, kind)))
                 })
             };
-            let icon_url = profile
+            let icon = profile
                 .as_ref()
-                .and_then(|profile| image_url(profile.icon().as_ref()));
-            let banner_url = profile
+                .and_then(|profile| rendition(profile.icon().as_ref(), ImageVariantKind::Avatar));
+            let banner = profile
                 .as_ref()
-                .and_then(|profile| image_url(profile.banner().as_ref()));
+                .and_then(|profile| rendition(profile.banner().as_ref(), ImageVariantKind::Header));
 
             Ok(Actor::new(
                 &ActorUrlBuilder::new(self.public_base_url().as_str(), account.nanoid().as_ref()),
                 account.name().as_ref(),
                 display_name.as_deref(),
                 summary.as_deref(),
-                &ActorImages {
-                    icon_url: icon_url.map(String::as_str),
-                    banner_url: banner_url.map(String::as_str),
-                },
+                ActorImages { icon, banner },
                 &signing_key.public_key_pem,
                 &signing_key.key_id_uri,
             ))
@@ -119,6 +119,19 @@ impl<T> GetActorUseCase for T where
 {
 }
 
+fn image_object(rendition: ImageRenditionDto) -> ImageObject {
+    let object = match (rendition.media_type, rendition.width, rendition.height) {
+        (Some(media_type), Some(width), Some(height)) => {
+            ImageObject::sized(&rendition.url, &media_type, width, height)
+        }
+        _ => ImageObject::new(&rendition.url),
+    };
+    object.described(
+        rendition.description.as_deref(),
+        rendition.focus.map(|focus| (focus.x, focus.y)),
+    )
+}
+
 pub trait GetWebFingerUseCase:
     'static + Sync + Send + DependOnAccountQuery + DependOnPublicBaseUrl
 {
@@ -1,5 +1,6 @@
 use super::fetch::{client_for_url, validate_fetch_url};
 use super::ACTIVITY_JSON;
+use crate::telemetry::OUTBOX_DELIVERIES_TOTAL;
 use base64::{engine::general_purpose, Engine as _};
 use error_stack::Report;
 use kernel::activitypub::Activity;
@@ -12,6 +13,7 @@ use kernel::interfaces::repository::{DependOnSigningKeyRepository, SigningKeyRep
 use kernel::prelude::entity::AccountId;
 use kernel::KernelError;
 use reqwest::header::{CONTENT_TYPE, DATE, HOST};
+use reqwest::StatusCode;
 use sha2::Digest;
 
 fn host_header(url: &reqwest::Url) -> error_stack::Result<String, KernelError> {
@@ -31,6 +33,55 @@ pub(super) async fn deliver_activity_to_inbox<T>(
     activity: &Activity,
     activity_name: &str,
 ) -> error_stack::Result<(), KernelError>
+where
+    T: DependOnSigningKeyRepository
+        + DependOnPasswordProvider
+        + DependOnKeyEncryptor
+        + DependOnHttpSigner
+        + ?Sized,
+{
+    let (outcome, result) = match send_activity_to_inbox(
+        module,
+        account_id,
+        inbox_url,
+        activity,
+        activity_name,
+    )
+    .await
+    {
+        Ok(status) if status.is_success() => ("delivered", Ok(())),
+        Ok(status) => (
+            "rejected",
+            Err(Report::new(KernelError::Rejected)
+                .attach_printable(format!("{activity_name} delivery returned {status}"))),
+        ),
+        Err(error) => ("failed", Err(error)),
+    };
+    metrics::counter!(
+        OUTBOX_DELIVERIES_TOTAL,
+        "outcome" => outcome,
+        "peer" => peer_label(inbox_url)
+    )
+    .increment(1);
+    result
+}
+
+/// Host of the remote inbox, the `peer` label of delivery metrics.
+fn peer_label(inbox_url: &str) -> String {
+    reqwest::Url::parse(inbox_url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_ascii_lowercase))
+        .unwrap_or_else(|| "invalid".to_string())
+}
+
+/// Sign and POST the activity, returning the status the peer answered with.
+async fn send_activity_to_inbox<T>(
+    module: &T,
+    account_id: &AccountId,
+    inbox_url: &str,
+    activity: &Activity,
+    activity_name: &str,
+) -> error_stack::Result<StatusCode, KernelError>
 where
     T: DependOnSigningKeyRepository
         + DependOnPasswordProvider
@@ -112,11 +163,5 @@ where
         Report::new(KernelError::Rejected)
             .attach_printable(format!("{activity_name} delivery failed: {e}"))
     })?;
-    if !response.status().is_success() {
-        return Err(Report::new(KernelError::Rejected).attach_printable(format!(
-            "{activity_name} delivery returned {}",
-            response.status()
-        )));
-    }
-    Ok(())
+    Ok(response.status())
 }
@@ -2,7 +2,7 @@ use error_stack::Report;
 use kernel::KernelError;
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 
-pub(super) async fn validate_fetch_url(
+pub(crate) async fn validate_fetch_url(
     url: &reqwest::Url,
 ) -> error_stack::Result<Vec<SocketAddr>, KernelError> {
     match url.scheme() {
@@ -60,7 +60,7 @@ pub(super) async fn validate_fetch_url(
     Ok(addresses)
 }
 
-pub(super) fn client_for_url(
+pub(crate) fn client_for_url(
     url: &reqwest::Url,
     resolved_addresses: &[SocketAddr],
 ) -> error_stack::Result<reqwest::Client, KernelError> {
@@ -666,6 +666,22 @@ mod tests {
             Ok(())
         }
 
+        async fn find_pending_media(
+            &self,
+            _executor: &mut Self::Connection,
+            _limit: usize,
+        ) -> error_stack::Result<Vec<RemoteAccount>, KernelError> {
+            Ok(Vec::new())
+        }
+
+        async fn update_cached_media(
+            &self,
+            _executor: &mut Self::Connection,
+            _account: &RemoteAccount,
+        ) -> error_stack::Result<bool, KernelError> {
+            Ok(false)
+        }
+
         async fn delete(
             &self,
             _executor: &mut Self::Connection,
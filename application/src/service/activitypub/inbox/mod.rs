@@ -2,6 +2,7 @@ mod handlers;
 
 use super::outbox::{DeliverOutboxActivityUseCase, StoreOutboxActivityUseCase};
 use crate::dto::activitypub::InboxActivityDto;
+use crate::telemetry::{activity_type_label, failure_outcome, INBOX_ACTIVITIES_TOTAL};
 use kernel::activitypub::Activity;
 use kernel::interfaces::config::DependOnPublicBaseUrl;
 use kernel::interfaces::crypto::{DependOnKeyEncryptor, DependOnPasswordProvider};
@@ -38,7 +39,8 @@ pub trait InboxUseCase:
         dto: InboxActivityDto,
     ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send {
         async move {
-            match dto.activity.type_.as_str() {
+            let type_label = activity_type_label(&dto.activity.type_);
+            let result = match dto.activity.type_.as_str() {
                 "Follow" => self.handle_follow_activity(dto).await,
                 "Accept" => self.handle_accept_activity(dto).await,
                 "Block" => self.handle_block_activity(dto).await,
@@ -53,9 +55,22 @@ pub trait InboxUseCase:
                         activity_type,
                         "Ignoring unsupported ActivityPub inbox activity"
                     );
-                    Ok(())
+                    metrics::counter!(
+                        INBOX_ACTIVITIES_TOTAL,
+                        "type" => type_label,
+                        "outcome" => "ignored"
+                    )
+                    .increment(1);
+                    return Ok(());
                 }
-            }
+            };
+            let outcome = match &result {
+                Ok(()) => "processed",
+                Err(error) => failure_outcome(error.current_context()),
+            };
+            metrics::counter!(INBOX_ACTIVITIES_TOTAL, "type" => type_label, "outcome" => outcome)
+                .increment(1);
+            result
         }
     }
 
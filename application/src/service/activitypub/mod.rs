@@ -1,7 +1,7 @@
 mod actor;
 mod collections;
 mod delivery;
-mod fetch;
+pub(crate) mod fetch;
 mod inbox;
 pub(crate) mod outbound_block;
 mod outbound_follow;
@@ -9,6 +9,7 @@ mod outbound_unfollow;
 mod outbox;
 pub mod relations;
 pub(crate) mod remote_actor;
+mod remote_media;
 mod update_person;
 
 use kernel::activitypub::ActorUrlBuilder;
@@ -23,6 +24,7 @@ pub use outbox::{DeliverOutboxActivityUseCase, GetOutboxUseCase, StoreOutboxActi
 pub use relations::GetFollowRelationsUseCase;
 #[cfg(any(test, feature = "test-mode"))]
 pub use remote_actor::inject_test_remote_actor;
+pub use remote_media::CacheRemoteMedia;
 pub use update_person::DeliverUpdatePersonUseCase;
 
 pub(super) const ACTIVITY_JSON: &str = "application/activity+json";
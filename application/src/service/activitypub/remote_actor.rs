@@ -8,6 +8,7 @@ use kernel::prelude::entity::{
 };
 use kernel::KernelError;
 use reqwest::header::{ACCEPT, USER_AGENT};
+use time::OffsetDateTime;
 
 #[derive(Debug)]
 pub(crate) struct ResolvedRemoteActor {
@@ -15,8 +16,14 @@ pub(crate) struct ResolvedRemoteActor {
     url: RemoteAccountUrl,
     inbox_url: Option<String>,
     public_key_pem: Option<String>,
+    icon_url: Option<String>,
+    banner_url: Option<String>,
 }
 
+/// After a media fetch failed or its copies were evicted, activity from the
+/// actor triggers another fetch only once this much time has passed.
+const MEDIA_REFETCH_INTERVAL: time::Duration = time::Duration::hours(6);
+
 /// Test-only: global cache of resolved remote actor data.
 ///
 /// `resolve_remote_actor` checks this before making an HTTP request.
@@ -54,6 +61,8 @@ pub fn inject_test_remote_actor(
             url: RemoteAccountUrl::new(actor_id_url.to_string()),
             inbox_url: Some(inbox_url.to_string()),
             public_key_pem: Some(public_key_pem.to_string()),
+            icon_url: None,
+            banner_url: None,
         },
     );
 }
@@ -71,6 +80,8 @@ pub(super) async fn resolve_remote_actor(
                 url: RemoteAccountUrl::new(cached.url.as_ref().clone()),
                 inbox_url: cached.inbox_url.clone(),
                 public_key_pem: cached.public_key_pem.clone(),
+                icon_url: cached.icon_url.clone(),
+                banner_url: cached.banner_url.clone(),
             });
         }
     }
@@ -151,6 +162,8 @@ pub(super) async fn resolve_remote_actor(
         url: RemoteAccountUrl::new(actor.id),
         inbox_url: Some(actor.inbox),
         public_key_pem: Some(actor.public_key.public_key_pem),
+        icon_url: actor.icon.and_then(|icon| icon.url),
+        banner_url: actor.image.and_then(|image| image.url),
     })
 }
 
@@ -266,13 +279,23 @@ where
     E: kernel::interfaces::database::Connection,
 {
     if let Some(existing) = repository.find_by_url(executor, &actor.url).await? {
+        let media_fetched_at = media_fetched_at(
+            &existing,
+            &actor.icon_url,
+            &actor.banner_url,
+            OffsetDateTime::now_utc(),
+        );
         let updated = RemoteAccount::new(
             existing.id().clone(),
             actor.acct,
             actor.url,
             existing.icon_id().clone(),
+            existing.banner_id().clone(),
+            actor.icon_url,
+            actor.banner_url,
             actor.inbox_url,
             actor.public_key_pem,
+            media_fetched_at,
         );
         repository.update(executor, &updated).await?;
         return Ok(updated);
@@ -283,13 +306,76 @@ where
         actor.acct,
         actor.url,
         None,
+        None,
+        actor.icon_url,
+        actor.banner_url,
         actor.inbox_url,
         actor.public_key_pem,
+        None,
     );
     repository.create(executor, &remote_account).await?;
     Ok(remote_account)
 }
 
+/// Keep the last fetch time unless the cached copies need a new fetch:
+/// the actor published other images, or a copy is missing (the fetch failed
+/// or the copy was evicted) and the refetch interval has passed.
+fn media_fetched_at(
+    existing: &RemoteAccount,
+    icon_url: &Option<String>,
+    banner_url: &Option<String>,
+    now: OffsetDateTime,
+) -> Option<OffsetDateTime> {
+    let fetched_at = (*existing.media_fetched_at())?;
+    if existing.icon_source_url() != icon_url || existing.banner_source_url() != banner_url {
+        return None;
+    }
+    let copy_missing = (icon_url.is_some() && existing.icon_id().is_none())
+        || (banner_url.is_some() && existing.banner_id().is_none());
+    if copy_missing && fetched_at < now - MEDIA_REFETCH_INTERVAL {
+        return None;
+    }
+    Some(fetched_at)
+}
+
 fn same_activitypub_id(left: &str, right: &str) -> bool {
     left.trim_end_matches('/') == right.trim_end_matches('/')
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kernel::prelude::entity::ImageId;
+    use kernel::test_utils::RemoteAccountBuilder;
+
+    const ICON: &str = "https://remote.example/icon.png";
+
+    #[test]
+    fn media_is_refetched_when_sources_change_or_copies_go_missing() {
+        let now = OffsetDateTime::now_utc();
+        let recent = now - time::Duration::minutes(5);
+        let stale = now - MEDIA_REFETCH_INTERVAL - time::Duration::minutes(5);
+        let cached = RemoteAccountBuilder::new()
+            .icon_id(Some(ImageId::new(1)))
+            .icon_source_url(Some(ICON))
+            .media_fetched_at(Some(recent))
+            .build();
+        let icon = Some(ICON.to_string());
+        assert_eq!(media_fetched_at(&cached, &icon, &None, now), Some(recent));
+        let other = Some("https://remote.example/new.png".to_string());
+        assert_eq!(media_fetched_at(&cached, &other, &None, now), None);
+        assert_eq!(media_fetched_at(&cached, &None, &None, now), None);
+
+        let failed = |fetched_at| {
+            RemoteAccountBuilder::new()
+                .icon_source_url(Some(ICON))
+                .media_fetched_at(Some(fetched_at))
+                .build()
+        };
+        assert_eq!(
+            media_fetched_at(&failed(recent), &icon, &None, now),
+            Some(recent)
+        );
+        assert_eq!(media_fetched_at(&failed(stale), &icon, &None, now), None);
+    }
+}
@@ -0,0 +1,284 @@
+use super::fetch::{client_for_url, validate_fetch_url};
+use crate::dto::media::UploadImageDto;
+use crate::service::media::{
+    delete_stored_objects, prepare_image, scan_image, sniffed_media_type, store_image,
+    MAX_IMAGE_BYTES,
+};
+use error_stack::Report;
+use kernel::interfaces::database::DatabaseConnection;
+use kernel::interfaces::repository::{
+    DependOnImageRepository, DependOnRemoteAccountRepository, ImageRepository,
+    RemoteAccountRepository,
+};
+use kernel::interfaces::storage::{
+    DependOnImageScanner, DependOnImageStorage, ImageScanner, ImageStorage,
+};
+use kernel::prelude::entity::{Image, ImageId, RemoteAccount};
+use kernel::KernelError;
+use reqwest::header::{ACCEPT, CONTENT_LENGTH, LOCATION, USER_AGENT};
+use std::future::Future;
+use time::OffsetDateTime;
+
+/// Remote accounts whose media is fetched per pass.
+const REMOTE_MEDIA_BATCH_LIMIT: usize = 20;
+/// Cached images evicted per pass.
+const REMOTE_MEDIA_EVICTION_LIMIT: usize = 100;
+const MAX_REDIRECTS: usize = 5;
+
+/// Keeps local copies of remote actors' icons and banners, so clients never
+/// load them from the peer: the peer does not learn their addresses and the
+/// images survive the peer going down. Copies go through the same
+/// validation and processing as uploads.
+pub trait CacheRemoteMedia:
+    Sync
+    + Send
+    + DependOnRemoteAccountRepository
+    + DependOnImageRepository
+    + DependOnImageStorage
+    + DependOnImageScanner
+{
+    /// Fetch the media of one batch of accounts marked for a fetch. Images
+    /// that cannot be fetched keep their previous copy until the next one.
+    /// Returns the number of accounts processed.
+    fn cache_remote_media(
+        &self,
+    ) -> impl Future<Output = error_stack::Result<usize, KernelError>> + Send {
+        async move {
+            let mut executor = self.database_connection().connection().await?;
+            let pending = self
+                .remote_account_repository()
+                .find_pending_media(&mut executor, REMOTE_MEDIA_BATCH_LIMIT)
+                .await?;
+            let processed = pending.len();
+            for account in pending {
+                let icon = fetch_and_store(
+                    self.image_storage(),
+                    self.image_scanner(),
+                    account.icon_source_url(),
+                )
+                .await;
+                let banner = fetch_and_store(
+                    self.image_storage(),
+                    self.image_scanner(),
+                    account.banner_source_url(),
+                )
+                .await;
+                let fetched: Vec<&Image> = icon.iter().chain(banner.iter()).collect();
+                for image in &fetched {
+                    self.image_repository().create(&mut executor, image).await?;
+                }
+                let cached = RemoteAccount::new(
+                    account.id().clone(),
+                    account.acct().clone(),
+                    account.url().clone(),
+                    cached_id(account.icon_source_url(), &icon, account.icon_id()),
+                    cached_id(account.banner_source_url(), &banner, account.banner_id()),
+                    account.icon_source_url().clone(),
+                    account.banner_source_url().clone(),
+                    account.inbox_url().clone(),
+                    account.public_key_pem().clone(),
+                    Some(OffsetDateTime::now_utc()),
+                );
+                if !self
+                    .remote_account_repository()
+                    .update_cached_media(&mut executor, &cached)
+                    .await?
+                {
+                    // The actor changed its images meanwhile; the account is
+                    // pending again and these copies are already outdated.
+                    for image in fetched {
+                        self.image_repository()
+                            .delete(&mut executor, image.id())
+                            .await?;
+                        delete_stored_objects(self.image_storage(), image).await;
+                    }
+                }
+            }
+            Ok(processed)
+        }
+    }
+
+    /// Evict the copies of accounts not seen for `max_age`, then the least
+    /// recently seen ones until the cache fits in `max_total_bytes`. An
+    /// evicted account fetches its media again the next time it is seen.
+    /// Returns the number of images evicted.
+    fn evict_remote_media(
+        &self,
+        max_age: time::Duration,
+        max_total_bytes: u64,
+    ) -> impl Future<Output = error_stack::Result<usize, KernelError>> + Send {
+        async move {
+            let mut executor = self.database_connection().connection().await?;
+            let evictions = self
+                .image_repository()
+                .find_remote_cache_evictions(
+                    &mut executor,
+                    OffsetDateTime::now_utc() - max_age,
+                    max_total_bytes,
+                    REMOTE_MEDIA_EVICTION_LIMIT,
+                )
+                .await?;
+            for image in &evictions {
+                self.image_repository()
+                    .delete(&mut executor, image.id())
+                    .await?;
+                delete_stored_objects(self.image_storage(), image).await;
+            }
+            Ok(evictions.len())
+        }
+    }
+}
+
+impl<T> CacheRemoteMedia for T where
+    T: Sync
+        + Send
+        + DependOnRemoteAccountRepository
+        + DependOnImageRepository
+        + DependOnImageStorage
+        + DependOnImageScanner
+{
+}
+
+/// The fresh copy if there is one, otherwise the previous copy as long as
+/// the actor still publishes an image in that slot.
+fn cached_id(
+    source_url: &Option<String>,
+    fetched: &Option<Image>,
+    previous: &Option<ImageId>,
+) -> Option<ImageId> {
+    source_url.as_ref()?;
+    fetched
+        .as_ref()
+        .map(|image| image.id().clone())
+        .or_else(|| previous.clone())
+}
+
+/// Remote media goes through the scanner like uploads; a copy the scanner
+/// would reject or quarantine is simply not cached.
+async fn fetch_and_store<S: ImageStorage, C: ImageScanner>(
+    storage: &S,
+    scanner: &C,
+    source_url: &Option<String>,
+) -> Option<Image> {
+    let source_url = source_url.as_deref()?;
+    let result = async {
+        let dto = fetch_remote_image(source_url).await?;
+        let processed = prepare_image(dto, MAX_IMAGE_BYTES).await?;
+        if scan_image(scanner, &processed).await? {
+            return Err(Report::new(KernelError::Rejected)
+                .attach_printable("Remote media is quarantined by the image scanner"));
+        }
+        store_image(storage, processed).await
+    }
+    .await;
+    match result {
+        Ok(image) => Some(image),
+        Err(error) => {
+            tracing::warn!(?error, source_url, "Failed to cache remote media");
+            None
+        }
+    }
+}
+
+/// Download an image through the SSRF-hardened client, following redirects
+/// (each target is validated again) and reading at most `MAX_IMAGE_BYTES`.
+/// The media type is taken from the bytes, never from the peer's headers.
+async fn fetch_remote_image(source_url: &str) -> error_stack::Result<UploadImageDto, KernelError> {
+    let mut url = reqwest::Url::parse(source_url).map_err(|e| {
+        Report::new(KernelError::Rejected).attach_printable(format!("Invalid media URL: {e}"))
+    })?;
+    let mut redirects = 0;
+    let mut response = loop {
+        if redirects > MAX_REDIRECTS {
+            return Err(Report::new(KernelError::Rejected)
+                .attach_printable("Too many redirects while fetching remote media"));
+        }
+        let resolved_addresses = validate_fetch_url(&url).await?;
+        let response = client_for_url(&url, &resolved_addresses)?
+            .get(url.clone())
+            .header(ACCEPT, "image/*")
+            .header(USER_AGENT, "Emumet/0.1 media fetcher")
+            .send()
+            .await
+            .map_err(|e| {
+                Report::new(KernelError::Rejected)
+                    .attach_printable(format!("Remote media fetch failed: {e}"))
+            })?;
+        if response.status().is_redirection() {
+            let location = response
+                .headers()
+                .get(LOCATION)
+                .and_then(|value| value.to_str().ok())
+                .ok_or_else(|| {
+                    Report::new(KernelError::Rejected)
+                        .attach_printable("Remote media redirect without Location header")
+                })?;
+            url = url.join(location).map_err(|e| {
+                Report::new(KernelError::Rejected)
+                    .attach_printable(format!("Remote media redirect URL is invalid: {e}"))
+            })?;
+            redirects += 1;
+            continue;
+        }
+        if !response.status().is_success() {
+            return Err(Report::new(KernelError::Rejected).attach_printable(format!(
+                "Remote media endpoint returned {}",
+                response.status()
+            )));
+        }
+        break response;
+    };
+
+    let too_large = || {
+        Report::new(KernelError::Rejected)
+            .attach_printable(format!("Remote media exceeds {MAX_IMAGE_BYTES} bytes"))
+    };
+    let declared_length = response
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<usize>().ok());
+    if declared_length.is_some_and(|length| length > MAX_IMAGE_BYTES) {
+        return Err(too_large());
+    }
+    let mut bytes = Vec::with_capacity(declared_length.unwrap_or(0));
+    while let Some(chunk) = response.chunk().await.map_err(|e| {
+        Report::new(KernelError::Rejected)
+            .attach_printable(format!("Remote media download failed: {e}"))
+    })? {
+        if bytes.len() + chunk.len() > MAX_IMAGE_BYTES {
+            return Err(too_large());
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    let content_type = sniffed_media_type(&bytes).ok_or_else(|| {
+        Report::new(KernelError::Rejected)
+            .attach_printable("Remote media is not a supported image format")
+    })?;
+    Ok(UploadImageDto {
+        content_type: content_type.to_string(),
+        bytes,
+        description: None,
+        focus: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::cached_id;
+    use kernel::prelude::entity::ImageId;
+    use kernel::test_utils::ImageBuilder;
+
+    #[test]
+    fn failed_fetches_keep_the_previous_copy_while_the_slot_is_published() {
+        let source = Some("https://remote.example/icon.png".to_string());
+        let previous = Some(ImageId::new(1));
+        let fresh = ImageBuilder::new().build();
+        assert_eq!(
+            cached_id(&source, &Some(fresh.clone()), &previous),
+            Some(fresh.id().clone())
+        );
+        assert_eq!(cached_id(&source, &None, &previous), previous);
+        assert_eq!(cached_id(&None, &None, &previous), None);
+    }
+}
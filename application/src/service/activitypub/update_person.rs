@@ -133,7 +133,7 @@ impl<T> DeliverUpdatePersonUseCase for T where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use kernel::activitypub::{ActorImages, ActorUrlBuilder};
+    use kernel::activitypub::{ActorImages, ActorUrlBuilder, ImageObject};
 
     #[test]
     fn update_person_activity_wraps_actor_as_public_update() {
@@ -143,9 +143,9 @@ mod tests {
             "alice",
             Some("Alice"),
             None,
-            &ActorImages {
-                icon_url: Some("https://media.example/avatar.png"),
-                banner_url: Some("https://media.example/banner.png"),
+            ActorImages {
+                icon: Some(ImageObject::new("https://media.example/avatar.png")),
+                banner: Some(ImageObject::new("https://media.example/banner.png")),
             },
             "pem-content",
             "https://local.example/ap/accounts/alice#main-key",
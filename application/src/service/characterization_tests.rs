@@ -9,6 +9,7 @@ use super::mute::{MuteAccountUseCase, UnmuteAccountUseCase};
 use crate::dto::account::{AccountFieldDto, CreateAccountDto, UpdateAccountDto};
 use crate::dto::activitypub::InboxActivityDto;
 use crate::dto::block_mute::{BlockAccountDto, MuteAccountDto};
+use crate::permission::without_scope_restriction;
 use crate::projection::{ProjectMetadataBatch, ProjectProfileBatch};
 use driver::crypto::{Argon2Encryptor, FilePasswordProvider, Rsa2048RawGenerator};
 use driver::database::PostgresDatabase;
@@ -68,6 +69,27 @@ impl PermissionChecker for AllowPermissions {
     ) -> error_stack::Result<Vec<InstanceRole>, KernelError> {
         Ok(Vec::new())
     }
+
+    async fn list_account_members(
+        &self,
+        _account_id: &kernel::prelude::entity::AccountId,
+    ) -> error_stack::Result<Vec<kernel::interfaces::permission::AccountMember>, KernelError> {
+        Ok(Vec::new())
+    }
+
+    async fn list_instance_role_holders(
+        &self,
+    ) -> error_stack::Result<Vec<kernel::interfaces::permission::InstanceRoleHolder>, KernelError>
+    {
+        Ok(Vec::new())
+    }
+
+    async fn list_custom_role_members(
+        &self,
+        _role_id: &kernel::prelude::entity::CustomRoleId,
+    ) -> error_stack::Result<Vec<AuthAccountId>, KernelError> {
+        Ok(Vec::new())
+    }
 }
 
 impl PermissionWriter for AllowPermissions {
@@ -95,7 +117,7 @@ impl PermissionWriter for AllowPermissions {
                     .unwrap()
                     .push(observed);
             }
-            RelationTarget::Instance { .. } => {}
+            RelationTarget::Instance { .. } | RelationTarget::CustomRole { .. } => {}
         }
         Ok(())
     }
@@ -108,6 +130,20 @@ impl PermissionWriter for AllowPermissions {
         self.deletes.fetch_add(1, Ordering::Relaxed);
         Ok(())
     }
+
+    async fn create_role_grant(
+        &self,
+        _grant: &kernel::interfaces::permission::RoleGrant,
+    ) -> error_stack::Result<(), KernelError> {
+        Ok(())
+    }
+
+    async fn delete_role_grant(
+        &self,
+        _grant: &kernel::interfaces::permission::RoleGrant,
+    ) -> error_stack::Result<(), KernelError> {
+        Ok(())
+    }
 }
 
 #[derive(Clone)]
@@ -311,82 +347,89 @@ async fn create_account_rolls_back_all_database_writes_when_signing_key_creation
 #[test_with::env(DATABASE_URL)]
 #[tokio::test]
 async fn update_account_detail_commits_all_current_database_writes() {
-    // Given
-    kernel::ensure_generator_initialized();
-    let password_file = tempfile::NamedTempFile::new().unwrap();
-    std::fs::write(password_file.path(), b"characterization-password").unwrap();
-    let module = TestModule::new(password_file.path()).await;
-    let auth_account_id = AuthAccountId::default();
-    module.seed_auth_account(&auth_account_id).await;
-    let created = module
-        .create_account(
-            auth_account_id.clone(),
-            CreateAccountDto {
-                name: kernel::test_utils::unique_account_name()
-                    .as_ref()
-                    .to_string(),
-                is_bot: false,
-            },
-        )
-        .await
-        .unwrap();
-
-    // When
-    let updated = module
-        .update_account_detail(
-            &auth_account_id,
-            UpdateAccountDto {
-                account_nanoid: created.nanoid.clone(),
-                is_bot: FieldAction::Set(true),
-                display_name: FieldAction::Set("Updated display name".to_string()),
-                summary: FieldAction::Set("Updated summary".to_string()),
-                icon_url: FieldAction::Unchanged,
-                banner_url: FieldAction::Unchanged,
-                fields: Some(vec![AccountFieldDto {
-                    label: "site".to_string(),
-                    content: "https://example.com".to_string(),
-                }]),
-            },
-        )
-        .await
-        .unwrap();
+    without_scope_restriction(async {
+        // Given
+        kernel::ensure_generator_initialized();
+        let password_file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(password_file.path(), b"characterization-password").unwrap();
+        let module = TestModule::new(password_file.path()).await;
+        let auth_account_id = AuthAccountId::default();
+        module.seed_auth_account(&auth_account_id).await;
+        let created = module
+            .create_account(
+                auth_account_id.clone(),
+                CreateAccountDto {
+                    name: kernel::test_utils::unique_account_name()
+                        .as_ref()
+                        .to_string(),
+                    is_bot: false,
+                },
+            )
+            .await
+            .unwrap();
 
-    // Tailing projectors apply the profile and metadata events to the read models.
-    module.database.project_profile_batch().await.unwrap();
-    module.database.project_metadata_batch().await.unwrap();
+        // When
+        let updated = module
+            .update_account_detail(
+                &auth_account_id,
+                UpdateAccountDto {
+                    account_nanoid: created.nanoid.clone(),
+                    is_bot: FieldAction::Set(true),
+                    display_name: FieldAction::Set("Updated display name".to_string()),
+                    summary: FieldAction::Set("Updated summary".to_string()),
+                    icon_url: FieldAction::Unchanged,
+                    banner_url: FieldAction::Unchanged,
+                    icon_description: FieldAction::Unchanged,
+                    icon_focus: FieldAction::Unchanged,
+                    banner_description: FieldAction::Unchanged,
+                    banner_focus: FieldAction::Unchanged,
+                    fields: Some(vec![AccountFieldDto {
+                        label: "site".to_string(),
+                        content: "https://example.com".to_string(),
+                    }]),
+                },
+            )
+            .await
+            .unwrap();
 
-    // Then
-    assert!(updated.is_bot);
-    assert_eq!(
-        updated.display_name.as_deref(),
-        Some("Updated display name")
-    );
-    assert_eq!(updated.summary.as_deref(), Some("Updated summary"));
-    assert_eq!(
-        updated.fields,
-        vec![AccountFieldDto {
-            label: "site".to_string(),
-            content: "https://example.com".to_string(),
-        }]
-    );
-    let persisted: (bool, Option<String>, Option<String>, i64) = sqlx::query_as(
-        "SELECT a.is_bot, p.display, p.summary, \
+        // Tailing projectors apply the profile and metadata events to the read models.
+        module.database.project_profile_batch().await.unwrap();
+        module.database.project_metadata_batch().await.unwrap();
+
+        // Then
+        assert!(updated.is_bot);
+        assert_eq!(
+            updated.display_name.as_deref(),
+            Some("Updated display name")
+        );
+        assert_eq!(updated.summary.as_deref(), Some("Updated summary"));
+        assert_eq!(
+            updated.fields,
+            vec![AccountFieldDto {
+                label: "site".to_string(),
+                content: "https://example.com".to_string(),
+            }]
+        );
+        let persisted: (bool, Option<String>, Option<String>, i64) = sqlx::query_as(
+            "SELECT a.is_bot, p.display, p.summary, \
          (SELECT COUNT(*) FROM metadatas m WHERE m.account_id = a.id) \
          FROM accounts a JOIN profiles p ON p.account_id = a.id WHERE a.nanoid = $1",
-    )
-    .bind(&created.nanoid)
-    .fetch_one(&mut *module.database.connection().await.unwrap())
-    .await
-    .unwrap();
-    assert_eq!(
-        persisted,
-        (
-            true,
-            Some("Updated display name".to_string()),
-            Some("Updated summary".to_string()),
-            1
         )
-    );
+        .bind(&created.nanoid)
+        .fetch_one(&mut *module.database.connection().await.unwrap())
+        .await
+        .unwrap();
+        assert_eq!(
+            persisted,
+            (
+                true,
+                Some("Updated display name".to_string()),
+                Some("Updated summary".to_string()),
+                1
+            )
+        );
+    })
+    .await;
 }
 
 #[test_with::env(DATABASE_URL)]
@@ -423,6 +466,10 @@ async fn update_account_detail_rolls_back_account_changes_when_later_write_fails
                 summary: FieldAction::Unchanged,
                 icon_url: FieldAction::Set("https://example.com/missing.png".to_string()),
                 banner_url: FieldAction::Unchanged,
+                icon_description: FieldAction::Unchanged,
+                icon_focus: FieldAction::Unchanged,
+                banner_description: FieldAction::Unchanged,
+                banner_focus: FieldAction::Unchanged,
                 fields: None,
             },
         )
@@ -444,73 +491,76 @@ async fn update_account_detail_rolls_back_account_changes_when_later_write_fails
 #[test_with::env(DATABASE_URL)]
 #[tokio::test]
 async fn moderation_use_cases_preserve_current_event_sequence_and_post_commit_deprovisioning() {
-    // Given
-    kernel::ensure_generator_initialized();
-    let password_file = tempfile::NamedTempFile::new().unwrap();
-    std::fs::write(password_file.path(), b"characterization-password").unwrap();
-    let module = TestModule::new(password_file.path()).await;
-    let auth_account_id = AuthAccountId::default();
-    module.seed_auth_account(&auth_account_id).await;
-    let created = module
-        .create_account(
-            auth_account_id.clone(),
-            CreateAccountDto {
-                name: kernel::test_utils::unique_account_name()
-                    .as_ref()
-                    .to_string(),
-                is_bot: false,
-            },
-        )
-        .await
-        .unwrap();
+    without_scope_restriction(async {
+        // Given
+        kernel::ensure_generator_initialized();
+        let password_file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(password_file.path(), b"characterization-password").unwrap();
+        let module = TestModule::new(password_file.path()).await;
+        let auth_account_id = AuthAccountId::default();
+        module.seed_auth_account(&auth_account_id).await;
+        let created = module
+            .create_account(
+                auth_account_id.clone(),
+                CreateAccountDto {
+                    name: kernel::test_utils::unique_account_name()
+                        .as_ref()
+                        .to_string(),
+                    is_bot: false,
+                },
+            )
+            .await
+            .unwrap();
 
-    // When
-    module
-        .suspend_account(
-            &auth_account_id,
-            created.nanoid.clone(),
-            "spam".to_string(),
-            None,
-        )
-        .await
-        .unwrap();
-    module
-        .unsuspend_account(&auth_account_id, created.nanoid.clone())
-        .await
-        .unwrap();
-    module
-        .ban_account(
-            &auth_account_id,
-            created.nanoid.clone(),
-            "abuse".to_string(),
+        // When
+        module
+            .suspend_account(
+                &auth_account_id,
+                created.nanoid.clone(),
+                "spam".to_string(),
+                None,
+            )
+            .await
+            .unwrap();
+        module
+            .unsuspend_account(&auth_account_id, created.nanoid.clone())
+            .await
+            .unwrap();
+        module
+            .ban_account(
+                &auth_account_id,
+                created.nanoid.clone(),
+                "abuse".to_string(),
+            )
+            .await
+            .unwrap();
+        module
+            .deactivate_account(&auth_account_id, created.nanoid.clone())
+            .await
+            .unwrap();
+
+        // Then
+        let event_names: Vec<(String,)> = sqlx::query_as(
+            "SELECT event_name FROM account_events e JOIN accounts a ON a.id = e.id \
+         WHERE a.nanoid = $1 ORDER BY e.version",
         )
+        .bind(&created.nanoid)
+        .fetch_all(&mut *module.database.connection().await.unwrap())
         .await
         .unwrap();
-    module
-        .deactivate_account(&auth_account_id, created.nanoid.clone())
-        .await
-        .unwrap();
-
-    // Then
-    let event_names: Vec<(String,)> = sqlx::query_as(
-        "SELECT event_name FROM account_events e JOIN accounts a ON a.id = e.id \
-         WHERE a.nanoid = $1 ORDER BY e.version",
-    )
-    .bind(&created.nanoid)
-    .fetch_all(&mut *module.database.connection().await.unwrap())
-    .await
-    .unwrap();
-    assert_eq!(
-        event_names,
-        vec![
-            ("account_created".to_string(),),
-            ("account_suspended".to_string(),),
-            ("account_unsuspended".to_string(),),
-            ("account_banned".to_string(),),
-            ("account_deactivated".to_string(),),
-        ]
-    );
-    assert_eq!(module.permissions.deletes.load(Ordering::Relaxed), 3);
+        assert_eq!(
+            event_names,
+            vec![
+                ("account_created".to_string(),),
+                ("account_suspended".to_string(),),
+                ("account_unsuspended".to_string(),),
+                ("account_banned".to_string(),),
+                ("account_deactivated".to_string(),),
+            ]
+        );
+        assert_eq!(module.permissions.deletes.load(Ordering::Relaxed), 3);
+    })
+    .await;
 }
 
 async fn mute_test_module() -> (TestModule, tempfile::NamedTempFile, AuthAccountId) {
@@ -573,101 +623,113 @@ async fn outbox_activity_count(module: &TestModule, account_nanoid: &str) -> i64
 #[test_with::env(DATABASE_URL)]
 #[tokio::test]
 async fn mute_use_case_equivalence_preserves_state_effects() {
-    // Given
-    let (module, _password_file, auth_account_id) = mute_test_module().await;
-    let muter = create_test_account(&module, &auth_account_id).await;
-    let target = create_test_account(&module, &auth_account_id).await;
-
-    // When
-    let relation = module
-        .mute_account(
-            auth_account_id,
-            MuteAccountDto {
-                account_nanoid: muter.clone(),
-                target: target.clone(),
-            },
-        )
-        .await
-        .unwrap();
+    without_scope_restriction(async {
+        // Given
+        let (module, _password_file, auth_account_id) = mute_test_module().await;
+        let muter = create_test_account(&module, &auth_account_id).await;
+        let target = create_test_account(&module, &auth_account_id).await;
+
+        // When
+        let relation = module
+            .mute_account(
+                auth_account_id,
+                MuteAccountDto {
+                    account_nanoid: muter.clone(),
+                    target: target.clone(),
+                },
+            )
+            .await
+            .unwrap();
 
-    // Then
-    assert_eq!(relation.target_type, "local");
-    assert_eq!(relation.target, target);
-    let mutes = find_local_mutes(&module, &muter).await;
-    assert_eq!(mutes.len(), 1);
-    assert_eq!(relation.id, mutes[0].id().as_ref().to_string());
-    assert_eq!(outbox_activity_count(&module, &muter).await, 0);
+        // Then
+        assert_eq!(relation.target_type, "local");
+        assert_eq!(relation.target, target);
+        let mutes = find_local_mutes(&module, &muter).await;
+        assert_eq!(mutes.len(), 1);
+        assert_eq!(relation.id, mutes[0].id().as_ref().to_string());
+        assert_eq!(outbox_activity_count(&module, &muter).await, 0);
+    })
+    .await;
 }
 
 #[test_with::env(DATABASE_URL)]
 #[tokio::test]
 async fn mute_twice_is_ok_no_rejected() {
-    // Given
-    let (module, _password_file, auth_account_id) = mute_test_module().await;
-    let muter = create_test_account(&module, &auth_account_id).await;
-    let target = create_test_account(&module, &auth_account_id).await;
-    let dto = || MuteAccountDto {
-        account_nanoid: muter.clone(),
-        target: target.clone(),
-    };
-    module
-        .mute_account(auth_account_id.clone(), dto())
-        .await
-        .unwrap();
+    without_scope_restriction(async {
+        // Given
+        let (module, _password_file, auth_account_id) = mute_test_module().await;
+        let muter = create_test_account(&module, &auth_account_id).await;
+        let target = create_test_account(&module, &auth_account_id).await;
+        let dto = || MuteAccountDto {
+            account_nanoid: muter.clone(),
+            target: target.clone(),
+        };
+        module
+            .mute_account(auth_account_id.clone(), dto())
+            .await
+            .unwrap();
 
-    // When
-    let second = module.mute_account(auth_account_id, dto()).await;
+        // When
+        let second = module.mute_account(auth_account_id, dto()).await;
 
-    // Then
-    assert!(second.is_ok());
-    assert_eq!(find_local_mutes(&module, &muter).await.len(), 1);
+        // Then
+        assert!(second.is_ok());
+        assert_eq!(find_local_mutes(&module, &muter).await.len(), 1);
+    })
+    .await;
 }
 
 #[test_with::env(DATABASE_URL)]
 #[tokio::test]
 async fn unmute_missing_is_ok_no_not_found() {
-    // Given
-    let (module, _password_file, auth_account_id) = mute_test_module().await;
-    let muter = create_test_account(&module, &auth_account_id).await;
-    let target = create_test_account(&module, &auth_account_id).await;
-
-    // When
-    let result = module
-        .unmute_account(
-            auth_account_id,
-            MuteAccountDto {
-                account_nanoid: muter,
-                target,
-            },
-        )
-        .await;
+    without_scope_restriction(async {
+        // Given
+        let (module, _password_file, auth_account_id) = mute_test_module().await;
+        let muter = create_test_account(&module, &auth_account_id).await;
+        let target = create_test_account(&module, &auth_account_id).await;
+
+        // When
+        let result = module
+            .unmute_account(
+                auth_account_id,
+                MuteAccountDto {
+                    account_nanoid: muter,
+                    target,
+                },
+            )
+            .await;
 
-    // Then
-    assert!(result.is_ok());
+        // Then
+        assert!(result.is_ok());
+    })
+    .await;
 }
 
 #[test_with::env(DATABASE_URL)]
 #[tokio::test]
 async fn unmute_removes_mute() {
-    // Given
-    let (module, _password_file, auth_account_id) = mute_test_module().await;
-    let muter = create_test_account(&module, &auth_account_id).await;
-    let target = create_test_account(&module, &auth_account_id).await;
-    let dto = || MuteAccountDto {
-        account_nanoid: muter.clone(),
-        target: target.clone(),
-    };
-    module
-        .mute_account(auth_account_id.clone(), dto())
-        .await
-        .unwrap();
-    assert_eq!(find_local_mutes(&module, &muter).await.len(), 1);
+    without_scope_restriction(async {
+        // Given
+        let (module, _password_file, auth_account_id) = mute_test_module().await;
+        let muter = create_test_account(&module, &auth_account_id).await;
+        let target = create_test_account(&module, &auth_account_id).await;
+        let dto = || MuteAccountDto {
+            account_nanoid: muter.clone(),
+            target: target.clone(),
+        };
+        module
+            .mute_account(auth_account_id.clone(), dto())
+            .await
+            .unwrap();
+        assert_eq!(find_local_mutes(&module, &muter).await.len(), 1);
 
-    // When
-    module.unmute_account(auth_account_id, dto()).await.unwrap();
+        // When
+        module.unmute_account(auth_account_id, dto()).await.unwrap();
 
-    // Then
-    assert_eq!(find_local_mutes(&module, &muter).await.len(), 0);
+        // Then
+        assert_eq!(find_local_mutes(&module, &muter).await.len(), 0);
+    })
+    .await;
 }
 
 /// Wraps a real [`PostgresDatabase`] and forces every transaction to roll back
@@ -751,14 +813,21 @@ delegate_database_dependence! {
     kernel::interfaces::repository::DependOnAuthAccountRepository { AuthAccountRepository, auth_account_repository },
     kernel::interfaces::repository::DependOnAuthHostRepository { AuthHostRepository, auth_host_repository },
     kernel::interfaces::repository::DependOnBlockRepository { BlockRepository, block_repository },
+    kernel::interfaces::repository::DependOnCustomRoleRepository { CustomRoleRepository, custom_role_repository },
     kernel::interfaces::repository::DependOnFollowRepository { FollowRepository, follow_repository },
     kernel::interfaces::repository::DependOnImageRepository { ImageRepository, image_repository },
+    kernel::interfaces::repository::DependOnImageBlocklistRepository { ImageBlocklistRepository, image_blocklist_repository },
+    kernel::interfaces::repository::DependOnInstanceRoleChangeRepository { InstanceRoleChangeRepository, instance_role_change_repository },
     kernel::interfaces::repository::DependOnMetadataRepository { MetadataRepository, metadata_repository },
     kernel::interfaces::repository::DependOnMuteRepository { MuteRepository, mute_repository },
     kernel::interfaces::repository::DependOnOutboxActivityRepository { OutboxActivityRepository, outbox_activity_repository },
+    kernel::interfaces::repository::DependOnPersonalAccessTokenRepository { PersonalAccessTokenRepository, personal_access_token_repository },
     kernel::interfaces::repository::DependOnProfileRepository { ProfileRepository, profile_repository },
     kernel::interfaces::repository::DependOnRemoteAccountRepository { RemoteAccountRepository, remote_account_repository },
     kernel::interfaces::repository::DependOnSigningKeyRepository { SigningKeyRepository, signing_key_repository },
+    kernel::interfaces::repository::DependOnUploadSessionRepository { UploadSessionRepository, upload_session_repository },
+    kernel::interfaces::repository::DependOnWebhookDeliveryRepository { WebhookDeliveryRepository, webhook_delivery_repository },
+    kernel::interfaces::repository::DependOnWebhookSubscriptionRepository { WebhookSubscriptionRepository, webhook_subscription_repository },
     kernel::interfaces::projection::DependOnAccountEventLog { AccountEventLog, account_event_log },
     kernel::interfaces::projection::DependOnAccountProjectionWriter { AccountProjectionWriter, account_projection_writer },
     kernel::interfaces::projection::DependOnMetadataEventLog { MetadataEventLog, metadata_event_log },
@@ -976,34 +1045,37 @@ async fn block_write_counts(
 #[test_with::env(DATABASE_URL)]
 #[tokio::test]
 async fn block_use_case_equivalence_preserves_state_effects() {
-    // Given: a local blocker following (and followed by) a remote actor whose
-    // inbox is unreachable, so post-commit delivery fails but is tolerated
-    let (module, _password_file, auth_account_id) = mute_test_module().await;
-    let blocker = create_test_account(&module, &auth_account_id).await;
-    let (actor_url, remote_id) =
-        seed_remote_actor(&module.database, "http://127.0.0.1:1/inbox").await;
-    let blocker_id = account_id_of(&module.database, &blocker).await;
-    seed_follows_between(&module.database, blocker_id, remote_id).await;
-
-    // When
-    let relation = module
-        .block_account(
-            auth_account_id,
-            BlockAccountDto {
-                account_nanoid: blocker,
-                target: actor_url,
-            },
-        )
-        .await
-        .unwrap();
+    without_scope_restriction(async {
+        // Given: a local blocker following (and followed by) a remote actor whose
+        // inbox is unreachable, so post-commit delivery fails but is tolerated
+        let (module, _password_file, auth_account_id) = mute_test_module().await;
+        let blocker = create_test_account(&module, &auth_account_id).await;
+        let (actor_url, remote_id) =
+            seed_remote_actor(&module.database, "http://127.0.0.1:1/inbox").await;
+        let blocker_id = account_id_of(&module.database, &blocker).await;
+        seed_follows_between(&module.database, blocker_id, remote_id).await;
+
+        // When
+        let relation = module
+            .block_account(
+                auth_account_id,
+                BlockAccountDto {
+                    account_nanoid: blocker,
+                    target: actor_url,
+                },
+            )
+            .await
+            .unwrap();
 
-    // Then
-    assert_eq!(relation.target_type, "remote");
-    assert_eq!(
-        block_write_counts(&module.database, blocker_id, remote_id).await,
-        (1, 0, 1),
-        "block row persisted, both-direction follows removed, one Block outbox row"
-    );
+        // Then
+        assert_eq!(relation.target_type, "remote");
+        assert_eq!(
+            block_write_counts(&module.database, blocker_id, remote_id).await,
+            (1, 0, 1),
+            "block row persisted, both-direction follows removed, one Block outbox row"
+        );
+    })
+    .await;
 }
 
 #[test_with::env(DATABASE_URL)]
@@ -1051,230 +1123,245 @@ async fn block_use_case_rolls_back_all_writes_when_transaction_fails() {
 #[test_with::env(DATABASE_URL)]
 #[tokio::test]
 async fn block_delivery_failure_leaves_retryable_outbox_record() {
-    // Given: a remote actor whose inbox refuses connections
-    let (module, _password_file, auth_account_id) = mute_test_module().await;
-    let blocker = create_test_account(&module, &auth_account_id).await;
-    let (actor_url, remote_id) =
-        seed_remote_actor(&module.database, "http://127.0.0.1:1/inbox").await;
-    let blocker_id = account_id_of(&module.database, &blocker).await;
+    without_scope_restriction(async {
+        // Given: a remote actor whose inbox refuses connections
+        let (module, _password_file, auth_account_id) = mute_test_module().await;
+        let blocker = create_test_account(&module, &auth_account_id).await;
+        let (actor_url, remote_id) =
+            seed_remote_actor(&module.database, "http://127.0.0.1:1/inbox").await;
+        let blocker_id = account_id_of(&module.database, &blocker).await;
+
+        // When
+        let relation = module
+            .block_account(
+                auth_account_id,
+                BlockAccountDto {
+                    account_nanoid: blocker,
+                    target: actor_url,
+                },
+            )
+            .await
+            .unwrap();
 
-    // When
-    let relation = module
-        .block_account(
-            auth_account_id,
-            BlockAccountDto {
-                account_nanoid: blocker,
-                target: actor_url,
-            },
+        // Then: the operation succeeds, the block is committed, and the outbox
+        // row records the failed attempt without leaving the delivered read path
+        assert_eq!(relation.target_type, "remote");
+        assert_eq!(
+            block_write_counts(&module.database, blocker_id, remote_id)
+                .await
+                .0,
+            1
+        );
+        let attempt_state: (bool, bool, bool) = sqlx::query_as(
+            "SELECT delivered_at IS NULL, attempted_at IS NOT NULL, error IS NOT NULL \
+         FROM outbox_activities WHERE account_id = $1 AND activity_type = 'Block'",
         )
+        .bind(blocker_id)
+        .fetch_one(&mut *module.database.connection().await.unwrap())
         .await
         .unwrap();
-
-    // Then: the operation succeeds, the block is committed, and the outbox
-    // row records the failed attempt without leaving the delivered read path
-    assert_eq!(relation.target_type, "remote");
-    assert_eq!(
-        block_write_counts(&module.database, blocker_id, remote_id)
-            .await
-            .0,
-        1
-    );
-    let attempt_state: (bool, bool, bool) = sqlx::query_as(
-        "SELECT delivered_at IS NULL, attempted_at IS NOT NULL, error IS NOT NULL \
-         FROM outbox_activities WHERE account_id = $1 AND activity_type = 'Block'",
-    )
-    .bind(blocker_id)
-    .fetch_one(&mut *module.database.connection().await.unwrap())
-    .await
-    .unwrap();
-    assert_eq!(attempt_state, (true, true, true));
-    let account_id = AccountId::new(blocker_id);
-    let mut executor = module.database.connection().await.unwrap();
-    assert!(module
-        .outbox_activity_repository()
-        .find_by_account_id(&mut executor, &account_id, 10, None)
-        .await
-        .unwrap()
-        .is_empty());
-    assert_eq!(
-        module
+        assert_eq!(attempt_state, (true, true, true));
+        let account_id = AccountId::new(blocker_id);
+        let mut executor = module.database.connection().await.unwrap();
+        assert!(module
             .outbox_activity_repository()
-            .count_by_account_id(&mut executor, &account_id)
+            .find_by_account_id(&mut executor, &account_id, 10, None)
             .await
-            .unwrap(),
-        0
-    );
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            module
+                .outbox_activity_repository()
+                .count_by_account_id(&mut executor, &account_id)
+                .await
+                .unwrap(),
+            0
+        );
+    })
+    .await;
 }
 
 #[test_with::env(DATABASE_URL)]
 #[tokio::test]
 async fn block_delivery_occurs_after_db_commit() {
-    // Given: an in-test HTTP server acting as the remote inbox; it observes
-    // the database from a fresh connection before accepting the activity
-    let (module, _password_file, auth_account_id) = mute_test_module().await;
-    let blocker = create_test_account(&module, &auth_account_id).await;
-    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
-    let inbox_url = format!(
-        "http://127.0.0.1:{}/inbox",
-        listener.local_addr().unwrap().port()
-    );
-    let (actor_url, _remote_id) = seed_remote_actor(&module.database, &inbox_url).await;
-
-    let server = {
-        let blocker = blocker.clone();
-        let database = module.database.clone();
-        tokio::spawn(async move {
-            use tokio::io::AsyncWriteExt;
-
-            let (mut socket, _) = listener.accept().await.unwrap();
-            let request = read_http_request(&mut socket).await;
-            assert_eq!(
-                (request.method.as_str(), request.path.as_str()),
-                ("POST", "/inbox")
-            );
-            let activity: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
-            assert_eq!(activity["type"], "Block");
-            let block_count: i64 = sqlx::query_scalar(
-                "SELECT COUNT(*) FROM blocks b JOIN accounts a ON a.id = b.blocker_local_id \
+    without_scope_restriction(async {
+        // Given: an in-test HTTP server acting as the remote inbox; it observes
+        // the database from a fresh connection before accepting the activity
+        let (module, _password_file, auth_account_id) = mute_test_module().await;
+        let blocker = create_test_account(&module, &auth_account_id).await;
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let inbox_url = format!(
+            "http://127.0.0.1:{}/inbox",
+            listener.local_addr().unwrap().port()
+        );
+        let (actor_url, _remote_id) = seed_remote_actor(&module.database, &inbox_url).await;
+
+        let server = {
+            let blocker = blocker.clone();
+            let database = module.database.clone();
+            tokio::spawn(async move {
+                use tokio::io::AsyncWriteExt;
+
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let request = read_http_request(&mut socket).await;
+                assert_eq!(
+                    (request.method.as_str(), request.path.as_str()),
+                    ("POST", "/inbox")
+                );
+                let activity: serde_json::Value = serde_json::from_slice(&request.body).unwrap();
+                assert_eq!(activity["type"], "Block");
+                let block_count: i64 = sqlx::query_scalar(
+                    "SELECT COUNT(*) FROM blocks b JOIN accounts a ON a.id = b.blocker_local_id \
                  WHERE a.nanoid = $1",
+                )
+                .bind(&blocker)
+                .fetch_one(&mut *database.connection().await.unwrap())
+                .await
+                .unwrap();
+                assert_eq!(
+                    block_count, 1,
+                    "block row must be committed before the activity is delivered"
+                );
+                socket
+                    .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
+                    .await
+                    .unwrap();
+            })
+        };
+
+        // When
+        module
+            .block_account(
+                auth_account_id,
+                BlockAccountDto {
+                    account_nanoid: blocker.clone(),
+                    target: actor_url,
+                },
             )
-            .bind(&blocker)
-            .fetch_one(&mut *database.connection().await.unwrap())
             .await
             .unwrap();
-            assert_eq!(
-                block_count, 1,
-                "block row must be committed before the activity is delivered"
-            );
-            socket
-                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
-                .await
-                .unwrap();
-        })
-    };
 
-    // When
-    module
-        .block_account(
-            auth_account_id,
-            BlockAccountDto {
-                account_nanoid: blocker.clone(),
-                target: actor_url,
-            },
+        // Then
+        server.await.unwrap();
+        let delivered: (bool,) = sqlx::query_as(
+            "SELECT delivered_at IS NOT NULL FROM outbox_activities o \
+         JOIN accounts a ON a.id = o.account_id \
+         WHERE a.nanoid = $1 AND o.activity_type = 'Block'",
         )
+        .bind(&blocker)
+        .fetch_one(&mut *module.database.connection().await.unwrap())
         .await
         .unwrap();
-
-    // Then
-    server.await.unwrap();
-    let delivered: (bool,) = sqlx::query_as(
-        "SELECT delivered_at IS NOT NULL FROM outbox_activities o \
-         JOIN accounts a ON a.id = o.account_id \
-         WHERE a.nanoid = $1 AND o.activity_type = 'Block'",
-    )
-    .bind(&blocker)
-    .fetch_one(&mut *module.database.connection().await.unwrap())
-    .await
-    .unwrap();
-    assert!(delivered.0);
+        assert!(delivered.0);
+    })
+    .await;
 }
 
 #[test_with::env(DATABASE_URL)]
 #[tokio::test]
 async fn second_block_returns_rejected_already_blocked() {
-    // Given
-    let (module, _password_file, auth_account_id) = mute_test_module().await;
-    let blocker = create_test_account(&module, &auth_account_id).await;
-    let target = create_test_account(&module, &auth_account_id).await;
-    let dto = || BlockAccountDto {
-        account_nanoid: blocker.clone(),
-        target: target.clone(),
-    };
-    module
-        .block_account(auth_account_id.clone(), dto())
-        .await
-        .unwrap();
+    without_scope_restriction(async {
+        // Given
+        let (module, _password_file, auth_account_id) = mute_test_module().await;
+        let blocker = create_test_account(&module, &auth_account_id).await;
+        let target = create_test_account(&module, &auth_account_id).await;
+        let dto = || BlockAccountDto {
+            account_nanoid: blocker.clone(),
+            target: target.clone(),
+        };
+        module
+            .block_account(auth_account_id.clone(), dto())
+            .await
+            .unwrap();
 
-    // When
-    let second = module.block_account(auth_account_id, dto()).await;
+        // When
+        let second = module.block_account(auth_account_id, dto()).await;
 
-    // Then
-    let Err(error) = second else {
-        panic!("second block must be rejected");
-    };
-    assert!(matches!(error.current_context(), KernelError::Rejected));
+        // Then
+        let Err(error) = second else {
+            panic!("second block must be rejected");
+        };
+        assert!(matches!(error.current_context(), KernelError::Rejected));
+    })
+    .await;
 }
 
 #[test_with::env(DATABASE_URL)]
 #[tokio::test]
 async fn unblock_missing_returns_not_found() {
-    // Given
-    let (module, _password_file, auth_account_id) = mute_test_module().await;
-    let blocker = create_test_account(&module, &auth_account_id).await;
-    let target = create_test_account(&module, &auth_account_id).await;
-
-    // When
-    let result = module
-        .unblock_account(
-            auth_account_id,
-            BlockAccountDto {
-                account_nanoid: blocker,
-                target,
-            },
-        )
-        .await;
+    without_scope_restriction(async {
+        // Given
+        let (module, _password_file, auth_account_id) = mute_test_module().await;
+        let blocker = create_test_account(&module, &auth_account_id).await;
+        let target = create_test_account(&module, &auth_account_id).await;
+
+        // When
+        let result = module
+            .unblock_account(
+                auth_account_id,
+                BlockAccountDto {
+                    account_nanoid: blocker,
+                    target,
+                },
+            )
+            .await;
 
-    // Then
-    let Err(error) = result else {
-        panic!("unblock without a block must be not found");
-    };
-    assert!(matches!(error.current_context(), KernelError::NotFound));
+        // Then
+        let Err(error) = result else {
+            panic!("unblock without a block must be not found");
+        };
+        assert!(matches!(error.current_context(), KernelError::NotFound));
+    })
+    .await;
 }
 
 #[test_with::env(DATABASE_URL)]
 #[tokio::test]
 async fn unblock_equivalence_removes_block_and_creates_undo_outbox() {
-    // Given: a blocked remote actor (unreachable inbox tolerated for both
-    // the Block and the Undo delivery)
-    let (module, _password_file, auth_account_id) = mute_test_module().await;
-    let blocker = create_test_account(&module, &auth_account_id).await;
-    let (actor_url, remote_id) =
-        seed_remote_actor(&module.database, "http://127.0.0.1:1/inbox").await;
-    let blocker_id = account_id_of(&module.database, &blocker).await;
-    let dto = || BlockAccountDto {
-        account_nanoid: blocker.clone(),
-        target: actor_url.clone(),
-    };
-    module
-        .block_account(auth_account_id.clone(), dto())
-        .await
-        .unwrap();
+    without_scope_restriction(async {
+        // Given: a blocked remote actor (unreachable inbox tolerated for both
+        // the Block and the Undo delivery)
+        let (module, _password_file, auth_account_id) = mute_test_module().await;
+        let blocker = create_test_account(&module, &auth_account_id).await;
+        let (actor_url, remote_id) =
+            seed_remote_actor(&module.database, "http://127.0.0.1:1/inbox").await;
+        let blocker_id = account_id_of(&module.database, &blocker).await;
+        let dto = || BlockAccountDto {
+            account_nanoid: blocker.clone(),
+            target: actor_url.clone(),
+        };
+        module
+            .block_account(auth_account_id.clone(), dto())
+            .await
+            .unwrap();
 
-    // When
-    module
-        .unblock_account(auth_account_id, dto())
+        // When
+        module
+            .unblock_account(auth_account_id, dto())
+            .await
+            .unwrap();
+
+        // Then
+        assert_eq!(
+            block_write_counts(&module.database, blocker_id, remote_id)
+                .await
+                .0,
+            0,
+            "block row removed"
+        );
+        let activity_types: Vec<(String,)> = sqlx::query_as(
+            "SELECT activity_type FROM outbox_activities WHERE account_id = $1 ORDER BY id",
+        )
+        .bind(blocker_id)
+        .fetch_all(&mut *module.database.connection().await.unwrap())
         .await
         .unwrap();
-
-    // Then
-    assert_eq!(
-        block_write_counts(&module.database, blocker_id, remote_id)
-            .await
-            .0,
-        0,
-        "block row removed"
-    );
-    let activity_types: Vec<(String,)> = sqlx::query_as(
-        "SELECT activity_type FROM outbox_activities WHERE account_id = $1 ORDER BY id",
-    )
-    .bind(blocker_id)
-    .fetch_all(&mut *module.database.connection().await.unwrap())
-    .await
-    .unwrap();
-    assert_eq!(
-        activity_types,
-        vec![("Block".to_string(),), ("Undo".to_string(),)]
-    );
+        assert_eq!(
+            activity_types,
+            vec![("Block".to_string(),), ("Undo".to_string(),)]
+        );
+    })
+    .await;
 }
 
 fn local_actor_url_for(account_nanoid: &str) -> String {
@@ -0,0 +1,332 @@
+use crate::dto::media::{BlockImageDto, ImageBlocklistEntryDto, UploadedImageDto};
+use crate::permission::{check_permission, instance_manage_media};
+use crate::service::activitypub::DeliverUpdatePersonUseCase;
+use crate::service::media::{delete_stored_objects, uploaded_image};
+use error_stack::Report;
+use kernel::interfaces::database::{
+    DatabaseConnection, DependOnTransactionManager, TransactionManager,
+};
+use kernel::interfaces::permission::DependOnPermissionChecker;
+use kernel::interfaces::read_model::{
+    AccountQuery, DependOnAccountQuery, DependOnProfileQuery, DependOnProfileReadModel,
+    ProfileQuery, ProfileReadModel,
+};
+use kernel::interfaces::repository::{
+    AggregateRepository, DependOnImageBlocklistRepository, DependOnImageRepository,
+    DependOnProfileRepository, ImageBlocklistRepository, ImageRepository,
+};
+use kernel::interfaces::storage::DependOnImageStorage;
+use kernel::prelude::entity::{
+    AccountId, AuthAccountId, FieldAction, ImageBlocklistAction, ImageBlocklistEntry,
+    ImageBlocklistEntryId, ImageId, Profile,
+};
+use kernel::KernelError;
+use std::future::Future;
+use time::OffsetDateTime;
+
+const MAX_QUARANTINE_PAGE: usize = 100;
+
+fn validation_error(message: String) -> Report<KernelError> {
+    Report::new(KernelError::Validation).attach_printable(message)
+}
+
+fn parse_image_id(id: &str) -> error_stack::Result<ImageId, KernelError> {
+    id.parse::<i64>()
+        .map(ImageId::new)
+        .map_err(|_| validation_error(format!("Invalid image ID: {id}")))
+}
+
+fn parse_action(action: &str) -> error_stack::Result<ImageBlocklistAction, KernelError> {
+    ImageBlocklistAction::parse(action).ok_or_else(|| {
+        validation_error(format!(
+            "Unknown blocklist action: {action}, expected reject or quarantine"
+        ))
+    })
+}
+
+pub trait BlockImageUseCase:
+    'static
+    + Sync
+    + Send
+    + Clone
+    + DependOnImageRepository
+    + DependOnImageBlocklistRepository
+    + DependOnImageStorage
+    + DependOnProfileQuery
+    + DependOnProfileReadModel
+    + DependOnProfileRepository
+    + DependOnAccountQuery
+    + DependOnTransactionManager
+    + DependOnPermissionChecker
+    + DeliverUpdatePersonUseCase
+{
+    /// Add the image to the blocklist by its hash and perceptual hash,
+    /// take it off every profile using it and delete it. Remote accounts
+    /// showing it lose their copy with it.
+    fn block_image(
+        &self,
+        auth_account_id: &AuthAccountId,
+        image_id: String,
+        dto: BlockImageDto,
+    ) -> impl Future<Output = error_stack::Result<ImageBlocklistEntryDto, KernelError>> + Send {
+        async move {
+            check_permission(self, auth_account_id, &instance_manage_media()).await?;
+            let action = parse_action(&dto.action)?;
+            ImageBlocklistEntry::validate_reason(&dto.reason)?;
+            let image_id = parse_image_id(&image_id)?;
+
+            let deps = self.clone();
+            let created_by = auth_account_id.clone();
+            let (entry, image, detached) = self
+                .transaction_manager()
+                .transaction(move |executor| {
+                    Box::pin(async move {
+                        let image = deps
+                            .image_repository()
+                            .find_by_id(executor, &image_id)
+                            .await?
+                            .ok_or_else(|| Report::new(KernelError::NotFound))?;
+                        let entry = ImageBlocklistEntry {
+                            id: ImageBlocklistEntryId::default(),
+                            hash: Some(image.hash().clone()),
+                            perceptual_hash: *image.perceptual_hash(),
+                            action,
+                            reason: dto.reason.trim().to_string(),
+                            created_by,
+                            created_at: OffsetDateTime::now_utc(),
+                        };
+                        deps.image_blocklist_repository()
+                            .create(executor, &entry)
+                            .await?;
+
+                        let profiles = deps
+                            .profile_query()
+                            .find_by_image_id(executor, &image_id)
+                            .await?;
+                        let mut detached = Vec::with_capacity(profiles.len());
+                        for profile in profiles {
+                            let clear_if_blocked = |slot: &Option<ImageId>| {
+                                if slot.as_ref() == Some(&image_id) {
+                                    FieldAction::Clear
+                                } else {
+                                    FieldAction::Unchanged
+                                }
+                            };
+                            deps.profile_repository()
+                                .save(
+                                    executor,
+                                    Profile::update(
+                                        profile.id().clone(),
+                                        FieldAction::Unchanged,
+                                        FieldAction::Unchanged,
+                                        clear_if_blocked(profile.icon()),
+                                        clear_if_blocked(profile.banner()),
+                                    ),
+                                )
+                                .await?;
+                            let rehydrated = deps
+                                .profile_repository()
+                                .load(executor, profile.id())
+                                .await?;
+                            deps.profile_read_model()
+                                .update(executor, rehydrated.aggregate())
+                                .await?;
+                            detached.push(profile.account_id().clone());
+                        }
+
+                        deps.image_repository().delete(executor, &image_id).await?;
+                        Ok((entry, image, detached))
+                    })
+                })
+                .await?;
+
+            delete_stored_objects(self.image_storage(), &image).await;
+            deliver_updates(self, &detached).await;
+            Ok(ImageBlocklistEntryDto::from(entry))
+        }
+    }
+}
+
+impl<T> BlockImageUseCase for T where
+    T: 'static
+        + Sync
+        + Send
+        + Clone
+        + DependOnImageRepository
+        + DependOnImageBlocklistRepository
+        + DependOnImageStorage
+        + DependOnProfileQuery
+        + DependOnProfileReadModel
+        + DependOnProfileRepository
+        + DependOnAccountQuery
+        + DependOnTransactionManager
+        + DependOnPermissionChecker
+        + DeliverUpdatePersonUseCase
+{
+}
+
+/// Followers see the profiles without the blocked image. Best effort: the
+/// profiles are already updated.
+async fn deliver_updates<T>(deps: &T, account_ids: &[AccountId])
+where
+    T: DependOnAccountQuery + DeliverUpdatePersonUseCase,
+{
+    for account_id in account_ids {
+        let result = async {
+            let mut executor = deps.database_connection().connection().await?;
+            let Some(account) = deps
+                .account_query()
+                .find_by_id(&mut executor, account_id)
+                .await?
+            else {
+                return Ok(());
+            };
+            deps.deliver_update_person(account_id, account.nanoid().as_ref())
+                .await
+        }
+        .await;
+        if let Err(error) = result {
+            tracing::warn!(
+                ?error,
+                "Update(Person) delivery failed after removing a blocked image"
+            );
+        }
+    }
+}
+
+pub trait ManageImageBlocklistUseCase:
+    'static + Sync + Send + DependOnImageBlocklistRepository + DependOnPermissionChecker
+{
+    fn get_image_blocklist(
+        &self,
+        auth_account_id: &AuthAccountId,
+    ) -> impl Future<Output = error_stack::Result<Vec<ImageBlocklistEntryDto>, KernelError>> + Send
+    {
+        async move {
+            check_permission(self, auth_account_id, &instance_manage_media()).await?;
+            let mut executor = self.database_connection().connection().await?;
+            let entries = self
+                .image_blocklist_repository()
+                .find_all(&mut executor)
+                .await?;
+            Ok(entries
+                .into_iter()
+                .map(ImageBlocklistEntryDto::from)
+                .collect())
+        }
+    }
+
+    /// Later uploads no longer match the entry. Images it already removed
+    /// stay deleted.
+    fn delete_image_blocklist_entry(
+        &self,
+        auth_account_id: &AuthAccountId,
+        entry_id: String,
+    ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send {
+        async move {
+            check_permission(self, auth_account_id, &instance_manage_media()).await?;
+            let id = entry_id
+                .parse::<i64>()
+                .map(ImageBlocklistEntryId::new)
+                .map_err(|_| validation_error(format!("Invalid blocklist entry ID: {entry_id}")))?;
+            let mut executor = self.database_connection().connection().await?;
+            if !self
+                .image_blocklist_repository()
+                .delete(&mut executor, &id)
+                .await?
+            {
+                return Err(Report::new(KernelError::NotFound));
+            }
+            Ok(())
+        }
+    }
+}
+
+impl<T> ManageImageBlocklistUseCase for T where
+    T: 'static + Sync + Send + DependOnImageBlocklistRepository + DependOnPermissionChecker
+{
+}
+
+pub trait ReviewQuarantinedImagesUseCase:
+    'static + Sync + Send + DependOnImageRepository + DependOnPermissionChecker
+{
+    /// Newest first. `cursor` is the last image ID of the previous page.
+    fn get_quarantined_images(
+        &self,
+        auth_account_id: &AuthAccountId,
+        limit: Option<u32>,
+        cursor: Option<String>,
+    ) -> impl Future<Output = error_stack::Result<Vec<UploadedImageDto>, KernelError>> + Send {
+        async move {
+            check_permission(self, auth_account_id, &instance_manage_media()).await?;
+            let cursor = cursor
+                .map(|cursor| {
+                    cursor.parse::<i64>().map_err(|_| {
+                        validation_error(format!("Invalid quarantined image cursor: {cursor}"))
+                    })
+                })
+                .transpose()?;
+            let limit = limit
+                .map(|limit| limit as usize)
+                .unwrap_or(20)
+                .clamp(1, MAX_QUARANTINE_PAGE);
+            let mut executor = self.database_connection().connection().await?;
+            let images = self
+                .image_repository()
+                .find_quarantined(&mut executor, limit, cursor)
+                .await?;
+            Ok(images.iter().map(uploaded_image).collect())
+        }
+    }
+
+    /// Clear the image for use. To get rid of it instead, block it.
+    fn release_quarantined_image(
+        &self,
+        auth_account_id: &AuthAccountId,
+        image_id: String,
+    ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send {
+        async move {
+            check_permission(self, auth_account_id, &instance_manage_media()).await?;
+            let image_id = parse_image_id(&image_id)?;
+            let mut executor = self.database_connection().connection().await?;
+            self.image_repository()
+                .find_by_id(&mut executor, &image_id)
+                .await?
+                .ok_or_else(|| Report::new(KernelError::NotFound))?;
+            if !self
+                .image_repository()
+                .set_quarantined(&mut executor, &image_id, false)
+                .await?
+            {
+                return Err(
+                    Report::new(KernelError::Rejected).attach_printable("Image is not quarantined")
+                );
+            }
+            Ok(())
+        }
+    }
+}
+
+impl<T> ReviewQuarantinedImagesUseCase for T where
+    T: 'static + Sync + Send + DependOnImageRepository + DependOnPermissionChecker
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_blocklist_actions() {
+        assert_eq!(
+            parse_action("reject").unwrap(),
+            ImageBlocklistAction::Reject
+        );
+        assert_eq!(
+            parse_action("quarantine").unwrap(),
+            ImageBlocklistAction::Quarantine
+        );
+        assert!(parse_action("delete").is_err());
+        assert!(parse_image_id("abc").is_err());
+    }
+}
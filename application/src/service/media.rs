@@ -1,99 +1,846 @@
-use crate::dto::media::{UploadImageDto, UploadedImageDto};
+use crate::dto::media::{
+    CreateUploadSessionDto, ImageFocusDto, ImageVariantDto, UploadImageDto, UploadSessionDto,
+    UploadedImageDto,
+};
+use crate::projection::PROFILE_PROJECTOR_NAME;
 use error_stack::Report;
-use kernel::interfaces::database::DatabaseConnection;
-use kernel::interfaces::repository::{DependOnImageRepository, ImageRepository};
-use kernel::interfaces::storage::{DependOnImageStorage, ImageStorage};
-use kernel::prelude::entity::{Image, ImageBlurHash, ImageHash, ImageId, ImageUrl};
+use image::codecs::gif::GifDecoder;
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::{PngDecoder, PngEncoder};
+use image::codecs::webp::{WebPDecoder, WebPEncoder};
+use image::imageops::FilterType;
+use image::metadata::Orientation;
+use image::{
+    AnimationDecoder, Delay, DynamicImage, GenericImageView, ImageDecoder, ImageEncoder,
+    ImageFormat, RgbaImage,
+};
+use kernel::interfaces::config::{DependOnMediaQuota, MediaQuota};
+use kernel::interfaces::database::{
+    DatabaseConnection, DependOnDatabaseConnection, Transaction, TransactionalDatabaseConnection,
+};
+use kernel::interfaces::projection::{
+    DependOnProfileEventLog, DependOnProjectionCheckpointStore, ProfileEventLog,
+    ProjectionCheckpointStore,
+};
+use kernel::interfaces::repository::{
+    DependOnImageRepository, DependOnUploadSessionRepository, ImageRepository,
+    UploadSessionRepository,
+};
+use kernel::interfaces::storage::{
+    DependOnImageScanner, DependOnImageStorage, ImageScanInput, ImageScanVerdict, ImageScanner,
+    ImageStorage,
+};
+use kernel::prelude::entity::{
+    AuthAccountId, CreatedAt, Image, ImageBlurHash, ImageDescription, ImageFocus, ImageHash,
+    ImageId, ImagePerceptualHash, ImageUrl, ImageVariant, ImageVariantKind, UploadSession,
+    UploadSessionId,
+};
 use kernel::KernelError;
 use sha2::Digest;
 use std::future::Future;
 use std::io::Cursor;
+use time::OffsetDateTime;
 
 pub const MAX_IMAGE_BYTES: usize = 10 * 1024 * 1024;
+/// Direct uploads skip the API nodes, so they may be larger.
+pub const MAX_DIRECT_UPLOAD_BYTES: usize = 40 * 1024 * 1024;
+const UPLOAD_SESSION_TTL: std::time::Duration = std::time::Duration::from_secs(15 * 60);
+const ACCEPTED_MEDIA_TYPES: [&str; 4] = ["image/png", "image/jpeg", "image/webp", "image/gif"];
 const MAX_IMAGE_DIMENSION: u32 = 4096;
+const JPEG_QUALITY: u8 = 90;
+const RENDITION_MEDIA_TYPE: &str = "image/webp";
+/// Animations are always stored as animated WebP.
+pub(crate) const ANIMATED_MEDIA_TYPE: &str = "image/webp";
+const RENDITION_QUALITY: f32 = 80.0;
+const ANIMATION_QUALITY: f32 = 90.0;
+const MAX_ANIMATION_FRAMES: usize = 300;
+const MAX_ANIMATION_DURATION_MS: u64 = 60_000;
+/// Decoded pixels over all frames, about 256 MiB of RGBA.
+const MAX_ANIMATION_PIXELS: u64 = 64 * 1024 * 1024;
+const MIN_FRAME_DELAY_MS: u32 = 20;
+/// Unreferenced images examined per garbage collection pass.
+const IMAGE_GC_BATCH_LIMIT: usize = 100;
+/// Expired upload sessions swept per pass.
+const UPLOAD_SESSION_GC_BATCH_LIMIT: usize = 100;
 
-pub trait UploadImageUseCase: Sync + Send + DependOnImageRepository + DependOnImageStorage {
+/// Renditions generated for every upload. Avatars and headers are centre
+/// cropped to their aspect ratio; thumbnails keep the original one.
+const RENDITIONS: [Rendition; 3] = [
+    Rendition {
+        kind: ImageVariantKind::Avatar,
+        width: 400,
+        height: 400,
+        crop: true,
+    },
+    Rendition {
+        kind: ImageVariantKind::Header,
+        width: 1500,
+        height: 500,
+        crop: true,
+    },
+    Rendition {
+        kind: ImageVariantKind::Thumbnail,
+        width: 160,
+        height: 160,
+        crop: false,
+    },
+];
+
+pub trait UploadImageUseCase:
+    Sync
+    + Send
+    + DependOnDatabaseConnection<DatabaseConnection: TransactionalDatabaseConnection>
+    + DependOnImageRepository
+    + DependOnImageStorage
+    + DependOnImageScanner
+    + DependOnMediaQuota
+{
     fn upload_image(
         &self,
+        auth_account_id: &AuthAccountId,
         dto: UploadImageDto,
     ) -> impl Future<Output = error_stack::Result<UploadedImageDto, KernelError>> + Send {
+        ingest_image(self, auth_account_id, dto, MAX_IMAGE_BYTES)
+    }
+}
+
+impl<T> UploadImageUseCase for T where
+    T: Sync
+        + Send
+        + DependOnDatabaseConnection<DatabaseConnection: TransactionalDatabaseConnection>
+        + DependOnImageRepository
+        + DependOnImageStorage
+        + DependOnImageScanner
+        + DependOnMediaQuota
+{
+}
+
+/// Uploads that bypass the API nodes: the client sends the bytes straight
+/// to storage through a presigned URL, then finalizes the session, which
+/// runs the same validation and processing as a regular upload.
+pub trait DirectUploadUseCase:
+    Sync
+    + Send
+    + DependOnDatabaseConnection<DatabaseConnection: TransactionalDatabaseConnection>
+    + DependOnImageRepository
+    + DependOnImageStorage
+    + DependOnImageScanner
+    + DependOnMediaQuota
+    + DependOnUploadSessionRepository
+{
+    fn create_upload_session(
+        &self,
+        auth_account_id: &AuthAccountId,
+        dto: CreateUploadSessionDto,
+    ) -> impl Future<Output = error_stack::Result<UploadSessionDto, KernelError>> + Send {
         async move {
-            let extension = validate_upload(&dto)?;
-            let processed = tokio::task::spawn_blocking(move || process_image(dto))
-                .await
-                .map_err(|error| {
-                    Report::new(KernelError::Internal)
-                        .attach_printable(format!("Image processing task failed: {error}"))
-                })??;
-            let id = ImageId::new(kernel::generate_id());
-            let key = format!("images/{}.{}", id.as_ref(), extension);
-            let stored = self
+            validate_size_and_type(dto.byte_size, &dto.content_type, MAX_DIRECT_UPLOAD_BYTES)?;
+            let (description, focus) = upload_metadata(dto.description.as_deref(), dto.focus)?;
+            let mut executor = self.database_connection().connection().await?;
+            // Processing can only shrink what counts against the quota by
+            // so much, so refuse before the client sends the bytes.
+            let used = self
+                .image_repository()
+                .total_bytes_by_owner(&mut executor, auth_account_id)
+                .await?;
+            check_quota(self.media_quota(), used, dto.byte_size)?;
+
+            let id = UploadSessionId::default();
+            let key = UploadSession::object_key_for(&id);
+            let presigned = self
                 .image_storage()
-                .put(&key, &processed.content_type, &processed.bytes)
+                .presign_upload(&key, &dto.content_type, dto.byte_size, UPLOAD_SESSION_TTL)
                 .await?;
-            let image = Image::new(
+            let now = OffsetDateTime::now_utc();
+            let session = UploadSession {
                 id,
-                ImageUrl::new(stored.url),
-                ImageHash::new(processed.hash),
-                ImageBlurHash::new(processed.blur_hash),
-            );
-            let mut executor = self.database_connection().connection().await?;
-            self.image_repository()
-                .create(&mut executor, &image)
+                owner: auth_account_id.clone(),
+                object_key: presigned.object.key,
+                object_url: presigned.object.url,
+                content_type: dto.content_type,
+                byte_size: dto.byte_size,
+                description,
+                focus,
+                expires_at: now + UPLOAD_SESSION_TTL,
+                created_at: now,
+            };
+            self.upload_session_repository()
+                .create(&mut executor, &session)
                 .await?;
-            Ok(UploadedImageDto {
-                id: image.id().as_ref().to_string(),
-                url: image.url().as_ref().to_string(),
-                hash: image.hash().as_ref().to_string(),
-                blur_hash: image.blur_hash().as_ref().to_string(),
+            Ok(UploadSessionDto {
+                id: session.id.as_ref().to_string(),
+                upload_url: presigned.upload_url,
+                method: presigned.method,
+                headers: presigned.headers,
+                expires_at: session.expires_at,
             })
         }
     }
+
+    /// Turn the uploaded object into an image. The session is used up even
+    /// when the bytes turn out not to be a valid image; only a finalize
+    /// before anything was uploaded leaves it open.
+    fn finalize_upload_session(
+        &self,
+        auth_account_id: &AuthAccountId,
+        session_id: String,
+    ) -> impl Future<Output = error_stack::Result<UploadedImageDto, KernelError>> + Send {
+        async move {
+            let id = session_id
+                .parse::<i64>()
+                .map(UploadSessionId::new)
+                .map_err(|_| {
+                    Report::new(KernelError::Validation)
+                        .attach_printable(format!("Invalid upload session ID: {session_id}"))
+                })?;
+            let mut executor = self.database_connection().connection().await?;
+            let session = self
+                .upload_session_repository()
+                .find_by_id(&mut executor, &id)
+                .await?
+                .ok_or_else(|| Report::new(KernelError::NotFound))?;
+            if &session.owner != auth_account_id {
+                return Err(Report::new(KernelError::PermissionDenied).attach_printable(
+                    "Only the account that opened an upload session can finalize it",
+                ));
+            }
+            if session.is_expired(OffsetDateTime::now_utc()) {
+                return Err(Report::new(KernelError::Rejected)
+                    .attach_printable("Upload session has expired"));
+            }
+            let bytes = self
+                .image_storage()
+                .fetch(&session.object_key, session.byte_size as usize)
+                .await?
+                .ok_or_else(|| {
+                    Report::new(KernelError::Rejected)
+                        .attach_printable("Nothing has been uploaded for this session yet")
+                })?;
+            // Whoever deletes the row owns the object, so a concurrent
+            // finalize cannot ingest it twice.
+            if !self
+                .upload_session_repository()
+                .delete(&mut executor, &session.id)
+                .await?
+            {
+                return Err(Report::new(KernelError::NotFound));
+            }
+            if let Err(error) = self.image_storage().delete(&session.object_url).await {
+                tracing::warn!(?error, key = %session.object_key, "Failed to delete staged upload");
+            }
+            let dto = UploadImageDto {
+                content_type: session.content_type,
+                bytes,
+                description: session
+                    .description
+                    .map(|description| description.as_ref().to_string()),
+                focus: session.focus.as_ref().map(ImageFocusDto::from),
+            };
+            ingest_image(self, auth_account_id, dto, MAX_DIRECT_UPLOAD_BYTES).await
+        }
+    }
 }
 
-impl<T> UploadImageUseCase for T where
+impl<T> DirectUploadUseCase for T where
+    T: Sync
+        + Send
+        + DependOnDatabaseConnection<DatabaseConnection: TransactionalDatabaseConnection>
+        + DependOnImageRepository
+        + DependOnImageStorage
+        + DependOnImageScanner
+        + DependOnMediaQuota
+        + DependOnUploadSessionRepository
+{
+}
+
+pub trait DeleteImageUseCase: Sync + Send + DependOnImageRepository + DependOnImageStorage {
+    /// Drops the reference the caller holds as one of the image's uploaders.
+    /// The image itself goes with its last reference, and only while no
+    /// profile or remote account uses it.
+    fn delete_image(
+        &self,
+        auth_account_id: &AuthAccountId,
+        image_id: String,
+    ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send {
+        async move {
+            let id = image_id.parse::<i64>().map(ImageId::new).map_err(|_| {
+                Report::new(KernelError::Validation)
+                    .attach_printable(format!("Invalid image ID: {image_id}"))
+            })?;
+            let mut executor = self.database_connection().connection().await?;
+            let image = self
+                .image_repository()
+                .find_by_id(&mut executor, &id)
+                .await?
+                .ok_or_else(|| Report::new(KernelError::NotFound))?;
+            let owners = self
+                .image_repository()
+                .find_owners(&mut executor, &id)
+                .await?;
+            if !owners.contains(auth_account_id) {
+                return Err(Report::new(KernelError::PermissionDenied)
+                    .attach_printable("Only an uploader can delete an image"));
+            }
+            if owners.len() > 1 {
+                self.image_repository()
+                    .remove_owner(&mut executor, &id, auth_account_id)
+                    .await?;
+                return Ok(());
+            }
+            if !self
+                .image_repository()
+                .delete_last_owned(&mut executor, &id, auth_account_id)
+                .await?
+            {
+                // A duplicate upload may have attached another owner since
+                // the owners were read; the image stays theirs.
+                let owners = self
+                    .image_repository()
+                    .find_owners(&mut executor, &id)
+                    .await?;
+                if owners.iter().any(|owner| owner != auth_account_id) {
+                    self.image_repository()
+                        .remove_owner(&mut executor, &id, auth_account_id)
+                        .await?;
+                    return Ok(());
+                }
+                return Err(Report::new(KernelError::Rejected)
+                    .attach_printable("Image is in use as a profile icon or banner"));
+            }
+            delete_stored_objects(self.image_storage(), &image).await;
+            Ok(())
+        }
+    }
+}
+
+impl<T> DeleteImageUseCase for T where
     T: Sync + Send + DependOnImageRepository + DependOnImageStorage
 {
 }
 
-struct ProcessedImage {
-    content_type: String,
+/// Removes images nothing references once they are older than a grace
+/// period, which leaves time to attach a fresh upload to a profile.
+/// Replaced avatars and uploads that were never used end up here.
+///
+/// Profile references are read from the `profiles` projection, so a pass is
+/// skipped while that projection is behind the profile event log (lagging,
+/// or being rebuilt from checkpoint zero): a live avatar could otherwise look
+/// unreferenced.
+pub trait CollectUnreferencedImages:
+    Sync
+    + Send
+    + DependOnImageRepository
+    + DependOnImageStorage
+    + DependOnProfileEventLog
+    + DependOnProjectionCheckpointStore
+{
+    /// Run one pass. Returns the number of images deleted.
+    fn collect_unreferenced_images(
+        &self,
+        grace_period: time::Duration,
+    ) -> impl Future<Output = error_stack::Result<usize, KernelError>> + Send {
+        async move {
+            let mut executor = self.database_connection().connection().await?;
+            let head_seq = self.profile_event_log().head_seq(&mut executor).await?;
+            let checkpoint = self
+                .projection_checkpoint_store()
+                .get(&mut executor, PROFILE_PROJECTOR_NAME)
+                .await?
+                .unwrap_or(0);
+            if checkpoint < head_seq {
+                tracing::info!(
+                    checkpoint,
+                    head_seq,
+                    "profile projection is behind, skipping image collection"
+                );
+                return Ok(0);
+            }
+            let created_before = OffsetDateTime::now_utc() - grace_period;
+            let candidates = self
+                .image_repository()
+                .find_unreferenced(&mut executor, created_before, IMAGE_GC_BATCH_LIMIT)
+                .await?;
+            let mut deleted = 0;
+            for image in candidates {
+                // Re-checked atomically: a profile may have picked the image
+                // up, or a duplicate upload attached an owner, since the
+                // candidates were read.
+                if self
+                    .image_repository()
+                    .delete_unreferenced(&mut executor, image.id(), created_before)
+                    .await?
+                {
+                    delete_stored_objects(self.image_storage(), &image).await;
+                    deleted += 1;
+                }
+            }
+            Ok(deleted)
+        }
+    }
+}
+
+impl<T> CollectUnreferencedImages for T where
+    T: Sync
+        + Send
+        + DependOnImageRepository
+        + DependOnImageStorage
+        + DependOnProfileEventLog
+        + DependOnProjectionCheckpointStore
+{
+}
+
+/// Drops upload sessions that were never finalized together with whatever
+/// was uploaded for them.
+pub trait ExpireUploadSessions:
+    Sync + Send + DependOnImageStorage + DependOnUploadSessionRepository
+{
+    /// Run one pass. Returns the number of sessions removed.
+    fn expire_upload_sessions(
+        &self,
+    ) -> impl Future<Output = error_stack::Result<usize, KernelError>> + Send {
+        async move {
+            let mut executor = self.database_connection().connection().await?;
+            let expired = self
+                .upload_session_repository()
+                .find_expired(
+                    &mut executor,
+                    OffsetDateTime::now_utc(),
+                    UPLOAD_SESSION_GC_BATCH_LIMIT,
+                )
+                .await?;
+            let mut removed = 0;
+            for session in expired {
+                if self
+                    .upload_session_repository()
+                    .delete(&mut executor, &session.id)
+                    .await?
+                {
+                    if let Err(error) = self.image_storage().delete(&session.object_url).await {
+                        tracing::warn!(?error, key = %session.object_key, "Failed to delete staged upload");
+                    }
+                    removed += 1;
+                }
+            }
+            Ok(removed)
+        }
+    }
+}
+
+impl<T> ExpireUploadSessions for T where
+    T: Sync + Send + DependOnImageStorage + DependOnUploadSessionRepository
+{
+}
+
+/// Process an upload and record it for `auth_account_id`, or take a
+/// reference to an identical image that is already stored.
+async fn ingest_image<T>(
+    module: &T,
+    auth_account_id: &AuthAccountId,
+    dto: UploadImageDto,
+    max_bytes: usize,
+) -> error_stack::Result<UploadedImageDto, KernelError>
+where
+    T: DependOnDatabaseConnection<DatabaseConnection: TransactionalDatabaseConnection>
+        + DependOnImageRepository
+        + DependOnImageStorage
+        + DependOnImageScanner
+        + DependOnMediaQuota
+        + ?Sized,
+{
+    let (description, focus) = upload_metadata(dto.description.as_deref(), dto.focus)?;
+    let mut processed = prepare_image(dto, max_bytes).await?;
+    processed.quarantined = scan_image(module.image_scanner(), &processed).await?;
+    let hash = ImageHash::new(processed.hash.clone());
+    let metadata = (description, focus);
+    let used = {
+        let mut transaction = module.database_connection().get_transaction().await?;
+        let executor = transaction.connection();
+        let used = module
+            .image_repository()
+            .total_bytes_by_owner(executor, auth_account_id)
+            .await?;
+        if let Some(existing) = module
+            .image_repository()
+            .find_by_hash(executor, &hash)
+            .await?
+        {
+            let uploaded = reference_existing(
+                module,
+                executor,
+                existing,
+                auth_account_id,
+                used,
+                processed.quarantined,
+                metadata,
+            )
+            .await?;
+            transaction.commit().await?;
+            return Ok(uploaded);
+        }
+        transaction.commit().await?;
+        used
+    };
+    check_quota(module.media_quota(), used, processed.byte_size())?;
+    // Objects are written outside the transaction; a concurrent upload of
+    // the same bytes may still win the insert below.
+    let image = store_image(module.image_storage(), processed).await?;
+    let mut transaction = module.database_connection().get_transaction().await?;
+    let executor = transaction.connection();
+    if !module
+        .image_repository()
+        .create_upload(executor, &image)
+        .await?
+    {
+        let existing = module
+            .image_repository()
+            .find_by_hash(executor, &hash)
+            .await?
+            .ok_or_else(|| {
+                Report::new(KernelError::Internal)
+                    .attach_printable("Upload with a conflicting hash disappeared")
+            })?;
+        let uploaded = reference_existing(
+            module,
+            executor,
+            existing,
+            auth_account_id,
+            used,
+            *image.quarantined(),
+            metadata,
+        )
+        .await?;
+        transaction.commit().await?;
+        delete_stored_objects(module.image_storage(), &image).await;
+        return Ok(uploaded);
+    }
+    module
+        .image_repository()
+        .add_owner(executor, image.id(), auth_account_id)
+        .await?;
+    let image = with_metadata(image, metadata.0, metadata.1);
+    if image.description().is_some() || image.focus().is_some() {
+        module
+            .image_repository()
+            .update_owner_metadata(executor, &image, auth_account_id)
+            .await?;
+    }
+    transaction.commit().await?;
+    Ok(uploaded_image(&image))
+}
+
+/// Identical bytes are already stored: take a reference to them instead of
+/// writing another copy.
+async fn reference_existing<T>(
+    module: &T,
+    executor: &mut <T::DatabaseConnection as DatabaseConnection>::Connection,
+    existing: Image,
+    auth_account_id: &AuthAccountId,
+    used: u64,
+    quarantined: bool,
+    (description, focus): (Option<ImageDescription>, Option<ImageFocus>),
+) -> error_stack::Result<UploadedImageDto, KernelError>
+where
+    T: DependOnImageRepository + DependOnMediaQuota + ?Sized,
+{
+    let owners = module
+        .image_repository()
+        .find_owners(executor, existing.id())
+        .await?;
+    if !owners.contains(auth_account_id) {
+        check_quota(module.media_quota(), used, *existing.byte_size())?;
+        module
+            .image_repository()
+            .add_owner(executor, existing.id(), auth_account_id)
+            .await?;
+    }
+    // The image may predate the blocklist entry it now matches.
+    let existing = if quarantined && !existing.quarantined() {
+        module
+            .image_repository()
+            .set_quarantined(executor, existing.id(), true)
+            .await?;
+        with_quarantine(existing, true)
+    } else {
+        existing
+    };
+    // Metadata belongs to this owner's reference: what the upload sends
+    // replaces what the owner set before, omitted fields stay, and other
+    // owners keep theirs.
+    let own = module
+        .image_repository()
+        .find_by_id_for_owner(executor, existing.id(), auth_account_id)
+        .await?
+        .ok_or_else(|| {
+            Report::new(KernelError::Internal)
+                .attach_printable("Reference to an existing image disappeared")
+        })?;
+    if description.is_none() && focus.is_none() {
+        return Ok(uploaded_image(&with_metadata(
+            existing,
+            own.description().clone(),
+            *own.focus(),
+        )));
+    }
+    let description = description.or_else(|| own.description().clone());
+    let focus = focus.or(*own.focus());
+    let existing = with_metadata(existing, description, focus);
+    module
+        .image_repository()
+        .update_owner_metadata(executor, &existing, auth_account_id)
+        .await?;
+    Ok(uploaded_image(&existing))
+}
+
+/// Run the content-safety scanner over a processed image. Returns whether
+/// it is to be quarantined; a rejection is an error.
+pub(crate) async fn scan_image<S: ImageScanner>(
+    scanner: &S,
+    processed: &ProcessedImage,
+) -> error_stack::Result<bool, KernelError> {
+    let hash = ImageHash::new(processed.hash.clone());
+    let perceptual_hash = ImagePerceptualHash::new(processed.perceptual_hash);
+    let verdict = scanner
+        .scan(ImageScanInput {
+            content_type: processed.content_type,
+            bytes: &processed.bytes,
+            hash: &hash,
+            perceptual_hash: Some(&perceptual_hash),
+        })
+        .await?;
+    match verdict {
+        ImageScanVerdict::Clean => Ok(false),
+        ImageScanVerdict::Quarantine { reason } => {
+            tracing::info!(hash = %processed.hash, reason, "Image quarantined by the scanner");
+            Ok(true)
+        }
+        // The reason is for moderators; the uploader only learns the outcome.
+        ImageScanVerdict::Reject { reason } => {
+            tracing::info!(hash = %processed.hash, reason, "Image rejected by the scanner");
+            Err(Report::new(KernelError::Rejected)
+                .attach_printable("Image matches blocked content"))
+        }
+    }
+}
+
+/// Validate the upload and process it off the async runtime.
+pub(crate) async fn prepare_image(
+    dto: UploadImageDto,
+    max_bytes: usize,
+) -> error_stack::Result<ProcessedImage, KernelError> {
+    validate_upload(&dto, max_bytes)?;
+    tokio::task::spawn_blocking(move || process_image(dto))
+        .await
+        .map_err(|error| {
+            Report::new(KernelError::Internal)
+                .attach_printable(format!("Image processing task failed: {error}"))
+        })?
+}
+
+/// Write the image and its renditions to storage under a new ID. The
+/// returned image is not recorded yet; that is up to the caller.
+pub(crate) async fn store_image<S: ImageStorage>(
+    storage: &S,
+    processed: ProcessedImage,
+) -> error_stack::Result<Image, KernelError> {
+    let byte_size = processed.byte_size();
+    let id = ImageId::new(kernel::generate_id());
+    let key = format!("images/{}.{}", id.as_ref(), processed.extension);
+    let stored = storage
+        .put(&key, processed.content_type, &processed.bytes)
+        .await?;
+    let mut variants = Vec::with_capacity(processed.variants.len());
+    for variant in processed.variants {
+        let key = format!("images/{}/{}.webp", id.as_ref(), variant.kind.as_str());
+        let stored = storage
+            .put(&key, RENDITION_MEDIA_TYPE, &variant.bytes)
+            .await?;
+        variants.push(ImageVariant::new(
+            variant.kind,
+            ImageUrl::new(stored.url),
+            variant.width,
+            variant.height,
+            RENDITION_MEDIA_TYPE.to_string(),
+        ));
+    }
+    Ok(Image::new(
+        id,
+        ImageUrl::new(stored.url),
+        ImageHash::new(processed.hash),
+        ImageBlurHash::new(processed.blur_hash),
+        variants,
+        processed.animated,
+        None,
+        None,
+        Some(ImagePerceptualHash::new(processed.perceptual_hash)),
+        processed.quarantined,
+        byte_size,
+        CreatedAt::now(),
+    ))
+}
+
+/// The alt text and focal point sent along with an upload, validated.
+/// A blank description counts as none.
+fn upload_metadata(
+    description: Option<&str>,
+    focus: Option<ImageFocusDto>,
+) -> error_stack::Result<(Option<ImageDescription>, Option<ImageFocus>), KernelError> {
+    let description = description
+        .map(str::trim)
+        .filter(|description| !description.is_empty())
+        .map(ImageDescription::new);
+    if let Some(description) = &description {
+        description.validate()?;
+    }
+    let focus = focus.map(ImageFocus::from);
+    if let Some(focus) = &focus {
+        focus.validate()?;
+    }
+    Ok((description, focus))
+}
+
+pub(crate) fn with_metadata(
+    image: Image,
+    description: Option<ImageDescription>,
+    focus: Option<ImageFocus>,
+) -> Image {
+    let mut image = image.into_destruct();
+    image.description = description;
+    image.focus = focus;
+    image.freeze()
+}
+
+pub(crate) fn with_quarantine(image: Image, quarantined: bool) -> Image {
+    let mut image = image.into_destruct();
+    image.quarantined = quarantined;
+    image.freeze()
+}
+
+pub(crate) fn uploaded_image(image: &Image) -> UploadedImageDto {
+    UploadedImageDto {
+        id: image.id().as_ref().to_string(),
+        url: image.url().as_ref().to_string(),
+        hash: image.hash().as_ref().to_string(),
+        blur_hash: image.blur_hash().as_ref().to_string(),
+        variants: image.variants().iter().map(ImageVariantDto::from).collect(),
+        animated: *image.animated(),
+        description: image
+            .description()
+            .as_ref()
+            .map(|description| description.as_ref().to_string()),
+        focus: image.focus().as_ref().map(ImageFocusDto::from),
+        quarantined: *image.quarantined(),
+    }
+}
+
+/// Best effort once the row is gone: a failure leaves an unreachable object
+/// in storage, never a row pointing at a missing one.
+pub(crate) async fn delete_stored_objects<S: ImageStorage>(storage: &S, image: &Image) {
+    let urls = std::iter::once(image.url()).chain(image.variants().iter().map(|v| v.url()));
+    for url in urls {
+        if let Err(error) = storage.delete(url.as_ref()).await {
+            tracing::warn!(?error, url = %url.as_ref(), "Failed to delete stored image object");
+        }
+    }
+}
+
+fn check_quota(
+    quota: &MediaQuota,
+    used: u64,
+    byte_size: u64,
+) -> error_stack::Result<(), KernelError> {
+    if used.saturating_add(byte_size) > quota.max_bytes {
+        return Err(Report::new(KernelError::Rejected).attach_printable(format!(
+            "Media quota of {} bytes exceeded ({used} bytes used, upload needs {byte_size})",
+            quota.max_bytes
+        )));
+    }
+    Ok(())
+}
+
+pub(crate) struct ProcessedImage {
+    content_type: &'static str,
+    extension: &'static str,
+    animated: bool,
     bytes: Vec<u8>,
     hash: String,
     blur_hash: String,
+    perceptual_hash: u64,
+    /// Set when the scanner holds the upload for moderator review.
+    quarantined: bool,
+    variants: Vec<ProcessedVariant>,
+}
+
+impl ProcessedImage {
+    fn byte_size(&self) -> u64 {
+        let renditions: usize = self.variants.iter().map(|v| v.bytes.len()).sum();
+        (self.bytes.len() + renditions) as u64
+    }
+}
+
+struct Rendition {
+    kind: ImageVariantKind,
+    width: u32,
+    height: u32,
+    crop: bool,
+}
+
+struct ProcessedVariant {
+    kind: ImageVariantKind,
+    bytes: Vec<u8>,
+    width: u32,
+    height: u32,
 }
 
-fn validate_upload(dto: &UploadImageDto) -> error_stack::Result<&'static str, KernelError> {
-    if dto.bytes.is_empty() {
+fn validate_upload(dto: &UploadImageDto, max_bytes: usize) -> error_stack::Result<(), KernelError> {
+    validate_size_and_type(dto.bytes.len() as u64, &dto.content_type, max_bytes)
+}
+
+fn validate_size_and_type(
+    byte_size: u64,
+    content_type: &str,
+    max_bytes: usize,
+) -> error_stack::Result<(), KernelError> {
+    if byte_size == 0 {
         return Err(Report::new(KernelError::Validation)
             .attach_printable("Image file cannot be empty".to_string()));
     }
-    if dto.bytes.len() > MAX_IMAGE_BYTES {
-        return Err(
-            Report::new(KernelError::Validation).attach_printable(format!(
-                "Image file must not exceed {MAX_IMAGE_BYTES} bytes"
-            )),
-        );
+    if byte_size > max_bytes as u64 {
+        return Err(Report::new(KernelError::Validation)
+            .attach_printable(format!("Image file must not exceed {max_bytes} bytes")));
     }
-    match dto.content_type.as_str() {
-        "image/png" => Ok("png"),
-        "image/jpeg" => Ok("jpg"),
-        "image/webp" => Ok("webp"),
-        _ => Err(Report::new(KernelError::Validation).attach_printable(
-            "Image MIME type must be image/png, image/jpeg, or image/webp".to_string(),
-        )),
+    if ACCEPTED_MEDIA_TYPES.contains(&content_type) {
+        return Ok(());
     }
+    Err(Report::new(KernelError::Validation).attach_printable(
+        "Image MIME type must be image/png, image/jpeg, image/webp, or image/gif".to_string(),
+    ))
+}
+
+/// MIME type of the bytes if they are in one of the accepted formats.
+pub(crate) fn sniffed_media_type(bytes: &[u8]) -> Option<&'static str> {
+    sniffed_format(bytes).map(|(_, media_type)| media_type)
 }
 
-fn sniffed_mime(bytes: &[u8]) -> Option<&'static str> {
+fn sniffed_format(bytes: &[u8]) -> Option<(ImageFormat, &'static str)> {
     match image::guess_format(bytes).ok()? {
-        image::ImageFormat::Png => Some("image/png"),
-        image::ImageFormat::Jpeg => Some("image/jpeg"),
-        image::ImageFormat::WebP => Some("image/webp"),
+        ImageFormat::Png => Some((ImageFormat::Png, "image/png")),
+        ImageFormat::Jpeg => Some((ImageFormat::Jpeg, "image/jpeg")),
+        ImageFormat::WebP => Some((ImageFormat::WebP, "image/webp")),
+        ImageFormat::Gif => Some((ImageFormat::Gif, "image/gif")),
         _ => None,
     }
 }
 
+/// MIME type and file extension a still image of `format` is stored as.
+/// There is no GIF encoder in the pipeline, so still GIFs become PNGs.
+fn stored_format(format: ImageFormat) -> (&'static str, &'static str) {
+    match format {
+        ImageFormat::Jpeg => ("image/jpeg", "jpg"),
+        ImageFormat::WebP => ("image/webp", "webp"),
+        _ => ("image/png", "png"),
+    }
+}
+
 fn check_dimensions(width: u32, height: u32) -> error_stack::Result<(), KernelError> {
     if width == 0 || height == 0 {
         return Err(Report::new(KernelError::Validation)
@@ -109,8 +856,14 @@ fn check_dimensions(width: u32, height: u32) -> error_stack::Result<(), KernelEr
     Ok(())
 }
 
+/// Decode, rotate upright and re-encode the upload. Only the pixels and the
+/// ICC profile survive, so EXIF (GPS coordinates, camera serials), XMP and
+/// any other metadata the client embedded never reach storage. The hash
+/// covers the sanitized bytes that are actually stored. The fixed-size
+/// renditions are cut from the same upright pixels. Animations are
+/// re-encoded as animated WebP; their renditions come from the first frame.
 fn process_image(dto: UploadImageDto) -> error_stack::Result<ProcessedImage, KernelError> {
-    let actual_mime = sniffed_mime(&dto.bytes).ok_or_else(|| {
+    let (format, actual_mime) = sniffed_format(&dto.bytes).ok_or_else(|| {
         Report::new(KernelError::Validation)
             .attach_printable("Image bytes are not a supported image format".to_string())
     })?;
@@ -122,22 +875,48 @@ fn process_image(dto: UploadImageDto) -> error_stack::Result<ProcessedImage, Ker
             )),
         );
     }
-    let (width, height) = image::ImageReader::new(Cursor::new(&dto.bytes))
-        .with_guessed_format()
+    if let Some(animation) = decode_animation(format, &dto.bytes)? {
+        let bytes = encode_animation(&animation)?;
+        let first_frame = animation
+            .frames
+            .into_iter()
+            .next()
+            .map(|frame| DynamicImage::ImageRgba8(frame.pixels))
+            .expect("animations have at least two frames");
+        return finish_processing(first_frame, bytes, (ANIMATED_MEDIA_TYPE, "webp"), true);
+    }
+    let mut decoder = image::ImageReader::with_format(Cursor::new(&dto.bytes), format)
+        .into_decoder()
         .map_err(|error| {
             Report::new(KernelError::Validation)
                 .attach_printable(format!("Failed to read image header: {error}"))
-        })?
-        .into_dimensions()
-        .map_err(|error| {
-            Report::new(KernelError::Validation)
-                .attach_printable(format!("Failed to read image dimensions: {error}"))
         })?;
+    let (width, height) = decoder.dimensions();
     check_dimensions(width, height)?;
-    let image = image::load_from_memory(&dto.bytes).map_err(|error| {
+    // Unreadable metadata only costs the rotation or colour profile; the
+    // pixels are still decoded (or rejected) below.
+    let orientation = decoder.orientation().unwrap_or(Orientation::NoTransforms);
+    let icc_profile = decoder.icc_profile().ok().flatten();
+    let mut image = DynamicImage::from_decoder(decoder).map_err(|error| {
         Report::new(KernelError::Validation)
             .attach_printable(format!("Image bytes are invalid: {error}"))
     })?;
+    image.apply_orientation(orientation);
+    let bytes = encode_sanitized(&image, format, &dto.bytes, icc_profile).map_err(|error| {
+        Report::new(KernelError::Internal)
+            .attach_printable(format!("Failed to re-encode image: {error}"))
+    })?;
+    finish_processing(image, bytes, stored_format(format), false)
+}
+
+/// Blurhash, hash and renditions shared by still and animated uploads.
+/// `image` is the still picture the renditions are cut from.
+fn finish_processing(
+    image: DynamicImage,
+    bytes: Vec<u8>,
+    (content_type, extension): (&'static str, &'static str),
+    animated: bool,
+) -> error_stack::Result<ProcessedImage, KernelError> {
     let thumbnail = image.thumbnail(64, 64).to_rgba8();
     let blur_hash = blurhash::encode(
         4,
@@ -150,15 +929,298 @@ fn process_image(dto: UploadImageDto) -> error_stack::Result<ProcessedImage, Ker
         Report::new(KernelError::Internal)
             .attach_printable(format!("Failed to generate blurhash: {error}"))
     })?;
-    let hash = format!("{:x}", sha2::Sha256::digest(&dto.bytes));
+    let hash = format!("{:x}", sha2::Sha256::digest(&bytes));
+    let mut variants: Vec<ProcessedVariant> = RENDITIONS
+        .iter()
+        .map(|rendition| render_variant(&image, rendition))
+        .collect();
+    if animated {
+        let (width, height) = image.dimensions();
+        let still = Rendition {
+            kind: ImageVariantKind::Static,
+            width,
+            height,
+            crop: false,
+        };
+        variants.push(render_variant(&image, &still));
+    }
     Ok(ProcessedImage {
-        content_type: dto.content_type,
-        bytes: dto.bytes,
+        content_type,
+        extension,
+        animated,
+        bytes,
         hash,
         blur_hash,
+        perceptual_hash: perceptual_hash(&image),
+        quarantined: false,
+        variants,
     })
 }
 
+/// 64-bit difference hash: each bit says whether a pixel of a 9x8
+/// grayscale thumbnail is darker than its right neighbour. Re-encoding,
+/// resizing and small edits flip only a few bits.
+fn perceptual_hash(image: &DynamicImage) -> u64 {
+    let small = image.resize_exact(9, 8, FilterType::Triangle).to_luma8();
+    let mut hash = 0u64;
+    for y in 0..8 {
+        for x in 0..8 {
+            hash <<= 1;
+            if small.get_pixel(x, y)[0] < small.get_pixel(x + 1, y)[0] {
+                hash |= 1;
+            }
+        }
+    }
+    hash
+}
+
+struct Animation {
+    width: u32,
+    height: u32,
+    frames: Vec<AnimationFrame>,
+}
+
+struct AnimationFrame {
+    pixels: RgbaImage,
+    delay_ms: u32,
+}
+
+/// Decode every frame of an animated GIF, WebP or APNG, enforcing the frame
+/// count, duration and decoded size limits. `None` for still images,
+/// including single-frame GIFs.
+fn decode_animation(
+    format: ImageFormat,
+    bytes: &[u8],
+) -> error_stack::Result<Option<Animation>, KernelError> {
+    let invalid = |error: image::ImageError| {
+        Report::new(KernelError::Validation)
+            .attach_printable(format!("Image bytes are invalid: {error}"))
+    };
+    let ((width, height), frames) = match format {
+        ImageFormat::Gif => {
+            let decoder = GifDecoder::new(Cursor::new(bytes)).map_err(invalid)?;
+            (decoder.dimensions(), decoder.into_frames())
+        }
+        ImageFormat::WebP => {
+            let decoder = WebPDecoder::new(Cursor::new(bytes)).map_err(invalid)?;
+            if !decoder.has_animation() {
+                return Ok(None);
+            }
+            (decoder.dimensions(), decoder.into_frames())
+        }
+        ImageFormat::Png => {
+            let decoder = PngDecoder::new(Cursor::new(bytes)).map_err(invalid)?;
+            if !decoder.is_apng().map_err(invalid)? {
+                return Ok(None);
+            }
+            let dimensions = decoder.dimensions();
+            (dimensions, decoder.apng().map_err(invalid)?.into_frames())
+        }
+        _ => return Ok(None),
+    };
+    check_dimensions(width, height)?;
+    let frame_pixels = u64::from(width) * u64::from(height);
+    let mut decoded = Vec::new();
+    let mut duration_ms = 0u64;
+    for frame in frames {
+        let frame = frame.map_err(invalid)?;
+        if decoded.len() == MAX_ANIMATION_FRAMES {
+            return Err(
+                Report::new(KernelError::Validation).attach_printable(format!(
+                    "Animations must not have more than {MAX_ANIMATION_FRAMES} frames"
+                )),
+            );
+        }
+        if frame_pixels * (decoded.len() as u64 + 1) > MAX_ANIMATION_PIXELS {
+            return Err(Report::new(KernelError::Validation).attach_printable(
+                "Animation is too large: reduce its dimensions or frame count".to_string(),
+            ));
+        }
+        let delay_ms = frame_delay_ms(frame.delay());
+        duration_ms += u64::from(delay_ms);
+        if duration_ms > MAX_ANIMATION_DURATION_MS {
+            return Err(
+                Report::new(KernelError::Validation).attach_printable(format!(
+                    "Animations must not play longer than {} seconds",
+                    MAX_ANIMATION_DURATION_MS / 1000
+                )),
+            );
+        }
+        let (left, top) = (frame.left(), frame.top());
+        let mut pixels = frame.into_buffer();
+        if pixels.dimensions() != (width, height) {
+            let mut canvas = RgbaImage::new(width, height);
+            image::imageops::overlay(&mut canvas, &pixels, i64::from(left), i64::from(top));
+            pixels = canvas;
+        }
+        decoded.push(AnimationFrame { pixels, delay_ms });
+    }
+    if decoded.len() < 2 {
+        return Ok(None);
+    }
+    Ok(Some(Animation {
+        width,
+        height,
+        frames: decoded,
+    }))
+}
+
+/// Browsers play frames shorter than `MIN_FRAME_DELAY_MS` (often 0 in GIFs)
+/// at 100 ms; the stored animation keeps that timing.
+fn frame_delay_ms(delay: Delay) -> u32 {
+    let (numerator, denominator) = delay.numer_denom_ms();
+    let delay_ms = numerator / denominator.max(1);
+    if delay_ms < MIN_FRAME_DELAY_MS {
+        100
+    } else {
+        delay_ms
+    }
+}
+
+/// Encode the frames as a looping lossy WebP animation. Like a still
+/// re-encode, this drops every metadata chunk of the original.
+fn encode_animation(animation: &Animation) -> error_stack::Result<Vec<u8>, KernelError> {
+    let encode_error = |detail: String| {
+        Report::new(KernelError::Internal)
+            .attach_printable(format!("Failed to encode animation: {detail}"))
+    };
+    let mut config =
+        webp::WebPConfig::new().map_err(|()| encode_error("invalid WebP config".to_string()))?;
+    config.quality = ANIMATION_QUALITY;
+    let mut encoder = webp::AnimEncoder::new(animation.width, animation.height, &config);
+    encoder.set_loop_count(0);
+    let mut timestamp_ms = 0i32;
+    for frame in &animation.frames {
+        encoder.add_frame(webp::AnimFrame::from_rgba(
+            frame.pixels.as_raw(),
+            animation.width,
+            animation.height,
+            timestamp_ms,
+        ));
+        timestamp_ms += frame.delay_ms as i32;
+    }
+    encoder
+        .try_encode()
+        .map(|memory| memory.to_vec())
+        .map_err(|error| encode_error(format!("{error:?}")))
+}
+
+/// Scale the image down to the rendition's bounds (never up, so small
+/// uploads are not blurred) and encode it as lossy WebP.
+fn render_variant(image: &DynamicImage, rendition: &Rendition) -> ProcessedVariant {
+    let (width, height) = image.dimensions();
+    let resized = if rendition.crop {
+        cover(image, rendition.width, rendition.height)
+    } else if width > rendition.width || height > rendition.height {
+        image.resize(rendition.width, rendition.height, FilterType::Lanczos3)
+    } else {
+        image.clone()
+    };
+    let (width, height) = resized.dimensions();
+    ProcessedVariant {
+        kind: rendition.kind,
+        bytes: encode_lossy_webp(&resized, RENDITION_QUALITY),
+        width,
+        height,
+    }
+}
+
+fn encode_lossy_webp(image: &DynamicImage, quality: f32) -> Vec<u8> {
+    let (width, height) = image.dimensions();
+    if image.color().has_alpha() {
+        let pixels = image.to_rgba8();
+        webp::Encoder::from_rgba(pixels.as_raw(), width, height)
+            .encode(quality)
+            .to_vec()
+    } else {
+        let pixels = image.to_rgb8();
+        webp::Encoder::from_rgb(pixels.as_raw(), width, height)
+            .encode(quality)
+            .to_vec()
+    }
+}
+
+/// Whether a still WebP holds lossy (`VP8 `) rather than lossless (`VP8L`)
+/// image data. Extended files (`VP8X`) put their other chunks first.
+fn is_lossy_webp(bytes: &[u8]) -> bool {
+    let mut offset = 12;
+    while let Some(header) = bytes.get(offset..offset + 8) {
+        match &header[..4] {
+            b"VP8 " => return true,
+            b"VP8L" => return false,
+            _ => {
+                let size = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+                // Chunks are padded to an even size.
+                offset = offset.saturating_add(8 + size as usize + (size as usize & 1));
+            }
+        }
+    }
+    false
+}
+
+/// Centre crop to the target aspect ratio, then shrink to the target size
+/// if the crop is larger.
+fn cover(image: &DynamicImage, width: u32, height: u32) -> DynamicImage {
+    let (source_width, source_height) = image.dimensions();
+    let crop_width = source_width
+        .min((u64::from(source_height) * u64::from(width) / u64::from(height)) as u32)
+        .max(1);
+    let crop_height = source_height
+        .min((u64::from(source_width) * u64::from(height) / u64::from(width)) as u32)
+        .max(1);
+    let cropped = image.crop_imm(
+        (source_width - crop_width) / 2,
+        (source_height - crop_height) / 2,
+        crop_width,
+        crop_height,
+    );
+    if crop_width > width || crop_height > height {
+        cropped.resize_exact(width, height, FilterType::Lanczos3)
+    } else {
+        cropped
+    }
+}
+
+/// Re-encode in the upload's own format. Lossy sources stay lossy: a lossy
+/// WebP photo re-encoded losslessly would grow several times over. The
+/// lossy WebP encoder does not embed ICC profiles, so those fall back to
+/// sRGB.
+fn encode_sanitized(
+    image: &DynamicImage,
+    format: ImageFormat,
+    source: &[u8],
+    icc_profile: Option<Vec<u8>>,
+) -> image::ImageResult<Vec<u8>> {
+    let mut bytes = Vec::new();
+    match format {
+        ImageFormat::Jpeg => encode_with(
+            JpegEncoder::new_with_quality(&mut bytes, JPEG_QUALITY),
+            image,
+            icc_profile,
+        )?,
+        ImageFormat::WebP if is_lossy_webp(source) => {
+            bytes = encode_lossy_webp(image, f32::from(JPEG_QUALITY));
+        }
+        ImageFormat::WebP => {
+            encode_with(WebPEncoder::new_lossless(&mut bytes), image, icc_profile)?
+        }
+        _ => encode_with(PngEncoder::new(&mut bytes), image, icc_profile)?,
+    }
+    Ok(bytes)
+}
+
+fn encode_with(
+    mut encoder: impl ImageEncoder,
+    image: &DynamicImage,
+    icc_profile: Option<Vec<u8>>,
+) -> image::ImageResult<()> {
+    if let Some(icc_profile) = icc_profile {
+        // Encoders without ICC support fall back to sRGB.
+        let _ = encoder.set_icc_profile(icc_profile);
+    }
+    image.write_with_encoder(encoder)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -176,9 +1238,11 @@ mod tests {
         let dto = UploadImageDto {
             content_type: "text/plain".to_string(),
             bytes: vec![1],
+            description: None,
+            focus: None,
         };
 
-        let result = validate_upload(&dto);
+        let result = validate_upload(&dto, MAX_IMAGE_BYTES);
 
         assert!(result.is_err());
     }
@@ -188,18 +1252,50 @@ mod tests {
         let dto = UploadImageDto {
             content_type: "image/png".to_string(),
             bytes: vec![0; MAX_IMAGE_BYTES + 1],
+            description: None,
+            focus: None,
         };
 
-        let result = validate_upload(&dto);
+        let result = validate_upload(&dto, MAX_IMAGE_BYTES);
 
         assert!(result.is_err());
     }
 
+    #[test]
+    fn direct_uploads_allow_larger_files() {
+        let size = (MAX_IMAGE_BYTES + 1) as u64;
+        assert!(validate_size_and_type(size, "image/png", MAX_IMAGE_BYTES).is_err());
+        assert!(validate_size_and_type(size, "image/png", MAX_DIRECT_UPLOAD_BYTES).is_ok());
+        let too_large = (MAX_DIRECT_UPLOAD_BYTES + 1) as u64;
+        assert!(validate_size_and_type(too_large, "image/png", MAX_DIRECT_UPLOAD_BYTES).is_err());
+        assert!(validate_size_and_type(0, "image/png", MAX_DIRECT_UPLOAD_BYTES).is_err());
+        assert!(validate_size_and_type(1, "image/svg+xml", MAX_DIRECT_UPLOAD_BYTES).is_err());
+    }
+
+    #[test]
+    fn perceptual_hash_survives_resizing_but_not_a_different_picture() {
+        let picture = DynamicImage::ImageRgb8(image::RgbImage::from_fn(256, 192, |x, y| {
+            let shade = ((x * 7 + y * 3) % 256) as u8;
+            image::Rgb([shade, (x % 200) as u8, (y % 160) as u8])
+        }));
+        let mirrored = picture.fliph();
+        let resized = picture.resize_exact(128, 96, FilterType::Lanczos3);
+
+        let original = ImagePerceptualHash::new(perceptual_hash(&picture));
+        let close = ImagePerceptualHash::new(perceptual_hash(&resized));
+        let other = ImagePerceptualHash::new(perceptual_hash(&mirrored));
+
+        assert!(original.distance(&close) <= 6);
+        assert!(original.distance(&other) > 6);
+    }
+
     #[test]
     fn rejects_mime_spoofed_image() {
         let dto = UploadImageDto {
             content_type: "image/jpeg".to_string(),
             bytes: PIXEL_PNG.to_vec(),
+            description: None,
+            focus: None,
         };
 
         let result = process_image(dto);
@@ -212,6 +1308,8 @@ mod tests {
         let dto = UploadImageDto {
             content_type: "image/png".to_string(),
             bytes: PIXEL_PNG.to_vec(),
+            description: None,
+            focus: None,
         };
 
         let processed = process_image(dto).unwrap();
@@ -221,6 +1319,277 @@ mod tests {
         assert!(!processed.blur_hash.is_empty());
     }
 
+    /// 16x8 JPEG, red on the left and blue on the right, with an EXIF APP1
+    /// segment holding orientation 6 (rotate 90° clockwise) and a GPS IFD.
+    fn jpeg_with_exif() -> Vec<u8> {
+        let pixels = image::RgbImage::from_fn(16, 8, |x, _| {
+            if x < 8 {
+                image::Rgb([255, 0, 0])
+            } else {
+                image::Rgb([0, 0, 255])
+            }
+        });
+        let mut jpeg = Vec::new();
+        JpegEncoder::new_with_quality(&mut jpeg, 95)
+            .encode_image(&pixels)
+            .unwrap();
+
+        let mut tiff = b"MM\0\x2a".to_vec();
+        tiff.extend(8u32.to_be_bytes());
+        // IFD0: Orientation, pointer to the GPS IFD at offset 38.
+        tiff.extend(2u16.to_be_bytes());
+        tiff.extend([
+            0x01, 0x12, 0x00, 0x03, 0x00, 0x00, 0x00, 0x01, 0x00, 0x06, 0x00, 0x00,
+        ]);
+        tiff.extend([0x88, 0x25, 0x00, 0x04, 0x00, 0x00, 0x00, 0x01]);
+        tiff.extend(38u32.to_be_bytes());
+        tiff.extend(0u32.to_be_bytes());
+        // GPS IFD: GPSLatitudeRef = "N".
+        tiff.extend(1u16.to_be_bytes());
+        tiff.extend([
+            0x00, 0x01, 0x00, 0x02, 0x00, 0x00, 0x00, 0x02, b'N', 0x00, 0x00, 0x00,
+        ]);
+        tiff.extend(0u32.to_be_bytes());
+
+        let mut segment = b"Exif\0\0".to_vec();
+        segment.extend(tiff);
+        let mut bytes = jpeg[..2].to_vec();
+        bytes.extend([0xff, 0xe1]);
+        bytes.extend(((segment.len() + 2) as u16).to_be_bytes());
+        bytes.extend(segment);
+        bytes.extend(&jpeg[2..]);
+        bytes
+    }
+
+    fn jpeg_decoder(bytes: &[u8]) -> impl ImageDecoder + '_ {
+        image::ImageReader::with_format(Cursor::new(bytes), ImageFormat::Jpeg)
+            .into_decoder()
+            .unwrap()
+    }
+
+    #[test]
+    fn strips_exif_and_stores_image_upright() {
+        let original = jpeg_with_exif();
+        assert_eq!(
+            jpeg_decoder(&original).orientation().unwrap(),
+            Orientation::Rotate90
+        );
+
+        let processed = process_image(UploadImageDto {
+            content_type: "image/jpeg".to_string(),
+            bytes: original,
+            description: None,
+            focus: None,
+        })
+        .unwrap();
+
+        let mut decoder = jpeg_decoder(&processed.bytes);
+        assert!(decoder.exif_metadata().unwrap().is_none());
+        assert!(!processed
+            .bytes
+            .windows(6)
+            .any(|window| window == b"Exif\0\0"));
+        assert_eq!(decoder.dimensions(), (8, 16));
+        let upright = DynamicImage::from_decoder(decoder).unwrap().to_rgb8();
+        let top = upright.get_pixel(4, 2);
+        let bottom = upright.get_pixel(4, 13);
+        assert!(top[0] > 200 && top[2] < 60, "top should be red: {top:?}");
+        assert!(
+            bottom[2] > 200 && bottom[0] < 60,
+            "bottom should be blue: {bottom:?}"
+        );
+        assert_eq!(
+            processed.hash,
+            format!("{:x}", sha2::Sha256::digest(&processed.bytes))
+        );
+    }
+
+    fn upload_of(width: u32, height: u32) -> UploadImageDto {
+        let pixels = image::RgbImage::from_pixel(width, height, image::Rgb([40, 120, 200]));
+        let mut bytes = Vec::new();
+        PngEncoder::new(&mut bytes)
+            .write_image(
+                pixels.as_raw(),
+                width,
+                height,
+                image::ExtendedColorType::Rgb8,
+            )
+            .unwrap();
+        UploadImageDto {
+            content_type: "image/png".to_string(),
+            bytes,
+            description: None,
+            focus: None,
+        }
+    }
+
+    fn variant_size(processed: &ProcessedImage, kind: ImageVariantKind) -> (u32, u32) {
+        let variant = processed
+            .variants
+            .iter()
+            .find(|variant| variant.kind == kind)
+            .unwrap();
+        let decoded =
+            image::load_from_memory_with_format(&variant.bytes, ImageFormat::WebP).unwrap();
+        assert_eq!(decoded.dimensions(), (variant.width, variant.height));
+        (variant.width, variant.height)
+    }
+
+    #[test]
+    fn generates_renditions_without_upscaling() {
+        let large = process_image(upload_of(2000, 1000)).unwrap();
+        assert_eq!(variant_size(&large, ImageVariantKind::Avatar), (400, 400));
+        assert_eq!(variant_size(&large, ImageVariantKind::Header), (1500, 500));
+        assert_eq!(variant_size(&large, ImageVariantKind::Thumbnail), (160, 80));
+
+        let small = process_image(upload_of(300, 200)).unwrap();
+        assert_eq!(variant_size(&small, ImageVariantKind::Avatar), (200, 200));
+        assert_eq!(variant_size(&small, ImageVariantKind::Header), (300, 100));
+        assert_eq!(
+            variant_size(&small, ImageVariantKind::Thumbnail),
+            (160, 107)
+        );
+    }
+
+    fn webp_photo(lossy: bool) -> UploadImageDto {
+        let pixels = image::RgbImage::from_fn(256, 256, |x, y| {
+            let noise = ((x * 31 + y * 17) ^ (x * y)) as u8;
+            image::Rgb([noise, (x as u8).wrapping_add(noise / 4), y as u8])
+        });
+        let encoder = webp::Encoder::from_rgb(pixels.as_raw(), 256, 256);
+        let bytes = if lossy {
+            encoder.encode(75.0).to_vec()
+        } else {
+            encoder.encode_lossless().to_vec()
+        };
+        UploadImageDto {
+            content_type: "image/webp".to_string(),
+            bytes,
+            description: None,
+            focus: None,
+        }
+    }
+
+    #[test]
+    fn webp_is_re_encoded_lossy_or_lossless_like_its_source() {
+        let lossy = webp_photo(true);
+        let source_len = lossy.bytes.len();
+        let processed = process_image(lossy).unwrap();
+        assert!(is_lossy_webp(&processed.bytes));
+        assert!(
+            processed.bytes.len() < source_len * 2,
+            "lossy source grew from {source_len} to {} bytes",
+            processed.bytes.len()
+        );
+
+        let processed = process_image(webp_photo(false)).unwrap();
+        assert_eq!(processed.content_type, "image/webp");
+        assert!(!is_lossy_webp(&processed.bytes));
+    }
+
+    fn gif_of(frames: usize, delay_ms: u32) -> UploadImageDto {
+        let mut bytes = Vec::new();
+        {
+            let mut encoder = image::codecs::gif::GifEncoder::new(&mut bytes);
+            for index in 0..frames {
+                let shade = (index * 40 % 256) as u8;
+                let pixels = RgbaImage::from_pixel(32, 16, image::Rgba([shade, 80, 160, 255]));
+                encoder
+                    .encode_frame(image::Frame::from_parts(
+                        pixels,
+                        0,
+                        0,
+                        Delay::from_numer_denom_ms(delay_ms, 1),
+                    ))
+                    .unwrap();
+            }
+        }
+        UploadImageDto {
+            content_type: "image/gif".to_string(),
+            bytes,
+            description: None,
+            focus: None,
+        }
+    }
+
+    #[test]
+    fn animated_gif_is_stored_as_animated_webp_with_a_static_frame() {
+        let processed = process_image(gif_of(3, 100)).unwrap();
+
+        assert!(processed.animated);
+        assert_eq!(processed.content_type, "image/webp");
+        assert_eq!(processed.extension, "webp");
+        let decoder = WebPDecoder::new(Cursor::new(&processed.bytes)).unwrap();
+        assert!(decoder.has_animation());
+        assert_eq!(decoder.into_frames().count(), 3);
+        assert_eq!(variant_size(&processed, ImageVariantKind::Static), (32, 16));
+        assert_eq!(variant_size(&processed, ImageVariantKind::Avatar), (16, 16));
+    }
+
+    #[test]
+    fn single_frame_gif_is_stored_as_still_png() {
+        let processed = process_image(gif_of(1, 100)).unwrap();
+
+        assert!(!processed.animated);
+        assert_eq!(processed.content_type, "image/png");
+        assert!(processed
+            .variants
+            .iter()
+            .all(|variant| variant.kind != ImageVariantKind::Static));
+    }
+
+    #[test]
+    fn rejects_animations_over_the_limits() {
+        let too_many_frames = process_image(gif_of(MAX_ANIMATION_FRAMES + 1, 20));
+        assert!(too_many_frames.is_err());
+
+        let too_long = process_image(gif_of(250, 250));
+        assert!(too_long.is_err());
+        assert!(process_image(gif_of(240, 250)).is_ok());
+    }
+
+    #[test]
+    fn zero_frame_delays_play_at_100_ms() {
+        assert_eq!(frame_delay_ms(Delay::from_numer_denom_ms(0, 1)), 100);
+        assert_eq!(frame_delay_ms(Delay::from_numer_denom_ms(10, 1)), 100);
+        assert_eq!(frame_delay_ms(Delay::from_numer_denom_ms(40, 1)), 40);
+    }
+
+    #[test]
+    fn identical_uploads_share_a_hash() {
+        let first = process_image(upload_of(300, 200)).unwrap();
+        let second = process_image(upload_of(300, 200)).unwrap();
+        let other = process_image(upload_of(200, 300)).unwrap();
+        assert_eq!(first.hash, second.hash);
+        assert_ne!(first.hash, other.hash);
+    }
+
+    #[test]
+    fn upload_metadata_is_validated_and_blank_text_dropped() {
+        let mut dto = upload_of(2, 2);
+        dto.description = Some("  ".to_string());
+        dto.focus = Some(ImageFocusDto { x: 0.5, y: -1.0 });
+        let (description, focus) = upload_metadata(dto.description.as_deref(), dto.focus).unwrap();
+        assert_eq!(description, None);
+        assert_eq!(focus, Some(ImageFocus::new(0.5, -1.0)));
+
+        dto.focus = Some(ImageFocusDto { x: 1.5, y: 0.0 });
+        assert!(upload_metadata(dto.description.as_deref(), dto.focus).is_err());
+
+        dto.focus = None;
+        dto.description = Some("a".repeat(ImageDescription::MAX_LENGTH + 1));
+        assert!(upload_metadata(dto.description.as_deref(), dto.focus).is_err());
+    }
+
+    #[test]
+    fn quota_counts_existing_usage_and_the_upload() {
+        let quota = MediaQuota { max_bytes: 1000 };
+
+        assert!(check_quota(&quota, 600, 400).is_ok());
+        assert!(check_quota(&quota, 600, 401).is_err());
+        assert!(check_quota(&quota, u64::MAX, 1).is_err());
+    }
+
     #[test]
     fn rejects_zero_and_excessive_dimensions() {
         assert!(check_dimensions(0, 100).is_err());
@@ -0,0 +1,407 @@
+use crate::dto::personal_access_token::{
+    CreatePersonalAccessTokenDto, PersonalAccessTokenDto, PersonalAccessTokenGrantDto,
+};
+use crate::permission::{account_view, granted_scopes, is_personal_access_token};
+use error_stack::Report;
+use kernel::interfaces::database::DatabaseConnection;
+use kernel::interfaces::permission::{DependOnPermissionChecker, PermissionChecker, Scope};
+use kernel::interfaces::read_model::{AccountQuery, DependOnAccountQuery};
+use kernel::interfaces::repository::{
+    AuthAccountRepository, AuthHostRepository, DependOnAuthAccountRepository,
+    DependOnAuthHostRepository, DependOnPersonalAccessTokenRepository,
+    PersonalAccessTokenRepository,
+};
+use kernel::prelude::entity::{
+    Account, AccountId, AuthAccountId, Nanoid, PersonalAccessToken, PersonalAccessTokenId,
+};
+use kernel::KernelError;
+use sha2::Digest;
+use std::future::Future;
+use time::OffsetDateTime;
+
+const MAX_TOKEN_NAME_LENGTH: usize = 100;
+const MAX_TOKEN_ACCOUNTS: usize = 100;
+
+fn validation_error(message: String) -> Report<KernelError> {
+    Report::new(KernelError::Validation).attach_printable(message)
+}
+
+fn token_hash(token: &str) -> String {
+    format!("{:x}", sha2::Sha256::digest(token.as_bytes()))
+}
+
+/// A personal access token could otherwise mint a broader one.
+fn ensure_not_personal_access_token() -> error_stack::Result<(), KernelError> {
+    if is_personal_access_token() {
+        return Err(Report::new(KernelError::PermissionDenied)
+            .attach_printable("Personal access tokens cannot manage personal access tokens"));
+    }
+    Ok(())
+}
+
+fn parse_scopes(scopes: Vec<String>) -> error_stack::Result<Vec<Scope>, KernelError> {
+    let mut parsed = scopes
+        .iter()
+        .map(|scope| {
+            Scope::parse(scope).ok_or_else(|| validation_error(format!("Unknown scope: {scope}")))
+        })
+        .collect::<error_stack::Result<Vec<_>, _>>()?;
+    if parsed.is_empty() {
+        return Err(validation_error(
+            "A personal access token needs at least one scope".to_string(),
+        ));
+    }
+    parsed.sort_by_key(|scope| scope.as_str());
+    parsed.dedup();
+    // A token can only hand on scopes it was granted itself.
+    if let Some(missing) = granted_scopes().and_then(|granted| {
+        parsed
+            .iter()
+            .find(|scope| !granted.contains(scope))
+            .copied()
+    }) {
+        return Err(
+            Report::new(KernelError::PermissionDenied).attach_printable(format!(
+                "Access token lacks the {} scope it tries to grant",
+                missing.as_str()
+            )),
+        );
+    }
+    Ok(parsed)
+}
+
+fn parse_token_id(id: &str) -> error_stack::Result<PersonalAccessTokenId, KernelError> {
+    id.parse::<i64>()
+        .map(PersonalAccessTokenId::new)
+        .map_err(|_| validation_error(format!("Invalid personal access token ID: {id}")))
+}
+
+/// Account nanoids of `account_ids`; accounts that are gone are left out.
+async fn account_nanoids<T>(
+    deps: &T,
+    account_ids: &[AccountId],
+) -> error_stack::Result<Vec<String>, KernelError>
+where
+    T: DependOnAccountQuery + ?Sized,
+{
+    let mut executor = deps.database_connection().connection().await?;
+    let mut nanoids = Vec::with_capacity(account_ids.len());
+    for account_id in account_ids {
+        if let Some(account) = deps
+            .account_query()
+            .find_by_id_unfiltered(&mut executor, account_id)
+            .await?
+        {
+            nanoids.push(account.nanoid().as_ref().to_string());
+        }
+    }
+    Ok(nanoids)
+}
+
+async fn to_dto<T>(
+    deps: &T,
+    token: PersonalAccessToken,
+    secret: Option<String>,
+) -> error_stack::Result<PersonalAccessTokenDto, KernelError>
+where
+    T: DependOnAccountQuery + ?Sized,
+{
+    let account_ids = match &token.account_ids {
+        Some(account_ids) => Some(account_nanoids(deps, account_ids).await?),
+        None => None,
+    };
+    Ok(PersonalAccessTokenDto {
+        id: token.id.as_ref().to_string(),
+        name: token.name,
+        token: secret,
+        scopes: token
+            .scopes
+            .iter()
+            .map(|scope| scope.as_str().to_string())
+            .collect(),
+        account_ids,
+        expires_at: token.expires_at,
+        last_used_at: token.last_used_at,
+        created_at: token.created_at,
+    })
+}
+
+pub trait CreatePersonalAccessTokenUseCase:
+    'static
+    + Sync
+    + Send
+    + DependOnPersonalAccessTokenRepository
+    + DependOnAccountQuery
+    + DependOnPermissionChecker
+{
+    /// The response is the only place the token is ever returned.
+    fn create_personal_access_token(
+        &self,
+        auth_account_id: &AuthAccountId,
+        dto: CreatePersonalAccessTokenDto,
+    ) -> impl Future<Output = error_stack::Result<PersonalAccessTokenDto, KernelError>> + Send {
+        async move {
+            ensure_not_personal_access_token()?;
+
+            let name = dto.name.trim().to_string();
+            if name.is_empty() || name.chars().count() > MAX_TOKEN_NAME_LENGTH {
+                return Err(validation_error(format!(
+                    "Token name must be 1 to {MAX_TOKEN_NAME_LENGTH} characters"
+                )));
+            }
+            let scopes = parse_scopes(dto.scopes)?;
+            let now = OffsetDateTime::now_utc();
+            if dto.expires_at.is_some_and(|expires_at| expires_at <= now) {
+                return Err(validation_error(
+                    "Token expiry must be in the future".to_string(),
+                ));
+            }
+
+            let mut executor = self.database_connection().connection().await?;
+            let account_ids = match dto.account_ids {
+                None => None,
+                Some(nanoids) if nanoids.is_empty() || nanoids.len() > MAX_TOKEN_ACCOUNTS => {
+                    return Err(validation_error(format!(
+                        "A token can be limited to 1 to {MAX_TOKEN_ACCOUNTS} accounts; omit account_ids to allow every account"
+                    )));
+                }
+                Some(nanoids) => {
+                    let mut account_ids = Vec::with_capacity(nanoids.len());
+                    for nanoid in nanoids {
+                        let nanoid = Nanoid::<Account>::new(nanoid);
+                        let account = self
+                            .account_query()
+                            .find_by_nanoid(&mut executor, &nanoid)
+                            .await?;
+                        // Accounts the owner cannot see are reported like
+                        // missing ones.
+                        let visible = match &account {
+                            Some(account) => {
+                                self.permission_checker()
+                                    .satisfies(auth_account_id, &account_view(account.id()))
+                                    .await?
+                            }
+                            None => false,
+                        };
+                        let account = account.filter(|_| visible).ok_or_else(|| {
+                            Report::new(KernelError::NotFound).attach_printable(format!(
+                                "Account not found with nanoid: {}",
+                                nanoid.as_ref()
+                            ))
+                        })?;
+                        if !account_ids.contains(account.id()) {
+                            account_ids.push(account.id().clone());
+                        }
+                    }
+                    Some(account_ids)
+                }
+            };
+
+            let secret = PersonalAccessToken::generate_token();
+            let token = PersonalAccessToken {
+                id: PersonalAccessTokenId::default(),
+                owner: auth_account_id.clone(),
+                name,
+                token_hash: token_hash(&secret),
+                scopes,
+                account_ids,
+                expires_at: dto.expires_at,
+                last_used_at: None,
+                created_at: now,
+            };
+            self.personal_access_token_repository()
+                .create(&mut executor, &token)
+                .await?;
+            to_dto(self, token, Some(secret)).await
+        }
+    }
+}
+
+impl<T> CreatePersonalAccessTokenUseCase for T where
+    T: 'static
+        + Sync
+        + Send
+        + DependOnPersonalAccessTokenRepository
+        + DependOnAccountQuery
+        + DependOnPermissionChecker
+{
+}
+
+pub trait GetPersonalAccessTokensUseCase:
+    'static + Sync + Send + DependOnPersonalAccessTokenRepository + DependOnAccountQuery
+{
+    fn get_personal_access_tokens(
+        &self,
+        auth_account_id: &AuthAccountId,
+    ) -> impl Future<Output = error_stack::Result<Vec<PersonalAccessTokenDto>, KernelError>> + Send
+    {
+        async move {
+            ensure_not_personal_access_token()?;
+
+            let mut executor = self.database_connection().connection().await?;
+            let tokens = self
+                .personal_access_token_repository()
+                .find_by_owner(&mut executor, auth_account_id)
+                .await?;
+            let mut dtos = Vec::with_capacity(tokens.len());
+            for token in tokens {
+                dtos.push(to_dto(self, token, None).await?);
+            }
+            Ok(dtos)
+        }
+    }
+}
+
+impl<T> GetPersonalAccessTokensUseCase for T where
+    T: 'static + Sync + Send + DependOnPersonalAccessTokenRepository + DependOnAccountQuery
+{
+}
+
+pub trait RevokePersonalAccessTokenUseCase:
+    'static + Sync + Send + DependOnPersonalAccessTokenRepository
+{
+    fn revoke_personal_access_token(
+        &self,
+        auth_account_id: &AuthAccountId,
+        token_id: String,
+    ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send {
+        async move {
+            ensure_not_personal_access_token()?;
+
+            let id = parse_token_id(&token_id)?;
+            let mut executor = self.database_connection().connection().await?;
+            let deleted = self
+                .personal_access_token_repository()
+                .delete(&mut executor, &id, auth_account_id)
+                .await?;
+            if !deleted {
+                return Err(Report::new(KernelError::NotFound)
+                    .attach_printable(format!("Personal access token not found: {token_id}")));
+            }
+            Ok(())
+        }
+    }
+}
+
+impl<T> RevokePersonalAccessTokenUseCase for T where
+    T: 'static + Sync + Send + DependOnPersonalAccessTokenRepository
+{
+}
+
+pub trait AuthenticatePersonalAccessTokenUseCase:
+    'static
+    + Sync
+    + Send
+    + DependOnPersonalAccessTokenRepository
+    + DependOnAuthAccountRepository
+    + DependOnAuthHostRepository
+{
+    /// `None` for unknown, revoked and expired tokens. Records the use.
+    fn authenticate_personal_access_token(
+        &self,
+        token: &str,
+    ) -> impl Future<Output = error_stack::Result<Option<PersonalAccessTokenGrantDto>, KernelError>> + Send
+    {
+        async move {
+            let mut executor = self.database_connection().connection().await?;
+            let Some(token) = self
+                .personal_access_token_repository()
+                .find_by_token_hash(&mut executor, &token_hash(token))
+                .await?
+            else {
+                return Ok(None);
+            };
+            let now = OffsetDateTime::now_utc();
+            if token.is_expired(now) {
+                return Ok(None);
+            }
+            let Some(auth_account) = self
+                .auth_account_repository()
+                .find_by_id(&mut executor, &token.owner)
+                .await?
+            else {
+                return Ok(None);
+            };
+            let auth_account = auth_account.into_destruct();
+            let auth_host = self
+                .auth_host_repository()
+                .find_by_id(&mut executor, &auth_account.host)
+                .await?
+                .ok_or_else(|| {
+                    Report::new(KernelError::Internal)
+                        .attach_printable("Auth account refers to a missing auth host")
+                })?;
+            self.personal_access_token_repository()
+                .touch(&mut executor, &token.id, now)
+                .await?;
+
+            Ok(Some(PersonalAccessTokenGrantDto {
+                token_id: token.id.as_ref().to_string(),
+                issuer: auth_host.into_destruct().url.into(),
+                subject: auth_account.client_id.into(),
+                scopes: token.scopes,
+                account_ids: token.account_ids,
+                expires_at: token.expires_at,
+            }))
+        }
+    }
+}
+
+impl<T> AuthenticatePersonalAccessTokenUseCase for T where
+    T: 'static
+        + Sync
+        + Send
+        + DependOnPersonalAccessTokenRepository
+        + DependOnAuthAccountRepository
+        + DependOnAuthHostRepository
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_are_hashed_and_prefixed() {
+        let token = PersonalAccessToken::generate_token();
+        assert!(token.starts_with(PersonalAccessToken::PREFIX));
+        assert_ne!(token, PersonalAccessToken::generate_token());
+        assert_eq!(token_hash(&token).len(), 64);
+        assert_eq!(token_hash(&token), token_hash(&token));
+    }
+
+    #[tokio::test]
+    async fn requested_scopes_must_be_known_and_granted() {
+        crate::permission::without_scope_restriction(async {
+            let parsed = parse_scopes(vec![
+                "sign".to_string(),
+                "accounts:read".to_string(),
+                "sign".to_string(),
+            ])
+            .unwrap();
+            assert_eq!(parsed, vec![Scope::AccountsRead, Scope::Sign]);
+            assert_eq!(
+                parse_scopes(vec!["openid".to_string()])
+                    .unwrap_err()
+                    .current_context(),
+                &KernelError::Validation
+            );
+            assert_eq!(
+                parse_scopes(vec![]).unwrap_err().current_context(),
+                &KernelError::Validation
+            );
+        })
+        .await;
+
+        crate::permission::with_granted_scopes(vec![Scope::AccountsRead], async {
+            assert!(parse_scopes(vec!["accounts:read".to_string()]).is_ok());
+            assert_eq!(
+                parse_scopes(vec!["sign".to_string()])
+                    .unwrap_err()
+                    .current_context(),
+                &KernelError::PermissionDenied
+            );
+        })
+        .await;
+    }
+}
@@ -71,6 +71,28 @@ mod tests {
                 }
             }
         }
+
+        async fn list_account_members(
+            &self,
+            _account_id: &kernel::prelude::entity::AccountId,
+        ) -> error_stack::Result<Vec<kernel::interfaces::permission::AccountMember>, KernelError>
+        {
+            Ok(Vec::new())
+        }
+
+        async fn list_instance_role_holders(
+            &self,
+        ) -> error_stack::Result<Vec<kernel::interfaces::permission::InstanceRoleHolder>, KernelError>
+        {
+            Ok(Vec::new())
+        }
+
+        async fn list_custom_role_members(
+            &self,
+            _role_id: &kernel::prelude::entity::CustomRoleId,
+        ) -> error_stack::Result<Vec<AuthAccountId>, KernelError> {
+            Ok(Vec::new())
+        }
     }
 
     struct TestDeps {
@@ -0,0 +1,264 @@
+use crate::change_feed::{ACCOUNT_AGGREGATE, METADATA_AGGREGATE, PROFILE_AGGREGATE};
+use crate::dto::webhook::{
+    CreateWebhookSubscriptionDto, WebhookDeliveryDto, WebhookSubscriptionDto,
+};
+use crate::permission::{check_permission, instance_administrate};
+use error_stack::Report;
+use kernel::interfaces::database::DatabaseConnection;
+use kernel::interfaces::permission::DependOnPermissionChecker;
+use kernel::interfaces::repository::{
+    DependOnWebhookDeliveryRepository, DependOnWebhookSubscriptionRepository,
+    WebhookDeliveryRepository, WebhookSubscriptionRepository,
+};
+use kernel::prelude::entity::{AuthAccountId, WebhookSubscription, WebhookSubscriptionId};
+use kernel::KernelError;
+use std::future::Future;
+use time::OffsetDateTime;
+
+const MAX_WEBHOOK_URL_LENGTH: usize = 2048;
+const MIN_WEBHOOK_SECRET_LENGTH: usize = 16;
+const MAX_WEBHOOK_DELIVERY_PAGE: usize = 100;
+
+fn validation_error(message: String) -> Report<KernelError> {
+    Report::new(KernelError::Validation).attach_printable(message)
+}
+
+fn validate_url(url: &str) -> error_stack::Result<(), KernelError> {
+    if url.len() > MAX_WEBHOOK_URL_LENGTH {
+        return Err(validation_error(format!(
+            "Webhook URL must not exceed {MAX_WEBHOOK_URL_LENGTH} characters"
+        )));
+    }
+    let parsed = reqwest::Url::parse(url)
+        .map_err(|e| validation_error(format!("Webhook URL is invalid: {e}")))?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        return Err(validation_error(
+            "Webhook URL must be an absolute http(s) URL".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Accepts `<aggregate>.<event>` or `<aggregate>.*` for the aggregates the
+/// event logs publish.
+fn validate_event_type(event_type: &str) -> error_stack::Result<(), KernelError> {
+    let valid = event_type
+        .split_once('.')
+        .is_some_and(|(aggregate, event)| {
+            [ACCOUNT_AGGREGATE, PROFILE_AGGREGATE, METADATA_AGGREGATE].contains(&aggregate)
+                && !event.is_empty()
+                && (event == "*" || event.chars().all(|c| c.is_ascii_lowercase() || c == '_'))
+        });
+    if valid {
+        Ok(())
+    } else {
+        Err(validation_error(format!(
+            "Unknown webhook event type: {event_type}"
+        )))
+    }
+}
+
+fn parse_subscription_id(id: &str) -> error_stack::Result<WebhookSubscriptionId, KernelError> {
+    id.parse::<i64>()
+        .map(WebhookSubscriptionId::new)
+        .map_err(|_| validation_error(format!("Invalid webhook subscription ID: {id}")))
+}
+
+pub trait CreateWebhookSubscriptionUseCase:
+    'static + Sync + Send + DependOnWebhookSubscriptionRepository + DependOnPermissionChecker
+{
+    /// The response is the only place the signing secret is ever returned.
+    fn create_webhook_subscription(
+        &self,
+        auth_account_id: &AuthAccountId,
+        dto: CreateWebhookSubscriptionDto,
+    ) -> impl Future<Output = error_stack::Result<WebhookSubscriptionDto, KernelError>> + Send {
+        async move {
+            check_permission(self, auth_account_id, &instance_administrate()).await?;
+
+            validate_url(&dto.url)?;
+            for event_type in &dto.event_types {
+                validate_event_type(event_type)?;
+            }
+            let secret = match dto.secret {
+                Some(secret) if secret.len() < MIN_WEBHOOK_SECRET_LENGTH => {
+                    return Err(validation_error(format!(
+                        "Webhook secret must be at least {MIN_WEBHOOK_SECRET_LENGTH} characters"
+                    )));
+                }
+                Some(secret) => secret,
+                None => WebhookSubscription::generate_secret(),
+            };
+            let mut event_types = dto.event_types;
+            event_types.sort();
+            event_types.dedup();
+
+            let subscription = WebhookSubscription {
+                id: WebhookSubscriptionId::default(),
+                url: dto.url,
+                event_types,
+                secret,
+                created_at: OffsetDateTime::now_utc(),
+            };
+            let mut executor = self.database_connection().connection().await?;
+            self.webhook_subscription_repository()
+                .create(&mut executor, &subscription)
+                .await?;
+            Ok(WebhookSubscriptionDto::with_secret(subscription))
+        }
+    }
+}
+
+impl<T> CreateWebhookSubscriptionUseCase for T where
+    T: 'static + Sync + Send + DependOnWebhookSubscriptionRepository + DependOnPermissionChecker
+{
+}
+
+pub trait GetWebhookSubscriptionsUseCase:
+    'static + Sync + Send + DependOnWebhookSubscriptionRepository + DependOnPermissionChecker
+{
+    fn get_webhook_subscriptions(
+        &self,
+        auth_account_id: &AuthAccountId,
+    ) -> impl Future<Output = error_stack::Result<Vec<WebhookSubscriptionDto>, KernelError>> + Send
+    {
+        async move {
+            check_permission(self, auth_account_id, &instance_administrate()).await?;
+
+            let mut executor = self.database_connection().connection().await?;
+            let subscriptions = self
+                .webhook_subscription_repository()
+                .find_all(&mut executor)
+                .await?;
+            Ok(subscriptions
+                .into_iter()
+                .map(WebhookSubscriptionDto::without_secret)
+                .collect())
+        }
+    }
+}
+
+impl<T> GetWebhookSubscriptionsUseCase for T where
+    T: 'static + Sync + Send + DependOnWebhookSubscriptionRepository + DependOnPermissionChecker
+{
+}
+
+pub trait DeleteWebhookSubscriptionUseCase:
+    'static + Sync + Send + DependOnWebhookSubscriptionRepository + DependOnPermissionChecker
+{
+    fn delete_webhook_subscription(
+        &self,
+        auth_account_id: &AuthAccountId,
+        subscription_id: String,
+    ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send {
+        async move {
+            check_permission(self, auth_account_id, &instance_administrate()).await?;
+
+            let id = parse_subscription_id(&subscription_id)?;
+            let mut executor = self.database_connection().connection().await?;
+            self.webhook_subscription_repository()
+                .find_by_id(&mut executor, &id)
+                .await?
+                .ok_or_else(|| {
+                    Report::new(KernelError::NotFound).attach_printable(format!(
+                        "Webhook subscription not found: {subscription_id}"
+                    ))
+                })?;
+            self.webhook_subscription_repository()
+                .delete(&mut executor, &id)
+                .await
+        }
+    }
+}
+
+impl<T> DeleteWebhookSubscriptionUseCase for T where
+    T: 'static + Sync + Send + DependOnWebhookSubscriptionRepository + DependOnPermissionChecker
+{
+}
+
+pub trait GetWebhookDeliveriesUseCase:
+    'static
+    + Sync
+    + Send
+    + DependOnWebhookSubscriptionRepository
+    + DependOnWebhookDeliveryRepository
+    + DependOnPermissionChecker
+{
+    /// Delivery log of one subscription, newest first. `cursor` is the ID of
+    /// the last delivery of the previous page.
+    fn get_webhook_deliveries(
+        &self,
+        auth_account_id: &AuthAccountId,
+        subscription_id: String,
+        limit: Option<u32>,
+        cursor: Option<String>,
+    ) -> impl Future<Output = error_stack::Result<Vec<WebhookDeliveryDto>, KernelError>> + Send
+    {
+        async move {
+            check_permission(self, auth_account_id, &instance_administrate()).await?;
+
+            let id = parse_subscription_id(&subscription_id)?;
+            let cursor = cursor
+                .map(|cursor| {
+                    cursor.parse::<i64>().map_err(|_| {
+                        validation_error(format!("Invalid webhook delivery cursor: {cursor}"))
+                    })
+                })
+                .transpose()?;
+            let limit = limit
+                .map(|limit| limit as usize)
+                .unwrap_or(20)
+                .clamp(1, MAX_WEBHOOK_DELIVERY_PAGE);
+
+            let mut executor = self.database_connection().connection().await?;
+            self.webhook_subscription_repository()
+                .find_by_id(&mut executor, &id)
+                .await?
+                .ok_or_else(|| {
+                    Report::new(KernelError::NotFound).attach_printable(format!(
+                        "Webhook subscription not found: {subscription_id}"
+                    ))
+                })?;
+            let deliveries = self
+                .webhook_delivery_repository()
+                .find_by_subscription_id(&mut executor, &id, limit, cursor)
+                .await?;
+            Ok(deliveries
+                .into_iter()
+                .map(WebhookDeliveryDto::from)
+                .collect())
+        }
+    }
+}
+
+impl<T> GetWebhookDeliveriesUseCase for T where
+    T: 'static
+        + Sync
+        + Send
+        + DependOnWebhookSubscriptionRepository
+        + DependOnWebhookDeliveryRepository
+        + DependOnPermissionChecker
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_type_filter_accepts_known_aggregates_only() {
+        assert!(validate_event_type("account.suspended").is_ok());
+        assert!(validate_event_type("profile.*").is_ok());
+        assert!(validate_event_type("metadata.deleted").is_ok());
+        assert!(validate_event_type("auth_account.created").is_err());
+        assert!(validate_event_type("account.").is_err());
+        assert!(validate_event_type("*").is_err());
+    }
+
+    #[test]
+    fn url_must_be_absolute_http() {
+        assert!(validate_url("https://hooks.example.com/emumet").is_ok());
+        assert!(validate_url("ftp://hooks.example.com/emumet").is_err());
+        assert!(validate_url("/relative").is_err());
+    }
+}
@@ -0,0 +1,52 @@
+//! Metric names recorded by the use cases through the `metrics` facade.
+//!
+//! Nothing is exported unless the binary installs a recorder; the server
+//! serves these next to its own series on `GET /metrics`.
+
+use kernel::KernelError;
+
+/// Counter labelled by `type` and `outcome` (`processed`, `ignored`,
+/// `rejected` or `failed`).
+pub const INBOX_ACTIVITIES_TOTAL: &str = "emumet_inbox_activities_total";
+
+/// Counter labelled by `outcome` (`delivered`, `rejected` when the peer
+/// answered with a non-2xx status, `failed` otherwise) and `peer`, the host
+/// of the remote inbox.
+pub const OUTBOX_DELIVERIES_TOTAL: &str = "emumet_outbox_deliveries_total";
+
+/// Activity types that keep their own `type` label; anything else a peer
+/// sends is counted as `other` so the label set stays bounded.
+const KNOWN_ACTIVITY_TYPES: &[&str] = &[
+    "Accept", "Add", "Announce", "Block", "Create", "Delete", "Follow", "Like", "Move", "Reject",
+    "Remove", "Undo", "Update",
+];
+
+pub(crate) fn activity_type_label(activity_type: &str) -> &'static str {
+    KNOWN_ACTIVITY_TYPES
+        .iter()
+        .find(|known| **known == activity_type)
+        .copied()
+        .unwrap_or("other")
+}
+
+pub(crate) fn failure_outcome(error: &KernelError) -> &'static str {
+    match error {
+        KernelError::Rejected
+        | KernelError::Validation
+        | KernelError::NotFound
+        | KernelError::PermissionDenied => "rejected",
+        KernelError::Concurrency | KernelError::Timeout | KernelError::Internal => "failed",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_activity_types_share_one_label() {
+        assert_eq!(activity_type_label("Follow"), "Follow");
+        assert_eq!(activity_type_label("EmojiReact"), "other");
+        assert_eq!(activity_type_label("follow"), "other");
+    }
+}
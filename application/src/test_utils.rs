@@ -0,0 +1,81 @@
+//! Helpers shared by the tests of the event-log tailers (change feed,
+//! webhook fan-out).
+
+use driver::database::PostgresDatabase;
+use kernel::interfaces::database::DatabaseConnection;
+use kernel::interfaces::event_store::{AccountEventStore, DependOnAccountEventStore};
+use kernel::prelude::entity::{
+    Account, AccountId, AccountIsBot, AccountName, AuthAccountId, EventVersion, Nanoid,
+};
+use sqlx::PgConnection;
+
+/// Point the tailer `name` right before the current account log head so the
+/// test only has to process its own events.
+pub(crate) async fn reset_checkpoint_to_head(db: &PostgresDatabase, name: &str) {
+    let mut conn = db.connection().await.unwrap();
+    let con: &mut PgConnection = &mut conn;
+    sqlx::query(
+        "INSERT INTO projection_checkpoints (projector_name, last_seq, updated_at) \
+         VALUES ($1, (SELECT COALESCE(MAX(seq), 0) FROM account_events), now()) \
+         ON CONFLICT (projector_name) DO UPDATE SET last_seq = EXCLUDED.last_seq",
+    )
+    .bind(name)
+    .execute(con)
+    .await
+    .unwrap();
+}
+
+/// Persist `account.created` followed by `account.suspended` for a new account.
+pub(crate) async fn persist_created_and_suspended(db: &PostgresDatabase, account_id: &AccountId) {
+    let mut conn = db.connection().await.unwrap();
+    let created = db
+        .account_event_store()
+        .persist_and_transform(
+            &mut conn,
+            Account::create(
+                account_id.clone(),
+                AccountName::new(kernel::test_utils::unique_account_name()),
+                AccountIsBot::new(false),
+                Nanoid::default(),
+                AuthAccountId::default(),
+            ),
+        )
+        .await
+        .unwrap();
+    db.account_event_store()
+        .persist(
+            &mut conn,
+            &Account::suspend(
+                account_id.clone(),
+                "spam".to_string(),
+                None,
+                EventVersion::new(*created.version.as_ref()),
+            ),
+        )
+        .await
+        .unwrap();
+}
+
+/// Highest seq of the events persisted for `account_id`.
+pub(crate) async fn last_account_event_seq(db: &PostgresDatabase, account_id: &AccountId) -> i64 {
+    let mut conn = db.connection().await.unwrap();
+    let con: &mut PgConnection = &mut conn;
+    let (seq,): (i64,) = sqlx::query_as("SELECT MAX(seq) FROM account_events WHERE id = $1")
+        .bind(account_id.as_ref())
+        .fetch_one(con)
+        .await
+        .unwrap();
+    seq
+}
+
+/// Delete the events a test persisted for `account_id`, so they do not pile
+/// up in the window every other tailer test has to scan.
+pub(crate) async fn delete_account_events(db: &PostgresDatabase, account_id: &AccountId) {
+    let mut conn = db.connection().await.unwrap();
+    let con: &mut PgConnection = &mut conn;
+    sqlx::query("DELETE FROM account_events WHERE id = $1")
+        .bind(account_id.as_ref())
+        .execute(con)
+        .await
+        .unwrap();
+}
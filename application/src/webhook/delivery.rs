@@ -0,0 +1,216 @@
+use super::signature::{
+    sign_webhook_payload, WEBHOOK_EVENT_HEADER, WEBHOOK_ID_HEADER, WEBHOOK_SIGNATURE_HEADER,
+    WEBHOOK_TIMESTAMP_HEADER,
+};
+use crate::service::activitypub::fetch::{client_for_url, validate_fetch_url};
+use kernel::interfaces::database::{DatabaseConnection, DependOnDatabaseConnection};
+use kernel::interfaces::repository::{
+    DependOnWebhookDeliveryRepository, DependOnWebhookSubscriptionRepository,
+    WebhookDeliveryRepository, WebhookSubscriptionRepository,
+};
+use kernel::prelude::entity::{WebhookDelivery, WebhookSubscription};
+use kernel::KernelError;
+use reqwest::header::CONTENT_TYPE;
+use std::collections::HashMap;
+use std::future::Future;
+use time::{Duration, OffsetDateTime};
+
+/// Deliveries attempted per poll.
+pub const WEBHOOK_DELIVERY_BATCH_LIMIT: usize = 100;
+
+/// Attempts before a delivery is marked failed for good.
+pub const WEBHOOK_MAX_ATTEMPTS: i32 = 8;
+
+/// How long a claimed delivery stays invisible to other workers. Longer than
+/// a full batch of sequential POSTs can take with their 10 second timeout.
+pub const WEBHOOK_DELIVERY_LEASE: Duration = Duration::minutes(30);
+
+/// Finished deliveries deleted per prune.
+pub const WEBHOOK_PRUNE_BATCH_LIMIT: usize = 1000;
+
+const WEBHOOK_INITIAL_BACKOFF: Duration = Duration::seconds(30);
+const WEBHOOK_MAX_BACKOFF: Duration = Duration::hours(6);
+
+/// When to retry after `attempts` failed attempts, or `None` to give up.
+///
+/// The delay doubles per attempt starting at 30 seconds, capped at 6 hours.
+pub fn webhook_retry_at(attempts: i32, now: OffsetDateTime) -> Option<OffsetDateTime> {
+    if attempts >= WEBHOOK_MAX_ATTEMPTS {
+        return None;
+    }
+    let exponent = attempts.saturating_sub(1).clamp(0, 20) as u32;
+    let delay = WEBHOOK_INITIAL_BACKOFF
+        .checked_mul(2_i32.pow(exponent))
+        .unwrap_or(WEBHOOK_MAX_BACKOFF)
+        .min(WEBHOOK_MAX_BACKOFF);
+    Some(now + delay)
+}
+
+/// Outcome of one POST: the status code when a response arrived.
+struct AttemptFailure {
+    status_code: Option<i32>,
+    error: String,
+}
+
+/// Drains due webhook deliveries.
+///
+/// Any 2xx response marks the delivery delivered. Everything else (non-2xx,
+/// network error, SSRF rejection of the target) counts as a failed attempt
+/// and is rescheduled by `webhook_retry_at`.
+pub trait DeliverDueWebhooks:
+    DependOnDatabaseConnection
+    + DependOnWebhookSubscriptionRepository
+    + DependOnWebhookDeliveryRepository
+{
+    /// Run one poll. Returns the number of deliveries attempted.
+    fn deliver_due_webhooks(
+        &self,
+    ) -> impl Future<Output = error_stack::Result<usize, KernelError>> + Send {
+        async move {
+            // Claim the batch and release the connection before any POST, so
+            // a slow endpoint does not hold a pooled connection.
+            let (due, subscriptions) = {
+                let mut executor = self.database_connection().connection().await?;
+                let now = OffsetDateTime::now_utc();
+                let due = self
+                    .webhook_delivery_repository()
+                    .claim_due(
+                        &mut executor,
+                        now,
+                        now + WEBHOOK_DELIVERY_LEASE,
+                        WEBHOOK_DELIVERY_BATCH_LIMIT,
+                    )
+                    .await?;
+                if due.is_empty() {
+                    return Ok(0);
+                }
+                let subscriptions: HashMap<i64, WebhookSubscription> = self
+                    .webhook_subscription_repository()
+                    .find_all(&mut executor)
+                    .await?
+                    .into_iter()
+                    .map(|subscription| (*subscription.id.as_ref(), subscription))
+                    .collect();
+                (due, subscriptions)
+            };
+
+            let attempted = due.len();
+            for delivery in due {
+                // Deleting a subscription cascades to its deliveries; a row
+                // claimed just before that has nowhere to go.
+                let Some(subscription) = subscriptions.get(delivery.subscription_id.as_ref())
+                else {
+                    continue;
+                };
+                let attempts = delivery.attempts + 1;
+                let outcome = post_webhook(subscription, &delivery).await;
+                let mut executor = self.database_connection().connection().await?;
+                match outcome {
+                    Ok(status_code) => {
+                        self.webhook_delivery_repository()
+                            .mark_delivered(&mut executor, &delivery.id, status_code)
+                            .await?;
+                    }
+                    Err(failure) => {
+                        let retry_at = webhook_retry_at(attempts, OffsetDateTime::now_utc());
+                        if retry_at.is_none() {
+                            tracing::warn!(
+                                delivery_id = delivery.id.as_ref(),
+                                attempts,
+                                error = %failure.error,
+                                "webhook delivery failed permanently"
+                            );
+                        }
+                        self.webhook_delivery_repository()
+                            .mark_attempt_failed(
+                                &mut executor,
+                                &delivery.id,
+                                failure.status_code,
+                                &failure.error,
+                                retry_at,
+                            )
+                            .await?;
+                    }
+                }
+            }
+            Ok(attempted)
+        }
+    }
+}
+
+impl<T> DeliverDueWebhooks for T where
+    T: DependOnDatabaseConnection
+        + DependOnWebhookSubscriptionRepository
+        + DependOnWebhookDeliveryRepository
+{
+}
+
+/// Deletes delivered and failed deliveries older than the retention period,
+/// so the delivery log does not grow without bound. Pending deliveries are
+/// kept however old they are.
+pub trait PruneWebhookDeliveries:
+    DependOnDatabaseConnection + DependOnWebhookDeliveryRepository
+{
+    /// Run one prune. Returns the number of deliveries deleted.
+    fn prune_webhook_deliveries(
+        &self,
+        retention: Duration,
+    ) -> impl Future<Output = error_stack::Result<u64, KernelError>> + Send {
+        async move {
+            let mut executor = self.database_connection().connection().await?;
+            self.webhook_delivery_repository()
+                .prune_finished(
+                    &mut executor,
+                    OffsetDateTime::now_utc() - retention,
+                    WEBHOOK_PRUNE_BATCH_LIMIT,
+                )
+                .await
+        }
+    }
+}
+
+impl<T> PruneWebhookDeliveries for T where
+    T: DependOnDatabaseConnection + DependOnWebhookDeliveryRepository
+{
+}
+
+async fn post_webhook(
+    subscription: &WebhookSubscription,
+    delivery: &WebhookDelivery,
+) -> Result<i32, AttemptFailure> {
+    let failure = |error: String| AttemptFailure {
+        status_code: None,
+        error,
+    };
+    let url = reqwest::Url::parse(&subscription.url)
+        .map_err(|e| failure(format!("Webhook URL is invalid: {e}")))?;
+    let resolved_addresses = validate_fetch_url(&url)
+        .await
+        .map_err(|e| failure(format!("{e:?}")))?;
+    let client =
+        client_for_url(&url, &resolved_addresses).map_err(|e| failure(format!("{e:?}")))?;
+
+    let timestamp = OffsetDateTime::now_utc().unix_timestamp();
+    let signature = sign_webhook_payload(&subscription.secret, timestamp, &delivery.payload);
+    let response = client
+        .post(url)
+        .header(CONTENT_TYPE, "application/json")
+        .header(WEBHOOK_ID_HEADER, delivery.id.as_ref().to_string())
+        .header(WEBHOOK_EVENT_HEADER, &delivery.event_type)
+        .header(WEBHOOK_TIMESTAMP_HEADER, timestamp.to_string())
+        .header(WEBHOOK_SIGNATURE_HEADER, signature)
+        .body(delivery.payload.clone())
+        .send()
+        .await
+        .map_err(|e| failure(format!("Webhook request failed: {e}")))?;
+
+    let status = response.status();
+    if status.is_success() {
+        Ok(i32::from(status.as_u16()))
+    } else {
+        Err(AttemptFailure {
+            status_code: Some(i32::from(status.as_u16())),
+            error: format!("Webhook endpoint responded with HTTP {status}"),
+        })
+    }
+}
@@ -0,0 +1,212 @@
+use crate::change_feed::{account_change, metadata_change, profile_change};
+use error_stack::Report;
+use kernel::interfaces::change_feed::ChangeEnvelope;
+use kernel::interfaces::database::{DatabaseConnection, DependOnDatabaseConnection};
+use kernel::interfaces::projection::{
+    AccountEventLog, DependOnAccountEventLog, DependOnMetadataEventLog, DependOnProfileEventLog,
+    DependOnProjectionCheckpointStore, MetadataEventLog, ProfileEventLog,
+    ProjectionCheckpointStore,
+};
+use kernel::interfaces::repository::{
+    DependOnWebhookDeliveryRepository, DependOnWebhookSubscriptionRepository,
+    WebhookDeliveryRepository, WebhookSubscriptionRepository,
+};
+use kernel::prelude::entity::{WebhookDelivery, WebhookDeliveryId, WebhookDeliveryStatus};
+use kernel::KernelError;
+use std::future::Future;
+use time::OffsetDateTime;
+
+pub const ACCOUNT_WEBHOOK_FANOUT_NAME: &str = "account_webhook_fanout";
+pub const PROFILE_WEBHOOK_FANOUT_NAME: &str = "profile_webhook_fanout";
+pub const METADATA_WEBHOOK_FANOUT_NAME: &str = "metadata_webhook_fanout";
+
+/// Window re-read margin (see `ACCOUNT_PROJECTOR_WINDOW`). Events queued on
+/// an earlier poll are skipped by the per subscription/event unique key.
+pub const WEBHOOK_FANOUT_WINDOW: i64 = 100;
+
+/// Upper bound of one poll batch. Must exceed `WEBHOOK_FANOUT_WINDOW`.
+pub const WEBHOOK_FANOUT_BATCH_LIMIT: i64 = 1000;
+
+/// Webhook fan-out tailing the account, profile and metadata event logs.
+///
+/// Each log keeps its own checkpoint. A poll maps the window to change feed
+/// envelopes, queues one delivery per matching subscription and only then
+/// advances the checkpoint. A new subscription receives the events fanned out
+/// after it was registered (plus whatever the re-read window still covers);
+/// older history is not replayed.
+pub trait FanOutWebhookEvents:
+    DependOnDatabaseConnection
+    + DependOnAccountEventLog
+    + DependOnProfileEventLog
+    + DependOnMetadataEventLog
+    + DependOnProjectionCheckpointStore
+    + DependOnWebhookSubscriptionRepository
+    + DependOnWebhookDeliveryRepository
+{
+    /// Run one poll of the account log. Returns the checkpoint after the poll.
+    fn fan_out_account_events(
+        &self,
+    ) -> impl Future<Output = error_stack::Result<i64, KernelError>> + Send {
+        async move {
+            let mut executor = self.database_connection().connection().await?;
+            let checkpoint = self
+                .projection_checkpoint_store()
+                .get(&mut executor, ACCOUNT_WEBHOOK_FANOUT_NAME)
+                .await?
+                .unwrap_or(0);
+            let events = self
+                .account_event_log()
+                .find_by_seq_window(
+                    &mut executor,
+                    checkpoint - WEBHOOK_FANOUT_WINDOW,
+                    WEBHOOK_FANOUT_BATCH_LIMIT,
+                )
+                .await?;
+            let envelopes = events.iter().map(account_change).collect();
+            enqueue_window(
+                self,
+                &mut executor,
+                ACCOUNT_WEBHOOK_FANOUT_NAME,
+                checkpoint,
+                envelopes,
+            )
+            .await
+        }
+    }
+
+    /// Run one poll of the profile log. Returns the checkpoint after the poll.
+    fn fan_out_profile_events(
+        &self,
+    ) -> impl Future<Output = error_stack::Result<i64, KernelError>> + Send {
+        async move {
+            let mut executor = self.database_connection().connection().await?;
+            let checkpoint = self
+                .projection_checkpoint_store()
+                .get(&mut executor, PROFILE_WEBHOOK_FANOUT_NAME)
+                .await?
+                .unwrap_or(0);
+            let events = self
+                .profile_event_log()
+                .find_by_seq_window(
+                    &mut executor,
+                    checkpoint - WEBHOOK_FANOUT_WINDOW,
+                    WEBHOOK_FANOUT_BATCH_LIMIT,
+                )
+                .await?;
+            let envelopes = events.iter().map(profile_change).collect();
+            enqueue_window(
+                self,
+                &mut executor,
+                PROFILE_WEBHOOK_FANOUT_NAME,
+                checkpoint,
+                envelopes,
+            )
+            .await
+        }
+    }
+
+    /// Run one poll of the metadata log. Returns the checkpoint after the poll.
+    fn fan_out_metadata_events(
+        &self,
+    ) -> impl Future<Output = error_stack::Result<i64, KernelError>> + Send {
+        async move {
+            let mut executor = self.database_connection().connection().await?;
+            let checkpoint = self
+                .projection_checkpoint_store()
+                .get(&mut executor, METADATA_WEBHOOK_FANOUT_NAME)
+                .await?
+                .unwrap_or(0);
+            let events = self
+                .metadata_event_log()
+                .find_by_seq_window(
+                    &mut executor,
+                    checkpoint - WEBHOOK_FANOUT_WINDOW,
+                    WEBHOOK_FANOUT_BATCH_LIMIT,
+                )
+                .await?;
+            let envelopes = events.iter().map(metadata_change).collect();
+            enqueue_window(
+                self,
+                &mut executor,
+                METADATA_WEBHOOK_FANOUT_NAME,
+                checkpoint,
+                envelopes,
+            )
+            .await
+        }
+    }
+}
+
+impl<T> FanOutWebhookEvents for T where
+    T: DependOnDatabaseConnection
+        + DependOnAccountEventLog
+        + DependOnProfileEventLog
+        + DependOnMetadataEventLog
+        + DependOnProjectionCheckpointStore
+        + DependOnWebhookSubscriptionRepository
+        + DependOnWebhookDeliveryRepository
+{
+}
+
+async fn enqueue_window<T>(
+    deps: &T,
+    executor: &mut <T::DatabaseConnection as DatabaseConnection>::Connection,
+    fanout_name: &str,
+    checkpoint: i64,
+    envelopes: Vec<ChangeEnvelope>,
+) -> error_stack::Result<i64, KernelError>
+where
+    T: DependOnProjectionCheckpointStore
+        + DependOnWebhookSubscriptionRepository
+        + DependOnWebhookDeliveryRepository
+        + ?Sized,
+{
+    let Some(max_seq) = envelopes.last().map(|envelope| envelope.seq) else {
+        return Ok(checkpoint);
+    };
+    let subscriptions = deps
+        .webhook_subscription_repository()
+        .find_all(executor)
+        .await?;
+    let now = OffsetDateTime::now_utc();
+    let mut deliveries = Vec::new();
+    for envelope in &envelopes {
+        let mut matching = subscriptions
+            .iter()
+            .filter(|subscription| subscription.matches(&envelope.event_type))
+            .peekable();
+        if matching.peek().is_none() {
+            continue;
+        }
+        let payload = serde_json::to_string(envelope).map_err(|error| {
+            Report::new(KernelError::Internal)
+                .attach_printable(format!("Failed to serialize webhook payload: {error}"))
+        })?;
+        for subscription in matching {
+            deliveries.push(WebhookDelivery {
+                id: WebhookDeliveryId::default(),
+                subscription_id: subscription.id.clone(),
+                event_id: envelope.event_id.clone(),
+                event_type: envelope.event_type.clone(),
+                payload: payload.clone(),
+                status: WebhookDeliveryStatus::Pending,
+                attempts: 0,
+                next_attempt_at: now,
+                last_status_code: None,
+                last_error: None,
+                created_at: now,
+                delivered_at: None,
+            });
+        }
+    }
+    if !deliveries.is_empty() {
+        deps.webhook_delivery_repository()
+            .enqueue(executor, &deliveries)
+            .await?;
+    }
+    let checkpoint = checkpoint.max(max_seq);
+    deps.projection_checkpoint_store()
+        .set(executor, fanout_name, checkpoint)
+        .await?;
+    Ok(checkpoint)
+}
@@ -0,0 +1,16 @@
+//! Outgoing webhooks driven by the account/profile/metadata event logs.
+//!
+//! Fan-out tails the logs like the projectors do and queues one delivery per
+//! matching subscription; delivery drains that queue with signed POSTs and
+//! exponential backoff. The queue rows double as the delivery log.
+
+mod delivery;
+mod fanout;
+mod signature;
+
+#[cfg(test)]
+mod tests;
+
+pub use delivery::*;
+pub use fanout::*;
+pub use signature::*;
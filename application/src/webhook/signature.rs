@@ -0,0 +1,28 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::fmt::Write;
+
+pub const WEBHOOK_ID_HEADER: &str = "x-emumet-webhook-id";
+pub const WEBHOOK_EVENT_HEADER: &str = "x-emumet-webhook-event";
+pub const WEBHOOK_TIMESTAMP_HEADER: &str = "x-emumet-webhook-timestamp";
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "x-emumet-webhook-signature";
+
+/// Signature header value for one delivery attempt: `sha256=<hex>` where the
+/// HMAC-SHA256 key is the subscription secret and the message is
+/// `"{timestamp}.{body}"`.
+///
+/// Covering the timestamp lets receivers reject replays of old deliveries.
+pub fn sign_webhook_payload(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    let digest = mac.finalize().into_bytes();
+    let mut signature = String::with_capacity(7 + digest.len() * 2);
+    signature.push_str("sha256=");
+    for byte in digest {
+        let _ = write!(signature, "{byte:02x}");
+    }
+    signature
+}
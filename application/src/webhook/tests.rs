@@ -0,0 +1,132 @@
+use super::{
+    sign_webhook_payload, webhook_retry_at, FanOutWebhookEvents, ACCOUNT_WEBHOOK_FANOUT_NAME,
+    WEBHOOK_MAX_ATTEMPTS,
+};
+use crate::test_utils::{
+    delete_account_events, last_account_event_seq, persist_created_and_suspended,
+    reset_checkpoint_to_head,
+};
+use driver::database::PostgresDatabase;
+use kernel::impl_database_delegation;
+use kernel::interfaces::database::DatabaseConnection;
+use kernel::interfaces::repository::{
+    DependOnWebhookDeliveryRepository, DependOnWebhookSubscriptionRepository,
+    WebhookDeliveryRepository, WebhookSubscriptionRepository,
+};
+use kernel::prelude::entity::{
+    AccountId, WebhookDeliveryStatus, WebhookSubscription, WebhookSubscriptionId,
+};
+use time::{Duration, OffsetDateTime};
+
+struct WebhookTest {
+    db: PostgresDatabase,
+}
+
+impl_database_delegation!(WebhookTest, db, PostgresDatabase);
+
+#[test]
+fn signature_is_hmac_sha256_over_timestamp_and_body() {
+    assert_eq!(
+        sign_webhook_payload(
+            "whsec_test",
+            1_767_323_045,
+            r#"{"event_type":"account.created"}"#
+        ),
+        "sha256=b816aaa63ff7c0ff02bab61541beda1029b814c5ba96b9a59d343a0a61667886"
+    );
+}
+
+#[test]
+fn retry_backoff_doubles_and_gives_up_after_max_attempts() {
+    let now = OffsetDateTime::UNIX_EPOCH;
+    assert_eq!(webhook_retry_at(1, now), Some(now + Duration::seconds(30)));
+    assert_eq!(webhook_retry_at(2, now), Some(now + Duration::seconds(60)));
+    assert_eq!(webhook_retry_at(5, now), Some(now + Duration::minutes(8)));
+    assert_eq!(webhook_retry_at(WEBHOOK_MAX_ATTEMPTS, now), None);
+}
+
+async fn create_subscription(db: &PostgresDatabase, event_types: &[&str]) -> WebhookSubscription {
+    let subscription = WebhookSubscription {
+        id: WebhookSubscriptionId::default(),
+        url: "https://hooks.example.com/emumet".to_string(),
+        event_types: event_types.iter().map(|s| s.to_string()).collect(),
+        secret: WebhookSubscription::generate_secret(),
+        created_at: OffsetDateTime::now_utc(),
+    };
+    let mut conn = db.connection().await.unwrap();
+    db.webhook_subscription_repository()
+        .create(&mut conn, &subscription)
+        .await
+        .unwrap();
+    subscription
+}
+
+/// Event types queued for `subscription` about `account_id`. The window
+/// re-read also picks up other tests' recent events, so filter by aggregate.
+async fn delivered_event_types(
+    db: &PostgresDatabase,
+    subscription: &WebhookSubscription,
+    account_id: &AccountId,
+) -> Vec<String> {
+    let event_id_prefix = format!("account:{}:", account_id.as_ref());
+    let mut conn = db.connection().await.unwrap();
+    let mut event_types: Vec<String> = db
+        .webhook_delivery_repository()
+        .find_by_subscription_id(&mut conn, &subscription.id, 100, None)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|delivery| delivery.event_id.starts_with(&event_id_prefix))
+        .inspect(|delivery| assert_eq!(delivery.status, WebhookDeliveryStatus::Pending))
+        .map(|delivery| delivery.event_type)
+        .collect();
+    event_types.sort();
+    event_types
+}
+
+#[test_with::env(DATABASE_URL)]
+#[tokio::test]
+async fn fan_out_queues_matching_events_once_per_subscription() {
+    kernel::ensure_generator_initialized();
+    let test = WebhookTest {
+        db: PostgresDatabase::new().await.unwrap(),
+    };
+    reset_checkpoint_to_head(&test.db, ACCOUNT_WEBHOOK_FANOUT_NAME).await;
+    let suspensions = create_subscription(&test.db, &["account.suspended"]).await;
+    let everything = create_subscription(&test.db, &["account.*"]).await;
+    let profiles = create_subscription(&test.db, &["profile.*"]).await;
+
+    let account_id = AccountId::default();
+    persist_created_and_suspended(&test.db, &account_id).await;
+    let target = last_account_event_seq(&test.db, &account_id).await;
+
+    for _ in 0..50 {
+        if test.fan_out_account_events().await.unwrap() >= target {
+            break;
+        }
+    }
+    // The window re-read must not queue the same events twice.
+    test.fan_out_account_events().await.unwrap();
+
+    assert_eq!(
+        delivered_event_types(&test.db, &suspensions, &account_id).await,
+        vec!["account.suspended"]
+    );
+    assert_eq!(
+        delivered_event_types(&test.db, &everything, &account_id).await,
+        vec!["account.created", "account.suspended"]
+    );
+    assert!(delivered_event_types(&test.db, &profiles, &account_id)
+        .await
+        .is_empty());
+
+    let mut conn = test.db.connection().await.unwrap();
+    for subscription in [suspensions, everything, profiles] {
+        test.db
+            .webhook_subscription_repository()
+            .delete(&mut conn, &subscription.id)
+            .await
+            .unwrap();
+    }
+    delete_account_events(&test.db, &account_id).await;
+}
@@ -0,0 +1,80 @@
+use crate::database::RedisDatabase;
+use deadpool_redis::redis;
+use error_stack::Report;
+use kernel::interfaces::change_feed::{ChangeEnvelope, ChangeFeedPublisher};
+use kernel::interfaces::database::DatabaseConnection;
+use kernel::KernelError;
+use std::ops::DerefMut;
+
+/// Publishes change feed envelopes to a Redis Stream.
+///
+/// Each envelope becomes one stream entry with the fields `event_type` (for
+/// cheap consumer-side filtering) and `envelope` (the JSON document). A batch
+/// is sent as one MULTI/EXEC pipeline so it is appended all-or-nothing.
+#[derive(Clone)]
+pub struct RedisChangeFeedPublisher {
+    redis: RedisDatabase,
+    stream_key: String,
+    max_len: usize,
+}
+
+impl RedisChangeFeedPublisher {
+    pub fn new(redis: RedisDatabase, stream_key: String, max_len: usize) -> Self {
+        Self {
+            redis,
+            stream_key,
+            max_len,
+        }
+    }
+
+    /// Reads `CHANGE_FEED_STREAM_KEY` (default `emumet:events`) and
+    /// `CHANGE_FEED_MAX_LEN` (approximate stream trim length, default 100000).
+    pub fn from_env(redis: RedisDatabase) -> Self {
+        let stream_key =
+            dotenvy::var("CHANGE_FEED_STREAM_KEY").unwrap_or_else(|_| "emumet:events".to_string());
+        let max_len = dotenvy::var("CHANGE_FEED_MAX_LEN")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(100_000);
+        Self::new(redis, stream_key, max_len)
+    }
+}
+
+impl ChangeFeedPublisher for RedisChangeFeedPublisher {
+    async fn publish(&self, envelopes: &[ChangeEnvelope]) -> error_stack::Result<(), KernelError> {
+        if envelopes.is_empty() {
+            return Ok(());
+        }
+        let mut pipeline = redis::pipe();
+        pipeline.atomic();
+        for envelope in envelopes {
+            let body = serde_json::to_string(envelope).map_err(|error| {
+                Report::new(KernelError::Internal)
+                    .attach_printable(format!("Failed to serialize change envelope: {error}"))
+            })?;
+            pipeline
+                .cmd("XADD")
+                .arg(&self.stream_key)
+                .arg("MAXLEN")
+                .arg("~")
+                .arg(self.max_len)
+                .arg("*")
+                .arg("event_type")
+                .arg(&envelope.event_type)
+                .arg("envelope")
+                .arg(body)
+                .ignore();
+        }
+        let mut connection = self.redis.connection().await?;
+        pipeline
+            .query_async::<()>(connection.deref_mut().deref_mut())
+            .await
+            .map_err(|error| {
+                Report::new(KernelError::Internal).attach_printable(format!(
+                    "Failed to append to change feed stream {}: {error}",
+                    self.stream_key
+                ))
+            })?;
+        Ok(())
+    }
+}
@@ -4,13 +4,17 @@ mod account_repository;
 mod auth_account;
 mod auth_host;
 mod block;
+mod custom_role;
 mod follow;
 mod image;
+mod image_blocklist;
+mod instance_role_change;
 mod metadata;
 mod metadata_event_store;
 mod metadata_repository;
 mod mute;
 mod outbox_activity;
+mod personal_access_token;
 mod profile;
 mod profile_event_store;
 mod profile_repository;
@@ -19,6 +23,8 @@ mod remote_account;
 mod signing_key;
 #[cfg(test)]
 mod transaction_manager_tests;
+mod upload_session;
+mod webhook;
 
 use crate::database::env;
 use crate::ConvertError;
@@ -67,6 +73,23 @@ impl PostgresDatabase {
             .change_context_lazy(|| KernelError::Internal)?;
         Ok(Self { pool })
     }
+
+    pub fn pool_usage(&self) -> PoolUsage {
+        PoolUsage {
+            size: self.pool.size(),
+            idle: self.pool.num_idle() as u32,
+            max: self.pool.options().get_max_connections(),
+        }
+    }
+}
+
+/// Connection counts of the pool at one point in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolUsage {
+    /// Open connections, idle or checked out.
+    pub size: u32,
+    pub idle: u32,
+    pub max: u32,
 }
 
 enum PostgresConnectionInner {
@@ -1,6 +1,7 @@
 use crate::database::{PostgresConnection, PostgresDatabase};
 use crate::ConvertError;
 use error_stack::Report;
+use kernel::interfaces::event::{upcast_event, EventSchema};
 use kernel::interfaces::event_store::{AccountEventStore, DependOnAccountEventStore};
 use kernel::prelude::entity::{
     Account, AccountEvent, CommandEnvelope, EventEnvelope, EventId, EventVersion, ExpectedVersion,
@@ -16,12 +17,13 @@ struct EventRow {
     #[allow(dead_code)]
     event_name: String,
     data: serde_json::Value,
+    schema_version: i32,
 }
 
 impl TryFrom<EventRow> for EventEnvelope<AccountEvent, Account> {
     type Error = Report<KernelError>;
     fn try_from(value: EventRow) -> Result<Self, Self::Error> {
-        let event: AccountEvent = serde_json::from_value(value.data).convert_error()?;
+        let event: AccountEvent = upcast_event(value.data, value.schema_version)?;
         Ok(EventEnvelope::new(
             EventId::new(value.id),
             event,
@@ -46,7 +48,7 @@ impl AccountEventStore for PostgresAccountEventStore {
             sqlx::query_as::<_, EventRow>(
                 //language=postgresql
                 r#"
-                SELECT version, id, event_name, data
+                SELECT version, id, event_name, data, schema_version
                 FROM account_events
                 WHERE id = $1 AND version > $2
                 ORDER BY version
@@ -61,7 +63,7 @@ impl AccountEventStore for PostgresAccountEventStore {
             sqlx::query_as::<_, EventRow>(
                 //language=postgresql
                 r#"
-                SELECT version, id, event_name, data
+                SELECT version, id, event_name, data, schema_version
                 FROM account_events
                 WHERE id = $1
                 ORDER BY version
@@ -121,8 +123,8 @@ impl PostgresAccountEventStore {
                 sqlx::query(
                     //language=postgresql
                     r#"
-                    INSERT INTO account_events (version, id, event_name, data)
-                    SELECT $1, $2, $3, $4
+                    INSERT INTO account_events (version, id, event_name, data, schema_version)
+                    SELECT $1, $2, $3, $4, $5
                     WHERE NOT EXISTS (SELECT 1 FROM account_events WHERE id = $2)
                     "#,
                 )
@@ -130,6 +132,7 @@ impl PostgresAccountEventStore {
                 .bind(command.id().as_ref())
                 .bind(event_name)
                 .bind(&data)
+                .bind(AccountEvent::SCHEMA_VERSION)
                 .execute(&mut *con)
                 .await
                 .convert_error()?
@@ -138,15 +141,16 @@ impl PostgresAccountEventStore {
                 sqlx::query(
                     //language=postgresql
                     r#"
-                    INSERT INTO account_events (version, id, event_name, data)
-                    SELECT $1, $2, $3, $4
-                    WHERE (SELECT MAX(version) FROM account_events WHERE id = $2) = $5
+                    INSERT INTO account_events (version, id, event_name, data, schema_version)
+                    SELECT $1, $2, $3, $4, $5
+                    WHERE (SELECT MAX(version) FROM account_events WHERE id = $2) = $6
                     "#,
                 )
                 .bind(version)
                 .bind(command.id().as_ref())
                 .bind(event_name)
                 .bind(&data)
+                .bind(AccountEvent::SCHEMA_VERSION)
                 .bind(prev.as_ref())
                 .execute(&mut *con)
                 .await
@@ -156,14 +160,15 @@ impl PostgresAccountEventStore {
                 sqlx::query(
                     //language=postgresql
                     r#"
-                    INSERT INTO account_events (version, id, event_name, data)
-                    VALUES ($1, $2, $3, $4)
+                    INSERT INTO account_events (version, id, event_name, data, schema_version)
+                    VALUES ($1, $2, $3, $4, $5)
                     "#,
                 )
                 .bind(version)
                 .bind(command.id().as_ref())
                 .bind(event_name)
                 .bind(&data)
+                .bind(AccountEvent::SCHEMA_VERSION)
                 .execute(con)
                 .await
                 .convert_error()?
@@ -196,11 +201,14 @@ mod test {
     mod query {
         use crate::database::PostgresDatabase;
         use kernel::interfaces::database::DatabaseConnection;
+        use kernel::interfaces::event::EventSchema;
         use kernel::interfaces::event_store::{AccountEventStore, DependOnAccountEventStore};
         use kernel::prelude::entity::{
             AccountEvent, AccountId, AccountIsBot, CommandEnvelope, EventId,
         };
         use kernel::test_utils::account_create_command;
+        use serde_json::json;
+        use sqlx::PgConnection;
 
         #[test_with::env(DATABASE_URL)]
         #[tokio::test]
@@ -323,6 +331,59 @@ mod test {
                 .unwrap();
             assert_eq!(no_events.len(), 0);
         }
+
+        #[test_with::env(DATABASE_URL)]
+        #[tokio::test]
+        async fn find_by_id_upcasts_legacy_rows() {
+            kernel::ensure_generator_initialized();
+            let db = PostgresDatabase::new().await.unwrap();
+            let mut conn = db.connection().await.unwrap();
+            let account_id = AccountId::default();
+            let event_id = EventId::from(account_id.clone());
+
+            let created_account = account_create_command(account_id.clone());
+            db.account_event_store()
+                .persist(&mut conn, &created_account)
+                .await
+                .unwrap();
+            let con: &mut PgConnection = &mut conn;
+            let schema_version: i32 =
+                sqlx::query_scalar("SELECT schema_version FROM account_events WHERE id = $1")
+                    .bind(account_id.as_ref())
+                    .fetch_one(&mut *con)
+                    .await
+                    .unwrap();
+            assert_eq!(schema_version, AccountEvent::SCHEMA_VERSION);
+
+            sqlx::query(
+                "INSERT INTO account_events (version, id, event_name, data, schema_version) VALUES ($1, $2, $3, $4, 1)",
+            )
+            .bind(kernel::generate_id())
+            .bind(account_id.as_ref())
+            .bind("account_deleted")
+            .bind(json!({ "type": "Deleted" }))
+            .execute(&mut *con)
+            .await
+            .unwrap();
+
+            let events = db
+                .account_event_store()
+                .find_by_id(&mut conn, &event_id, None)
+                .await
+                .unwrap();
+            assert_eq!(events.len(), 2);
+            assert_eq!(&events[0].event, created_account.event());
+            assert_eq!(events[1].event, AccountEvent::Deactivated);
+
+            // Raw rows bypass the usual lifecycle, so drop them here rather
+            // than leave them for the projection and outbox tests to scan.
+            let con: &mut PgConnection = &mut conn;
+            sqlx::query("DELETE FROM account_events WHERE id = $1")
+                .bind(account_id.as_ref())
+                .execute(con)
+                .await
+                .unwrap();
+        }
     }
 
     mod persist {
@@ -0,0 +1,255 @@
+use crate::database::{PostgresConnection, PostgresDatabase};
+use crate::ConvertError;
+use kernel::interfaces::permission::InstancePermission;
+use kernel::interfaces::repository::{CustomRoleRepository, DependOnCustomRoleRepository};
+use kernel::prelude::entity::{CustomRole, CustomRoleId};
+use kernel::KernelError;
+use sqlx::PgConnection;
+use time::OffsetDateTime;
+
+#[derive(sqlx::FromRow)]
+struct CustomRoleRow {
+    id: i64,
+    name: String,
+    permissions: Vec<String>,
+    created_at: OffsetDateTime,
+    updated_at: OffsetDateTime,
+}
+
+impl From<CustomRoleRow> for CustomRole {
+    fn from(value: CustomRoleRow) -> Self {
+        CustomRole {
+            id: CustomRoleId::new(value.id),
+            name: value.name,
+            // Permissions this version no longer knows grant nothing.
+            permissions: value
+                .permissions
+                .iter()
+                .filter_map(|permission| InstancePermission::parse(permission))
+                .collect(),
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        }
+    }
+}
+
+fn permission_names(role: &CustomRole) -> Vec<&'static str> {
+    role.permissions
+        .iter()
+        .map(InstancePermission::as_str)
+        .collect()
+}
+
+pub struct PostgresCustomRoleRepository;
+
+impl CustomRoleRepository for PostgresCustomRoleRepository {
+    type Connection = PostgresConnection;
+
+    async fn create(
+        &self,
+        executor: &mut Self::Connection,
+        role: &CustomRole,
+    ) -> error_stack::Result<(), KernelError> {
+        let con: &mut PgConnection = executor;
+        // language=postgresql
+        sqlx::query(
+            r#"
+            INSERT INTO custom_roles (id, name, permissions, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(role.id.as_ref())
+        .bind(&role.name)
+        .bind(permission_names(role))
+        .bind(role.created_at)
+        .bind(role.updated_at)
+        .execute(con)
+        .await
+        .convert_error()?;
+        Ok(())
+    }
+
+    async fn find_by_id(
+        &self,
+        executor: &mut Self::Connection,
+        id: &CustomRoleId,
+    ) -> error_stack::Result<Option<CustomRole>, KernelError> {
+        let con: &mut PgConnection = executor;
+        // language=postgresql
+        sqlx::query_as::<_, CustomRoleRow>(
+            r#"
+            SELECT id, name, permissions, created_at, updated_at
+            FROM custom_roles
+            WHERE id = $1
+            "#,
+        )
+        .bind(id.as_ref())
+        .fetch_optional(con)
+        .await
+        .convert_error()
+        .map(|row| row.map(CustomRole::from))
+    }
+
+    async fn find_by_name(
+        &self,
+        executor: &mut Self::Connection,
+        name: &str,
+    ) -> error_stack::Result<Option<CustomRole>, KernelError> {
+        let con: &mut PgConnection = executor;
+        // language=postgresql
+        sqlx::query_as::<_, CustomRoleRow>(
+            r#"
+            SELECT id, name, permissions, created_at, updated_at
+            FROM custom_roles
+            WHERE name = $1
+            "#,
+        )
+        .bind(name)
+        .fetch_optional(con)
+        .await
+        .convert_error()
+        .map(|row| row.map(CustomRole::from))
+    }
+
+    async fn find_all(
+        &self,
+        executor: &mut Self::Connection,
+    ) -> error_stack::Result<Vec<CustomRole>, KernelError> {
+        let con: &mut PgConnection = executor;
+        // language=postgresql
+        sqlx::query_as::<_, CustomRoleRow>(
+            r#"
+            SELECT id, name, permissions, created_at, updated_at
+            FROM custom_roles
+            ORDER BY name, id
+            "#,
+        )
+        .fetch_all(con)
+        .await
+        .convert_error()
+        .map(|rows| rows.into_iter().map(CustomRole::from).collect())
+    }
+
+    async fn update(
+        &self,
+        executor: &mut Self::Connection,
+        role: &CustomRole,
+    ) -> error_stack::Result<(), KernelError> {
+        let con: &mut PgConnection = executor;
+        // language=postgresql
+        sqlx::query(
+            r#"
+            UPDATE custom_roles
+            SET name = $2, permissions = $3, updated_at = $4
+            WHERE id = $1
+            "#,
+        )
+        .bind(role.id.as_ref())
+        .bind(&role.name)
+        .bind(permission_names(role))
+        .bind(role.updated_at)
+        .execute(con)
+        .await
+        .convert_error()?;
+        Ok(())
+    }
+
+    async fn delete(
+        &self,
+        executor: &mut Self::Connection,
+        id: &CustomRoleId,
+    ) -> error_stack::Result<bool, KernelError> {
+        let con: &mut PgConnection = executor;
+        // language=postgresql
+        let result = sqlx::query(
+            r#"
+            DELETE FROM custom_roles WHERE id = $1
+            "#,
+        )
+        .bind(id.as_ref())
+        .execute(con)
+        .await
+        .convert_error()?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+impl DependOnCustomRoleRepository for PostgresDatabase {
+    type CustomRoleRepository = PostgresCustomRoleRepository;
+
+    fn custom_role_repository(&self) -> &Self::CustomRoleRepository {
+        &PostgresCustomRoleRepository
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kernel::interfaces::database::DatabaseConnection;
+
+    #[test_with::env(DATABASE_URL)]
+    #[tokio::test]
+    async fn create_find_update_and_delete() {
+        kernel::ensure_generator_initialized();
+        let database = PostgresDatabase::new().await.unwrap();
+        let mut executor = database.connection().await.unwrap();
+        let now = OffsetDateTime::now_utc().replace_nanosecond(0).unwrap();
+        let role = CustomRole {
+            id: CustomRoleId::default(),
+            name: format!("media-{}", now.unix_timestamp_nanos()),
+            permissions: vec![
+                InstancePermission::ManageMedia,
+                InstancePermission::ViewAuditLog,
+            ],
+            created_at: now,
+            updated_at: now,
+        };
+        database
+            .custom_role_repository()
+            .create(&mut executor, &role)
+            .await
+            .unwrap();
+
+        let found = database
+            .custom_role_repository()
+            .find_by_name(&mut executor, &role.name)
+            .await
+            .unwrap();
+        assert_eq!(found, Some(role.clone()));
+
+        let updated = CustomRole {
+            name: format!("{}-renamed", role.name),
+            permissions: vec![InstancePermission::ManageReports],
+            updated_at: now + time::Duration::minutes(1),
+            ..role.clone()
+        };
+        database
+            .custom_role_repository()
+            .update(&mut executor, &updated)
+            .await
+            .unwrap();
+        let found = database
+            .custom_role_repository()
+            .find_by_id(&mut executor, &role.id)
+            .await
+            .unwrap();
+        assert_eq!(found, Some(updated.clone()));
+        let all = database
+            .custom_role_repository()
+            .find_all(&mut executor)
+            .await
+            .unwrap();
+        assert!(all.contains(&updated));
+
+        assert!(database
+            .custom_role_repository()
+            .delete(&mut executor, &role.id)
+            .await
+            .unwrap());
+        assert!(!database
+            .custom_role_repository()
+            .delete(&mut executor, &role.id)
+            .await
+            .unwrap());
+    }
+}
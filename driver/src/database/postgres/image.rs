@@ -2,9 +2,14 @@ use crate::database::{PostgresConnection, PostgresDatabase};
 use crate::ConvertError;
 use error_stack::Report;
 use kernel::interfaces::repository::{DependOnImageRepository, ImageRepository};
-use kernel::prelude::entity::{Image, ImageBlurHash, ImageHash, ImageId, ImageUrl};
+use kernel::prelude::entity::{
+    AccountId, AuthAccountId, CreatedAt, Image, ImageBlurHash, ImageDescription, ImageFocus,
+    ImageHash, ImageId, ImagePerceptualHash, ImageUrl, ImageVariant, ImageVariantKind,
+};
 use kernel::KernelError;
 use sqlx::PgConnection;
+use std::collections::HashMap;
+use time::OffsetDateTime;
 
 #[derive(sqlx::FromRow)]
 struct ImageRow {
@@ -12,17 +17,167 @@ struct ImageRow {
     url: String,
     hash: String,
     blurhash: String,
+    animated: bool,
+    /// Only selected when the image is loaded through a reference.
+    #[sqlx(default)]
+    description: Option<String>,
+    #[sqlx(default)]
+    focus_x: Option<f32>,
+    #[sqlx(default)]
+    focus_y: Option<f32>,
+    perceptual_hash: Option<i64>,
+    quarantined: bool,
+    byte_size: i64,
+    created_at: OffsetDateTime,
 }
 
-impl From<ImageRow> for Image {
-    fn from(row: ImageRow) -> Self {
-        Self::new(
-            ImageId::new(row.id),
+#[derive(sqlx::FromRow)]
+struct ProfileImageRow {
+    account_id: i64,
+    #[sqlx(flatten)]
+    image: ImageRow,
+}
+
+#[derive(sqlx::FromRow)]
+struct ImageVariantRow {
+    image_id: i64,
+    kind: String,
+    url: String,
+    width: i32,
+    height: i32,
+    media_type: String,
+}
+
+impl TryFrom<ImageVariantRow> for ImageVariant {
+    type Error = Report<KernelError>;
+
+    fn try_from(row: ImageVariantRow) -> Result<Self, Self::Error> {
+        let kind = ImageVariantKind::parse(&row.kind).ok_or_else(|| {
+            Report::new(KernelError::Internal)
+                .attach_printable(format!("Unknown image variant kind: {}", row.kind))
+        })?;
+        Ok(ImageVariant::new(
+            kind,
             ImageUrl::new(row.url),
-            ImageHash::new(row.hash),
-            ImageBlurHash::new(row.blurhash),
-        )
+            row.width as u32,
+            row.height as u32,
+            row.media_type,
+        ))
+    }
+}
+
+/// Load the variants of every row in one query and assemble the images.
+async fn with_variants(
+    con: &mut PgConnection,
+    rows: Vec<ImageRow>,
+) -> error_stack::Result<Vec<Image>, KernelError> {
+    if rows.is_empty() {
+        return Ok(Vec::new());
+    }
+    let ids: Vec<i64> = rows.iter().map(|row| row.id).collect();
+    let variant_rows = sqlx::query_as::<_, ImageVariantRow>(
+        // language=postgresql
+        r#"
+        SELECT image_id, kind, url, width, height, media_type
+        FROM image_variants
+        WHERE image_id = ANY($1)
+        ORDER BY image_id, kind
+        "#,
+    )
+    .bind(&ids)
+    .fetch_all(con)
+    .await
+    .convert_error()?;
+    let mut variants: HashMap<i64, Vec<ImageVariant>> = HashMap::new();
+    for row in variant_rows {
+        let image_id = row.image_id;
+        variants
+            .entry(image_id)
+            .or_default()
+            .push(ImageVariant::try_from(row)?);
+    }
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            Image::new(
+                ImageId::new(row.id),
+                ImageUrl::new(row.url),
+                ImageHash::new(row.hash),
+                ImageBlurHash::new(row.blurhash),
+                variants.remove(&row.id).unwrap_or_default(),
+                row.animated,
+                row.description.map(ImageDescription::new),
+                row.focus_x
+                    .zip(row.focus_y)
+                    .map(|(x, y)| ImageFocus::new(x, y)),
+                row.perceptual_hash
+                    .map(|hash| ImagePerceptualHash::new(hash as u64)),
+                row.quarantined,
+                row.byte_size as u64,
+                CreatedAt::new(row.created_at),
+            )
+        })
+        .collect())
+}
+
+/// Insert the image and its variants. An upload whose hash is already taken
+/// by another upload is skipped, which the return value reports.
+async fn insert_image(
+    con: &mut PgConnection,
+    image: &Image,
+    uploaded: bool,
+) -> error_stack::Result<bool, KernelError> {
+    let result = sqlx::query(
+        // language=postgresql
+        r#"
+        INSERT INTO images
+            (id, url, hash, blurhash, animated, perceptual_hash, quarantined, byte_size, created_at, uploaded)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        ON CONFLICT (hash) WHERE uploaded DO NOTHING
+        "#,
+    )
+    .bind(image.id().as_ref())
+    .bind(image.url().as_ref())
+    .bind(image.hash().as_ref())
+    .bind(image.blur_hash().as_ref())
+    .bind(image.animated())
+    .bind(image.perceptual_hash().map(|hash| *hash.as_ref() as i64))
+    .bind(image.quarantined())
+    .bind(*image.byte_size() as i64)
+    .bind(image.created_at().as_ref())
+    .bind(uploaded)
+    .execute(&mut *con)
+    .await
+    .convert_error()?;
+    if result.rows_affected() == 0 {
+        return Ok(false);
     }
+    if image.variants().is_empty() {
+        return Ok(true);
+    }
+    let variants = image.variants();
+    let kinds: Vec<&str> = variants.iter().map(|v| v.kind().as_str()).collect();
+    let urls: Vec<&str> = variants.iter().map(|v| v.url().as_ref().as_str()).collect();
+    let widths: Vec<i32> = variants.iter().map(|v| *v.width() as i32).collect();
+    let heights: Vec<i32> = variants.iter().map(|v| *v.height() as i32).collect();
+    let media_types: Vec<&str> = variants.iter().map(|v| v.media_type().as_str()).collect();
+    sqlx::query(
+        // language=postgresql
+        r#"
+        INSERT INTO image_variants (image_id, kind, url, width, height, media_type)
+        SELECT $1, * FROM UNNEST($2::TEXT[], $3::TEXT[], $4::INTEGER[], $5::INTEGER[], $6::TEXT[])
+        "#,
+    )
+    .bind(image.id().as_ref())
+    .bind(&kinds)
+    .bind(&urls)
+    .bind(&widths)
+    .bind(&heights)
+    .bind(&media_types)
+    .execute(con)
+    .await
+    .convert_error()?;
+    Ok(true)
 }
 
 pub struct PostgresImageRepository;
@@ -36,17 +191,17 @@ impl ImageRepository for PostgresImageRepository {
         id: &ImageId,
     ) -> error_stack::Result<Option<Image>, KernelError> {
         let con: &mut PgConnection = executor;
-        sqlx::query_as::<_, ImageRow>(
+        let row = sqlx::query_as::<_, ImageRow>(
             // language=postgresql
             r#"
-            SELECT id, url, hash, blurhash FROM images WHERE id = $1
+            SELECT id, url, hash, blurhash, animated, perceptual_hash, quarantined, byte_size, created_at FROM images WHERE id = $1
             "#,
         )
         .bind(id.as_ref())
-        .fetch_optional(con)
+        .fetch_optional(&mut *con)
         .await
-        .convert_error()
-        .map(|option| option.map(|row| row.into()))
+        .convert_error()?;
+        Ok(with_variants(con, row.into_iter().collect()).await?.pop())
     }
 
     async fn find_by_ids(
@@ -56,17 +211,17 @@ impl ImageRepository for PostgresImageRepository {
     ) -> error_stack::Result<Vec<Image>, KernelError> {
         let con: &mut PgConnection = executor;
         let ids: Vec<i64> = ids.iter().map(|id| *id.as_ref()).collect();
-        sqlx::query_as::<_, ImageRow>(
+        let rows = sqlx::query_as::<_, ImageRow>(
             // language=postgresql
             r#"
-            SELECT id, url, hash, blurhash FROM images WHERE id = ANY($1)
+            SELECT id, url, hash, blurhash, animated, perceptual_hash, quarantined, byte_size, created_at FROM images WHERE id = ANY($1)
             "#,
         )
         .bind(&ids)
-        .fetch_all(con)
+        .fetch_all(&mut *con)
         .await
-        .convert_error()
-        .map(|rows| rows.into_iter().map(|row| row.into()).collect())
+        .convert_error()?;
+        with_variants(con, rows).await
     }
 
     async fn find_by_url(
@@ -75,41 +230,56 @@ impl ImageRepository for PostgresImageRepository {
         url: &ImageUrl,
     ) -> error_stack::Result<Option<Image>, KernelError> {
         let con: &mut PgConnection = executor;
-        sqlx::query_as::<_, ImageRow>(
+        let row = sqlx::query_as::<_, ImageRow>(
             // language=postgresql
             r#"
-            SELECT id, url, hash, blurhash FROM images WHERE url = $1
+            SELECT id, url, hash, blurhash, animated, perceptual_hash, quarantined, byte_size, created_at FROM images WHERE url = $1
             "#,
         )
         .bind(url.as_ref())
-        .fetch_optional(con)
+        .fetch_optional(&mut *con)
         .await
-        .convert_error()
-        .map(|option| option.map(|row| row.into()))
+        .convert_error()?;
+        Ok(with_variants(con, row.into_iter().collect()).await?.pop())
     }
 
-    async fn create(
+    async fn find_by_hash(
         &self,
         executor: &mut Self::Connection,
-        image: &Image,
-    ) -> error_stack::Result<(), KernelError> {
+        hash: &ImageHash,
+    ) -> error_stack::Result<Option<Image>, KernelError> {
         let con: &mut PgConnection = executor;
-        sqlx::query(
+        let row = sqlx::query_as::<_, ImageRow>(
             // language=postgresql
             r#"
-            INSERT INTO images (id, url, hash, blurhash) VALUES ($1, $2, $3, $4)
+            SELECT id, url, hash, blurhash, animated, perceptual_hash, quarantined, byte_size, created_at FROM images
+            WHERE hash = $1 AND uploaded
             "#,
         )
-        .bind(image.id().as_ref())
-        .bind(image.url().as_ref())
-        .bind(image.hash().as_ref())
-        .bind(image.blur_hash().as_ref())
-        .execute(con)
+        .bind(hash.as_ref())
+        .fetch_optional(&mut *con)
         .await
         .convert_error()?;
+        Ok(with_variants(con, row.into_iter().collect()).await?.pop())
+    }
+
+    async fn create(
+        &self,
+        executor: &mut Self::Connection,
+        image: &Image,
+    ) -> error_stack::Result<(), KernelError> {
+        insert_image(executor, image, false).await?;
         Ok(())
     }
 
+    async fn create_upload(
+        &self,
+        executor: &mut Self::Connection,
+        image: &Image,
+    ) -> error_stack::Result<bool, KernelError> {
+        insert_image(executor, image, true).await
+    }
+
     async fn delete(
         &self,
         executor: &mut Self::Connection,
@@ -132,6 +302,365 @@ impl ImageRepository for PostgresImageRepository {
         }
         Ok(())
     }
+
+    async fn find_quarantined(
+        &self,
+        executor: &mut Self::Connection,
+        limit: usize,
+        cursor: Option<i64>,
+    ) -> error_stack::Result<Vec<Image>, KernelError> {
+        let con: &mut PgConnection = executor;
+        let rows = sqlx::query_as::<_, ImageRow>(
+            // language=postgresql
+            r#"
+            SELECT id, url, hash, blurhash, animated, perceptual_hash, quarantined, byte_size, created_at
+            FROM images
+            WHERE quarantined AND ($2::BIGINT IS NULL OR id < $2)
+            ORDER BY id DESC
+            LIMIT $1
+            "#,
+        )
+        .bind(limit as i64)
+        .bind(cursor)
+        .fetch_all(&mut *con)
+        .await
+        .convert_error()?;
+        with_variants(con, rows).await
+    }
+
+    async fn set_quarantined(
+        &self,
+        executor: &mut Self::Connection,
+        image_id: &ImageId,
+        quarantined: bool,
+    ) -> error_stack::Result<bool, KernelError> {
+        let con: &mut PgConnection = executor;
+        let result = sqlx::query(
+            // language=postgresql
+            r#"
+            UPDATE images SET quarantined = $2 WHERE id = $1 AND quarantined <> $2
+            "#,
+        )
+        .bind(image_id.as_ref())
+        .bind(quarantined)
+        .execute(con)
+        .await
+        .convert_error()?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn add_owner(
+        &self,
+        executor: &mut Self::Connection,
+        image_id: &ImageId,
+        owner: &AuthAccountId,
+    ) -> error_stack::Result<bool, KernelError> {
+        let con: &mut PgConnection = executor;
+        let result = sqlx::query(
+            // language=postgresql
+            r#"
+            INSERT INTO image_owners (image_id, owner_id)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(image_id.as_ref())
+        .bind(owner.as_ref())
+        .execute(con)
+        .await
+        .convert_error()?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn remove_owner(
+        &self,
+        executor: &mut Self::Connection,
+        image_id: &ImageId,
+        owner: &AuthAccountId,
+    ) -> error_stack::Result<bool, KernelError> {
+        let con: &mut PgConnection = executor;
+        let result = sqlx::query(
+            // language=postgresql
+            r#"
+            DELETE FROM image_owners WHERE image_id = $1 AND owner_id = $2
+            "#,
+        )
+        .bind(image_id.as_ref())
+        .bind(owner.as_ref())
+        .execute(con)
+        .await
+        .convert_error()?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find_by_id_for_owner(
+        &self,
+        executor: &mut Self::Connection,
+        image_id: &ImageId,
+        owner: &AuthAccountId,
+    ) -> error_stack::Result<Option<Image>, KernelError> {
+        let con: &mut PgConnection = executor;
+        let row = sqlx::query_as::<_, ImageRow>(
+            // language=postgresql
+            r#"
+            SELECT images.id, url, hash, blurhash, animated, image_owners.description, image_owners.focus_x, image_owners.focus_y, perceptual_hash, quarantined, byte_size, images.created_at
+            FROM images
+            JOIN image_owners ON image_owners.image_id = images.id
+            WHERE images.id = $1 AND image_owners.owner_id = $2
+            "#,
+        )
+        .bind(image_id.as_ref())
+        .bind(owner.as_ref())
+        .fetch_optional(&mut *con)
+        .await
+        .convert_error()?;
+        Ok(with_variants(con, row.into_iter().collect()).await?.pop())
+    }
+
+    async fn update_owner_metadata(
+        &self,
+        executor: &mut Self::Connection,
+        image: &Image,
+        owner: &AuthAccountId,
+    ) -> error_stack::Result<(), KernelError> {
+        let con: &mut PgConnection = executor;
+        sqlx::query(
+            // language=postgresql
+            r#"
+            UPDATE image_owners SET description = $3, focus_x = $4, focus_y = $5
+            WHERE image_id = $1 AND owner_id = $2
+            "#,
+        )
+        .bind(image.id().as_ref())
+        .bind(owner.as_ref())
+        .bind(image.description().as_ref().map(AsRef::<String>::as_ref))
+        .bind(image.focus().map(|focus| *focus.x()))
+        .bind(image.focus().map(|focus| *focus.y()))
+        .execute(con)
+        .await
+        .convert_error()?;
+        Ok(())
+    }
+
+    async fn find_profile_images(
+        &self,
+        executor: &mut Self::Connection,
+        references: &[(AccountId, ImageId)],
+    ) -> error_stack::Result<Vec<(AccountId, Image)>, KernelError> {
+        if references.is_empty() {
+            return Ok(Vec::new());
+        }
+        let con: &mut PgConnection = executor;
+        let account_ids: Vec<i64> = references.iter().map(|(id, _)| *id.as_ref()).collect();
+        let image_ids: Vec<i64> = references.iter().map(|(_, id)| *id.as_ref()).collect();
+        let rows = sqlx::query_as::<_, ProfileImageRow>(
+            // language=postgresql
+            r#"
+            SELECT refs.account_id, images.id, url, hash, blurhash, animated, metadata.description, metadata.focus_x, metadata.focus_y, perceptual_hash, quarantined, byte_size, images.created_at
+            FROM UNNEST($1::BIGINT[], $2::BIGINT[]) AS refs (account_id, image_id)
+            JOIN images ON images.id = refs.image_id
+            LEFT JOIN profile_image_metadata AS metadata
+              ON metadata.account_id = refs.account_id AND metadata.image_id = refs.image_id
+            "#,
+        )
+        .bind(&account_ids)
+        .bind(&image_ids)
+        .fetch_all(&mut *con)
+        .await
+        .convert_error()?;
+        let (account_ids, rows): (Vec<_>, Vec<_>) = rows
+            .into_iter()
+            .map(|row| (AccountId::new(row.account_id), row.image))
+            .unzip();
+        let images = with_variants(con, rows).await?;
+        Ok(account_ids.into_iter().zip(images).collect())
+    }
+
+    async fn update_profile_metadata(
+        &self,
+        executor: &mut Self::Connection,
+        account_id: &AccountId,
+        image: &Image,
+    ) -> error_stack::Result<(), KernelError> {
+        let con: &mut PgConnection = executor;
+        sqlx::query(
+            // language=postgresql
+            r#"
+            INSERT INTO profile_image_metadata (account_id, image_id, description, focus_x, focus_y)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (account_id, image_id)
+            DO UPDATE SET description = $3, focus_x = $4, focus_y = $5
+            "#,
+        )
+        .bind(account_id.as_ref())
+        .bind(image.id().as_ref())
+        .bind(image.description().as_ref().map(AsRef::<String>::as_ref))
+        .bind(image.focus().map(|focus| *focus.x()))
+        .bind(image.focus().map(|focus| *focus.y()))
+        .execute(con)
+        .await
+        .convert_error()?;
+        Ok(())
+    }
+
+    async fn find_owners(
+        &self,
+        executor: &mut Self::Connection,
+        image_id: &ImageId,
+    ) -> error_stack::Result<Vec<AuthAccountId>, KernelError> {
+        let con: &mut PgConnection = executor;
+        let owners = sqlx::query_scalar::<_, i64>(
+            // language=postgresql
+            r#"
+            SELECT owner_id FROM image_owners WHERE image_id = $1 ORDER BY created_at, owner_id
+            "#,
+        )
+        .bind(image_id.as_ref())
+        .fetch_all(con)
+        .await
+        .convert_error()?;
+        Ok(owners.into_iter().map(AuthAccountId::new).collect())
+    }
+
+    async fn total_bytes_by_owner(
+        &self,
+        executor: &mut Self::Connection,
+        owner: &AuthAccountId,
+    ) -> error_stack::Result<u64, KernelError> {
+        let con: &mut PgConnection = executor;
+        let total = sqlx::query_scalar::<_, i64>(
+            // language=postgresql
+            r#"
+            SELECT COALESCE(SUM(images.byte_size), 0)::BIGINT
+            FROM image_owners
+            JOIN images ON images.id = image_owners.image_id
+            WHERE image_owners.owner_id = $1
+            "#,
+        )
+        .bind(owner.as_ref())
+        .fetch_one(con)
+        .await
+        .convert_error()?;
+        Ok(total as u64)
+    }
+
+    async fn find_unreferenced(
+        &self,
+        executor: &mut Self::Connection,
+        created_before: OffsetDateTime,
+        limit: usize,
+    ) -> error_stack::Result<Vec<Image>, KernelError> {
+        let con: &mut PgConnection = executor;
+        let rows = sqlx::query_as::<_, ImageRow>(
+            // language=postgresql
+            r#"
+            SELECT id, url, hash, blurhash, animated, perceptual_hash, quarantined, byte_size, created_at
+            FROM images
+            WHERE created_at < $1
+              AND NOT EXISTS (SELECT 1 FROM image_owners WHERE image_id = images.id AND created_at >= $1)
+              AND NOT EXISTS (SELECT 1 FROM profiles WHERE icon_id = images.id OR banner_id = images.id)
+              AND NOT EXISTS (SELECT 1 FROM remote_accounts WHERE icon_id = images.id OR banner_id = images.id)
+            ORDER BY created_at, id
+            LIMIT $2
+            "#,
+        )
+        .bind(created_before)
+        .bind(limit as i64)
+        .fetch_all(&mut *con)
+        .await
+        .convert_error()?;
+        with_variants(con, rows).await
+    }
+
+    async fn delete_unreferenced(
+        &self,
+        executor: &mut Self::Connection,
+        image_id: &ImageId,
+        created_before: OffsetDateTime,
+    ) -> error_stack::Result<bool, KernelError> {
+        let con: &mut PgConnection = executor;
+        let result = sqlx::query(
+            // language=postgresql
+            r#"
+            DELETE FROM images
+            WHERE id = $1
+              AND NOT EXISTS (SELECT 1 FROM image_owners WHERE image_id = $1 AND created_at >= $2)
+              AND NOT EXISTS (SELECT 1 FROM profiles WHERE icon_id = $1 OR banner_id = $1)
+              AND NOT EXISTS (SELECT 1 FROM remote_accounts WHERE icon_id = $1 OR banner_id = $1)
+            "#,
+        )
+        .bind(image_id.as_ref())
+        .bind(created_before)
+        .execute(con)
+        .await
+        .convert_error()?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn delete_last_owned(
+        &self,
+        executor: &mut Self::Connection,
+        image_id: &ImageId,
+        owner: &AuthAccountId,
+    ) -> error_stack::Result<bool, KernelError> {
+        let con: &mut PgConnection = executor;
+        let result = sqlx::query(
+            // language=postgresql
+            r#"
+            DELETE FROM images
+            WHERE id = $1
+              AND NOT EXISTS (SELECT 1 FROM image_owners WHERE image_id = $1 AND owner_id <> $2)
+              AND NOT EXISTS (SELECT 1 FROM profiles WHERE icon_id = $1 OR banner_id = $1)
+              AND NOT EXISTS (SELECT 1 FROM remote_accounts WHERE icon_id = $1 OR banner_id = $1)
+            "#,
+        )
+        .bind(image_id.as_ref())
+        .bind(owner.as_ref())
+        .execute(con)
+        .await
+        .convert_error()?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find_remote_cache_evictions(
+        &self,
+        executor: &mut Self::Connection,
+        unseen_before: OffsetDateTime,
+        max_total_bytes: u64,
+        limit: usize,
+    ) -> error_stack::Result<Vec<Image>, KernelError> {
+        let con: &mut PgConnection = executor;
+        let rows = sqlx::query_as::<_, ImageRow>(
+            // language=postgresql
+            r#"
+            WITH cached AS (
+                SELECT images.id, images.byte_size, remote_accounts.last_seen_at
+                FROM images
+                JOIN remote_accounts
+                  ON remote_accounts.icon_id = images.id OR remote_accounts.banner_id = images.id
+                WHERE NOT EXISTS (SELECT 1 FROM image_owners WHERE image_id = images.id)
+                  AND NOT EXISTS (SELECT 1 FROM profiles WHERE icon_id = images.id OR banner_id = images.id)
+            ), ranked AS (
+                SELECT id, last_seen_at,
+                       SUM(byte_size) OVER (ORDER BY last_seen_at DESC, id DESC) AS retained_bytes
+                FROM cached
+            )
+            SELECT images.id, url, hash, blurhash, animated, perceptual_hash, quarantined, byte_size, created_at
+            FROM images
+            JOIN ranked ON ranked.id = images.id
+            WHERE ranked.last_seen_at < $1 OR ranked.retained_bytes > $2
+            ORDER BY ranked.last_seen_at, images.id
+            LIMIT $3
+            "#,
+        )
+        .bind(unseen_before)
+        .bind(i64::try_from(max_total_bytes).unwrap_or(i64::MAX))
+        .bind(limit as i64)
+        .fetch_all(&mut *con)
+        .await
+        .convert_error()?;
+        with_variants(con, rows).await
+    }
 }
 
 impl DependOnImageRepository for PostgresDatabase {
@@ -299,8 +828,80 @@ mod test {
     mod modifier {
         use crate::database::PostgresDatabase;
         use kernel::interfaces::database::DatabaseConnection;
-        use kernel::interfaces::repository::{DependOnImageRepository, ImageRepository};
-        use kernel::test_utils::ImageBuilder;
+        use kernel::interfaces::read_model::{
+            AccountReadModel, DependOnAccountReadModel, DependOnProfileReadModel, ProfileReadModel,
+        };
+        use kernel::interfaces::repository::{
+            AuthAccountRepository, AuthHostRepository, DependOnAuthAccountRepository,
+            DependOnAuthHostRepository, DependOnImageRepository, DependOnRemoteAccountRepository,
+            ImageRepository, RemoteAccountRepository,
+        };
+        use kernel::prelude::entity::{
+            AccountId, AuthAccountId, AuthHostId, CreatedAt, ImageHash, ImageId, ImageUrl,
+            ImageVariant, ImageVariantKind,
+        };
+        use kernel::test_utils::{
+            unique_image_url, AccountBuilder, AuthAccountBuilder, AuthHostBuilder, ImageBuilder,
+            ProfileBuilder, RemoteAccountBuilder,
+        };
+        use time::{Duration, OffsetDateTime};
+
+        fn variant(kind: ImageVariantKind, width: u32, height: u32) -> ImageVariant {
+            ImageVariant::new(
+                kind,
+                ImageUrl::new(format!(
+                    "{}.{}.webp",
+                    unique_image_url().as_ref(),
+                    kind.as_str()
+                )),
+                width,
+                height,
+                "image/webp".to_string(),
+            )
+        }
+
+        #[test_with::env(DATABASE_URL)]
+        #[tokio::test]
+        async fn create_with_variants() {
+            kernel::ensure_generator_initialized();
+            let database = PostgresDatabase::new().await.unwrap();
+            let mut conn = database.connection().await.unwrap();
+
+            let image = ImageBuilder::new()
+                .variant(variant(ImageVariantKind::Avatar, 400, 400))
+                .variant(variant(ImageVariantKind::Header, 1500, 500))
+                .build();
+
+            database
+                .image_repository()
+                .create(&mut conn, &image)
+                .await
+                .unwrap();
+            let found = database
+                .image_repository()
+                .find_by_id(&mut conn, image.id())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(found, image);
+            assert_eq!(
+                found.variant(ImageVariantKind::Header).map(|v| *v.width()),
+                Some(1500)
+            );
+            assert!(found.variant(ImageVariantKind::Thumbnail).is_none());
+            let found = database
+                .image_repository()
+                .find_by_ids(&mut conn, &[image.id().clone()])
+                .await
+                .unwrap();
+            assert_eq!(found, vec![image.clone()]);
+
+            database
+                .image_repository()
+                .delete(&mut conn, image.id())
+                .await
+                .unwrap();
+        }
 
         #[test_with::env(DATABASE_URL)]
         #[tokio::test]
@@ -343,5 +944,563 @@ mod test {
                 .await
                 .unwrap();
         }
+
+        #[test_with::env(DATABASE_URL)]
+        #[tokio::test]
+        async fn total_bytes_by_owner() {
+            kernel::ensure_generator_initialized();
+            let database = PostgresDatabase::new().await.unwrap();
+            let mut conn = database.connection().await.unwrap();
+
+            let host_id = AuthHostId::default();
+            database
+                .auth_host_repository()
+                .create(
+                    &mut conn,
+                    &AuthHostBuilder::new().id(host_id.clone()).build(),
+                )
+                .await
+                .unwrap();
+            let owner = AuthAccountId::default();
+            database
+                .auth_account_repository()
+                .create(
+                    &mut conn,
+                    &AuthAccountBuilder::new()
+                        .id(owner.clone())
+                        .host(host_id)
+                        .client_id(format!("media-{}", owner.as_ref()))
+                        .build(),
+                )
+                .await
+                .unwrap();
+            let images = [
+                ImageBuilder::new().byte_size(100).build(),
+                ImageBuilder::new().byte_size(250).build(),
+                ImageBuilder::new().byte_size(1000).build(),
+            ];
+            for image in &images {
+                database
+                    .image_repository()
+                    .create(&mut conn, image)
+                    .await
+                    .unwrap();
+            }
+            for image in &images[..2] {
+                assert!(database
+                    .image_repository()
+                    .add_owner(&mut conn, image.id(), &owner)
+                    .await
+                    .unwrap());
+            }
+            assert!(!database
+                .image_repository()
+                .add_owner(&mut conn, images[0].id(), &owner)
+                .await
+                .unwrap());
+
+            let total = database
+                .image_repository()
+                .total_bytes_by_owner(&mut conn, &owner)
+                .await
+                .unwrap();
+            assert_eq!(total, 350);
+            let owners = database
+                .image_repository()
+                .find_owners(&mut conn, images[0].id())
+                .await
+                .unwrap();
+            assert_eq!(owners, vec![owner.clone()]);
+
+            assert!(database
+                .image_repository()
+                .remove_owner(&mut conn, images[0].id(), &owner)
+                .await
+                .unwrap());
+            assert!(!database
+                .image_repository()
+                .remove_owner(&mut conn, images[0].id(), &owner)
+                .await
+                .unwrap());
+            let total = database
+                .image_repository()
+                .total_bytes_by_owner(&mut conn, &owner)
+                .await
+                .unwrap();
+            assert_eq!(total, 250);
+
+            for image in &images {
+                database
+                    .image_repository()
+                    .delete(&mut conn, image.id())
+                    .await
+                    .unwrap();
+            }
+        }
+
+        #[test_with::env(DATABASE_URL)]
+        #[tokio::test]
+        async fn create_upload_keeps_one_upload_per_hash() {
+            kernel::ensure_generator_initialized();
+            let database = PostgresDatabase::new().await.unwrap();
+            let mut conn = database.connection().await.unwrap();
+
+            let hash = format!("{:064x}", kernel::generate_id());
+            let first = ImageBuilder::new().hash(hash.clone()).build();
+            let second = ImageBuilder::new().hash(hash.clone()).build();
+            let remote_copy = ImageBuilder::new().hash(hash.clone()).build();
+            let repository = database.image_repository();
+            assert!(repository.create_upload(&mut conn, &first).await.unwrap());
+            assert!(!repository.create_upload(&mut conn, &second).await.unwrap());
+            // Copies that are not uploads may repeat the bytes.
+            repository.create(&mut conn, &remote_copy).await.unwrap();
+
+            let found = repository
+                .find_by_hash(&mut conn, &ImageHash::new(hash))
+                .await
+                .unwrap();
+            assert_eq!(found.as_ref(), Some(&first));
+            assert!(repository
+                .find_by_id(&mut conn, second.id())
+                .await
+                .unwrap()
+                .is_none());
+            let missing = repository
+                .find_by_hash(&mut conn, &ImageHash::new("0".repeat(63) + "x"))
+                .await
+                .unwrap();
+            assert!(missing.is_none());
+
+            for image in [&first, &remote_copy] {
+                repository.delete(&mut conn, image.id()).await.unwrap();
+            }
+        }
+
+        #[test_with::env(DATABASE_URL)]
+        #[tokio::test]
+        async fn metadata_is_kept_per_owner_and_per_profile() {
+            kernel::ensure_generator_initialized();
+            let database = PostgresDatabase::new().await.unwrap();
+            let mut conn = database.connection().await.unwrap();
+
+            let host_id = AuthHostId::default();
+            database
+                .auth_host_repository()
+                .create(
+                    &mut conn,
+                    &AuthHostBuilder::new().id(host_id.clone()).build(),
+                )
+                .await
+                .unwrap();
+            let owners = [AuthAccountId::default(), AuthAccountId::default()];
+            for owner in &owners {
+                database
+                    .auth_account_repository()
+                    .create(
+                        &mut conn,
+                        &AuthAccountBuilder::new()
+                            .id(owner.clone())
+                            .host(host_id.clone())
+                            .client_id(format!("media-{}", owner.as_ref()))
+                            .build(),
+                    )
+                    .await
+                    .unwrap();
+            }
+            let image = ImageBuilder::new().build();
+            database
+                .image_repository()
+                .create(&mut conn, &image)
+                .await
+                .unwrap();
+            for owner in &owners {
+                database
+                    .image_repository()
+                    .add_owner(&mut conn, image.id(), owner)
+                    .await
+                    .unwrap();
+            }
+            let described = ImageBuilder::new()
+                .id(image.id().clone())
+                .url(image.url().as_ref())
+                .created_at(image.created_at().clone())
+                .description("A cat asleep on a keyboard")
+                .focus(-0.25, 0.5)
+                .build();
+            database
+                .image_repository()
+                .update_owner_metadata(&mut conn, &described, &owners[0])
+                .await
+                .unwrap();
+
+            let found = database
+                .image_repository()
+                .find_by_id_for_owner(&mut conn, image.id(), &owners[0])
+                .await
+                .unwrap();
+            assert_eq!(found.as_ref(), Some(&described));
+            let found = database
+                .image_repository()
+                .find_by_id_for_owner(&mut conn, image.id(), &owners[1])
+                .await
+                .unwrap();
+            assert_eq!(found.as_ref(), Some(&image));
+            let found = database
+                .image_repository()
+                .find_by_id(&mut conn, image.id())
+                .await
+                .unwrap();
+            assert_eq!(found.as_ref(), Some(&image));
+
+            let accounts = [AccountId::default(), AccountId::default()];
+            for account_id in &accounts {
+                database
+                    .account_read_model()
+                    .create(
+                        &mut conn,
+                        &AccountBuilder::new().id(account_id.clone()).build(),
+                    )
+                    .await
+                    .unwrap();
+            }
+            database
+                .image_repository()
+                .update_profile_metadata(&mut conn, &accounts[0], &described)
+                .await
+                .unwrap();
+            let references = [
+                (accounts[0].clone(), image.id().clone()),
+                (accounts[1].clone(), image.id().clone()),
+                (accounts[1].clone(), ImageId::new(kernel::generate_id())),
+            ];
+            let mut found = database
+                .image_repository()
+                .find_profile_images(&mut conn, &references)
+                .await
+                .unwrap();
+            found.sort_by_key(|(account_id, _)| *account_id.as_ref());
+            let mut expected = vec![
+                (accounts[0].clone(), described.clone()),
+                (accounts[1].clone(), image.clone()),
+            ];
+            expected.sort_by_key(|(account_id, _)| *account_id.as_ref());
+            assert_eq!(found, expected);
+
+            database
+                .image_repository()
+                .update_profile_metadata(&mut conn, &accounts[0], &image)
+                .await
+                .unwrap();
+            let found = database
+                .image_repository()
+                .find_profile_images(&mut conn, &references[..1])
+                .await
+                .unwrap();
+            assert_eq!(found, vec![(accounts[0].clone(), image.clone())]);
+
+            for account_id in &accounts {
+                database
+                    .account_read_model()
+                    .deactivate(&mut conn, account_id)
+                    .await
+                    .unwrap();
+            }
+            database
+                .image_repository()
+                .delete(&mut conn, image.id())
+                .await
+                .unwrap();
+        }
+
+        #[test_with::env(DATABASE_URL)]
+        #[tokio::test]
+        async fn quarantined_images_are_listed_and_released() {
+            kernel::ensure_generator_initialized();
+            let database = PostgresDatabase::new().await.unwrap();
+            let mut conn = database.connection().await.unwrap();
+
+            let image = ImageBuilder::new()
+                .perceptual_hash(u64::MAX)
+                .quarantined(true)
+                .build();
+            database
+                .image_repository()
+                .create(&mut conn, &image)
+                .await
+                .unwrap();
+            let found = database
+                .image_repository()
+                .find_by_id(&mut conn, image.id())
+                .await
+                .unwrap();
+            assert_eq!(found.as_ref(), Some(&image));
+
+            let quarantined = database
+                .image_repository()
+                .find_quarantined(&mut conn, 100, None)
+                .await
+                .unwrap();
+            assert!(quarantined.iter().any(|found| found.id() == image.id()));
+            let after = database
+                .image_repository()
+                .find_quarantined(&mut conn, 100, Some(*image.id().as_ref()))
+                .await
+                .unwrap();
+            assert!(after.iter().all(|found| found.id() != image.id()));
+
+            assert!(database
+                .image_repository()
+                .set_quarantined(&mut conn, image.id(), false)
+                .await
+                .unwrap());
+            assert!(!database
+                .image_repository()
+                .set_quarantined(&mut conn, image.id(), false)
+                .await
+                .unwrap());
+            let quarantined = database
+                .image_repository()
+                .find_quarantined(&mut conn, 100, None)
+                .await
+                .unwrap();
+            assert!(quarantined.iter().all(|found| found.id() != image.id()));
+
+            database
+                .image_repository()
+                .delete(&mut conn, image.id())
+                .await
+                .unwrap();
+        }
+
+        #[test_with::env(DATABASE_URL)]
+        #[tokio::test]
+        async fn unreferenced_images_are_found_and_deleted() {
+            kernel::ensure_generator_initialized();
+            let database = PostgresDatabase::new().await.unwrap();
+            let mut conn = database.connection().await.unwrap();
+
+            // Far in the past so images left by other tests never compete.
+            let old_time = OffsetDateTime::from_unix_timestamp(946_684_800).unwrap();
+            let old = CreatedAt::new(old_time);
+            let cutoff = old_time + Duration::days(1);
+            let orphan = ImageBuilder::new().created_at(old.clone()).build();
+            let icon = ImageBuilder::new().created_at(old).build();
+            let recent = ImageBuilder::new().build();
+            for image in [&orphan, &icon, &recent] {
+                database
+                    .image_repository()
+                    .create(&mut conn, image)
+                    .await
+                    .unwrap();
+            }
+            let account_id = AccountId::default();
+            let account = AccountBuilder::new().id(account_id.clone()).build();
+            database
+                .account_read_model()
+                .create(&mut conn, &account)
+                .await
+                .unwrap();
+            database
+                .profile_read_model()
+                .create(
+                    &mut conn,
+                    &ProfileBuilder::new()
+                        .account_id(account_id)
+                        .icon(Some(icon.id().clone()))
+                        .build(),
+                )
+                .await
+                .unwrap();
+
+            let candidates = database
+                .image_repository()
+                .find_unreferenced(&mut conn, cutoff, 100)
+                .await
+                .unwrap();
+            assert!(candidates.contains(&orphan));
+            assert!(!candidates.contains(&icon));
+            assert!(!candidates.contains(&recent));
+            assert!(!database
+                .image_repository()
+                .delete_unreferenced(&mut conn, icon.id(), cutoff)
+                .await
+                .unwrap());
+            assert!(database
+                .image_repository()
+                .delete_unreferenced(&mut conn, orphan.id(), cutoff)
+                .await
+                .unwrap());
+            assert!(!database
+                .image_repository()
+                .delete_unreferenced(&mut conn, orphan.id(), cutoff)
+                .await
+                .unwrap());
+
+            database
+                .account_read_model()
+                .deactivate(&mut conn, account.id())
+                .await
+                .unwrap();
+            for image in [&icon, &recent] {
+                database
+                    .image_repository()
+                    .delete(&mut conn, image.id())
+                    .await
+                    .unwrap();
+            }
+        }
+
+        #[test_with::env(DATABASE_URL)]
+        #[tokio::test]
+        async fn owner_added_after_candidate_read_keeps_image() {
+            kernel::ensure_generator_initialized();
+            let database = PostgresDatabase::new().await.unwrap();
+            let mut conn = database.connection().await.unwrap();
+
+            let host_id = AuthHostId::default();
+            database
+                .auth_host_repository()
+                .create(
+                    &mut conn,
+                    &AuthHostBuilder::new().id(host_id.clone()).build(),
+                )
+                .await
+                .unwrap();
+            let owners = [AuthAccountId::default(), AuthAccountId::default()];
+            for owner in &owners {
+                database
+                    .auth_account_repository()
+                    .create(
+                        &mut conn,
+                        &AuthAccountBuilder::new()
+                            .id(owner.clone())
+                            .host(host_id.clone())
+                            .client_id(format!("media-{}", owner.as_ref()))
+                            .build(),
+                    )
+                    .await
+                    .unwrap();
+            }
+            // Far in the past so images left by other tests never compete.
+            let old_time = OffsetDateTime::from_unix_timestamp(946_684_800).unwrap();
+            let cutoff = old_time + Duration::days(1);
+            let image = ImageBuilder::new()
+                .created_at(CreatedAt::new(old_time))
+                .build();
+            database
+                .image_repository()
+                .create(&mut conn, &image)
+                .await
+                .unwrap();
+            database
+                .image_repository()
+                .add_owner(&mut conn, image.id(), &owners[0])
+                .await
+                .unwrap();
+            sqlx::query("UPDATE image_owners SET created_at = $2 WHERE image_id = $1")
+                .bind(image.id().as_ref())
+                .bind(old_time)
+                .execute(&mut *conn)
+                .await
+                .unwrap();
+
+            let candidates = database
+                .image_repository()
+                .find_unreferenced(&mut conn, cutoff, 100)
+                .await
+                .unwrap();
+            assert!(candidates.contains(&image));
+            // A duplicate upload lands between the read and the delete.
+            database
+                .image_repository()
+                .add_owner(&mut conn, image.id(), &owners[1])
+                .await
+                .unwrap();
+            assert!(!database
+                .image_repository()
+                .delete_unreferenced(&mut conn, image.id(), cutoff)
+                .await
+                .unwrap());
+            assert!(!database
+                .image_repository()
+                .delete_last_owned(&mut conn, image.id(), &owners[0])
+                .await
+                .unwrap());
+            assert!(database
+                .image_repository()
+                .find_by_id(&mut conn, image.id())
+                .await
+                .unwrap()
+                .is_some());
+            let mut found = database
+                .image_repository()
+                .find_owners(&mut conn, image.id())
+                .await
+                .unwrap();
+            found.sort_by_key(|owner| *owner.as_ref());
+            let mut expected = owners.to_vec();
+            expected.sort_by_key(|owner| *owner.as_ref());
+            assert_eq!(found, expected);
+
+            database
+                .image_repository()
+                .remove_owner(&mut conn, image.id(), &owners[1])
+                .await
+                .unwrap();
+            assert!(database
+                .image_repository()
+                .delete_last_owned(&mut conn, image.id(), &owners[0])
+                .await
+                .unwrap());
+        }
+
+        #[test_with::env(DATABASE_URL)]
+        #[tokio::test]
+        async fn remote_cache_evicts_by_age_and_total_size() {
+            kernel::ensure_generator_initialized();
+            let database = PostgresDatabase::new().await.unwrap();
+            let mut conn = database.connection().await.unwrap();
+
+            let icon = ImageBuilder::new().byte_size(1000).build();
+            database
+                .image_repository()
+                .create(&mut conn, &icon)
+                .await
+                .unwrap();
+            let remote_account = RemoteAccountBuilder::new()
+                .icon_id(Some(icon.id().clone()))
+                .build();
+            database
+                .remote_account_repository()
+                .create(&mut conn, &remote_account)
+                .await
+                .unwrap();
+
+            let now = OffsetDateTime::now_utc();
+            let cases = [
+                (now - Duration::hours(1), u64::MAX, false),
+                (now + Duration::hours(1), u64::MAX, true),
+                (now - Duration::hours(1), 0, true),
+            ];
+            for (unseen_before, max_total_bytes, evicted) in cases {
+                let evictions = database
+                    .image_repository()
+                    .find_remote_cache_evictions(&mut conn, unseen_before, max_total_bytes, 10_000)
+                    .await
+                    .unwrap();
+                assert_eq!(evictions.contains(&icon), evicted);
+            }
+
+            database
+                .remote_account_repository()
+                .delete(&mut conn, remote_account.id())
+                .await
+                .unwrap();
+            database
+                .image_repository()
+                .delete(&mut conn, icon.id())
+                .await
+                .unwrap();
+        }
     }
 }
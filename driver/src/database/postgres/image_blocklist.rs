@@ -0,0 +1,261 @@
+use crate::database::{PostgresConnection, PostgresDatabase};
+use crate::ConvertError;
+use error_stack::Report;
+use kernel::interfaces::repository::{DependOnImageBlocklistRepository, ImageBlocklistRepository};
+use kernel::prelude::entity::{
+    AuthAccountId, ImageBlocklistAction, ImageBlocklistEntry, ImageBlocklistEntryId, ImageHash,
+    ImagePerceptualHash,
+};
+use kernel::KernelError;
+use sqlx::PgConnection;
+use time::OffsetDateTime;
+
+#[derive(sqlx::FromRow)]
+struct ImageBlocklistRow {
+    id: i64,
+    hash: Option<String>,
+    perceptual_hash: Option<i64>,
+    action: String,
+    reason: String,
+    created_by: i64,
+    created_at: OffsetDateTime,
+}
+
+impl TryFrom<ImageBlocklistRow> for ImageBlocklistEntry {
+    type Error = Report<KernelError>;
+
+    fn try_from(value: ImageBlocklistRow) -> Result<Self, Self::Error> {
+        let action = ImageBlocklistAction::parse(&value.action).ok_or_else(|| {
+            Report::new(KernelError::Internal)
+                .attach_printable(format!("Unknown image blocklist action: {}", value.action))
+        })?;
+        Ok(ImageBlocklistEntry {
+            id: ImageBlocklistEntryId::new(value.id),
+            hash: value.hash.map(ImageHash::new),
+            perceptual_hash: value
+                .perceptual_hash
+                .map(|hash| ImagePerceptualHash::new(hash as u64)),
+            action,
+            reason: value.reason,
+            created_by: AuthAccountId::new(value.created_by),
+            created_at: value.created_at,
+        })
+    }
+}
+
+fn convert_entries(
+    rows: Vec<ImageBlocklistRow>,
+) -> error_stack::Result<Vec<ImageBlocklistEntry>, KernelError> {
+    rows.into_iter()
+        .map(ImageBlocklistEntry::try_from)
+        .collect()
+}
+
+pub struct PostgresImageBlocklistRepository;
+
+impl ImageBlocklistRepository for PostgresImageBlocklistRepository {
+    type Connection = PostgresConnection;
+
+    async fn create(
+        &self,
+        executor: &mut Self::Connection,
+        entry: &ImageBlocklistEntry,
+    ) -> error_stack::Result<(), KernelError> {
+        let con: &mut PgConnection = executor;
+        // language=postgresql
+        sqlx::query(
+            r#"
+            INSERT INTO image_blocklist
+                (id, hash, perceptual_hash, action, reason, created_by, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+        )
+        .bind(entry.id.as_ref())
+        .bind(entry.hash.as_ref().map(AsRef::<String>::as_ref))
+        .bind(entry.perceptual_hash.map(|hash| *hash.as_ref() as i64))
+        .bind(entry.action.as_str())
+        .bind(&entry.reason)
+        .bind(entry.created_by.as_ref())
+        .bind(entry.created_at)
+        .execute(con)
+        .await
+        .convert_error()?;
+        Ok(())
+    }
+
+    async fn find_all(
+        &self,
+        executor: &mut Self::Connection,
+    ) -> error_stack::Result<Vec<ImageBlocklistEntry>, KernelError> {
+        let con: &mut PgConnection = executor;
+        // language=postgresql
+        let rows = sqlx::query_as::<_, ImageBlocklistRow>(
+            r#"
+            SELECT id, hash, perceptual_hash, action, reason, created_by, created_at
+            FROM image_blocklist
+            ORDER BY id DESC
+            "#,
+        )
+        .fetch_all(con)
+        .await
+        .convert_error()?;
+        convert_entries(rows)
+    }
+
+    async fn find_matches(
+        &self,
+        executor: &mut Self::Connection,
+        hash: &ImageHash,
+        perceptual_hash: Option<&ImagePerceptualHash>,
+        max_distance: u32,
+    ) -> error_stack::Result<Vec<ImageBlocklistEntry>, KernelError> {
+        let con: &mut PgConnection = executor;
+        // language=postgresql
+        let rows = sqlx::query_as::<_, ImageBlocklistRow>(
+            r#"
+            SELECT id, hash, perceptual_hash, action, reason, created_by, created_at
+            FROM image_blocklist
+            WHERE hash = $1
+               OR ($2::BIGINT IS NOT NULL
+                   AND bit_count((perceptual_hash # $2)::BIT(64)) <= $3)
+            ORDER BY id
+            "#,
+        )
+        .bind(hash.as_ref())
+        .bind(perceptual_hash.map(|hash| *hash.as_ref() as i64))
+        .bind(max_distance as i64)
+        .fetch_all(con)
+        .await
+        .convert_error()?;
+        convert_entries(rows)
+    }
+
+    async fn delete(
+        &self,
+        executor: &mut Self::Connection,
+        id: &ImageBlocklistEntryId,
+    ) -> error_stack::Result<bool, KernelError> {
+        let con: &mut PgConnection = executor;
+        // language=postgresql
+        let result = sqlx::query(
+            r#"
+            DELETE FROM image_blocklist WHERE id = $1
+            "#,
+        )
+        .bind(id.as_ref())
+        .execute(con)
+        .await
+        .convert_error()?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+impl DependOnImageBlocklistRepository for PostgresDatabase {
+    type ImageBlocklistRepository = PostgresImageBlocklistRepository;
+
+    fn image_blocklist_repository(&self) -> &Self::ImageBlocklistRepository {
+        &PostgresImageBlocklistRepository
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kernel::interfaces::database::DatabaseConnection;
+    use kernel::interfaces::repository::{
+        AuthAccountRepository, AuthHostRepository, DependOnAuthAccountRepository,
+        DependOnAuthHostRepository,
+    };
+    use kernel::prelude::entity::AuthHostId;
+    use kernel::test_utils::{AuthAccountBuilder, AuthHostBuilder};
+
+    fn entry(
+        moderator: &AuthAccountId,
+        hash: Option<&str>,
+        perceptual_hash: Option<u64>,
+    ) -> ImageBlocklistEntry {
+        ImageBlocklistEntry {
+            id: ImageBlocklistEntryId::default(),
+            hash: hash.map(ImageHash::new),
+            perceptual_hash: perceptual_hash.map(ImagePerceptualHash::new),
+            action: ImageBlocklistAction::Quarantine,
+            reason: "Known bad".to_string(),
+            created_by: moderator.clone(),
+            created_at: OffsetDateTime::now_utc().replace_nanosecond(0).unwrap(),
+        }
+    }
+
+    #[test_with::env(DATABASE_URL)]
+    #[tokio::test]
+    async fn matches_by_exact_hash_and_perceptual_distance() {
+        kernel::ensure_generator_initialized();
+        let database = PostgresDatabase::new().await.unwrap();
+        let mut executor = database.connection().await.unwrap();
+        let host_id = AuthHostId::default();
+        database
+            .auth_host_repository()
+            .create(
+                &mut executor,
+                &AuthHostBuilder::new().id(host_id.clone()).build(),
+            )
+            .await
+            .unwrap();
+        let moderator = AuthAccountId::default();
+        database
+            .auth_account_repository()
+            .create(
+                &mut executor,
+                &AuthAccountBuilder::new()
+                    .id(moderator.clone())
+                    .host(host_id)
+                    .client_id(format!("blocklist-{}", moderator.as_ref()))
+                    .build(),
+            )
+            .await
+            .unwrap();
+
+        // Unique per run, so entries left by other tests cannot match.
+        let exact_hash = format!("blocked-{}", moderator.as_ref());
+        let perceptual = (*moderator.as_ref() as u64).rotate_left(17) | 1 << 63;
+        let by_hash = entry(&moderator, Some(&exact_hash), None);
+        let by_perceptual = entry(&moderator, None, Some(perceptual));
+        for entry in [&by_hash, &by_perceptual] {
+            database
+                .image_blocklist_repository()
+                .create(&mut executor, entry)
+                .await
+                .unwrap();
+        }
+
+        let all = database
+            .image_blocklist_repository()
+            .find_all(&mut executor)
+            .await
+            .unwrap();
+        assert!(all.contains(&by_hash) && all.contains(&by_perceptual));
+
+        let repository = database.image_blocklist_repository();
+        let other_hash = ImageHash::new(format!("other-{}", moderator.as_ref()));
+        let matches = repository
+            .find_matches(&mut executor, &ImageHash::new(exact_hash.clone()), None, 4)
+            .await
+            .unwrap();
+        assert_eq!(matches, vec![by_hash.clone()]);
+        let near = ImagePerceptualHash::new(perceptual ^ 0b1011);
+        let matches = repository
+            .find_matches(&mut executor, &other_hash, Some(&near), 3)
+            .await
+            .unwrap();
+        assert_eq!(matches, vec![by_perceptual.clone()]);
+        let matches = repository
+            .find_matches(&mut executor, &other_hash, Some(&near), 2)
+            .await
+            .unwrap();
+        assert!(matches.is_empty());
+
+        for entry in [&by_hash, &by_perceptual] {
+            assert!(repository.delete(&mut executor, &entry.id).await.unwrap());
+        }
+        assert!(!repository.delete(&mut executor, &by_hash.id).await.unwrap());
+    }
+}
@@ -0,0 +1,167 @@
+use crate::database::{PostgresConnection, PostgresDatabase};
+use crate::ConvertError;
+use error_stack::Report;
+use kernel::interfaces::permission::InstanceRole;
+use kernel::interfaces::repository::{
+    DependOnInstanceRoleChangeRepository, InstanceRoleChangeRepository,
+};
+use kernel::prelude::entity::{
+    AuthAccountId, InstanceRoleChange, InstanceRoleChangeAction, InstanceRoleChangeId,
+};
+use kernel::KernelError;
+use sqlx::PgConnection;
+use time::OffsetDateTime;
+
+#[derive(sqlx::FromRow)]
+struct InstanceRoleChangeRow {
+    id: i64,
+    role: String,
+    action: String,
+    subject_id: i64,
+    actor_id: i64,
+    created_at: OffsetDateTime,
+}
+
+impl TryFrom<InstanceRoleChangeRow> for InstanceRoleChange {
+    type Error = Report<KernelError>;
+
+    fn try_from(value: InstanceRoleChangeRow) -> Result<Self, Self::Error> {
+        let role = InstanceRole::parse(&value.role).ok_or_else(|| {
+            Report::new(KernelError::Internal)
+                .attach_printable(format!("Unknown instance role: {}", value.role))
+        })?;
+        let action = InstanceRoleChangeAction::parse(&value.action).ok_or_else(|| {
+            Report::new(KernelError::Internal).attach_printable(format!(
+                "Unknown instance role change action: {}",
+                value.action
+            ))
+        })?;
+        Ok(InstanceRoleChange {
+            id: InstanceRoleChangeId::new(value.id),
+            role,
+            action,
+            subject: AuthAccountId::new(value.subject_id),
+            actor: AuthAccountId::new(value.actor_id),
+            created_at: value.created_at,
+        })
+    }
+}
+
+pub struct PostgresInstanceRoleChangeRepository;
+
+impl InstanceRoleChangeRepository for PostgresInstanceRoleChangeRepository {
+    type Connection = PostgresConnection;
+
+    async fn create(
+        &self,
+        executor: &mut Self::Connection,
+        change: &InstanceRoleChange,
+    ) -> error_stack::Result<(), KernelError> {
+        let con: &mut PgConnection = executor;
+        // language=postgresql
+        sqlx::query(
+            r#"
+            INSERT INTO instance_role_changes
+                (id, role, action, subject_id, actor_id, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            "#,
+        )
+        .bind(change.id.as_ref())
+        .bind(change.role.as_str())
+        .bind(change.action.as_str())
+        .bind(change.subject.as_ref())
+        .bind(change.actor.as_ref())
+        .bind(change.created_at)
+        .execute(con)
+        .await
+        .convert_error()?;
+        Ok(())
+    }
+
+    async fn find_page(
+        &self,
+        executor: &mut Self::Connection,
+        limit: usize,
+        cursor: Option<i64>,
+    ) -> error_stack::Result<Vec<InstanceRoleChange>, KernelError> {
+        let con: &mut PgConnection = executor;
+        // language=postgresql
+        let rows = sqlx::query_as::<_, InstanceRoleChangeRow>(
+            r#"
+            SELECT id, role, action, subject_id, actor_id, created_at
+            FROM instance_role_changes
+            WHERE $2::BIGINT IS NULL OR id < $2
+            ORDER BY id DESC
+            LIMIT $1
+            "#,
+        )
+        .bind(limit as i64)
+        .bind(cursor)
+        .fetch_all(con)
+        .await
+        .convert_error()?;
+        rows.into_iter().map(InstanceRoleChange::try_from).collect()
+    }
+}
+
+impl DependOnInstanceRoleChangeRepository for PostgresDatabase {
+    type InstanceRoleChangeRepository = PostgresInstanceRoleChangeRepository;
+
+    fn instance_role_change_repository(&self) -> &Self::InstanceRoleChangeRepository {
+        &PostgresInstanceRoleChangeRepository
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kernel::interfaces::database::DatabaseConnection;
+
+    #[test_with::env(DATABASE_URL)]
+    #[tokio::test]
+    async fn create_and_page_newest_first() {
+        kernel::ensure_generator_initialized();
+        let database = PostgresDatabase::new().await.unwrap();
+        let mut executor = database.connection().await.unwrap();
+        let actor = AuthAccountId::default();
+        let subject = AuthAccountId::default();
+        let now = OffsetDateTime::now_utc().replace_nanosecond(0).unwrap();
+
+        let changes: Vec<InstanceRoleChange> = [
+            (InstanceRole::Moderator, InstanceRoleChangeAction::Granted),
+            (InstanceRole::Admin, InstanceRoleChangeAction::Granted),
+            (InstanceRole::Moderator, InstanceRoleChangeAction::Revoked),
+        ]
+        .into_iter()
+        .map(|(role, action)| InstanceRoleChange {
+            id: InstanceRoleChangeId::default(),
+            role,
+            action,
+            subject: subject.clone(),
+            actor: actor.clone(),
+            created_at: now,
+        })
+        .collect();
+        for change in &changes {
+            database
+                .instance_role_change_repository()
+                .create(&mut executor, change)
+                .await
+                .unwrap();
+        }
+
+        let older = database
+            .instance_role_change_repository()
+            .find_page(&mut executor, 2, Some(*changes[2].id.as_ref()))
+            .await
+            .unwrap();
+        assert_eq!(older, vec![changes[1].clone(), changes[0].clone()]);
+
+        let single = database
+            .instance_role_change_repository()
+            .find_page(&mut executor, 1, Some(*changes[1].id.as_ref()))
+            .await
+            .unwrap();
+        assert_eq!(single, vec![changes[0].clone()]);
+    }
+}
@@ -0,0 +1,306 @@
+use crate::database::{PostgresConnection, PostgresDatabase};
+use crate::ConvertError;
+use kernel::interfaces::permission::Scope;
+use kernel::interfaces::repository::{
+    DependOnPersonalAccessTokenRepository, PersonalAccessTokenRepository,
+};
+use kernel::prelude::entity::{
+    AccountId, AuthAccountId, PersonalAccessToken, PersonalAccessTokenId,
+};
+use kernel::KernelError;
+use sqlx::PgConnection;
+use time::OffsetDateTime;
+
+#[derive(sqlx::FromRow)]
+struct PersonalAccessTokenRow {
+    id: i64,
+    owner_id: i64,
+    name: String,
+    token_hash: String,
+    scopes: Vec<String>,
+    account_ids: Option<Vec<i64>>,
+    expires_at: Option<OffsetDateTime>,
+    last_used_at: Option<OffsetDateTime>,
+    created_at: OffsetDateTime,
+}
+
+impl From<PersonalAccessTokenRow> for PersonalAccessToken {
+    fn from(value: PersonalAccessTokenRow) -> Self {
+        PersonalAccessToken {
+            id: PersonalAccessTokenId::new(value.id),
+            owner: AuthAccountId::new(value.owner_id),
+            name: value.name,
+            token_hash: value.token_hash,
+            // Scopes this version no longer knows grant nothing.
+            scopes: value
+                .scopes
+                .iter()
+                .filter_map(|scope| Scope::parse(scope))
+                .collect(),
+            account_ids: value
+                .account_ids
+                .map(|ids| ids.into_iter().map(AccountId::new).collect()),
+            expires_at: value.expires_at,
+            last_used_at: value.last_used_at,
+            created_at: value.created_at,
+        }
+    }
+}
+
+pub struct PostgresPersonalAccessTokenRepository;
+
+impl PersonalAccessTokenRepository for PostgresPersonalAccessTokenRepository {
+    type Connection = PostgresConnection;
+
+    async fn create(
+        &self,
+        executor: &mut Self::Connection,
+        token: &PersonalAccessToken,
+    ) -> error_stack::Result<(), KernelError> {
+        let con: &mut PgConnection = executor;
+        let scopes: Vec<&str> = token.scopes.iter().map(Scope::as_str).collect();
+        let account_ids: Option<Vec<i64>> = token
+            .account_ids
+            .as_ref()
+            .map(|ids| ids.iter().map(|id| *id.as_ref()).collect());
+        // language=postgresql
+        sqlx::query(
+            r#"
+            INSERT INTO personal_access_tokens
+                (id, owner_id, name, token_hash, scopes, account_ids,
+                 expires_at, last_used_at, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            "#,
+        )
+        .bind(token.id.as_ref())
+        .bind(token.owner.as_ref())
+        .bind(&token.name)
+        .bind(&token.token_hash)
+        .bind(scopes)
+        .bind(account_ids)
+        .bind(token.expires_at)
+        .bind(token.last_used_at)
+        .bind(token.created_at)
+        .execute(con)
+        .await
+        .convert_error()?;
+        Ok(())
+    }
+
+    async fn find_by_token_hash(
+        &self,
+        executor: &mut Self::Connection,
+        token_hash: &str,
+    ) -> error_stack::Result<Option<PersonalAccessToken>, KernelError> {
+        let con: &mut PgConnection = executor;
+        // language=postgresql
+        sqlx::query_as::<_, PersonalAccessTokenRow>(
+            r#"
+            SELECT id, owner_id, name, token_hash, scopes, account_ids,
+                   expires_at, last_used_at, created_at
+            FROM personal_access_tokens
+            WHERE token_hash = $1
+            "#,
+        )
+        .bind(token_hash)
+        .fetch_optional(con)
+        .await
+        .convert_error()
+        .map(|row| row.map(PersonalAccessToken::from))
+    }
+
+    async fn find_by_owner(
+        &self,
+        executor: &mut Self::Connection,
+        owner: &AuthAccountId,
+    ) -> error_stack::Result<Vec<PersonalAccessToken>, KernelError> {
+        let con: &mut PgConnection = executor;
+        // language=postgresql
+        sqlx::query_as::<_, PersonalAccessTokenRow>(
+            r#"
+            SELECT id, owner_id, name, token_hash, scopes, account_ids,
+                   expires_at, last_used_at, created_at
+            FROM personal_access_tokens
+            WHERE owner_id = $1
+            ORDER BY id DESC
+            "#,
+        )
+        .bind(owner.as_ref())
+        .fetch_all(con)
+        .await
+        .convert_error()
+        .map(|rows| rows.into_iter().map(PersonalAccessToken::from).collect())
+    }
+
+    async fn touch(
+        &self,
+        executor: &mut Self::Connection,
+        id: &PersonalAccessTokenId,
+        now: OffsetDateTime,
+    ) -> error_stack::Result<(), KernelError> {
+        let con: &mut PgConnection = executor;
+        // language=postgresql
+        sqlx::query(
+            r#"
+            UPDATE personal_access_tokens
+            SET last_used_at = $2
+            WHERE id = $1
+              AND (last_used_at IS NULL OR last_used_at < $2 - INTERVAL '1 minute')
+            "#,
+        )
+        .bind(id.as_ref())
+        .bind(now)
+        .execute(con)
+        .await
+        .convert_error()?;
+        Ok(())
+    }
+
+    async fn delete(
+        &self,
+        executor: &mut Self::Connection,
+        id: &PersonalAccessTokenId,
+        owner: &AuthAccountId,
+    ) -> error_stack::Result<bool, KernelError> {
+        let con: &mut PgConnection = executor;
+        // language=postgresql
+        let result = sqlx::query(
+            r#"
+            DELETE FROM personal_access_tokens WHERE id = $1 AND owner_id = $2
+            "#,
+        )
+        .bind(id.as_ref())
+        .bind(owner.as_ref())
+        .execute(con)
+        .await
+        .convert_error()?;
+        Ok(result.rows_affected() > 0)
+    }
+}
+
+impl DependOnPersonalAccessTokenRepository for PostgresDatabase {
+    type PersonalAccessTokenRepository = PostgresPersonalAccessTokenRepository;
+
+    fn personal_access_token_repository(&self) -> &Self::PersonalAccessTokenRepository {
+        &PostgresPersonalAccessTokenRepository
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kernel::interfaces::database::DatabaseConnection;
+    use kernel::interfaces::repository::{
+        AuthAccountRepository, AuthHostRepository, DependOnAuthAccountRepository,
+        DependOnAuthHostRepository,
+    };
+    use kernel::prelude::entity::AuthHostId;
+    use kernel::test_utils::{AuthAccountBuilder, AuthHostBuilder};
+    use time::Duration;
+
+    #[test_with::env(DATABASE_URL)]
+    #[tokio::test]
+    async fn create_find_touch_and_delete() {
+        kernel::ensure_generator_initialized();
+        let database = PostgresDatabase::new().await.unwrap();
+        let mut executor = database.connection().await.unwrap();
+        let host_id = AuthHostId::default();
+        database
+            .auth_host_repository()
+            .create(
+                &mut executor,
+                &AuthHostBuilder::new().id(host_id.clone()).build(),
+            )
+            .await
+            .unwrap();
+        let owner = AuthAccountId::default();
+        database
+            .auth_account_repository()
+            .create(
+                &mut executor,
+                &AuthAccountBuilder::new()
+                    .id(owner.clone())
+                    .host(host_id)
+                    .client_id(format!("pat-{}", owner.as_ref()))
+                    .build(),
+            )
+            .await
+            .unwrap();
+
+        let now = OffsetDateTime::now_utc().replace_nanosecond(0).unwrap();
+        let token = PersonalAccessToken {
+            id: PersonalAccessTokenId::default(),
+            owner: owner.clone(),
+            name: "CI".to_string(),
+            token_hash: format!("hash-{}", owner.as_ref()),
+            scopes: vec![Scope::AccountsRead, Scope::Sign],
+            account_ids: Some(vec![AccountId::new(1), AccountId::new(2)]),
+            expires_at: Some(now + Duration::days(30)),
+            last_used_at: None,
+            created_at: now,
+        };
+        let unrestricted = PersonalAccessToken {
+            id: PersonalAccessTokenId::default(),
+            name: "Bot".to_string(),
+            token_hash: format!("other-hash-{}", owner.as_ref()),
+            account_ids: None,
+            expires_at: None,
+            ..token.clone()
+        };
+        for token in [&token, &unrestricted] {
+            database
+                .personal_access_token_repository()
+                .create(&mut executor, token)
+                .await
+                .unwrap();
+        }
+
+        let found = database
+            .personal_access_token_repository()
+            .find_by_token_hash(&mut executor, &token.token_hash)
+            .await
+            .unwrap();
+        assert_eq!(found, Some(token.clone()));
+        let owned = database
+            .personal_access_token_repository()
+            .find_by_owner(&mut executor, &owner)
+            .await
+            .unwrap();
+        assert_eq!(owned, vec![unrestricted.clone(), token.clone()]);
+
+        // Touching twice within a minute keeps the first timestamp.
+        for offset in [0, 30] {
+            database
+                .personal_access_token_repository()
+                .touch(&mut executor, &token.id, now + Duration::seconds(offset))
+                .await
+                .unwrap();
+        }
+        let touched = database
+            .personal_access_token_repository()
+            .find_by_token_hash(&mut executor, &token.token_hash)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(touched.last_used_at, Some(now));
+
+        assert!(!database
+            .personal_access_token_repository()
+            .delete(&mut executor, &token.id, &AuthAccountId::default())
+            .await
+            .unwrap());
+        for token in [&token, &unrestricted] {
+            assert!(database
+                .personal_access_token_repository()
+                .delete(&mut executor, &token.id, &owner)
+                .await
+                .unwrap());
+        }
+        let found = database
+            .personal_access_token_repository()
+            .find_by_token_hash(&mut executor, &token.token_hash)
+            .await
+            .unwrap();
+        assert!(found.is_none());
+    }
+}
@@ -114,6 +114,26 @@ impl ProfileReadModel for PostgresProfileReadModel {
         .map(|rows| rows.into_iter().map(ProfileProjection::from).collect())
     }
 
+    async fn find_by_image_id(
+        &self,
+        executor: &mut Self::Connection,
+        image_id: &ImageId,
+    ) -> error_stack::Result<Vec<ProfileProjection>, KernelError> {
+        let con: &mut PgConnection = executor;
+        sqlx::query_as::<_, ProfileRow>(
+            //language=postgresql
+            r#"
+            SELECT id, account_id, display, summary, icon_id, banner_id, version, nanoid
+            FROM profiles WHERE icon_id = $1 OR banner_id = $1
+            "#,
+        )
+        .bind(image_id.as_ref())
+        .fetch_all(con)
+        .await
+        .convert_error()
+        .map(|rows| rows.into_iter().map(ProfileProjection::from).collect())
+    }
+
     async fn create(
         &self,
         executor: &mut Self::Connection,
@@ -1,6 +1,7 @@
 use crate::database::{PostgresConnection, PostgresDatabase};
 use crate::ConvertError;
 use error_stack::Report;
+use kernel::interfaces::event::{upcast_event, EventSchema};
 use kernel::interfaces::event_store::{DependOnProfileEventStore, ProfileEventStore};
 use kernel::prelude::entity::{
     CommandEnvelope, EventEnvelope, EventId, EventVersion, ExpectedVersion, Profile, ProfileEvent,
@@ -16,12 +17,13 @@ struct EventRow {
     #[allow(dead_code)]
     event_name: String,
     data: serde_json::Value,
+    schema_version: i32,
 }
 
 impl TryFrom<EventRow> for EventEnvelope<ProfileEvent, Profile> {
     type Error = Report<KernelError>;
     fn try_from(value: EventRow) -> Result<Self, Self::Error> {
-        let event: ProfileEvent = serde_json::from_value(value.data).convert_error()?;
+        let event: ProfileEvent = upcast_event(value.data, value.schema_version)?;
         Ok(EventEnvelope::new(
             EventId::new(value.id),
             event,
@@ -46,7 +48,7 @@ impl ProfileEventStore for PostgresProfileEventStore {
             sqlx::query_as::<_, EventRow>(
                 //language=postgresql
                 r#"
-                SELECT version, id, event_name, data
+                SELECT version, id, event_name, data, schema_version
                 FROM profile_events
                 WHERE id = $1 AND version > $2
                 ORDER BY version
@@ -61,7 +63,7 @@ impl ProfileEventStore for PostgresProfileEventStore {
             sqlx::query_as::<_, EventRow>(
                 //language=postgresql
                 r#"
-                SELECT version, id, event_name, data
+                SELECT version, id, event_name, data, schema_version
                 FROM profile_events
                 WHERE id = $1
                 ORDER BY version
@@ -121,8 +123,8 @@ impl PostgresProfileEventStore {
                 sqlx::query(
                     //language=postgresql
                     r#"
-                    INSERT INTO profile_events (version, id, event_name, data)
-                    SELECT $1, $2, $3, $4
+                    INSERT INTO profile_events (version, id, event_name, data, schema_version)
+                    SELECT $1, $2, $3, $4, $5
                     WHERE NOT EXISTS (SELECT 1 FROM profile_events WHERE id = $2)
                     "#,
                 )
@@ -130,6 +132,7 @@ impl PostgresProfileEventStore {
                 .bind(command.id().as_ref())
                 .bind(event_name)
                 .bind(&data)
+                .bind(ProfileEvent::SCHEMA_VERSION)
                 .execute(&mut *con)
                 .await
                 .convert_error()?
@@ -138,15 +141,16 @@ impl PostgresProfileEventStore {
                 sqlx::query(
                     //language=postgresql
                     r#"
-                    INSERT INTO profile_events (version, id, event_name, data)
-                    SELECT $1, $2, $3, $4
-                    WHERE (SELECT MAX(version) FROM profile_events WHERE id = $2) = $5
+                    INSERT INTO profile_events (version, id, event_name, data, schema_version)
+                    SELECT $1, $2, $3, $4, $5
+                    WHERE (SELECT MAX(version) FROM profile_events WHERE id = $2) = $6
                     "#,
                 )
                 .bind(version)
                 .bind(command.id().as_ref())
                 .bind(event_name)
                 .bind(&data)
+                .bind(ProfileEvent::SCHEMA_VERSION)
                 .bind(prev.as_ref())
                 .execute(&mut *con)
                 .await
@@ -156,14 +160,15 @@ impl PostgresProfileEventStore {
                 sqlx::query(
                     //language=postgresql
                     r#"
-                    INSERT INTO profile_events (version, id, event_name, data)
-                    VALUES ($1, $2, $3, $4)
+                    INSERT INTO profile_events (version, id, event_name, data, schema_version)
+                    VALUES ($1, $2, $3, $4, $5)
                     "#,
                 )
                 .bind(version)
                 .bind(command.id().as_ref())
                 .bind(event_name)
                 .bind(&data)
+                .bind(ProfileEvent::SCHEMA_VERSION)
                 .execute(con)
                 .await
                 .convert_error()?
@@ -1,6 +1,7 @@
 use crate::database::{PostgresConnection, PostgresDatabase};
 use crate::ConvertError;
 use error_stack::Report;
+use kernel::interfaces::event::upcast_event;
 use kernel::interfaces::projection::{
     AccountProjectionWriter, DependOnAccountEventLog, DependOnAccountProjectionWriter,
     DependOnMetadataEventLog, DependOnMetadataProjectionWriter, DependOnProfileEventLog,
@@ -24,12 +25,13 @@ struct SeqEventRow {
     #[allow(dead_code)]
     event_name: String,
     data: serde_json::Value,
+    schema_version: i32,
 }
 
 impl TryFrom<SeqEventRow> for SeqEvent<AccountEvent, Account> {
     type Error = Report<KernelError>;
     fn try_from(value: SeqEventRow) -> Result<Self, Self::Error> {
-        let event: AccountEvent = serde_json::from_value(value.data).convert_error()?;
+        let event: AccountEvent = upcast_event(value.data, value.schema_version)?;
         Ok(SeqEvent {
             seq: value.seq,
             envelope: EventEnvelope::new(
@@ -56,7 +58,7 @@ impl kernel::interfaces::projection::AccountEventLog for PostgresAccountEventLog
         let rows = sqlx::query_as::<_, SeqEventRow>(
             //language=postgresql
             r#"
-            SELECT seq, version, id, event_name, data
+            SELECT seq, version, id, event_name, data, schema_version
             FROM account_events
             WHERE seq > $1
             ORDER BY seq
@@ -72,6 +74,24 @@ impl kernel::interfaces::projection::AccountEventLog for PostgresAccountEventLog
             .map(TryFrom::try_from)
             .collect::<error_stack::Result<Vec<_>, KernelError>>()
     }
+
+    async fn head_seq(
+        &self,
+        executor: &mut Self::Connection,
+    ) -> error_stack::Result<i64, KernelError> {
+        let con: &mut PgConnection = executor;
+        let (head,): (i64,) = sqlx::query_as(
+            //language=postgresql
+            r#"
+            SELECT COALESCE(MAX(seq), 0)
+            FROM account_events
+            "#,
+        )
+        .fetch_one(con)
+        .await
+        .convert_error()?;
+        Ok(head)
+    }
 }
 
 pub struct PostgresProjectionCheckpointStore;
@@ -225,7 +245,7 @@ impl DependOnAccountProjectionWriter for PostgresDatabase {
 impl TryFrom<SeqEventRow> for SeqEvent<ProfileEvent, Profile> {
     type Error = Report<KernelError>;
     fn try_from(value: SeqEventRow) -> Result<Self, Self::Error> {
-        let event: ProfileEvent = serde_json::from_value(value.data).convert_error()?;
+        let event: ProfileEvent = upcast_event(value.data, value.schema_version)?;
         Ok(SeqEvent {
             seq: value.seq,
             envelope: EventEnvelope::new(
@@ -252,7 +272,7 @@ impl kernel::interfaces::projection::ProfileEventLog for PostgresProfileEventLog
         let rows = sqlx::query_as::<_, SeqEventRow>(
             //language=postgresql
             r#"
-            SELECT seq, version, id, event_name, data
+            SELECT seq, version, id, event_name, data, schema_version
             FROM profile_events
             WHERE seq > $1
             ORDER BY seq
@@ -268,6 +288,24 @@ impl kernel::interfaces::projection::ProfileEventLog for PostgresProfileEventLog
             .map(TryFrom::try_from)
             .collect::<error_stack::Result<Vec<_>, KernelError>>()
     }
+
+    async fn head_seq(
+        &self,
+        executor: &mut Self::Connection,
+    ) -> error_stack::Result<i64, KernelError> {
+        let con: &mut PgConnection = executor;
+        let (head,): (i64,) = sqlx::query_as(
+            //language=postgresql
+            r#"
+            SELECT COALESCE(MAX(seq), 0)
+            FROM profile_events
+            "#,
+        )
+        .fetch_one(con)
+        .await
+        .convert_error()?;
+        Ok(head)
+    }
 }
 
 pub struct PostgresProfileProjectionWriter;
@@ -285,7 +323,13 @@ impl ProfileProjectionWriter for PostgresProfileProjectionWriter {
             //language=postgresql
             r#"
             INSERT INTO profiles (id, account_id, display, summary, icon_id, banner_id, version, nanoid)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            VALUES (
+                $1, $2, $3, $4,
+                -- An image deleted since the event was written reads as unset.
+                (SELECT id FROM images WHERE id = $5),
+                (SELECT id FROM images WHERE id = $6),
+                $7, $8
+            )
             ON CONFLICT (id) DO UPDATE SET
                 account_id = EXCLUDED.account_id,
                 display = EXCLUDED.display,
@@ -334,7 +378,7 @@ impl ProfileProjectionWriter for PostgresProfileProjectionWriter {
 impl TryFrom<SeqEventRow> for SeqEvent<MetadataEvent, Metadata> {
     type Error = Report<KernelError>;
     fn try_from(value: SeqEventRow) -> Result<Self, Self::Error> {
-        let event: MetadataEvent = serde_json::from_value(value.data).convert_error()?;
+        let event: MetadataEvent = upcast_event(value.data, value.schema_version)?;
         Ok(SeqEvent {
             seq: value.seq,
             envelope: EventEnvelope::new(
@@ -361,7 +405,7 @@ impl kernel::interfaces::projection::MetadataEventLog for PostgresMetadataEventL
         let rows = sqlx::query_as::<_, SeqEventRow>(
             //language=postgresql
             r#"
-            SELECT seq, version, id, event_name, data
+            SELECT seq, version, id, event_name, data, schema_version
             FROM metadata_events
             WHERE seq > $1
             ORDER BY seq
@@ -377,6 +421,24 @@ impl kernel::interfaces::projection::MetadataEventLog for PostgresMetadataEventL
             .map(TryFrom::try_from)
             .collect::<error_stack::Result<Vec<_>, KernelError>>()
     }
+
+    async fn head_seq(
+        &self,
+        executor: &mut Self::Connection,
+    ) -> error_stack::Result<i64, KernelError> {
+        let con: &mut PgConnection = executor;
+        let (head,): (i64,) = sqlx::query_as(
+            //language=postgresql
+            r#"
+            SELECT COALESCE(MAX(seq), 0)
+            FROM metadata_events
+            "#,
+        )
+        .fetch_one(con)
+        .await
+        .convert_error()?;
+        Ok(head)
+    }
 }
 
 pub struct PostgresMetadataProjectionWriter;
@@ -7,6 +7,7 @@ use kernel::prelude::entity::{
 };
 use kernel::KernelError;
 use sqlx::PgConnection;
+use time::OffsetDateTime;
 
 #[derive(sqlx::FromRow)]
 struct RemoteAccountRow {
@@ -14,8 +15,12 @@ struct RemoteAccountRow {
     acct: String,
     url: String,
     icon_id: Option<i64>,
+    banner_id: Option<i64>,
+    icon_source_url: Option<String>,
+    banner_source_url: Option<String>,
     inbox_url: Option<String>,
     public_key_pem: Option<String>,
+    media_fetched_at: Option<OffsetDateTime>,
 }
 
 impl From<RemoteAccountRow> for RemoteAccount {
@@ -25,8 +30,12 @@ impl From<RemoteAccountRow> for RemoteAccount {
             RemoteAccountAcct::new(row.acct),
             RemoteAccountUrl::new(row.url),
             row.icon_id.map(ImageId::new),
+            row.banner_id.map(ImageId::new),
+            row.icon_source_url,
+            row.banner_source_url,
             row.inbox_url,
             row.public_key_pem,
+            row.media_fetched_at,
         )
     }
 }
@@ -45,7 +54,8 @@ impl RemoteAccountRepository for PostgresRemoteAccountRepository {
         sqlx::query_as::<_, RemoteAccountRow>(
             // language=postgresql
             r#"
-            SELECT id, acct, url, icon_id, inbox_url, public_key_pem
+            SELECT id, acct, url, icon_id, banner_id, icon_source_url, banner_source_url,
+                   inbox_url, public_key_pem, media_fetched_at
             FROM remote_accounts
             WHERE id = $1
             "#,
@@ -66,7 +76,8 @@ impl RemoteAccountRepository for PostgresRemoteAccountRepository {
         sqlx::query_as::<_, RemoteAccountRow>(
             // language=postgresql
             r#"
-            SELECT id, acct, url, icon_id, inbox_url, public_key_pem
+            SELECT id, acct, url, icon_id, banner_id, icon_source_url, banner_source_url,
+                   inbox_url, public_key_pem, media_fetched_at
             FROM remote_accounts
             WHERE acct = $1
             "#,
@@ -87,7 +98,8 @@ impl RemoteAccountRepository for PostgresRemoteAccountRepository {
         sqlx::query_as::<_, RemoteAccountRow>(
             // language=postgresql
             r#"
-            SELECT id, acct, url, icon_id, inbox_url, public_key_pem
+            SELECT id, acct, url, icon_id, banner_id, icon_source_url, banner_source_url,
+                   inbox_url, public_key_pem, media_fetched_at
             FROM remote_accounts
             WHERE url = $1
             "#,
@@ -108,16 +120,23 @@ impl RemoteAccountRepository for PostgresRemoteAccountRepository {
         sqlx::query(
             // language=postgresql
             r#"
-            INSERT INTO remote_accounts (id, acct, url, icon_id, inbox_url, public_key_pem)
-            VALUES ($1, $2, $3, $4, $5, $6)
+            INSERT INTO remote_accounts (
+                id, acct, url, icon_id, banner_id, icon_source_url, banner_source_url,
+                inbox_url, public_key_pem, media_fetched_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
             "#,
         )
         .bind(account.id().as_ref())
         .bind(account.acct().as_ref())
         .bind(account.url().as_ref())
         .bind(account.icon_id().as_ref().map(ImageId::as_ref))
+        .bind(account.banner_id().as_ref().map(ImageId::as_ref))
+        .bind(account.icon_source_url())
+        .bind(account.banner_source_url())
         .bind(account.inbox_url())
         .bind(account.public_key_pem())
+        .bind(account.media_fetched_at())
         .execute(con)
         .await
         .convert_error()?;
@@ -134,7 +153,9 @@ impl RemoteAccountRepository for PostgresRemoteAccountRepository {
             // language=postgresql
             r#"
             UPDATE remote_accounts
-            SET acct = $2, url = $3, icon_id = $4, inbox_url = $5, public_key_pem = $6
+            SET acct = $2, url = $3, icon_id = $4, banner_id = $5, icon_source_url = $6,
+                banner_source_url = $7, inbox_url = $8, public_key_pem = $9,
+                media_fetched_at = $10, last_seen_at = NOW()
             WHERE id = $1
             "#,
         )
@@ -142,8 +163,12 @@ impl RemoteAccountRepository for PostgresRemoteAccountRepository {
         .bind(account.acct().as_ref())
         .bind(account.url().as_ref())
         .bind(account.icon_id().as_ref().map(ImageId::as_ref))
+        .bind(account.banner_id().as_ref().map(ImageId::as_ref))
+        .bind(account.icon_source_url())
+        .bind(account.banner_source_url())
         .bind(account.inbox_url())
         .bind(account.public_key_pem())
+        .bind(account.media_fetched_at())
         .execute(con)
         .await
         .convert_error()?;
@@ -154,6 +179,59 @@ impl RemoteAccountRepository for PostgresRemoteAccountRepository {
         Ok(())
     }
 
+    async fn find_pending_media(
+        &self,
+        executor: &mut Self::Connection,
+        limit: usize,
+    ) -> error_stack::Result<Vec<RemoteAccount>, KernelError> {
+        let con: &mut PgConnection = executor;
+        sqlx::query_as::<_, RemoteAccountRow>(
+            // language=postgresql
+            r#"
+            SELECT id, acct, url, icon_id, banner_id, icon_source_url, banner_source_url,
+                   inbox_url, public_key_pem, media_fetched_at
+            FROM remote_accounts
+            WHERE media_fetched_at IS NULL
+              AND (icon_source_url IS NOT NULL OR banner_source_url IS NOT NULL)
+            ORDER BY last_seen_at DESC, id
+            LIMIT $1
+            "#,
+        )
+        .bind(limit as i64)
+        .fetch_all(con)
+        .await
+        .convert_error()
+        .map(|rows| rows.into_iter().map(RemoteAccount::from).collect())
+    }
+
+    async fn update_cached_media(
+        &self,
+        executor: &mut Self::Connection,
+        account: &RemoteAccount,
+    ) -> error_stack::Result<bool, KernelError> {
+        let con: &mut PgConnection = executor;
+        let result = sqlx::query(
+            // language=postgresql
+            r#"
+            UPDATE remote_accounts
+            SET icon_id = $2, banner_id = $3, media_fetched_at = $4
+            WHERE id = $1
+              AND icon_source_url IS NOT DISTINCT FROM $5
+              AND banner_source_url IS NOT DISTINCT FROM $6
+            "#,
+        )
+        .bind(account.id().as_ref())
+        .bind(account.icon_id().as_ref().map(ImageId::as_ref))
+        .bind(account.banner_id().as_ref().map(ImageId::as_ref))
+        .bind(account.media_fetched_at())
+        .bind(account.icon_source_url())
+        .bind(account.banner_source_url())
+        .execute(con)
+        .await
+        .convert_error()?;
+        Ok(result.rows_affected() > 0)
+    }
+
     async fn delete(
         &self,
         executor: &mut Self::Connection,
@@ -289,10 +367,12 @@ mod test {
         use crate::database::PostgresDatabase;
         use kernel::interfaces::database::DatabaseConnection;
         use kernel::interfaces::repository::{
-            DependOnRemoteAccountRepository, RemoteAccountRepository,
+            DependOnImageRepository, DependOnRemoteAccountRepository, ImageRepository,
+            RemoteAccountRepository,
         };
         use kernel::prelude::entity::RemoteAccountId;
-        use kernel::test_utils::RemoteAccountBuilder;
+        use kernel::test_utils::{ImageBuilder, RemoteAccountBuilder};
+        use time::OffsetDateTime;
 
         #[test_with::env(DATABASE_URL)]
         #[tokio::test]
@@ -374,5 +454,88 @@ mod test {
                 .unwrap();
             assert_eq!(result, None);
         }
+
+        #[test_with::env(DATABASE_URL)]
+        #[tokio::test]
+        async fn cached_media_is_stored_only_for_current_sources() {
+            kernel::ensure_generator_initialized();
+            let database = PostgresDatabase::new().await.unwrap();
+            let mut conn = database.connection().await.unwrap();
+
+            let id = RemoteAccountId::new(kernel::generate_id());
+            let source = "https://remote.example/icon.png";
+            let pending = RemoteAccountBuilder::new()
+                .id(id.clone())
+                .icon_source_url(Some(source))
+                .build();
+            database
+                .remote_account_repository()
+                .create(&mut conn, &pending)
+                .await
+                .unwrap();
+            let found = database
+                .remote_account_repository()
+                .find_pending_media(&mut conn, 1000)
+                .await
+                .unwrap();
+            assert!(found.contains(&pending));
+
+            let image = ImageBuilder::new().build();
+            database
+                .image_repository()
+                .create(&mut conn, &image)
+                .await
+                .unwrap();
+            let fetched_at = OffsetDateTime::now_utc().replace_nanosecond(0).unwrap();
+            let outdated = RemoteAccountBuilder::new()
+                .id(id.clone())
+                .acct(pending.acct().as_ref())
+                .url(pending.url().as_ref())
+                .icon_id(Some(image.id().clone()))
+                .icon_source_url(Some("https://remote.example/old.png"))
+                .media_fetched_at(Some(fetched_at))
+                .build();
+            assert!(!database
+                .remote_account_repository()
+                .update_cached_media(&mut conn, &outdated)
+                .await
+                .unwrap());
+            let cached = RemoteAccountBuilder::new()
+                .id(id.clone())
+                .acct(pending.acct().as_ref())
+                .url(pending.url().as_ref())
+                .icon_id(Some(image.id().clone()))
+                .icon_source_url(Some(source))
+                .media_fetched_at(Some(fetched_at))
+                .build();
+            assert!(database
+                .remote_account_repository()
+                .update_cached_media(&mut conn, &cached)
+                .await
+                .unwrap());
+            let result = database
+                .remote_account_repository()
+                .find_by_id(&mut conn, &id)
+                .await
+                .unwrap();
+            assert_eq!(result, Some(cached));
+            let found = database
+                .remote_account_repository()
+                .find_pending_media(&mut conn, 1000)
+                .await
+                .unwrap();
+            assert!(found.iter().all(|account| account.id() != &id));
+
+            database
+                .remote_account_repository()
+                .delete(&mut conn, &id)
+                .await
+                .unwrap();
+            database
+                .image_repository()
+                .delete(&mut conn, image.id())
+                .await
+                .unwrap();
+        }
     }
 }
@@ -0,0 +1,262 @@
+use crate::database::{PostgresConnection, PostgresDatabase};
+use crate::ConvertError;
+use kernel::interfaces::repository::{DependOnUploadSessionRepository, UploadSessionRepository};
+use kernel::prelude::entity::{
+    AuthAccountId, ImageDescription, ImageFocus, UploadSession, UploadSessionId,
+};
+use kernel::KernelError;
+use sqlx::PgConnection;
+use time::OffsetDateTime;
+
+#[derive(sqlx::FromRow)]
+struct UploadSessionRow {
+    id: i64,
+    owner_id: i64,
+    object_key: String,
+    object_url: String,
+    content_type: String,
+    byte_size: i64,
+    description: Option<String>,
+    focus_x: Option<f32>,
+    focus_y: Option<f32>,
+    expires_at: OffsetDateTime,
+    created_at: OffsetDateTime,
+}
+
+impl From<UploadSessionRow> for UploadSession {
+    fn from(value: UploadSessionRow) -> Self {
+        UploadSession {
+            id: UploadSessionId::new(value.id),
+            owner: AuthAccountId::new(value.owner_id),
+            object_key: value.object_key,
+            object_url: value.object_url,
+            content_type: value.content_type,
+            byte_size: value.byte_size as u64,
+            description: value.description.map(ImageDescription::new),
+            focus: value
+                .focus_x
+                .zip(value.focus_y)
+                .map(|(x, y)| ImageFocus::new(x, y)),
+            expires_at: value.expires_at,
+            created_at: value.created_at,
+        }
+    }
+}
+
+pub struct PostgresUploadSessionRepository;
+
+impl UploadSessionRepository for PostgresUploadSessionRepository {
+    type Connection = PostgresConnection;
+
+    async fn create(
+        &self,
+        executor: &mut Self::Connection,
+        session: &UploadSession,
+    ) -> error_stack::Result<(), KernelError> {
+        let con: &mut PgConnection = executor;
+        // language=postgresql
+        sqlx::query(
+            r#"
+            INSERT INTO upload_sessions
+                (id, owner_id, object_key, object_url, content_type, byte_size,
+                 description, focus_x, focus_y, expires_at, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            "#,
+        )
+        .bind(session.id.as_ref())
+        .bind(session.owner.as_ref())
+        .bind(&session.object_key)
+        .bind(&session.object_url)
+        .bind(&session.content_type)
+        .bind(session.byte_size as i64)
+        .bind(session.description.as_ref().map(AsRef::<String>::as_ref))
+        .bind(session.focus.as_ref().map(|focus| *focus.x()))
+        .bind(session.focus.as_ref().map(|focus| *focus.y()))
+        .bind(session.expires_at)
+        .bind(session.created_at)
+        .execute(con)
+        .await
+        .convert_error()?;
+        Ok(())
+    }
+
+    async fn find_by_id(
+        &self,
+        executor: &mut Self::Connection,
+        id: &UploadSessionId,
+    ) -> error_stack::Result<Option<UploadSession>, KernelError> {
+        let con: &mut PgConnection = executor;
+        // language=postgresql
+        sqlx::query_as::<_, UploadSessionRow>(
+            r#"
+            SELECT id, owner_id, object_key, object_url, content_type, byte_size,
+                   description, focus_x, focus_y, expires_at, created_at
+            FROM upload_sessions
+            WHERE id = $1
+            "#,
+        )
+        .bind(id.as_ref())
+        .fetch_optional(con)
+        .await
+        .convert_error()
+        .map(|row| row.map(UploadSession::from))
+    }
+
+    async fn delete(
+        &self,
+        executor: &mut Self::Connection,
+        id: &UploadSessionId,
+    ) -> error_stack::Result<bool, KernelError> {
+        let con: &mut PgConnection = executor;
+        // language=postgresql
+        let result = sqlx::query(
+            r#"
+            DELETE FROM upload_sessions WHERE id = $1
+            "#,
+        )
+        .bind(id.as_ref())
+        .execute(con)
+        .await
+        .convert_error()?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find_expired(
+        &self,
+        executor: &mut Self::Connection,
+        now: OffsetDateTime,
+        limit: usize,
+    ) -> error_stack::Result<Vec<UploadSession>, KernelError> {
+        let con: &mut PgConnection = executor;
+        // language=postgresql
+        sqlx::query_as::<_, UploadSessionRow>(
+            r#"
+            SELECT id, owner_id, object_key, object_url, content_type, byte_size,
+                   description, focus_x, focus_y, expires_at, created_at
+            FROM upload_sessions
+            WHERE expires_at <= $1
+            ORDER BY expires_at
+            LIMIT $2
+            "#,
+        )
+        .bind(now)
+        .bind(limit as i64)
+        .fetch_all(con)
+        .await
+        .convert_error()
+        .map(|rows| rows.into_iter().map(UploadSession::from).collect())
+    }
+}
+
+impl DependOnUploadSessionRepository for PostgresDatabase {
+    type UploadSessionRepository = PostgresUploadSessionRepository;
+
+    fn upload_session_repository(&self) -> &Self::UploadSessionRepository {
+        &PostgresUploadSessionRepository
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kernel::interfaces::database::DatabaseConnection;
+    use kernel::interfaces::repository::{
+        AuthAccountRepository, AuthHostRepository, DependOnAuthAccountRepository,
+        DependOnAuthHostRepository,
+    };
+    use kernel::prelude::entity::AuthHostId;
+    use kernel::test_utils::{AuthAccountBuilder, AuthHostBuilder};
+    use time::Duration;
+
+    fn session(owner: &AuthAccountId, expires_at: OffsetDateTime) -> UploadSession {
+        let id = UploadSessionId::default();
+        let object_key = format!("uploads/{}/blob", id.as_ref());
+        UploadSession {
+            object_url: format!("http://localhost:9000/emumet-media/{object_key}"),
+            object_key,
+            id,
+            owner: owner.clone(),
+            content_type: "image/png".to_string(),
+            byte_size: 12345,
+            description: Some(ImageDescription::new("A cat".to_string())),
+            focus: Some(ImageFocus::new(0.5, -0.25)),
+            expires_at: expires_at.replace_nanosecond(0).unwrap(),
+            created_at: OffsetDateTime::now_utc().replace_nanosecond(0).unwrap(),
+        }
+    }
+
+    #[test_with::env(DATABASE_URL)]
+    #[tokio::test]
+    async fn create_find_expire_and_delete() {
+        kernel::ensure_generator_initialized();
+        let database = PostgresDatabase::new().await.unwrap();
+        let mut executor = database.connection().await.unwrap();
+        let host_id = AuthHostId::default();
+        database
+            .auth_host_repository()
+            .create(
+                &mut executor,
+                &AuthHostBuilder::new().id(host_id.clone()).build(),
+            )
+            .await
+            .unwrap();
+        let owner = AuthAccountId::default();
+        database
+            .auth_account_repository()
+            .create(
+                &mut executor,
+                &AuthAccountBuilder::new()
+                    .id(owner.clone())
+                    .host(host_id)
+                    .client_id(format!("upload-{}", owner.as_ref()))
+                    .build(),
+            )
+            .await
+            .unwrap();
+
+        let now = OffsetDateTime::now_utc();
+        let expired = session(&owner, now - Duration::minutes(1));
+        let open = session(&owner, now + Duration::minutes(15));
+        for session in [&expired, &open] {
+            database
+                .upload_session_repository()
+                .create(&mut executor, session)
+                .await
+                .unwrap();
+        }
+
+        let found = database
+            .upload_session_repository()
+            .find_by_id(&mut executor, &open.id)
+            .await
+            .unwrap();
+        assert_eq!(found, Some(open.clone()));
+
+        let due = database
+            .upload_session_repository()
+            .find_expired(&mut executor, now, 1000)
+            .await
+            .unwrap();
+        assert!(due.iter().any(|session| session.id == expired.id));
+        assert!(due.iter().all(|session| session.id != open.id));
+
+        for session in [&expired, &open] {
+            assert!(database
+                .upload_session_repository()
+                .delete(&mut executor, &session.id)
+                .await
+                .unwrap());
+        }
+        assert!(!database
+            .upload_session_repository()
+            .delete(&mut executor, &open.id)
+            .await
+            .unwrap());
+        let found = database
+            .upload_session_repository()
+            .find_by_id(&mut executor, &open.id)
+            .await
+            .unwrap();
+        assert!(found.is_none());
+    }
+}
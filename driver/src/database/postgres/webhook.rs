@@ -0,0 +1,700 @@
+use crate::database::{PostgresConnection, PostgresDatabase};
+use crate::ConvertError;
+use error_stack::Report;
+use kernel::interfaces::repository::{
+    DependOnWebhookDeliveryRepository, DependOnWebhookSubscriptionRepository,
+    WebhookDeliveryRepository, WebhookSubscriptionRepository,
+};
+use kernel::prelude::entity::{
+    WebhookDelivery, WebhookDeliveryId, WebhookDeliveryStatus, WebhookSubscription,
+    WebhookSubscriptionId,
+};
+use kernel::KernelError;
+use sqlx::PgConnection;
+use time::OffsetDateTime;
+
+#[derive(sqlx::FromRow)]
+struct WebhookSubscriptionRow {
+    id: i64,
+    url: String,
+    event_types: Vec<String>,
+    secret: String,
+    created_at: OffsetDateTime,
+}
+
+impl From<WebhookSubscriptionRow> for WebhookSubscription {
+    fn from(value: WebhookSubscriptionRow) -> Self {
+        WebhookSubscription {
+            id: WebhookSubscriptionId::new(value.id),
+            url: value.url,
+            event_types: value.event_types,
+            secret: value.secret,
+            created_at: value.created_at,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct WebhookDeliveryRow {
+    id: i64,
+    subscription_id: i64,
+    event_id: String,
+    event_type: String,
+    payload: String,
+    status: String,
+    attempts: i32,
+    next_attempt_at: OffsetDateTime,
+    last_status_code: Option<i32>,
+    last_error: Option<String>,
+    created_at: OffsetDateTime,
+    delivered_at: Option<OffsetDateTime>,
+}
+
+impl TryFrom<WebhookDeliveryRow> for WebhookDelivery {
+    type Error = Report<KernelError>;
+
+    fn try_from(value: WebhookDeliveryRow) -> Result<Self, Self::Error> {
+        let status = WebhookDeliveryStatus::parse(&value.status).ok_or_else(|| {
+            Report::new(KernelError::Internal)
+                .attach_printable(format!("Unknown webhook delivery status: {}", value.status))
+        })?;
+        Ok(WebhookDelivery {
+            id: WebhookDeliveryId::new(value.id),
+            subscription_id: WebhookSubscriptionId::new(value.subscription_id),
+            event_id: value.event_id,
+            event_type: value.event_type,
+            payload: value.payload,
+            status,
+            attempts: value.attempts,
+            next_attempt_at: value.next_attempt_at,
+            last_status_code: value.last_status_code,
+            last_error: value.last_error,
+            created_at: value.created_at,
+            delivered_at: value.delivered_at,
+        })
+    }
+}
+
+fn convert_deliveries(
+    rows: Vec<WebhookDeliveryRow>,
+) -> error_stack::Result<Vec<WebhookDelivery>, KernelError> {
+    rows.into_iter().map(WebhookDelivery::try_from).collect()
+}
+
+pub struct PostgresWebhookSubscriptionRepository;
+
+impl WebhookSubscriptionRepository for PostgresWebhookSubscriptionRepository {
+    type Connection = PostgresConnection;
+
+    async fn create(
+        &self,
+        executor: &mut Self::Connection,
+        subscription: &WebhookSubscription,
+    ) -> error_stack::Result<(), KernelError> {
+        let con: &mut PgConnection = executor;
+        // language=postgresql
+        sqlx::query(
+            r#"
+            INSERT INTO webhook_subscriptions (id, url, event_types, secret, created_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(subscription.id.as_ref())
+        .bind(&subscription.url)
+        .bind(&subscription.event_types)
+        .bind(&subscription.secret)
+        .bind(subscription.created_at)
+        .execute(con)
+        .await
+        .convert_error()?;
+        Ok(())
+    }
+
+    async fn find_by_id(
+        &self,
+        executor: &mut Self::Connection,
+        id: &WebhookSubscriptionId,
+    ) -> error_stack::Result<Option<WebhookSubscription>, KernelError> {
+        let con: &mut PgConnection = executor;
+        // language=postgresql
+        sqlx::query_as::<_, WebhookSubscriptionRow>(
+            r#"
+            SELECT id, url, event_types, secret, created_at
+            FROM webhook_subscriptions
+            WHERE id = $1
+            "#,
+        )
+        .bind(id.as_ref())
+        .fetch_optional(con)
+        .await
+        .convert_error()
+        .map(|row| row.map(WebhookSubscription::from))
+    }
+
+    async fn find_all(
+        &self,
+        executor: &mut Self::Connection,
+    ) -> error_stack::Result<Vec<WebhookSubscription>, KernelError> {
+        let con: &mut PgConnection = executor;
+        // language=postgresql
+        sqlx::query_as::<_, WebhookSubscriptionRow>(
+            r#"
+            SELECT id, url, event_types, secret, created_at
+            FROM webhook_subscriptions
+            ORDER BY id
+            "#,
+        )
+        .fetch_all(con)
+        .await
+        .convert_error()
+        .map(|rows| rows.into_iter().map(WebhookSubscription::from).collect())
+    }
+
+    async fn delete(
+        &self,
+        executor: &mut Self::Connection,
+        id: &WebhookSubscriptionId,
+    ) -> error_stack::Result<(), KernelError> {
+        let con: &mut PgConnection = executor;
+        // language=postgresql
+        sqlx::query(
+            r#"
+            DELETE FROM webhook_subscriptions WHERE id = $1
+            "#,
+        )
+        .bind(id.as_ref())
+        .execute(con)
+        .await
+        .convert_error()?;
+        Ok(())
+    }
+}
+
+impl DependOnWebhookSubscriptionRepository for PostgresDatabase {
+    type WebhookSubscriptionRepository = PostgresWebhookSubscriptionRepository;
+
+    fn webhook_subscription_repository(&self) -> &Self::WebhookSubscriptionRepository {
+        &PostgresWebhookSubscriptionRepository
+    }
+}
+
+pub struct PostgresWebhookDeliveryRepository;
+
+impl WebhookDeliveryRepository for PostgresWebhookDeliveryRepository {
+    type Connection = PostgresConnection;
+
+    async fn enqueue(
+        &self,
+        executor: &mut Self::Connection,
+        deliveries: &[WebhookDelivery],
+    ) -> error_stack::Result<(), KernelError> {
+        let con: &mut PgConnection = executor;
+        for delivery in deliveries {
+            // language=postgresql
+            sqlx::query(
+                r#"
+                INSERT INTO webhook_deliveries
+                    (id, subscription_id, event_id, event_type, payload, status, attempts, next_attempt_at, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                ON CONFLICT (subscription_id, event_id) DO NOTHING
+                "#,
+            )
+            .bind(delivery.id.as_ref())
+            .bind(delivery.subscription_id.as_ref())
+            .bind(&delivery.event_id)
+            .bind(&delivery.event_type)
+            .bind(&delivery.payload)
+            .bind(delivery.status.as_str())
+            .bind(delivery.attempts)
+            .bind(delivery.next_attempt_at)
+            .bind(delivery.created_at)
+            .execute(&mut *con)
+            .await
+            .convert_error()?;
+        }
+        Ok(())
+    }
+
+    async fn claim_due(
+        &self,
+        executor: &mut Self::Connection,
+        now: OffsetDateTime,
+        lease_until: OffsetDateTime,
+        limit: usize,
+    ) -> error_stack::Result<Vec<WebhookDelivery>, KernelError> {
+        let con: &mut PgConnection = executor;
+        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+        // language=postgresql
+        let mut rows = sqlx::query_as::<_, WebhookDeliveryRow>(
+            r#"
+            UPDATE webhook_deliveries
+            SET next_attempt_at = $2
+            WHERE id IN (
+                SELECT id
+                FROM webhook_deliveries
+                WHERE status = 'pending' AND next_attempt_at <= $1
+                ORDER BY next_attempt_at, id
+                LIMIT $3
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING id, subscription_id, event_id, event_type, payload, status, attempts,
+                      next_attempt_at, last_status_code, last_error, created_at, delivered_at
+            "#,
+        )
+        .bind(now)
+        .bind(lease_until)
+        .bind(limit)
+        .fetch_all(con)
+        .await
+        .convert_error()?;
+        // RETURNING does not keep the subquery's order.
+        rows.sort_by_key(|row| row.id);
+        convert_deliveries(rows)
+    }
+
+    async fn find_by_subscription_id(
+        &self,
+        executor: &mut Self::Connection,
+        subscription_id: &WebhookSubscriptionId,
+        limit: usize,
+        cursor: Option<i64>,
+    ) -> error_stack::Result<Vec<WebhookDelivery>, KernelError> {
+        let con: &mut PgConnection = executor;
+        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+        // language=postgresql
+        let rows = sqlx::query_as::<_, WebhookDeliveryRow>(
+            r#"
+            SELECT id, subscription_id, event_id, event_type, payload, status, attempts,
+                   next_attempt_at, last_status_code, last_error, created_at, delivered_at
+            FROM webhook_deliveries
+            WHERE subscription_id = $1 AND ($2::BIGINT IS NULL OR id < $2)
+            ORDER BY id DESC
+            LIMIT $3
+            "#,
+        )
+        .bind(subscription_id.as_ref())
+        .bind(cursor)
+        .bind(limit)
+        .fetch_all(con)
+        .await
+        .convert_error()?;
+        convert_deliveries(rows)
+    }
+
+    async fn mark_delivered(
+        &self,
+        executor: &mut Self::Connection,
+        id: &WebhookDeliveryId,
+        status_code: i32,
+    ) -> error_stack::Result<(), KernelError> {
+        let con: &mut PgConnection = executor;
+        // language=postgresql
+        sqlx::query(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = 'delivered', attempts = attempts + 1, last_status_code = $2,
+                last_error = NULL, delivered_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(id.as_ref())
+        .bind(status_code)
+        .execute(con)
+        .await
+        .convert_error()?;
+        Ok(())
+    }
+
+    async fn mark_attempt_failed(
+        &self,
+        executor: &mut Self::Connection,
+        id: &WebhookDeliveryId,
+        status_code: Option<i32>,
+        error: &str,
+        next_attempt_at: Option<OffsetDateTime>,
+    ) -> error_stack::Result<(), KernelError> {
+        let con: &mut PgConnection = executor;
+        // language=postgresql
+        sqlx::query(
+            r#"
+            UPDATE webhook_deliveries
+            SET attempts = attempts + 1, last_status_code = $2, last_error = $3,
+                status = CASE WHEN $4::TIMESTAMPTZ IS NULL THEN 'failed' ELSE 'pending' END,
+                next_attempt_at = COALESCE($4, next_attempt_at)
+            WHERE id = $1
+            "#,
+        )
+        .bind(id.as_ref())
+        .bind(status_code)
+        .bind(error)
+        .bind(next_attempt_at)
+        .execute(con)
+        .await
+        .convert_error()?;
+        Ok(())
+    }
+
+    async fn prune_finished(
+        &self,
+        executor: &mut Self::Connection,
+        created_before: OffsetDateTime,
+        limit: usize,
+    ) -> error_stack::Result<u64, KernelError> {
+        let con: &mut PgConnection = executor;
+        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+        // language=postgresql
+        let result = sqlx::query(
+            r#"
+            DELETE FROM webhook_deliveries
+            WHERE id IN (
+                SELECT id
+                FROM webhook_deliveries
+                WHERE status <> 'pending' AND created_at < $1
+                LIMIT $2
+            )
+            "#,
+        )
+        .bind(created_before)
+        .bind(limit)
+        .execute(con)
+        .await
+        .convert_error()?;
+        Ok(result.rows_affected())
+    }
+}
+
+impl DependOnWebhookDeliveryRepository for PostgresDatabase {
+    type WebhookDeliveryRepository = PostgresWebhookDeliveryRepository;
+
+    fn webhook_delivery_repository(&self) -> &Self::WebhookDeliveryRepository {
+        &PostgresWebhookDeliveryRepository
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kernel::interfaces::database::DatabaseConnection;
+    use time::Duration;
+
+    fn subscription(event_types: &[&str]) -> WebhookSubscription {
+        WebhookSubscription {
+            id: WebhookSubscriptionId::default(),
+            url: "https://hooks.example.com/emumet".to_string(),
+            event_types: event_types.iter().map(|s| s.to_string()).collect(),
+            secret: WebhookSubscription::generate_secret(),
+            created_at: OffsetDateTime::now_utc().replace_nanosecond(0).unwrap(),
+        }
+    }
+
+    fn delivery(subscription_id: &WebhookSubscriptionId, event_id: &str) -> WebhookDelivery {
+        let now = OffsetDateTime::now_utc();
+        WebhookDelivery {
+            id: WebhookDeliveryId::default(),
+            subscription_id: subscription_id.clone(),
+            event_id: event_id.to_string(),
+            event_type: "account.created".to_string(),
+            payload: format!(r#"{{"event_id":"{event_id}"}}"#),
+            status: WebhookDeliveryStatus::Pending,
+            attempts: 0,
+            next_attempt_at: now,
+            last_status_code: None,
+            last_error: None,
+            created_at: now,
+            delivered_at: None,
+        }
+    }
+
+    /// Dropping the subscription cascades to its deliveries, so a test leaves
+    /// nothing behind in the shared due queue.
+    async fn cleanup(database: &PostgresDatabase, subscription: &WebhookSubscription) {
+        let mut executor = database.connection().await.unwrap();
+        database
+            .webhook_subscription_repository()
+            .delete(&mut executor, &subscription.id)
+            .await
+            .unwrap();
+    }
+
+    #[test_with::env(DATABASE_URL)]
+    #[tokio::test]
+    async fn create_find_and_delete_subscription() {
+        kernel::ensure_generator_initialized();
+        let database = PostgresDatabase::new().await.unwrap();
+        let mut executor = database.connection().await.unwrap();
+        let subscription = subscription(&["account.*", "profile.updated"]);
+
+        database
+            .webhook_subscription_repository()
+            .create(&mut executor, &subscription)
+            .await
+            .unwrap();
+        let found = database
+            .webhook_subscription_repository()
+            .find_by_id(&mut executor, &subscription.id)
+            .await
+            .unwrap();
+        assert_eq!(found, Some(subscription.clone()));
+
+        database
+            .webhook_subscription_repository()
+            .delete(&mut executor, &subscription.id)
+            .await
+            .unwrap();
+        let found = database
+            .webhook_subscription_repository()
+            .find_by_id(&mut executor, &subscription.id)
+            .await
+            .unwrap();
+        assert!(found.is_none());
+    }
+
+    #[test_with::env(DATABASE_URL)]
+    #[tokio::test]
+    async fn enqueue_is_idempotent_per_subscription_and_event() {
+        kernel::ensure_generator_initialized();
+        let database = PostgresDatabase::new().await.unwrap();
+        let mut executor = database.connection().await.unwrap();
+        let subscription = subscription(&[]);
+        database
+            .webhook_subscription_repository()
+            .create(&mut executor, &subscription)
+            .await
+            .unwrap();
+
+        let repository = database.webhook_delivery_repository();
+        repository
+            .enqueue(&mut executor, &[delivery(&subscription.id, "account:1:1")])
+            .await
+            .unwrap();
+        repository
+            .enqueue(
+                &mut executor,
+                &[
+                    delivery(&subscription.id, "account:1:1"),
+                    delivery(&subscription.id, "account:1:2"),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let log = repository
+            .find_by_subscription_id(&mut executor, &subscription.id, 10, None)
+            .await
+            .unwrap();
+        let event_ids: Vec<&str> = log.iter().map(|d| d.event_id.as_str()).collect();
+        assert_eq!(event_ids.len(), 2);
+        assert!(event_ids.contains(&"account:1:1"));
+        assert!(event_ids.contains(&"account:1:2"));
+        cleanup(&database, &subscription).await;
+    }
+
+    #[test_with::env(DATABASE_URL)]
+    #[tokio::test]
+    async fn failed_attempt_reschedules_then_gives_up() {
+        kernel::ensure_generator_initialized();
+        let database = PostgresDatabase::new().await.unwrap();
+        let mut executor = database.connection().await.unwrap();
+        let subscription = subscription(&[]);
+        database
+            .webhook_subscription_repository()
+            .create(&mut executor, &subscription)
+            .await
+            .unwrap();
+        let repository = database.webhook_delivery_repository();
+        let queued = delivery(&subscription.id, "account:2:1");
+        repository
+            .enqueue(&mut executor, std::slice::from_ref(&queued))
+            .await
+            .unwrap();
+
+        let retry_at =
+            OffsetDateTime::now_utc().replace_nanosecond(0).unwrap() + Duration::minutes(5);
+        repository
+            .mark_attempt_failed(
+                &mut executor,
+                &queued.id,
+                Some(503),
+                "HTTP 503",
+                Some(retry_at),
+            )
+            .await
+            .unwrap();
+        let log = repository
+            .find_by_subscription_id(&mut executor, &subscription.id, 10, None)
+            .await
+            .unwrap();
+        assert_eq!(log[0].status, WebhookDeliveryStatus::Pending);
+        assert_eq!(log[0].next_attempt_at, retry_at);
+
+        repository
+            .mark_attempt_failed(&mut executor, &queued.id, None, "timeout", None)
+            .await
+            .unwrap();
+        let log = repository
+            .find_by_subscription_id(&mut executor, &subscription.id, 10, None)
+            .await
+            .unwrap();
+        assert_eq!(log[0].status, WebhookDeliveryStatus::Failed);
+        assert_eq!(log[0].attempts, 2);
+        assert_eq!(log[0].last_error.as_deref(), Some("timeout"));
+        cleanup(&database, &subscription).await;
+    }
+
+    #[test_with::env(DATABASE_URL)]
+    #[tokio::test]
+    async fn mark_delivered_records_status_code() {
+        kernel::ensure_generator_initialized();
+        let database = PostgresDatabase::new().await.unwrap();
+        let mut executor = database.connection().await.unwrap();
+        let subscription = subscription(&[]);
+        database
+            .webhook_subscription_repository()
+            .create(&mut executor, &subscription)
+            .await
+            .unwrap();
+        let repository = database.webhook_delivery_repository();
+        let queued = delivery(&subscription.id, "account:3:1");
+        repository
+            .enqueue(&mut executor, std::slice::from_ref(&queued))
+            .await
+            .unwrap();
+
+        repository
+            .mark_delivered(&mut executor, &queued.id, 204)
+            .await
+            .unwrap();
+        let log = repository
+            .find_by_subscription_id(&mut executor, &subscription.id, 10, None)
+            .await
+            .unwrap();
+        assert_eq!(log[0].status, WebhookDeliveryStatus::Delivered);
+        assert_eq!(log[0].last_status_code, Some(204));
+        assert!(log[0].delivered_at.is_some());
+        cleanup(&database, &subscription).await;
+    }
+
+    // The only test here that claims, so no other test's rows get leased away.
+    #[test_with::env(DATABASE_URL)]
+    #[tokio::test]
+    async fn claim_due_leases_each_delivery_to_one_worker() {
+        kernel::ensure_generator_initialized();
+        let database = PostgresDatabase::new().await.unwrap();
+        let mut executor = database.connection().await.unwrap();
+        let subscription = subscription(&[]);
+        database
+            .webhook_subscription_repository()
+            .create(&mut executor, &subscription)
+            .await
+            .unwrap();
+        let repository = database.webhook_delivery_repository();
+        // Oldest possible due time so the delivery is within the first page.
+        let queued = WebhookDelivery {
+            next_attempt_at: OffsetDateTime::UNIX_EPOCH,
+            ..delivery(&subscription.id, "account:4:1")
+        };
+        repository
+            .enqueue(&mut executor, std::slice::from_ref(&queued))
+            .await
+            .unwrap();
+
+        let now = OffsetDateTime::now_utc();
+        let lease_until = now.replace_nanosecond(0).unwrap() + Duration::minutes(30);
+        let claimed = repository
+            .claim_due(&mut executor, now, lease_until, 1000)
+            .await
+            .unwrap();
+        let mine = claimed.iter().find(|d| d.id == queued.id).unwrap();
+        assert_eq!(mine.next_attempt_at, lease_until);
+
+        let mut other_worker = database.connection().await.unwrap();
+        let claimed_again = repository
+            .claim_due(&mut other_worker, now, lease_until, 1000)
+            .await
+            .unwrap();
+        assert!(claimed_again.iter().all(|d| d.id != queued.id));
+
+        // Once the lease has run out, the delivery is due again.
+        let reclaimed = repository
+            .claim_due(
+                &mut other_worker,
+                lease_until,
+                lease_until + Duration::minutes(30),
+                1000,
+            )
+            .await
+            .unwrap();
+        assert!(reclaimed.iter().any(|d| d.id == queued.id));
+        cleanup(&database, &subscription).await;
+    }
+
+    #[test_with::env(DATABASE_URL)]
+    #[tokio::test]
+    async fn prune_finished_keeps_pending_and_recent_deliveries() {
+        kernel::ensure_generator_initialized();
+        let database = PostgresDatabase::new().await.unwrap();
+        let mut executor = database.connection().await.unwrap();
+        let subscription = subscription(&[]);
+        database
+            .webhook_subscription_repository()
+            .create(&mut executor, &subscription)
+            .await
+            .unwrap();
+        let repository = database.webhook_delivery_repository();
+        // Created long before anything other tests write, so the prune
+        // cutoff below never reaches their rows.
+        let old = |event_id: &str| WebhookDelivery {
+            created_at: OffsetDateTime::UNIX_EPOCH,
+            next_attempt_at: OffsetDateTime::UNIX_EPOCH + Duration::days(365),
+            ..delivery(&subscription.id, event_id)
+        };
+        let delivered = old("account:5:1");
+        let failed = old("account:5:2");
+        let pending = old("account:5:3");
+        let recent = delivery(&subscription.id, "account:5:4");
+        repository
+            .enqueue(
+                &mut executor,
+                &[
+                    delivered.clone(),
+                    failed.clone(),
+                    pending.clone(),
+                    recent.clone(),
+                ],
+            )
+            .await
+            .unwrap();
+        repository
+            .mark_delivered(&mut executor, &delivered.id, 200)
+            .await
+            .unwrap();
+        repository
+            .mark_attempt_failed(&mut executor, &failed.id, Some(410), "HTTP 410", None)
+            .await
+            .unwrap();
+        repository
+            .mark_delivered(&mut executor, &recent.id, 200)
+            .await
+            .unwrap();
+
+        let pruned = repository
+            .prune_finished(
+                &mut executor,
+                OffsetDateTime::UNIX_EPOCH + Duration::days(1),
+                1000,
+            )
+            .await
+            .unwrap();
+        assert!(pruned >= 2);
+        let mut remaining: Vec<String> = repository
+            .find_by_subscription_id(&mut executor, &subscription.id, 10, None)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|d| d.event_id)
+            .collect();
+        remaining.sort();
+        assert_eq!(remaining, vec!["account:5:3", "account:5:4"]);
+        cleanup(&database, &subscription).await;
+    }
+}
@@ -3,7 +3,7 @@ use deadpool_redis::{Config, Pool, Runtime};
 use error_stack::{Report, ResultExt};
 use kernel::interfaces::database::{Connection, DatabaseConnection};
 use kernel::KernelError;
-use std::ops::Deref;
+use std::ops::{Deref, DerefMut};
 use vodca::References;
 
 // redis://127.0.0.1
@@ -57,6 +57,12 @@ impl Deref for RedisConnection {
     }
 }
 
+impl DerefMut for RedisConnection {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
 impl DatabaseConnection for RedisDatabase {
     type Connection = RedisConnection;
 
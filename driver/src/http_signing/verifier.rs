@@ -93,7 +93,7 @@ impl HttpSignatureVerifier for HttpSignatureVerifierImpl {
     ) -> Result<SignatureVerificationResult, KernelError> {
         if get_header(&request.headers, "signature-input").is_some() {
             return Ok(SignatureVerificationResult::Invalid(
-                "RFC 9421 verification is not implemented".to_string(),
+                "UnsupportedSignature: RFC 9421 verification is not implemented".to_string(),
             ));
         }
 
@@ -1,8 +1,9 @@
 use error_stack::{Report, ResultExt};
 use kernel::interfaces::permission::{
-    InstanceRole, PermissionChecker, PermissionReq, PermissionWriter, RelationTarget,
+    AccountMember, AccountRelation, InstanceRole, InstanceRoleHolder, PermissionChecker,
+    PermissionReq, PermissionWriter, RelationTarget, RoleGrant,
 };
-use kernel::prelude::entity::AuthAccountId;
+use kernel::prelude::entity::{AccountId, AuthAccountId, CustomRoleId};
 use kernel::KernelError;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
@@ -39,17 +40,88 @@ struct CheckResponse {
     allowed: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Exactly one of `subject_id` and `subject_set` is set.
+#[derive(Debug, Serialize)]
 struct RelationTuple {
     namespace: String,
     object: String,
     relation: String,
-    subject_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    subject_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    subject_set: Option<SubjectSet>,
+}
+
+#[derive(Debug, Serialize)]
+struct SubjectSet {
+    namespace: String,
+    object: String,
+    relation: String,
+}
+
+impl RelationTuple {
+    fn for_subject(target: &RelationTarget, subject: &AuthAccountId) -> Self {
+        Self {
+            namespace: target.namespace().to_string(),
+            object: target.object_id(),
+            relation: target.relation_str().to_string(),
+            subject_id: Some(subject.as_ref().to_string()),
+            subject_set: None,
+        }
+    }
+
+    fn for_grant(grant: &RoleGrant) -> error_stack::Result<Self, KernelError> {
+        let relation = grant.relation_str().ok_or_else(|| {
+            Report::new(KernelError::Validation).attach_printable(format!(
+                "Custom roles cannot grant {}",
+                grant.permission.as_str()
+            ))
+        })?;
+        let members = grant.subject_set();
+        Ok(Self {
+            namespace: grant.namespace().to_string(),
+            object: grant.object_id(),
+            relation: relation.to_string(),
+            subject_id: None,
+            subject_set: Some(SubjectSet {
+                namespace: members.namespace().to_string(),
+                object: members.object_id(),
+                relation: members.relation_str().to_string(),
+            }),
+        })
+    }
+
+    /// Query parameters selecting exactly this tuple.
+    fn as_query(&self) -> Vec<(&'static str, &str)> {
+        let mut query = vec![
+            ("namespace", self.namespace.as_str()),
+            ("object", self.object.as_str()),
+            ("relation", self.relation.as_str()),
+        ];
+        if let Some(subject_id) = &self.subject_id {
+            query.push(("subject_id", subject_id.as_str()));
+        }
+        if let Some(subject_set) = &self.subject_set {
+            query.push(("subject_set.namespace", subject_set.namespace.as_str()));
+            query.push(("subject_set.object", subject_set.object.as_str()));
+            query.push(("subject_set.relation", subject_set.relation.as_str()));
+        }
+        query
+    }
+}
+
+/// Listed tuples may have a subject set instead of a `subject_id`.
+#[derive(Debug, Deserialize)]
+struct ListedRelationTuple {
+    namespace: String,
+    object: String,
+    relation: String,
+    subject_id: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 struct ListRelationTuplesResponse {
-    relation_tuples: Vec<RelationTuple>,
+    relation_tuples: Vec<ListedRelationTuple>,
     next_page_token: String,
 }
 
@@ -96,20 +168,124 @@ impl PermissionChecker for KetoClient {
         Ok(check.allowed)
     }
 
+    /// Keto v0.12 has no batch check endpoint, so the checks run
+    /// concurrently instead.
+    async fn check_batch(
+        &self,
+        subject: &AuthAccountId,
+        reqs: &[PermissionReq],
+    ) -> error_stack::Result<Vec<bool>, KernelError> {
+        futures::future::try_join_all(reqs.iter().map(|req| self.check(subject, req))).await
+    }
+
     async fn list_instance_roles(
         &self,
         subject: &AuthAccountId,
     ) -> error_stack::Result<Vec<InstanceRole>, KernelError> {
         let subject_id = subject.as_ref().to_string();
-        let mut relations: Vec<String> = Vec::new();
-        let mut page_token = String::new();
-
-        loop {
-            let mut query = vec![
+        let relations: Vec<String> = self
+            .list_relation_tuples(&[
                 ("namespace", "Instance"),
                 ("object", "singleton"),
                 ("subject_id", subject_id.as_str()),
-            ];
+            ])
+            .await?
+            .into_iter()
+            .filter(|tuple| tuple.namespace == "Instance" && tuple.object == "singleton")
+            .map(|tuple| tuple.relation)
+            .collect();
+
+        Ok([InstanceRole::Admin, InstanceRole::Moderator]
+            .into_iter()
+            .filter(|role| relations.iter().any(|relation| relation == role.as_str()))
+            .collect())
+    }
+
+    async fn list_account_members(
+        &self,
+        account_id: &AccountId,
+    ) -> error_stack::Result<Vec<AccountMember>, KernelError> {
+        let object = account_id.as_ref().to_string();
+        let tuples = self
+            .list_relation_tuples(&[("namespace", "Account"), ("object", object.as_str())])
+            .await?;
+
+        // Subject sets and non-numeric subjects are not auth accounts.
+        Ok(tuples
+            .into_iter()
+            .filter(|tuple| tuple.namespace == "Account" && tuple.object == object)
+            .filter_map(|tuple| {
+                let relation = AccountRelation::parse(&tuple.relation)?;
+                let subject_id = tuple.subject_id?.parse::<i64>().ok()?;
+                Some(AccountMember {
+                    auth_account_id: AuthAccountId::new(subject_id),
+                    relation,
+                })
+            })
+            .collect())
+    }
+
+    async fn list_instance_role_holders(
+        &self,
+    ) -> error_stack::Result<Vec<InstanceRoleHolder>, KernelError> {
+        let tuples = self
+            .list_relation_tuples(&[("namespace", "Instance"), ("object", "singleton")])
+            .await?;
+
+        Ok(tuples
+            .into_iter()
+            .filter(|tuple| tuple.namespace == "Instance" && tuple.object == "singleton")
+            .filter_map(|tuple| {
+                let role = InstanceRole::parse(&tuple.relation)?;
+                let subject_id = tuple.subject_id?.parse::<i64>().ok()?;
+                Some(InstanceRoleHolder {
+                    auth_account_id: AuthAccountId::new(subject_id),
+                    role,
+                })
+            })
+            .collect())
+    }
+
+    async fn list_custom_role_members(
+        &self,
+        role_id: &CustomRoleId,
+    ) -> error_stack::Result<Vec<AuthAccountId>, KernelError> {
+        let members = RelationTarget::CustomRole {
+            role_id: role_id.clone(),
+        };
+        let object = members.object_id();
+        let tuples = self
+            .list_relation_tuples(&[
+                ("namespace", members.namespace()),
+                ("object", object.as_str()),
+                ("relation", members.relation_str()),
+            ])
+            .await?;
+
+        Ok(tuples
+            .into_iter()
+            .filter(|tuple| {
+                tuple.namespace == members.namespace()
+                    && tuple.object == object
+                    && tuple.relation == members.relation_str()
+            })
+            .filter_map(|tuple| tuple.subject_id?.parse::<i64>().ok())
+            .map(AuthAccountId::new)
+            .collect())
+    }
+}
+
+impl KetoClient {
+    /// Follows `next_page_token` until every matching tuple is read.
+    async fn list_relation_tuples(
+        &self,
+        filter: &[(&str, &str)],
+    ) -> error_stack::Result<Vec<ListedRelationTuple>, KernelError> {
+        let mut tuples = Vec::new();
+        let mut page_token = String::new();
+
+        loop {
+            let mut query = filter.to_vec();
             if !page_token.is_empty() {
                 query.push(("page_token", page_token.as_str()));
             }
@@ -136,12 +312,7 @@ impl PermissionChecker for KetoClient {
                 .change_context_lazy(|| KernelError::Internal)
                 .attach_printable("Failed to parse Keto relation-tuples response")?;
 
-            relations.extend(
-                list.relation_tuples
-                    .into_iter()
-                    .filter(|tuple| tuple.namespace == "Instance" && tuple.object == "singleton")
-                    .map(|tuple| tuple.relation),
-            );
+            tuples.extend(list.relation_tuples);
 
             if list.next_page_token.is_empty() {
                 break;
@@ -149,30 +320,17 @@ impl PermissionChecker for KetoClient {
             page_token = list.next_page_token;
         }
 
-        Ok([InstanceRole::Admin, InstanceRole::Moderator]
-            .into_iter()
-            .filter(|role| relations.iter().any(|relation| relation == role.as_str()))
-            .collect())
+        Ok(tuples)
     }
 }
 
-impl PermissionWriter for KetoClient {
-    async fn create_relation(
-        &self,
-        target: &RelationTarget,
-        subject: &AuthAccountId,
-    ) -> error_stack::Result<(), KernelError> {
-        let tuple = RelationTuple {
-            namespace: target.namespace().to_string(),
-            object: target.object_id(),
-            relation: target.relation_str().to_string(),
-            subject_id: subject.as_ref().to_string(),
-        };
-
+impl KetoClient {
+    /// Writing a tuple that already exists is a no-op.
+    async fn put_tuple(&self, tuple: &RelationTuple) -> error_stack::Result<(), KernelError> {
         let response = self
             .http_client
             .put(format!("{}/admin/relation-tuples", self.write_url))
-            .json(&tuple)
+            .json(tuple)
             .send()
             .await
             .change_context_lazy(|| KernelError::Internal)
@@ -187,20 +345,12 @@ impl PermissionWriter for KetoClient {
         Ok(())
     }
 
-    async fn delete_relation(
-        &self,
-        target: &RelationTarget,
-        subject: &AuthAccountId,
-    ) -> error_stack::Result<(), KernelError> {
+    /// Deleting a tuple that does not exist is a no-op.
+    async fn delete_tuple(&self, tuple: &RelationTuple) -> error_stack::Result<(), KernelError> {
         let response = self
             .http_client
             .delete(format!("{}/admin/relation-tuples", self.write_url))
-            .query(&[
-                ("namespace", target.namespace()),
-                ("object", &target.object_id()),
-                ("relation", target.relation_str()),
-                ("subject_id", &subject.as_ref().to_string()),
-            ])
+            .query(&tuple.as_query())
             .send()
             .await
             .change_context_lazy(|| KernelError::Internal)
@@ -216,6 +366,34 @@ impl PermissionWriter for KetoClient {
     }
 }
 
+impl PermissionWriter for KetoClient {
+    async fn create_relation(
+        &self,
+        target: &RelationTarget,
+        subject: &AuthAccountId,
+    ) -> error_stack::Result<(), KernelError> {
+        self.put_tuple(&RelationTuple::for_subject(target, subject))
+            .await
+    }
+
+    async fn delete_relation(
+        &self,
+        target: &RelationTarget,
+        subject: &AuthAccountId,
+    ) -> error_stack::Result<(), KernelError> {
+        self.delete_tuple(&RelationTuple::for_subject(target, subject))
+            .await
+    }
+
+    async fn create_role_grant(&self, grant: &RoleGrant) -> error_stack::Result<(), KernelError> {
+        self.put_tuple(&RelationTuple::for_grant(grant)?).await
+    }
+
+    async fn delete_role_grant(&self, grant: &RoleGrant) -> error_stack::Result<(), KernelError> {
+        self.delete_tuple(&RelationTuple::for_grant(grant)?).await
+    }
+}
+
 #[cfg(test)]
 #[path = "keto/tests.rs"]
 mod tests;
@@ -1,5 +1,5 @@
 use super::*;
-use wiremock::matchers::{method, path, query_param, query_param_is_missing};
+use wiremock::matchers::{body_json, method, path, query_param, query_param_is_missing};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
 fn new_subject() -> AuthAccountId {
@@ -299,3 +299,204 @@ async fn list_instance_roles_returns_empty_vec_when_no_tuples() {
     assert_eq!(roles, Vec::<InstanceRole>::new());
     server.verify().await;
 }
+
+/// Given: Keto Read API が Account の tuple (subject set を含む) を返す
+/// When: list_account_members を呼ぶ
+/// Then: namespace=Account&object=<account_id> で問い合わせ、subject_id を持つ tuple だけが relation 付きで返る
+#[tokio::test]
+async fn list_account_members_maps_subjects_and_relations() {
+    let server = MockServer::start().await;
+    let owner = new_subject();
+    let editor = new_subject();
+    let account_id = kernel::prelude::entity::AccountId::default();
+    let object = AsRef::<i64>::as_ref(&account_id).to_string();
+
+    Mock::given(method("GET"))
+        .and(path("/relation-tuples"))
+        .and(query_param("namespace", "Account"))
+        .and(query_param("object", object.as_str()))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "relation_tuples": [
+                {"namespace": "Account", "object": object, "relation": "owners", "subject_id": subject_id_string(&owner)},
+                {"namespace": "Account", "object": object, "relation": "editors", "subject_id": subject_id_string(&editor)},
+                {"namespace": "Account", "object": object, "relation": "editors", "subject_set": {"namespace": "Group", "object": "staff", "relation": "members"}}
+            ],
+            "next_page_token": ""
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let members = keto_client(&server.uri())
+        .list_account_members(&account_id)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        members,
+        vec![
+            AccountMember {
+                auth_account_id: owner,
+                relation: AccountRelation::Owner,
+            },
+            AccountMember {
+                auth_account_id: editor,
+                relation: AccountRelation::Editor,
+            },
+        ]
+    );
+    server.verify().await;
+}
+
+/// Given: Keto Read API が Instance の tuple (未知の relation と subject set を含む) を返す
+/// When: list_instance_role_holders を呼ぶ
+/// Then: subject_id と既知の relation を持つ tuple だけが role 付きで返る
+#[tokio::test]
+async fn list_instance_role_holders_maps_subjects_and_roles() {
+    let server = MockServer::start().await;
+    let admin = new_subject();
+    let moderator = new_subject();
+
+    Mock::given(method("GET"))
+        .and(path("/relation-tuples"))
+        .and(query_param("namespace", "Instance"))
+        .and(query_param("object", "singleton"))
+        .and(query_param_is_missing("subject_id"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "relation_tuples": [
+                {"namespace": "Instance", "object": "singleton", "relation": "admins", "subject_id": subject_id_string(&admin)},
+                {"namespace": "Instance", "object": "singleton", "relation": "moderators", "subject_id": subject_id_string(&moderator)},
+                {"namespace": "Instance", "object": "singleton", "relation": "auditors", "subject_id": subject_id_string(&admin)},
+                {"namespace": "Instance", "object": "singleton", "relation": "moderators", "subject_set": {"namespace": "Group", "object": "staff", "relation": "members"}}
+            ],
+            "next_page_token": ""
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let holders = keto_client(&server.uri())
+        .list_instance_role_holders()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        holders,
+        vec![
+            InstanceRoleHolder {
+                auth_account_id: admin,
+                role: InstanceRole::Admin,
+            },
+            InstanceRoleHolder {
+                auth_account_id: moderator,
+                role: InstanceRole::Moderator,
+            },
+        ]
+    );
+    server.verify().await;
+}
+
+/// Given: カスタムロールに manage_media を付与する
+/// When: create_role_grant / delete_role_grant を呼ぶ
+/// Then: Instance の manage_media_grants に Role:<id>#members の subject set が書き込まれ、同じ tuple で削除される
+#[tokio::test]
+async fn role_grants_write_subject_set_tuples() {
+    let server = MockServer::start().await;
+    let role_id = CustomRoleId::default();
+    let object = AsRef::<i64>::as_ref(&role_id).to_string();
+    let grant = RoleGrant {
+        role_id: role_id.clone(),
+        permission: kernel::interfaces::permission::InstancePermission::ManageMedia,
+    };
+
+    Mock::given(method("PUT"))
+        .and(path("/admin/relation-tuples"))
+        .and(body_json(serde_json::json!({
+            "namespace": "Instance",
+            "object": "singleton",
+            "relation": "manage_media_grants",
+            "subject_set": {"namespace": "Role", "object": object, "relation": "members"}
+        })))
+        .respond_with(ResponseTemplate::new(201))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/admin/relation-tuples"))
+        .and(query_param("namespace", "Instance"))
+        .and(query_param("relation", "manage_media_grants"))
+        .and(query_param("subject_set.namespace", "Role"))
+        .and(query_param("subject_set.object", object.as_str()))
+        .and(query_param("subject_set.relation", "members"))
+        .and(query_param_is_missing("subject_id"))
+        .respond_with(ResponseTemplate::new(204))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let writer = keto_writer(&server.uri());
+    writer.create_role_grant(&grant).await.unwrap();
+    writer.delete_role_grant(&grant).await.unwrap();
+    server.verify().await;
+}
+
+/// Given: administrate を束ねようとする grant
+/// When: create_role_grant を呼ぶ
+/// Then: Keto に書き込まずに Validation エラーになる
+#[tokio::test]
+async fn role_grant_for_administrate_is_rejected_without_writing() {
+    let server = MockServer::start().await;
+    Mock::given(method("PUT"))
+        .respond_with(ResponseTemplate::new(201))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let result = keto_writer(&server.uri())
+        .create_role_grant(&RoleGrant {
+            role_id: CustomRoleId::default(),
+            permission: kernel::interfaces::permission::InstancePermission::Administrate,
+        })
+        .await;
+
+    assert_eq!(
+        result.unwrap_err().current_context(),
+        &KernelError::Validation
+    );
+    server.verify().await;
+}
+
+/// Given: Keto Read API が Role:<id>#members の tuple を返す
+/// When: list_custom_role_members を呼ぶ
+/// Then: subject_id を持つメンバーだけが返る
+#[tokio::test]
+async fn list_custom_role_members_returns_member_subjects() {
+    let server = MockServer::start().await;
+    let member = new_subject();
+    let role_id = CustomRoleId::default();
+    let object = AsRef::<i64>::as_ref(&role_id).to_string();
+
+    Mock::given(method("GET"))
+        .and(path("/relation-tuples"))
+        .and(query_param("namespace", "Role"))
+        .and(query_param("object", object.as_str()))
+        .and(query_param("relation", "members"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "relation_tuples": [
+                {"namespace": "Role", "object": object, "relation": "members", "subject_id": subject_id_string(&member)},
+                {"namespace": "Role", "object": object, "relation": "members", "subject_set": {"namespace": "Role", "object": "other", "relation": "members"}}
+            ],
+            "next_page_token": ""
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let members = keto_client(&server.uri())
+        .list_custom_role_members(&role_id)
+        .await
+        .unwrap();
+
+    assert_eq!(members, vec![member]);
+    server.verify().await;
+}
@@ -1,8 +1,10 @@
+pub mod change_feed;
 pub mod crypto;
 pub mod database;
 mod error;
 pub mod http_signing;
 pub mod keto;
+pub mod permission;
 pub mod storage;
 
 pub use self::error::*;
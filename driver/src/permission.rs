@@ -0,0 +1,147 @@
+mod cache;
+mod postgres;
+
+use crate::database::PostgresDatabase;
+use crate::keto::KetoClient;
+use error_stack::Report;
+use kernel::interfaces::permission::{
+    AccountMember, InstanceRole, InstanceRoleHolder, PermissionChecker, PermissionReq,
+    PermissionWriter, RelationTarget, RoleGrant,
+};
+use kernel::prelude::entity::{AccountId, AuthAccountId, CustomRoleId};
+use kernel::KernelError;
+
+pub use self::cache::*;
+pub use self::postgres::*;
+
+/// Permission backend chosen by `PERMISSION_BACKEND`: `keto` (default) or
+/// `postgres`, which evaluates the same rules in-process for deployments
+/// and tests without Keto.
+#[derive(Clone)]
+pub enum ConfiguredPermissionStore {
+    Keto(KetoClient),
+    Postgres(PostgresPermissionStore),
+}
+
+impl ConfiguredPermissionStore {
+    pub fn from_env(database: PostgresDatabase) -> error_stack::Result<Self, KernelError> {
+        match env_or("PERMISSION_BACKEND", "keto").as_str() {
+            "keto" => Ok(Self::Keto(KetoClient::new(
+                env_or("KETO_READ_URL", "http://localhost:4466"),
+                env_or("KETO_WRITE_URL", "http://localhost:4467"),
+            ))),
+            "postgres" => Ok(Self::Postgres(PostgresPermissionStore::new(database))),
+            other => Err(Report::new(KernelError::Internal).attach_printable(format!(
+                "Unknown PERMISSION_BACKEND {other:?}, expected \"keto\" or \"postgres\""
+            ))),
+        }
+    }
+}
+
+impl PermissionChecker for ConfiguredPermissionStore {
+    async fn check(
+        &self,
+        subject: &AuthAccountId,
+        req: &PermissionReq,
+    ) -> error_stack::Result<bool, KernelError> {
+        match self {
+            Self::Keto(store) => store.check(subject, req).await,
+            Self::Postgres(store) => store.check(subject, req).await,
+        }
+    }
+
+    async fn check_batch(
+        &self,
+        subject: &AuthAccountId,
+        reqs: &[PermissionReq],
+    ) -> error_stack::Result<Vec<bool>, KernelError> {
+        match self {
+            Self::Keto(store) => store.check_batch(subject, reqs).await,
+            Self::Postgres(store) => store.check_batch(subject, reqs).await,
+        }
+    }
+
+    async fn list_instance_roles(
+        &self,
+        subject: &AuthAccountId,
+    ) -> error_stack::Result<Vec<InstanceRole>, KernelError> {
+        match self {
+            Self::Keto(store) => store.list_instance_roles(subject).await,
+            Self::Postgres(store) => store.list_instance_roles(subject).await,
+        }
+    }
+
+    async fn list_account_members(
+        &self,
+        account_id: &AccountId,
+    ) -> error_stack::Result<Vec<AccountMember>, KernelError> {
+        match self {
+            Self::Keto(store) => store.list_account_members(account_id).await,
+            Self::Postgres(store) => store.list_account_members(account_id).await,
+        }
+    }
+
+    async fn list_instance_role_holders(
+        &self,
+    ) -> error_stack::Result<Vec<InstanceRoleHolder>, KernelError> {
+        match self {
+            Self::Keto(store) => store.list_instance_role_holders().await,
+            Self::Postgres(store) => store.list_instance_role_holders().await,
+        }
+    }
+
+    async fn list_custom_role_members(
+        &self,
+        role_id: &CustomRoleId,
+    ) -> error_stack::Result<Vec<AuthAccountId>, KernelError> {
+        match self {
+            Self::Keto(store) => store.list_custom_role_members(role_id).await,
+            Self::Postgres(store) => store.list_custom_role_members(role_id).await,
+        }
+    }
+}
+
+impl PermissionWriter for ConfiguredPermissionStore {
+    async fn create_relation(
+        &self,
+        target: &RelationTarget,
+        subject: &AuthAccountId,
+    ) -> error_stack::Result<(), KernelError> {
+        match self {
+            Self::Keto(store) => store.create_relation(target, subject).await,
+            Self::Postgres(store) => store.create_relation(target, subject).await,
+        }
+    }
+
+    async fn delete_relation(
+        &self,
+        target: &RelationTarget,
+        subject: &AuthAccountId,
+    ) -> error_stack::Result<(), KernelError> {
+        match self {
+            Self::Keto(store) => store.delete_relation(target, subject).await,
+            Self::Postgres(store) => store.delete_relation(target, subject).await,
+        }
+    }
+
+    async fn create_role_grant(&self, grant: &RoleGrant) -> error_stack::Result<(), KernelError> {
+        match self {
+            Self::Keto(store) => store.create_role_grant(grant).await,
+            Self::Postgres(store) => store.create_role_grant(grant).await,
+        }
+    }
+
+    async fn delete_role_grant(&self, grant: &RoleGrant) -> error_stack::Result<(), KernelError> {
+        match self {
+            Self::Keto(store) => store.delete_role_grant(grant).await,
+            Self::Postgres(store) => store.delete_role_grant(grant).await,
+        }
+    }
+}
+
+fn env_or(name: &str, default: &str) -> String {
+    dotenvy::var(name).unwrap_or_else(|_| default.to_string())
+}
+
+#[cfg(test)]
+mod conformance;
@@ -0,0 +1,453 @@
+use kernel::interfaces::permission::{
+    AccountMember, InstanceRole, InstanceRoleHolder, PermissionChecker, PermissionReq,
+    PermissionWriter, RelationTarget, RoleGrant,
+};
+use kernel::prelude::entity::{AccountId, AuthAccountId, CustomRoleId};
+use kernel::KernelError;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Default lifetime of a shared cache entry. Another process's writes are
+/// only seen once the entries they affect expire.
+const DEFAULT_TTL: Duration = Duration::from_secs(5);
+
+/// Beyond this many entries, expired ones are dropped, and failing that
+/// the whole shared cache.
+const MAX_ENTRIES: usize = 10_000;
+
+/// Subject, namespace, object and permission of a check.
+type CheckKey = (i64, &'static str, String, &'static str);
+
+fn check_key(subject: &AuthAccountId, req: &PermissionReq) -> CheckKey {
+    (
+        *subject.as_ref(),
+        req.namespace(),
+        req.object_id(),
+        req.permission_name(),
+    )
+}
+
+tokio::task_local! {
+    static REQUEST_CACHE: RefCell<HashMap<CheckKey, bool>>;
+}
+
+/// Run `future` with its own permission check cache, so a request asking
+/// the same question twice reaches the backend once.
+pub async fn with_request_cache<F: Future>(future: F) -> F::Output {
+    REQUEST_CACHE
+        .scope(RefCell::new(HashMap::new()), future)
+        .await
+}
+
+#[derive(Default)]
+struct SharedEntries {
+    /// Bumped by every write, so answers fetched before it are not stored.
+    generation: u64,
+    checks: HashMap<CheckKey, (bool, Instant)>,
+}
+
+/// Caches `check` answers per request and, for `ttl`, across requests.
+/// Any write through this store empties both caches; listings are never
+/// cached.
+#[derive(Clone)]
+pub struct CachedPermissionStore<S> {
+    store: S,
+    ttl: Duration,
+    shared: Arc<Mutex<SharedEntries>>,
+}
+
+impl<S> CachedPermissionStore<S> {
+    /// A zero `ttl` keeps only the per-request cache.
+    pub fn new(store: S, ttl: Duration) -> Self {
+        Self {
+            store,
+            ttl,
+            shared: Arc::new(Mutex::new(SharedEntries::default())),
+        }
+    }
+
+    /// `PERMISSION_CACHE_TTL_MS` (default 5000, 0 disables the shared
+    /// cache).
+    pub fn from_env(store: S) -> Self {
+        let ttl = dotenvy::var("PERMISSION_CACHE_TTL_MS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .map(Duration::from_millis)
+            .unwrap_or(DEFAULT_TTL);
+        Self::new(store, ttl)
+    }
+
+    fn generation(&self) -> u64 {
+        self.shared.lock().unwrap().generation
+    }
+
+    fn lookup(&self, key: &CheckKey) -> Option<bool> {
+        let cached = REQUEST_CACHE
+            .try_with(|cache| cache.borrow().get(key).copied())
+            .ok()
+            .flatten();
+        if cached.is_some() {
+            return cached;
+        }
+        let shared = self.shared.lock().unwrap();
+        shared
+            .checks
+            .get(key)
+            .filter(|(_, expires_at)| *expires_at > Instant::now())
+            .map(|(allowed, _)| *allowed)
+    }
+
+    fn remember(&self, key: CheckKey, allowed: bool, generation: u64) {
+        let mut shared = self.shared.lock().unwrap();
+        if shared.generation != generation {
+            return;
+        }
+        let _ = REQUEST_CACHE.try_with(|cache| cache.borrow_mut().insert(key.clone(), allowed));
+        if self.ttl.is_zero() {
+            return;
+        }
+        let now = Instant::now();
+        if shared.checks.len() >= MAX_ENTRIES {
+            shared.checks.retain(|_, (_, expires_at)| *expires_at > now);
+            if shared.checks.len() >= MAX_ENTRIES {
+                shared.checks.clear();
+            }
+        }
+        shared.checks.insert(key, (allowed, now + self.ttl));
+    }
+
+    fn invalidate(&self) {
+        let mut shared = self.shared.lock().unwrap();
+        shared.generation += 1;
+        shared.checks.clear();
+        let _ = REQUEST_CACHE.try_with(|cache| cache.borrow_mut().clear());
+    }
+}
+
+impl<S: PermissionChecker> PermissionChecker for CachedPermissionStore<S> {
+    async fn check(
+        &self,
+        subject: &AuthAccountId,
+        req: &PermissionReq,
+    ) -> error_stack::Result<bool, KernelError> {
+        let key = check_key(subject, req);
+        if let Some(allowed) = self.lookup(&key) {
+            return Ok(allowed);
+        }
+        let generation = self.generation();
+        let allowed = self.store.check(subject, req).await?;
+        self.remember(key, allowed, generation);
+        Ok(allowed)
+    }
+
+    /// Only the requests missing from both caches reach the backend, in
+    /// one batch.
+    async fn check_batch(
+        &self,
+        subject: &AuthAccountId,
+        reqs: &[PermissionReq],
+    ) -> error_stack::Result<Vec<bool>, KernelError> {
+        let keys: Vec<CheckKey> = reqs.iter().map(|req| check_key(subject, req)).collect();
+        let mut answers: Vec<Option<bool>> = keys.iter().map(|key| self.lookup(key)).collect();
+        let misses: Vec<usize> = answers
+            .iter()
+            .enumerate()
+            .filter(|(_, answer)| answer.is_none())
+            .map(|(index, _)| index)
+            .collect();
+
+        if !misses.is_empty() {
+            let generation = self.generation();
+            let missing: Vec<PermissionReq> =
+                misses.iter().map(|&index| reqs[index].clone()).collect();
+            let fetched = self.store.check_batch(subject, &missing).await?;
+            for (&index, allowed) in misses.iter().zip(fetched) {
+                self.remember(keys[index].clone(), allowed, generation);
+                answers[index] = Some(allowed);
+            }
+        }
+
+        Ok(answers
+            .into_iter()
+            .map(|answer| answer.unwrap_or(false))
+            .collect())
+    }
+
+    async fn list_instance_roles(
+        &self,
+        subject: &AuthAccountId,
+    ) -> error_stack::Result<Vec<InstanceRole>, KernelError> {
+        self.store.list_instance_roles(subject).await
+    }
+
+    async fn list_account_members(
+        &self,
+        account_id: &AccountId,
+    ) -> error_stack::Result<Vec<AccountMember>, KernelError> {
+        self.store.list_account_members(account_id).await
+    }
+
+    async fn list_instance_role_holders(
+        &self,
+    ) -> error_stack::Result<Vec<InstanceRoleHolder>, KernelError> {
+        self.store.list_instance_role_holders().await
+    }
+
+    async fn list_custom_role_members(
+        &self,
+        role_id: &CustomRoleId,
+    ) -> error_stack::Result<Vec<AuthAccountId>, KernelError> {
+        self.store.list_custom_role_members(role_id).await
+    }
+}
+
+impl<S: PermissionWriter> PermissionWriter for CachedPermissionStore<S> {
+    async fn create_relation(
+        &self,
+        target: &RelationTarget,
+        subject: &AuthAccountId,
+    ) -> error_stack::Result<(), KernelError> {
+        let result = self.store.create_relation(target, subject).await;
+        self.invalidate();
+        result
+    }
+
+    async fn delete_relation(
+        &self,
+        target: &RelationTarget,
+        subject: &AuthAccountId,
+    ) -> error_stack::Result<(), KernelError> {
+        let result = self.store.delete_relation(target, subject).await;
+        self.invalidate();
+        result
+    }
+
+    async fn create_role_grant(&self, grant: &RoleGrant) -> error_stack::Result<(), KernelError> {
+        let result = self.store.create_role_grant(grant).await;
+        self.invalidate();
+        result
+    }
+
+    async fn delete_role_grant(&self, grant: &RoleGrant) -> error_stack::Result<(), KernelError> {
+        let result = self.store.delete_role_grant(grant).await;
+        self.invalidate();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kernel::interfaces::permission::{AccountRelation, InstancePermission};
+    use std::collections::HashSet;
+
+    /// Grants exactly the relations written through it and counts how
+    /// many checks reach it.
+    #[derive(Default)]
+    struct CountingStore {
+        granted: Mutex<HashSet<(i64, String)>>,
+        checks: Mutex<Vec<usize>>,
+    }
+
+    impl CountingStore {
+        fn checked(&self) -> Vec<usize> {
+            self.checks.lock().unwrap().clone()
+        }
+    }
+
+    impl PermissionChecker for CountingStore {
+        async fn check(
+            &self,
+            subject: &AuthAccountId,
+            req: &PermissionReq,
+        ) -> error_stack::Result<bool, KernelError> {
+            self.checks.lock().unwrap().push(1);
+            Ok(self
+                .granted
+                .lock()
+                .unwrap()
+                .contains(&(*subject.as_ref(), req.object_id())))
+        }
+
+        async fn check_batch(
+            &self,
+            subject: &AuthAccountId,
+            reqs: &[PermissionReq],
+        ) -> error_stack::Result<Vec<bool>, KernelError> {
+            self.checks.lock().unwrap().push(reqs.len());
+            let granted = self.granted.lock().unwrap();
+            Ok(reqs
+                .iter()
+                .map(|req| granted.contains(&(*subject.as_ref(), req.object_id())))
+                .collect())
+        }
+
+        async fn list_instance_roles(
+            &self,
+            _subject: &AuthAccountId,
+        ) -> error_stack::Result<Vec<InstanceRole>, KernelError> {
+            Ok(Vec::new())
+        }
+
+        async fn list_account_members(
+            &self,
+            _account_id: &AccountId,
+        ) -> error_stack::Result<Vec<AccountMember>, KernelError> {
+            Ok(Vec::new())
+        }
+
+        async fn list_instance_role_holders(
+            &self,
+        ) -> error_stack::Result<Vec<InstanceRoleHolder>, KernelError> {
+            Ok(Vec::new())
+        }
+
+        async fn list_custom_role_members(
+            &self,
+            _role_id: &CustomRoleId,
+        ) -> error_stack::Result<Vec<AuthAccountId>, KernelError> {
+            Ok(Vec::new())
+        }
+    }
+
+    impl PermissionWriter for CountingStore {
+        async fn create_relation(
+            &self,
+            target: &RelationTarget,
+            subject: &AuthAccountId,
+        ) -> error_stack::Result<(), KernelError> {
+            self.granted
+                .lock()
+                .unwrap()
+                .insert((*subject.as_ref(), target.object_id()));
+            Ok(())
+        }
+
+        async fn delete_relation(
+            &self,
+            target: &RelationTarget,
+            subject: &AuthAccountId,
+        ) -> error_stack::Result<(), KernelError> {
+            self.granted
+                .lock()
+                .unwrap()
+                .remove(&(*subject.as_ref(), target.object_id()));
+            Ok(())
+        }
+
+        async fn create_role_grant(
+            &self,
+            _grant: &RoleGrant,
+        ) -> error_stack::Result<(), KernelError> {
+            Ok(())
+        }
+
+        async fn delete_role_grant(
+            &self,
+            _grant: &RoleGrant,
+        ) -> error_stack::Result<(), KernelError> {
+            Ok(())
+        }
+    }
+
+    fn view(account_id: i64) -> PermissionReq {
+        PermissionReq::account(AccountId::new(account_id), "view")
+    }
+
+    fn owner_of(account_id: i64) -> RelationTarget {
+        RelationTarget::Account {
+            account_id: AccountId::new(account_id),
+            relation: AccountRelation::Owner,
+        }
+    }
+
+    fn cached(ttl: Duration) -> CachedPermissionStore<CountingStore> {
+        CachedPermissionStore::new(CountingStore::default(), ttl)
+    }
+
+    #[tokio::test]
+    async fn repeated_checks_are_answered_from_the_cache_until_it_expires() {
+        let store = cached(Duration::from_millis(50));
+        let subject = AuthAccountId::new(1);
+
+        assert!(!store.check(&subject, &view(10)).await.unwrap());
+        assert!(!store.check(&subject, &view(10)).await.unwrap());
+        assert_eq!(store.store.checked(), [1]);
+
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        assert!(!store.check(&subject, &view(10)).await.unwrap());
+        assert_eq!(store.store.checked(), [1, 1]);
+    }
+
+    #[tokio::test]
+    async fn writes_invalidate_cached_answers() {
+        let store = cached(DEFAULT_TTL);
+        let subject = AuthAccountId::new(1);
+
+        assert!(!store.check(&subject, &view(10)).await.unwrap());
+        store
+            .create_relation(&owner_of(10), &subject)
+            .await
+            .unwrap();
+        assert!(store.check(&subject, &view(10)).await.unwrap());
+        store
+            .delete_relation(&owner_of(10), &subject)
+            .await
+            .unwrap();
+        assert!(!store.check(&subject, &view(10)).await.unwrap());
+        store
+            .create_role_grant(&RoleGrant {
+                role_id: CustomRoleId::new(1),
+                permission: InstancePermission::ManageMedia,
+            })
+            .await
+            .unwrap();
+        assert!(!store.check(&subject, &view(10)).await.unwrap());
+
+        assert_eq!(store.store.checked(), [1, 1, 1, 1]);
+    }
+
+    #[tokio::test]
+    async fn request_cache_works_without_the_shared_cache() {
+        let store = cached(Duration::ZERO);
+        let subject = AuthAccountId::new(1);
+
+        with_request_cache(async {
+            store.check(&subject, &view(10)).await.unwrap();
+            store.check(&subject, &view(10)).await.unwrap();
+        })
+        .await;
+        assert_eq!(store.store.checked(), [1]);
+
+        store.check(&subject, &view(10)).await.unwrap();
+        store.check(&subject, &view(10)).await.unwrap();
+        assert_eq!(store.store.checked(), [1, 1, 1]);
+    }
+
+    #[tokio::test]
+    async fn batches_only_fetch_uncached_answers() {
+        let store = cached(DEFAULT_TTL);
+        let subject = AuthAccountId::new(1);
+        store
+            .create_relation(&owner_of(11), &subject)
+            .await
+            .unwrap();
+        store.check(&subject, &view(10)).await.unwrap();
+
+        let allowed = store
+            .check_batch(&subject, &[view(10), view(11), view(12)])
+            .await
+            .unwrap();
+        let again = store
+            .check_batch(&subject, &[view(12), view(11)])
+            .await
+            .unwrap();
+
+        assert_eq!(allowed, [false, true, false]);
+        assert_eq!(again, [false, true]);
+        assert_eq!(store.store.checked(), [1, 2]);
+    }
+}
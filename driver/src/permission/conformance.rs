@@ -0,0 +1,379 @@
+//! Behaviour every permission backend shares, run against both the
+//! Postgres store and a live Keto. Each scenario uses fresh ids and removes
+//! the tuples it wrote, so the backends can be shared with other tests.
+
+use super::PostgresPermissionStore;
+use crate::database::PostgresDatabase;
+use crate::keto::KetoClient;
+use kernel::interfaces::permission::{
+    AccountMember, AccountRelation, InstancePermission, InstanceRole, InstanceRoleHolder,
+    PermissionChecker, PermissionReq, PermissionWriter, RelationTarget, RoleGrant,
+};
+use kernel::prelude::entity::{AccountId, AuthAccountId, CustomRoleId};
+use kernel::KernelError;
+
+const ACCOUNT_PERMISSIONS: [&str; 5] = ["view", "edit", "sign", "deactivate", "share"];
+
+async fn allowed<P: PermissionChecker>(
+    store: &P,
+    subject: &AuthAccountId,
+    req: PermissionReq,
+) -> bool {
+    store.check(subject, &req).await.unwrap()
+}
+
+async fn account_permissions<P: PermissionChecker>(
+    store: &P,
+    subject: &AuthAccountId,
+    account_id: &AccountId,
+) -> Vec<&'static str> {
+    let mut granted = Vec::new();
+    for permission in ACCOUNT_PERMISSIONS {
+        if allowed(
+            store,
+            subject,
+            PermissionReq::account(account_id.clone(), permission),
+        )
+        .await
+        {
+            granted.push(permission);
+        }
+    }
+    granted
+}
+
+async fn instance_permissions<P: PermissionChecker>(
+    store: &P,
+    subject: &AuthAccountId,
+) -> Vec<InstancePermission> {
+    let mut granted = Vec::new();
+    for permission in InstancePermission::ALL {
+        if allowed(store, subject, PermissionReq::instance(permission)).await {
+            granted.push(permission);
+        }
+    }
+    granted
+}
+
+async fn account_relations_grant_their_permits<P: PermissionChecker + PermissionWriter>(store: &P) {
+    kernel::ensure_generator_initialized();
+    let account_id = AccountId::default();
+    let stranger = AuthAccountId::default();
+    let members = [
+        (AccountRelation::Owner, AuthAccountId::default()),
+        (AccountRelation::Editor, AuthAccountId::default()),
+        (AccountRelation::Signer, AuthAccountId::default()),
+    ];
+    for (relation, subject) in &members {
+        let target = RelationTarget::Account {
+            account_id: account_id.clone(),
+            relation: *relation,
+        };
+        store.create_relation(&target, subject).await.unwrap();
+    }
+
+    assert_eq!(
+        account_permissions(store, &members[0].1, &account_id).await,
+        ACCOUNT_PERMISSIONS
+    );
+    assert_eq!(
+        account_permissions(store, &members[1].1, &account_id).await,
+        ["view", "edit"]
+    );
+    assert_eq!(
+        account_permissions(store, &members[2].1, &account_id).await,
+        ["view", "sign"]
+    );
+    assert!(account_permissions(store, &stranger, &account_id)
+        .await
+        .is_empty());
+    assert!(
+        account_permissions(store, &members[0].1, &AccountId::default())
+            .await
+            .is_empty(),
+        "relations are scoped to their account"
+    );
+
+    let mut listed = store.list_account_members(&account_id).await.unwrap();
+    listed.sort_by_key(|member| member.relation.as_str());
+    let mut expected: Vec<AccountMember> = members
+        .iter()
+        .map(|(relation, subject)| AccountMember {
+            auth_account_id: subject.clone(),
+            relation: *relation,
+        })
+        .collect();
+    expected.sort_by_key(|member| member.relation.as_str());
+    assert_eq!(listed, expected);
+
+    for (relation, subject) in &members {
+        let target = RelationTarget::Account {
+            account_id: account_id.clone(),
+            relation: *relation,
+        };
+        store.delete_relation(&target, subject).await.unwrap();
+    }
+    assert!(account_permissions(store, &members[0].1, &account_id)
+        .await
+        .is_empty());
+    assert!(store
+        .list_account_members(&account_id)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+async fn instance_roles_grant_their_permits<P: PermissionChecker + PermissionWriter>(store: &P) {
+    kernel::ensure_generator_initialized();
+    let admin = AuthAccountId::default();
+    let moderator = AuthAccountId::default();
+    let admins = RelationTarget::Instance {
+        role: InstanceRole::Admin,
+    };
+    let moderators = RelationTarget::Instance {
+        role: InstanceRole::Moderator,
+    };
+    store.create_relation(&admins, &admin).await.unwrap();
+    store
+        .create_relation(&moderators, &moderator)
+        .await
+        .unwrap();
+    store.create_relation(&moderators, &admin).await.unwrap();
+
+    assert_eq!(
+        instance_permissions(store, &admin).await,
+        InstancePermission::ALL
+    );
+    assert_eq!(
+        instance_permissions(store, &moderator).await,
+        [
+            InstancePermission::Moderate,
+            InstancePermission::ManageReports,
+            InstancePermission::ManageDomainBlocks,
+            InstancePermission::ManageMedia,
+        ]
+    );
+    assert_eq!(
+        store.list_instance_roles(&admin).await.unwrap(),
+        [InstanceRole::Admin, InstanceRole::Moderator]
+    );
+    assert_eq!(
+        store.list_instance_roles(&moderator).await.unwrap(),
+        [InstanceRole::Moderator]
+    );
+    let holders = store.list_instance_role_holders().await.unwrap();
+    for (auth_account_id, role) in [
+        (&admin, InstanceRole::Admin),
+        (&admin, InstanceRole::Moderator),
+        (&moderator, InstanceRole::Moderator),
+    ] {
+        assert!(holders.contains(&InstanceRoleHolder {
+            auth_account_id: auth_account_id.clone(),
+            role,
+        }));
+    }
+
+    store.delete_relation(&admins, &admin).await.unwrap();
+    store.delete_relation(&moderators, &admin).await.unwrap();
+    store
+        .delete_relation(&moderators, &moderator)
+        .await
+        .unwrap();
+    assert!(instance_permissions(store, &admin).await.is_empty());
+    assert!(store
+        .list_instance_roles(&moderator)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+async fn custom_role_grants_reach_members<P: PermissionChecker + PermissionWriter>(store: &P) {
+    kernel::ensure_generator_initialized();
+    let role_id = CustomRoleId::default();
+    let member = AuthAccountId::default();
+    let outsider = AuthAccountId::default();
+    let members = RelationTarget::CustomRole {
+        role_id: role_id.clone(),
+    };
+    let grants = [
+        InstancePermission::ManageMedia,
+        InstancePermission::ViewAuditLog,
+    ]
+    .map(|permission| RoleGrant {
+        role_id: role_id.clone(),
+        permission,
+    });
+    store.create_relation(&members, &member).await.unwrap();
+    for grant in &grants {
+        store.create_role_grant(grant).await.unwrap();
+    }
+
+    assert_eq!(
+        instance_permissions(store, &member).await,
+        [
+            InstancePermission::ManageMedia,
+            InstancePermission::ViewAuditLog
+        ]
+    );
+    assert!(instance_permissions(store, &outsider).await.is_empty());
+    assert_eq!(
+        store.list_custom_role_members(&role_id).await.unwrap(),
+        std::slice::from_ref(&member)
+    );
+    assert!(
+        store.list_instance_roles(&member).await.unwrap().is_empty(),
+        "custom roles are not instance roles"
+    );
+
+    store.delete_role_grant(&grants[1]).await.unwrap();
+    assert_eq!(
+        instance_permissions(store, &member).await,
+        [InstancePermission::ManageMedia]
+    );
+
+    store.delete_relation(&members, &member).await.unwrap();
+    assert!(instance_permissions(store, &member).await.is_empty());
+    assert!(store
+        .list_custom_role_members(&role_id)
+        .await
+        .unwrap()
+        .is_empty());
+    store.delete_role_grant(&grants[0]).await.unwrap();
+}
+
+async fn batch_checks_match_single_checks<P: PermissionChecker + PermissionWriter>(store: &P) {
+    kernel::ensure_generator_initialized();
+    let subject = AuthAccountId::default();
+    let account_id = AccountId::default();
+    let editors = RelationTarget::Account {
+        account_id: account_id.clone(),
+        relation: AccountRelation::Editor,
+    };
+    store.create_relation(&editors, &subject).await.unwrap();
+
+    let reqs: Vec<PermissionReq> = ACCOUNT_PERMISSIONS
+        .into_iter()
+        .map(|permission| PermissionReq::account(account_id.clone(), permission))
+        .chain(InstancePermission::ALL.map(PermissionReq::instance))
+        .collect();
+    let batch = store.check_batch(&subject, &reqs).await.unwrap();
+    let mut single = Vec::new();
+    for req in &reqs {
+        single.push(store.check(&subject, req).await.unwrap());
+    }
+
+    assert_eq!(batch, single);
+    assert_eq!(batch.iter().filter(|allowed| **allowed).count(), 2);
+    assert!(store.check_batch(&subject, &[]).await.unwrap().is_empty());
+    store.delete_relation(&editors, &subject).await.unwrap();
+}
+
+async fn writes_are_idempotent<P: PermissionChecker + PermissionWriter>(store: &P) {
+    kernel::ensure_generator_initialized();
+    let subject = AuthAccountId::default();
+    let account_id = AccountId::default();
+    let target = RelationTarget::Account {
+        account_id: account_id.clone(),
+        relation: AccountRelation::Owner,
+    };
+    let grant = RoleGrant {
+        role_id: CustomRoleId::default(),
+        permission: InstancePermission::ManageReports,
+    };
+
+    store.create_relation(&target, &subject).await.unwrap();
+    store.create_relation(&target, &subject).await.unwrap();
+    store.create_role_grant(&grant).await.unwrap();
+    store.create_role_grant(&grant).await.unwrap();
+    assert_eq!(
+        store.list_account_members(&account_id).await.unwrap().len(),
+        1
+    );
+
+    store.delete_relation(&target, &subject).await.unwrap();
+    store.delete_relation(&target, &subject).await.unwrap();
+    store.delete_role_grant(&grant).await.unwrap();
+    store.delete_role_grant(&grant).await.unwrap();
+    assert!(!allowed(store, &subject, PermissionReq::account(account_id, "view")).await);
+}
+
+async fn administrate_cannot_be_granted<P: PermissionWriter>(store: &P) {
+    kernel::ensure_generator_initialized();
+    let grant = RoleGrant {
+        role_id: CustomRoleId::default(),
+        permission: InstancePermission::Administrate,
+    };
+
+    let created = store.create_role_grant(&grant).await;
+    let deleted = store.delete_role_grant(&grant).await;
+
+    assert_eq!(
+        created.unwrap_err().current_context(),
+        &KernelError::Validation
+    );
+    assert_eq!(
+        deleted.unwrap_err().current_context(),
+        &KernelError::Validation
+    );
+}
+
+/// Runs every scenario of the suite against one backend.
+macro_rules! conformance_tests {
+    ($backend:ident, [$($env:ident),*], $store:expr) => {
+        mod $backend {
+            use super::*;
+
+            #[test_with::env($($env),*)]
+            #[tokio::test]
+            async fn account_relations_grant_their_permits() {
+                super::account_relations_grant_their_permits(&$store).await;
+            }
+
+            #[test_with::env($($env),*)]
+            #[tokio::test]
+            async fn instance_roles_grant_their_permits() {
+                super::instance_roles_grant_their_permits(&$store).await;
+            }
+
+            #[test_with::env($($env),*)]
+            #[tokio::test]
+            async fn custom_role_grants_reach_members() {
+                super::custom_role_grants_reach_members(&$store).await;
+            }
+
+            #[test_with::env($($env),*)]
+            #[tokio::test]
+            async fn batch_checks_match_single_checks() {
+                super::batch_checks_match_single_checks(&$store).await;
+            }
+
+            #[test_with::env($($env),*)]
+            #[tokio::test]
+            async fn writes_are_idempotent() {
+                super::writes_are_idempotent(&$store).await;
+            }
+
+            #[test_with::env($($env),*)]
+            #[tokio::test]
+            async fn administrate_cannot_be_granted() {
+                super::administrate_cannot_be_granted(&$store).await;
+            }
+        }
+    };
+}
+
+conformance_tests!(
+    postgres,
+    [DATABASE_URL],
+    PostgresPermissionStore::new(PostgresDatabase::new().await.unwrap())
+);
+
+// Needs a Keto loaded with `ory/keto/namespaces.ts`, e.g. from compose.yml.
+conformance_tests!(
+    keto,
+    [KETO_READ_URL, KETO_WRITE_URL],
+    KetoClient::new(
+        std::env::var("KETO_READ_URL").unwrap(),
+        std::env::var("KETO_WRITE_URL").unwrap(),
+    )
+);
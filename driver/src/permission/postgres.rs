@@ -0,0 +1,410 @@
+use crate::database::PostgresDatabase;
+use crate::ConvertError;
+use error_stack::Report;
+use kernel::interfaces::database::DatabaseConnection;
+use kernel::interfaces::permission::{
+    AccountMember, AccountRelation, InstancePermission, InstanceRole, InstanceRoleHolder,
+    PermissionChecker, PermissionReq, PermissionWriter, RelationTarget, RoleGrant,
+};
+use kernel::prelude::entity::{AccountId, AuthAccountId, CustomRoleId};
+use kernel::KernelError;
+use sqlx::PgConnection;
+
+/// How many subject sets a check follows, matching Keto's default
+/// `limit.max_read_depth`.
+const MAX_READ_DEPTH: i32 = 5;
+
+/// Stores relation tuples in Postgres and evaluates the rules of
+/// `ory/keto/namespaces.ts` locally, for deployments without Keto.
+#[derive(Clone)]
+pub struct PostgresPermissionStore {
+    database: PostgresDatabase,
+}
+
+impl PostgresPermissionStore {
+    pub fn new(database: PostgresDatabase) -> Self {
+        Self { database }
+    }
+}
+
+/// The relations whose holders are granted `req`, as declared by the
+/// `permits` of `ory/keto/namespaces.ts`. Anything that is not a permit is
+/// checked as a plain relation, as Keto does.
+fn granting_relations(req: &PermissionReq) -> Vec<&'static str> {
+    match req {
+        PermissionReq::Account { permission, .. } => match *permission {
+            "view" => vec![
+                AccountRelation::Owner.as_str(),
+                AccountRelation::Editor.as_str(),
+                AccountRelation::Signer.as_str(),
+            ],
+            "edit" => vec![
+                AccountRelation::Owner.as_str(),
+                AccountRelation::Editor.as_str(),
+            ],
+            "sign" => vec![
+                AccountRelation::Owner.as_str(),
+                AccountRelation::Signer.as_str(),
+            ],
+            "deactivate" | "share" => vec![AccountRelation::Owner.as_str()],
+            relation => vec![relation],
+        },
+        PermissionReq::Instance { permission } => {
+            let mut relations = match permission {
+                InstancePermission::Moderate
+                | InstancePermission::ManageReports
+                | InstancePermission::ManageDomainBlocks
+                | InstancePermission::ManageMedia => vec![
+                    InstanceRole::Admin.as_str(),
+                    InstanceRole::Moderator.as_str(),
+                ],
+                InstancePermission::Administrate
+                | InstancePermission::ViewAuditLog
+                | InstancePermission::ManageRoles => vec![InstanceRole::Admin.as_str()],
+            };
+            relations.extend(permission.grant_relation());
+            relations
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct SubjectRelationRow {
+    relation: String,
+    subject_id: String,
+}
+
+impl PostgresPermissionStore {
+    /// Relations on one object held directly by an auth account. Subject
+    /// sets and non-numeric subjects are skipped.
+    async fn find_subject_relations(
+        &self,
+        namespace: &str,
+        object: &str,
+        relation: Option<&str>,
+        subject: Option<&AuthAccountId>,
+    ) -> error_stack::Result<Vec<(String, AuthAccountId)>, KernelError> {
+        let mut executor = self.database.connection().await?;
+        let con: &mut PgConnection = &mut executor;
+        // language=postgresql
+        let rows = sqlx::query_as::<_, SubjectRelationRow>(
+            r#"
+            SELECT relation, subject_id
+            FROM relation_tuples
+            WHERE namespace = $1 AND object = $2
+              AND ($3::TEXT IS NULL OR relation = $3)
+              AND ($4::TEXT IS NULL OR subject_id = $4)
+              AND subject_id IS NOT NULL
+            ORDER BY created_at, subject_id
+            "#,
+        )
+        .bind(namespace)
+        .bind(object)
+        .bind(relation)
+        .bind(subject.map(|subject| subject.as_ref().to_string()))
+        .fetch_all(con)
+        .await
+        .convert_error()?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                let subject_id = row.subject_id.parse::<i64>().ok()?;
+                Some((row.relation, AuthAccountId::new(subject_id)))
+            })
+            .collect())
+    }
+
+    async fn insert_tuple(
+        &self,
+        namespace: &str,
+        object: &str,
+        relation: &str,
+        subject_id: Option<&str>,
+        subject_set: Option<&RelationTarget>,
+    ) -> error_stack::Result<(), KernelError> {
+        let mut executor = self.database.connection().await?;
+        let con: &mut PgConnection = &mut executor;
+        let subject_set_object = subject_set.map(RelationTarget::object_id);
+        // language=postgresql
+        sqlx::query(
+            r#"
+            INSERT INTO relation_tuples
+                (namespace, object, relation, subject_id,
+                 subject_set_namespace, subject_set_object, subject_set_relation)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(namespace)
+        .bind(object)
+        .bind(relation)
+        .bind(subject_id)
+        .bind(subject_set.map(RelationTarget::namespace))
+        .bind(subject_set_object)
+        .bind(subject_set.map(RelationTarget::relation_str))
+        .execute(con)
+        .await
+        .convert_error()?;
+        Ok(())
+    }
+
+    async fn delete_tuple(
+        &self,
+        namespace: &str,
+        object: &str,
+        relation: &str,
+        subject_id: Option<&str>,
+        subject_set: Option<&RelationTarget>,
+    ) -> error_stack::Result<(), KernelError> {
+        let mut executor = self.database.connection().await?;
+        let con: &mut PgConnection = &mut executor;
+        let subject_set_object = subject_set.map(RelationTarget::object_id);
+        // language=postgresql
+        sqlx::query(
+            r#"
+            DELETE FROM relation_tuples
+            WHERE namespace = $1 AND object = $2 AND relation = $3
+              AND subject_id IS NOT DISTINCT FROM $4
+              AND subject_set_namespace IS NOT DISTINCT FROM $5
+              AND subject_set_object IS NOT DISTINCT FROM $6
+              AND subject_set_relation IS NOT DISTINCT FROM $7
+            "#,
+        )
+        .bind(namespace)
+        .bind(object)
+        .bind(relation)
+        .bind(subject_id)
+        .bind(subject_set.map(RelationTarget::namespace))
+        .bind(subject_set_object)
+        .bind(subject_set.map(RelationTarget::relation_str))
+        .execute(con)
+        .await
+        .convert_error()?;
+        Ok(())
+    }
+}
+
+fn grant_relation(grant: &RoleGrant) -> error_stack::Result<&'static str, KernelError> {
+    grant.relation_str().ok_or_else(|| {
+        Report::new(KernelError::Validation).attach_printable(format!(
+            "Custom roles cannot grant {}",
+            grant.permission.as_str()
+        ))
+    })
+}
+
+impl PermissionChecker for PostgresPermissionStore {
+    async fn check(
+        &self,
+        subject: &AuthAccountId,
+        req: &PermissionReq,
+    ) -> error_stack::Result<bool, KernelError> {
+        let allowed = self.check_batch(subject, std::slice::from_ref(req)).await?;
+        Ok(allowed[0])
+    }
+
+    async fn check_batch(
+        &self,
+        subject: &AuthAccountId,
+        reqs: &[PermissionReq],
+    ) -> error_stack::Result<Vec<bool>, KernelError> {
+        if reqs.is_empty() {
+            return Ok(Vec::new());
+        }
+        // One row per request and relation granting it.
+        let mut indexes = Vec::new();
+        let mut namespaces = Vec::new();
+        let mut objects = Vec::new();
+        let mut relations = Vec::new();
+        for (index, req) in reqs.iter().enumerate() {
+            for relation in granting_relations(req) {
+                indexes.push(index as i32);
+                namespaces.push(req.namespace());
+                objects.push(req.object_id());
+                relations.push(relation);
+            }
+        }
+
+        let mut executor = self.database.connection().await?;
+        let con: &mut PgConnection = &mut executor;
+        // Walks from the subject's own tuples outwards through every
+        // subject set containing them, e.g. Role:<id>#members into
+        // Instance:singleton#<permission>_grants.
+        // language=postgresql
+        let granted: Vec<(i32,)> = sqlx::query_as(
+            r#"
+            WITH RECURSIVE reachable (namespace, object, relation, depth) AS (
+                SELECT namespace, object, relation, 1
+                FROM relation_tuples
+                WHERE subject_id = $1
+                UNION
+                SELECT t.namespace, t.object, t.relation, r.depth + 1
+                FROM relation_tuples t
+                JOIN reachable r
+                  ON t.subject_set_namespace = r.namespace
+                 AND t.subject_set_object = r.object
+                 AND t.subject_set_relation = r.relation
+                WHERE r.depth < $6
+            )
+            SELECT DISTINCT req.index
+            FROM UNNEST($2::INT[], $3::TEXT[], $4::TEXT[], $5::TEXT[])
+                AS req (index, namespace, object, relation)
+            JOIN reachable USING (namespace, object, relation)
+            "#,
+        )
+        .bind(subject.as_ref().to_string())
+        .bind(indexes)
+        .bind(namespaces)
+        .bind(objects)
+        .bind(relations)
+        .bind(MAX_READ_DEPTH)
+        .fetch_all(con)
+        .await
+        .convert_error()?;
+
+        let mut allowed = vec![false; reqs.len()];
+        for (index,) in granted {
+            allowed[index as usize] = true;
+        }
+        Ok(allowed)
+    }
+
+    async fn list_instance_roles(
+        &self,
+        subject: &AuthAccountId,
+    ) -> error_stack::Result<Vec<InstanceRole>, KernelError> {
+        let target = RelationTarget::Instance {
+            role: InstanceRole::Admin,
+        };
+        let relations: Vec<String> = self
+            .find_subject_relations(target.namespace(), &target.object_id(), None, Some(subject))
+            .await?
+            .into_iter()
+            .map(|(relation, _)| relation)
+            .collect();
+
+        Ok([InstanceRole::Admin, InstanceRole::Moderator]
+            .into_iter()
+            .filter(|role| relations.iter().any(|relation| relation == role.as_str()))
+            .collect())
+    }
+
+    async fn list_account_members(
+        &self,
+        account_id: &AccountId,
+    ) -> error_stack::Result<Vec<AccountMember>, KernelError> {
+        let target = RelationTarget::Account {
+            account_id: account_id.clone(),
+            relation: AccountRelation::Owner,
+        };
+        Ok(self
+            .find_subject_relations(target.namespace(), &target.object_id(), None, None)
+            .await?
+            .into_iter()
+            .filter_map(|(relation, auth_account_id)| {
+                Some(AccountMember {
+                    auth_account_id,
+                    relation: AccountRelation::parse(&relation)?,
+                })
+            })
+            .collect())
+    }
+
+    async fn list_instance_role_holders(
+        &self,
+    ) -> error_stack::Result<Vec<InstanceRoleHolder>, KernelError> {
+        let target = RelationTarget::Instance {
+            role: InstanceRole::Admin,
+        };
+        Ok(self
+            .find_subject_relations(target.namespace(), &target.object_id(), None, None)
+            .await?
+            .into_iter()
+            .filter_map(|(relation, auth_account_id)| {
+                Some(InstanceRoleHolder {
+                    auth_account_id,
+                    role: InstanceRole::parse(&relation)?,
+                })
+            })
+            .collect())
+    }
+
+    async fn list_custom_role_members(
+        &self,
+        role_id: &CustomRoleId,
+    ) -> error_stack::Result<Vec<AuthAccountId>, KernelError> {
+        let members = RelationTarget::CustomRole {
+            role_id: role_id.clone(),
+        };
+        Ok(self
+            .find_subject_relations(
+                members.namespace(),
+                &members.object_id(),
+                Some(members.relation_str()),
+                None,
+            )
+            .await?
+            .into_iter()
+            .map(|(_, auth_account_id)| auth_account_id)
+            .collect())
+    }
+}
+
+impl PermissionWriter for PostgresPermissionStore {
+    async fn create_relation(
+        &self,
+        target: &RelationTarget,
+        subject: &AuthAccountId,
+    ) -> error_stack::Result<(), KernelError> {
+        let subject_id = subject.as_ref().to_string();
+        self.insert_tuple(
+            target.namespace(),
+            &target.object_id(),
+            target.relation_str(),
+            Some(&subject_id),
+            None,
+        )
+        .await
+    }
+
+    async fn delete_relation(
+        &self,
+        target: &RelationTarget,
+        subject: &AuthAccountId,
+    ) -> error_stack::Result<(), KernelError> {
+        let subject_id = subject.as_ref().to_string();
+        self.delete_tuple(
+            target.namespace(),
+            &target.object_id(),
+            target.relation_str(),
+            Some(&subject_id),
+            None,
+        )
+        .await
+    }
+
+    async fn create_role_grant(&self, grant: &RoleGrant) -> error_stack::Result<(), KernelError> {
+        let relation = grant_relation(grant)?;
+        self.insert_tuple(
+            grant.namespace(),
+            &grant.object_id(),
+            relation,
+            None,
+            Some(&grant.subject_set()),
+        )
+        .await
+    }
+
+    async fn delete_role_grant(&self, grant: &RoleGrant) -> error_stack::Result<(), KernelError> {
+        let relation = grant_relation(grant)?;
+        self.delete_tuple(
+            grant.namespace(),
+            &grant.object_id(),
+            relation,
+            None,
+            Some(&grant.subject_set()),
+        )
+        .await
+    }
+}
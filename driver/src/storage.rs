@@ -1,113 +1,93 @@
-use aws_config::{BehaviorVersion, Region};
-use aws_sdk_s3::config::Credentials;
-use aws_sdk_s3::error::ProvideErrorMetadata;
-use aws_sdk_s3::primitives::ByteStream;
+mod filesystem;
+mod s3;
+mod scanner;
+
 use error_stack::Report;
-use kernel::interfaces::storage::{ImageStorage, StoredObject};
+use kernel::interfaces::storage::{ImageStorage, PresignedUpload, StoredObject};
 use kernel::KernelError;
+use std::time::Duration;
+
+pub use self::filesystem::*;
+pub use self::s3::*;
+pub use self::scanner::*;
 
+/// Image storage backend chosen by `MEDIA_STORAGE`: `s3` (default) or
+/// `filesystem` for single-node deployments without object storage.
 #[derive(Clone)]
-pub struct S3ImageStorage {
-    client: aws_sdk_s3::Client,
-    bucket: String,
-    public_base_url: String,
+pub enum ConfiguredImageStorage {
+    S3(S3ImageStorage),
+    Filesystem(FilesystemImageStorage),
 }
 
-impl S3ImageStorage {
+impl ConfiguredImageStorage {
     pub async fn from_env() -> error_stack::Result<Self, KernelError> {
-        let endpoint = env_or("S3_ENDPOINT", "http://localhost:9000");
-        let region_name = env_or("S3_REGION", "us-east-1");
-        let bucket_name = env_or("S3_BUCKET", "emumet-media");
-        let access_key = env_or("S3_ACCESS_KEY", "emumetdevelop");
-        let secret_key = env_or("S3_SECRET_KEY", "emumet-develop-secret");
-        let public_base_url = env_or(
-            "MEDIA_PUBLIC_BASE_URL",
-            "http://localhost:9000/emumet-media",
-        )
-        .trim_end_matches('/')
-        .to_string();
-        let credentials = Credentials::new(access_key, secret_key, None, None, "emumet-env");
-        let shared_config = aws_config::defaults(BehaviorVersion::latest())
-            .region(Region::new(region_name))
-            .credentials_provider(credentials)
-            .load()
-            .await;
-        let config = aws_sdk_s3::config::Builder::from(&shared_config)
-            .endpoint_url(endpoint)
-            .force_path_style(true)
-            .build();
+        match env_or("MEDIA_STORAGE", "s3").as_str() {
+            "s3" => Ok(Self::S3(S3ImageStorage::from_env().await?)),
+            "filesystem" => Ok(Self::Filesystem(FilesystemImageStorage::from_env())),
+            other => Err(Report::new(KernelError::Internal).attach_printable(format!(
+                "Unknown MEDIA_STORAGE {other:?}, expected \"s3\" or \"filesystem\""
+            ))),
+        }
+    }
 
-        Ok(Self {
-            client: aws_sdk_s3::Client::from_conf(config),
-            bucket: bucket_name,
-            public_base_url,
-        })
+    pub fn filesystem(&self) -> Option<&FilesystemImageStorage> {
+        match self {
+            Self::Filesystem(storage) => Some(storage),
+            Self::S3(_) => None,
+        }
     }
 }
 
-impl ImageStorage for S3ImageStorage {
+impl ImageStorage for ConfiguredImageStorage {
     async fn put(
         &self,
         key: &str,
         content_type: &str,
         bytes: &[u8],
     ) -> error_stack::Result<StoredObject, KernelError> {
-        if self
-            .client
-            .head_bucket()
-            .bucket(&self.bucket)
-            .send()
-            .await
-            .is_err()
-        {
-            let create_result = self
-                .client
-                .create_bucket()
-                .bucket(&self.bucket)
-                .send()
-                .await;
-            if let Err(error) = create_result {
-                let service_error = error.into_service_error();
-                let already_exists = matches!(
-                    service_error.code(),
-                    Some("BucketAlreadyOwnedByYou") | Some("BucketAlreadyExists")
-                );
-                if !already_exists {
-                    return Err(Report::new(KernelError::Internal)
-                        .attach_printable(format!("Failed to create S3 bucket: {service_error}")));
-                }
+        match self {
+            Self::S3(storage) => storage.put(key, content_type, bytes).await,
+            Self::Filesystem(storage) => storage.put(key, content_type, bytes).await,
+        }
+    }
+
+    async fn presign_upload(
+        &self,
+        key: &str,
+        content_type: &str,
+        byte_size: u64,
+        expires_in: Duration,
+    ) -> error_stack::Result<PresignedUpload, KernelError> {
+        match self {
+            Self::S3(storage) => {
+                storage
+                    .presign_upload(key, content_type, byte_size, expires_in)
+                    .await
+            }
+            Self::Filesystem(storage) => {
+                storage
+                    .presign_upload(key, content_type, byte_size, expires_in)
+                    .await
             }
-            let policy = format!(
-                r#"{{"Version":"2012-10-17","Statement":[{{"Effect":"Allow","Principal":{{"AWS":["*"]}},"Action":["s3:GetObject"],"Resource":["arn:aws:s3:::{bucket}/*"]}}]}}"#,
-                bucket = self.bucket
-            );
-            self.client
-                .put_bucket_policy()
-                .bucket(&self.bucket)
-                .policy(policy)
-                .send()
-                .await
-                .map_err(|error| {
-                    Report::new(KernelError::Internal)
-                        .attach_printable(format!("Failed to set S3 bucket policy: {error}"))
-                })?;
         }
-        self.client
-            .put_object()
-            .bucket(&self.bucket)
-            .key(key)
-            .content_type(content_type)
-            .body(ByteStream::from(bytes.to_vec()))
-            .send()
-            .await
-            .map_err(|error| {
-                Report::new(KernelError::Internal)
-                    .attach_printable(format!("Failed to upload image to S3: {error}"))
-            })?;
-        Ok(StoredObject {
-            key: key.to_string(),
-            url: format!("{}/{}", self.public_base_url, key.trim_start_matches('/')),
-        })
+    }
+
+    async fn fetch(
+        &self,
+        key: &str,
+        max_bytes: usize,
+    ) -> error_stack::Result<Option<Vec<u8>>, KernelError> {
+        match self {
+            Self::S3(storage) => storage.fetch(key, max_bytes).await,
+            Self::Filesystem(storage) => storage.fetch(key, max_bytes).await,
+        }
+    }
+
+    async fn delete(&self, url: &str) -> error_stack::Result<(), KernelError> {
+        match self {
+            Self::S3(storage) => storage.delete(url).await,
+            Self::Filesystem(storage) => storage.delete(url).await,
+        }
     }
 }
 
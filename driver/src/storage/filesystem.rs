@@ -0,0 +1,238 @@
+use super::env_or;
+use error_stack::Report;
+use kernel::interfaces::storage::{ImageStorage, PresignedUpload, StoredObject};
+use kernel::KernelError;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Stores objects as files under `root`, keyed by their relative path. The
+/// server can serve them itself (`GET /media/...`) or leave that to a
+/// reverse proxy pointed at the same directory.
+#[derive(Clone)]
+pub struct FilesystemImageStorage {
+    root: PathBuf,
+    public_base_url: String,
+}
+
+impl FilesystemImageStorage {
+    pub fn new(root: impl Into<PathBuf>, public_base_url: &str) -> Self {
+        Self {
+            root: root.into(),
+            public_base_url: public_base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// `MEDIA_ROOT_DIR` (default `./media`) and `MEDIA_PUBLIC_BASE_URL`
+    /// (default `{PUBLIC_BASE_URL}/media`, the server's own route).
+    pub fn from_env() -> Self {
+        let public_base_url = dotenvy::var("MEDIA_PUBLIC_BASE_URL").unwrap_or_else(|_| {
+            format!(
+                "{}/media",
+                env_or("PUBLIC_BASE_URL", "http://localhost:8080").trim_end_matches('/')
+            )
+        });
+        Self::new(env_or("MEDIA_ROOT_DIR", "./media"), &public_base_url)
+    }
+
+    /// Contents of the object stored under `key`, or `None` when there is
+    /// none or the key could point outside the root.
+    pub async fn read(&self, key: &str) -> error_stack::Result<Option<Vec<u8>>, KernelError> {
+        let Some(path) = self.path_of(key) else {
+            return Ok(None);
+        };
+        match tokio::fs::read(&path).await {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(Report::new(KernelError::Internal)
+                .attach_printable(format!("Failed to read {}: {error}", path.display()))),
+        }
+    }
+
+    /// Only plain relative paths made of `[A-Za-z0-9._-]` segments map to a
+    /// file, so no key can escape the root.
+    fn path_of(&self, key: &str) -> Option<PathBuf> {
+        let mut path = self.root.clone();
+        for segment in key.split('/') {
+            let valid = !segment.is_empty()
+                && segment != "."
+                && segment != ".."
+                && segment
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'));
+            if !valid {
+                return None;
+            }
+            path.push(segment);
+        }
+        Some(path)
+    }
+}
+
+impl ImageStorage for FilesystemImageStorage {
+    async fn put(
+        &self,
+        key: &str,
+        _content_type: &str,
+        bytes: &[u8],
+    ) -> error_stack::Result<StoredObject, KernelError> {
+        let path = self.path_of(key).ok_or_else(|| {
+            Report::new(KernelError::Internal)
+                .attach_printable(format!("Invalid media storage key: {key}"))
+        })?;
+        let write_error = |error: std::io::Error| {
+            Report::new(KernelError::Internal)
+                .attach_printable(format!("Failed to write {}: {error}", path.display()))
+        };
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(write_error)?;
+        }
+        // Readers never see a partially written file.
+        let partial = partial_path(&path);
+        tokio::fs::write(&partial, bytes)
+            .await
+            .map_err(write_error)?;
+        tokio::fs::rename(&partial, &path)
+            .await
+            .map_err(write_error)?;
+        Ok(StoredObject {
+            key: key.to_string(),
+            url: format!("{}/{}", self.public_base_url, key),
+        })
+    }
+
+    async fn presign_upload(
+        &self,
+        _key: &str,
+        _content_type: &str,
+        _byte_size: u64,
+        _expires_in: Duration,
+    ) -> error_stack::Result<PresignedUpload, KernelError> {
+        Err(Report::new(KernelError::Rejected)
+            .attach_printable("Direct uploads require the s3 media storage"))
+    }
+
+    async fn fetch(
+        &self,
+        key: &str,
+        max_bytes: usize,
+    ) -> error_stack::Result<Option<Vec<u8>>, KernelError> {
+        let Some(path) = self.path_of(key) else {
+            return Ok(None);
+        };
+        match tokio::fs::metadata(&path).await {
+            Ok(metadata) if metadata.len() > max_bytes as u64 => {
+                return Err(Report::new(KernelError::Rejected).attach_printable(format!(
+                    "Object is {} bytes, the limit is {max_bytes}",
+                    metadata.len()
+                )));
+            }
+            Ok(_) => {}
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(error) => {
+                return Err(Report::new(KernelError::Internal)
+                    .attach_printable(format!("Failed to read {}: {error}", path.display())))
+            }
+        }
+        self.read(key).await
+    }
+
+    async fn delete(&self, url: &str) -> error_stack::Result<(), KernelError> {
+        let Some(path) = url
+            .strip_prefix(&self.public_base_url)
+            .and_then(|path| path.strip_prefix('/'))
+            .and_then(|key| self.path_of(key))
+        else {
+            return Ok(());
+        };
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(()),
+            Err(error) => Err(Report::new(KernelError::Internal)
+                .attach_printable(format!("Failed to delete {}: {error}", path.display()))),
+        }
+    }
+}
+
+fn partial_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".partial");
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn stores_reads_and_deletes_objects() {
+        let root = tempfile::tempdir().unwrap();
+        let storage = FilesystemImageStorage::new(root.path(), "https://example.com/media/");
+
+        let stored = storage
+            .put("images/1/avatar.webp", "image/webp", b"pixels")
+            .await
+            .unwrap();
+        assert_eq!(stored.url, "https://example.com/media/images/1/avatar.webp");
+        assert_eq!(
+            std::fs::read(root.path().join("images/1/avatar.webp")).unwrap(),
+            b"pixels"
+        );
+        assert_eq!(
+            storage.read("images/1/avatar.webp").await.unwrap(),
+            Some(b"pixels".to_vec())
+        );
+
+        storage.delete(&stored.url).await.unwrap();
+        assert_eq!(storage.read("images/1/avatar.webp").await.unwrap(), None);
+        // Already gone, and URLs of other storages, are not errors.
+        storage.delete(&stored.url).await.unwrap();
+        storage
+            .delete("https://cdn.example.net/images/1.png")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn fetch_enforces_the_size_limit_and_presigning_is_rejected() {
+        let root = tempfile::tempdir().unwrap();
+        let storage = FilesystemImageStorage::new(root.path(), "http://localhost/media");
+        storage
+            .put("uploads/1/blob", "image/png", b"0123456789")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            storage.fetch("uploads/1/blob", 10).await.unwrap(),
+            Some(b"0123456789".to_vec())
+        );
+        let too_large = storage.fetch("uploads/1/blob", 9).await.unwrap_err();
+        assert!(matches!(too_large.current_context(), KernelError::Rejected));
+        assert_eq!(storage.fetch("uploads/2/blob", 10).await.unwrap(), None);
+
+        let presign = storage
+            .presign_upload("uploads/3/blob", "image/png", 10, Duration::from_secs(60))
+            .await
+            .unwrap_err();
+        assert!(matches!(presign.current_context(), KernelError::Rejected));
+    }
+
+    #[tokio::test]
+    async fn keys_cannot_escape_the_root() {
+        let root = tempfile::tempdir().unwrap();
+        let storage = FilesystemImageStorage::new(root.path().join("media"), "http://localhost");
+
+        for key in [
+            "../secret",
+            "images/../../secret",
+            "/etc/passwd",
+            "a//b",
+            "a\\b",
+        ] {
+            assert!(storage.put(key, "image/png", b"x").await.is_err(), "{key}");
+            assert_eq!(storage.read(key).await.unwrap(), None, "{key}");
+        }
+    }
+}
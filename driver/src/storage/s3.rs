@@ -0,0 +1,220 @@
+use super::env_or;
+use aws_config::{BehaviorVersion, Region};
+use aws_sdk_s3::config::Credentials;
+use aws_sdk_s3::error::ProvideErrorMetadata;
+use aws_sdk_s3::presigning::PresigningConfig;
+use aws_sdk_s3::primitives::ByteStream;
+use error_stack::Report;
+use kernel::interfaces::storage::{ImageStorage, PresignedUpload, StoredObject};
+use kernel::KernelError;
+use std::time::Duration;
+
+#[derive(Clone)]
+pub struct S3ImageStorage {
+    client: aws_sdk_s3::Client,
+    bucket: String,
+    public_base_url: String,
+}
+
+impl S3ImageStorage {
+    pub async fn from_env() -> error_stack::Result<Self, KernelError> {
+        let endpoint = env_or("S3_ENDPOINT", "http://localhost:9000");
+        let region_name = env_or("S3_REGION", "us-east-1");
+        let bucket_name = env_or("S3_BUCKET", "emumet-media");
+        let access_key = env_or("S3_ACCESS_KEY", "emumetdevelop");
+        let secret_key = env_or("S3_SECRET_KEY", "emumet-develop-secret");
+        let public_base_url = env_or(
+            "MEDIA_PUBLIC_BASE_URL",
+            "http://localhost:9000/emumet-media",
+        )
+        .trim_end_matches('/')
+        .to_string();
+        let credentials = Credentials::new(access_key, secret_key, None, None, "emumet-env");
+        let shared_config = aws_config::defaults(BehaviorVersion::latest())
+            .region(Region::new(region_name))
+            .credentials_provider(credentials)
+            .load()
+            .await;
+        let config = aws_sdk_s3::config::Builder::from(&shared_config)
+            .endpoint_url(endpoint)
+            .force_path_style(true)
+            .build();
+
+        Ok(Self {
+            client: aws_sdk_s3::Client::from_conf(config),
+            bucket: bucket_name,
+            public_base_url,
+        })
+    }
+
+    fn object(&self, key: &str) -> StoredObject {
+        StoredObject {
+            key: key.to_string(),
+            url: format!("{}/{}", self.public_base_url, key.trim_start_matches('/')),
+        }
+    }
+
+    /// Create the bucket with a public-read policy on first use.
+    async fn ensure_bucket(&self) -> error_stack::Result<(), KernelError> {
+        if self
+            .client
+            .head_bucket()
+            .bucket(&self.bucket)
+            .send()
+            .await
+            .is_err()
+        {
+            let create_result = self
+                .client
+                .create_bucket()
+                .bucket(&self.bucket)
+                .send()
+                .await;
+            if let Err(error) = create_result {
+                let service_error = error.into_service_error();
+                let already_exists = matches!(
+                    service_error.code(),
+                    Some("BucketAlreadyOwnedByYou") | Some("BucketAlreadyExists")
+                );
+                if !already_exists {
+                    return Err(Report::new(KernelError::Internal)
+                        .attach_printable(format!("Failed to create S3 bucket: {service_error}")));
+                }
+            }
+            let policy = format!(
+                r#"{{"Version":"2012-10-17","Statement":[{{"Effect":"Allow","Principal":{{"AWS":["*"]}},"Action":["s3:GetObject"],"Resource":["arn:aws:s3:::{bucket}/*"]}}]}}"#,
+                bucket = self.bucket
+            );
+            self.client
+                .put_bucket_policy()
+                .bucket(&self.bucket)
+                .policy(policy)
+                .send()
+                .await
+                .map_err(|error| {
+                    Report::new(KernelError::Internal)
+                        .attach_printable(format!("Failed to set S3 bucket policy: {error}"))
+                })?;
+        }
+        Ok(())
+    }
+}
+
+impl ImageStorage for S3ImageStorage {
+    async fn put(
+        &self,
+        key: &str,
+        content_type: &str,
+        bytes: &[u8],
+    ) -> error_stack::Result<StoredObject, KernelError> {
+        self.ensure_bucket().await?;
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .content_type(content_type)
+            .body(ByteStream::from(bytes.to_vec()))
+            .send()
+            .await
+            .map_err(|error| {
+                Report::new(KernelError::Internal)
+                    .attach_printable(format!("Failed to upload image to S3: {error}"))
+            })?;
+        Ok(self.object(key))
+    }
+
+    async fn presign_upload(
+        &self,
+        key: &str,
+        content_type: &str,
+        byte_size: u64,
+        expires_in: Duration,
+    ) -> error_stack::Result<PresignedUpload, KernelError> {
+        self.ensure_bucket().await?;
+        let config = PresigningConfig::expires_in(expires_in).map_err(|error| {
+            Report::new(KernelError::Internal)
+                .attach_printable(format!("Invalid presigning expiry: {error}"))
+        })?;
+        // Content type and length are signed, so the client cannot upload
+        // something other than what the session was opened for.
+        let request = self
+            .client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .content_type(content_type)
+            .content_length(byte_size as i64)
+            .presigned(config)
+            .await
+            .map_err(|error| {
+                Report::new(KernelError::Internal)
+                    .attach_printable(format!("Failed to presign S3 upload: {error}"))
+            })?;
+        Ok(PresignedUpload {
+            object: self.object(key),
+            upload_url: request.uri().to_string(),
+            method: request.method().to_string(),
+            headers: request
+                .headers()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+        })
+    }
+
+    async fn fetch(
+        &self,
+        key: &str,
+        max_bytes: usize,
+    ) -> error_stack::Result<Option<Vec<u8>>, KernelError> {
+        let output = match self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+        {
+            Ok(output) => output,
+            Err(error) => {
+                let service_error = error.into_service_error();
+                if service_error.is_no_such_key() {
+                    return Ok(None);
+                }
+                return Err(Report::new(KernelError::Internal)
+                    .attach_printable(format!("Failed to read {key} from S3: {service_error}")));
+            }
+        };
+        let byte_size = output.content_length().unwrap_or_default();
+        if byte_size > max_bytes as i64 {
+            return Err(Report::new(KernelError::Rejected).attach_printable(format!(
+                "Object is {byte_size} bytes, the limit is {max_bytes}"
+            )));
+        }
+        let bytes = output.body.collect().await.map_err(|error| {
+            Report::new(KernelError::Internal)
+                .attach_printable(format!("Failed to read {key} from S3: {error}"))
+        })?;
+        Ok(Some(bytes.into_bytes().to_vec()))
+    }
+
+    async fn delete(&self, url: &str) -> error_stack::Result<(), KernelError> {
+        let Some(key) = url
+            .strip_prefix(&self.public_base_url)
+            .and_then(|path| path.strip_prefix('/'))
+        else {
+            return Ok(());
+        };
+        // S3 answers a delete of a missing key with success.
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|error| {
+                Report::new(KernelError::Internal)
+                    .attach_printable(format!("Failed to delete image from S3: {error}"))
+            })?;
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use crate::database::PostgresDatabase;
+use kernel::interfaces::database::DatabaseConnection;
+use kernel::interfaces::repository::{DependOnImageBlocklistRepository, ImageBlocklistRepository};
+use kernel::interfaces::storage::{ImageScanInput, ImageScanVerdict, ImageScanner};
+use kernel::prelude::entity::ImageBlocklistAction;
+use kernel::KernelError;
+
+/// Default Hamming distance at which two perceptual hashes are treated as
+/// the same picture. Re-encodes and resizes stay well below it.
+const DEFAULT_MAX_DISTANCE: u32 = 6;
+
+/// Checks uploads against the moderator-managed image blocklist in
+/// Postgres. When several entries match, rejecting wins over quarantine.
+#[derive(Clone)]
+pub struct BlocklistImageScanner {
+    database: PostgresDatabase,
+    max_distance: u32,
+}
+
+impl BlocklistImageScanner {
+    pub fn new(database: PostgresDatabase, max_distance: u32) -> Self {
+        Self {
+            database,
+            max_distance,
+        }
+    }
+
+    /// `MEDIA_BLOCKLIST_MAX_DISTANCE` (default 6 of 64 bits).
+    pub fn from_env(database: PostgresDatabase) -> Self {
+        let max_distance = dotenvy::var("MEDIA_BLOCKLIST_MAX_DISTANCE")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_MAX_DISTANCE);
+        Self::new(database, max_distance)
+    }
+}
+
+impl ImageScanner for BlocklistImageScanner {
+    async fn scan(
+        &self,
+        image: ImageScanInput<'_>,
+    ) -> error_stack::Result<ImageScanVerdict, KernelError> {
+        let mut executor = self.database.connection().await?;
+        let matches = self
+            .database
+            .image_blocklist_repository()
+            .find_matches(
+                &mut executor,
+                image.hash,
+                image.perceptual_hash,
+                self.max_distance,
+            )
+            .await?;
+        let verdict = match matches
+            .iter()
+            .find(|entry| entry.action == ImageBlocklistAction::Reject)
+            .or(matches.first())
+        {
+            None => ImageScanVerdict::Clean,
+            Some(entry) => match entry.action {
+                ImageBlocklistAction::Reject => ImageScanVerdict::Reject {
+                    reason: entry.reason.clone(),
+                },
+                ImageBlocklistAction::Quarantine => ImageScanVerdict::Quarantine {
+                    reason: entry.reason.clone(),
+                },
+            },
+        };
+        Ok(verdict)
+    }
+}
@@ -134,7 +134,7 @@ impl Actor {
     /// * `preferred_username` - Account username (used as `preferredUsername`)
     /// * `display_name`       - Optional display name (used as `name`)
     /// * `summary`            - Optional profile bio/summary
-    /// * `images`             - Optional profile icon/banner URLs
+    /// * `images`             - Optional profile icon/banner images
     /// * `public_key_pem`     - PEM-encoded public key string
     /// * `public_key_id`      - Canonical URI for the public key
     pub fn new(
@@ -142,7 +142,7 @@ impl Actor {
         preferred_username: &str,
         display_name: Option<&str>,
         summary: Option<&str>,
-        images: &ActorImages<'_>,
+        images: ActorImages,
         public_key_pem: &str,
         public_key_id: &str,
     ) -> Self {
@@ -150,7 +150,12 @@ impl Actor {
         let context = vec![
             serde_json::Value::String("https://www.w3.org/ns/activitystreams".to_string()),
             serde_json::json!({
-                "publicKey": "https://w3id.org/security/v1#publicKey"
+                "publicKey": "https://w3id.org/security/v1#publicKey",
+                "toot": "http://joinmastodon.org/ns#",
+                "focalPoint": {
+                    "@container": "@list",
+                    "@id": "toot:focalPoint"
+                }
             }),
         ];
 
@@ -162,8 +167,8 @@ impl Actor {
             preferred_username: preferred_username.to_string(),
             name: display_name.map(|s| s.to_string()),
             summary: summary.map(|s| s.to_string()),
-            icon: images.icon_url.map(ImageObject::new),
-            image: images.banner_url.map(ImageObject::new),
+            icon: images.icon,
+            image: images.banner,
             inbox: urls.inbox(),
             outbox: urls.outbox(),
             followers: urls.followers(),
@@ -177,10 +182,10 @@ impl Actor {
     }
 }
 
-#[derive(Debug, Clone, Copy, Default)]
-pub struct ActorImages<'a> {
-    pub icon_url: Option<&'a str>,
-    pub banner_url: Option<&'a str>,
+#[derive(Debug, Clone, Default)]
+pub struct ActorImages {
+    pub icon: Option<ImageObject>,
+    pub banner: Option<ImageObject>,
 }
 
 impl ImageObject {
@@ -189,6 +194,29 @@ impl ImageObject {
             type_: "Image".to_string(),
             url: Some(url.to_string()),
             media_type: None,
+            width: None,
+            height: None,
+            name: None,
+            focal_point: None,
+        }
+    }
+
+    /// An image whose format and pixel size are known.
+    pub fn sized(url: &str, media_type: &str, width: u32, height: u32) -> Self {
+        Self {
+            media_type: Some(media_type.to_string()),
+            width: Some(width),
+            height: Some(height),
+            ..Self::new(url)
+        }
+    }
+
+    /// Attach alt text and a focal point (`x`, `y`, each from -1 to 1).
+    pub fn described(self, name: Option<&str>, focal_point: Option<(f32, f32)>) -> Self {
+        Self {
+            name: name.map(str::to_string),
+            focal_point: focal_point.map(|(x, y)| [x, y]),
+            ..self
         }
     }
 }
@@ -222,6 +250,16 @@ pub struct ImageObject {
     pub url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub media_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    /// Alt text.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Mastodon's `toot:focalPoint`, `[x, y]`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub focal_point: Option<[f32; 2]>,
 }
 
 // ---------------------------------------------------------------------------
@@ -376,7 +414,7 @@ mod tests {
             "alice",
             Some("Alice"),
             Some("Hello, I'm Alice!"),
-            &ActorImages::default(),
+            ActorImages::default(),
             "-----BEGIN PUBLIC KEY-----\nMIIBIjANBgkqhkiG9w0BAQEFAAOCAQ8AMIIBCgKCAQEA...\n-----END PUBLIC KEY-----",
             "https://example.com/accounts/abc123#main-key",
         )
@@ -479,7 +517,7 @@ mod tests {
             "bob",
             None, // display_name
             None, // summary
-            &ActorImages::default(),
+            ActorImages::default(),
             "pem-content",
             "https://example.com/accounts/xyz789#main-key",
         );
@@ -499,9 +537,17 @@ mod tests {
             "alice",
             Some("Alice"),
             None,
-            &ActorImages {
-                icon_url: Some("https://media.example.com/avatar.png"),
-                banner_url: Some("https://media.example.com/banner.png"),
+            ActorImages {
+                icon: Some(
+                    ImageObject::sized(
+                        "https://media.example.com/avatar.webp",
+                        "image/webp",
+                        400,
+                        400,
+                    )
+                    .described(Some("A red fox"), Some((0.5, 0.0))),
+                ),
+                banner: Some(ImageObject::new("https://media.example.com/banner.png")),
             },
             "pem-content",
             "https://example.com/ap/accounts/abc123#main-key",
@@ -511,8 +557,15 @@ mod tests {
         let json = serde_json::to_value(actor).unwrap();
 
         // Then
-        assert_eq!(json["icon"]["url"], "https://media.example.com/avatar.png");
+        assert_eq!(json["icon"]["url"], "https://media.example.com/avatar.webp");
+        assert_eq!(json["icon"]["mediaType"], "image/webp");
+        assert_eq!(json["icon"]["width"], 400);
+        assert_eq!(json["icon"]["height"], 400);
+        assert_eq!(json["icon"]["name"], "A red fox");
+        assert_eq!(json["icon"]["focalPoint"], serde_json::json!([0.5, 0.0]));
         assert_eq!(json["image"]["url"], "https://media.example.com/banner.png");
+        assert!(json["image"].get("width").is_none());
+        assert!(json["image"].get("name").is_none());
     }
 
     #[test]
@@ -723,6 +776,10 @@ mod tests {
             type_: "Image".to_string(),
             url: Some("https://example.com/media/avatar.png".to_string()),
             media_type: Some("image/png".to_string()),
+            width: Some(400),
+            height: Some(300),
+            name: Some("A red fox".to_string()),
+            focal_point: Some([0.5, -0.25]),
         };
 
         let json = serde_json::to_value(&image).unwrap();
@@ -731,6 +788,10 @@ mod tests {
         assert_eq!(map["type"], "Image");
         assert_eq!(map["url"], "https://example.com/media/avatar.png");
         assert_eq!(map["mediaType"], "image/png");
+        assert_eq!(map["width"], 400);
+        assert_eq!(map["height"], 300);
+        assert_eq!(map["name"], "A red fox");
+        assert_eq!(map["focalPoint"], serde_json::json!([0.5, -0.25]));
     }
 
     #[test]
@@ -739,6 +800,10 @@ mod tests {
             type_: "Image".to_string(),
             url: None,
             media_type: None,
+            width: None,
+            height: None,
+            name: None,
+            focal_point: None,
         };
 
         let json = serde_json::to_value(&image).unwrap();
@@ -746,6 +811,10 @@ mod tests {
 
         assert!(!map.contains_key("url"));
         assert!(!map.contains_key("mediaType"));
+        assert!(!map.contains_key("width"));
+        assert!(!map.contains_key("height"));
+        assert!(!map.contains_key("name"));
+        assert!(!map.contains_key("focalPoint"));
     }
 
     // -----------------------------------------------------------------------
@@ -794,7 +863,7 @@ mod tests {
             "test",
             None,
             None,
-            &ActorImages::default(),
+            ActorImages::default(),
             "pem",
             "https://example.com/ap/accounts/abc123#main-key",
         );
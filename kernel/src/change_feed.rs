@@ -0,0 +1,85 @@
+use crate::KernelError;
+use serde::Serialize;
+use std::future::Future;
+
+/// Version of the change feed envelope shape. Bump only on breaking changes;
+/// consumers must ignore fields they do not know.
+pub const CHANGE_FEED_SCHEMA_VERSION: u32 = 1;
+
+/// Stable, versioned envelope published to downstream consumers (CDC stream).
+///
+/// The envelope and its `data` payload are a public contract, decoupled from
+/// the internal serde shape of the stored events. Delivery is at-least-once:
+/// consumers deduplicate by `event_id`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChangeEnvelope {
+    pub schema_version: u32,
+    /// `{aggregate_type}:{aggregate_id}:{aggregate_version}`; unique per event.
+    pub event_id: String,
+    /// Dotted event name, e.g. `account.suspended`.
+    pub event_type: String,
+    pub aggregate_type: String,
+    pub aggregate_id: String,
+    pub aggregate_version: i64,
+    /// Global event log position. Ordered within one aggregate type only.
+    pub seq: i64,
+    pub data: serde_json::Value,
+}
+
+impl ChangeEnvelope {
+    pub fn new(
+        aggregate_type: &str,
+        aggregate_id: i64,
+        aggregate_version: i64,
+        seq: i64,
+        event_type: &str,
+        data: serde_json::Value,
+    ) -> Self {
+        Self {
+            schema_version: CHANGE_FEED_SCHEMA_VERSION,
+            event_id: format!("{aggregate_type}:{aggregate_id}:{aggregate_version}"),
+            event_type: event_type.to_string(),
+            aggregate_type: aggregate_type.to_string(),
+            aggregate_id: aggregate_id.to_string(),
+            aggregate_version,
+            seq,
+            data,
+        }
+    }
+}
+
+/// Sink for change feed envelopes (e.g. Redis Streams).
+pub trait ChangeFeedPublisher: Send + Sync + 'static {
+    /// Publish the envelopes in order. Either the whole batch is accepted or an
+    /// error is returned, in which case the caller retries the batch later.
+    fn publish(
+        &self,
+        envelopes: &[ChangeEnvelope],
+    ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send;
+}
+
+pub trait DependOnChangeFeedPublisher: Send + Sync {
+    type ChangeFeedPublisher: ChangeFeedPublisher;
+    fn change_feed_publisher(&self) -> &Self::ChangeFeedPublisher;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn envelope_event_id_is_stable_per_aggregate_version() {
+        let envelope = ChangeEnvelope::new(
+            "account",
+            42,
+            3,
+            1001,
+            "account.suspended",
+            serde_json::json!({}),
+        );
+
+        assert_eq!(envelope.event_id, "account:42:3");
+        assert_eq!(envelope.aggregate_id, "42");
+        assert_eq!(envelope.schema_version, CHANGE_FEED_SCHEMA_VERSION);
+    }
+}
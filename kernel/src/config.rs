@@ -15,3 +15,13 @@ impl PublicBaseUrl {
 pub trait DependOnPublicBaseUrl: Send + Sync {
     fn public_base_url(&self) -> &PublicBaseUrl;
 }
+
+/// Per-auth-account limit on stored media, originals plus renditions.
+#[derive(Debug, Clone, Copy)]
+pub struct MediaQuota {
+    pub max_bytes: u64,
+}
+
+pub trait DependOnMediaQuota: Send + Sync {
+    fn media_quota(&self) -> &MediaQuota;
+}
@@ -4,14 +4,20 @@ mod auth_account;
 mod auth_host;
 mod block;
 mod common;
+mod custom_role;
 mod event;
 mod follow;
 mod image;
+mod image_blocklist;
+mod instance_role_change;
 mod metadata;
 mod mute;
+mod personal_access_token;
 mod profile;
 mod remote_account;
 mod signing_key;
+mod upload_session;
+mod webhook;
 
 pub use self::account::*;
 pub use self::activitypub::*;
@@ -19,11 +25,17 @@ pub use self::auth_account::*;
 pub use self::auth_host::*;
 pub use self::block::*;
 pub use self::common::*;
+pub use self::custom_role::*;
 pub use self::event::*;
 pub use self::follow::*;
 pub use self::image::*;
+pub use self::image_blocklist::*;
+pub use self::instance_role_change::*;
 pub use self::metadata::*;
 pub use self::mute::*;
+pub use self::personal_access_token::*;
 pub use self::profile::*;
 pub use self::remote_account::*;
 pub use self::signing_key::*;
+pub use self::upload_session::*;
+pub use self::webhook::*;
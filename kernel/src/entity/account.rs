@@ -52,7 +52,6 @@ pub enum AccountEvent {
     Updated {
         is_bot: AccountIsBot,
     },
-    #[serde(alias = "deleted")]
     Deactivated,
     Suspended {
         reason: String,
@@ -0,0 +1,55 @@
+mod id;
+
+pub use self::id::*;
+
+use crate::permission::InstancePermission;
+use crate::KernelError;
+use error_stack::Report;
+use time::OffsetDateTime;
+
+/// Admin-defined bundle of instance permissions. Membership and the
+/// permissions themselves are relation tuples in the permission backend;
+/// this keeps the name and the list of bundled permissions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CustomRole {
+    pub id: CustomRoleId,
+    pub name: String,
+    pub permissions: Vec<InstancePermission>,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+impl CustomRole {
+    pub const MAX_NAME_LENGTH: usize = 64;
+
+    pub fn validate_name(name: &str) -> error_stack::Result<(), KernelError> {
+        if name.trim().is_empty() || name.chars().count() > Self::MAX_NAME_LENGTH {
+            return Err(
+                Report::new(KernelError::Validation).attach_printable(format!(
+                    "Role name must be 1 to {} characters",
+                    Self::MAX_NAME_LENGTH
+                )),
+            );
+        }
+        Ok(())
+    }
+
+    /// A role bundles at least one permission, and only permissions a
+    /// custom role can grant.
+    pub fn validate_permissions(
+        permissions: &[InstancePermission],
+    ) -> error_stack::Result<(), KernelError> {
+        if permissions.is_empty() {
+            return Err(Report::new(KernelError::Validation)
+                .attach_printable("Role must bundle at least one permission"));
+        }
+        if let Some(permission) = permissions
+            .iter()
+            .find(|permission| permission.grant_relation().is_none())
+        {
+            return Err(Report::new(KernelError::Validation)
+                .attach_printable(format!("Custom roles cannot grant {}", permission.as_str())));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+use vodca::{AsRefln, Fromln, Newln};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Fromln, AsRefln, Newln, Serialize, Deserialize)]
+pub struct CustomRoleId(i64);
+
+impl Default for CustomRoleId {
+    fn default() -> Self {
+        CustomRoleId(crate::generate_id())
+    }
+}
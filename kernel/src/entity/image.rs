@@ -1,16 +1,25 @@
 mod blurhash;
+mod description;
+mod focus;
 mod hash;
 mod id;
+mod perceptual_hash;
 mod url;
+mod variant;
 
+use crate::entity::CreatedAt;
 use destructure::Destructure;
 use serde::{Deserialize, Serialize};
 use vodca::{Newln, References};
 
 pub use self::blurhash::*;
+pub use self::description::*;
+pub use self::focus::*;
 pub use self::hash::*;
 pub use self::id::*;
+pub use self::perceptual_hash::*;
 pub use self::url::*;
+pub use self::variant::*;
 
 #[derive(
     Debug, Clone, Hash, Eq, PartialEq, References, Newln, Destructure, Serialize, Deserialize,
@@ -20,4 +29,30 @@ pub struct Image {
     url: ImageUrl,
     hash: ImageHash,
     blur_hash: ImageBlurHash,
+    variants: Vec<ImageVariant>,
+    /// Whether the original has more than one frame. Renditions are always
+    /// still images; animated originals also get a `Static` one.
+    animated: bool,
+    /// Alt text of the reference the image was loaded through: an
+    /// uploader's or a profile's. Identical uploads share the image, so
+    /// loading it on its own yields none.
+    description: Option<ImageDescription>,
+    /// Point to keep in frame when the image is cropped; per reference
+    /// like `description`.
+    focus: Option<ImageFocus>,
+    /// Absent for images stored before perceptual hashing was added.
+    perceptual_hash: Option<ImagePerceptualHash>,
+    /// Matched a quarantine entry of the image blocklist: kept for
+    /// moderator review and refused as a profile icon or banner.
+    quarantined: bool,
+    /// Stored bytes of the original and its renditions, counted against
+    /// the quota of every auth account owning the image.
+    byte_size: u64,
+    created_at: CreatedAt<Image>,
+}
+
+impl Image {
+    pub fn variant(&self, kind: ImageVariantKind) -> Option<&ImageVariant> {
+        self.variants.iter().find(|variant| *variant.kind() == kind)
+    }
 }
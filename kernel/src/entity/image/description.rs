@@ -0,0 +1,44 @@
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+use vodca::{AsRefln, Fromln, Newln};
+
+use crate::KernelError;
+
+/// Alt text read out by screen readers in place of the image.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Fromln, AsRefln, Newln, Serialize, Deserialize)]
+pub struct ImageDescription(String);
+
+impl ImageDescription {
+    pub const MAX_LENGTH: usize = 1500;
+
+    pub fn validate(&self) -> error_stack::Result<(), KernelError> {
+        if self.0.chars().count() > Self::MAX_LENGTH {
+            return Err(
+                Report::new(KernelError::Validation).attach_printable(format!(
+                    "Image description must not exceed {} characters",
+                    Self::MAX_LENGTH
+                )),
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_description_at_limit_multibyte_chars() {
+        let description = ImageDescription::new("あ".repeat(ImageDescription::MAX_LENGTH));
+        assert!(description.validate().is_ok());
+    }
+
+    #[test]
+    fn test_description_over_limit() {
+        let description = ImageDescription::new("a".repeat(ImageDescription::MAX_LENGTH + 1));
+        let result = description.validate();
+        assert!(result.is_err());
+        assert!(format!("{:?}", result).contains("must not exceed 1500 characters"));
+    }
+}
@@ -0,0 +1,59 @@
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+use std::hash::{Hash, Hasher};
+use vodca::References;
+
+use crate::KernelError;
+
+/// The point cropped views of the image should keep in frame, in the
+/// coordinates Mastodon's `focalPoint` uses: `x` runs from -1 (left edge)
+/// to 1 (right edge), `y` from -1 (bottom edge) to 1 (top edge), and
+/// `(0, 0)` is the centre.
+#[derive(Debug, Clone, Copy, PartialEq, References, Serialize, Deserialize)]
+pub struct ImageFocus {
+    x: f32,
+    y: f32,
+}
+
+impl ImageFocus {
+    pub fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+
+    pub fn validate(&self) -> error_stack::Result<(), KernelError> {
+        let in_range = |value: f32| (-1.0..=1.0).contains(&value);
+        if !in_range(self.x) || !in_range(self.y) {
+            return Err(Report::new(KernelError::Validation)
+                .attach_printable("Focal point coordinates must be between -1 and 1"));
+        }
+        Ok(())
+    }
+}
+
+// Validated coordinates are never NaN, so bitwise equality is total.
+impl Eq for ImageFocus {}
+
+impl Hash for ImageFocus {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.x.to_bits().hash(state);
+        self.y.to_bits().hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_focus_within_range() {
+        assert!(ImageFocus::new(0.0, 0.0).validate().is_ok());
+        assert!(ImageFocus::new(-1.0, 1.0).validate().is_ok());
+    }
+
+    #[test]
+    fn test_focus_out_of_range() {
+        assert!(ImageFocus::new(1.01, 0.0).validate().is_err());
+        assert!(ImageFocus::new(0.0, -1.5).validate().is_err());
+        assert!(ImageFocus::new(f32::NAN, 0.0).validate().is_err());
+    }
+}
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use vodca::{AsRefln, Fromln, Newln};
+
+/// 64-bit difference hash of the image's pixels. Unlike [`super::ImageHash`]
+/// it survives re-encoding and resizing: near-identical pictures differ in
+/// only a few bits.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Fromln, AsRefln, Newln, Serialize, Deserialize,
+)]
+pub struct ImagePerceptualHash(u64);
+
+impl ImagePerceptualHash {
+    /// Number of differing bits.
+    pub fn distance(&self, other: &ImagePerceptualHash) -> u32 {
+        (self.0 ^ other.0).count_ones()
+    }
+
+    pub fn to_hex(&self) -> String {
+        format!("{:016x}", self.0)
+    }
+
+    pub fn from_hex(value: &str) -> Option<Self> {
+        if value.len() != 16 {
+            return None;
+        }
+        u64::from_str_radix(value, 16).ok().map(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_distance_counts_differing_bits() {
+        let hash = ImagePerceptualHash::new(0b1011u64);
+        assert_eq!(hash.distance(&hash), 0);
+        assert_eq!(hash.distance(&ImagePerceptualHash::new(0b0010u64)), 2);
+        assert_eq!(
+            ImagePerceptualHash::new(0u64).distance(&ImagePerceptualHash::new(u64::MAX)),
+            64
+        );
+    }
+
+    #[test]
+    fn test_hex_round_trip() {
+        let hash = ImagePerceptualHash::new(0x00ff_1234_abcd_0001u64);
+        assert_eq!(hash.to_hex(), "00ff1234abcd0001");
+        assert_eq!(ImagePerceptualHash::from_hex(&hash.to_hex()), Some(hash));
+        assert_eq!(ImagePerceptualHash::from_hex("ff"), None);
+        assert_eq!(ImagePerceptualHash::from_hex("zzzzzzzzzzzzzzzz"), None);
+    }
+}
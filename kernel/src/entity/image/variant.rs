@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+use vodca::{Newln, References};
+
+use super::ImageUrl;
+
+/// Purpose of a rendition generated from an uploaded original.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageVariantKind {
+    /// Square crop used for actor icons.
+    Avatar,
+    /// 3:1 crop used for actor headers.
+    Header,
+    /// Small preview that keeps the original aspect ratio.
+    Thumbnail,
+    /// First frame of an animated original at full size, for clients that
+    /// do not play animations. Only animated images have one.
+    Static,
+}
+
+impl ImageVariantKind {
+    pub const ALL: [ImageVariantKind; 4] = [
+        ImageVariantKind::Avatar,
+        ImageVariantKind::Header,
+        ImageVariantKind::Thumbnail,
+        ImageVariantKind::Static,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImageVariantKind::Avatar => "avatar",
+            ImageVariantKind::Header => "header",
+            ImageVariantKind::Thumbnail => "thumbnail",
+            ImageVariantKind::Static => "static",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "avatar" => Some(ImageVariantKind::Avatar),
+            "header" => Some(ImageVariantKind::Header),
+            "thumbnail" => Some(ImageVariantKind::Thumbnail),
+            "static" => Some(ImageVariantKind::Static),
+            _ => None,
+        }
+    }
+}
+
+/// A stored rendition of an [`Image`](super::Image).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, References, Newln, Serialize, Deserialize)]
+pub struct ImageVariant {
+    kind: ImageVariantKind,
+    url: ImageUrl,
+    width: u32,
+    height: u32,
+    media_type: String,
+}
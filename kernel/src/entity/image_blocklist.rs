@@ -0,0 +1,65 @@
+mod id;
+
+pub use self::id::*;
+
+use crate::entity::{AuthAccountId, ImageHash, ImagePerceptualHash};
+use error_stack::Report;
+use time::OffsetDateTime;
+
+use crate::KernelError;
+
+/// What happens to an upload that matches a blocklist entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImageBlocklistAction {
+    /// The upload fails.
+    Reject,
+    /// The upload is stored but held for moderator review.
+    Quarantine,
+}
+
+impl ImageBlocklistAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImageBlocklistAction::Reject => "reject",
+            ImageBlocklistAction::Quarantine => "quarantine",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "reject" => Some(ImageBlocklistAction::Reject),
+            "quarantine" => Some(ImageBlocklistAction::Quarantine),
+            _ => None,
+        }
+    }
+}
+
+/// A moderator-managed entry of the image blocklist. Uploads match it by
+/// exact `hash` or by a `perceptual_hash` within the scanner's distance;
+/// at least one of the two is set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageBlocklistEntry {
+    pub id: ImageBlocklistEntryId,
+    pub hash: Option<ImageHash>,
+    pub perceptual_hash: Option<ImagePerceptualHash>,
+    pub action: ImageBlocklistAction,
+    pub reason: String,
+    pub created_by: AuthAccountId,
+    pub created_at: OffsetDateTime,
+}
+
+impl ImageBlocklistEntry {
+    pub const MAX_REASON_LENGTH: usize = 500;
+
+    pub fn validate_reason(reason: &str) -> error_stack::Result<(), KernelError> {
+        if reason.trim().is_empty() || reason.chars().count() > Self::MAX_REASON_LENGTH {
+            return Err(
+                Report::new(KernelError::Validation).attach_printable(format!(
+                    "Blocklist reason must be 1 to {} characters",
+                    Self::MAX_REASON_LENGTH
+                )),
+            );
+        }
+        Ok(())
+    }
+}
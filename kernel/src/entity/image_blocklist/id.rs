@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+use vodca::{AsRefln, Fromln, Newln};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Fromln, AsRefln, Newln, Serialize, Deserialize)]
+pub struct ImageBlocklistEntryId(i64);
+
+impl Default for ImageBlocklistEntryId {
+    fn default() -> Self {
+        ImageBlocklistEntryId(crate::generate_id())
+    }
+}
@@ -0,0 +1,43 @@
+mod id;
+
+pub use self::id::*;
+
+use crate::entity::AuthAccountId;
+use crate::permission::InstanceRole;
+use time::OffsetDateTime;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InstanceRoleChangeAction {
+    Granted,
+    Revoked,
+}
+
+impl InstanceRoleChangeAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InstanceRoleChangeAction::Granted => "granted",
+            InstanceRoleChangeAction::Revoked => "revoked",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "granted" => Some(InstanceRoleChangeAction::Granted),
+            "revoked" => Some(InstanceRoleChangeAction::Revoked),
+            _ => None,
+        }
+    }
+}
+
+/// Audit record of an admin granting or revoking an instance role. The
+/// role tuples themselves live in the permission backend; this only keeps
+/// who changed what, and when.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstanceRoleChange {
+    pub id: InstanceRoleChangeId,
+    pub role: InstanceRole,
+    pub action: InstanceRoleChangeAction,
+    pub subject: AuthAccountId,
+    pub actor: AuthAccountId,
+    pub created_at: OffsetDateTime,
+}
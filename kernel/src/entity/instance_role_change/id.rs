@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+use vodca::{AsRefln, Fromln, Newln};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Fromln, AsRefln, Newln, Serialize, Deserialize)]
+pub struct InstanceRoleChangeId(i64);
+
+impl Default for InstanceRoleChangeId {
+    fn default() -> Self {
+        InstanceRoleChangeId(crate::generate_id())
+    }
+}
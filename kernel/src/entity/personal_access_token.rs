@@ -0,0 +1,40 @@
+mod id;
+
+pub use self::id::*;
+
+use crate::entity::{AccountId, AuthAccountId};
+use crate::permission::Scope;
+use time::OffsetDateTime;
+
+/// Long-lived bearer token a user creates for bots and CI. Only the SHA-256
+/// of the token is stored; the token itself is shown once, at creation.
+///
+/// Requests made with it act as `owner`, limited to `scopes` and, when set,
+/// to `account_ids`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PersonalAccessToken {
+    pub id: PersonalAccessTokenId,
+    pub owner: AuthAccountId,
+    pub name: String,
+    /// Hex-encoded SHA-256 of the token.
+    pub token_hash: String,
+    pub scopes: Vec<Scope>,
+    /// `None` allows every account the owner has a relation on.
+    pub account_ids: Option<Vec<AccountId>>,
+    pub expires_at: Option<OffsetDateTime>,
+    pub last_used_at: Option<OffsetDateTime>,
+    pub created_at: OffsetDateTime,
+}
+
+impl PersonalAccessToken {
+    /// Prefix telling personal access tokens apart from OAuth2 access tokens.
+    pub const PREFIX: &'static str = "emu_pat_";
+
+    pub fn generate_token() -> String {
+        format!("{}{}", Self::PREFIX, nanoid::nanoid!(43))
+    }
+
+    pub fn is_expired(&self, now: OffsetDateTime) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+use vodca::{AsRefln, Fromln, Newln};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Fromln, AsRefln, Newln, Serialize, Deserialize)]
+pub struct PersonalAccessTokenId(i64);
+
+impl Default for PersonalAccessTokenId {
+    fn default() -> Self {
+        PersonalAccessTokenId(crate::generate_id())
+    }
+}
@@ -7,14 +7,23 @@ pub use self::id::*;
 pub use self::url::*;
 use crate::entity::image::ImageId;
 use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
 use vodca::{Newln, References};
 
+/// A peer's actor as last resolved. `icon_id` and `banner_id` point at
+/// local copies of the images published at `icon_source_url` and
+/// `banner_source_url`; `media_fetched_at` is `None` while those copies are
+/// due to be (re)fetched.
 #[derive(Debug, Clone, Eq, PartialEq, References, Newln, Serialize, Deserialize)]
 pub struct RemoteAccount {
     id: RemoteAccountId,
     acct: RemoteAccountAcct,
     url: RemoteAccountUrl,
     icon_id: Option<ImageId>,
+    banner_id: Option<ImageId>,
+    icon_source_url: Option<String>,
+    banner_source_url: Option<String>,
     inbox_url: Option<String>,
     public_key_pem: Option<String>,
+    media_fetched_at: Option<OffsetDateTime>,
 }
@@ -0,0 +1,37 @@
+mod id;
+
+pub use self::id::*;
+
+use crate::entity::{AuthAccountId, ImageDescription, ImageFocus};
+use time::OffsetDateTime;
+
+/// A direct-to-storage upload in progress: the client PUTs the bytes to a
+/// presigned URL for `object_key`, then finalizes the session, which
+/// processes the staged object like a regular upload and removes it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadSession {
+    pub id: UploadSessionId,
+    pub owner: AuthAccountId,
+    pub object_key: String,
+    /// Public URL of the staged object, used to delete it.
+    pub object_url: String,
+    /// The presigned PUT only accepts this content type and size.
+    pub content_type: String,
+    pub byte_size: u64,
+    pub description: Option<ImageDescription>,
+    pub focus: Option<ImageFocus>,
+    pub expires_at: OffsetDateTime,
+    pub created_at: OffsetDateTime,
+}
+
+impl UploadSession {
+    /// Staging key for the session's object. Staged objects sit in the
+    /// public bucket until finalized, so the key carries a random part.
+    pub fn object_key_for(id: &UploadSessionId) -> String {
+        format!("uploads/{}/{}", id.as_ref(), nanoid::nanoid!(32))
+    }
+
+    pub fn is_expired(&self, now: OffsetDateTime) -> bool {
+        self.expires_at <= now
+    }
+}
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+use vodca::{AsRefln, Fromln, Newln};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Fromln, AsRefln, Newln, Serialize, Deserialize)]
+pub struct UploadSessionId(i64);
+
+impl Default for UploadSessionId {
+    fn default() -> Self {
+        UploadSessionId(crate::generate_id())
+    }
+}
@@ -0,0 +1,122 @@
+mod id;
+
+pub use self::id::*;
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+/// Instance-level webhook registration managed by instance admins.
+///
+/// `event_types` filters the change feed event types (`account.created`,
+/// `profile.updated`, ...). An entry ending in `.*` matches every event of
+/// that aggregate, and an empty list matches every event.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WebhookSubscription {
+    pub id: WebhookSubscriptionId,
+    pub url: String,
+    pub event_types: Vec<String>,
+    pub secret: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+impl WebhookSubscription {
+    /// Random signing secret for subscriptions registered without one.
+    pub fn generate_secret() -> String {
+        nanoid::nanoid!(40)
+    }
+
+    pub fn matches(&self, event_type: &str) -> bool {
+        self.event_types.is_empty()
+            || self
+                .event_types
+                .iter()
+                .any(|filter| match filter.strip_suffix(".*") {
+                    Some(aggregate) => event_type
+                        .strip_prefix(aggregate)
+                        .is_some_and(|rest| rest.starts_with('.')),
+                    None => filter == event_type,
+                })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookDeliveryStatus {
+    /// Waiting for its first attempt or for a retry at `next_attempt_at`.
+    Pending,
+    Delivered,
+    /// Gave up after the maximum number of attempts.
+    Failed,
+}
+
+impl WebhookDeliveryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookDeliveryStatus::Pending => "pending",
+            WebhookDeliveryStatus::Delivered => "delivered",
+            WebhookDeliveryStatus::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(WebhookDeliveryStatus::Pending),
+            "delivered" => Some(WebhookDeliveryStatus::Delivered),
+            "failed" => Some(WebhookDeliveryStatus::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// One event queued for one subscription, doubling as the delivery log entry.
+///
+/// `payload` is the exact request body, so every retry sends (and signs) the
+/// same bytes. `(subscription_id, event_id)` is unique, which makes enqueueing
+/// idempotent under the fan-out's window re-read.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WebhookDelivery {
+    pub id: WebhookDeliveryId,
+    pub subscription_id: WebhookSubscriptionId,
+    pub event_id: String,
+    pub event_type: String,
+    pub payload: String,
+    pub status: WebhookDeliveryStatus,
+    pub attempts: i32,
+    #[serde(with = "time::serde::rfc3339")]
+    pub next_attempt_at: OffsetDateTime,
+    pub last_status_code: Option<i32>,
+    pub last_error: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    pub delivered_at: Option<OffsetDateTime>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn subscription(event_types: &[&str]) -> WebhookSubscription {
+        WebhookSubscription {
+            id: WebhookSubscriptionId::new(1),
+            url: "https://hooks.example.com/emumet".to_string(),
+            event_types: event_types.iter().map(|s| s.to_string()).collect(),
+            secret: "secret".to_string(),
+            created_at: OffsetDateTime::UNIX_EPOCH,
+        }
+    }
+
+    #[test]
+    fn empty_filter_matches_every_event() {
+        assert!(subscription(&[]).matches("metadata.deleted"));
+    }
+
+    #[test]
+    fn filter_matches_exact_types_and_aggregate_wildcards() {
+        let subscription = subscription(&["account.*", "profile.updated"]);
+        assert!(subscription.matches("account.suspended"));
+        assert!(subscription.matches("profile.updated"));
+        assert!(!subscription.matches("profile.created"));
+        assert!(!subscription.matches("accounts.created"));
+    }
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use vodca::{AsRefln, Fromln, Newln};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Fromln, AsRefln, Newln, Serialize, Deserialize)]
+pub struct WebhookSubscriptionId(i64);
+
+impl Default for WebhookSubscriptionId {
+    fn default() -> Self {
+        WebhookSubscriptionId(crate::generate_id())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Fromln, AsRefln, Newln, Serialize, Deserialize)]
+pub struct WebhookDeliveryId(i64);
+
+impl Default for WebhookDeliveryId {
+    fn default() -> Self {
+        WebhookDeliveryId(crate::generate_id())
+    }
+}
@@ -1,5 +1,10 @@
+mod upcaster;
+
 use crate::entity::EventEnvelope;
 use crate::KernelError;
+use error_stack::{Report, ResultExt};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use std::fmt::{Debug, Display};
 
 pub trait EventApplier {
@@ -24,3 +29,49 @@ pub trait EventApplier {
         format!("{} not found: {}", Self::ENTITY_NAME, id)
     }
 }
+
+/// Transforms a stored payload of one schema version into the next one.
+pub type Upcaster = fn(serde_json::Value) -> serde_json::Value;
+
+/// Versioned payload shape of a persisted event.
+///
+/// Event stores write `SCHEMA_VERSION` next to every payload and pass stored
+/// payloads through [`upcast_event`] on read, so the event enum only has to
+/// deserialize its current shape.
+pub trait EventSchema: Serialize + DeserializeOwned {
+    const EVENT_NAME: &'static str;
+
+    /// Version written for new payloads. Starts at 1.
+    const SCHEMA_VERSION: i32;
+
+    /// `UPCASTERS[n]` lifts version `n + 1` to `n + 2`, so the slice holds
+    /// exactly `SCHEMA_VERSION - 1` entries.
+    const UPCASTERS: &'static [Upcaster];
+}
+
+/// Bring a payload stored at `schema_version` up to the current shape of `E`
+/// and deserialize it.
+pub fn upcast_event<E: EventSchema>(
+    data: serde_json::Value,
+    schema_version: i32,
+) -> error_stack::Result<E, KernelError> {
+    debug_assert_eq!(E::UPCASTERS.len() as i32, E::SCHEMA_VERSION - 1);
+    if !(1..=E::SCHEMA_VERSION).contains(&schema_version) {
+        return Err(Report::new(KernelError::Internal).attach_printable(format!(
+            "Unsupported {} schema version {schema_version} (current is {})",
+            E::EVENT_NAME,
+            E::SCHEMA_VERSION
+        )));
+    }
+    let data = E::UPCASTERS[(schema_version - 1) as usize..]
+        .iter()
+        .fold(data, |data, upcaster| upcaster(data));
+    serde_json::from_value(data)
+        .change_context_lazy(|| KernelError::Internal)
+        .attach_printable_lazy(|| {
+            format!(
+                "Failed to deserialize {} payload stored at schema version {schema_version}",
+                E::EVENT_NAME
+            )
+        })
+}
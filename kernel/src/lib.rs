@@ -1,4 +1,5 @@
 pub mod activitypub;
+mod change_feed;
 mod config;
 mod crypto;
 mod database;
@@ -30,6 +31,9 @@ pub mod prelude {
 
 #[cfg(feature = "interfaces")]
 pub mod interfaces {
+    pub mod change_feed {
+        pub use crate::change_feed::*;
+    }
     pub mod crypto {
         pub use crate::crypto::*;
     }
@@ -77,8 +81,12 @@ pub mod interfaces {
 /// - DependOnAuthHostRepository
 /// - DependOnFollowRepository
 /// - DependOnRemoteAccountRepository
-/// - DependOnImageRepository
+/// - DependOnImageRepository, DependOnImageBlocklistRepository
 /// - DependOnSigningKeyRepository
+/// - DependOnWebhookSubscriptionRepository, DependOnWebhookDeliveryRepository
+/// - DependOnUploadSessionRepository
+/// - DependOnPersonalAccessTokenRepository
+/// - DependOnInstanceRoleChangeRepository, DependOnCustomRoleRepository
 ///
 /// # Usage
 /// ```ignore
@@ -281,5 +289,54 @@ macro_rules! impl_database_delegation {
             }
         }
 
+        impl $crate::interfaces::repository::DependOnWebhookSubscriptionRepository for $impl_type {
+            type WebhookSubscriptionRepository = <$db_type as $crate::interfaces::repository::DependOnWebhookSubscriptionRepository>::WebhookSubscriptionRepository;
+            fn webhook_subscription_repository(&self) -> &Self::WebhookSubscriptionRepository {
+                $crate::interfaces::repository::DependOnWebhookSubscriptionRepository::webhook_subscription_repository(&self.$field)
+            }
+        }
+
+        impl $crate::interfaces::repository::DependOnWebhookDeliveryRepository for $impl_type {
+            type WebhookDeliveryRepository = <$db_type as $crate::interfaces::repository::DependOnWebhookDeliveryRepository>::WebhookDeliveryRepository;
+            fn webhook_delivery_repository(&self) -> &Self::WebhookDeliveryRepository {
+                $crate::interfaces::repository::DependOnWebhookDeliveryRepository::webhook_delivery_repository(&self.$field)
+            }
+        }
+
+        impl $crate::interfaces::repository::DependOnImageBlocklistRepository for $impl_type {
+            type ImageBlocklistRepository = <$db_type as $crate::interfaces::repository::DependOnImageBlocklistRepository>::ImageBlocklistRepository;
+            fn image_blocklist_repository(&self) -> &Self::ImageBlocklistRepository {
+                $crate::interfaces::repository::DependOnImageBlocklistRepository::image_blocklist_repository(&self.$field)
+            }
+        }
+
+        impl $crate::interfaces::repository::DependOnUploadSessionRepository for $impl_type {
+            type UploadSessionRepository = <$db_type as $crate::interfaces::repository::DependOnUploadSessionRepository>::UploadSessionRepository;
+            fn upload_session_repository(&self) -> &Self::UploadSessionRepository {
+                $crate::interfaces::repository::DependOnUploadSessionRepository::upload_session_repository(&self.$field)
+            }
+        }
+
+        impl $crate::interfaces::repository::DependOnPersonalAccessTokenRepository for $impl_type {
+            type PersonalAccessTokenRepository = <$db_type as $crate::interfaces::repository::DependOnPersonalAccessTokenRepository>::PersonalAccessTokenRepository;
+            fn personal_access_token_repository(&self) -> &Self::PersonalAccessTokenRepository {
+                $crate::interfaces::repository::DependOnPersonalAccessTokenRepository::personal_access_token_repository(&self.$field)
+            }
+        }
+
+        impl $crate::interfaces::repository::DependOnInstanceRoleChangeRepository for $impl_type {
+            type InstanceRoleChangeRepository = <$db_type as $crate::interfaces::repository::DependOnInstanceRoleChangeRepository>::InstanceRoleChangeRepository;
+            fn instance_role_change_repository(&self) -> &Self::InstanceRoleChangeRepository {
+                $crate::interfaces::repository::DependOnInstanceRoleChangeRepository::instance_role_change_repository(&self.$field)
+            }
+        }
+
+        impl $crate::interfaces::repository::DependOnCustomRoleRepository for $impl_type {
+            type CustomRoleRepository = <$db_type as $crate::interfaces::repository::DependOnCustomRoleRepository>::CustomRoleRepository;
+            fn custom_role_repository(&self) -> &Self::CustomRoleRepository {
+                $crate::interfaces::repository::DependOnCustomRoleRepository::custom_role_repository(&self.$field)
+            }
+        }
+
     };
 }
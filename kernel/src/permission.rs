@@ -1,4 +1,4 @@
-use crate::entity::{AccountId, AuthAccountId};
+use crate::entity::{AccountId, AuthAccountId, CustomRoleId};
 use crate::KernelError;
 use std::future::Future;
 use std::ops::Add;
@@ -18,6 +18,24 @@ impl AccountRelation {
             AccountRelation::Signer => "signers",
         }
     }
+
+    pub fn parse(relation: &str) -> Option<Self> {
+        [
+            AccountRelation::Owner,
+            AccountRelation::Editor,
+            AccountRelation::Signer,
+        ]
+        .into_iter()
+        .find(|candidate| candidate.as_str() == relation)
+    }
+}
+
+/// An auth account holding a relation on an account. Someone holding
+/// several relations appears once per relation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AccountMember {
+    pub auth_account_id: AuthAccountId,
+    pub relation: AccountRelation,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -33,11 +51,155 @@ impl InstanceRole {
             InstanceRole::Moderator => "moderators",
         }
     }
+
+    pub fn parse(relation: &str) -> Option<Self> {
+        [InstanceRole::Admin, InstanceRole::Moderator]
+            .into_iter()
+            .find(|candidate| candidate.as_str() == relation)
+    }
+}
+
+/// An auth account holding an instance role directly. Someone holding both
+/// roles appears once per role.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InstanceRoleHolder {
+    pub auth_account_id: AuthAccountId,
+    pub role: InstanceRole,
+}
+
+/// Instance-wide permissions, named after the `Instance` permits in
+/// `ory/keto/namespaces.ts`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InstancePermission {
+    Moderate,
+    Administrate,
+    ManageReports,
+    ManageDomainBlocks,
+    ManageMedia,
+    ViewAuditLog,
+    ManageRoles,
+}
+
+impl InstancePermission {
+    pub const ALL: [InstancePermission; 7] = [
+        InstancePermission::Moderate,
+        InstancePermission::Administrate,
+        InstancePermission::ManageReports,
+        InstancePermission::ManageDomainBlocks,
+        InstancePermission::ManageMedia,
+        InstancePermission::ViewAuditLog,
+        InstancePermission::ManageRoles,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InstancePermission::Moderate => "moderate",
+            InstancePermission::Administrate => "administrate",
+            InstancePermission::ManageReports => "manage_reports",
+            InstancePermission::ManageDomainBlocks => "manage_domain_blocks",
+            InstancePermission::ManageMedia => "manage_media",
+            InstancePermission::ViewAuditLog => "view_audit_log",
+            InstancePermission::ManageRoles => "manage_roles",
+        }
+    }
+
+    pub fn parse(permission: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|candidate| candidate.as_str() == permission)
+    }
+
+    /// The `Instance` relation through which custom roles grant this
+    /// permission to their members. `administrate` is only held by the
+    /// built-in admin role, so no custom role can bundle it.
+    pub fn grant_relation(&self) -> Option<&'static str> {
+        match self {
+            InstancePermission::Moderate => Some("moderate_grants"),
+            InstancePermission::Administrate => None,
+            InstancePermission::ManageReports => Some("manage_reports_grants"),
+            InstancePermission::ManageDomainBlocks => Some("manage_domain_blocks_grants"),
+            InstancePermission::ManageMedia => Some("manage_media_grants"),
+            InstancePermission::ViewAuditLog => Some("view_audit_log_grants"),
+            InstancePermission::ManageRoles => Some("manage_roles_grants"),
+        }
+    }
+
+    pub fn required_scope(&self) -> Scope {
+        match self {
+            InstancePermission::Moderate
+            | InstancePermission::ManageReports
+            | InstancePermission::ManageDomainBlocks
+            | InstancePermission::ManageMedia => Scope::AdminModerate,
+            InstancePermission::Administrate
+            | InstancePermission::ViewAuditLog
+            | InstancePermission::ManageRoles => Scope::AdminAdministrate,
+        }
+    }
+}
+
+/// OAuth2 scopes Emumet understands. An access token can only use a
+/// permission when it carries the matching scope, on top of the Keto
+/// relations of its subject. Scopes do not imply one another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Scope {
+    AccountsRead,
+    AccountsWrite,
+    Sign,
+    AdminModerate,
+    AdminAdministrate,
+    Sessions,
+}
+
+impl Scope {
+    pub const ALL: [Scope; 6] = [
+        Scope::AccountsRead,
+        Scope::AccountsWrite,
+        Scope::Sign,
+        Scope::AdminModerate,
+        Scope::AdminAdministrate,
+        Scope::Sessions,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Scope::AccountsRead => "accounts:read",
+            Scope::AccountsWrite => "accounts:write",
+            Scope::Sign => "sign",
+            Scope::AdminModerate => "admin:moderate",
+            Scope::AdminAdministrate => "admin:administrate",
+            Scope::Sessions => "sessions",
+        }
+    }
+
+    /// `None` for scopes Emumet does not use, such as `openid`.
+    pub fn parse(scope: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|candidate| candidate.as_str() == scope)
+    }
+
+    /// What granting the scope allows, for consent screens.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Scope::AccountsRead => "View your accounts, their followers, blocks and mutes",
+            Scope::AccountsWrite => {
+                "Create, edit and deactivate your accounts, follow, block and mute, and upload images"
+            }
+            Scope::Sign => "Sign ActivityPub requests as your accounts",
+            Scope::AdminModerate => "Moderate accounts and media on this instance",
+            Scope::AdminAdministrate => {
+                "Grant and revoke instance and custom roles, and manage webhooks"
+            }
+            Scope::Sessions => "View and sign out your sessions, and revoke apps you authorized",
+        }
+    }
 }
 
 const ACCOUNT_NAMESPACE: &str = "Account";
 const INSTANCE_NAMESPACE: &str = "Instance";
 const INSTANCE_OBJECT_ID: &str = "singleton";
+const ROLE_NAMESPACE: &str = "Role";
+const ROLE_MEMBERS_RELATION: &str = "members";
 
 #[derive(Debug, Clone)]
 pub enum PermissionReq {
@@ -46,7 +208,7 @@ pub enum PermissionReq {
         permission: &'static str,
     },
     Instance {
-        permission: &'static str,
+        permission: InstancePermission,
     },
 }
 
@@ -58,7 +220,7 @@ impl PermissionReq {
         }
     }
 
-    pub fn instance(permission: &'static str) -> Self {
+    pub fn instance(permission: InstancePermission) -> Self {
         Self::Instance { permission }
     }
 
@@ -79,7 +241,20 @@ impl PermissionReq {
     pub fn permission_name(&self) -> &'static str {
         match self {
             PermissionReq::Account { permission, .. } => permission,
-            PermissionReq::Instance { permission, .. } => permission,
+            PermissionReq::Instance { permission, .. } => permission.as_str(),
+        }
+    }
+
+    /// The scope a token needs to use this permission. Permissions without
+    /// a dedicated scope need `accounts:write`.
+    pub fn required_scope(&self) -> Scope {
+        match self {
+            PermissionReq::Account { permission, .. } => match *permission {
+                "view" => Scope::AccountsRead,
+                "sign" => Scope::Sign,
+                _ => Scope::AccountsWrite,
+            },
+            PermissionReq::Instance { permission } => permission.required_scope(),
         }
     }
 }
@@ -122,6 +297,37 @@ pub trait PermissionChecker: Send + Sync + 'static {
         subject: &AuthAccountId,
     ) -> impl Future<Output = error_stack::Result<Vec<InstanceRole>, KernelError>> + Send;
 
+    fn list_account_members(
+        &self,
+        account_id: &AccountId,
+    ) -> impl Future<Output = error_stack::Result<Vec<AccountMember>, KernelError>> + Send;
+
+    /// Every direct holder of an instance role.
+    fn list_instance_role_holders(
+        &self,
+    ) -> impl Future<Output = error_stack::Result<Vec<InstanceRoleHolder>, KernelError>> + Send;
+
+    fn list_custom_role_members(
+        &self,
+        role_id: &CustomRoleId,
+    ) -> impl Future<Output = error_stack::Result<Vec<AuthAccountId>, KernelError>> + Send;
+
+    /// One answer per entry of `reqs`, in order. Backends override this to
+    /// answer in fewer round trips than one `check` per request.
+    fn check_batch(
+        &self,
+        subject: &AuthAccountId,
+        reqs: &[PermissionReq],
+    ) -> impl Future<Output = error_stack::Result<Vec<bool>, KernelError>> + Send {
+        async move {
+            let mut allowed = Vec::with_capacity(reqs.len());
+            for req in reqs {
+                allowed.push(self.check(subject, req).await?);
+            }
+            Ok(allowed)
+        }
+    }
+
     fn satisfies(
         &self,
         subject: &AuthAccountId,
@@ -152,6 +358,10 @@ pub enum RelationTarget {
     Instance {
         role: InstanceRole,
     },
+    /// Membership of a custom role.
+    CustomRole {
+        role_id: CustomRoleId,
+    },
 }
 
 impl RelationTarget {
@@ -159,6 +369,7 @@ impl RelationTarget {
         match self {
             RelationTarget::Account { .. } => ACCOUNT_NAMESPACE,
             RelationTarget::Instance { .. } => INSTANCE_NAMESPACE,
+            RelationTarget::CustomRole { .. } => ROLE_NAMESPACE,
         }
     }
 
@@ -166,6 +377,7 @@ impl RelationTarget {
         match self {
             RelationTarget::Account { account_id, .. } => account_id.as_ref().to_string(),
             RelationTarget::Instance { .. } => INSTANCE_OBJECT_ID.to_string(),
+            RelationTarget::CustomRole { role_id } => role_id.as_ref().to_string(),
         }
     }
 
@@ -173,6 +385,36 @@ impl RelationTarget {
         match self {
             RelationTarget::Account { relation, .. } => relation.as_str(),
             RelationTarget::Instance { role, .. } => role.as_str(),
+            RelationTarget::CustomRole { .. } => ROLE_MEMBERS_RELATION,
+        }
+    }
+}
+
+/// The tuple letting the members of a custom role use an instance
+/// permission: `Instance:singleton#<grant relation>@Role:<id>#members`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoleGrant {
+    pub role_id: CustomRoleId,
+    pub permission: InstancePermission,
+}
+
+impl RoleGrant {
+    pub fn namespace(&self) -> &'static str {
+        INSTANCE_NAMESPACE
+    }
+
+    pub fn object_id(&self) -> String {
+        INSTANCE_OBJECT_ID.to_string()
+    }
+
+    /// `None` for permissions custom roles cannot bundle.
+    pub fn relation_str(&self) -> Option<&'static str> {
+        self.permission.grant_relation()
+    }
+
+    pub fn subject_set(&self) -> RelationTarget {
+        RelationTarget::CustomRole {
+            role_id: self.role_id.clone(),
         }
     }
 }
@@ -189,6 +431,16 @@ pub trait PermissionWriter: Send + Sync + 'static {
         target: &RelationTarget,
         subject: &AuthAccountId,
     ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send;
+
+    fn create_role_grant(
+        &self,
+        grant: &RoleGrant,
+    ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send;
+
+    fn delete_role_grant(
+        &self,
+        grant: &RoleGrant,
+    ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send;
 }
 
 pub trait DependOnPermissionWriter: Send + Sync {
@@ -234,6 +486,69 @@ mod tests {
                 }
             }
         }
+
+        async fn list_account_members(
+            &self,
+            _account_id: &AccountId,
+        ) -> error_stack::Result<Vec<AccountMember>, KernelError> {
+            Ok(Vec::new())
+        }
+
+        async fn list_instance_role_holders(
+            &self,
+        ) -> error_stack::Result<Vec<InstanceRoleHolder>, KernelError> {
+            Ok(Vec::new())
+        }
+
+        async fn list_custom_role_members(
+            &self,
+            _role_id: &CustomRoleId,
+        ) -> error_stack::Result<Vec<AuthAccountId>, KernelError> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[test]
+    fn permissions_map_to_scopes() {
+        let account_id = crate::entity::AccountId::new(1);
+        for (permission, scope) in [
+            ("view", Scope::AccountsRead),
+            ("edit", Scope::AccountsWrite),
+            ("deactivate", Scope::AccountsWrite),
+            ("sign", Scope::Sign),
+        ] {
+            assert_eq!(
+                PermissionReq::account(account_id.clone(), permission).required_scope(),
+                scope
+            );
+        }
+        for (permission, scope) in [
+            (InstancePermission::Moderate, Scope::AdminModerate),
+            (InstancePermission::ManageMedia, Scope::AdminModerate),
+            (InstancePermission::Administrate, Scope::AdminAdministrate),
+            (InstancePermission::ManageRoles, Scope::AdminAdministrate),
+        ] {
+            assert_eq!(PermissionReq::instance(permission).required_scope(), scope);
+        }
+        for permission in InstancePermission::ALL {
+            assert_eq!(
+                InstancePermission::parse(permission.as_str()),
+                Some(permission)
+            );
+        }
+        for scope in Scope::ALL {
+            assert_eq!(Scope::parse(scope.as_str()), Some(scope));
+        }
+        assert_eq!(Scope::parse("openid"), None);
+    }
+
+    #[test]
+    fn account_relation_parses_keto_relation_names() {
+        assert_eq!(
+            AccountRelation::parse("editors"),
+            Some(AccountRelation::Editor)
+        );
+        assert_eq!(AccountRelation::parse("editor"), None);
     }
 
     /// Vec<InstanceRole> がそのまま返る
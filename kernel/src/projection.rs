@@ -4,7 +4,10 @@ use crate::entity::{
     ProfileEvent, ProfileId,
 };
 use crate::KernelError;
+use std::collections::HashMap;
 use std::future::Future;
+use std::sync::{Arc, Mutex};
+use time::OffsetDateTime;
 
 /// One row of a transactional event log: the global `seq` plus the envelope.
 ///
@@ -31,6 +34,12 @@ pub trait AccountEventLog: Sync + Send + 'static {
         limit: i64,
     ) -> impl Future<Output = error_stack::Result<Vec<SeqEvent<AccountEvent, Account>>, KernelError>>
            + Send;
+
+    /// Highest committed `seq` in the log, 0 when the log is empty.
+    fn head_seq(
+        &self,
+        executor: &mut Self::Connection,
+    ) -> impl Future<Output = error_stack::Result<i64, KernelError>> + Send;
 }
 
 pub trait DependOnAccountEventLog: Sync + Send + DependOnDatabaseConnection {
@@ -105,6 +114,12 @@ pub trait ProfileEventLog: Sync + Send + 'static {
         limit: i64,
     ) -> impl Future<Output = error_stack::Result<Vec<SeqEvent<ProfileEvent, Profile>>, KernelError>>
            + Send;
+
+    /// Highest committed `seq` in the log, 0 when the log is empty.
+    fn head_seq(
+        &self,
+        executor: &mut Self::Connection,
+    ) -> impl Future<Output = error_stack::Result<i64, KernelError>> + Send;
 }
 
 pub trait DependOnProfileEventLog: Sync + Send + DependOnDatabaseConnection {
@@ -151,6 +166,12 @@ pub trait MetadataEventLog: Sync + Send + 'static {
         limit: i64,
     ) -> impl Future<Output = error_stack::Result<Vec<SeqEvent<MetadataEvent, Metadata>>, KernelError>>
            + Send;
+
+    /// Highest committed `seq` in the log, 0 when the log is empty.
+    fn head_seq(
+        &self,
+        executor: &mut Self::Connection,
+    ) -> impl Future<Output = error_stack::Result<i64, KernelError>> + Send;
 }
 
 pub trait DependOnMetadataEventLog: Sync + Send + DependOnDatabaseConnection {
@@ -185,3 +206,93 @@ pub trait DependOnMetadataProjectionWriter: Sync + Send + DependOnDatabaseConnec
 
     fn metadata_projection_writer(&self) -> &Self::MetadataProjectionWriter;
 }
+
+/// Outcome of the latest tailing polls of one projector in this process.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProjectorRunStatus {
+    pub last_success_at: Option<OffsetDateTime>,
+    pub last_error: Option<String>,
+    pub last_error_at: Option<OffsetDateTime>,
+}
+
+impl ProjectorRunStatus {
+    /// True until a poll fails, and again once a later poll succeeds.
+    pub fn is_healthy(&self) -> bool {
+        match (self.last_error_at, self.last_success_at) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(error_at), Some(success_at)) => success_at > error_at,
+        }
+    }
+}
+
+/// In-memory record of projector poll outcomes, written by the projection
+/// worker and read by health reporting. Checkpoints live in the database;
+/// this only carries what the database does not (timings and errors).
+#[derive(Debug, Clone, Default)]
+pub struct ProjectionRunRegistry {
+    statuses: Arc<Mutex<HashMap<String, ProjectorRunStatus>>>,
+}
+
+impl ProjectionRunRegistry {
+    pub fn record_success(&self, projector_name: &str, at: OffsetDateTime) {
+        self.update(projector_name, |status| status.last_success_at = Some(at));
+    }
+
+    pub fn record_failure(&self, projector_name: &str, error: String, at: OffsetDateTime) {
+        self.update(projector_name, |status| {
+            status.last_error = Some(error);
+            status.last_error_at = Some(at);
+        });
+    }
+
+    pub fn status(&self, projector_name: &str) -> ProjectorRunStatus {
+        self.statuses
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(projector_name)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn update(&self, projector_name: &str, f: impl FnOnce(&mut ProjectorRunStatus)) {
+        let mut statuses = self
+            .statuses
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        f(statuses.entry(projector_name.to_string()).or_default());
+    }
+}
+
+pub trait DependOnProjectionRunRegistry: Send + Sync {
+    fn projection_run_registry(&self) -> &ProjectionRunRegistry;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::Duration;
+
+    #[test]
+    fn run_status_recovers_after_a_later_success() {
+        let registry = ProjectionRunRegistry::default();
+        let start = OffsetDateTime::UNIX_EPOCH;
+        assert!(registry.status("account_projector").is_healthy());
+
+        registry.record_success("account_projector", start);
+        registry.record_failure(
+            "account_projector",
+            "boom".to_string(),
+            start + Duration::seconds(1),
+        );
+        let status = registry.status("account_projector");
+        assert!(!status.is_healthy());
+        assert_eq!(status.last_error.as_deref(), Some("boom"));
+
+        registry.record_success("account_projector", start + Duration::seconds(2));
+        let status = registry.status("account_projector");
+        assert!(status.is_healthy());
+        assert_eq!(status.last_error.as_deref(), Some("boom"));
+        assert!(registry.status("profile_projector").is_healthy());
+    }
+}
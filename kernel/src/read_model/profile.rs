@@ -134,6 +134,13 @@ pub trait ProfileReadModel: Sync + Send + 'static {
         account_ids: &[AccountId],
     ) -> impl Future<Output = error_stack::Result<Vec<ProfileProjection>, KernelError>> + Send;
 
+    /// Profiles using the image as icon or banner.
+    fn find_by_image_id(
+        &self,
+        executor: &mut Self::Connection,
+        image_id: &ImageId,
+    ) -> impl Future<Output = error_stack::Result<Vec<ProfileProjection>, KernelError>> + Send;
+
     fn create(
         &self,
         executor: &mut Self::Connection,
@@ -185,6 +192,13 @@ pub trait ProfileQuery: Send + Sync + 'static {
         executor: &mut Self::Connection,
         account_ids: &[AccountId],
     ) -> impl Future<Output = error_stack::Result<Vec<ProfileProjection>, KernelError>> + Send;
+
+    /// Profiles using the image as icon or banner.
+    fn find_by_image_id(
+        &self,
+        executor: &mut Self::Connection,
+        image_id: &ImageId,
+    ) -> impl Future<Output = error_stack::Result<Vec<ProfileProjection>, KernelError>> + Send;
 }
 
 impl<T> ProfileQuery for T
@@ -221,6 +235,16 @@ where
             .find_by_account_ids(executor, account_ids)
             .await
     }
+
+    async fn find_by_image_id(
+        &self,
+        executor: &mut Self::Connection,
+        image_id: &ImageId,
+    ) -> error_stack::Result<Vec<ProfileProjection>, KernelError> {
+        self.profile_read_model()
+            .find_by_image_id(executor, image_id)
+            .await
+    }
 }
 
 pub trait DependOnProfileQuery: DependOnDatabaseConnection + Send + Sync {
@@ -2,18 +2,30 @@ mod aggregate;
 mod auth_account;
 mod auth_host;
 mod block;
+mod custom_role;
 mod follow;
 mod image;
+mod image_blocklist;
+mod instance_role_change;
 mod mute;
 mod outbox_activity;
+mod personal_access_token;
 mod remote_account;
+mod upload_session;
+mod webhook;
 
 pub use self::aggregate::*;
 pub use self::auth_account::*;
 pub use self::auth_host::*;
 pub use self::block::*;
+pub use self::custom_role::*;
 pub use self::follow::*;
 pub use self::image::*;
+pub use self::image_blocklist::*;
+pub use self::instance_role_change::*;
 pub use self::mute::*;
 pub use self::outbox_activity::*;
+pub use self::personal_access_token::*;
 pub use self::remote_account::*;
+pub use self::upload_session::*;
+pub use self::webhook::*;
@@ -0,0 +1,54 @@
+use crate::database::{Connection, DatabaseConnection, DependOnDatabaseConnection};
+use crate::entity::{CustomRole, CustomRoleId};
+use crate::KernelError;
+use std::future::Future;
+
+pub trait CustomRoleRepository: Sync + Send + 'static {
+    type Connection: Connection;
+
+    fn create(
+        &self,
+        executor: &mut Self::Connection,
+        role: &CustomRole,
+    ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send;
+
+    fn find_by_id(
+        &self,
+        executor: &mut Self::Connection,
+        id: &CustomRoleId,
+    ) -> impl Future<Output = error_stack::Result<Option<CustomRole>, KernelError>> + Send;
+
+    fn find_by_name(
+        &self,
+        executor: &mut Self::Connection,
+        name: &str,
+    ) -> impl Future<Output = error_stack::Result<Option<CustomRole>, KernelError>> + Send;
+
+    /// Every role, by name.
+    fn find_all(
+        &self,
+        executor: &mut Self::Connection,
+    ) -> impl Future<Output = error_stack::Result<Vec<CustomRole>, KernelError>> + Send;
+
+    /// Overwrites the name, permissions and `updated_at`.
+    fn update(
+        &self,
+        executor: &mut Self::Connection,
+        role: &CustomRole,
+    ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send;
+
+    /// Returns `false` if there was no such role.
+    fn delete(
+        &self,
+        executor: &mut Self::Connection,
+        id: &CustomRoleId,
+    ) -> impl Future<Output = error_stack::Result<bool, KernelError>> + Send;
+}
+
+pub trait DependOnCustomRoleRepository: Sync + Send + DependOnDatabaseConnection {
+    type CustomRoleRepository: CustomRoleRepository<
+        Connection = <Self::DatabaseConnection as DatabaseConnection>::Connection,
+    >;
+
+    fn custom_role_repository(&self) -> &Self::CustomRoleRepository;
+}
@@ -1,7 +1,8 @@
 use crate::database::{Connection, DatabaseConnection, DependOnDatabaseConnection};
-use crate::entity::{Image, ImageId, ImageUrl};
+use crate::entity::{AccountId, AuthAccountId, Image, ImageHash, ImageId, ImageUrl};
 use crate::KernelError;
 use std::future::Future;
+use time::OffsetDateTime;
 
 pub trait ImageRepository: Sync + Send + 'static {
     type Connection: Connection;
@@ -27,17 +28,159 @@ pub trait ImageRepository: Sync + Send + 'static {
         url: &ImageUrl,
     ) -> impl Future<Output = error_stack::Result<Option<Image>, KernelError>> + Send;
 
+    /// The uploaded image whose stored bytes have this hash, to reuse
+    /// instead of storing identical bytes again.
+    fn find_by_hash(
+        &self,
+        executor: &mut Self::Connection,
+        hash: &ImageHash,
+    ) -> impl Future<Output = error_stack::Result<Option<Image>, KernelError>> + Send;
+
     fn create(
         &self,
         executor: &mut Self::Connection,
         image: &Image,
     ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send;
 
+    /// Insert an uploaded image. Uploads are unique by hash: when one with
+    /// the same hash already exists, nothing is inserted and `false` is
+    /// returned, and `find_by_hash` yields the existing one.
+    fn create_upload(
+        &self,
+        executor: &mut Self::Connection,
+        image: &Image,
+    ) -> impl Future<Output = error_stack::Result<bool, KernelError>> + Send;
+
     fn delete(
         &self,
         executor: &mut Self::Connection,
         image_id: &ImageId,
     ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send;
+
+    /// Quarantined images, newest first. `cursor` is the last image id of
+    /// the previous page.
+    fn find_quarantined(
+        &self,
+        executor: &mut Self::Connection,
+        limit: usize,
+        cursor: Option<i64>,
+    ) -> impl Future<Output = error_stack::Result<Vec<Image>, KernelError>> + Send;
+
+    /// Put the image into quarantine or release it. Returns `false` if it
+    /// already was in that state.
+    fn set_quarantined(
+        &self,
+        executor: &mut Self::Connection,
+        image_id: &ImageId,
+        quarantined: bool,
+    ) -> impl Future<Output = error_stack::Result<bool, KernelError>> + Send;
+
+    /// Record `owner` as holding a reference to the image. Returns `false`
+    /// if it already held one.
+    fn add_owner(
+        &self,
+        executor: &mut Self::Connection,
+        image_id: &ImageId,
+        owner: &AuthAccountId,
+    ) -> impl Future<Output = error_stack::Result<bool, KernelError>> + Send;
+
+    /// Drop the reference `owner` holds. Returns `false` if it held none.
+    fn remove_owner(
+        &self,
+        executor: &mut Self::Connection,
+        image_id: &ImageId,
+        owner: &AuthAccountId,
+    ) -> impl Future<Output = error_stack::Result<bool, KernelError>> + Send;
+
+    /// The image with the description and focal point `owner` gave its
+    /// reference, or `None` if it holds none.
+    fn find_by_id_for_owner(
+        &self,
+        executor: &mut Self::Connection,
+        image_id: &ImageId,
+        owner: &AuthAccountId,
+    ) -> impl Future<Output = error_stack::Result<Option<Image>, KernelError>> + Send;
+
+    /// Overwrite the description and focal point of the reference `owner`
+    /// holds with those of `image`.
+    fn update_owner_metadata(
+        &self,
+        executor: &mut Self::Connection,
+        image: &Image,
+        owner: &AuthAccountId,
+    ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send;
+
+    /// Images shown by profiles, each with the description and focal point
+    /// its profile gave it. A pair with no such image is left out.
+    fn find_profile_images(
+        &self,
+        executor: &mut Self::Connection,
+        references: &[(AccountId, ImageId)],
+    ) -> impl Future<Output = error_stack::Result<Vec<(AccountId, Image)>, KernelError>> + Send;
+
+    /// Overwrite the description and focal point the profile of `account_id`
+    /// shows with `image`.
+    fn update_profile_metadata(
+        &self,
+        executor: &mut Self::Connection,
+        account_id: &AccountId,
+        image: &Image,
+    ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send;
+
+    fn find_owners(
+        &self,
+        executor: &mut Self::Connection,
+        image_id: &ImageId,
+    ) -> impl Future<Output = error_stack::Result<Vec<AuthAccountId>, KernelError>> + Send;
+
+    /// Sum of `byte_size` over the images owned by `owner`.
+    fn total_bytes_by_owner(
+        &self,
+        executor: &mut Self::Connection,
+        owner: &AuthAccountId,
+    ) -> impl Future<Output = error_stack::Result<u64, KernelError>> + Send;
+
+    /// Images that no local profile and no remote account uses as icon or
+    /// banner, and that neither were created nor gained an owner since
+    /// `created_before`, oldest first.
+    fn find_unreferenced(
+        &self,
+        executor: &mut Self::Connection,
+        created_before: OffsetDateTime,
+        limit: usize,
+    ) -> impl Future<Output = error_stack::Result<Vec<Image>, KernelError>> + Send;
+
+    /// Delete the image unless something references it or it gained an owner
+    /// since `created_before`, checked in the same statement. Returns whether
+    /// a row was deleted; `false` also covers an image that no longer exists.
+    fn delete_unreferenced(
+        &self,
+        executor: &mut Self::Connection,
+        image_id: &ImageId,
+        created_before: OffsetDateTime,
+    ) -> impl Future<Output = error_stack::Result<bool, KernelError>> + Send;
+
+    /// Delete the image unless a profile or remote account uses it or an
+    /// owner other than `owner` holds a reference, checked in the same
+    /// statement. Returns whether a row was deleted.
+    fn delete_last_owned(
+        &self,
+        executor: &mut Self::Connection,
+        image_id: &ImageId,
+        owner: &AuthAccountId,
+    ) -> impl Future<Output = error_stack::Result<bool, KernelError>> + Send;
+
+    /// Cached copies of remote account icons and banners to evict: those of
+    /// accounts not seen since `unseen_before`, then, least recently seen
+    /// first, whatever keeps the cache above `max_total_bytes`.
+    /// Copies an uploader owns or a local profile uses are never evicted.
+    fn find_remote_cache_evictions(
+        &self,
+        executor: &mut Self::Connection,
+        unseen_before: OffsetDateTime,
+        max_total_bytes: u64,
+        limit: usize,
+    ) -> impl Future<Output = error_stack::Result<Vec<Image>, KernelError>> + Send;
 }
 
 pub trait DependOnImageRepository: Sync + Send + DependOnDatabaseConnection {
@@ -0,0 +1,45 @@
+use crate::database::{Connection, DatabaseConnection, DependOnDatabaseConnection};
+use crate::entity::{ImageBlocklistEntry, ImageBlocklistEntryId, ImageHash, ImagePerceptualHash};
+use crate::KernelError;
+use std::future::Future;
+
+pub trait ImageBlocklistRepository: Sync + Send + 'static {
+    type Connection: Connection;
+
+    fn create(
+        &self,
+        executor: &mut Self::Connection,
+        entry: &ImageBlocklistEntry,
+    ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send;
+
+    /// Every entry, newest first.
+    fn find_all(
+        &self,
+        executor: &mut Self::Connection,
+    ) -> impl Future<Output = error_stack::Result<Vec<ImageBlocklistEntry>, KernelError>> + Send;
+
+    /// Entries whose hash equals `hash` or whose perceptual hash is at most
+    /// `max_distance` bits away from `perceptual_hash`.
+    fn find_matches(
+        &self,
+        executor: &mut Self::Connection,
+        hash: &ImageHash,
+        perceptual_hash: Option<&ImagePerceptualHash>,
+        max_distance: u32,
+    ) -> impl Future<Output = error_stack::Result<Vec<ImageBlocklistEntry>, KernelError>> + Send;
+
+    /// Returns `false` if there was no such entry.
+    fn delete(
+        &self,
+        executor: &mut Self::Connection,
+        id: &ImageBlocklistEntryId,
+    ) -> impl Future<Output = error_stack::Result<bool, KernelError>> + Send;
+}
+
+pub trait DependOnImageBlocklistRepository: Sync + Send + DependOnDatabaseConnection {
+    type ImageBlocklistRepository: ImageBlocklistRepository<
+        Connection = <Self::DatabaseConnection as DatabaseConnection>::Connection,
+    >;
+
+    fn image_blocklist_repository(&self) -> &Self::ImageBlocklistRepository;
+}
@@ -0,0 +1,31 @@
+use crate::database::{Connection, DatabaseConnection, DependOnDatabaseConnection};
+use crate::entity::InstanceRoleChange;
+use crate::KernelError;
+use std::future::Future;
+
+pub trait InstanceRoleChangeRepository: Sync + Send + 'static {
+    type Connection: Connection;
+
+    fn create(
+        &self,
+        executor: &mut Self::Connection,
+        change: &InstanceRoleChange,
+    ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send;
+
+    /// Changes, newest first. `cursor` is the last change id of the
+    /// previous page.
+    fn find_page(
+        &self,
+        executor: &mut Self::Connection,
+        limit: usize,
+        cursor: Option<i64>,
+    ) -> impl Future<Output = error_stack::Result<Vec<InstanceRoleChange>, KernelError>> + Send;
+}
+
+pub trait DependOnInstanceRoleChangeRepository: Sync + Send + DependOnDatabaseConnection {
+    type InstanceRoleChangeRepository: InstanceRoleChangeRepository<
+        Connection = <Self::DatabaseConnection as DatabaseConnection>::Connection,
+    >;
+
+    fn instance_role_change_repository(&self) -> &Self::InstanceRoleChangeRepository;
+}
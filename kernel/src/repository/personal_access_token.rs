@@ -0,0 +1,53 @@
+use crate::database::{Connection, DatabaseConnection, DependOnDatabaseConnection};
+use crate::entity::{AuthAccountId, PersonalAccessToken, PersonalAccessTokenId};
+use crate::KernelError;
+use std::future::Future;
+use time::OffsetDateTime;
+
+pub trait PersonalAccessTokenRepository: Sync + Send + 'static {
+    type Connection: Connection;
+
+    fn create(
+        &self,
+        executor: &mut Self::Connection,
+        token: &PersonalAccessToken,
+    ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send;
+
+    fn find_by_token_hash(
+        &self,
+        executor: &mut Self::Connection,
+        token_hash: &str,
+    ) -> impl Future<Output = error_stack::Result<Option<PersonalAccessToken>, KernelError>> + Send;
+
+    /// Tokens of `owner`, newest first.
+    fn find_by_owner(
+        &self,
+        executor: &mut Self::Connection,
+        owner: &AuthAccountId,
+    ) -> impl Future<Output = error_stack::Result<Vec<PersonalAccessToken>, KernelError>> + Send;
+
+    /// Sets `last_used_at` to `now` unless it was already set within the
+    /// last minute, so busy tokens do not write on every request.
+    fn touch(
+        &self,
+        executor: &mut Self::Connection,
+        id: &PersonalAccessTokenId,
+        now: OffsetDateTime,
+    ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send;
+
+    /// Returns `false` when `owner` has no such token.
+    fn delete(
+        &self,
+        executor: &mut Self::Connection,
+        id: &PersonalAccessTokenId,
+        owner: &AuthAccountId,
+    ) -> impl Future<Output = error_stack::Result<bool, KernelError>> + Send;
+}
+
+pub trait DependOnPersonalAccessTokenRepository: Sync + Send + DependOnDatabaseConnection {
+    type PersonalAccessTokenRepository: PersonalAccessTokenRepository<
+        Connection = <Self::DatabaseConnection as DatabaseConnection>::Connection,
+    >;
+
+    fn personal_access_token_repository(&self) -> &Self::PersonalAccessTokenRepository;
+}
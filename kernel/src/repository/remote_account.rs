@@ -30,12 +30,31 @@ pub trait RemoteAccountRepository: Sync + Send + 'static {
         account: &RemoteAccount,
     ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send;
 
+    /// Also records the account as seen now, which orders the eviction of
+    /// its cached media.
     fn update(
         &self,
         executor: &mut Self::Connection,
         account: &RemoteAccount,
     ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send;
 
+    /// Accounts with a media source URL whose copies are due to be fetched
+    /// (`media_fetched_at` is `None`), most recently seen first.
+    fn find_pending_media(
+        &self,
+        executor: &mut Self::Connection,
+        limit: usize,
+    ) -> impl Future<Output = error_stack::Result<Vec<RemoteAccount>, KernelError>> + Send;
+
+    /// Store the cached image IDs and `media_fetched_at` of `account`,
+    /// provided its source URLs are still the ones stored. Returns `false`
+    /// when the actor published other images in the meantime.
+    fn update_cached_media(
+        &self,
+        executor: &mut Self::Connection,
+        account: &RemoteAccount,
+    ) -> impl Future<Output = error_stack::Result<bool, KernelError>> + Send;
+
     fn delete(
         &self,
         executor: &mut Self::Connection,
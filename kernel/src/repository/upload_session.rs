@@ -0,0 +1,45 @@
+use crate::database::{Connection, DatabaseConnection, DependOnDatabaseConnection};
+use crate::entity::{UploadSession, UploadSessionId};
+use crate::KernelError;
+use std::future::Future;
+use time::OffsetDateTime;
+
+pub trait UploadSessionRepository: Sync + Send + 'static {
+    type Connection: Connection;
+
+    fn create(
+        &self,
+        executor: &mut Self::Connection,
+        session: &UploadSession,
+    ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send;
+
+    fn find_by_id(
+        &self,
+        executor: &mut Self::Connection,
+        id: &UploadSessionId,
+    ) -> impl Future<Output = error_stack::Result<Option<UploadSession>, KernelError>> + Send;
+
+    /// Returns `false` when the session was already gone, so concurrent
+    /// finalize calls can tell which one claimed it.
+    fn delete(
+        &self,
+        executor: &mut Self::Connection,
+        id: &UploadSessionId,
+    ) -> impl Future<Output = error_stack::Result<bool, KernelError>> + Send;
+
+    /// Sessions whose `expires_at` is not after `now`, oldest first.
+    fn find_expired(
+        &self,
+        executor: &mut Self::Connection,
+        now: OffsetDateTime,
+        limit: usize,
+    ) -> impl Future<Output = error_stack::Result<Vec<UploadSession>, KernelError>> + Send;
+}
+
+pub trait DependOnUploadSessionRepository: Sync + Send + DependOnDatabaseConnection {
+    type UploadSessionRepository: UploadSessionRepository<
+        Connection = <Self::DatabaseConnection as DatabaseConnection>::Connection,
+    >;
+
+    fn upload_session_repository(&self) -> &Self::UploadSessionRepository;
+}
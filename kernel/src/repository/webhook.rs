@@ -0,0 +1,115 @@
+use crate::database::{Connection, DatabaseConnection, DependOnDatabaseConnection};
+use crate::entity::{
+    WebhookDelivery, WebhookDeliveryId, WebhookSubscription, WebhookSubscriptionId,
+};
+use crate::KernelError;
+use std::future::Future;
+use time::OffsetDateTime;
+
+pub trait WebhookSubscriptionRepository: Sync + Send + 'static {
+    type Connection: Connection;
+
+    fn create(
+        &self,
+        executor: &mut Self::Connection,
+        subscription: &WebhookSubscription,
+    ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send;
+
+    fn find_by_id(
+        &self,
+        executor: &mut Self::Connection,
+        id: &WebhookSubscriptionId,
+    ) -> impl Future<Output = error_stack::Result<Option<WebhookSubscription>, KernelError>> + Send;
+
+    fn find_all(
+        &self,
+        executor: &mut Self::Connection,
+    ) -> impl Future<Output = error_stack::Result<Vec<WebhookSubscription>, KernelError>> + Send;
+
+    /// Delete the subscription together with its delivery log.
+    fn delete(
+        &self,
+        executor: &mut Self::Connection,
+        id: &WebhookSubscriptionId,
+    ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send;
+}
+
+pub trait DependOnWebhookSubscriptionRepository: Sync + Send + DependOnDatabaseConnection {
+    type WebhookSubscriptionRepository: WebhookSubscriptionRepository<
+        Connection = <Self::DatabaseConnection as DatabaseConnection>::Connection,
+    >;
+
+    fn webhook_subscription_repository(&self) -> &Self::WebhookSubscriptionRepository;
+}
+
+pub trait WebhookDeliveryRepository: Sync + Send + 'static {
+    type Connection: Connection;
+
+    /// Insert the deliveries, skipping any `(subscription_id, event_id)` pair
+    /// that is already queued or logged.
+    fn enqueue(
+        &self,
+        executor: &mut Self::Connection,
+        deliveries: &[WebhookDelivery],
+    ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send;
+
+    /// Claim pending deliveries whose `next_attempt_at` is not after `now`,
+    /// oldest first, by moving their `next_attempt_at` to `lease_until`.
+    ///
+    /// Rows locked by a concurrent claim are skipped, and a claimed row is not
+    /// due again until the lease runs out, so each delivery goes to one
+    /// worker. A worker that dies mid-batch leaves its rows to be retried
+    /// once the lease expires.
+    fn claim_due(
+        &self,
+        executor: &mut Self::Connection,
+        now: OffsetDateTime,
+        lease_until: OffsetDateTime,
+        limit: usize,
+    ) -> impl Future<Output = error_stack::Result<Vec<WebhookDelivery>, KernelError>> + Send;
+
+    /// Delivery log of one subscription, newest first. `cursor` is the
+    /// last delivery id of the previous page.
+    fn find_by_subscription_id(
+        &self,
+        executor: &mut Self::Connection,
+        subscription_id: &WebhookSubscriptionId,
+        limit: usize,
+        cursor: Option<i64>,
+    ) -> impl Future<Output = error_stack::Result<Vec<WebhookDelivery>, KernelError>> + Send;
+
+    fn mark_delivered(
+        &self,
+        executor: &mut Self::Connection,
+        id: &WebhookDeliveryId,
+        status_code: i32,
+    ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send;
+
+    /// Record a failed attempt. `next_attempt_at` schedules a retry; `None`
+    /// gives up and marks the delivery failed.
+    fn mark_attempt_failed(
+        &self,
+        executor: &mut Self::Connection,
+        id: &WebhookDeliveryId,
+        status_code: Option<i32>,
+        error: &str,
+        next_attempt_at: Option<OffsetDateTime>,
+    ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send;
+
+    /// Delete up to `limit` delivered or failed deliveries created before
+    /// `created_before`. Returns the number of rows deleted.
+    fn prune_finished(
+        &self,
+        executor: &mut Self::Connection,
+        created_before: OffsetDateTime,
+        limit: usize,
+    ) -> impl Future<Output = error_stack::Result<u64, KernelError>> + Send;
+}
+
+pub trait DependOnWebhookDeliveryRepository: Sync + Send + DependOnDatabaseConnection {
+    type WebhookDeliveryRepository: WebhookDeliveryRepository<
+        Connection = <Self::DatabaseConnection as DatabaseConnection>::Connection,
+    >;
+
+    fn webhook_delivery_repository(&self) -> &Self::WebhookDeliveryRepository;
+}
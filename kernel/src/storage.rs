@@ -1,5 +1,7 @@
+use crate::entity::{ImageHash, ImagePerceptualHash};
 use crate::KernelError;
 use std::future::Future;
+use std::time::Duration;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct StoredObject {
@@ -7,6 +9,16 @@ pub struct StoredObject {
     pub url: String,
 }
 
+/// A time-limited URL the client sends the object bytes to directly.
+/// `headers` must accompany the request for the signature to match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PresignedUpload {
+    pub object: StoredObject,
+    pub upload_url: String,
+    pub method: String,
+    pub headers: Vec<(String, String)>,
+}
+
 pub trait ImageStorage: Send + Sync + 'static {
     fn put(
         &self,
@@ -14,6 +26,32 @@ pub trait ImageStorage: Send + Sync + 'static {
         content_type: &str,
         bytes: &[u8],
     ) -> impl Future<Output = error_stack::Result<StoredObject, KernelError>> + Send;
+
+    /// Presign a direct upload of exactly `byte_size` bytes of
+    /// `content_type` to `key`. Backends that cannot hand out upload URLs
+    /// reject this.
+    fn presign_upload(
+        &self,
+        key: &str,
+        content_type: &str,
+        byte_size: u64,
+        expires_in: Duration,
+    ) -> impl Future<Output = error_stack::Result<PresignedUpload, KernelError>> + Send;
+
+    /// Read the object at `key`, `None` if nothing was uploaded there.
+    /// Objects larger than `max_bytes` are rejected without being read.
+    fn fetch(
+        &self,
+        key: &str,
+        max_bytes: usize,
+    ) -> impl Future<Output = error_stack::Result<Option<Vec<u8>>, KernelError>> + Send;
+
+    /// Remove the object served at `url`. URLs this storage did not hand
+    /// out and objects that are already gone are not errors.
+    fn delete(
+        &self,
+        url: &str,
+    ) -> impl Future<Output = error_stack::Result<(), KernelError>> + Send;
 }
 
 pub trait DependOnImageStorage: Send + Sync {
@@ -21,3 +59,39 @@ pub trait DependOnImageStorage: Send + Sync {
 
     fn image_storage(&self) -> &Self::ImageStorage;
 }
+
+/// An upload after sanitizing, as it is about to be stored.
+#[derive(Debug, Clone, Copy)]
+pub struct ImageScanInput<'a> {
+    pub content_type: &'a str,
+    pub bytes: &'a [u8],
+    pub hash: &'a ImageHash,
+    pub perceptual_hash: Option<&'a ImagePerceptualHash>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImageScanVerdict {
+    Clean,
+    /// Store the image but hold it for moderator review.
+    Quarantine {
+        reason: String,
+    },
+    /// Refuse the upload.
+    Reject {
+        reason: String,
+    },
+}
+
+/// Content-safety check every upload passes before it is stored.
+pub trait ImageScanner: Send + Sync + 'static {
+    fn scan(
+        &self,
+        image: ImageScanInput<'_>,
+    ) -> impl Future<Output = error_stack::Result<ImageScanVerdict, KernelError>> + Send;
+}
+
+pub trait DependOnImageScanner: Send + Sync {
+    type ImageScanner: ImageScanner;
+
+    fn image_scanner(&self) -> &Self::ImageScanner;
+}
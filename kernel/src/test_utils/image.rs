@@ -1,4 +1,8 @@
-use crate::entity::{Image, ImageBlurHash, ImageHash, ImageId, ImageUrl};
+use crate::entity::{
+    CreatedAt, Image, ImageBlurHash, ImageDescription, ImageFocus, ImageHash, ImageId,
+    ImagePerceptualHash, ImageUrl, ImageVariant,
+};
+use time::OffsetDateTime;
 
 use super::{unique_image_url, DEFAULT_BLUR_HASH, DEFAULT_IMAGE_HASH};
 
@@ -7,6 +11,14 @@ pub struct ImageBuilder {
     url: Option<ImageUrl>,
     hash: Option<ImageHash>,
     blur_hash: Option<ImageBlurHash>,
+    variants: Vec<ImageVariant>,
+    animated: bool,
+    description: Option<ImageDescription>,
+    focus: Option<ImageFocus>,
+    perceptual_hash: Option<ImagePerceptualHash>,
+    quarantined: bool,
+    byte_size: u64,
+    created_at: Option<CreatedAt<Image>>,
 }
 
 impl Default for ImageBuilder {
@@ -22,6 +34,14 @@ impl ImageBuilder {
             url: None,
             hash: None,
             blur_hash: None,
+            variants: Vec::new(),
+            animated: false,
+            description: None,
+            focus: None,
+            perceptual_hash: None,
+            quarantined: false,
+            byte_size: 0,
+            created_at: None,
         }
     }
 
@@ -45,6 +65,46 @@ impl ImageBuilder {
         self
     }
 
+    pub fn variant(mut self, variant: ImageVariant) -> Self {
+        self.variants.push(variant);
+        self
+    }
+
+    pub fn animated(mut self, animated: bool) -> Self {
+        self.animated = animated;
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(ImageDescription::new(description));
+        self
+    }
+
+    pub fn focus(mut self, x: f32, y: f32) -> Self {
+        self.focus = Some(ImageFocus::new(x, y));
+        self
+    }
+
+    pub fn perceptual_hash(mut self, perceptual_hash: u64) -> Self {
+        self.perceptual_hash = Some(ImagePerceptualHash::new(perceptual_hash));
+        self
+    }
+
+    pub fn quarantined(mut self, quarantined: bool) -> Self {
+        self.quarantined = quarantined;
+        self
+    }
+
+    pub fn byte_size(mut self, byte_size: u64) -> Self {
+        self.byte_size = byte_size;
+        self
+    }
+
+    pub fn created_at(mut self, created_at: CreatedAt<Image>) -> Self {
+        self.created_at = Some(created_at);
+        self
+    }
+
     pub fn build(self) -> Image {
         crate::ensure_generator_initialized();
         Image::new(
@@ -55,6 +115,17 @@ impl ImageBuilder {
                 .unwrap_or_else(|| ImageHash::new(DEFAULT_IMAGE_HASH)),
             self.blur_hash
                 .unwrap_or_else(|| ImageBlurHash::new(DEFAULT_BLUR_HASH)),
+            self.variants,
+            self.animated,
+            self.description,
+            self.focus,
+            self.perceptual_hash,
+            self.quarantined,
+            self.byte_size,
+            // Postgres keeps microseconds; whole seconds round-trip exactly.
+            self.created_at.unwrap_or_else(|| {
+                CreatedAt::new(OffsetDateTime::now_utc().replace_nanosecond(0).unwrap())
+            }),
         )
     }
 }
@@ -1,4 +1,5 @@
 use crate::entity::{ImageId, RemoteAccount, RemoteAccountAcct, RemoteAccountId, RemoteAccountUrl};
+use time::OffsetDateTime;
 
 use super::unique_remote_acct;
 
@@ -7,8 +8,12 @@ pub struct RemoteAccountBuilder {
     acct: Option<RemoteAccountAcct>,
     url: Option<RemoteAccountUrl>,
     icon_id: Option<Option<ImageId>>,
+    banner_id: Option<Option<ImageId>>,
+    icon_source_url: Option<Option<String>>,
+    banner_source_url: Option<Option<String>>,
     inbox_url: Option<Option<String>>,
     public_key_pem: Option<Option<String>>,
+    media_fetched_at: Option<Option<OffsetDateTime>>,
 }
 
 impl Default for RemoteAccountBuilder {
@@ -24,8 +29,12 @@ impl RemoteAccountBuilder {
             acct: None,
             url: None,
             icon_id: None,
+            banner_id: None,
+            icon_source_url: None,
+            banner_source_url: None,
             inbox_url: None,
             public_key_pem: None,
+            media_fetched_at: None,
         }
     }
 
@@ -49,6 +58,21 @@ impl RemoteAccountBuilder {
         self
     }
 
+    pub fn banner_id(mut self, banner_id: Option<ImageId>) -> Self {
+        self.banner_id = Some(banner_id);
+        self
+    }
+
+    pub fn icon_source_url(mut self, url: Option<impl Into<String>>) -> Self {
+        self.icon_source_url = Some(url.map(Into::into));
+        self
+    }
+
+    pub fn banner_source_url(mut self, url: Option<impl Into<String>>) -> Self {
+        self.banner_source_url = Some(url.map(Into::into));
+        self
+    }
+
     pub fn inbox_url(mut self, inbox_url: Option<impl Into<String>>) -> Self {
         self.inbox_url = Some(inbox_url.map(Into::into));
         self
@@ -59,6 +83,11 @@ impl RemoteAccountBuilder {
         self
     }
 
+    pub fn media_fetched_at(mut self, fetched_at: Option<OffsetDateTime>) -> Self {
+        self.media_fetched_at = Some(fetched_at);
+        self
+    }
+
     pub fn build(self) -> RemoteAccount {
         crate::ensure_generator_initialized();
         let (default_acct, default_url) = unique_remote_acct();
@@ -68,8 +97,12 @@ impl RemoteAccountBuilder {
             self.acct.unwrap_or(default_acct),
             self.url.unwrap_or(default_url),
             self.icon_id.unwrap_or(None),
+            self.banner_id.unwrap_or(None),
+            self.icon_source_url.unwrap_or(None),
+            self.banner_source_url.unwrap_or(None),
             self.inbox_url.unwrap_or(None),
             self.public_key_pem.unwrap_or(None),
+            self.media_fetched_at.unwrap_or(None),
         )
     }
 }
@@ -0,0 +1,6 @@
+-- Payload shape version of each stored event, upcast to the current shape on
+-- read (see kernel::event::EventSchema). Rows written before this column
+-- existed are version 1.
+ALTER TABLE account_events ADD COLUMN schema_version INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE profile_events ADD COLUMN schema_version INTEGER NOT NULL DEFAULT 1;
+ALTER TABLE metadata_events ADD COLUMN schema_version INTEGER NOT NULL DEFAULT 1;
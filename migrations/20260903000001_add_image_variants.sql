@@ -0,0 +1,12 @@
+-- Fixed-size renditions generated from an uploaded image. Images uploaded
+-- before renditions existed simply have no rows here.
+CREATE TABLE image_variants (
+    image_id BIGINT NOT NULL REFERENCES images (id) ON DELETE CASCADE,
+    kind TEXT NOT NULL,
+    url TEXT UNIQUE NOT NULL,
+    width INTEGER NOT NULL,
+    height INTEGER NOT NULL,
+    media_type TEXT NOT NULL,
+    PRIMARY KEY (image_id, kind),
+    CONSTRAINT chk_image_variants_kind CHECK (kind IN ('avatar', 'header', 'thumbnail'))
+);
@@ -0,0 +1,14 @@
+-- Uploads are owned by the uploading auth account and counted against its
+-- media quota. Existing images keep a NULL owner and a zero size.
+ALTER TABLE "images"
+  ADD COLUMN "owner_id" BIGINT REFERENCES "auth_accounts" ("id") ON DELETE SET NULL,
+  ADD COLUMN "byte_size" BIGINT NOT NULL DEFAULT 0,
+  ADD COLUMN "created_at" TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+CREATE INDEX idx_images_owner_id ON images (owner_id);
+CREATE INDEX idx_images_created_at ON images (created_at);
+
+-- Garbage collection looks up references to every candidate image.
+CREATE INDEX idx_profiles_icon_id ON profiles (icon_id);
+CREATE INDEX idx_profiles_banner_id ON profiles (banner_id);
+CREATE INDEX idx_remote_accounts_icon_id ON remote_accounts (icon_id);
@@ -0,0 +1,13 @@
+-- Remote actor icons and banners are fetched into local storage. The source
+-- URLs are the ones the actor last published; a NULL media_fetched_at marks
+-- the copies as due for a fetch. last_seen_at orders the cache eviction.
+ALTER TABLE "remote_accounts"
+  ADD COLUMN "banner_id" BIGINT REFERENCES "images" ("id") ON DELETE SET NULL,
+  ADD COLUMN "icon_source_url" TEXT,
+  ADD COLUMN "banner_source_url" TEXT,
+  ADD COLUMN "media_fetched_at" TIMESTAMPTZ,
+  ADD COLUMN "last_seen_at" TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+CREATE INDEX idx_remote_accounts_banner_id ON remote_accounts (banner_id);
+CREATE INDEX idx_remote_accounts_pending_media ON remote_accounts (last_seen_at)
+  WHERE media_fetched_at IS NULL;
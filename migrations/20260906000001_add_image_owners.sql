@@ -0,0 +1,19 @@
+-- Identical uploads share one image. Each auth account that uploaded it
+-- holds a reference, which grants deletion and counts the image against
+-- its quota; the image is deleted with its last reference.
+CREATE TABLE "image_owners" (
+  "image_id" BIGINT NOT NULL REFERENCES "images" ("id") ON DELETE CASCADE,
+  "owner_id" BIGINT NOT NULL REFERENCES "auth_accounts" ("id") ON DELETE CASCADE,
+  "created_at" TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  PRIMARY KEY ("image_id", "owner_id")
+);
+
+CREATE INDEX idx_image_owners_owner_id ON image_owners (owner_id);
+
+INSERT INTO image_owners (image_id, owner_id, created_at)
+SELECT id, owner_id, created_at FROM images WHERE owner_id IS NOT NULL;
+
+DROP INDEX idx_images_owner_id;
+ALTER TABLE "images" DROP COLUMN "owner_id";
+
+CREATE INDEX idx_images_hash ON images (hash);
@@ -0,0 +1,7 @@
+-- Animated originals (GIF, animated WebP, APNG) are flagged and get a
+-- still first-frame rendition next to the existing ones.
+ALTER TABLE "images" ADD COLUMN "animated" BOOLEAN NOT NULL DEFAULT FALSE;
+
+ALTER TABLE image_variants DROP CONSTRAINT chk_image_variants_kind;
+ALTER TABLE image_variants ADD CONSTRAINT chk_image_variants_kind
+    CHECK (kind IN ('avatar', 'header', 'thumbnail', 'static'));
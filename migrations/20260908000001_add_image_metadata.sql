@@ -0,0 +1,10 @@
+-- Alt text and focal point, in Mastodon's focalPoint coordinates.
+ALTER TABLE "images" ADD COLUMN "description" TEXT;
+ALTER TABLE "images" ADD COLUMN "focus_x" REAL;
+ALTER TABLE "images" ADD COLUMN "focus_y" REAL;
+
+ALTER TABLE "images" ADD CONSTRAINT chk_images_focus
+    CHECK (
+        (focus_x IS NULL AND focus_y IS NULL)
+        OR (focus_x BETWEEN -1 AND 1 AND focus_y BETWEEN -1 AND 1)
+    );
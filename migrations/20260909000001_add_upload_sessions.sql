@@ -0,0 +1,19 @@
+-- Direct-to-storage uploads: the client PUTs the bytes to a presigned URL
+-- for object_key, then finalizes the session to turn it into an image.
+-- Expired sessions are swept by the media GC worker together with their
+-- staged objects.
+CREATE TABLE "upload_sessions" (
+  "id" BIGINT PRIMARY KEY NOT NULL,
+  "owner_id" BIGINT NOT NULL REFERENCES "auth_accounts" ("id") ON DELETE CASCADE,
+  "object_key" TEXT NOT NULL,
+  "object_url" TEXT NOT NULL,
+  "content_type" TEXT NOT NULL,
+  "byte_size" BIGINT NOT NULL,
+  "description" TEXT,
+  "focus_x" REAL,
+  "focus_y" REAL,
+  "expires_at" TIMESTAMPTZ NOT NULL,
+  "created_at" TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_upload_sessions_expires_at ON upload_sessions (expires_at);
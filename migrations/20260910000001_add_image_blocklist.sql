@@ -0,0 +1,26 @@
+-- Content-safety scanning of uploads. perceptual_hash is a 64-bit
+-- difference hash of the pixels (stored as its signed bit pattern); images
+-- stored before it existed have none. Quarantined images matched a
+-- quarantine entry of the blocklist and wait for moderator review.
+ALTER TABLE "images"
+  ADD COLUMN "perceptual_hash" BIGINT,
+  ADD COLUMN "quarantined" BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE INDEX idx_images_quarantined ON images (id DESC) WHERE quarantined;
+
+-- Moderator-managed blocklist. An upload matches an entry by exact hash
+-- of its stored bytes, or by a perceptual hash within the scanner's
+-- Hamming distance.
+CREATE TABLE "image_blocklist" (
+  "id" BIGINT PRIMARY KEY NOT NULL,
+  "hash" TEXT,
+  "perceptual_hash" BIGINT,
+  "action" TEXT NOT NULL,
+  "reason" TEXT NOT NULL,
+  "created_by" BIGINT NOT NULL REFERENCES "auth_accounts" ("id") ON DELETE CASCADE,
+  "created_at" TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  CONSTRAINT chk_image_blocklist_action CHECK (action IN ('reject', 'quarantine')),
+  CONSTRAINT chk_image_blocklist_hash CHECK (hash IS NOT NULL OR perceptual_hash IS NOT NULL)
+);
+
+CREATE INDEX idx_image_blocklist_hash ON image_blocklist (hash);
@@ -0,0 +1,16 @@
+-- Personal access tokens for bots and CI. Only the SHA-256 of the token is
+-- stored. scopes holds Emumet OAuth2 scope names; account_ids, when not
+-- null, limits the token to those accounts. Revoking a token deletes it.
+CREATE TABLE "personal_access_tokens" (
+  "id" BIGINT PRIMARY KEY NOT NULL,
+  "owner_id" BIGINT NOT NULL REFERENCES "auth_accounts" ("id") ON DELETE CASCADE,
+  "name" TEXT NOT NULL,
+  "token_hash" TEXT NOT NULL UNIQUE,
+  "scopes" TEXT[] NOT NULL,
+  "account_ids" BIGINT[],
+  "expires_at" TIMESTAMPTZ,
+  "last_used_at" TIMESTAMPTZ,
+  "created_at" TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_personal_access_tokens_owner_id ON personal_access_tokens (owner_id, id DESC);
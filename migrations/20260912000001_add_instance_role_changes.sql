@@ -0,0 +1,12 @@
+-- Audit trail of instance role grants and revocations. The role tuples
+-- live in the permission backend; this records who changed them. No
+-- foreign keys, so the history outlives the auth accounts it names.
+CREATE TABLE "instance_role_changes" (
+  "id" BIGINT PRIMARY KEY NOT NULL,
+  "role" TEXT NOT NULL,
+  "action" TEXT NOT NULL,
+  "subject_id" BIGINT NOT NULL,
+  "actor_id" BIGINT NOT NULL,
+  "created_at" TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  CONSTRAINT chk_instance_role_changes_action CHECK (action IN ('granted', 'revoked'))
+);
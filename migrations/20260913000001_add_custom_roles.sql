@@ -0,0 +1,11 @@
+-- Admin-defined roles bundling instance permissions. Members and the
+-- granted permissions are relation tuples in the permission backend
+-- (Role:<id>#members and Instance:singleton#<permission>_grants); this
+-- table keeps the name and the permission list.
+CREATE TABLE "custom_roles" (
+  "id" BIGINT PRIMARY KEY NOT NULL,
+  "name" TEXT NOT NULL UNIQUE,
+  "permissions" TEXT[] NOT NULL,
+  "created_at" TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  "updated_at" TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
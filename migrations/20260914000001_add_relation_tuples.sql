@@ -0,0 +1,30 @@
+-- Relation tuples for the Postgres permission backend
+-- (PERMISSION_BACKEND=postgres), mirroring Keto's tuple model. Each tuple
+-- has either a subject id or a subject set, never both.
+CREATE TABLE "relation_tuples" (
+  "namespace" TEXT NOT NULL,
+  "object" TEXT NOT NULL,
+  "relation" TEXT NOT NULL,
+  "subject_id" TEXT,
+  "subject_set_namespace" TEXT,
+  "subject_set_object" TEXT,
+  "subject_set_relation" TEXT,
+  "created_at" TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+  CHECK (
+    ("subject_id" IS NOT NULL) <> (
+      "subject_set_namespace" IS NOT NULL
+      AND "subject_set_object" IS NOT NULL
+      AND "subject_set_relation" IS NOT NULL
+    )
+  )
+);
+
+CREATE UNIQUE INDEX "relation_tuples_unique_idx" ON "relation_tuples" (
+  "namespace", "object", "relation", "subject_id",
+  "subject_set_namespace", "subject_set_object", "subject_set_relation"
+) NULLS NOT DISTINCT;
+
+CREATE INDEX "relation_tuples_subject_id_idx" ON "relation_tuples" ("subject_id");
+CREATE INDEX "relation_tuples_subject_set_idx" ON "relation_tuples" (
+  "subject_set_namespace", "subject_set_object", "subject_set_relation"
+);
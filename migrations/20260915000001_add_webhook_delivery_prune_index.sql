@@ -0,0 +1,2 @@
+-- Finished deliveries are pruned by age; pending rows are never candidates.
+CREATE INDEX idx_webhook_deliveries_finished ON webhook_deliveries (created_at) WHERE status <> 'pending';
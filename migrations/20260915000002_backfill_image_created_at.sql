@@ -0,0 +1,18 @@
+-- 20260904000001 stamped every image that already existed with the time of
+-- the migration. Move images that a profile picked up earlier back to the
+-- first profile event that referenced them. Moving created_at only ever
+-- earlier keeps images uploaded since then untouched.
+UPDATE images
+SET created_at = referenced.first_referenced_at
+FROM (
+  SELECT image_id::BIGINT AS image_id, MIN(occurred_at) AS first_referenced_at
+  FROM (
+    SELECT data->>'icon' AS image_id, occurred_at FROM profile_events
+    UNION ALL
+    SELECT data->>'banner' AS image_id, occurred_at FROM profile_events
+  ) AS profile_references
+  WHERE image_id ~ '^[0-9]+$'
+  GROUP BY image_id
+) AS referenced
+WHERE referenced.image_id = images.id
+  AND referenced.first_referenced_at < images.created_at;
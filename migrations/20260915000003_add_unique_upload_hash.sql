@@ -0,0 +1,17 @@
+-- Uploads are shared by content hash, so at most one uploaded image may hold
+-- a given hash. Remote cache copies are fetched per actor and may repeat
+-- bytes, so they stay outside the constraint. Of uploads that already share
+-- a hash, the oldest one keeps serving new references; the others keep
+-- their owners but are no longer found by hash.
+ALTER TABLE "images" ADD COLUMN "uploaded" BOOLEAN NOT NULL DEFAULT FALSE;
+
+UPDATE images SET uploaded = TRUE
+WHERE id IN (
+  SELECT DISTINCT ON (hash) id
+  FROM images
+  WHERE EXISTS (SELECT 1 FROM image_owners WHERE image_id = images.id)
+  ORDER BY hash, created_at, id
+);
+
+DROP INDEX idx_images_hash;
+CREATE UNIQUE INDEX uq_images_uploaded_hash ON images (hash) WHERE uploaded;
@@ -0,0 +1,45 @@
+-- Identical uploads share one image, so alt text and focal point move off
+-- the image onto the references to it: what each uploader sent with its
+-- upload, and what each profile shows with its icon or banner.
+ALTER TABLE "image_owners" ADD COLUMN "description" TEXT;
+ALTER TABLE "image_owners" ADD COLUMN "focus_x" REAL;
+ALTER TABLE "image_owners" ADD COLUMN "focus_y" REAL;
+
+ALTER TABLE "image_owners" ADD CONSTRAINT chk_image_owners_focus
+    CHECK (
+        (focus_x IS NULL AND focus_y IS NULL)
+        OR (focus_x BETWEEN -1 AND 1 AND focus_y BETWEEN -1 AND 1)
+    );
+
+CREATE TABLE "profile_image_metadata" (
+  "account_id" BIGINT NOT NULL REFERENCES "accounts" ("id") ON DELETE CASCADE,
+  "image_id" BIGINT NOT NULL REFERENCES "images" ("id") ON DELETE CASCADE,
+  "description" TEXT,
+  "focus_x" REAL,
+  "focus_y" REAL,
+  PRIMARY KEY ("account_id", "image_id"),
+  CONSTRAINT chk_profile_image_metadata_focus
+    CHECK (
+        (focus_x IS NULL AND focus_y IS NULL)
+        OR (focus_x BETWEEN -1 AND 1 AND focus_y BETWEEN -1 AND 1)
+    )
+);
+
+CREATE INDEX idx_profile_image_metadata_image_id ON profile_image_metadata (image_id);
+
+UPDATE image_owners
+SET description = images.description, focus_x = images.focus_x, focus_y = images.focus_y
+FROM images
+WHERE images.id = image_owners.image_id
+  AND (images.description IS NOT NULL OR images.focus_x IS NOT NULL);
+
+INSERT INTO profile_image_metadata (account_id, image_id, description, focus_x, focus_y)
+SELECT DISTINCT profiles.account_id, images.id, images.description, images.focus_x, images.focus_y
+FROM profiles
+JOIN images ON images.id = profiles.icon_id OR images.id = profiles.banner_id
+WHERE images.description IS NOT NULL OR images.focus_x IS NOT NULL;
+
+ALTER TABLE "images" DROP CONSTRAINT chk_images_focus;
+ALTER TABLE "images" DROP COLUMN "description";
+ALTER TABLE "images" DROP COLUMN "focus_x";
+ALTER TABLE "images" DROP COLUMN "focus_y";
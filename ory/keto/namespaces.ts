@@ -1,5 +1,8 @@
 import { Namespace, Context } from "@ory/keto-namespace-types"
 
+// The postgres permission backend (driver/src/permission/postgres.rs)
+// evaluates these permits in-process; keep both in sync.
+
 class User implements Namespace {}
 
 class Account implements Namespace {
@@ -21,6 +24,16 @@ class Account implements Namespace {
       this.related.signers.includes(ctx.subject),
     deactivate: (ctx: Context): boolean =>
       this.related.owners.includes(ctx.subject),
+    share: (ctx: Context): boolean =>
+      this.related.owners.includes(ctx.subject),
+  }
+}
+
+// Admin-defined role. Each instance permission it bundles is a tuple
+// Instance:singleton#<permission>_grants@Role:<id>#members.
+class Role implements Namespace {
+  related: {
+    members: User[]
   }
 }
 
@@ -28,12 +41,37 @@ class Instance implements Namespace {
   related: {
     admins: User[]
     moderators: User[]
+    moderate_grants: SubjectSet<Role, "members">[]
+    manage_reports_grants: SubjectSet<Role, "members">[]
+    manage_domain_blocks_grants: SubjectSet<Role, "members">[]
+    manage_media_grants: SubjectSet<Role, "members">[]
+    view_audit_log_grants: SubjectSet<Role, "members">[]
+    manage_roles_grants: SubjectSet<Role, "members">[]
   }
   permits = {
     moderate: (ctx: Context): boolean =>
       this.related.admins.includes(ctx.subject) ||
-      this.related.moderators.includes(ctx.subject),
+      this.related.moderators.includes(ctx.subject) ||
+      this.related.moderate_grants.includes(ctx.subject),
     administrate: (ctx: Context): boolean =>
       this.related.admins.includes(ctx.subject),
+    manage_reports: (ctx: Context): boolean =>
+      this.related.admins.includes(ctx.subject) ||
+      this.related.moderators.includes(ctx.subject) ||
+      this.related.manage_reports_grants.includes(ctx.subject),
+    manage_domain_blocks: (ctx: Context): boolean =>
+      this.related.admins.includes(ctx.subject) ||
+      this.related.moderators.includes(ctx.subject) ||
+      this.related.manage_domain_blocks_grants.includes(ctx.subject),
+    manage_media: (ctx: Context): boolean =>
+      this.related.admins.includes(ctx.subject) ||
+      this.related.moderators.includes(ctx.subject) ||
+      this.related.manage_media_grants.includes(ctx.subject),
+    view_audit_log: (ctx: Context): boolean =>
+      this.related.admins.includes(ctx.subject) ||
+      this.related.view_audit_log_grants.includes(ctx.subject),
+    manage_roles: (ctx: Context): boolean =>
+      this.related.admins.includes(ctx.subject) ||
+      this.related.manage_roles_grants.includes(ctx.subject),
   }
 }
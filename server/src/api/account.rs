@@ -1,14 +1,17 @@
 use super::resolve_auth_account_id;
 use crate::auth::OidcAuthInfo;
 use crate::handler::AppModule;
-use application::dto::account::{AccountDetailDto, AccountDto, CreateAccountDto, UpdateAccountDto};
+use application::dto::account::{
+    AccountDetailDto, AccountDto, AccountMemberDto, CreateAccountDto, UpdateAccountDto,
+};
 use application::dto::activitypub::{
     FollowRelationDto, SendFollowDto, SendFollowResultDto, SendUndoFollowDto,
 };
 use application::dto::block_mute::{BlockAccountDto, MuteAccountDto, RelationDto};
 use application::dto::pagination::Pagination;
 use application::service::account::{
-    CreateAccountUseCase, DeactivateAccountUseCase, ReactivateAccountUseCase,
+    CreateAccountUseCase, DeactivateAccountUseCase, GetAccountMembersUseCase,
+    GrantAccountRelationUseCase, ReactivateAccountUseCase, RevokeAccountRelationUseCase,
 };
 use application::service::account_detail::{GetAccountDetailUseCase, UpdateAccountDetailUseCase};
 use application::service::activitypub::{
@@ -17,6 +20,7 @@ use application::service::activitypub::{
 use application::service::block::{BlockAccountUseCase, GetBlocksUseCase, UnblockAccountUseCase};
 use application::service::mute::{GetMutesUseCase, MuteAccountUseCase, UnmuteAccountUseCase};
 use axum::extract::FromRef;
+use kernel::interfaces::permission::AccountRelation;
 use kernel::prelude::entity::AuthAccountId;
 use kernel::KernelError;
 use std::sync::Arc;
@@ -191,6 +195,40 @@ impl AccountApi {
             .get_following(auth_account_id, account_nanoid)
             .await
     }
+
+    pub async fn get_account_members(
+        &self,
+        auth_account_id: &AuthAccountId,
+        account_nanoid: String,
+    ) -> error_stack::Result<Vec<AccountMemberDto>, KernelError> {
+        self.module
+            .get_account_members(auth_account_id, account_nanoid)
+            .await
+    }
+
+    pub async fn grant_account_relation(
+        &self,
+        auth_account_id: &AuthAccountId,
+        account_nanoid: String,
+        member_id: String,
+        relation: AccountRelation,
+    ) -> error_stack::Result<(), KernelError> {
+        self.module
+            .grant_account_relation(auth_account_id, account_nanoid, member_id, relation)
+            .await
+    }
+
+    pub async fn revoke_account_relation(
+        &self,
+        auth_account_id: &AuthAccountId,
+        account_nanoid: String,
+        member_id: String,
+        relation: AccountRelation,
+    ) -> error_stack::Result<(), KernelError> {
+        self.module
+            .revoke_account_relation(auth_account_id, account_nanoid, member_id, relation)
+            .await
+    }
 }
 
 impl FromRef<AppModule> for AccountApi {
@@ -1,6 +1,7 @@
 use crate::error::ErrorStatus;
 use crate::handler::AppModule;
 use application::dto::activitypub::{GetActorDto, GetWebFingerDto, InboxActivityDto};
+use application::permission::without_scope_restriction;
 use application::service::activitypub::{
     GetActorUseCase, GetFollowersCollectionUseCase, GetOutboxUseCase, GetWebFingerUseCase,
     InboxUseCase,
@@ -134,7 +135,8 @@ impl ActivityPubApi {
         &self,
         dto: InboxActivityDto,
     ) -> error_stack::Result<(), KernelError> {
-        self.module.handle_inbox_activity(dto).await
+        // Signed by a remote server, not made with an access token.
+        without_scope_restriction(self.module.handle_inbox_activity(dto)).await
     }
 }
 
@@ -1,8 +1,10 @@
 use super::resolve_auth_account_id;
 use crate::auth::OidcAuthInfo;
 use crate::handler::AppModule;
+use application::dto::account::{InstanceRoleChangeDto, InstanceRoleHolderDto};
 use application::service::account::{
-    AssignInstanceRoleUseCase, BanAccountUseCase, RevokeInstanceRoleUseCase, SuspendAccountUseCase,
+    AssignInstanceRoleUseCase, BanAccountUseCase, GetInstanceRoleHistoryUseCase,
+    GetInstanceRoleHoldersUseCase, RevokeInstanceRoleUseCase, SuspendAccountUseCase,
     UnbanAccountUseCase, UnsuspendAccountUseCase,
 };
 use axum::extract::FromRef;
@@ -90,6 +92,24 @@ impl AdminAccountApi {
             .revoke_instance_role(auth_account_id, account_id, role)
             .await
     }
+
+    pub async fn get_instance_role_holders(
+        &self,
+        auth_account_id: &AuthAccountId,
+    ) -> error_stack::Result<Vec<InstanceRoleHolderDto>, KernelError> {
+        self.module.get_instance_role_holders(auth_account_id).await
+    }
+
+    pub async fn get_instance_role_history(
+        &self,
+        auth_account_id: &AuthAccountId,
+        limit: Option<u32>,
+        cursor: Option<String>,
+    ) -> error_stack::Result<Vec<InstanceRoleChangeDto>, KernelError> {
+        self.module
+            .get_instance_role_history(auth_account_id, limit, cursor)
+            .await
+    }
 }
 
 impl FromRef<AppModule> for AdminAccountApi {
@@ -0,0 +1,94 @@
+use super::resolve_auth_account_id;
+use crate::auth::OidcAuthInfo;
+use crate::handler::AppModule;
+use application::dto::custom_role::{CreateCustomRoleDto, CustomRoleDto, UpdateCustomRoleDto};
+use application::service::account::{
+    AssignCustomRoleUseCase, CreateCustomRoleUseCase, DeleteCustomRoleUseCase,
+    GetCustomRolesUseCase, RevokeCustomRoleUseCase, UpdateCustomRoleUseCase,
+};
+use axum::extract::FromRef;
+use kernel::prelude::entity::AuthAccountId;
+use kernel::KernelError;
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct AdminCustomRoleApi {
+    module: Arc<AppModule>,
+}
+
+impl AdminCustomRoleApi {
+    pub fn new(module: Arc<AppModule>) -> Self {
+        Self { module }
+    }
+
+    pub async fn resolve_auth_account_id(
+        &self,
+        auth_info: OidcAuthInfo,
+    ) -> error_stack::Result<AuthAccountId, KernelError> {
+        resolve_auth_account_id(&self.module, auth_info).await
+    }
+
+    pub async fn create_custom_role(
+        &self,
+        auth_account_id: &AuthAccountId,
+        dto: CreateCustomRoleDto,
+    ) -> error_stack::Result<CustomRoleDto, KernelError> {
+        self.module.create_custom_role(auth_account_id, dto).await
+    }
+
+    pub async fn get_custom_roles(
+        &self,
+        auth_account_id: &AuthAccountId,
+    ) -> error_stack::Result<Vec<CustomRoleDto>, KernelError> {
+        self.module.get_custom_roles(auth_account_id).await
+    }
+
+    pub async fn update_custom_role(
+        &self,
+        auth_account_id: &AuthAccountId,
+        role_id: String,
+        dto: UpdateCustomRoleDto,
+    ) -> error_stack::Result<CustomRoleDto, KernelError> {
+        self.module
+            .update_custom_role(auth_account_id, role_id, dto)
+            .await
+    }
+
+    pub async fn delete_custom_role(
+        &self,
+        auth_account_id: &AuthAccountId,
+        role_id: String,
+    ) -> error_stack::Result<(), KernelError> {
+        self.module
+            .delete_custom_role(auth_account_id, role_id)
+            .await
+    }
+
+    pub async fn assign_custom_role(
+        &self,
+        auth_account_id: &AuthAccountId,
+        account_id: String,
+        role_id: String,
+    ) -> error_stack::Result<(), KernelError> {
+        self.module
+            .assign_custom_role(auth_account_id, account_id, role_id)
+            .await
+    }
+
+    pub async fn revoke_custom_role(
+        &self,
+        auth_account_id: &AuthAccountId,
+        account_id: String,
+        role_id: String,
+    ) -> error_stack::Result<(), KernelError> {
+        self.module
+            .revoke_custom_role(auth_account_id, account_id, role_id)
+            .await
+    }
+}
+
+impl FromRef<AppModule> for AdminCustomRoleApi {
+    fn from_ref(module: &AppModule) -> Self {
+        Self::new(Arc::new(module.clone()))
+    }
+}
@@ -1,6 +1,8 @@
 use super::resolve_auth_account_id;
 use crate::auth::OidcAuthInfo;
 use crate::handler::AppModule;
+use crate::hydra::PreviousConsentSession;
+use crate::kratos::KratosSessionDetail;
 use application::service::session_context::{GetSessionContextUseCase, SessionContext};
 use axum::extract::FromRef;
 use kernel::prelude::entity::AuthAccountId;
@@ -30,6 +32,87 @@ impl MeApi {
     ) -> error_stack::Result<SessionContext, KernelError> {
         self.module.get_session_context(auth_account_id).await
     }
+
+    pub async fn list_login_sessions(
+        &self,
+        identity_id: &str,
+    ) -> Result<Vec<KratosSessionDetail>, reqwest::Error> {
+        self.module
+            .kratos_client()
+            .list_identity_sessions(identity_id)
+            .await
+    }
+
+    /// Revokes one of the identity's sessions. `false` when the identity
+    /// has no active session with that id.
+    pub async fn revoke_login_session(
+        &self,
+        identity_id: &str,
+        session_id: &str,
+    ) -> Result<bool, reqwest::Error> {
+        let kratos = self.module.kratos_client();
+        let owned = kratos
+            .list_identity_sessions(identity_id)
+            .await?
+            .iter()
+            .any(|session| session.id == session_id);
+        if owned {
+            kratos.revoke_session(session_id).await?;
+        }
+        Ok(owned)
+    }
+
+    /// Signs the identity out everywhere: its Kratos sessions and Hydra's
+    /// remembered logins, which would otherwise skip the next sign-in.
+    pub async fn revoke_all_login_sessions(&self, identity_id: &str) -> Result<(), reqwest::Error> {
+        self.module
+            .kratos_client()
+            .revoke_identity_sessions(identity_id)
+            .await?;
+        self.module
+            .hydra_admin_client()
+            .revoke_login_sessions(identity_id)
+            .await
+    }
+
+    pub async fn list_consents(
+        &self,
+        subject: &str,
+    ) -> Result<Vec<PreviousConsentSession>, reqwest::Error> {
+        self.module
+            .hydra_admin_client()
+            .list_consent_sessions(subject)
+            .await
+    }
+
+    /// Revokes the subject's consents for one client. `false` when the
+    /// subject never consented to it.
+    pub async fn revoke_consent(
+        &self,
+        subject: &str,
+        client_id: &str,
+    ) -> Result<bool, reqwest::Error> {
+        let hydra = self.module.hydra_admin_client();
+        let consented = hydra
+            .list_consent_sessions(subject)
+            .await?
+            .iter()
+            .filter_map(|session| session.consent_request.as_ref()?.client.as_ref())
+            .any(|client| client.client_id.as_deref() == Some(client_id));
+        if consented {
+            hydra
+                .revoke_consent_sessions(subject, Some(client_id))
+                .await?;
+        }
+        Ok(consented)
+    }
+
+    pub async fn revoke_all_consents(&self, subject: &str) -> Result<(), reqwest::Error> {
+        self.module
+            .hydra_admin_client()
+            .revoke_consent_sessions(subject, None)
+            .await
+    }
 }
 
 impl FromRef<AppModule> for MeApi {
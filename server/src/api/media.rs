@@ -1,7 +1,16 @@
+use super::resolve_auth_account_id;
+use crate::auth::OidcAuthInfo;
 use crate::handler::AppModule;
-use application::dto::media::{UploadImageDto, UploadedImageDto};
-use application::service::media::UploadImageUseCase;
+use application::dto::media::{
+    BlockImageDto, CreateUploadSessionDto, ImageBlocklistEntryDto, UploadImageDto,
+    UploadSessionDto, UploadedImageDto,
+};
+use application::service::image_moderation::{
+    BlockImageUseCase, ManageImageBlocklistUseCase, ReviewQuarantinedImagesUseCase,
+};
+use application::service::media::{DeleteImageUseCase, DirectUploadUseCase, UploadImageUseCase};
 use axum::extract::FromRef;
+use kernel::prelude::entity::AuthAccountId;
 use kernel::KernelError;
 use std::sync::Arc;
 
@@ -15,11 +24,47 @@ impl MediaApi {
         Self { module }
     }
 
+    pub async fn resolve_auth_account_id(
+        &self,
+        auth_info: OidcAuthInfo,
+    ) -> error_stack::Result<AuthAccountId, KernelError> {
+        resolve_auth_account_id(&self.module, auth_info).await
+    }
+
     pub async fn upload_image(
         &self,
+        auth_account_id: &AuthAccountId,
         dto: UploadImageDto,
     ) -> error_stack::Result<UploadedImageDto, KernelError> {
-        self.module.upload_image(dto).await
+        self.module.upload_image(auth_account_id, dto).await
+    }
+
+    pub async fn create_upload_session(
+        &self,
+        auth_account_id: &AuthAccountId,
+        dto: CreateUploadSessionDto,
+    ) -> error_stack::Result<UploadSessionDto, KernelError> {
+        self.module
+            .create_upload_session(auth_account_id, dto)
+            .await
+    }
+
+    pub async fn finalize_upload_session(
+        &self,
+        auth_account_id: &AuthAccountId,
+        session_id: String,
+    ) -> error_stack::Result<UploadedImageDto, KernelError> {
+        self.module
+            .finalize_upload_session(auth_account_id, session_id)
+            .await
+    }
+
+    pub async fn delete_image(
+        &self,
+        auth_account_id: &AuthAccountId,
+        image_id: String,
+    ) -> error_stack::Result<(), KernelError> {
+        self.module.delete_image(auth_account_id, image_id).await
     }
 }
 
@@ -28,3 +73,104 @@ impl FromRef<AppModule> for MediaApi {
         Self::new(Arc::new(module.clone()))
     }
 }
+
+/// Moderator endpoints for the image blocklist and quarantined uploads.
+#[derive(Clone)]
+pub struct AdminMediaApi {
+    module: Arc<AppModule>,
+}
+
+impl AdminMediaApi {
+    pub fn new(module: Arc<AppModule>) -> Self {
+        Self { module }
+    }
+
+    pub async fn resolve_auth_account_id(
+        &self,
+        auth_info: OidcAuthInfo,
+    ) -> error_stack::Result<AuthAccountId, KernelError> {
+        resolve_auth_account_id(&self.module, auth_info).await
+    }
+
+    pub async fn block_image(
+        &self,
+        auth_account_id: &AuthAccountId,
+        image_id: String,
+        dto: BlockImageDto,
+    ) -> error_stack::Result<ImageBlocklistEntryDto, KernelError> {
+        self.module
+            .block_image(auth_account_id, image_id, dto)
+            .await
+    }
+
+    pub async fn get_image_blocklist(
+        &self,
+        auth_account_id: &AuthAccountId,
+    ) -> error_stack::Result<Vec<ImageBlocklistEntryDto>, KernelError> {
+        self.module.get_image_blocklist(auth_account_id).await
+    }
+
+    pub async fn delete_image_blocklist_entry(
+        &self,
+        auth_account_id: &AuthAccountId,
+        entry_id: String,
+    ) -> error_stack::Result<(), KernelError> {
+        self.module
+            .delete_image_blocklist_entry(auth_account_id, entry_id)
+            .await
+    }
+
+    pub async fn get_quarantined_images(
+        &self,
+        auth_account_id: &AuthAccountId,
+        limit: Option<u32>,
+        cursor: Option<String>,
+    ) -> error_stack::Result<Vec<UploadedImageDto>, KernelError> {
+        self.module
+            .get_quarantined_images(auth_account_id, limit, cursor)
+            .await
+    }
+
+    pub async fn release_quarantined_image(
+        &self,
+        auth_account_id: &AuthAccountId,
+        image_id: String,
+    ) -> error_stack::Result<(), KernelError> {
+        self.module
+            .release_quarantined_image(auth_account_id, image_id)
+            .await
+    }
+}
+
+impl FromRef<AppModule> for AdminMediaApi {
+    fn from_ref(module: &AppModule) -> Self {
+        Self::new(Arc::new(module.clone()))
+    }
+}
+
+/// Reads files of the filesystem media backend for `GET /media/...`.
+#[derive(Clone)]
+pub struct LocalMediaApi {
+    module: Arc<AppModule>,
+}
+
+impl LocalMediaApi {
+    pub fn new(module: Arc<AppModule>) -> Self {
+        Self { module }
+    }
+
+    /// `None` when the file does not exist or media is not stored on the
+    /// local filesystem.
+    pub async fn read(&self, key: &str) -> error_stack::Result<Option<Vec<u8>>, KernelError> {
+        match self.module.local_media_storage() {
+            Some(storage) => storage.read(key).await,
+            None => Ok(None),
+        }
+    }
+}
+
+impl FromRef<AppModule> for LocalMediaApi {
+    fn from_ref(module: &AppModule) -> Self {
+        Self::new(Arc::new(module.clone()))
+    }
+}
@@ -0,0 +1,33 @@
+use crate::handler::AppModule;
+use crate::telemetry::{prometheus_handle, record_pool_usage};
+use axum::extract::FromRef;
+use metrics_exporter_prometheus::PrometheusHandle;
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct MetricsApi {
+    module: Arc<AppModule>,
+    handle: PrometheusHandle,
+}
+
+impl MetricsApi {
+    pub fn new(module: Arc<AppModule>) -> Self {
+        Self {
+            module,
+            handle: prometheus_handle(),
+        }
+    }
+
+    /// Prometheus text exposition of every series, with the pool gauges
+    /// sampled at scrape time.
+    pub fn render(&self) -> String {
+        record_pool_usage(self.module.database_pool_usage());
+        self.handle.render()
+    }
+}
+
+impl FromRef<AppModule> for MetricsApi {
+    fn from_ref(module: &AppModule) -> Self {
+        Self::new(Arc::new(module.clone()))
+    }
+}
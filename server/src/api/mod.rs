@@ -6,10 +6,15 @@
 pub(crate) mod account;
 pub(crate) mod activitypub;
 pub(crate) mod admin_account;
+pub(crate) mod custom_role;
 pub(crate) mod me;
 pub(crate) mod media;
+pub(crate) mod metrics;
 pub(crate) mod oauth2;
+pub(crate) mod personal_access_token;
+pub(crate) mod projection;
 pub(crate) mod signing;
+pub(crate) mod webhook;
 
 use crate::auth::OidcAuthInfo;
 use crate::handler::AppModule;
@@ -26,10 +31,15 @@ use kernel::KernelError;
 pub(crate) use account::AccountApi;
 pub(crate) use activitypub::ActivityPubApi;
 pub(crate) use admin_account::AdminAccountApi;
+pub(crate) use custom_role::AdminCustomRoleApi;
 pub(crate) use me::MeApi;
-pub(crate) use media::MediaApi;
+pub(crate) use media::{AdminMediaApi, LocalMediaApi, MediaApi};
+pub(crate) use metrics::MetricsApi;
 pub(crate) use oauth2::OAuth2Api;
+pub(crate) use personal_access_token::PersonalAccessTokenApi;
+pub(crate) use projection::ProjectionApi;
 pub(crate) use signing::SigningApi;
+pub(crate) use webhook::AdminWebhookApi;
 
 pub(crate) async fn resolve_auth_account_id(
     app: &AppModule,
@@ -0,0 +1,74 @@
+use super::resolve_auth_account_id;
+use crate::auth::OidcAuthInfo;
+use crate::handler::AppModule;
+use application::dto::personal_access_token::{
+    CreatePersonalAccessTokenDto, PersonalAccessTokenDto, PersonalAccessTokenGrantDto,
+};
+use application::service::personal_access_token::{
+    AuthenticatePersonalAccessTokenUseCase, CreatePersonalAccessTokenUseCase,
+    GetPersonalAccessTokensUseCase, RevokePersonalAccessTokenUseCase,
+};
+use axum::extract::FromRef;
+use kernel::prelude::entity::AuthAccountId;
+use kernel::KernelError;
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct PersonalAccessTokenApi {
+    module: Arc<AppModule>,
+}
+
+impl PersonalAccessTokenApi {
+    pub fn new(module: Arc<AppModule>) -> Self {
+        Self { module }
+    }
+
+    pub async fn resolve_auth_account_id(
+        &self,
+        auth_info: OidcAuthInfo,
+    ) -> error_stack::Result<AuthAccountId, KernelError> {
+        resolve_auth_account_id(&self.module, auth_info).await
+    }
+
+    pub async fn create_personal_access_token(
+        &self,
+        auth_account_id: &AuthAccountId,
+        dto: CreatePersonalAccessTokenDto,
+    ) -> error_stack::Result<PersonalAccessTokenDto, KernelError> {
+        self.module
+            .create_personal_access_token(auth_account_id, dto)
+            .await
+    }
+
+    pub async fn get_personal_access_tokens(
+        &self,
+        auth_account_id: &AuthAccountId,
+    ) -> error_stack::Result<Vec<PersonalAccessTokenDto>, KernelError> {
+        self.module
+            .get_personal_access_tokens(auth_account_id)
+            .await
+    }
+
+    pub async fn revoke_personal_access_token(
+        &self,
+        auth_account_id: &AuthAccountId,
+        token_id: String,
+    ) -> error_stack::Result<(), KernelError> {
+        self.module
+            .revoke_personal_access_token(auth_account_id, token_id)
+            .await
+    }
+
+    pub async fn authenticate(
+        &self,
+        token: &str,
+    ) -> error_stack::Result<Option<PersonalAccessTokenGrantDto>, KernelError> {
+        self.module.authenticate_personal_access_token(token).await
+    }
+}
+
+impl FromRef<AppModule> for PersonalAccessTokenApi {
+    fn from_ref(module: &AppModule) -> Self {
+        Self::new(Arc::new(module.clone()))
+    }
+}
@@ -0,0 +1,42 @@
+use crate::handler::AppModule;
+use application::dto::projection::{ProjectionToken, ProjectorHealthDto};
+use application::projection::{GetProjectionHealth, WaitForProjection};
+use axum::extract::FromRef;
+use kernel::KernelError;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Clone)]
+pub struct ProjectionApi {
+    module: Arc<AppModule>,
+}
+
+impl ProjectionApi {
+    pub fn new(module: Arc<AppModule>) -> Self {
+        Self { module }
+    }
+
+    pub async fn projection_health(
+        &self,
+    ) -> error_stack::Result<Vec<ProjectorHealthDto>, KernelError> {
+        self.module.projection_health().await
+    }
+
+    pub async fn projection_token(&self) -> error_stack::Result<ProjectionToken, KernelError> {
+        self.module.projection_token().await
+    }
+
+    pub async fn wait_for_projection(
+        &self,
+        token: ProjectionToken,
+        timeout: Duration,
+    ) -> error_stack::Result<bool, KernelError> {
+        self.module.wait_for_projection(token, timeout).await
+    }
+}
+
+impl FromRef<AppModule> for ProjectionApi {
+    fn from_ref(module: &AppModule) -> Self {
+        Self::new(Arc::new(module.clone()))
+    }
+}
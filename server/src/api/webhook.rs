@@ -0,0 +1,77 @@
+use super::resolve_auth_account_id;
+use crate::auth::OidcAuthInfo;
+use crate::handler::AppModule;
+use application::dto::webhook::{
+    CreateWebhookSubscriptionDto, WebhookDeliveryDto, WebhookSubscriptionDto,
+};
+use application::service::webhook::{
+    CreateWebhookSubscriptionUseCase, DeleteWebhookSubscriptionUseCase,
+    GetWebhookDeliveriesUseCase, GetWebhookSubscriptionsUseCase,
+};
+use axum::extract::FromRef;
+use kernel::prelude::entity::AuthAccountId;
+use kernel::KernelError;
+use std::sync::Arc;
+
+#[derive(Clone)]
+pub struct AdminWebhookApi {
+    module: Arc<AppModule>,
+}
+
+impl AdminWebhookApi {
+    pub fn new(module: Arc<AppModule>) -> Self {
+        Self { module }
+    }
+
+    pub async fn resolve_auth_account_id(
+        &self,
+        auth_info: OidcAuthInfo,
+    ) -> error_stack::Result<AuthAccountId, KernelError> {
+        resolve_auth_account_id(&self.module, auth_info).await
+    }
+
+    pub async fn create_webhook_subscription(
+        &self,
+        auth_account_id: &AuthAccountId,
+        dto: CreateWebhookSubscriptionDto,
+    ) -> error_stack::Result<WebhookSubscriptionDto, KernelError> {
+        self.module
+            .create_webhook_subscription(auth_account_id, dto)
+            .await
+    }
+
+    pub async fn get_webhook_subscriptions(
+        &self,
+        auth_account_id: &AuthAccountId,
+    ) -> error_stack::Result<Vec<WebhookSubscriptionDto>, KernelError> {
+        self.module.get_webhook_subscriptions(auth_account_id).await
+    }
+
+    pub async fn delete_webhook_subscription(
+        &self,
+        auth_account_id: &AuthAccountId,
+        subscription_id: String,
+    ) -> error_stack::Result<(), KernelError> {
+        self.module
+            .delete_webhook_subscription(auth_account_id, subscription_id)
+            .await
+    }
+
+    pub async fn get_webhook_deliveries(
+        &self,
+        auth_account_id: &AuthAccountId,
+        subscription_id: String,
+        limit: Option<u32>,
+        cursor: Option<String>,
+    ) -> error_stack::Result<Vec<WebhookDeliveryDto>, KernelError> {
+        self.module
+            .get_webhook_deliveries(auth_account_id, subscription_id, limit, cursor)
+            .await
+    }
+}
+
+impl FromRef<AppModule> for AdminWebhookApi {
+    fn from_ref(module: &AppModule) -> Self {
+        Self::new(Arc::new(module.clone()))
+    }
+}
@@ -1,13 +1,25 @@
+use crate::api::PersonalAccessTokenApi;
+use crate::hydra::{HydraAdminClient, IntrospectionResponse};
+use crate::telemetry::record_jwks_refresh_failure;
+use application::permission::{with_granted_scopes, with_personal_access_token};
 use axum::body::Body;
 use axum::extract::State;
+use axum::http::{HeaderValue, Method};
 use axum::http::{Request, StatusCode};
 use axum::middleware::Next;
+use axum::response::IntoResponse;
 use axum::response::Response;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use driver::permission::with_request_cache;
 use jsonwebtoken::jwk::KeyAlgorithm;
 use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use kernel::interfaces::permission::Scope;
+use kernel::prelude::entity::{AccountId, PersonalAccessToken};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::sync::{Mutex, RwLock};
 
 // ---------------------------------------------------------------------------
@@ -22,7 +34,8 @@ pub struct OidcConfig {
 }
 
 impl OidcConfig {
-    /// Initialize from environment variables `HYDRA_ISSUER_URL` and `EXPECTED_AUDIENCE`.
+    /// Initialize the primary (Hydra) issuer from environment variables
+    /// `HYDRA_ISSUER_URL` and `EXPECTED_AUDIENCE`.
     pub fn from_env() -> Self {
         let issuer_url = dotenvy::var("HYDRA_ISSUER_URL").unwrap_or_else(|_| {
             let default = "http://localhost:4444".to_string();
@@ -54,6 +67,33 @@ pub struct AuthClaims {
     /// `aud` may be a single string or an array of strings.
     pub aud: OneOrMany,
     pub exp: u64,
+    /// Granted scopes as Hydra writes them into JWT access tokens.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scp: Option<OneOrMany>,
+    /// Space-separated granted scopes (RFC 9068), for issuers without `scp`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+}
+
+impl AuthClaims {
+    /// Emumet scopes the token carries. A token without either claim has
+    /// none.
+    pub fn granted_scopes(&self) -> Vec<Scope> {
+        let scp = match &self.scp {
+            Some(OneOrMany::One(scope)) => vec![scope.as_str()],
+            Some(OneOrMany::Many(scopes)) => scopes.iter().map(String::as_str).collect(),
+            None => Vec::new(),
+        };
+        let scope = self.scope.iter().flat_map(|scope| scope.split_whitespace());
+        let mut granted: Vec<Scope> = scp
+            .into_iter()
+            .chain(scope)
+            .filter_map(Scope::parse)
+            .collect();
+        granted.sort_by_key(|scope| scope.as_str());
+        granted.dedup();
+        granted
+    }
 }
 
 /// Represents a JSON value that can be either a single string or a list of strings.
@@ -80,7 +120,8 @@ impl OneOrMany {
 
 /// Extracted auth info consumed by `resolve_auth_account_id` (task 5.1).
 pub struct OidcAuthInfo {
-    /// Hydra issuer URL → used as `AuthHost.url`
+    /// Trusted issuer URL → used as `AuthHost.url`, so each identity
+    /// provider gets its own `AuthHost`
     pub issuer: String,
     /// Kratos identity UUID → used as `AuthAccount.client_id`
     pub subject: String,
@@ -180,11 +221,13 @@ impl JwksCache {
             Ok(resp) => match resp.json().await {
                 Ok(d) => d,
                 Err(e) => {
+                    record_jwks_refresh_failure("discovery");
                     tracing::warn!("JwksCache: failed to parse OIDC discovery: {e}");
                     return;
                 }
             },
             Err(e) => {
+                record_jwks_refresh_failure("discovery");
                 tracing::warn!("JwksCache: OIDC discovery request failed ({discovery_url}): {e}");
                 return;
             }
@@ -208,11 +251,13 @@ impl JwksCache {
                 }
                 Err(e) => {
                     self.inner.write().await.last_fetch = Instant::now();
+                    record_jwks_refresh_failure("jwks");
                     tracing::warn!("JwksCache: failed to parse JWKS response: {e}");
                 }
             },
             Err(e) => {
                 self.inner.write().await.last_fetch = Instant::now();
+                record_jwks_refresh_failure("jwks");
                 tracing::warn!("JwksCache: JWKS fetch failed ({jwks_uri}): {e}");
             }
         }
@@ -274,6 +319,372 @@ impl JwksCache {
     }
 }
 
+// ---------------------------------------------------------------------------
+// TrustedIssuers
+// ---------------------------------------------------------------------------
+
+/// One identity provider whose JWTs are accepted.
+pub struct TrustedIssuer {
+    pub config: Arc<OidcConfig>,
+    pub jwks_cache: Arc<JwksCache>,
+}
+
+impl TrustedIssuer {
+    pub fn new(config: OidcConfig) -> Self {
+        let jwks_cache = Arc::new(JwksCache::new(
+            config.issuer_url.clone(),
+            Duration::from_secs(config.jwks_refetch_interval_secs),
+        ));
+        Self {
+            config: Arc::new(config),
+            jwks_cache,
+        }
+    }
+}
+
+/// Issuers accepted by [`auth_middleware`], each with its own JWKS and
+/// audience. A token is validated against the issuer named by its `iss`.
+pub struct TrustedIssuers {
+    issuers: Vec<TrustedIssuer>,
+}
+
+impl TrustedIssuers {
+    pub fn new(issuers: Vec<TrustedIssuer>) -> Self {
+        Self { issuers }
+    }
+
+    /// The primary issuer from [`OidcConfig::from_env`], followed by
+    /// `OIDC_ADDITIONAL_ISSUERS`: comma-separated `issuer_url` or
+    /// `issuer_url|audience` entries. An entry without an audience uses the
+    /// primary one.
+    pub fn from_env(primary: OidcConfig) -> Self {
+        let additional = dotenvy::var("OIDC_ADDITIONAL_ISSUERS").unwrap_or_default();
+        let mut configs = vec![];
+        for entry in additional
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+        {
+            let (issuer_url, expected_audience) = match entry.split_once('|') {
+                Some((issuer_url, audience)) => (issuer_url.trim(), audience.trim()),
+                None => (entry, primary.expected_audience.as_str()),
+            };
+            if issuer_url == primary.issuer_url
+                || configs
+                    .iter()
+                    .any(|config: &OidcConfig| config.issuer_url == issuer_url)
+            {
+                tracing::warn!("OIDC_ADDITIONAL_ISSUERS lists {issuer_url} twice, ignoring");
+                continue;
+            }
+            configs.push(OidcConfig {
+                issuer_url: issuer_url.to_string(),
+                expected_audience: expected_audience.to_string(),
+                jwks_refetch_interval_secs: primary.jwks_refetch_interval_secs,
+            });
+        }
+        Self::new(
+            std::iter::once(primary)
+                .chain(configs)
+                .map(TrustedIssuer::new)
+                .collect(),
+        )
+    }
+
+    /// Eager JWKS initialisation for every issuer (non-fatal).
+    pub async fn try_init(&self) {
+        for issuer in &self.issuers {
+            issuer.jwks_cache.try_init().await;
+        }
+    }
+
+    /// The issuer from [`OidcConfig::from_env`], i.e. this instance's Hydra.
+    fn primary_url(&self) -> Option<&str> {
+        self.urls().next()
+    }
+
+    pub fn urls(&self) -> impl Iterator<Item = &str> {
+        self.issuers
+            .iter()
+            .map(|issuer| issuer.config.issuer_url.as_str())
+    }
+
+    /// The issuer named by the token's (still unverified) `iss` claim.
+    fn for_token(&self, token: &str) -> Result<&TrustedIssuer, StatusCode> {
+        #[derive(Deserialize)]
+        struct Issuer {
+            iss: String,
+        }
+
+        let iss = token
+            .split('.')
+            .nth(1)
+            .and_then(|payload| URL_SAFE_NO_PAD.decode(payload).ok())
+            .and_then(|payload| serde_json::from_slice::<Issuer>(&payload).ok())
+            .map(|claims| claims.iss)
+            .ok_or_else(|| {
+                tracing::warn!("auth_middleware: JWT payload has no readable 'iss'");
+                StatusCode::UNAUTHORIZED
+            })?;
+        self.issuers
+            .iter()
+            .find(|issuer| issuer.config.issuer_url == iss)
+            .ok_or_else(|| {
+                tracing::warn!("auth_middleware: JWT issued by untrusted issuer '{iss}'");
+                StatusCode::UNAUTHORIZED
+            })
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Token introspection (opaque access tokens)
+// ---------------------------------------------------------------------------
+
+/// How bearer tokens are validated, from `AUTH_TOKEN_MODE`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenMode {
+    /// JWT access tokens checked against the issuer's JWKS (default).
+    Jwt,
+    /// Opaque access tokens checked with Hydra's admin introspection
+    /// endpoint.
+    Introspection,
+}
+
+impl TokenMode {
+    pub fn from_env() -> Self {
+        match dotenvy::var("AUTH_TOKEN_MODE").as_deref() {
+            Ok("introspection") => TokenMode::Introspection,
+            Ok("jwt") | Err(_) => TokenMode::Jwt,
+            Ok(other) => {
+                tracing::warn!("Unknown AUTH_TOKEN_MODE {other}, using jwt");
+                TokenMode::Jwt
+            }
+        }
+    }
+}
+
+/// Upper bound on cached introspection results; beyond it expired entries
+/// are dropped, then the whole cache.
+const MAX_CACHED_INTROSPECTIONS: usize = 10_000;
+
+const DEFAULT_INTROSPECTION_CACHE_SECS: u64 = 60;
+
+/// Validates opaque tokens with Hydra. Active tokens are cached for the rest
+/// of their lifetime, but at most `max_cache_age`, so a revoked token stops
+/// working within that time. Inactive tokens are never cached.
+pub struct TokenIntrospector {
+    hydra: HydraAdminClient,
+    issuer_url: String,
+    expected_audience: String,
+    max_cache_age: Duration,
+    /// Keyed by the SHA-256 of the token, with the time the entry expires.
+    cache: std::sync::Mutex<HashMap<[u8; 32], (AuthClaims, Instant)>>,
+}
+
+impl TokenIntrospector {
+    pub fn new(hydra: HydraAdminClient, config: &OidcConfig, max_cache_age: Duration) -> Self {
+        Self {
+            hydra,
+            issuer_url: config.issuer_url.clone(),
+            expected_audience: config.expected_audience.clone(),
+            max_cache_age,
+            cache: std::sync::Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// `AUTH_INTROSPECTION_CACHE_SECS` caps how long a result is reused
+    /// (default 60, 0 disables the cache).
+    pub fn from_env(hydra: HydraAdminClient, config: &OidcConfig) -> Self {
+        let max_cache_age = dotenvy::var("AUTH_INTROSPECTION_CACHE_SECS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_INTROSPECTION_CACHE_SECS);
+        Self::new(hydra, config, Duration::from_secs(max_cache_age))
+    }
+
+    pub async fn authenticate(&self, token: &str) -> Result<AuthClaims, StatusCode> {
+        let key: [u8; 32] = Sha256::digest(token.as_bytes()).into();
+        if let Some(claims) = self.cached(&key) {
+            return Ok(claims);
+        }
+
+        let response = self.hydra.introspect_token(token).await.map_err(|e| {
+            tracing::warn!("auth_middleware: token introspection failed: {e}");
+            StatusCode::SERVICE_UNAVAILABLE
+        })?;
+        let claims = self.claims_from(response)?;
+
+        let remaining = claims.exp.saturating_sub(unix_now());
+        let ttl = self.max_cache_age.min(Duration::from_secs(remaining));
+        if !ttl.is_zero() {
+            let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+            if cache.len() >= MAX_CACHED_INTROSPECTIONS {
+                let now = Instant::now();
+                cache.retain(|_, (_, expires_at)| *expires_at > now);
+                if cache.len() >= MAX_CACHED_INTROSPECTIONS {
+                    cache.clear();
+                }
+            }
+            cache.insert(key, (claims.clone(), Instant::now() + ttl));
+        }
+        Ok(claims)
+    }
+
+    fn cached(&self, key: &[u8; 32]) -> Option<AuthClaims> {
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        match cache.get(key) {
+            Some((claims, expires_at)) if *expires_at > Instant::now() => Some(claims.clone()),
+            Some(_) => {
+                cache.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Applies the checks JWT validation does: active access token, issuer,
+    /// audience and expiry.
+    fn claims_from(&self, response: IntrospectionResponse) -> Result<AuthClaims, StatusCode> {
+        let reject = |reason: &str| {
+            tracing::warn!("auth_middleware: introspected token rejected: {reason}");
+            StatusCode::UNAUTHORIZED
+        };
+        if !response.active {
+            return Err(reject("inactive"));
+        }
+        if response
+            .token_use
+            .as_deref()
+            .is_some_and(|token_use| token_use != "access_token")
+        {
+            return Err(reject("not an access token"));
+        }
+        let iss = response.iss.unwrap_or_else(|| self.issuer_url.clone());
+        if iss.trim_end_matches('/') != self.issuer_url.trim_end_matches('/') {
+            return Err(reject("unexpected issuer"));
+        }
+        if !response
+            .aud
+            .iter()
+            .any(|aud| aud == &self.expected_audience)
+        {
+            return Err(reject("unexpected audience"));
+        }
+        let sub = response.sub.ok_or_else(|| reject("no subject"))?;
+        let exp = response.exp.ok_or_else(|| reject("no expiry"))?;
+        if exp <= unix_now() {
+            return Err(reject("expired"));
+        }
+        Ok(AuthClaims {
+            iss,
+            sub,
+            aud: OneOrMany::Many(response.aud),
+            exp,
+            scp: None,
+            scope: response.scope,
+        })
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|now| now.as_secs())
+        .unwrap_or(0)
+}
+
+/// Token validation used by [`auth_middleware`].
+#[derive(Clone)]
+pub enum AuthBackend {
+    Jwt(Arc<TrustedIssuers>),
+    Introspection(Arc<TokenIntrospector>),
+}
+
+impl AuthBackend {
+    /// The issuer whose subjects are this instance's Kratos identities.
+    fn primary_issuer(&self) -> Option<&str> {
+        match self {
+            AuthBackend::Jwt(issuers) => issuers.primary_url(),
+            AuthBackend::Introspection(introspector) => Some(&introspector.issuer_url),
+        }
+    }
+}
+
+/// The token's subject, inserted next to [`AuthClaims`] only for OAuth2
+/// access tokens from the primary issuer, where subjects are Kratos identity
+/// ids. Routes acting on Hydra or Kratos sessions require it, so neither a
+/// subject from another issuer nor a personal access token reaches them.
+#[derive(Debug, Clone)]
+pub struct IdentitySubject(pub String);
+
+fn insert_identity_subject(backend: &AuthBackend, request: &mut Request<Body>) {
+    let Some(primary) = backend.primary_issuer() else {
+        return;
+    };
+    let subject = request
+        .extensions()
+        .get::<AuthClaims>()
+        .filter(|claims| claims.iss.trim_end_matches('/') == primary.trim_end_matches('/'))
+        .map(|claims| IdentitySubject(claims.sub.clone()));
+    if let Some(subject) = subject {
+        request.extensions_mut().insert(subject);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Personal access tokens
+// ---------------------------------------------------------------------------
+
+/// `aud` of the claims made up for personal access tokens.
+pub const PERSONAL_ACCESS_TOKEN_AUDIENCE: &str = "personal-access-token";
+
+/// State of [`auth_middleware`]: OAuth2 access tokens go to `backend`,
+/// tokens with the [`PersonalAccessToken::PREFIX`] to
+/// `personal_access_tokens`.
+#[derive(Clone)]
+pub struct AuthState {
+    pub backend: AuthBackend,
+    pub personal_access_tokens: PersonalAccessTokenApi,
+}
+
+/// Claims for a personal access token, naming its owner's auth host and
+/// subject so routes resolve the owner like for an OAuth2 token, plus the
+/// accounts it is limited to.
+async fn authenticate_personal_access_token(
+    api: &PersonalAccessTokenApi,
+    token: &str,
+) -> Result<(AuthClaims, Option<Vec<AccountId>>), StatusCode> {
+    let grant = api
+        .authenticate(token)
+        .await
+        .map_err(|e| {
+            tracing::warn!("auth_middleware: personal access token lookup failed: {e:?}");
+            StatusCode::SERVICE_UNAVAILABLE
+        })?
+        .ok_or_else(|| {
+            tracing::warn!("auth_middleware: unknown or expired personal access token");
+            StatusCode::UNAUTHORIZED
+        })?;
+    let scope = grant
+        .scopes
+        .iter()
+        .map(Scope::as_str)
+        .collect::<Vec<_>>()
+        .join(" ");
+    let claims = AuthClaims {
+        iss: grant.issuer,
+        sub: grant.subject,
+        aud: OneOrMany::One(PERSONAL_ACCESS_TOKEN_AUDIENCE.to_string()),
+        exp: grant
+            .expires_at
+            .map(|expires_at| expires_at.unix_timestamp().max(0) as u64)
+            .unwrap_or(u64::MAX),
+        scp: None,
+        scope: Some(scope),
+    };
+    Ok((claims, grant.account_ids))
+}
+
 #[cfg(test)]
 pub(crate) struct TestKeys {
     pub(crate) encoding_key: jsonwebtoken::EncodingKey,
@@ -283,7 +694,6 @@ pub(crate) struct TestKeys {
 
 #[cfg(test)]
 pub(crate) fn generate_test_keys() -> TestKeys {
-    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
     use jsonwebtoken::jwk::{
         AlgorithmParameters, CommonParameters, Jwk, KeyAlgorithm, PublicKeyUse, RSAKeyParameters,
     };
@@ -336,21 +746,108 @@ pub(crate) fn encode_test_jwt(
 // auth_middleware (axum middleware layer)
 // ---------------------------------------------------------------------------
 
-/// Axum middleware that validates Bearer JWTs and inserts
+/// Axum middleware that validates Bearer tokens and inserts
 /// [`Extension<AuthClaims>`] into the request.
 ///
 /// Usage with router:
 /// ```ignore
-/// let state = (config, jwks_cache);
+/// let backend = AuthBackend::Jwt(Arc::new(TrustedIssuers::from_env(config)));
+/// let state = AuthState { backend, personal_access_tokens };
 /// router.layer(axum::middleware::from_fn_with_state(state, auth_middleware))
 /// ```
 pub async fn auth_middleware(
-    State((config, jwks_cache)): State<(Arc<OidcConfig>, Arc<JwksCache>)>,
+    State(state): State<AuthState>,
     mut request: Request<Body>,
     next: Next,
 ) -> Result<Response, StatusCode> {
-    auth_middleware_core(config, jwks_cache, &mut request).await?;
-    Ok(next.run(request).await)
+    let token = extract_bearer_token(&request)?;
+    if token.starts_with(PersonalAccessToken::PREFIX) {
+        let (claims, account_ids) =
+            authenticate_personal_access_token(&state.personal_access_tokens, token).await?;
+        request.extensions_mut().insert(claims);
+        let scopes = granted_scopes(&request);
+        return Ok(with_request_cache(with_granted_scopes(
+            scopes,
+            with_personal_access_token(account_ids, next.run(request)),
+        ))
+        .await);
+    }
+    authenticate_access_token(&state.backend, &mut request).await?;
+    insert_identity_subject(&state.backend, &mut request);
+    let scopes = granted_scopes(&request);
+    Ok(with_request_cache(with_granted_scopes(scopes, next.run(request))).await)
+}
+
+/// Validates an OAuth2 access token with `backend` and inserts its claims.
+async fn authenticate_access_token(
+    backend: &AuthBackend,
+    request: &mut Request<Body>,
+) -> Result<(), StatusCode> {
+    match backend {
+        AuthBackend::Jwt(issuers) => {
+            let issuer = issuers.for_token(extract_bearer_token(request)?)?;
+            auth_middleware_core(issuer.config.clone(), issuer.jwks_cache.clone(), request).await
+        }
+        AuthBackend::Introspection(introspector) => {
+            let claims = introspector
+                .authenticate(extract_bearer_token(request)?)
+                .await?;
+            request.extensions_mut().insert(claims);
+            Ok(())
+        }
+    }
+}
+
+fn granted_scopes(request: &Request<Body>) -> Vec<Scope> {
+    request
+        .extensions()
+        .get::<AuthClaims>()
+        .map(AuthClaims::granted_scopes)
+        .unwrap_or_default()
+}
+
+// ---------------------------------------------------------------------------
+// Route scopes
+// ---------------------------------------------------------------------------
+
+/// Route layer for `/api/v1` user routes: reads need `accounts:read`,
+/// everything else `accounts:write`. Use cases additionally check the scope
+/// of every Keto permission they require.
+pub async fn require_account_scope(request: Request<Body>, next: Next) -> Response {
+    let scope = if matches!(*request.method(), Method::GET | Method::HEAD) {
+        Scope::AccountsRead
+    } else {
+        Scope::AccountsWrite
+    };
+    require(scope, request, next).await
+}
+
+/// Route layer requiring one scope for every route, e.g. `admin:moderate`
+/// under `/api/v1/admin` or `sign` under `/internal/v1`.
+pub async fn require_scope(
+    State(scope): State<Scope>,
+    request: Request<Body>,
+    next: Next,
+) -> Response {
+    require(scope, request, next).await
+}
+
+async fn require(scope: Scope, request: Request<Body>, next: Next) -> Response {
+    if granted_scopes(&request).contains(&scope) {
+        return next.run(request).await;
+    }
+    tracing::info!(scope = scope.as_str(), "access token lacks the route scope");
+    let challenge = format!(
+        "Bearer error=\"insufficient_scope\", scope=\"{}\"",
+        scope.as_str()
+    );
+    let mut response = StatusCode::FORBIDDEN.into_response();
+    if let Ok(value) = HeaderValue::from_str(&challenge) {
+        response
+            .headers_mut()
+            .insert(axum::http::header::WWW_AUTHENTICATE, value);
+    }
+    response
 }
 
 /// Core middleware logic: extracts and validates the Bearer token, then
@@ -427,6 +924,8 @@ fn extract_bearer_token(request: &Request<Body>) -> Result<&str, StatusCode> {
 mod tests {
     use super::*;
     use std::time::{SystemTime, UNIX_EPOCH};
+    use wiremock::matchers::{body_string_contains, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
 
     // -----------------------------------------------------------------------
     // Test helpers
@@ -446,6 +945,8 @@ mod tests {
             sub: sub.to_string(),
             aud: OneOrMany::One(aud.to_string()),
             exp,
+            scp: None,
+            scope: None,
         }
     }
 
@@ -567,6 +1068,80 @@ mod tests {
         assert_eq!(result, Err(StatusCode::UNAUTHORIZED));
     }
 
+    #[tokio::test]
+    async fn each_trusted_issuer_uses_its_own_keys_and_audience() {
+        let hydra_keys = generate_test_keys();
+        let partner_keys = generate_test_keys();
+        let hydra = "https://hydra.example.com";
+        let partner = "https://idp.partner.example";
+        let issuers = TrustedIssuers::new(vec![
+            TrustedIssuer {
+                config: make_config(hydra, "emumet"),
+                jwks_cache: Arc::new(JwksCache::new_with_jwks(
+                    hydra.to_string(),
+                    hydra_keys.jwk_set,
+                )),
+            },
+            TrustedIssuer {
+                config: make_config(partner, "emumet-partner"),
+                jwks_cache: Arc::new(JwksCache::new_with_jwks(
+                    partner.to_string(),
+                    partner_keys.jwk_set,
+                )),
+            },
+        ]);
+        let backend = AuthBackend::Jwt(Arc::new(issuers));
+        let call = |token: String| {
+            let backend = backend.clone();
+            async move {
+                let mut request: Request<Body> = Request::builder()
+                    .header("Authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap();
+                authenticate_access_token(&backend, &mut request).await?;
+                let claims = request.extensions().get::<AuthClaims>().unwrap();
+                Ok::<_, StatusCode>(format!("{} {}", claims.iss, claims.sub))
+            }
+        };
+
+        let hydra_token = encode_test_jwt(
+            &make_claims(hydra, "emumet", "kratos-user", 3600),
+            &hydra_keys.encoding_key,
+            &hydra_keys.kid,
+        );
+        assert_eq!(call(hydra_token).await, Ok(format!("{hydra} kratos-user")));
+
+        let partner_claims = make_claims(partner, "emumet-partner", "partner-user", 3600);
+        let partner_token = encode_test_jwt(
+            &partner_claims,
+            &partner_keys.encoding_key,
+            &partner_keys.kid,
+        );
+        assert_eq!(
+            call(partner_token).await,
+            Ok(format!("{partner} partner-user"))
+        );
+
+        // Signed with Hydra's key but claiming the partner as issuer.
+        let forged = encode_test_jwt(&partner_claims, &hydra_keys.encoding_key, &hydra_keys.kid);
+        assert_eq!(call(forged).await, Err(StatusCode::UNAUTHORIZED));
+
+        // The partner's key is only good for the partner's audience.
+        let wrong_audience = encode_test_jwt(
+            &make_claims(partner, "emumet", "partner-user", 3600),
+            &partner_keys.encoding_key,
+            &partner_keys.kid,
+        );
+        assert_eq!(call(wrong_audience).await, Err(StatusCode::UNAUTHORIZED));
+
+        let untrusted = encode_test_jwt(
+            &make_claims("https://evil-issuer.example.com", "emumet", "sub", 3600),
+            &partner_keys.encoding_key,
+            &partner_keys.kid,
+        );
+        assert_eq!(call(untrusted).await, Err(StatusCode::UNAUTHORIZED));
+    }
+
     #[tokio::test]
     async fn oidc_auth_info_from_claims() {
         let claims = AuthClaims {
@@ -574,12 +1149,211 @@ mod tests {
             sub: "kratos-uuid-abc".to_string(),
             aud: OneOrMany::One("emumet".to_string()),
             exp: unix_now() + 3600,
+            scp: None,
+            scope: None,
         };
         let info: OidcAuthInfo = claims.into();
         assert_eq!(info.issuer, "https://hydra.example.com");
         assert_eq!(info.subject, "kratos-uuid-abc");
     }
 
+    #[tokio::test]
+    async fn granted_scopes_are_read_from_scp_and_scope() {
+        let keys = generate_test_keys();
+        let issuer = "https://hydra.example.com";
+        let config = make_config(issuer, "emumet");
+        let cache = Arc::new(JwksCache::new_with_jwks(issuer.to_string(), keys.jwk_set));
+
+        let mut claims = make_claims(issuer, "emumet", "sub", 3600);
+        claims.scp = Some(OneOrMany::Many(vec![
+            "openid".to_string(),
+            "accounts:read".to_string(),
+        ]));
+        claims.scope = Some("sign accounts:read unknown".to_string());
+        let token = encode_test_jwt(&claims, &keys.encoding_key, &keys.kid);
+
+        let decoded = validate(config, cache, &token).await.unwrap();
+        assert_eq!(
+            decoded.granted_scopes(),
+            vec![Scope::AccountsRead, Scope::Sign]
+        );
+        assert!(make_claims(issuer, "emumet", "sub", 3600)
+            .granted_scopes()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn route_scope_layers_reject_tokens_without_the_scope() {
+        use tower::ServiceExt;
+
+        let router = axum::Router::new()
+            .route(
+                "/accounts",
+                axum::routing::get(|| async { "ok" }).post(|| async { "ok" }),
+            )
+            .route_layer(axum::middleware::from_fn(require_account_scope))
+            .route(
+                "/sign",
+                axum::routing::post(|| async { "ok" }).route_layer(
+                    axum::middleware::from_fn_with_state(Scope::Sign, require_scope),
+                ),
+            );
+        let request = |method: &str, uri: &str, scopes: &[&str]| {
+            let mut claims = make_claims("https://hydra.example.com", "emumet", "sub", 3600);
+            claims.scope = Some(scopes.join(" "));
+            let mut request = Request::builder()
+                .method(method)
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap();
+            request.extensions_mut().insert(claims);
+            request
+        };
+
+        for (method, uri, scopes, expected) in [
+            ("GET", "/accounts", &["accounts:read"][..], StatusCode::OK),
+            (
+                "POST",
+                "/accounts",
+                &["accounts:read"][..],
+                StatusCode::FORBIDDEN,
+            ),
+            ("POST", "/accounts", &["accounts:write"][..], StatusCode::OK),
+            (
+                "POST",
+                "/sign",
+                &["accounts:write"][..],
+                StatusCode::FORBIDDEN,
+            ),
+            ("POST", "/sign", &["sign"][..], StatusCode::OK),
+        ] {
+            let response = router
+                .clone()
+                .oneshot(request(method, uri, scopes))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), expected, "{method} {uri} {scopes:?}");
+            if expected == StatusCode::FORBIDDEN {
+                assert!(response
+                    .headers()
+                    .get(axum::http::header::WWW_AUTHENTICATE)
+                    .is_some_and(|value| value.to_str().unwrap().contains("insufficient_scope")));
+            }
+        }
+    }
+
+    fn introspector(hydra_url: String, max_cache_age: Duration) -> TokenIntrospector {
+        let config = OidcConfig {
+            issuer_url: "http://hydra.test/".to_string(),
+            expected_audience: "emumet".to_string(),
+            jwks_refetch_interval_secs: 0,
+        };
+        TokenIntrospector::new(HydraAdminClient::new(hydra_url), &config, max_cache_age)
+    }
+
+    async fn mock_introspection(body: serde_json::Value, expected_calls: u64) -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/admin/oauth2/introspect"))
+            .and(body_string_contains("token=opaque-token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .expect(expected_calls)
+            .mount(&server)
+            .await;
+        server
+    }
+
+    #[tokio::test]
+    async fn introspection_caches_active_tokens() {
+        let server = mock_introspection(
+            serde_json::json!({
+                "active": true,
+                "sub": "user-1",
+                "iss": "http://hydra.test/",
+                "aud": ["emumet"],
+                "exp": unix_now() + 600,
+                "scope": "openid accounts:read",
+                "token_use": "access_token",
+            }),
+            1,
+        )
+        .await;
+        let introspector = introspector(server.uri(), Duration::from_secs(60));
+
+        for _ in 0..2 {
+            let claims = introspector.authenticate("opaque-token").await.unwrap();
+            assert_eq!(claims.sub, "user-1");
+            assert_eq!(claims.granted_scopes(), vec![Scope::AccountsRead]);
+        }
+    }
+
+    #[tokio::test]
+    async fn introspection_rechecks_once_the_cache_age_passes() {
+        let server = mock_introspection(
+            serde_json::json!({
+                "active": true,
+                "sub": "user-1",
+                "iss": "http://hydra.test/",
+                "aud": ["emumet"],
+                "exp": unix_now() + 600,
+            }),
+            2,
+        )
+        .await;
+        let introspector = introspector(server.uri(), Duration::ZERO);
+
+        introspector.authenticate("opaque-token").await.unwrap();
+        introspector.authenticate("opaque-token").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn introspection_rejects_inactive_tokens_without_caching() {
+        let server = mock_introspection(serde_json::json!({ "active": false }), 2).await;
+        let introspector = introspector(server.uri(), Duration::from_secs(60));
+
+        for _ in 0..2 {
+            assert_eq!(
+                introspector.authenticate("opaque-token").await,
+                Err(StatusCode::UNAUTHORIZED)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn introspection_rejects_foreign_audiences_and_refresh_tokens() {
+        for body in [
+            serde_json::json!({
+                "active": true,
+                "sub": "user-1",
+                "aud": ["someone-else"],
+                "exp": unix_now() + 600,
+            }),
+            serde_json::json!({
+                "active": true,
+                "sub": "user-1",
+                "aud": ["emumet"],
+                "exp": unix_now() + 600,
+                "token_use": "refresh_token",
+            }),
+        ] {
+            let server = mock_introspection(body, 1).await;
+            let introspector = introspector(server.uri(), Duration::from_secs(60));
+            assert_eq!(
+                introspector.authenticate("opaque-token").await,
+                Err(StatusCode::UNAUTHORIZED)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn introspection_outage_is_unavailable() {
+        let introspector = introspector("http://127.0.0.1:9".to_string(), Duration::from_secs(60));
+        assert_eq!(
+            introspector.authenticate("opaque-token").await,
+            Err(StatusCode::SERVICE_UNAVAILABLE)
+        );
+    }
+
     #[test]
     fn one_or_many_variants() {
         let one = OneOrMany::One("emumet".to_string());
@@ -0,0 +1,92 @@
+use crate::handler::AppModule;
+use application::change_feed::{PublishChangeFeedBatch, PublishedWindow};
+use application::permission::without_scope_restriction;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+/// Poll-driven change feed publisher (CDC stream) for downstream consumers.
+/// Runs one poll per feed once per interval and stops on shutdown trigger.
+pub struct ChangeFeedWorker {
+    module: Arc<AppModule>,
+    interval: Duration,
+    shutdown: watch::Receiver<bool>,
+    account_window: PublishedWindow,
+    profile_window: PublishedWindow,
+    metadata_window: PublishedWindow,
+}
+
+/// Cooperative shutdown handle for the worker.
+#[derive(Clone)]
+pub struct ChangeFeedShutdown {
+    tx: watch::Sender<bool>,
+}
+
+impl ChangeFeedShutdown {
+    pub fn trigger(&self) {
+        let _ = self.tx.send(true);
+    }
+}
+
+impl ChangeFeedWorker {
+    pub fn spawn(
+        module: Arc<AppModule>,
+        interval: Duration,
+    ) -> (JoinHandle<()>, ChangeFeedShutdown) {
+        let (tx, rx) = watch::channel(false);
+        let worker = Self {
+            module,
+            interval,
+            shutdown: rx,
+            account_window: PublishedWindow::default(),
+            profile_window: PublishedWindow::default(),
+            metadata_window: PublishedWindow::default(),
+        };
+        let handle = tokio::spawn(without_scope_restriction(worker.run()));
+        (handle, ChangeFeedShutdown { tx })
+    }
+
+    async fn run(mut self) {
+        let mut ticker = tokio::time::interval(self.interval);
+        loop {
+            if *self.shutdown.borrow() {
+                break;
+            }
+            tokio::select! {
+                _ = ticker.tick() => {
+                    if let Err(error) = self.module.publish_account_changes(&mut self.account_window).await {
+                        tracing::error!(error = %error, "account change feed batch failed");
+                    }
+                    if let Err(error) = self.module.publish_profile_changes(&mut self.profile_window).await {
+                        tracing::error!(error = %error, "profile change feed batch failed");
+                    }
+                    if let Err(error) = self.module.publish_metadata_changes(&mut self.metadata_window).await {
+                        tracing::error!(error = %error, "metadata change feed batch failed");
+                    }
+                }
+                _ = self.shutdown.changed() => {
+                    if *self.shutdown.borrow() {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Parse `CHANGE_FEED_ENABLED` (default off; the feed needs Redis).
+pub fn change_feed_enabled_from_env() -> bool {
+    dotenvy::var("CHANGE_FEED_ENABLED")
+        .map(|value| value == "1" || value.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+/// Parse `CHANGE_FEED_POLL_INTERVAL_MS` (default 500ms).
+pub fn change_feed_poll_interval_from_env() -> Duration {
+    let millis: u64 = std::env::var("CHANGE_FEED_POLL_INTERVAL_MS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(500);
+    Duration::from_millis(millis)
+}
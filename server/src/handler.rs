@@ -1,19 +1,25 @@
+use crate::change_feed_worker::change_feed_enabled_from_env;
 use crate::hydra::HydraAdminClient;
 use crate::kratos::KratosClient;
+use driver::change_feed::RedisChangeFeedPublisher;
 use driver::crypto::{
     Argon2Encryptor, FilePasswordProvider, Rsa2048RawGenerator, Rsa2048Signer, Rsa2048Verifier,
 };
-use driver::database::PostgresDatabase;
+use driver::database::{PoolUsage, PostgresDatabase, RedisDatabase};
 use driver::http_signing::{HttpSignatureVerifierImpl, HttpSignerImpl};
-use driver::keto::KetoClient;
-use driver::storage::S3ImageStorage;
-use kernel::interfaces::config::{DependOnPublicBaseUrl, PublicBaseUrl};
+use driver::permission::{CachedPermissionStore, ConfiguredPermissionStore};
+use driver::storage::{BlocklistImageScanner, ConfiguredImageStorage, FilesystemImageStorage};
+use kernel::interfaces::change_feed::DependOnChangeFeedPublisher;
+use kernel::interfaces::config::{
+    DependOnMediaQuota, DependOnPublicBaseUrl, MediaQuota, PublicBaseUrl,
+};
 use kernel::interfaces::crypto::{
     DependOnKeyEncryptor, DependOnPasswordProvider, DependOnRawKeyGenerator,
     DependOnSignatureVerifier, DependOnSigner,
 };
 use kernel::interfaces::http_signing::{DependOnHttpSignatureVerifier, DependOnHttpSigner};
 use kernel::interfaces::permission::{DependOnPermissionChecker, DependOnPermissionWriter};
+use kernel::interfaces::projection::{DependOnProjectionRunRegistry, ProjectionRunRegistry};
 use kernel::KernelError;
 
 /// Single wiring root for the HTTP server (ADR 0006 decision 7).
@@ -34,8 +40,12 @@ pub struct AppModule {
     public_base_url: PublicBaseUrl,
     hydra_admin_client: HydraAdminClient,
     kratos_client: KratosClient,
-    keto_client: KetoClient,
-    image_storage: S3ImageStorage,
+    permission_store: CachedPermissionStore<ConfiguredPermissionStore>,
+    image_storage: ConfiguredImageStorage,
+    image_scanner: BlocklistImageScanner,
+    media_quota: MediaQuota,
+    change_feed_publisher: RedisChangeFeedPublisher,
+    projection_run_registry: ProjectionRunRegistry,
 }
 
 impl AppModule {
@@ -44,15 +54,22 @@ impl AppModule {
             dotenvy::var("HYDRA_ADMIN_URL").unwrap_or_else(|_| "http://localhost:4445".to_string());
         let kratos_public_url = dotenvy::var("KRATOS_PUBLIC_URL")
             .unwrap_or_else(|_| "http://localhost:4433".to_string());
-        let keto_read_url =
-            dotenvy::var("KETO_READ_URL").unwrap_or_else(|_| "http://localhost:4466".to_string());
-        let keto_write_url =
-            dotenvy::var("KETO_WRITE_URL").unwrap_or_else(|_| "http://localhost:4467".to_string());
+        let kratos_admin_url = dotenvy::var("KRATOS_ADMIN_URL")
+            .unwrap_or_else(|_| "http://localhost:4434".to_string());
         let public_base_url =
             dotenvy::var("PUBLIC_BASE_URL").unwrap_or_else(|_| "http://localhost:8080".to_string());
 
         let pgpool = PostgresDatabase::new().await?;
-        let image_storage = S3ImageStorage::from_env().await?;
+        let permission_store =
+            CachedPermissionStore::from_env(ConfiguredPermissionStore::from_env(pgpool.clone())?);
+        let image_storage = ConfiguredImageStorage::from_env().await?;
+        let image_scanner = BlocklistImageScanner::from_env(pgpool.clone());
+        // Without the change feed nothing touches Redis, so it stays optional.
+        let redis = if change_feed_enabled_from_env() {
+            RedisDatabase::new()?
+        } else {
+            RedisDatabase::new_noop()?
+        };
 
         Ok(Self {
             pgpool,
@@ -65,9 +82,13 @@ impl AppModule {
             http_signature_verifier: HttpSignatureVerifierImpl::new()?,
             public_base_url: PublicBaseUrl::new(public_base_url),
             hydra_admin_client: HydraAdminClient::new(hydra_admin_url),
-            kratos_client: KratosClient::new(kratos_public_url),
-            keto_client: KetoClient::new(keto_read_url, keto_write_url),
+            kratos_client: KratosClient::new(kratos_public_url, kratos_admin_url),
+            permission_store,
             image_storage,
+            image_scanner,
+            media_quota: media_quota_from_env(),
+            change_feed_publisher: RedisChangeFeedPublisher::from_env(redis),
+            projection_run_registry: ProjectionRunRegistry::default(),
         })
     }
 
@@ -79,7 +100,8 @@ impl AppModule {
         keto_write_url: String,
     ) -> error_stack::Result<Self, KernelError> {
         let pgpool = PostgresDatabase::new().await?;
-        let image_storage = S3ImageStorage::from_env().await?;
+        let image_storage = ConfiguredImageStorage::from_env().await?;
+        let image_scanner = BlocklistImageScanner::from_env(pgpool.clone());
         let public_base_url =
             dotenvy::var("PUBLIC_BASE_URL").unwrap_or_else(|_| "http://localhost:8080".to_string());
         Ok(Self {
@@ -93,9 +115,22 @@ impl AppModule {
             http_signature_verifier: HttpSignatureVerifierImpl::new()?,
             public_base_url: PublicBaseUrl::new(public_base_url),
             hydra_admin_client: HydraAdminClient::new(hydra_admin_url),
-            kratos_client: KratosClient::new(kratos_public_url),
-            keto_client: KetoClient::new(keto_read_url, keto_write_url),
+            // One mock server answers both the public and the admin API.
+            kratos_client: KratosClient::new(kratos_public_url.clone(), kratos_public_url),
+            // Tests swap Keto responses between requests, so only the
+            // per-request cache applies.
+            permission_store: CachedPermissionStore::new(
+                ConfiguredPermissionStore::Keto(driver::keto::KetoClient::new(
+                    keto_read_url,
+                    keto_write_url,
+                )),
+                std::time::Duration::ZERO,
+            ),
             image_storage,
+            image_scanner,
+            media_quota: media_quota_from_env(),
+            change_feed_publisher: RedisChangeFeedPublisher::from_env(RedisDatabase::new_noop()?),
+            projection_run_registry: ProjectionRunRegistry::default(),
         })
     }
 
@@ -124,10 +159,28 @@ impl AppModule {
     pub fn kratos_client(&self) -> &KratosClient {
         &self.kratos_client
     }
+
+    pub fn database_pool_usage(&self) -> PoolUsage {
+        self.pgpool.pool_usage()
+    }
+
+    /// The filesystem media backend, when it is the configured one.
+    pub fn local_media_storage(&self) -> Option<&FilesystemImageStorage> {
+        self.image_storage.filesystem()
+    }
 }
 
 kernel::impl_database_delegation!(AppModule, pgpool, PostgresDatabase);
 
+/// Parse `MEDIA_QUOTA_BYTES` (default 256 MiB per auth account).
+fn media_quota_from_env() -> MediaQuota {
+    let max_bytes = dotenvy::var("MEDIA_QUOTA_BYTES")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(256 * 1024 * 1024);
+    MediaQuota { max_bytes }
+}
+
 impl DependOnPasswordProvider for AppModule {
     type PasswordProvider = FilePasswordProvider;
     fn password_provider(&self) -> &Self::PasswordProvider {
@@ -164,16 +217,16 @@ impl DependOnSignatureVerifier for AppModule {
 }
 
 impl DependOnPermissionChecker for AppModule {
-    type PermissionChecker = KetoClient;
+    type PermissionChecker = CachedPermissionStore<ConfiguredPermissionStore>;
     fn permission_checker(&self) -> &Self::PermissionChecker {
-        &self.keto_client
+        &self.permission_store
     }
 }
 
 impl DependOnPermissionWriter for AppModule {
-    type PermissionWriter = KetoClient;
+    type PermissionWriter = CachedPermissionStore<ConfiguredPermissionStore>;
     fn permission_writer(&self) -> &Self::PermissionWriter {
-        &self.keto_client
+        &self.permission_store
     }
 }
 
@@ -198,9 +251,37 @@ impl DependOnPublicBaseUrl for AppModule {
 }
 
 impl kernel::interfaces::storage::DependOnImageStorage for AppModule {
-    type ImageStorage = S3ImageStorage;
+    type ImageStorage = ConfiguredImageStorage;
 
     fn image_storage(&self) -> &Self::ImageStorage {
         &self.image_storage
     }
 }
+
+impl kernel::interfaces::storage::DependOnImageScanner for AppModule {
+    type ImageScanner = BlocklistImageScanner;
+
+    fn image_scanner(&self) -> &Self::ImageScanner {
+        &self.image_scanner
+    }
+}
+
+impl DependOnMediaQuota for AppModule {
+    fn media_quota(&self) -> &MediaQuota {
+        &self.media_quota
+    }
+}
+
+impl DependOnChangeFeedPublisher for AppModule {
+    type ChangeFeedPublisher = RedisChangeFeedPublisher;
+
+    fn change_feed_publisher(&self) -> &Self::ChangeFeedPublisher {
+        &self.change_feed_publisher
+    }
+}
+
+impl DependOnProjectionRunRegistry for AppModule {
+    fn projection_run_registry(&self) -> &ProjectionRunRegistry {
+        &self.projection_run_registry
+    }
+}
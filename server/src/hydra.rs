@@ -1,5 +1,7 @@
-use reqwest::Client;
+use reqwest::header::{HeaderMap, LINK};
+use reqwest::{Client, StatusCode};
 use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
 use url::Url;
 
 #[derive(Clone)]
@@ -141,6 +143,121 @@ impl HydraAdminClient {
             .json::<RedirectResponse>()
             .await
     }
+
+    /// RFC 7662 introspection of an access or refresh token. Revoked,
+    /// expired and unknown tokens come back with `active: false`.
+    pub async fn introspect_token(
+        &self,
+        token: &str,
+    ) -> Result<IntrospectionResponse, reqwest::Error> {
+        self.http_client
+            .post(format!("{}/admin/oauth2/introspect", self.admin_url))
+            .form(&[("token", token)])
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<IntrospectionResponse>()
+            .await
+    }
+
+    /// Remembered consents of a subject, across all pages. A subject that
+    /// never consented has none.
+    pub async fn list_consent_sessions(
+        &self,
+        subject: &str,
+    ) -> Result<Vec<PreviousConsentSession>, reqwest::Error> {
+        let mut sessions = Vec::new();
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut url = self.build_url("/admin/oauth2/auth/sessions/consent", "subject", subject);
+            if let Some(token) = &page_token {
+                url.query_pairs_mut().append_pair("page_token", token);
+            }
+            let response = self.http_client.get(url).send().await?;
+            if response.status() == StatusCode::NOT_FOUND {
+                return Ok(sessions);
+            }
+            let response = response.error_for_status()?;
+            let next = next_page_token(response.headers());
+            sessions.extend(response.json::<Vec<PreviousConsentSession>>().await?);
+            match next {
+                Some(next) if page_token.as_ref() != Some(&next) => page_token = Some(next),
+                _ => return Ok(sessions),
+            }
+        }
+    }
+
+    /// Revokes the subject's consents for `client_id`, or for every client
+    /// when it is `None`. Tokens issued under those consents are revoked too.
+    pub async fn revoke_consent_sessions(
+        &self,
+        subject: &str,
+        client_id: Option<&str>,
+    ) -> Result<(), reqwest::Error> {
+        let mut url = self.build_url("/admin/oauth2/auth/sessions/consent", "subject", subject);
+        match client_id {
+            Some(client_id) => url.query_pairs_mut().append_pair("client", client_id),
+            None => url.query_pairs_mut().append_pair("all", "true"),
+        };
+        self.http_client
+            .delete(url)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    /// Ends the subject's remembered login, so the next authorization
+    /// request asks them to sign in again.
+    pub async fn revoke_login_sessions(&self, subject: &str) -> Result<(), reqwest::Error> {
+        let url = self.build_url("/admin/oauth2/auth/sessions/login", "subject", subject);
+        self.http_client
+            .delete(url)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+/// The `page_token` of the `rel="next"` entry in an Ory `Link` header.
+/// Hydra and Kratos paginate their admin listings the same way.
+pub(crate) fn next_page_token(headers: &HeaderMap) -> Option<String> {
+    let link = headers.get(LINK)?.to_str().ok()?;
+    link.split(',').find_map(|entry| {
+        let (target, params) = entry.split_once(';')?;
+        if !params
+            .split(';')
+            .any(|param| matches!(param.trim(), "rel=\"next\"" | "rel=next"))
+        {
+            return None;
+        }
+        let target = target.trim().trim_start_matches('<').trim_end_matches('>');
+        // Ory links are usually relative; only the query matters here.
+        let url = Url::parse("http://localhost").ok()?.join(target).ok()?;
+        url.query_pairs()
+            .find(|(key, _)| key == "page_token")
+            .map(|(_, value)| value.into_owned())
+    })
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct IntrospectionResponse {
+    pub active: bool,
+    #[serde(default)]
+    pub sub: Option<String>,
+    #[serde(default)]
+    pub iss: Option<String>,
+    #[serde(default)]
+    pub aud: Vec<String>,
+    #[serde(default)]
+    pub exp: Option<u64>,
+    /// Space-separated granted scopes.
+    #[serde(default)]
+    pub scope: Option<String>,
+    /// `access_token` or `refresh_token`.
+    #[serde(default)]
+    pub token_use: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -218,3 +335,49 @@ pub struct RejectRequest {
 pub struct RedirectResponse {
     pub redirect_to: String,
 }
+
+#[derive(Debug, Deserialize)]
+pub struct PreviousConsentSession {
+    #[serde(default)]
+    pub consent_request: Option<PreviousConsentRequest>,
+    #[serde(default)]
+    pub grant_scope: Vec<String>,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub handled_at: Option<OffsetDateTime>,
+    #[serde(default)]
+    pub remember: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PreviousConsentRequest {
+    #[serde(default)]
+    pub client: Option<OAuth2Client>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn link(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(LINK, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn next_page_token_follows_the_next_link() {
+        let headers = link(
+            "</admin/oauth2/auth/sessions/consent?page_size=250&page_token=first>; rel=\"first\",\
+             </admin/oauth2/auth/sessions/consent?page_size=250&page_token=abc%3D>; rel=\"next\"",
+        );
+        assert_eq!(next_page_token(&headers).as_deref(), Some("abc="));
+    }
+
+    #[test]
+    fn last_page_has_no_next_token() {
+        let headers = link("</sessions?page_token=first>; rel=\"first\"");
+        assert_eq!(next_page_token(&headers), None);
+        assert_eq!(next_page_token(&HeaderMap::new()), None);
+    }
+}
@@ -1,25 +1,43 @@
-use reqwest::Client;
+use reqwest::{Client, StatusCode};
 use serde::Deserialize;
+use time::OffsetDateTime;
 use url::Url;
 
+use crate::hydra::next_page_token;
+
 #[derive(Clone)]
 pub struct KratosClient {
     public_url: String,
+    admin_url: Url,
     http_client: Client,
 }
 
 impl KratosClient {
-    /// Create a new KratosClient. Panics if `public_url` is not a valid URL.
-    pub fn new(public_url: String) -> Self {
+    /// Create a new KratosClient. Panics if either URL is not valid.
+    pub fn new(public_url: String, admin_url: String) -> Self {
         let public_url = public_url.trim_end_matches('/').to_string();
         Url::parse(&public_url)
             .unwrap_or_else(|e| panic!("KRATOS_PUBLIC_URL is not a valid URL ({public_url}): {e}"));
+        let admin_url = Url::parse(admin_url.trim_end_matches('/'))
+            .unwrap_or_else(|e| panic!("KRATOS_ADMIN_URL is not a valid URL ({admin_url}): {e}"));
         Self {
             public_url,
+            admin_url,
             http_client: Client::new(),
         }
     }
 
+    /// Admin API URL with each segment percent-encoded, so identity and
+    /// session ids cannot escape their path position.
+    fn admin_path(&self, segments: &[&str]) -> Url {
+        let mut url = self.admin_url.clone();
+        url.path_segments_mut()
+            .expect("KRATOS_ADMIN_URL is a base URL")
+            .pop_if_empty()
+            .extend(segments);
+        url
+    }
+
     /// Kratos の /sessions/whoami エンドポイントを呼び出し、
     /// 有効なセッションがあれば KratosSession を返す。
     /// セッションがない場合（401）は None を返す。
@@ -53,6 +71,60 @@ impl KratosClient {
         }
         Ok(Some(session))
     }
+
+    /// Active sessions of an identity, across all pages. An unknown
+    /// identity has none.
+    pub async fn list_identity_sessions(
+        &self,
+        identity_id: &str,
+    ) -> Result<Vec<KratosSessionDetail>, reqwest::Error> {
+        let mut sessions = Vec::new();
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut url = self.admin_path(&["admin", "identities", identity_id, "sessions"]);
+            url.query_pairs_mut().append_pair("active", "true");
+            if let Some(token) = &page_token {
+                url.query_pairs_mut().append_pair("page_token", token);
+            }
+            let response = self.http_client.get(url).send().await?;
+            if response.status() == StatusCode::NOT_FOUND {
+                return Ok(sessions);
+            }
+            let response = response.error_for_status()?;
+            let next = next_page_token(response.headers());
+            sessions.extend(response.json::<Vec<KratosSessionDetail>>().await?);
+            match next {
+                Some(next) if page_token.as_ref() != Some(&next) => page_token = Some(next),
+                _ => return Ok(sessions),
+            }
+        }
+    }
+
+    /// Revokes a single session; revoking an unknown one is a no-op.
+    pub async fn revoke_session(&self, session_id: &str) -> Result<(), reqwest::Error> {
+        let response = self
+            .http_client
+            .delete(self.admin_path(&["admin", "sessions", session_id]))
+            .send()
+            .await?;
+        if response.status() != StatusCode::NOT_FOUND {
+            response.error_for_status()?;
+        }
+        Ok(())
+    }
+
+    /// Revokes every session of an identity.
+    pub async fn revoke_identity_sessions(&self, identity_id: &str) -> Result<(), reqwest::Error> {
+        let response = self
+            .http_client
+            .delete(self.admin_path(&["admin", "identities", identity_id, "sessions"]))
+            .send()
+            .await?;
+        if response.status() != StatusCode::NOT_FOUND {
+            response.error_for_status()?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -70,3 +142,32 @@ pub struct KratosIdentity {
     #[serde(default)]
     pub traits: serde_json::Value,
 }
+
+/// A session as returned by the admin API, with the details shown to its owner.
+#[derive(Debug, Clone, Deserialize)]
+pub struct KratosSessionDetail {
+    pub id: String,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub authenticated_at: Option<OffsetDateTime>,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub expires_at: Option<OffsetDateTime>,
+    #[serde(default)]
+    pub authentication_methods: Vec<KratosAuthenticationMethod>,
+    #[serde(default)]
+    pub devices: Vec<KratosSessionDevice>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct KratosAuthenticationMethod {
+    pub method: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct KratosSessionDevice {
+    #[serde(default)]
+    pub ip_address: Option<String>,
+    #[serde(default)]
+    pub user_agent: Option<String>,
+    #[serde(default)]
+    pub location: Option<String>,
+}
@@ -1,28 +1,59 @@
 mod api;
 mod auth;
+mod change_feed_worker;
 mod error;
 mod handler;
 mod hydra;
 mod kratos;
+mod media_gc_worker;
 mod openapi;
 mod projection_worker;
+mod read_your_writes;
+mod remote_media_worker;
 mod route;
 mod schema;
+mod telemetry;
+mod webhook_worker;
 
-use crate::auth::{JwksCache, OidcConfig};
+use crate::api::{PersonalAccessTokenApi, ProjectionApi};
+use crate::auth::{
+    AuthBackend, AuthState, OidcConfig, TokenIntrospector, TokenMode, TrustedIssuers,
+};
+use crate::change_feed_worker::{
+    change_feed_enabled_from_env, change_feed_poll_interval_from_env, ChangeFeedWorker,
+};
 use crate::error::StackTrace;
 use crate::handler::AppModule;
-use crate::projection_worker::{projection_poll_interval_from_env, ProjectionWorker};
+use crate::media_gc_worker::{
+    media_gc_grace_period_from_env, media_gc_interval_from_env, MediaGcWorker,
+};
+use crate::projection_worker::{
+    projection_metrics_interval_from_env, projection_poll_interval_from_env,
+    projection_wait_timeout_from_env, ProjectionWorker,
+};
+use crate::read_your_writes::ReadYourWrites;
+use crate::remote_media_worker::{
+    remote_media_interval_from_env, remote_media_limits_from_env, RemoteMediaWorker,
+};
 use crate::route::account::{AccountRouter, AdminAccountRouter};
 use crate::route::activitypub::{ActivityPubRouter, FederationRouter};
+use crate::route::custom_role::AdminCustomRoleRouter;
+use crate::route::health::HealthRouter;
 use crate::route::me::MeRouter;
-use crate::route::media::MediaRouter;
+use crate::route::media::{AdminMediaRouter, LocalMediaRouter, MediaRouter};
+use crate::route::metrics::MetricsRouter;
 use crate::route::oauth2::OAuth2Router;
+use crate::route::personal_access_token::PersonalAccessTokenRouter;
 use crate::route::signing::SigningRouter;
 #[cfg(feature = "test-mode")]
 use crate::route::test_mode::TestModeRouter;
+use crate::route::webhook::AdminWebhookRouter;
+use crate::webhook_worker::{
+    webhook_delivery_retention_from_env, webhook_poll_interval_from_env, WebhookWorker,
+};
 use axum::http::{header, HeaderValue, Method};
 use error_stack::ResultExt;
+use kernel::interfaces::permission::Scope;
 use kernel::KernelError;
 use std::net::SocketAddr;
 use std::sync::Arc;
@@ -55,6 +86,9 @@ async fn main() -> Result<(), StackTrace> {
         )
         .init();
 
+    // Prometheus recorder for `GET /metrics`; installed before anything records.
+    telemetry::spawn_upkeep(telemetry::prometheus_handle(), Duration::from_secs(5));
+
     // Initialize Snowflake ID generator (must happen before any ID generation)
     let worker_id: u64 = std::env::var("WORKER_ID")
         .ok()
@@ -63,21 +97,64 @@ async fn main() -> Result<(), StackTrace> {
     kernel::init_generator(worker_id);
     tracing::info!(worker_id, "Snowflake ID generator initialized");
 
-    // OIDC / JWT auth setup
-    let oidc_config = OidcConfig::from_env();
-    let jwks_cache = Arc::new(JwksCache::new(
-        oidc_config.issuer_url.clone(),
-        Duration::from_secs(oidc_config.jwks_refetch_interval_secs),
-    ));
-    // Attempt eager JWKS init (non-fatal if Hydra is not yet available).
-    jwks_cache.try_init().await;
-    let oidc_config = Arc::new(oidc_config);
-
     let app = AppModule::new().await?;
 
+    // OIDC auth setup: JWTs against the JWKS, or opaque tokens via Hydra
+    // introspection.
+    let oidc_config = OidcConfig::from_env();
+    let auth_backend = match TokenMode::from_env() {
+        TokenMode::Jwt => {
+            let issuers = TrustedIssuers::from_env(oidc_config);
+            tracing::info!(
+                issuers = ?issuers.urls().collect::<Vec<_>>(),
+                "Accepting JWTs from trusted issuers"
+            );
+            // Attempt eager JWKS init (non-fatal if an issuer is not yet available).
+            issuers.try_init().await;
+            AuthBackend::Jwt(Arc::new(issuers))
+        }
+        TokenMode::Introspection => {
+            tracing::info!("Validating access tokens with Hydra introspection");
+            AuthBackend::Introspection(Arc::new(TokenIntrospector::from_env(
+                app.hydra_admin_client().clone(),
+                &oidc_config,
+            )))
+        }
+    };
+
     // Transactional log tailing worker for account projections (ADR 0006 Stage 3).
-    let (_projection_handle, projection_shutdown) =
-        ProjectionWorker::spawn(Arc::new(app.clone()), projection_poll_interval_from_env());
+    let (_projection_handle, projection_shutdown) = ProjectionWorker::spawn(
+        Arc::new(app.clone()),
+        projection_poll_interval_from_env(),
+        projection_metrics_interval_from_env(),
+    );
+
+    // Change feed (CDC stream) for downstream consumers, opt-in because it
+    // needs Redis.
+    let change_feed_shutdown = change_feed_enabled_from_env().then(|| {
+        ChangeFeedWorker::spawn(Arc::new(app.clone()), change_feed_poll_interval_from_env()).1
+    });
+
+    // Outgoing webhooks, fanned out from the same event logs.
+    let (_webhook_handle, webhook_shutdown) = WebhookWorker::spawn(
+        Arc::new(app.clone()),
+        webhook_poll_interval_from_env(),
+        webhook_delivery_retention_from_env(),
+    );
+
+    // Replaced avatars and never-used uploads.
+    let (_media_gc_handle, media_gc_shutdown) = MediaGcWorker::spawn(
+        Arc::new(app.clone()),
+        media_gc_interval_from_env(),
+        media_gc_grace_period_from_env(),
+    );
+
+    // Local copies of remote actors' icons and banners.
+    let (_remote_media_handle, remote_media_shutdown) = RemoteMediaWorker::spawn(
+        Arc::new(app.clone()),
+        remote_media_interval_from_env(),
+        remote_media_limits_from_env(),
+    );
 
     #[cfg(feature = "test-mode")]
     {
@@ -92,32 +169,90 @@ async fn main() -> Result<(), StackTrace> {
 
     // Routes that require JWT auth (/api/v1, /api/v1/admin, /internal/v1).
     // Admin authorization (Keto instance_moderate) lives inside the use cases.
+    // Each group also requires an OAuth2 scope of the access token:
+    // /me/sessions and /me/consents need sessions; under /admin, moderation
+    // and media need admin:moderate, roles and webhooks admin:administrate.
     let api_v1 = axum::Router::new()
         .route_account()
         .route_me()
+        .route_personal_access_token()
         .route_media()
-        .nest("/admin", axum::Router::new().route_admin_account());
+        .route_layer(axum::middleware::from_fn(auth::require_account_scope))
+        .merge(axum::Router::new().route_me_sessions().route_layer(
+            axum::middleware::from_fn_with_state(Scope::Sessions, auth::require_scope),
+        ))
+        .nest(
+            "/admin",
+            axum::Router::new()
+                .merge(
+                    axum::Router::new()
+                        .route_admin_account()
+                        .route_admin_media()
+                        .route_layer(axum::middleware::from_fn_with_state(
+                            Scope::AdminModerate,
+                            auth::require_scope,
+                        )),
+                )
+                .merge(
+                    axum::Router::new()
+                        .route_admin_instance_role()
+                        .route_admin_custom_role()
+                        .route_admin_webhook()
+                        .route_layer(axum::middleware::from_fn_with_state(
+                            Scope::AdminAdministrate,
+                            auth::require_scope,
+                        )),
+                ),
+        );
 
-    let authed_routes = axum::Router::new()
-        .nest("/api/v1", api_v1)
-        .nest("/internal/v1", axum::Router::new().route_signing())
-        .layer(axum::middleware::from_fn_with_state(
-            (oidc_config, jwks_cache),
-            auth::auth_middleware,
-        ));
+    let authed_routes =
+        axum::Router::new()
+            .nest("/api/v1", api_v1)
+            .nest(
+                "/internal/v1",
+                axum::Router::new().route_signing().route_layer(
+                    axum::middleware::from_fn_with_state(Scope::Sign, auth::require_scope),
+                ),
+            )
+            .layer(axum::middleware::from_fn_with_state(
+                ReadYourWrites::new(
+                    ProjectionApi::new(Arc::new(app.clone())),
+                    projection_wait_timeout_from_env(),
+                ),
+                read_your_writes::read_your_writes,
+            ))
+            .layer(axum::middleware::from_fn_with_state(
+                AuthState {
+                    backend: auth_backend,
+                    personal_access_tokens: PersonalAccessTokenApi::new(Arc::new(app.clone())),
+                },
+                auth::auth_middleware,
+            ));
 
     // Routes that do NOT require JWT auth (OAuth2 Login/Consent Provider,
-    // webfinger, federation under /ap — inbox is HTTP-Signature guarded)
+    // webfinger, federation under /ap — inbox is HTTP-Signature guarded,
+    // health probes, Prometheus scrapes)
     let public_routes = axum::Router::new()
         .route_oauth2()
+        .route_health()
+        .route_metrics()
         .route_activitypub()
         .nest("/ap", axum::Router::new().route_federation());
 
+    // Files of the filesystem media backend, unless a reverse proxy serves
+    // MEDIA_ROOT_DIR itself.
+    let public_routes = if app.local_media_storage().is_some() && serve_local_media_from_env() {
+        public_routes.route_local_media()
+    } else {
+        public_routes
+    };
+
     #[cfg(feature = "test-mode")]
     let public_routes = public_routes.route_test_mode();
 
     let router = authed_routes
         .merge(public_routes)
+        .layer(axum::middleware::from_fn(telemetry::track_http_requests))
         .layer(build_cors_layer())
         .with_state(app);
 
@@ -135,6 +270,12 @@ async fn main() -> Result<(), StackTrace> {
         .change_context_lazy(|| KernelError::Internal)?;
 
     projection_shutdown.trigger();
+    webhook_shutdown.trigger();
+    media_gc_shutdown.trigger();
+    remote_media_shutdown.trigger();
+    if let Some(change_feed_shutdown) = change_feed_shutdown {
+        change_feed_shutdown.trigger();
+    }
 
     Ok(())
 }
@@ -154,3 +295,11 @@ fn build_cors_layer() -> CorsLayer {
         }
     }
 }
+
+/// `MEDIA_SERVE_LOCAL` (default true): serve the filesystem media backend
+/// under `/media`.
+fn serve_local_media_from_env() -> bool {
+    dotenvy::var("MEDIA_SERVE_LOCAL")
+        .map(|value| !(value == "0" || value.eq_ignore_ascii_case("false")))
+        .unwrap_or(true)
+}
@@ -0,0 +1,98 @@
+use crate::handler::AppModule;
+use application::permission::without_scope_restriction;
+use application::service::media::{CollectUnreferencedImages, ExpireUploadSessions};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+/// Periodic media garbage collector: deletes images that no profile or
+/// remote account references once they are older than the grace period,
+/// from Postgres and object storage, and sweeps expired upload sessions
+/// with their staged objects. Image collection waits for the profile
+/// projection to catch up. Stops on shutdown trigger.
+pub struct MediaGcWorker {
+    module: Arc<AppModule>,
+    interval: Duration,
+    grace_period: Duration,
+    shutdown: watch::Receiver<bool>,
+}
+
+/// Cooperative shutdown handle for the worker.
+#[derive(Clone)]
+pub struct MediaGcShutdown {
+    tx: watch::Sender<bool>,
+}
+
+impl MediaGcShutdown {
+    pub fn trigger(&self) {
+        let _ = self.tx.send(true);
+    }
+}
+
+impl MediaGcWorker {
+    pub fn spawn(
+        module: Arc<AppModule>,
+        interval: Duration,
+        grace_period: Duration,
+    ) -> (JoinHandle<()>, MediaGcShutdown) {
+        let (tx, rx) = watch::channel(false);
+        let worker = Self {
+            module,
+            interval,
+            grace_period,
+            shutdown: rx,
+        };
+        let handle = tokio::spawn(without_scope_restriction(worker.run()));
+        (handle, MediaGcShutdown { tx })
+    }
+
+    async fn run(mut self) {
+        let grace_period =
+            time::Duration::try_from(self.grace_period).unwrap_or(time::Duration::MAX);
+        let mut ticker = tokio::time::interval(self.interval);
+        loop {
+            if *self.shutdown.borrow() {
+                break;
+            }
+            tokio::select! {
+                _ = ticker.tick() => {
+                    match self.module.collect_unreferenced_images(grace_period).await {
+                        Ok(0) => {}
+                        Ok(deleted) => tracing::info!(deleted, "unreferenced images collected"),
+                        Err(error) => tracing::error!(error = %error, "media garbage collection failed"),
+                    }
+                    match self.module.expire_upload_sessions().await {
+                        Ok(0) => {}
+                        Ok(removed) => tracing::info!(removed, "expired upload sessions removed"),
+                        Err(error) => tracing::error!(error = %error, "upload session expiry failed"),
+                    }
+                }
+                _ = self.shutdown.changed() => {
+                    if *self.shutdown.borrow() {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Parse `MEDIA_GC_INTERVAL_SECS` (default 1 hour).
+pub fn media_gc_interval_from_env() -> Duration {
+    let secs: u64 = std::env::var("MEDIA_GC_INTERVAL_SECS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(60 * 60);
+    Duration::from_secs(secs)
+}
+
+/// Parse `MEDIA_GC_GRACE_PERIOD_SECS` (default 24 hours): how long an
+/// unreferenced upload is kept before it is collected.
+pub fn media_gc_grace_period_from_env() -> Duration {
+    let secs: u64 = std::env::var("MEDIA_GC_GRACE_PERIOD_SECS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(24 * 60 * 60);
+    Duration::from_secs(secs)
+}
@@ -13,6 +13,9 @@ impl Modify for SecurityAddon {
                     HttpBuilder::new()
                         .scheme(HttpAuthScheme::Bearer)
                         .bearer_format("JWT")
+                        .description(Some(
+                            "Hydra access token, or a personal access token (`emu_pat_...`) from `/api/v1/me/tokens`. `/api/v1` reads need the `accounts:read` scope and writes `accounts:write`, except `/api/v1/me/sessions` and `/api/v1/me/consents`, which need `sessions`; `/api/v1/admin` moderation and media routes need `admin:moderate` and its role, custom role and webhook routes `admin:administrate` (assigning a custom role also needs the scopes of the permissions it bundles) and `/internal/v1` signing needs `sign`. A missing scope is answered with 403 and `WWW-Authenticate: Bearer error=\"insufficient_scope\"`.",
+                        ))
                         .build(),
                 ),
             );
@@ -41,6 +44,14 @@ impl Modify for SecurityAddon {
         crate::route::account::unban_account_by_id,
         crate::route::account::assign_instance_role,
         crate::route::account::revoke_instance_role,
+        crate::route::account::get_instance_role_holders,
+        crate::route::account::get_instance_role_history,
+        crate::route::custom_role::create_custom_role,
+        crate::route::custom_role::get_custom_roles,
+        crate::route::custom_role::update_custom_role,
+        crate::route::custom_role::delete_custom_role,
+        crate::route::custom_role::assign_custom_role,
+        crate::route::custom_role::revoke_custom_role,
         crate::route::account::follow_account,
         crate::route::account::unfollow_account,
         crate::route::account::get_followers,
@@ -51,8 +62,29 @@ impl Modify for SecurityAddon {
         crate::route::account::mute_account,
         crate::route::account::unmute_account,
         crate::route::account::get_mutes,
+        crate::route::account::get_account_members,
+        crate::route::account::grant_account_relation,
+        crate::route::account::revoke_account_relation,
         crate::route::me::get_me,
+        crate::route::me::get_login_sessions,
+        crate::route::me::revoke_login_sessions,
+        crate::route::me::revoke_login_session,
+        crate::route::me::get_consents,
+        crate::route::me::revoke_consents,
+        crate::route::me::revoke_consent,
+        crate::route::personal_access_token::create_personal_access_token,
+        crate::route::personal_access_token::get_personal_access_tokens,
+        crate::route::personal_access_token::revoke_personal_access_token,
         crate::route::media::upload_image,
+        crate::route::media::create_upload_session,
+        crate::route::media::finalize_upload_session,
+        crate::route::media::delete_image,
+        crate::route::media::get_local_media,
+        crate::route::media::block_image,
+        crate::route::media::get_image_blocklist,
+        crate::route::media::delete_image_blocklist_entry,
+        crate::route::media::get_quarantined_images,
+        crate::route::media::release_quarantined_image,
         crate::route::oauth2::login,
         crate::route::oauth2::get_consent,
         crate::route::oauth2::post_consent,
@@ -64,6 +96,12 @@ impl Modify for SecurityAddon {
         crate::route::activitypub::get_outbox,
         crate::route::activitypub::get_followers,
         crate::route::activitypub::get_following,
+        crate::route::webhook::create_webhook_subscription,
+        crate::route::webhook::get_webhook_subscriptions,
+        crate::route::webhook::delete_webhook_subscription,
+        crate::route::webhook::get_webhook_deliveries,
+        crate::route::health::get_projection_health,
+        crate::route::metrics::get_metrics,
     ),
     components(schemas(
         crate::schema::account::CreateAccountRequest,
@@ -76,6 +114,7 @@ impl Modify for SecurityAddon {
         crate::schema::account::AccountsResponse,
         crate::schema::oauth2::OAuth2Response,
         crate::schema::oauth2::ConsentDecision,
+        crate::schema::oauth2::ScopeDetail,
         crate::route::signing::SignRequestBody,
         crate::route::signing::SignResponse,
         crate::route::signing::PublicKeyResponse,
@@ -91,17 +130,54 @@ impl Modify for SecurityAddon {
         crate::schema::account::MuteAccountRequest,
         crate::schema::account::RelationResponse,
         crate::schema::account::RelationListResponse,
+        crate::schema::account::AccountMemberResponse,
+        crate::schema::account::AccountMembersResponse,
+        crate::schema::account::InstanceRoleHolderAccountResponse,
+        crate::schema::account::InstanceRoleHolderResponse,
+        crate::schema::account::InstanceRoleHoldersResponse,
+        crate::schema::account::InstanceRoleChangeResponse,
+        crate::schema::account::InstanceRoleHistoryResponse,
+        crate::schema::custom_role::CreateCustomRoleRequest,
+        crate::schema::custom_role::UpdateCustomRoleRequest,
+        crate::schema::custom_role::CustomRoleResponse,
+        crate::schema::custom_role::CustomRolesResponse,
         crate::schema::me::MeResponse,
+        crate::schema::me::LoginSessionResponse,
+        crate::schema::me::LoginSessionDeviceResponse,
+        crate::schema::me::LoginSessionsResponse,
+        crate::schema::me::ConsentResponse,
+        crate::schema::me::ConsentsResponse,
+        crate::schema::personal_access_token::CreatePersonalAccessTokenRequest,
+        crate::schema::personal_access_token::PersonalAccessTokenResponse,
+        crate::schema::personal_access_token::PersonalAccessTokensResponse,
         crate::schema::media::UploadedImageResponse,
+        crate::schema::media::CreateUploadSessionRequest,
+        crate::schema::media::UploadSessionResponse,
+        crate::schema::media::ImageVariantResponse,
+        crate::schema::media::ImageRenditionResponse,
+        crate::schema::media::BlockImageRequest,
+        crate::schema::media::ImageBlocklistEntryResponse,
+        crate::schema::media::ImageBlocklistResponse,
+        crate::schema::media::QuarantinedImagesResponse,
+        crate::schema::webhook::CreateWebhookSubscriptionRequest,
+        crate::schema::webhook::WebhookSubscriptionResponse,
+        crate::schema::webhook::WebhookSubscriptionsResponse,
+        crate::schema::webhook::WebhookDeliveryResponse,
+        crate::schema::webhook::WebhookDeliveriesResponse,
+        crate::schema::health::ProjectionHealthResponse,
+        crate::schema::health::ProjectorHealthResponse,
     )),
     modifiers(&SecurityAddon),
     tags(
         (name = "Account", description = "Account management"),
+        (name = "Custom Role", description = "Custom roles bundling instance permissions"),
         (name = "Me", description = "Authenticated session"),
         (name = "Media", description = "Image uploads"),
         (name = "OAuth2", description = "OAuth2 Login/Consent Provider"),
         (name = "Signing", description = "HTTP Signature signing"),
         (name = "ActivityPub", description = "ActivityPub discovery and actor endpoints"),
+        (name = "Webhook", description = "Outgoing webhook subscriptions"),
+        (name = "Health", description = "Service health probes and metrics"),
     )
 )]
 #[allow(dead_code)] // utoipa OpenApiマクロ内部で使用される
@@ -178,6 +254,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn session_and_consent_management_contract_is_registered() {
+        let spec: serde_json::Value = serde_json::from_str(&generate_openapi_json())
+            .expect("generated OpenAPI spec is valid JSON");
+        for (path, method) in [
+            ("/api/v1/me/sessions", "get"),
+            ("/api/v1/me/sessions", "delete"),
+            ("/api/v1/me/sessions/{session_id}", "delete"),
+            ("/api/v1/me/consents", "get"),
+            ("/api/v1/me/consents", "delete"),
+            ("/api/v1/me/consents/{client_id}", "delete"),
+        ] {
+            let operation = &spec["paths"][path][method];
+            assert!(operation.is_object(), "{method} {path} must be registered");
+            assert_eq!(
+                operation["security"],
+                serde_json::json!([{"bearer_auth": []}])
+            );
+            for status in ["403", "502"] {
+                assert!(
+                    operation["responses"].get(status).is_some(),
+                    "{method} {path} must document {status}"
+                );
+            }
+        }
+        for schema in ["LoginSessionsResponse", "ConsentsResponse"] {
+            assert!(
+                spec["components"]["schemas"].get(schema).is_some(),
+                "{schema} schema must be registered"
+            );
+        }
+    }
+
     #[test]
     fn follow_management_contract_is_registered() {
         let spec: serde_json::Value = serde_json::from_str(&generate_openapi_json())
@@ -196,6 +305,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn account_member_contract_is_registered() {
+        let spec: serde_json::Value = serde_json::from_str(&generate_openapi_json())
+            .expect("generated OpenAPI spec is valid JSON");
+        for (path, method) in [
+            ("/api/v1/accounts/{account_id}/members", "get"),
+            (
+                "/api/v1/accounts/{account_id}/members/{auth_account_id}/{relation}",
+                "put",
+            ),
+            (
+                "/api/v1/accounts/{account_id}/members/{auth_account_id}/{relation}",
+                "delete",
+            ),
+        ] {
+            let operation = &spec["paths"][path][method];
+            assert!(operation.is_object(), "{method} {path} must be registered");
+            assert_eq!(
+                operation["security"],
+                serde_json::json!([{"bearer_auth": []}])
+            );
+            assert!(
+                operation["responses"].get("403").is_some(),
+                "{method} {path} must document 403"
+            );
+        }
+        assert!(
+            spec["paths"]["/api/v1/accounts/{account_id}/members/{auth_account_id}/{relation}"]
+                ["delete"]["responses"]
+                .get("422")
+                .is_some(),
+            "revoking must document the last-owner safeguard"
+        );
+    }
+
     #[test]
     fn admin_instance_role_contract_is_registered() {
         let spec: serde_json::Value = serde_json::from_str(&generate_openapi_json())
@@ -221,6 +365,173 @@ mod tests {
         }
     }
 
+    #[test]
+    fn admin_instance_role_listing_contract_is_registered() {
+        let spec: serde_json::Value = serde_json::from_str(&generate_openapi_json())
+            .expect("generated OpenAPI spec is valid JSON");
+        for path in ["/api/v1/admin/roles", "/api/v1/admin/roles/history"] {
+            let operation = &spec["paths"][path]["get"];
+            assert!(operation.is_object(), "GET {path} must be registered");
+            assert_eq!(
+                operation["security"],
+                serde_json::json!([{"bearer_auth": []}]),
+                "GET {path} must require bearer authentication"
+            );
+        }
+        assert!(
+            spec["paths"]["/api/v1/admin/accounts/{account_id}/roles/{role}"]["delete"]
+                ["responses"]
+                .get("422")
+                .is_some(),
+            "revoking must document the last-admin safeguard"
+        );
+    }
+
+    #[test]
+    fn admin_webhook_contract_is_registered() {
+        let spec: serde_json::Value = serde_json::from_str(&generate_openapi_json())
+            .expect("generated OpenAPI spec is valid JSON");
+        for (path, method) in [
+            ("/api/v1/admin/webhooks", "post"),
+            ("/api/v1/admin/webhooks", "get"),
+            ("/api/v1/admin/webhooks/{webhook_id}", "delete"),
+            ("/api/v1/admin/webhooks/{webhook_id}/deliveries", "get"),
+        ] {
+            let operation = &spec["paths"][path][method];
+            assert!(operation.is_object(), "{method} {path} must be registered");
+            assert_eq!(
+                operation["security"],
+                serde_json::json!([{"bearer_auth": []}]),
+                "{method} {path} must require bearer authentication"
+            );
+            assert!(
+                operation["responses"].get("403").is_some(),
+                "{method} {path} must document 403"
+            );
+        }
+    }
+
+    #[test]
+    fn admin_custom_role_contract_is_registered() {
+        let spec: serde_json::Value = serde_json::from_str(&generate_openapi_json())
+            .expect("generated OpenAPI spec is valid JSON");
+        for (path, method) in [
+            ("/api/v1/admin/custom-roles", "post"),
+            ("/api/v1/admin/custom-roles", "get"),
+            ("/api/v1/admin/custom-roles/{role_id}", "patch"),
+            ("/api/v1/admin/custom-roles/{role_id}", "delete"),
+            (
+                "/api/v1/admin/accounts/{account_id}/custom-roles/{role_id}",
+                "put",
+            ),
+            (
+                "/api/v1/admin/accounts/{account_id}/custom-roles/{role_id}",
+                "delete",
+            ),
+        ] {
+            let operation = &spec["paths"][path][method];
+            assert!(operation.is_object(), "{method} {path} must be registered");
+            assert_eq!(
+                operation["security"],
+                serde_json::json!([{"bearer_auth": []}]),
+                "{method} {path} must require bearer authentication"
+            );
+            assert!(
+                operation["responses"].get("403").is_some(),
+                "{method} {path} must document 403"
+            );
+        }
+    }
+
+    #[test]
+    fn personal_access_token_contract_is_registered() {
+        let spec: serde_json::Value = serde_json::from_str(&generate_openapi_json())
+            .expect("generated OpenAPI spec is valid JSON");
+        for (path, method) in [
+            ("/api/v1/me/tokens", "post"),
+            ("/api/v1/me/tokens", "get"),
+            ("/api/v1/me/tokens/{token_id}", "delete"),
+        ] {
+            let operation = &spec["paths"][path][method];
+            assert!(operation.is_object(), "{method} {path} must be registered");
+            assert_eq!(
+                operation["security"],
+                serde_json::json!([{"bearer_auth": []}]),
+                "{method} {path} must require bearer authentication"
+            );
+            assert!(
+                operation["responses"].get("403").is_some(),
+                "{method} {path} must document 403"
+            );
+        }
+    }
+
+    #[test]
+    fn admin_media_contract_is_registered() {
+        let spec: serde_json::Value = serde_json::from_str(&generate_openapi_json())
+            .expect("generated OpenAPI spec is valid JSON");
+        for (path, method) in [
+            ("/api/v1/admin/images/{image_id}/block", "post"),
+            ("/api/v1/admin/images/blocklist", "get"),
+            ("/api/v1/admin/images/blocklist/{entry_id}", "delete"),
+            ("/api/v1/admin/images/quarantined", "get"),
+            ("/api/v1/admin/images/{image_id}/release", "post"),
+        ] {
+            let operation = &spec["paths"][path][method];
+            assert!(operation.is_object(), "{method} {path} must be registered");
+            assert_eq!(
+                operation["security"],
+                serde_json::json!([{"bearer_auth": []}]),
+                "{method} {path} must require bearer authentication"
+            );
+            assert!(
+                operation["responses"].get("403").is_some(),
+                "{method} {path} must document 403"
+            );
+        }
+    }
+
+    #[test]
+    fn projection_health_contract_is_public() {
+        let spec: serde_json::Value = serde_json::from_str(&generate_openapi_json())
+            .expect("generated OpenAPI spec is valid JSON");
+        let operation = &spec["paths"]["/health/projections"]["get"];
+
+        assert!(
+            operation.is_object(),
+            "GET /health/projections must be registered"
+        );
+        assert!(
+            operation.get("security").is_none(),
+            "health probes must not require authentication"
+        );
+        for status in ["200", "503"] {
+            assert!(
+                operation["responses"].get(status).is_some(),
+                "GET /health/projections must document {status}"
+            );
+        }
+    }
+
+    #[test]
+    fn metrics_contract_is_public() {
+        let spec: serde_json::Value = serde_json::from_str(&generate_openapi_json())
+            .expect("generated OpenAPI spec is valid JSON");
+        let operation = &spec["paths"]["/metrics"]["get"];
+
+        assert!(operation.is_object(), "GET /metrics must be registered");
+        assert!(
+            operation.get("security").is_none(),
+            "Prometheus scrapes must not require authentication"
+        );
+        assert!(
+            operation["responses"]["200"]["content"]
+                .get("text/plain")
+                .is_some(),
+            "GET /metrics must document the text exposition format"
+        );
+    }
+
     #[test]
     fn media_upload_contract_is_registered() {
         let spec: serde_json::Value = serde_json::from_str(&generate_openapi_json())
@@ -238,5 +549,19 @@ mod tests {
                 .map(|content| content.contains_key("multipart/form-data")),
             Some(true)
         );
+        assert_eq!(
+            spec["paths"]["/api/v1/images/{image_id}"]["delete"]["security"],
+            serde_json::json!([{"bearer_auth": []}])
+        );
+        for path in [
+            "/api/v1/images/upload-sessions",
+            "/api/v1/images/upload-sessions/{session_id}/finalize",
+        ] {
+            assert_eq!(
+                spec["paths"][path]["post"]["security"],
+                serde_json::json!([{"bearer_auth": []}]),
+                "{path}"
+            );
+        }
     }
 }
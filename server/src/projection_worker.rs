@@ -1,15 +1,27 @@
 use crate::handler::AppModule;
-use application::projection::{ProjectAccountBatch, ProjectMetadataBatch, ProjectProfileBatch};
+use crate::telemetry::record_projection_lag;
+use application::permission::without_scope_restriction;
+use application::projection::{
+    GetProjectionHealth, ProjectAccountBatch, ProjectMetadataBatch, ProjectProfileBatch,
+    ACCOUNT_PROJECTOR_NAME, METADATA_PROJECTOR_NAME, PROFILE_PROJECTOR_NAME,
+};
+use kernel::interfaces::projection::DependOnProjectionRunRegistry;
+use kernel::KernelError;
 use std::sync::Arc;
 use std::time::Duration;
+use time::OffsetDateTime;
 use tokio::sync::watch;
 use tokio::task::JoinHandle;
 
 /// Poll-driven tailing worker for account, profile, and metadata projections (ADR 0006).
 /// Runs `project_batch` for each projector once per interval and stops on shutdown trigger.
+/// Every poll outcome is recorded in the projection run registry, and the lag
+/// of each projector is logged as a structured event and exported as a gauge
+/// once per metrics interval.
 pub struct ProjectionWorker {
     module: Arc<AppModule>,
     interval: Duration,
+    metrics_interval: Duration,
     shutdown: watch::Receiver<bool>,
 }
 
@@ -29,34 +41,37 @@ impl ProjectionWorker {
     pub fn spawn(
         module: Arc<AppModule>,
         interval: Duration,
+        metrics_interval: Duration,
     ) -> (JoinHandle<()>, ProjectionShutdown) {
         let (tx, rx) = watch::channel(false);
         let worker = Self {
             module,
             interval,
+            metrics_interval,
             shutdown: rx,
         };
-        let handle = tokio::spawn(worker.run());
+        let handle = tokio::spawn(without_scope_restriction(worker.run()));
         (handle, ProjectionShutdown { tx })
     }
 
     async fn run(mut self) {
         let mut ticker = tokio::time::interval(self.interval);
+        let mut metrics_ticker = tokio::time::interval(self.metrics_interval);
         loop {
             if *self.shutdown.borrow() {
                 break;
             }
             tokio::select! {
                 _ = ticker.tick() => {
-                    if let Err(error) = self.module.project_batch().await {
-                        tracing::error!(error = %error, "account projection tailing batch failed");
-                    }
-                    if let Err(error) = self.module.project_profile_batch().await {
-                        tracing::error!(error = %error, "profile projection tailing batch failed");
-                    }
-                    if let Err(error) = self.module.project_metadata_batch().await {
-                        tracing::error!(error = %error, "metadata projection tailing batch failed");
-                    }
+                    let result = self.module.project_batch().await;
+                    self.record(ACCOUNT_PROJECTOR_NAME, result);
+                    let result = self.module.project_profile_batch().await;
+                    self.record(PROFILE_PROJECTOR_NAME, result);
+                    let result = self.module.project_metadata_batch().await;
+                    self.record(METADATA_PROJECTOR_NAME, result);
+                }
+                _ = metrics_ticker.tick() => {
+                    self.report_lag().await;
                 }
                 _ = self.shutdown.changed() => {
                     if *self.shutdown.borrow() {
@@ -66,6 +81,37 @@ impl ProjectionWorker {
             }
         }
     }
+
+    fn record(&self, projector: &str, result: error_stack::Result<i64, KernelError>) {
+        let registry = self.module.projection_run_registry();
+        let now = OffsetDateTime::now_utc();
+        match result {
+            Ok(_) => registry.record_success(projector, now),
+            Err(error) => {
+                tracing::error!(projector, error = %error, "projection tailing batch failed");
+                registry.record_failure(projector, error.to_string(), now);
+            }
+        }
+    }
+
+    async fn report_lag(&self) {
+        match self.module.projection_health().await {
+            Ok(projectors) => {
+                for projector in projectors {
+                    record_projection_lag(&projector.name, projector.lag, projector.healthy);
+                    tracing::info!(
+                        projector = %projector.name,
+                        checkpoint = projector.checkpoint,
+                        head_seq = projector.head_seq,
+                        lag = projector.lag,
+                        healthy = projector.healthy,
+                        "projection lag"
+                    );
+                }
+            }
+            Err(error) => tracing::warn!(error = %error, "failed to measure projection lag"),
+        }
+    }
 }
 
 /// Parse `PROJECTION_POLL_INTERVAL_MS` (default 100ms; e2e-compatible latency).
@@ -76,3 +122,22 @@ pub fn projection_poll_interval_from_env() -> Duration {
         .unwrap_or(100);
     Duration::from_millis(millis)
 }
+
+/// Parse `PROJECTION_METRICS_INTERVAL_MS` (default 15s).
+pub fn projection_metrics_interval_from_env() -> Duration {
+    let millis: u64 = std::env::var("PROJECTION_METRICS_INTERVAL_MS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(15_000);
+    Duration::from_millis(millis)
+}
+
+/// Parse `PROJECTION_WAIT_TIMEOUT_MS`, the longest a request waits for the
+/// projections to catch up (default 5s).
+pub fn projection_wait_timeout_from_env() -> Duration {
+    let millis: u64 = std::env::var("PROJECTION_WAIT_TIMEOUT_MS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(5_000);
+    Duration::from_millis(millis)
+}
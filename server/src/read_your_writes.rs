@@ -0,0 +1,149 @@
+use crate::api::ProjectionApi;
+use crate::error::ErrorStatus;
+use application::dto::projection::ProjectionToken;
+use axum::extract::{Request, State};
+use axum::http::{HeaderMap, HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use std::time::Duration;
+
+/// Request header asking a write endpoint to respond only after the
+/// projections include the write.
+pub const WAIT_FOR_PROJECTION_HEADER: &str = "x-emumet-wait-for-projection";
+/// Event log heads after a write; sent back on later requests to wait for them.
+pub const PROJECTION_TOKEN_HEADER: &str = "x-emumet-projection-token";
+/// `true`/`false` on responses that waited for the projections.
+pub const PROJECTION_CAUGHT_UP_HEADER: &str = "x-emumet-projection-caught-up";
+
+#[derive(Clone)]
+pub struct ReadYourWrites {
+    api: ProjectionApi,
+    timeout: Duration,
+}
+
+impl ReadYourWrites {
+    pub fn new(api: ProjectionApi, timeout: Duration) -> Self {
+        Self { api, timeout }
+    }
+
+    /// Waiting never fails the request: on timeout or error the response is
+    /// served as is and flagged as not caught up.
+    async fn wait(&self, token: ProjectionToken) -> bool {
+        match self.api.wait_for_projection(token, self.timeout).await {
+            Ok(caught_up) => caught_up,
+            Err(error) => {
+                tracing::warn!(error = %error, "failed to wait for projections");
+                false
+            }
+        }
+    }
+}
+
+/// Read-your-writes middleware for the authenticated API.
+///
+/// A request carrying a projection token waits for it before the handler
+/// runs. A successful write that asked to wait gets the log heads after it as
+/// its token and waits for them before responding.
+pub async fn read_your_writes(
+    State(state): State<ReadYourWrites>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let token = match parse_token(request.headers()) {
+        Ok(token) => token,
+        Err(message) => {
+            return ErrorStatus::from((StatusCode::BAD_REQUEST, message)).into_response()
+        }
+    };
+    let wants_wait = is_write(request.method()) && wants_wait(request.headers());
+
+    let mut caught_up = match token {
+        Some(token) => Some(state.wait(token).await),
+        None => None,
+    };
+    let mut response = next.run(request).await;
+
+    if wants_wait && response.status().is_success() {
+        match state.api.projection_token().await {
+            Ok(token) => {
+                if let Ok(value) = HeaderValue::from_str(&token.to_string()) {
+                    response
+                        .headers_mut()
+                        .insert(PROJECTION_TOKEN_HEADER, value);
+                }
+                caught_up = Some(state.wait(token).await);
+            }
+            Err(error) => {
+                tracing::warn!(error = %error, "failed to read projection token");
+                caught_up = Some(false);
+            }
+        }
+    }
+    if let Some(caught_up) = caught_up {
+        response.headers_mut().insert(
+            PROJECTION_CAUGHT_UP_HEADER,
+            HeaderValue::from_static(if caught_up { "true" } else { "false" }),
+        );
+    }
+    response
+}
+
+fn parse_token(headers: &HeaderMap) -> Result<Option<ProjectionToken>, String> {
+    let Some(value) = headers.get(PROJECTION_TOKEN_HEADER) else {
+        return Ok(None);
+    };
+    value
+        .to_str()
+        .map_err(|_| "Invalid projection token".to_string())?
+        .parse()
+        .map(Some)
+}
+
+fn wants_wait(headers: &HeaderMap) -> bool {
+    headers
+        .get(WAIT_FOR_PROJECTION_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.eq_ignore_ascii_case("true") || value == "1")
+}
+
+fn is_write(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_writes_opt_into_waiting() {
+        let mut headers = HeaderMap::new();
+        assert!(!wants_wait(&headers));
+        headers.insert(WAIT_FOR_PROJECTION_HEADER, HeaderValue::from_static("TRUE"));
+        assert!(wants_wait(&headers));
+        headers.insert(WAIT_FOR_PROJECTION_HEADER, HeaderValue::from_static("no"));
+        assert!(!wants_wait(&headers));
+
+        assert!(is_write(&Method::PATCH));
+        assert!(!is_write(&Method::GET));
+    }
+
+    #[test]
+    fn projection_token_header_is_optional_but_must_be_valid() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(parse_token(&headers), Ok(None));
+        headers.insert(PROJECTION_TOKEN_HEADER, HeaderValue::from_static("1.2.3"));
+        assert_eq!(
+            parse_token(&headers),
+            Ok(Some(ProjectionToken {
+                account_seq: 1,
+                profile_seq: 2,
+                metadata_seq: 3,
+            }))
+        );
+        headers.insert(PROJECTION_TOKEN_HEADER, HeaderValue::from_static("1.2"));
+        assert!(parse_token(&headers).is_err());
+    }
+}
@@ -0,0 +1,114 @@
+use crate::handler::AppModule;
+use application::permission::without_scope_restriction;
+use application::service::activitypub::CacheRemoteMedia;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+/// Periodic remote media cache: fetches the icons and banners of remote
+/// actors seen with new or missing images into local storage, then evicts
+/// copies by age and total size. Stops on shutdown trigger.
+pub struct RemoteMediaWorker {
+    module: Arc<AppModule>,
+    interval: Duration,
+    limits: RemoteMediaLimits,
+    shutdown: watch::Receiver<bool>,
+}
+
+/// Eviction policy of the cache.
+#[derive(Debug, Clone, Copy)]
+pub struct RemoteMediaLimits {
+    pub max_age: Duration,
+    pub max_total_bytes: u64,
+}
+
+/// Cooperative shutdown handle for the worker.
+#[derive(Clone)]
+pub struct RemoteMediaShutdown {
+    tx: watch::Sender<bool>,
+}
+
+impl RemoteMediaShutdown {
+    pub fn trigger(&self) {
+        let _ = self.tx.send(true);
+    }
+}
+
+impl RemoteMediaWorker {
+    pub fn spawn(
+        module: Arc<AppModule>,
+        interval: Duration,
+        limits: RemoteMediaLimits,
+    ) -> (JoinHandle<()>, RemoteMediaShutdown) {
+        let (tx, rx) = watch::channel(false);
+        let worker = Self {
+            module,
+            interval,
+            limits,
+            shutdown: rx,
+        };
+        let handle = tokio::spawn(without_scope_restriction(worker.run()));
+        (handle, RemoteMediaShutdown { tx })
+    }
+
+    async fn run(mut self) {
+        let max_age = time::Duration::try_from(self.limits.max_age).unwrap_or(time::Duration::MAX);
+        let mut ticker = tokio::time::interval(self.interval);
+        loop {
+            if *self.shutdown.borrow() {
+                break;
+            }
+            tokio::select! {
+                _ = ticker.tick() => {
+                    match self.module.cache_remote_media().await {
+                        Ok(0) => {}
+                        Ok(accounts) => tracing::debug!(accounts, "remote media cached"),
+                        Err(error) => tracing::error!(error = %error, "remote media caching failed"),
+                    }
+                    match self
+                        .module
+                        .evict_remote_media(max_age, self.limits.max_total_bytes)
+                        .await
+                    {
+                        Ok(0) => {}
+                        Ok(evicted) => tracing::info!(evicted, "remote media evicted"),
+                        Err(error) => tracing::error!(error = %error, "remote media eviction failed"),
+                    }
+                }
+                _ = self.shutdown.changed() => {
+                    if *self.shutdown.borrow() {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Parse `REMOTE_MEDIA_INTERVAL_SECS` (default 60 seconds).
+pub fn remote_media_interval_from_env() -> Duration {
+    let secs: u64 = std::env::var("REMOTE_MEDIA_INTERVAL_SECS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(60);
+    Duration::from_secs(secs)
+}
+
+/// Parse `REMOTE_MEDIA_MAX_AGE_SECS` (default 30 days): copies of accounts
+/// not seen for this long are evicted; and `REMOTE_MEDIA_MAX_BYTES`
+/// (default 1 GiB): the least recently seen copies are evicted beyond it.
+pub fn remote_media_limits_from_env() -> RemoteMediaLimits {
+    let max_age_secs: u64 = std::env::var("REMOTE_MEDIA_MAX_AGE_SECS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(30 * 24 * 60 * 60);
+    let max_total_bytes = std::env::var("REMOTE_MEDIA_MAX_BYTES")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(1024 * 1024 * 1024);
+    RemoteMediaLimits {
+        max_age: Duration::from_secs(max_age_secs),
+        max_total_bytes,
+    }
+}
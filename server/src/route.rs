@@ -4,10 +4,15 @@ use axum::http::StatusCode;
 
 pub mod account;
 pub mod activitypub;
+pub mod custom_role;
+pub mod health;
 pub mod me;
 pub mod media;
+pub mod metrics;
 pub mod oauth2;
+pub mod personal_access_token;
 pub mod signing;
+pub mod webhook;
 
 #[cfg(feature = "test-mode")]
 pub mod test_mode;
@@ -72,34 +77,107 @@ pub(crate) fn build_test_router_with_auth(
     app: crate::handler::AppModule,
     oidc_config: std::sync::Arc<crate::auth::OidcConfig>,
     jwks_cache: std::sync::Arc<crate::auth::JwksCache>,
+) -> axum::Router {
+    use crate::auth::{AuthBackend, TrustedIssuer, TrustedIssuers};
+    let issuers = TrustedIssuers::new(vec![TrustedIssuer {
+        config: oidc_config,
+        jwks_cache,
+    }]);
+    build_test_router_with_backend(app, AuthBackend::Jwt(std::sync::Arc::new(issuers)))
+}
+
+#[cfg(test)]
+pub(crate) fn build_test_router_with_backend(
+    app: crate::handler::AppModule,
+    auth_backend: crate::auth::AuthBackend,
 ) -> axum::Router {
     use crate::route::account::{AccountRouter, AdminAccountRouter};
     use crate::route::activitypub::{ActivityPubRouter, FederationRouter};
+    use crate::route::custom_role::AdminCustomRoleRouter;
+    use crate::route::health::HealthRouter;
     use crate::route::me::MeRouter;
-    use crate::route::media::MediaRouter;
+    use crate::route::media::{AdminMediaRouter, MediaRouter};
+    use crate::route::metrics::MetricsRouter;
     use crate::route::oauth2::OAuth2Router;
+    use crate::route::personal_access_token::PersonalAccessTokenRouter;
     use crate::route::signing::SigningRouter;
+    use crate::route::webhook::AdminWebhookRouter;
+    use kernel::interfaces::permission::Scope;
 
     let api_v1 = axum::Router::new()
         .route_account()
         .route_me()
+        .route_personal_access_token()
         .route_media()
-        .nest("/admin", axum::Router::new().route_admin_account());
-
-    let authed_routes = axum::Router::new()
-        .nest("/api/v1", api_v1)
-        .nest("/internal/v1", axum::Router::new().route_signing())
-        .layer(axum::middleware::from_fn_with_state(
-            (oidc_config, jwks_cache),
-            crate::auth::auth_middleware,
-        ));
+        .route_layer(axum::middleware::from_fn(
+            crate::auth::require_account_scope,
+        ))
+        .merge(axum::Router::new().route_me_sessions().route_layer(
+            axum::middleware::from_fn_with_state(Scope::Sessions, crate::auth::require_scope),
+        ))
+        .nest(
+            "/admin",
+            axum::Router::new()
+                .merge(
+                    axum::Router::new()
+                        .route_admin_account()
+                        .route_admin_media()
+                        .route_layer(axum::middleware::from_fn_with_state(
+                            Scope::AdminModerate,
+                            crate::auth::require_scope,
+                        )),
+                )
+                .merge(
+                    axum::Router::new()
+                        .route_admin_instance_role()
+                        .route_admin_custom_role()
+                        .route_admin_webhook()
+                        .route_layer(axum::middleware::from_fn_with_state(
+                            Scope::AdminAdministrate,
+                            crate::auth::require_scope,
+                        )),
+                ),
+        );
+
+    let authed_routes =
+        axum::Router::new()
+            .nest("/api/v1", api_v1)
+            .nest(
+                "/internal/v1",
+                axum::Router::new().route_signing().route_layer(
+                    axum::middleware::from_fn_with_state(Scope::Sign, crate::auth::require_scope),
+                ),
+            )
+            .layer(axum::middleware::from_fn_with_state(
+                crate::read_your_writes::ReadYourWrites::new(
+                    crate::api::ProjectionApi::new(std::sync::Arc::new(app.clone())),
+                    std::time::Duration::from_secs(1),
+                ),
+                crate::read_your_writes::read_your_writes,
+            ))
+            .layer(axum::middleware::from_fn_with_state(
+                crate::auth::AuthState {
+                    backend: auth_backend,
+                    personal_access_tokens: crate::api::PersonalAccessTokenApi::new(
+                        std::sync::Arc::new(app.clone()),
+                    ),
+                },
+                crate::auth::auth_middleware,
+            ));
 
     let public_routes = axum::Router::new()
         .route_oauth2()
+        .route_health()
+        .route_metrics()
         .route_activitypub()
         .nest("/ap", axum::Router::new().route_federation());
 
-    authed_routes.merge(public_routes).with_state(app)
+    authed_routes
+        .merge(public_routes)
+        .layer(axum::middleware::from_fn(
+            crate::telemetry::track_http_requests,
+        ))
+        .with_state(app)
 }
 
 #[cfg(test)]
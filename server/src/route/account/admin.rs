@@ -1,8 +1,11 @@
 use crate::api::AdminAccountApi;
 use crate::auth::{AuthClaims, OidcAuthInfo};
 use crate::error::ErrorStatus;
-use crate::schema::account::{BanAccountRequest, SuspendAccountRequest};
-use axum::extract::{Path, State};
+use crate::schema::account::{
+    BanAccountRequest, GetInstanceRoleHistoryQuery, InstanceRoleHistoryResponse,
+    InstanceRoleHoldersResponse, SuspendAccountRequest,
+};
+use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::{Extension, Json};
 use kernel::interfaces::permission::InstanceRole;
@@ -229,7 +232,7 @@ pub(crate) async fn assign_instance_role(
 #[utoipa::path(
     delete,
     path = "/api/v1/admin/accounts/{account_id}/roles/{role}",
-    description = "Revoke an instance role (admin or moderator) from the owner of an account. Admins cannot revoke their own admin role.",
+    description = "Revoke an instance role (admin or moderator) from the owner of an account. Admins cannot revoke their own admin role, nor the last remaining admin.",
     params(
         ("account_id" = String, Path, description = "Account nanoid"),
         ("role" = String, Path, description = "Instance role: admin or moderator"),
@@ -239,6 +242,7 @@ pub(crate) async fn assign_instance_role(
         (status = 400, description = "Invalid request"),
         (status = 403, description = "Permission denied"),
         (status = 404, description = "Account not found"),
+        (status = 422, description = "Own admin role or last remaining admin"),
     ),
     security(("bearer_auth" = [])),
     tag = "Account",
@@ -270,3 +274,65 @@ pub(crate) async fn revoke_instance_role(
 
     Ok(StatusCode::NO_CONTENT)
 }
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/roles",
+    description = "Every auth account holding an instance role, with the accounts linked to it. Someone holding both roles is listed once per role.",
+    responses(
+        (status = 200, description = "Instance role holders", body = InstanceRoleHoldersResponse),
+        (status = 403, description = "Permission denied"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Account",
+)]
+pub(crate) async fn get_instance_role_holders(
+    Extension(claims): Extension<AuthClaims>,
+    State(api): State<AdminAccountApi>,
+) -> Result<Json<InstanceRoleHoldersResponse>, ErrorStatus> {
+    let auth_account_id = api
+        .resolve_auth_account_id(OidcAuthInfo::from(claims))
+        .await
+        .map_err(ErrorStatus::from)?;
+    let holders = api
+        .get_instance_role_holders(&auth_account_id)
+        .await
+        .map_err(ErrorStatus::from)?;
+    Ok(Json(InstanceRoleHoldersResponse {
+        items: holders.into_iter().map(Into::into).collect(),
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/roles/history",
+    description = "Instance role grants and revocations, newest first.",
+    params(
+        ("limit" = Option<u32>, Query, description = "Page size (1-100, default 20)"),
+        ("cursor" = Option<String>, Query, description = "ID of the last change of the previous page"),
+    ),
+    responses(
+        (status = 200, description = "Instance role changes", body = InstanceRoleHistoryResponse),
+        (status = 400, description = "Invalid cursor"),
+        (status = 403, description = "Permission denied"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Account",
+)]
+pub(crate) async fn get_instance_role_history(
+    Extension(claims): Extension<AuthClaims>,
+    State(api): State<AdminAccountApi>,
+    Query(GetInstanceRoleHistoryQuery { limit, cursor }): Query<GetInstanceRoleHistoryQuery>,
+) -> Result<Json<InstanceRoleHistoryResponse>, ErrorStatus> {
+    let auth_account_id = api
+        .resolve_auth_account_id(OidcAuthInfo::from(claims))
+        .await
+        .map_err(ErrorStatus::from)?;
+    let changes = api
+        .get_instance_role_history(&auth_account_id, limit, cursor)
+        .await
+        .map_err(ErrorStatus::from)?;
+    Ok(Json(InstanceRoleHistoryResponse {
+        items: changes.into_iter().map(Into::into).collect(),
+    }))
+}
@@ -152,7 +152,7 @@ pub(crate) async fn create_account(
 #[utoipa::path(
     patch,
     path = "/api/v1/accounts/{account_id}",
-    description = "Update account properties.",
+    description = "Update account properties. `icon_description`, `icon_focus`, `banner_description` and `banner_focus` set the alt text and focal point of the image in that slot after the update; they belong to this account's profile, so other accounts using the same image keep their own. A newly set image starts from the values the caller uploaded it with. They are federated with the actor's `icon` and `image`.",
     params(("account_id" = String, Path, description = "Account nanoid")),
     request_body = UpdateAccountRequest,
     responses(
@@ -0,0 +1,176 @@
+use crate::api::AccountApi;
+use crate::auth::{AuthClaims, OidcAuthInfo};
+use crate::error::ErrorStatus;
+use crate::schema::account::{AccountMemberResponse, AccountMembersResponse};
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::{Extension, Json};
+use kernel::interfaces::permission::AccountRelation;
+
+fn parse_account_relation(relation: &str) -> Result<AccountRelation, ErrorStatus> {
+    match relation {
+        "owner" => Ok(AccountRelation::Owner),
+        "editor" => Ok(AccountRelation::Editor),
+        "signer" => Ok(AccountRelation::Signer),
+        _ => Err(ErrorStatus::from((
+            StatusCode::BAD_REQUEST,
+            format!(
+                "invalid account relation: expected \"owner\", \"editor\" or \"signer\": {relation}"
+            ),
+        ))),
+    }
+}
+
+fn account_relation_name(relation: AccountRelation) -> &'static str {
+    match relation {
+        AccountRelation::Owner => "owner",
+        AccountRelation::Editor => "editor",
+        AccountRelation::Signer => "signer",
+    }
+}
+
+fn validate_account_id(account_id: &str) -> Result<(), ErrorStatus> {
+    if account_id.trim().is_empty() {
+        return Err(ErrorStatus::from((
+            StatusCode::BAD_REQUEST,
+            "Account ID cannot be empty".to_string(),
+        )));
+    }
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/accounts/{account_id}/members",
+    description = "List who holds which relation on an account: owners can do everything, editors can view and edit it, signers can view it and sign with its keys. Only owners can see or change this list; listing needs the `accounts:write` scope like the changes do.",
+    params(("account_id" = String, Path, description = "Account nanoid")),
+    responses(
+        (status = 200, description = "Account members", body = AccountMembersResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 403, description = "Caller does not own the account"),
+        (status = 404, description = "Account not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Account",
+)]
+pub(crate) async fn get_account_members(
+    Extension(claims): Extension<AuthClaims>,
+    State(api): State<AccountApi>,
+    Path(account_id): Path<String>,
+) -> Result<Json<AccountMembersResponse>, ErrorStatus> {
+    validate_account_id(&account_id)?;
+    let auth_account_id = api
+        .resolve_auth_account_id(OidcAuthInfo::from(claims))
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    let members = api
+        .get_account_members(&auth_account_id, account_id)
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    Ok(Json(AccountMembersResponse {
+        items: members
+            .into_iter()
+            .map(|member| AccountMemberResponse {
+                auth_account_id: member.auth_account_id,
+                relation: account_relation_name(member.relation).to_string(),
+            })
+            .collect(),
+    }))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/accounts/{account_id}/members/{auth_account_id}/{relation}",
+    description = "Give another auth account a relation on an account. Granting a relation it already holds does nothing.",
+    params(
+        ("account_id" = String, Path, description = "Account nanoid"),
+        ("auth_account_id" = String, Path, description = "The `account_id` of the member's `GET /api/v1/me`"),
+        ("relation" = String, Path, description = "Account relation: owner, editor or signer"),
+    ),
+    responses(
+        (status = 204, description = "Relation granted"),
+        (status = 400, description = "Invalid request"),
+        (status = 403, description = "Caller does not own the account"),
+        (status = 404, description = "Account or auth account not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Account",
+)]
+pub(crate) async fn grant_account_relation(
+    Extension(claims): Extension<AuthClaims>,
+    State(api): State<AccountApi>,
+    Path((account_id, member_id, relation)): Path<(String, String, String)>,
+) -> Result<StatusCode, ErrorStatus> {
+    validate_account_id(&account_id)?;
+    let relation = parse_account_relation(&relation)?;
+    let auth_account_id = api
+        .resolve_auth_account_id(OidcAuthInfo::from(claims))
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    api.grant_account_relation(&auth_account_id, account_id, member_id, relation)
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/accounts/{account_id}/members/{auth_account_id}/{relation}",
+    description = "Take a relation on an account away from an auth account, including the caller's own. The last owner cannot be removed.",
+    params(
+        ("account_id" = String, Path, description = "Account nanoid"),
+        ("auth_account_id" = String, Path, description = "The `account_id` of the member's `GET /api/v1/me`"),
+        ("relation" = String, Path, description = "Account relation: owner, editor or signer"),
+    ),
+    responses(
+        (status = 204, description = "Relation revoked"),
+        (status = 400, description = "Invalid request"),
+        (status = 403, description = "Caller does not own the account"),
+        (status = 404, description = "Account not found, or the auth account does not hold the relation"),
+        (status = 422, description = "The relation is the account's last owner"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Account",
+)]
+pub(crate) async fn revoke_account_relation(
+    Extension(claims): Extension<AuthClaims>,
+    State(api): State<AccountApi>,
+    Path((account_id, member_id, relation)): Path<(String, String, String)>,
+) -> Result<StatusCode, ErrorStatus> {
+    validate_account_id(&account_id)?;
+    let relation = parse_account_relation(&relation)?;
+    let auth_account_id = api
+        .resolve_auth_account_id(OidcAuthInfo::from(claims))
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    api.revoke_account_relation(&auth_account_id, account_id, member_id, relation)
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn account_relations_round_trip_through_their_api_names() {
+        for relation in [
+            AccountRelation::Owner,
+            AccountRelation::Editor,
+            AccountRelation::Signer,
+        ] {
+            assert_eq!(
+                parse_account_relation(account_relation_name(relation)).ok(),
+                Some(relation)
+            );
+        }
+        assert!(parse_account_relation("owners").is_err());
+    }
+}
@@ -3,12 +3,14 @@ mod block_mute;
 mod client;
 mod follow;
 mod follow_relations;
+mod member;
 mod unfollow;
 pub(crate) use admin::{
-    __path_assign_instance_role, __path_ban_account_by_id, __path_revoke_instance_role,
-    __path_suspend_account_by_id, __path_unban_account_by_id, __path_unsuspend_account_by_id,
-    assign_instance_role, ban_account_by_id, revoke_instance_role, suspend_account_by_id,
-    unban_account_by_id, unsuspend_account_by_id,
+    __path_assign_instance_role, __path_ban_account_by_id, __path_get_instance_role_history,
+    __path_get_instance_role_holders, __path_revoke_instance_role, __path_suspend_account_by_id,
+    __path_unban_account_by_id, __path_unsuspend_account_by_id, assign_instance_role,
+    ban_account_by_id, get_instance_role_history, get_instance_role_holders, revoke_instance_role,
+    suspend_account_by_id, unban_account_by_id, unsuspend_account_by_id,
 };
 pub(crate) use block_mute::{
     __path_block_account, __path_get_blocks, __path_get_mutes, __path_mute_account,
@@ -25,6 +27,10 @@ pub(crate) use follow::{__path_follow_account, follow_account};
 pub(crate) use follow_relations::{
     __path_get_followers, __path_get_following, get_followers, get_following,
 };
+pub(crate) use member::{
+    __path_get_account_members, __path_grant_account_relation, __path_revoke_account_relation,
+    get_account_members, grant_account_relation, revoke_account_relation,
+};
 pub(crate) use unfollow::{__path_unfollow_account, unfollow_account};
 
 use crate::handler::AppModule;
@@ -37,6 +43,7 @@ pub trait AccountRouter {
 
 pub trait AdminAccountRouter {
     fn route_admin_account(self) -> Self;
+    fn route_admin_instance_role(self) -> Self;
 }
 
 impl AccountRouter for Router<AppModule> {
@@ -60,6 +67,11 @@ impl AccountRouter for Router<AppModule> {
             .route("/accounts/{account_id}/mute", post(mute_account))
             .route("/accounts/{account_id}/unmute", post(unmute_account))
             .route("/accounts/{account_id}/mutes", get(get_mutes))
+            .route("/accounts/{account_id}/members", get(get_account_members))
+            .route(
+                "/accounts/{account_id}/members/{auth_account_id}/{relation}",
+                put(grant_account_relation).delete(revoke_account_relation),
+            )
     }
 }
 
@@ -75,9 +87,14 @@ impl AdminAccountRouter for Router<AppModule> {
         )
         .route("/accounts/{account_id}/ban", post(ban_account_by_id))
         .route("/accounts/{account_id}/unban", post(unban_account_by_id))
-        .route(
+    }
+
+    fn route_admin_instance_role(self) -> Self {
+        self.route(
             "/accounts/{account_id}/roles/{role}",
             put(assign_instance_role).delete(revoke_instance_role),
         )
+        .route("/roles", get(get_instance_role_holders))
+        .route("/roles/history", get(get_instance_role_history))
     }
 }
@@ -1,5 +1,6 @@
 use crate::api::ActivityPubApi;
 use crate::error::ErrorStatus;
+use crate::telemetry::{record_signature_failure, ACTOR_MISMATCH, HOST_MISMATCH};
 use application::dto::activitypub::InboxActivityDto;
 use axum::body::Bytes;
 use axum::extract::{OriginalUri, Path, State};
@@ -53,7 +54,8 @@ pub(crate) async fn post_inbox(
         headers: headers_to_map(&headers),
         body: Some(body.to_vec()),
     };
-    ensure_host_matches_public_base_url(&api.public_base_host_header()?, &headers)?;
+    ensure_host_matches_public_base_url(&api.public_base_host_header()?, &headers)
+        .inspect_err(|_| record_signature_failure(HOST_MISMATCH))?;
     let key_id = match api
         .verify_http_signature(&verification_input)
         .await
@@ -61,6 +63,7 @@ pub(crate) async fn post_inbox(
     {
         SignatureVerificationResult::Valid { key_id } => key_id,
         SignatureVerificationResult::Invalid(reason) => {
+            record_signature_failure(&reason);
             tracing::warn!(
                 reason,
                 "Rejected ActivityPub inbox request with invalid signature"
@@ -105,6 +108,7 @@ async fn ensure_signature_owner_matches_actor(
     activity: &kernel::activitypub::Activity,
 ) -> Result<(), ErrorStatus> {
     let actor_key = api.fetch_actor_key(key_id).await.map_err(|e| {
+        record_signature_failure("KeyFetchFailed");
         tracing::warn!(?e, key_id, "Failed to fetch ActivityPub signer actor key");
         ErrorStatus::from(StatusCode::UNAUTHORIZED)
     })?;
@@ -113,6 +117,7 @@ async fn ensure_signature_owner_matches_actor(
     {
         Ok(())
     } else {
+        record_signature_failure(ACTOR_MISMATCH);
         tracing::warn!(
             key_owner = actor_key.owner,
             key_id,
@@ -0,0 +1,254 @@
+use crate::api::AdminCustomRoleApi;
+use crate::auth::{AuthClaims, OidcAuthInfo};
+use crate::error::ErrorStatus;
+use crate::handler::AppModule;
+use crate::schema::custom_role::{
+    CreateCustomRoleRequest, CustomRoleResponse, CustomRolesResponse, UpdateCustomRoleRequest,
+};
+use application::dto::custom_role::{CreateCustomRoleDto, UpdateCustomRoleDto};
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::{get, patch, put};
+use axum::{Extension, Json};
+use kernel::interfaces::permission::InstancePermission;
+
+pub trait AdminCustomRoleRouter {
+    fn route_admin_custom_role(self) -> Self;
+}
+
+impl AdminCustomRoleRouter for axum::Router<AppModule> {
+    fn route_admin_custom_role(self) -> Self {
+        self.route(
+            "/custom-roles",
+            get(get_custom_roles).post(create_custom_role),
+        )
+        .route(
+            "/custom-roles/{role_id}",
+            patch(update_custom_role).delete(delete_custom_role),
+        )
+        .route(
+            "/accounts/{account_id}/custom-roles/{role_id}",
+            put(assign_custom_role).delete(revoke_custom_role),
+        )
+    }
+}
+
+fn parse_permissions(permissions: Vec<String>) -> Result<Vec<InstancePermission>, ErrorStatus> {
+    permissions
+        .iter()
+        .map(|permission| {
+            InstancePermission::parse(permission).ok_or_else(|| {
+                ErrorStatus::from((
+                    StatusCode::BAD_REQUEST,
+                    format!("invalid instance permission: {permission}"),
+                ))
+            })
+        })
+        .collect()
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/custom-roles",
+    description = "Create a custom role bundling instance permissions. The caller must hold every bundled permission.",
+    request_body = CreateCustomRoleRequest,
+    responses(
+        (status = 201, description = "Custom role created", body = CustomRoleResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 403, description = "Permission denied"),
+        (status = 422, description = "Role name already in use"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Custom Role",
+)]
+pub(crate) async fn create_custom_role(
+    Extension(claims): Extension<AuthClaims>,
+    State(api): State<AdminCustomRoleApi>,
+    Json(request): Json<CreateCustomRoleRequest>,
+) -> Result<(StatusCode, Json<CustomRoleResponse>), ErrorStatus> {
+    let dto = CreateCustomRoleDto {
+        name: request.name,
+        permissions: parse_permissions(request.permissions)?,
+    };
+
+    let auth_account_id = api
+        .resolve_auth_account_id(OidcAuthInfo::from(claims))
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    let role = api
+        .create_custom_role(&auth_account_id, dto)
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    Ok((StatusCode::CREATED, Json(role.into())))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/custom-roles",
+    description = "List custom roles with their permissions and members, ordered by name.",
+    responses(
+        (status = 200, description = "Custom roles", body = CustomRolesResponse),
+        (status = 403, description = "Permission denied"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Custom Role",
+)]
+pub(crate) async fn get_custom_roles(
+    Extension(claims): Extension<AuthClaims>,
+    State(api): State<AdminCustomRoleApi>,
+) -> Result<Json<CustomRolesResponse>, ErrorStatus> {
+    let auth_account_id = api
+        .resolve_auth_account_id(OidcAuthInfo::from(claims))
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    let roles = api
+        .get_custom_roles(&auth_account_id)
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    Ok(Json(CustomRolesResponse {
+        items: roles.into_iter().map(Into::into).collect(),
+    }))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/v1/admin/custom-roles/{role_id}",
+    description = "Rename a custom role or replace its permissions. Members gain or lose the changed permissions immediately; the caller must hold any permission being added.",
+    params(("role_id" = String, Path, description = "Custom role ID")),
+    request_body = UpdateCustomRoleRequest,
+    responses(
+        (status = 200, description = "Custom role updated", body = CustomRoleResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 403, description = "Permission denied"),
+        (status = 404, description = "Custom role not found"),
+        (status = 422, description = "Role name already in use"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Custom Role",
+)]
+pub(crate) async fn update_custom_role(
+    Extension(claims): Extension<AuthClaims>,
+    State(api): State<AdminCustomRoleApi>,
+    Path(role_id): Path<String>,
+    Json(request): Json<UpdateCustomRoleRequest>,
+) -> Result<Json<CustomRoleResponse>, ErrorStatus> {
+    let dto = UpdateCustomRoleDto {
+        name: request.name,
+        permissions: request.permissions.map(parse_permissions).transpose()?,
+    };
+
+    let auth_account_id = api
+        .resolve_auth_account_id(OidcAuthInfo::from(claims))
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    let role = api
+        .update_custom_role(&auth_account_id, role_id, dto)
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    Ok(Json(role.into()))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/custom-roles/{role_id}",
+    description = "Delete a custom role. Its members lose the bundled permissions.",
+    params(("role_id" = String, Path, description = "Custom role ID")),
+    responses(
+        (status = 204, description = "Custom role deleted"),
+        (status = 400, description = "Invalid request"),
+        (status = 403, description = "Permission denied"),
+        (status = 404, description = "Custom role not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Custom Role",
+)]
+pub(crate) async fn delete_custom_role(
+    Extension(claims): Extension<AuthClaims>,
+    State(api): State<AdminCustomRoleApi>,
+    Path(role_id): Path<String>,
+) -> Result<StatusCode, ErrorStatus> {
+    let auth_account_id = api
+        .resolve_auth_account_id(OidcAuthInfo::from(claims))
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    api.delete_custom_role(&auth_account_id, role_id)
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/admin/accounts/{account_id}/custom-roles/{role_id}",
+    description = "Assign a custom role to the owner of an account. The caller must hold every permission the role bundles.",
+    params(
+        ("account_id" = String, Path, description = "Account nanoid"),
+        ("role_id" = String, Path, description = "Custom role ID"),
+    ),
+    responses(
+        (status = 204, description = "Custom role assigned"),
+        (status = 400, description = "Invalid request"),
+        (status = 403, description = "Permission denied"),
+        (status = 404, description = "Account or custom role not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Custom Role",
+)]
+pub(crate) async fn assign_custom_role(
+    Extension(claims): Extension<AuthClaims>,
+    State(api): State<AdminCustomRoleApi>,
+    Path((account_id, role_id)): Path<(String, String)>,
+) -> Result<StatusCode, ErrorStatus> {
+    let auth_account_id = api
+        .resolve_auth_account_id(OidcAuthInfo::from(claims))
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    api.assign_custom_role(&auth_account_id, account_id, role_id)
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/accounts/{account_id}/custom-roles/{role_id}",
+    description = "Remove the owner of an account from a custom role. The caller must hold every permission the role bundles.",
+    params(
+        ("account_id" = String, Path, description = "Account nanoid"),
+        ("role_id" = String, Path, description = "Custom role ID"),
+    ),
+    responses(
+        (status = 204, description = "Custom role revoked"),
+        (status = 400, description = "Invalid request"),
+        (status = 403, description = "Permission denied"),
+        (status = 404, description = "Account or custom role not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Custom Role",
+)]
+pub(crate) async fn revoke_custom_role(
+    Extension(claims): Extension<AuthClaims>,
+    State(api): State<AdminCustomRoleApi>,
+    Path((account_id, role_id)): Path<(String, String)>,
+) -> Result<StatusCode, ErrorStatus> {
+    let auth_account_id = api
+        .resolve_auth_account_id(OidcAuthInfo::from(claims))
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    api.revoke_custom_role(&auth_account_id, account_id, role_id)
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
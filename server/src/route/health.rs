@@ -0,0 +1,53 @@
+use crate::api::ProjectionApi;
+use crate::error::ErrorStatus;
+use crate::handler::AppModule;
+use crate::schema::health::{ProjectionHealthResponse, ProjectorHealthResponse};
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::Json;
+
+pub trait HealthRouter {
+    fn route_health(self) -> Self;
+}
+
+impl HealthRouter for axum::Router<AppModule> {
+    fn route_health(self) -> Self {
+        self.route("/health/projections", get(get_projection_health))
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/health/projections",
+    description = "Per-projector checkpoint, event log head, lag and the outcome of this server's latest polls. Responds 503 when the latest poll of any projector failed. Write endpoints under `/api/v1` accept `X-Emumet-Wait-For-Projection: true` to respond only once the projections include the write, and return an `X-Emumet-Projection-Token` that later requests can send back to wait for the same point; `X-Emumet-Projection-Caught-Up` reports whether the wait finished in time.",
+    responses(
+        (status = 200, description = "All projectors healthy", body = ProjectionHealthResponse),
+        (status = 503, description = "A projector's latest poll failed", body = ProjectionHealthResponse),
+    ),
+    tag = "Health",
+)]
+pub(crate) async fn get_projection_health(
+    State(api): State<ProjectionApi>,
+) -> Result<(StatusCode, Json<ProjectionHealthResponse>), ErrorStatus> {
+    let projectors: Vec<ProjectorHealthResponse> = api
+        .projection_health()
+        .await
+        .map_err(ErrorStatus::from)?
+        .into_iter()
+        .map(ProjectorHealthResponse::from)
+        .collect();
+    let healthy = projectors.iter().all(|projector| projector.healthy);
+    let status = if healthy {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    Ok((
+        status,
+        Json(ProjectionHealthResponse {
+            healthy,
+            projectors,
+        }),
+    ))
+}
@@ -1,20 +1,51 @@
 use crate::api::MeApi;
-use crate::auth::{AuthClaims, OidcAuthInfo};
+use crate::auth::{AuthClaims, IdentitySubject, OidcAuthInfo};
 use crate::error::ErrorStatus;
 use crate::handler::AppModule;
-use crate::schema::me::MeResponse;
-use axum::extract::State;
+use crate::schema::me::{
+    ConsentResponse, ConsentsResponse, LoginSessionResponse, LoginSessionsResponse, MeResponse,
+};
+use axum::extract::{Path, State};
 use axum::http::StatusCode;
+use axum::routing::{delete, get};
 use axum::{Extension, Json};
 use kernel::interfaces::permission::InstanceRole;
 
 pub trait MeRouter {
     fn route_me(self) -> Self;
+    /// Sessions and consents, which sign the identity out of every app;
+    /// gated on the `sessions` scope rather than the account scopes.
+    fn route_me_sessions(self) -> Self;
 }
 
 impl MeRouter for axum::Router<AppModule> {
     fn route_me(self) -> Self {
-        self.route("/me", axum::routing::get(get_me))
+        self.route("/me", get(get_me))
+    }
+
+    fn route_me_sessions(self) -> Self {
+        self.route(
+            "/me/sessions",
+            get(get_login_sessions).delete(revoke_login_sessions),
+        )
+        .route("/me/sessions/{session_id}", delete(revoke_login_session))
+        .route("/me/consents", get(get_consents).delete(revoke_consents))
+        .route("/me/consents/{client_id}", delete(revoke_consent))
+    }
+}
+
+/// Sessions and consents belong to the identity behind a sign-in through
+/// this instance; other tokens have none to manage.
+fn identity_subject(subject: Option<Extension<IdentitySubject>>) -> Result<String, ErrorStatus> {
+    subject
+        .map(|Extension(IdentitySubject(subject))| subject)
+        .ok_or(ErrorStatus::StatusCode(StatusCode::FORBIDDEN))
+}
+
+fn bad_gateway(action: &str) -> impl FnOnce(reqwest::Error) -> ErrorStatus + '_ {
+    move |e| {
+        tracing::error!("Failed to {action}: {e}");
+        ErrorStatus::StatusCode(StatusCode::BAD_GATEWAY)
     }
 }
 
@@ -57,11 +88,175 @@ pub(crate) async fn get_me(
     }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/me/sessions",
+    description = "List your active login sessions, with the devices they were used from.",
+    responses(
+        (status = 200, description = "Active login sessions", body = LoginSessionsResponse),
+        (status = 403, description = "Not signed in through this instance's identity provider"),
+        (status = 502, description = "Identity provider unavailable"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Me",
+)]
+pub(crate) async fn get_login_sessions(
+    subject: Option<Extension<IdentitySubject>>,
+    State(api): State<MeApi>,
+) -> Result<Json<LoginSessionsResponse>, ErrorStatus> {
+    let subject = identity_subject(subject)?;
+    let sessions = api
+        .list_login_sessions(&subject)
+        .await
+        .map_err(bad_gateway("list sessions at Kratos"))?;
+    Ok(Json(LoginSessionsResponse {
+        items: sessions
+            .into_iter()
+            .map(LoginSessionResponse::from)
+            .collect(),
+    }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/me/sessions",
+    description = "Sign out everywhere: revoke all your login sessions, including the current one, and the remembered OAuth2 login. Issued access tokens stay valid until they expire or their consent is revoked.",
+    responses(
+        (status = 204, description = "Login sessions revoked"),
+        (status = 403, description = "Not signed in through this instance's identity provider"),
+        (status = 502, description = "Identity provider unavailable"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Me",
+)]
+pub(crate) async fn revoke_login_sessions(
+    subject: Option<Extension<IdentitySubject>>,
+    State(api): State<MeApi>,
+) -> Result<StatusCode, ErrorStatus> {
+    let subject = identity_subject(subject)?;
+    api.revoke_all_login_sessions(&subject)
+        .await
+        .map_err(bad_gateway("revoke login sessions"))?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/me/sessions/{session_id}",
+    description = "Revoke one of your login sessions.",
+    params(("session_id" = String, Path, description = "Login session ID")),
+    responses(
+        (status = 204, description = "Login session revoked"),
+        (status = 403, description = "Not signed in through this instance's identity provider"),
+        (status = 404, description = "No active login session with this ID"),
+        (status = 502, description = "Identity provider unavailable"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Me",
+)]
+pub(crate) async fn revoke_login_session(
+    subject: Option<Extension<IdentitySubject>>,
+    State(api): State<MeApi>,
+    Path(session_id): Path<String>,
+) -> Result<StatusCode, ErrorStatus> {
+    let subject = identity_subject(subject)?;
+    let revoked = api
+        .revoke_login_session(&subject, &session_id)
+        .await
+        .map_err(bad_gateway("revoke a session at Kratos"))?;
+    if !revoked {
+        return Err(ErrorStatus::StatusCode(StatusCode::NOT_FOUND));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/me/consents",
+    description = "List the OAuth2 clients you granted access to, with the granted scopes.",
+    responses(
+        (status = 200, description = "Granted consents", body = ConsentsResponse),
+        (status = 403, description = "Not signed in through this instance's identity provider"),
+        (status = 502, description = "OAuth2 provider unavailable"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Me",
+)]
+pub(crate) async fn get_consents(
+    subject: Option<Extension<IdentitySubject>>,
+    State(api): State<MeApi>,
+) -> Result<Json<ConsentsResponse>, ErrorStatus> {
+    let subject = identity_subject(subject)?;
+    let consents = api
+        .list_consents(&subject)
+        .await
+        .map_err(bad_gateway("list consent sessions at Hydra"))?;
+    Ok(Json(ConsentsResponse {
+        items: consents
+            .into_iter()
+            .filter_map(ConsentResponse::from_session)
+            .collect(),
+    }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/me/consents",
+    description = "Revoke every consent you granted. All access and refresh tokens issued under them, including the calling one, stop working.",
+    responses(
+        (status = 204, description = "Consents revoked"),
+        (status = 403, description = "Not signed in through this instance's identity provider"),
+        (status = 502, description = "OAuth2 provider unavailable"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Me",
+)]
+pub(crate) async fn revoke_consents(
+    subject: Option<Extension<IdentitySubject>>,
+    State(api): State<MeApi>,
+) -> Result<StatusCode, ErrorStatus> {
+    let subject = identity_subject(subject)?;
+    api.revoke_all_consents(&subject)
+        .await
+        .map_err(bad_gateway("revoke consent sessions at Hydra"))?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/me/consents/{client_id}",
+    description = "Revoke your consent for one OAuth2 client. Its access and refresh tokens stop working and it has to ask for consent again.",
+    params(("client_id" = String, Path, description = "OAuth2 client ID")),
+    responses(
+        (status = 204, description = "Consent revoked"),
+        (status = 403, description = "Not signed in through this instance's identity provider"),
+        (status = 404, description = "No consent for this client"),
+        (status = 502, description = "OAuth2 provider unavailable"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Me",
+)]
+pub(crate) async fn revoke_consent(
+    subject: Option<Extension<IdentitySubject>>,
+    State(api): State<MeApi>,
+    Path(client_id): Path<String>,
+) -> Result<StatusCode, ErrorStatus> {
+    let subject = identity_subject(subject)?;
+    let revoked = api
+        .revoke_consent(&subject, &client_id)
+        .await
+        .map_err(bad_gateway("revoke a consent session at Hydra"))?;
+    if !revoked {
+        return Err(ErrorStatus::StatusCode(StatusCode::NOT_FOUND));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::auth::{encode_test_jwt, generate_test_keys, AuthClaims, JwksCache, OidcConfig};
     use crate::handler::AppModule;
-    use crate::route::build_test_router_with_auth;
+    use crate::route::{build_test_router_with_auth, build_test_router_with_backend};
     use axum::body::Body;
     use axum::http::{Request, StatusCode};
     use http_body_util::BodyExt;
@@ -75,7 +270,7 @@ mod tests {
     use std::sync::Arc;
     use std::time::{SystemTime, UNIX_EPOCH};
     use tower::ServiceExt;
-    use wiremock::matchers::{method, path};
+    use wiremock::matchers::{method, path, query_param};
     use wiremock::{Mock, MockServer, ResponseTemplate};
 
     const AUDIENCE: &str = "emumet";
@@ -83,10 +278,15 @@ mod tests {
     struct AuthenticatedRouter {
         router: axum::Router,
         token: String,
+        subject: String,
         auth_account_id: AuthAccountId,
     }
 
     fn claims(issuer: &str, subject: &str) -> AuthClaims {
+        claims_with_scopes(issuer, subject, &["accounts:read"])
+    }
+
+    fn claims_with_scopes(issuer: &str, subject: &str, scopes: &[&str]) -> AuthClaims {
         let exp = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .expect("system clock after Unix epoch")
@@ -97,16 +297,35 @@ mod tests {
             sub: subject.to_string(),
             aud: crate::auth::OneOrMany::One(AUDIENCE.to_string()),
             exp,
+            scp: Some(crate::auth::OneOrMany::Many(
+                scopes.iter().map(|scope| scope.to_string()).collect(),
+            )),
+            scope: None,
         }
     }
 
     async fn authenticated_router(keto_read_url: &str) -> AuthenticatedRouter {
+        authenticated_router_with(
+            "http://localhost:65535",
+            "http://localhost:65535",
+            keto_read_url,
+            &["accounts:read"],
+        )
+        .await
+    }
+
+    async fn authenticated_router_with(
+        hydra_admin_url: &str,
+        kratos_url: &str,
+        keto_read_url: &str,
+        scopes: &[&str],
+    ) -> AuthenticatedRouter {
         kernel::ensure_generator_initialized();
         let issuer = format!("https://issuer.example/{}", uuid::Uuid::new_v4());
         let subject = format!("subject-{}", uuid::Uuid::new_v4());
         let module = AppModule::new_for_test_urls(
-            "http://localhost:65535".to_string(),
-            "http://localhost:65535".to_string(),
+            hydra_admin_url.to_string(),
+            kratos_url.to_string(),
             keto_read_url.to_string(),
             "http://localhost:65535".to_string(),
         )
@@ -141,7 +360,11 @@ mod tests {
             .expect("seed auth account");
 
         let keys = generate_test_keys();
-        let token = encode_test_jwt(&claims(&issuer, &subject), &keys.encoding_key, &keys.kid);
+        let token = encode_test_jwt(
+            &claims_with_scopes(&issuer, &subject, scopes),
+            &keys.encoding_key,
+            &keys.kid,
+        );
         let oidc_config = Arc::new(OidcConfig {
             issuer_url: issuer.clone(),
             expected_audience: AUDIENCE.to_string(),
@@ -152,6 +375,7 @@ mod tests {
         AuthenticatedRouter {
             router: build_test_router_with_auth(module, oidc_config, jwks_cache),
             token,
+            subject,
             auth_account_id,
         }
     }
@@ -286,4 +510,477 @@ mod tests {
 
         assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
     }
+
+    #[test_with::env(DATABASE_URL)]
+    #[tokio::test]
+    async fn same_subject_from_two_issuers_resolves_to_separate_auth_accounts() {
+        use crate::auth::{AuthBackend, TrustedIssuer, TrustedIssuers};
+
+        kernel::ensure_generator_initialized();
+        let keto = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/relation-tuples"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "relation_tuples": [],
+                "next_page_token": "",
+            })))
+            .mount(&keto)
+            .await;
+        let module = AppModule::new_for_test_urls(
+            "http://localhost:65535".to_string(),
+            "http://localhost:65535".to_string(),
+            keto.uri(),
+            "http://localhost:65535".to_string(),
+        )
+        .await
+        .expect("AppModule init failed (is DATABASE_URL set?)");
+
+        let subject = format!("subject-{}", uuid::Uuid::new_v4());
+        let mut issuers = vec![];
+        let mut tokens = vec![];
+        for _ in 0..2 {
+            let issuer = format!("https://issuer.example/{}", uuid::Uuid::new_v4());
+            let keys = generate_test_keys();
+            tokens.push(encode_test_jwt(
+                &claims(&issuer, &subject),
+                &keys.encoding_key,
+                &keys.kid,
+            ));
+            issuers.push(TrustedIssuer {
+                config: Arc::new(OidcConfig {
+                    issuer_url: issuer.clone(),
+                    expected_audience: AUDIENCE.to_string(),
+                    jwks_refetch_interval_secs: 0,
+                }),
+                jwks_cache: Arc::new(JwksCache::new_with_jwks(issuer, keys.jwk_set)),
+            });
+        }
+        let router = build_test_router_with_backend(
+            module,
+            AuthBackend::Jwt(Arc::new(TrustedIssuers::new(issuers))),
+        );
+
+        let mut account_ids = vec![];
+        for token in tokens.iter().chain(tokens.iter()) {
+            let response = router.clone().oneshot(me_request(token)).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            account_ids.push(response_json(response).await["account_id"].clone());
+        }
+
+        assert_ne!(account_ids[0], account_ids[1]);
+        assert_eq!(account_ids[0], account_ids[2]);
+        assert_eq!(account_ids[1], account_ids[3]);
+    }
+
+    async fn ory_router() -> (MockServer, MockServer, AuthenticatedRouter) {
+        let hydra = MockServer::start().await;
+        let kratos = MockServer::start().await;
+        let app = authenticated_router_with(
+            &hydra.uri(),
+            &kratos.uri(),
+            "http://localhost:65535",
+            &["sessions"],
+        )
+        .await;
+        (hydra, kratos, app)
+    }
+
+    fn request(method: &str, uri: &str, token: &str) -> Request<Body> {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("authorization", format!("Bearer {token}"))
+            .body(Body::empty())
+            .expect("valid request")
+    }
+
+    fn kratos_sessions_path(subject: &str) -> String {
+        format!("/admin/identities/{subject}/sessions")
+    }
+
+    #[test_with::env(DATABASE_URL)]
+    #[tokio::test]
+    async fn get_sessions_lists_active_kratos_sessions_of_the_identity() {
+        let (_hydra, kratos, app) = ory_router().await;
+        Mock::given(method("GET"))
+            .and(path(kratos_sessions_path(&app.subject)))
+            .and(query_param("active", "true"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+                    "id": "session-1",
+                    "active": true,
+                    "authenticated_at": "2026-10-01T12:00:00Z",
+                    "expires_at": "2026-10-02T12:00:00Z",
+                    "authentication_methods": [{"method": "password"}, {"method": "totp"}],
+                    "devices": [{
+                        "ip_address": "192.0.2.1",
+                        "user_agent": "Firefox",
+                        "location": "Tokyo, JP",
+                    }],
+                    "identity": {"id": app.subject},
+                }])),
+            )
+            .expect(1)
+            .mount(&kratos)
+            .await;
+
+        let response = app
+            .router
+            .oneshot(request("GET", "/api/v1/me/sessions", &app.token))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response_json(response).await,
+            serde_json::json!({
+                "items": [{
+                    "id": "session-1",
+                    "authentication_methods": ["password", "totp"],
+                    "devices": [{
+                        "ip_address": "192.0.2.1",
+                        "user_agent": "Firefox",
+                        "location": "Tokyo, JP",
+                    }],
+                    "authenticated_at": "2026-10-01T12:00:00Z",
+                    "expires_at": "2026-10-02T12:00:00Z",
+                }],
+            })
+        );
+        kratos.verify().await;
+    }
+
+    #[test_with::env(DATABASE_URL)]
+    #[tokio::test]
+    async fn revoke_session_deletes_it_at_kratos() {
+        let (_hydra, kratos, app) = ory_router().await;
+        Mock::given(method("GET"))
+            .and(path(kratos_sessions_path(&app.subject)))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!([{"id": "session-1"}])),
+            )
+            .mount(&kratos)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/admin/sessions/session-1"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&kratos)
+            .await;
+
+        let response = app
+            .router
+            .oneshot(request(
+                "DELETE",
+                "/api/v1/me/sessions/session-1",
+                &app.token,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        kratos.verify().await;
+    }
+
+    #[test_with::env(DATABASE_URL)]
+    #[tokio::test]
+    async fn revoke_session_of_another_identity_is_not_found() {
+        let (_hydra, kratos, app) = ory_router().await;
+        Mock::given(method("GET"))
+            .and(path(kratos_sessions_path(&app.subject)))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!([{"id": "session-1"}])),
+            )
+            .mount(&kratos)
+            .await;
+        Mock::given(method("DELETE"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(0)
+            .mount(&kratos)
+            .await;
+
+        let response = app
+            .router
+            .oneshot(request(
+                "DELETE",
+                "/api/v1/me/sessions/someone-elses-session",
+                &app.token,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        kratos.verify().await;
+    }
+
+    #[test_with::env(DATABASE_URL)]
+    #[tokio::test]
+    async fn revoke_all_sessions_signs_out_at_kratos_and_hydra() {
+        let (hydra, kratos, app) = ory_router().await;
+        Mock::given(method("DELETE"))
+            .and(path(kratos_sessions_path(&app.subject)))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&kratos)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/admin/oauth2/auth/sessions/login"))
+            .and(query_param("subject", app.subject.as_str()))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&hydra)
+            .await;
+
+        let response = app
+            .router
+            .oneshot(request("DELETE", "/api/v1/me/sessions", &app.token))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        kratos.verify().await;
+        hydra.verify().await;
+    }
+
+    #[test_with::env(DATABASE_URL)]
+    #[tokio::test]
+    async fn get_consents_follows_hydra_pagination() {
+        let (hydra, _kratos, app) = ory_router().await;
+        let consent = |client_id: &str| {
+            serde_json::json!([{
+                "consent_request": {
+                    "client": {"client_id": client_id, "client_name": format!("{client_id} app")},
+                },
+                "grant_scope": ["openid", "accounts:read"],
+                "handled_at": "2026-10-01T12:00:00Z",
+                "remember": true,
+            }])
+        };
+        Mock::given(method("GET"))
+            .and(path("/admin/oauth2/auth/sessions/consent"))
+            .and(query_param("subject", app.subject.as_str()))
+            .and(query_param("page_token", "second"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(consent("client-b")))
+            .expect(1)
+            .mount(&hydra)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/admin/oauth2/auth/sessions/consent"))
+            .and(query_param("subject", app.subject.as_str()))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header(
+                        "link",
+                        "</admin/oauth2/auth/sessions/consent?page_token=second>; rel=\"next\"",
+                    )
+                    .set_body_json(consent("client-a")),
+            )
+            .up_to_n_times(1)
+            .mount(&hydra)
+            .await;
+
+        let response = app
+            .router
+            .oneshot(request("GET", "/api/v1/me/consents", &app.token))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response_json(response).await;
+        assert_eq!(
+            body["items"][0],
+            serde_json::json!({
+                "client_id": "client-a",
+                "client_name": "client-a app",
+                "scopes": ["openid", "accounts:read"],
+                "remember": true,
+                "granted_at": "2026-10-01T12:00:00Z",
+            })
+        );
+        assert_eq!(body["items"][1]["client_id"], "client-b");
+        assert_eq!(body["items"].as_array().map(Vec::len), Some(2));
+        hydra.verify().await;
+    }
+
+    #[test_with::env(DATABASE_URL)]
+    #[tokio::test]
+    async fn revoke_consent_revokes_only_consented_clients() {
+        let (hydra, _kratos, app) = ory_router().await;
+        Mock::given(method("GET"))
+            .and(path("/admin/oauth2/auth/sessions/consent"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+                    "consent_request": {"client": {"client_id": "client-a"}},
+                    "grant_scope": ["openid"],
+                }])),
+            )
+            .mount(&hydra)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/admin/oauth2/auth/sessions/consent"))
+            .and(query_param("subject", app.subject.as_str()))
+            .and(query_param("client", "client-a"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&hydra)
+            .await;
+
+        let revoked = app
+            .router
+            .clone()
+            .oneshot(request(
+                "DELETE",
+                "/api/v1/me/consents/client-a",
+                &app.token,
+            ))
+            .await
+            .unwrap();
+        let unknown = app
+            .router
+            .oneshot(request(
+                "DELETE",
+                "/api/v1/me/consents/client-b",
+                &app.token,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(revoked.status(), StatusCode::NO_CONTENT);
+        assert_eq!(unknown.status(), StatusCode::NOT_FOUND);
+        hydra.verify().await;
+    }
+
+    #[test_with::env(DATABASE_URL)]
+    #[tokio::test]
+    async fn revoke_all_consents_revokes_every_client_at_hydra() {
+        let (hydra, _kratos, app) = ory_router().await;
+        Mock::given(method("DELETE"))
+            .and(path("/admin/oauth2/auth/sessions/consent"))
+            .and(query_param("subject", app.subject.as_str()))
+            .and(query_param("all", "true"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&hydra)
+            .await;
+
+        let response = app
+            .router
+            .oneshot(request("DELETE", "/api/v1/me/consents", &app.token))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        hydra.verify().await;
+    }
+
+    #[test_with::env(DATABASE_URL)]
+    #[tokio::test]
+    async fn sessions_return_bad_gateway_when_kratos_fails() {
+        let (_hydra, kratos, app) = ory_router().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&kratos)
+            .await;
+
+        let response = app
+            .router
+            .oneshot(request("GET", "/api/v1/me/sessions", &app.token))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+    }
+
+    #[test_with::env(DATABASE_URL)]
+    #[tokio::test]
+    async fn sessions_and_consents_need_the_sessions_scope() {
+        let hydra = MockServer::start().await;
+        let kratos = MockServer::start().await;
+        for server in [&hydra, &kratos] {
+            Mock::given(method("GET"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+                .expect(0)
+                .mount(server)
+                .await;
+        }
+        let app = authenticated_router_with(
+            &hydra.uri(),
+            &kratos.uri(),
+            "http://localhost:65535",
+            &["accounts:read", "accounts:write"],
+        )
+        .await;
+
+        for (verb, uri) in [
+            ("GET", "/api/v1/me/sessions"),
+            ("DELETE", "/api/v1/me/sessions"),
+            ("GET", "/api/v1/me/consents"),
+            ("DELETE", "/api/v1/me/consents"),
+        ] {
+            let response = app
+                .router
+                .clone()
+                .oneshot(request(verb, uri, &app.token))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::FORBIDDEN, "{verb} {uri}");
+        }
+        hydra.verify().await;
+        kratos.verify().await;
+    }
+
+    #[test_with::env(DATABASE_URL)]
+    #[tokio::test]
+    async fn sessions_are_forbidden_for_tokens_from_additional_issuers() {
+        use crate::auth::{AuthBackend, TrustedIssuer, TrustedIssuers};
+
+        kernel::ensure_generator_initialized();
+        let kratos = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+            .expect(0)
+            .mount(&kratos)
+            .await;
+        let module = AppModule::new_for_test_urls(
+            "http://localhost:65535".to_string(),
+            kratos.uri(),
+            "http://localhost:65535".to_string(),
+            "http://localhost:65535".to_string(),
+        )
+        .await
+        .expect("AppModule init failed (is DATABASE_URL set?)");
+
+        let subject = format!("subject-{}", uuid::Uuid::new_v4());
+        let mut issuers = vec![];
+        let mut tokens = vec![];
+        for _ in 0..2 {
+            let issuer = format!("https://issuer.example/{}", uuid::Uuid::new_v4());
+            let keys = generate_test_keys();
+            tokens.push(encode_test_jwt(
+                &claims_with_scopes(&issuer, &subject, &["sessions"]),
+                &keys.encoding_key,
+                &keys.kid,
+            ));
+            issuers.push(TrustedIssuer {
+                config: Arc::new(OidcConfig {
+                    issuer_url: issuer.clone(),
+                    expected_audience: AUDIENCE.to_string(),
+                    jwks_refetch_interval_secs: 0,
+                }),
+                jwks_cache: Arc::new(JwksCache::new_with_jwks(issuer, keys.jwk_set)),
+            });
+        }
+        let router = build_test_router_with_backend(
+            module,
+            AuthBackend::Jwt(Arc::new(TrustedIssuers::new(issuers))),
+        );
+
+        let response = router
+            .oneshot(request("GET", "/api/v1/me/sessions", &tokens[1]))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        kratos.verify().await;
+    }
 }
@@ -1,13 +1,20 @@
-use crate::api::MediaApi;
+use crate::api::{AdminMediaApi, LocalMediaApi, MediaApi};
+use crate::auth::{AuthClaims, OidcAuthInfo};
 use crate::error::ErrorStatus;
 use crate::handler::AppModule;
-use crate::schema::media::UploadedImageResponse;
-use application::dto::media::UploadImageDto;
+use crate::schema::media::{
+    BlockImageRequest, CreateUploadSessionRequest, GetQuarantinedImagesQuery,
+    ImageBlocklistEntryResponse, ImageBlocklistResponse, QuarantinedImagesResponse,
+    UploadSessionResponse, UploadedImageResponse,
+};
+use application::dto::media::{ImageFocusDto, UploadImageDto};
 use application::service::media::MAX_IMAGE_BYTES;
-use axum::extract::{DefaultBodyLimit, Multipart, State};
-use axum::http::StatusCode;
-use axum::routing::post;
-use axum::{Json, Router};
+use axum::extract::multipart::Field;
+use axum::extract::{DefaultBodyLimit, Multipart, Path, Query, State};
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{delete, get, post};
+use axum::{Extension, Json, Router};
 use error_stack::Report;
 use kernel::KernelError;
 
@@ -21,72 +28,470 @@ impl MediaRouter for Router<AppModule> {
             "/images",
             post(upload_image).layer(DefaultBodyLimit::max(MAX_IMAGE_BYTES + 64 * 1024)),
         )
+        .route("/images/upload-sessions", post(create_upload_session))
+        .route(
+            "/images/upload-sessions/{session_id}/finalize",
+            post(finalize_upload_session),
+        )
+        .route("/images/{image_id}", delete(delete_image))
+    }
+}
+
+/// Moderation of uploaded media, mounted under `/api/v1/admin`.
+pub trait AdminMediaRouter {
+    fn route_admin_media(self) -> Self;
+}
+
+impl AdminMediaRouter for Router<AppModule> {
+    fn route_admin_media(self) -> Self {
+        self.route("/images/blocklist", get(get_image_blocklist))
+            .route(
+                "/images/blocklist/{entry_id}",
+                delete(delete_image_blocklist_entry),
+            )
+            .route("/images/quarantined", get(get_quarantined_images))
+            .route("/images/{image_id}/block", post(block_image))
+            .route(
+                "/images/{image_id}/release",
+                post(release_quarantined_image),
+            )
     }
 }
 
+/// Serves the filesystem media backend under `/media`. Only mounted when
+/// that backend is configured and `MEDIA_SERVE_LOCAL` is not `false`.
+pub trait LocalMediaRouter {
+    fn route_local_media(self) -> Self;
+}
+
+impl LocalMediaRouter for Router<AppModule> {
+    fn route_local_media(self) -> Self {
+        self.route("/media/{*key}", get(get_local_media))
+    }
+}
+
+/// Keys embed the image ID, so a stored file never changes.
+const LOCAL_MEDIA_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
 #[utoipa::path(
     post,
     path = "/api/v1/images",
-    description = "Upload an image to Emumet media storage.",
+    description = "Upload an image to Emumet media storage. The image is re-encoded upright with EXIF and other embedded metadata removed; `hash` is the SHA-256 of the stored bytes. PNG, JPEG, WebP and GIF are accepted. Animated GIF, WebP and APNG uploads (at most 300 frames and 60 seconds) are stored as animated WebP with `animated` set, plus a `static` first-frame rendition. Avatar, header and thumbnail renditions are generated as WebP and listed in `variants`. The image is owned by the caller and counts against their media quota; images no profile uses are garbage collected after a grace period. Uploading bytes that are already stored returns the existing image, with the caller added as one of its owners. Optional `description` (alt text, up to 1500 characters) and `focus` (`x,y` focal point, each from -1 to 1) fields are stored with the caller's reference to the image, never shown to its other owners; on a repeated upload they replace the values the caller sent before.",
     request_body(content = String, content_type = "multipart/form-data"),
     responses(
         (status = 201, description = "Image uploaded", body = UploadedImageResponse),
         (status = 400, description = "Invalid image upload"),
+        (status = 422, description = "Media quota exceeded"),
     ),
     security(("bearer_auth" = [])),
     tag = "Media",
 )]
 pub(crate) async fn upload_image(
+    Extension(claims): Extension<AuthClaims>,
     State(api): State<MediaApi>,
     mut multipart: Multipart,
 ) -> Result<(StatusCode, Json<UploadedImageResponse>), ErrorStatus> {
-    let mut upload = None;
+    let auth_account_id = api
+        .resolve_auth_account_id(OidcAuthInfo::from(claims))
+        .await
+        .map_err(ErrorStatus::from)?;
+    let mut file = None;
+    let mut description = None;
+    let mut focus = None;
     while let Some(field) = multipart.next_field().await.map_err(|error| {
         ErrorStatus::from(
             Report::new(KernelError::Validation)
                 .attach_printable(format!("Invalid multipart body: {error}")),
         )
     })? {
-        if field.name() != Some("file") {
-            continue;
-        }
-        if upload.is_some() {
-            return Err(ErrorStatus::from(
-                Report::new(KernelError::Validation)
-                    .attach_printable("Exactly one file field is required".to_string()),
-            ));
+        match field.name() {
+            Some("file") => {
+                if file.is_some() {
+                    return Err(ErrorStatus::from(
+                        Report::new(KernelError::Validation)
+                            .attach_printable("Exactly one file field is required".to_string()),
+                    ));
+                }
+                let content_type = field.content_type().map(str::to_string).ok_or_else(|| {
+                    ErrorStatus::from(
+                        Report::new(KernelError::Validation)
+                            .attach_printable("Image MIME type is required".to_string()),
+                    )
+                })?;
+                let bytes = field.bytes().await.map_err(|error| {
+                    ErrorStatus::from(
+                        Report::new(KernelError::Validation)
+                            .attach_printable(format!("Failed to read image bytes: {error}")),
+                    )
+                })?;
+                file = Some((content_type, bytes.to_vec()));
+            }
+            Some("description") => description = Some(text_field(field).await?),
+            Some("focus") => {
+                let value = text_field(field).await?;
+                focus = Some(parse_focus(&value).ok_or_else(|| {
+                    ErrorStatus::from(Report::new(KernelError::Validation).attach_printable(
+                        "Field 'focus' must be two comma-separated numbers, x,y".to_string(),
+                    ))
+                })?);
+            }
+            _ => {}
         }
-        let content_type = field.content_type().map(str::to_string).ok_or_else(|| {
-            ErrorStatus::from(
-                Report::new(KernelError::Validation)
-                    .attach_printable("Image MIME type is required".to_string()),
-            )
-        })?;
-        let bytes = field.bytes().await.map_err(|error| {
-            ErrorStatus::from(
-                Report::new(KernelError::Validation)
-                    .attach_printable(format!("Failed to read image bytes: {error}")),
-            )
-        })?;
-        upload = Some(UploadImageDto {
-            content_type,
-            bytes: bytes.to_vec(),
-        });
     }
-    let upload = upload.ok_or_else(|| {
+    let (content_type, bytes) = file.ok_or_else(|| {
         ErrorStatus::from(
             Report::new(KernelError::Validation)
                 .attach_printable("Multipart field 'file' is required".to_string()),
         )
     })?;
-    let image = api.upload_image(upload).await.map_err(ErrorStatus::from)?;
+    let upload = UploadImageDto {
+        content_type,
+        bytes,
+        description,
+        focus,
+    };
+    let image = api
+        .upload_image(&auth_account_id, upload)
+        .await
+        .map_err(ErrorStatus::from)?;
     Ok((StatusCode::CREATED, Json(image.into())))
 }
 
+async fn text_field(field: Field<'_>) -> Result<String, ErrorStatus> {
+    field.text().await.map_err(|error| {
+        ErrorStatus::from(
+            Report::new(KernelError::Validation)
+                .attach_printable(format!("Invalid multipart text field: {error}")),
+        )
+    })
+}
+
+/// Mastodon's `focus` parameter: `x,y`, each from -1 to 1. The range is
+/// checked by the use case.
+fn parse_focus(value: &str) -> Option<ImageFocusDto> {
+    let (x, y) = value.split_once(',')?;
+    Some(ImageFocusDto {
+        x: x.trim().parse().ok()?,
+        y: y.trim().parse().ok()?,
+    })
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/images/upload-sessions",
+    description = "Open a direct upload for files too large to send through `POST /api/v1/images` (up to 40 MiB). The response carries a presigned URL: send the file as the body of a `method` request to `upload_url` with exactly the returned `headers`, before `expires_at` (15 minutes), then call the finalize endpoint. The declared `content_type` and `byte_size` are part of the signature. Only available with the S3 media backend. The declared size is checked against the caller's media quota up front. Sessions that are never finalized are removed together with their upload by the media garbage collector.",
+    request_body = CreateUploadSessionRequest,
+    responses(
+        (status = 201, description = "Upload session opened", body = UploadSessionResponse),
+        (status = 400, description = "Invalid MIME type, size or metadata"),
+        (status = 422, description = "Media quota exceeded, or the media backend does not support direct uploads"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Media",
+)]
+pub(crate) async fn create_upload_session(
+    Extension(claims): Extension<AuthClaims>,
+    State(api): State<MediaApi>,
+    Json(request): Json<CreateUploadSessionRequest>,
+) -> Result<(StatusCode, Json<UploadSessionResponse>), ErrorStatus> {
+    let auth_account_id = api
+        .resolve_auth_account_id(OidcAuthInfo::from(claims))
+        .await
+        .map_err(ErrorStatus::from)?;
+    let session = api
+        .create_upload_session(&auth_account_id, request.into_dto())
+        .await
+        .map_err(ErrorStatus::from)?;
+    Ok((StatusCode::CREATED, Json(session.into())))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/images/upload-sessions/{session_id}/finalize",
+    description = "Process the file uploaded for a session and create the image, exactly as `POST /api/v1/images` would, with the session's `description` and `focus`. Finalizing before the upload completed fails and leaves the session open; otherwise the session is used up, even if the file is rejected as an image.",
+    params(("session_id" = String, Path, description = "Upload session ID")),
+    responses(
+        (status = 201, description = "Image uploaded", body = UploadedImageResponse),
+        (status = 400, description = "Invalid session ID or image"),
+        (status = 403, description = "Session belongs to another account"),
+        (status = 404, description = "Upload session not found"),
+        (status = 422, description = "Session expired, nothing uploaded yet, or media quota exceeded"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Media",
+)]
+pub(crate) async fn finalize_upload_session(
+    Extension(claims): Extension<AuthClaims>,
+    State(api): State<MediaApi>,
+    Path(session_id): Path<String>,
+) -> Result<(StatusCode, Json<UploadedImageResponse>), ErrorStatus> {
+    let auth_account_id = api
+        .resolve_auth_account_id(OidcAuthInfo::from(claims))
+        .await
+        .map_err(ErrorStatus::from)?;
+    let image = api
+        .finalize_upload_session(&auth_account_id, session_id)
+        .await
+        .map_err(ErrorStatus::from)?;
+    Ok((StatusCode::CREATED, Json(image.into())))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/images/{image_id}",
+    description = "Delete an uploaded image. While other accounts also uploaded it, only the caller's ownership is removed; the last owner deletes the image and its renditions from the database and object storage, which is refused while a profile uses it as icon or banner.",
+    params(("image_id" = String, Path, description = "Image ID")),
+    responses(
+        (status = 204, description = "Image deleted, or the caller's ownership removed"),
+        (status = 400, description = "Invalid image ID"),
+        (status = 403, description = "Caller does not own the image"),
+        (status = 404, description = "Image not found"),
+        (status = 422, description = "Image is in use"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Media",
+)]
+pub(crate) async fn delete_image(
+    Extension(claims): Extension<AuthClaims>,
+    State(api): State<MediaApi>,
+    Path(image_id): Path<String>,
+) -> Result<StatusCode, ErrorStatus> {
+    let auth_account_id = api
+        .resolve_auth_account_id(OidcAuthInfo::from(claims))
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    api.delete_image(&auth_account_id, image_id)
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/images/{image_id}/block",
+    description = "Add an image to the blocklist by its SHA-256 and perceptual hash, then delete it. Local profiles using it as icon or banner lose it (followers receive the updated profile) and remote accounts showing a cached copy drop it. Later uploads with the same bytes or a perceptually close picture are refused (`reject`) or stored quarantined (`quarantine`), including remote media being cached.",
+    params(("image_id" = String, Path, description = "Image ID")),
+    request_body = BlockImageRequest,
+    responses(
+        (status = 201, description = "Image blocked", body = ImageBlocklistEntryResponse),
+        (status = 400, description = "Invalid image ID, action or reason"),
+        (status = 403, description = "Permission denied"),
+        (status = 404, description = "Image not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Media",
+)]
+pub(crate) async fn block_image(
+    Extension(claims): Extension<AuthClaims>,
+    State(api): State<AdminMediaApi>,
+    Path(image_id): Path<String>,
+    Json(request): Json<BlockImageRequest>,
+) -> Result<(StatusCode, Json<ImageBlocklistEntryResponse>), ErrorStatus> {
+    let auth_account_id = api
+        .resolve_auth_account_id(OidcAuthInfo::from(claims))
+        .await
+        .map_err(ErrorStatus::from)?;
+    let entry = api
+        .block_image(&auth_account_id, image_id, request.into_dto())
+        .await
+        .map_err(ErrorStatus::from)?;
+    Ok((StatusCode::CREATED, Json(entry.into())))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/images/blocklist",
+    description = "List image blocklist entries, newest first.",
+    responses(
+        (status = 200, description = "Image blocklist", body = ImageBlocklistResponse),
+        (status = 403, description = "Permission denied"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Media",
+)]
+pub(crate) async fn get_image_blocklist(
+    Extension(claims): Extension<AuthClaims>,
+    State(api): State<AdminMediaApi>,
+) -> Result<Json<ImageBlocklistResponse>, ErrorStatus> {
+    let auth_account_id = api
+        .resolve_auth_account_id(OidcAuthInfo::from(claims))
+        .await
+        .map_err(ErrorStatus::from)?;
+    let entries = api
+        .get_image_blocklist(&auth_account_id)
+        .await
+        .map_err(ErrorStatus::from)?;
+    Ok(Json(ImageBlocklistResponse {
+        items: entries.into_iter().map(Into::into).collect(),
+    }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/images/blocklist/{entry_id}",
+    description = "Remove a blocklist entry so matching uploads are accepted again. Images it already removed are not restored.",
+    params(("entry_id" = String, Path, description = "Blocklist entry ID")),
+    responses(
+        (status = 204, description = "Blocklist entry removed"),
+        (status = 400, description = "Invalid entry ID"),
+        (status = 403, description = "Permission denied"),
+        (status = 404, description = "Blocklist entry not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Media",
+)]
+pub(crate) async fn delete_image_blocklist_entry(
+    Extension(claims): Extension<AuthClaims>,
+    State(api): State<AdminMediaApi>,
+    Path(entry_id): Path<String>,
+) -> Result<StatusCode, ErrorStatus> {
+    let auth_account_id = api
+        .resolve_auth_account_id(OidcAuthInfo::from(claims))
+        .await
+        .map_err(ErrorStatus::from)?;
+    api.delete_image_blocklist_entry(&auth_account_id, entry_id)
+        .await
+        .map_err(ErrorStatus::from)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/images/quarantined",
+    description = "Uploads held for review because they matched a `quarantine` blocklist entry, newest first. Release an image to let its owners use it, or block it to delete it.",
+    params(
+        ("limit" = Option<u32>, Query, description = "Page size (1-100, default 20)"),
+        ("cursor" = Option<String>, Query, description = "ID of the last image of the previous page"),
+    ),
+    responses(
+        (status = 200, description = "Quarantined images", body = QuarantinedImagesResponse),
+        (status = 400, description = "Invalid cursor"),
+        (status = 403, description = "Permission denied"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Media",
+)]
+pub(crate) async fn get_quarantined_images(
+    Extension(claims): Extension<AuthClaims>,
+    State(api): State<AdminMediaApi>,
+    Query(GetQuarantinedImagesQuery { limit, cursor }): Query<GetQuarantinedImagesQuery>,
+) -> Result<Json<QuarantinedImagesResponse>, ErrorStatus> {
+    let auth_account_id = api
+        .resolve_auth_account_id(OidcAuthInfo::from(claims))
+        .await
+        .map_err(ErrorStatus::from)?;
+    let images = api
+        .get_quarantined_images(&auth_account_id, limit, cursor)
+        .await
+        .map_err(ErrorStatus::from)?;
+    Ok(Json(QuarantinedImagesResponse {
+        items: images.into_iter().map(Into::into).collect(),
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/images/{image_id}/release",
+    description = "Release a quarantined image so it can be used as icon or banner.",
+    params(("image_id" = String, Path, description = "Image ID")),
+    responses(
+        (status = 204, description = "Image released"),
+        (status = 400, description = "Invalid image ID"),
+        (status = 403, description = "Permission denied"),
+        (status = 404, description = "Image not found"),
+        (status = 422, description = "Image is not quarantined"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Media",
+)]
+pub(crate) async fn release_quarantined_image(
+    Extension(claims): Extension<AuthClaims>,
+    State(api): State<AdminMediaApi>,
+    Path(image_id): Path<String>,
+) -> Result<StatusCode, ErrorStatus> {
+    let auth_account_id = api
+        .resolve_auth_account_id(OidcAuthInfo::from(claims))
+        .await
+        .map_err(ErrorStatus::from)?;
+    api.release_quarantined_image(&auth_account_id, image_id)
+        .await
+        .map_err(ErrorStatus::from)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/media/{key}",
+    description = "Serve an image stored by the filesystem media backend. Only available when `MEDIA_STORAGE=filesystem`.",
+    params(("key" = String, Path, description = "Storage key, e.g. `images/{id}/avatar.webp`")),
+    responses(
+        (status = 200, description = "Image file", content_type = "image/*"),
+        (status = 404, description = "No such file"),
+    ),
+    tag = "Media",
+)]
+pub(crate) async fn get_local_media(
+    State(api): State<LocalMediaApi>,
+    Path(key): Path<String>,
+) -> Result<Response, ErrorStatus> {
+    let bytes = api
+        .read(&key)
+        .await
+        .map_err(ErrorStatus::from)?
+        .ok_or(ErrorStatus::StatusCode(StatusCode::NOT_FOUND))?;
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                HeaderValue::from_static(media_content_type(&key)),
+            ),
+            (
+                header::CACHE_CONTROL,
+                HeaderValue::from_static(LOCAL_MEDIA_CACHE_CONTROL),
+            ),
+            (
+                header::X_CONTENT_TYPE_OPTIONS,
+                HeaderValue::from_static("nosniff"),
+            ),
+        ],
+        bytes,
+    )
+        .into_response())
+}
+
+fn media_content_type(key: &str) -> &'static str {
+    match key.rsplit_once('.').map(|(_, extension)| extension) {
+        Some("png") => "image/png",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("webp") => "image/webp",
+        Some("gif") => "image/gif",
+        _ => "application/octet-stream",
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn focus_is_parsed_from_comma_separated_coordinates() {
+        assert_eq!(
+            parse_focus("-0.5, 0.25"),
+            Some(ImageFocusDto { x: -0.5, y: 0.25 })
+        );
+        assert_eq!(parse_focus("0.5"), None);
+        assert_eq!(parse_focus("left,top"), None);
+    }
+
+    #[test]
+    fn local_media_content_type_follows_extension() {
+        assert_eq!(media_content_type("images/1.jpg"), "image/jpeg");
+        assert_eq!(media_content_type("images/1/avatar.webp"), "image/webp");
+        assert_eq!(media_content_type("images/1"), "application/octet-stream");
+    }
+
     #[test]
     fn image_upload_limit_allows_multipart_overhead() {
         let configured_limit = MAX_IMAGE_BYTES + 64 * 1024;
@@ -0,0 +1,34 @@
+use crate::api::MetricsApi;
+use crate::handler::AppModule;
+use axum::extract::State;
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::routing::get;
+
+const PROMETHEUS_TEXT_FORMAT: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+pub trait MetricsRouter {
+    fn route_metrics(self) -> Self;
+}
+
+impl MetricsRouter for axum::Router<AppModule> {
+    fn route_metrics(self) -> Self {
+        self.route("/metrics", get(get_metrics))
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    description = "Prometheus metrics: HTTP requests and latency per route, inbox activities by type and outcome, outgoing deliveries by outcome and peer, signature verification failures by reason, projection lag, database pool usage and JWKS refresh failures.",
+    responses(
+        (status = 200, description = "Prometheus text exposition format", body = String, content_type = "text/plain"),
+    ),
+    tag = "Health",
+)]
+pub(crate) async fn get_metrics(State(api): State<MetricsApi>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, PROMETHEUS_TEXT_FORMAT)],
+        api.render(),
+    )
+}
@@ -1,7 +1,7 @@
 use super::REMEMBER_FOR_SECS;
 use crate::api::OAuth2Api;
 use crate::hydra::{AcceptConsentRequest, ConsentSession, RejectRequest};
-use crate::schema::oauth2::{ConsentDecision, ConsentQuery, OAuth2Response};
+use crate::schema::oauth2::{ConsentDecision, ConsentQuery, OAuth2Response, ScopeDetail};
 use axum::extract::{Query, State};
 use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
@@ -105,6 +105,7 @@ pub(crate) async fn get_consent(
     Ok(OAuth2Response::ShowConsent {
         consent_challenge,
         client_name,
+        scope_details: ScopeDetail::for_requested(&consent_request.requested_scope),
         requested_scope: consent_request.requested_scope,
     }
     .into_response())
@@ -323,7 +324,7 @@ mod tests {
                     "client_name": "My App",
                     "skip_consent": false
                 },
-                "requested_scope": ["openid", "profile"],
+                "requested_scope": ["openid", "profile", "sign"],
                 "requested_access_token_audience": ["account"]
             })))
             .mount(&hydra_mock)
@@ -347,7 +348,14 @@ mod tests {
         assert_eq!(json["client_name"], "My App");
         assert_eq!(
             json["requested_scope"],
-            serde_json::json!(["openid", "profile"])
+            serde_json::json!(["openid", "profile", "sign"])
+        );
+        assert_eq!(
+            json["scope_details"],
+            serde_json::json!([{
+                "scope": "sign",
+                "description": "Sign ActivityPub requests as your accounts"
+            }])
         );
     }
 
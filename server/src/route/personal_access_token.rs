@@ -0,0 +1,313 @@
+use crate::api::PersonalAccessTokenApi;
+use crate::auth::{AuthClaims, OidcAuthInfo};
+use crate::error::ErrorStatus;
+use crate::handler::AppModule;
+use crate::schema::personal_access_token::{
+    CreatePersonalAccessTokenRequest, PersonalAccessTokenResponse, PersonalAccessTokensResponse,
+};
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::{delete, get};
+use axum::{Extension, Json};
+
+pub trait PersonalAccessTokenRouter {
+    fn route_personal_access_token(self) -> Self;
+}
+
+impl PersonalAccessTokenRouter for axum::Router<AppModule> {
+    fn route_personal_access_token(self) -> Self {
+        self.route(
+            "/me/tokens",
+            get(get_personal_access_tokens).post(create_personal_access_token),
+        )
+        .route(
+            "/me/tokens/{token_id}",
+            delete(revoke_personal_access_token),
+        )
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/me/tokens",
+    description = "Create a personal access token for bots and CI. It is used as a bearer token in place of an OAuth2 access token, acting as you within its scopes and accounts. The token is only returned here. Personal access tokens cannot manage personal access tokens.",
+    request_body = CreatePersonalAccessTokenRequest,
+    responses(
+        (status = 201, description = "Personal access token created", body = PersonalAccessTokenResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 403, description = "Scope not granted to the calling token"),
+        (status = 404, description = "Account not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Me",
+)]
+pub(crate) async fn create_personal_access_token(
+    Extension(claims): Extension<AuthClaims>,
+    State(api): State<PersonalAccessTokenApi>,
+    Json(request): Json<CreatePersonalAccessTokenRequest>,
+) -> Result<(StatusCode, Json<PersonalAccessTokenResponse>), ErrorStatus> {
+    let auth_account_id = api
+        .resolve_auth_account_id(OidcAuthInfo::from(claims))
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    let token = api
+        .create_personal_access_token(&auth_account_id, request.into_dto())
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    Ok((StatusCode::CREATED, Json(token.into())))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/me/tokens",
+    description = "List your personal access tokens, newest first. Tokens themselves are never included.",
+    responses(
+        (status = 200, description = "Personal access tokens", body = PersonalAccessTokensResponse),
+        (status = 403, description = "Called with a personal access token"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Me",
+)]
+pub(crate) async fn get_personal_access_tokens(
+    Extension(claims): Extension<AuthClaims>,
+    State(api): State<PersonalAccessTokenApi>,
+) -> Result<Json<PersonalAccessTokensResponse>, ErrorStatus> {
+    let auth_account_id = api
+        .resolve_auth_account_id(OidcAuthInfo::from(claims))
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    let tokens = api
+        .get_personal_access_tokens(&auth_account_id)
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    Ok(Json(PersonalAccessTokensResponse {
+        items: tokens.into_iter().map(Into::into).collect(),
+    }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/me/tokens/{token_id}",
+    description = "Revoke one of your personal access tokens. It stops working immediately.",
+    params(("token_id" = String, Path, description = "Personal access token ID")),
+    responses(
+        (status = 204, description = "Personal access token revoked"),
+        (status = 400, description = "Invalid request"),
+        (status = 403, description = "Called with a personal access token"),
+        (status = 404, description = "Personal access token not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Me",
+)]
+pub(crate) async fn revoke_personal_access_token(
+    Extension(claims): Extension<AuthClaims>,
+    State(api): State<PersonalAccessTokenApi>,
+    Path(token_id): Path<String>,
+) -> Result<StatusCode, ErrorStatus> {
+    let auth_account_id = api
+        .resolve_auth_account_id(OidcAuthInfo::from(claims))
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    api.revoke_personal_access_token(&auth_account_id, token_id)
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::auth::{encode_test_jwt, generate_test_keys, AuthClaims, JwksCache, OidcConfig};
+    use crate::handler::AppModule;
+    use crate::route::build_test_router_with_auth;
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use http_body_util::BodyExt;
+    use std::sync::Arc;
+    use std::time::{SystemTime, UNIX_EPOCH};
+    use tower::ServiceExt;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn request(
+        method: &str,
+        uri: &str,
+        token: &str,
+        body: Option<serde_json::Value>,
+    ) -> Request<Body> {
+        let builder = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("authorization", format!("Bearer {token}"));
+        match body {
+            Some(body) => builder
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string())),
+            None => builder.body(Body::empty()),
+        }
+        .expect("valid request")
+    }
+
+    async fn response_json(response: axum::response::Response) -> serde_json::Value {
+        let body = response
+            .into_body()
+            .collect()
+            .await
+            .expect("response body")
+            .to_bytes();
+        serde_json::from_slice(&body).expect("JSON response")
+    }
+
+    #[test_with::env(DATABASE_URL)]
+    #[tokio::test]
+    async fn personal_access_tokens_authenticate_until_revoked() {
+        kernel::ensure_generator_initialized();
+        let keto = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/relation-tuples"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "relation_tuples": [],
+                "next_page_token": "",
+            })))
+            .mount(&keto)
+            .await;
+        let module = AppModule::new_for_test_urls(
+            "http://localhost:65535".to_string(),
+            "http://localhost:65535".to_string(),
+            keto.uri(),
+            "http://localhost:65535".to_string(),
+        )
+        .await
+        .expect("AppModule init failed (is DATABASE_URL set?)");
+
+        let issuer = format!("https://issuer.example/{}", uuid::Uuid::new_v4());
+        let keys = generate_test_keys();
+        let jwt = encode_test_jwt(
+            &AuthClaims {
+                iss: issuer.clone(),
+                sub: format!("subject-{}", uuid::Uuid::new_v4()),
+                aud: crate::auth::OneOrMany::One("emumet".to_string()),
+                exp: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .expect("system clock after Unix epoch")
+                    .as_secs()
+                    + 3600,
+                scp: None,
+                scope: Some("accounts:read accounts:write".to_string()),
+            },
+            &keys.encoding_key,
+            &keys.kid,
+        );
+        let router = build_test_router_with_auth(
+            module,
+            Arc::new(OidcConfig {
+                issuer_url: issuer.clone(),
+                expected_audience: "emumet".to_string(),
+                jwks_refetch_interval_secs: 0,
+            }),
+            Arc::new(JwksCache::new_with_jwks(issuer, keys.jwk_set)),
+        );
+
+        // The calling token cannot grant scopes it lacks.
+        let response = router
+            .clone()
+            .oneshot(request(
+                "POST",
+                "/api/v1/me/tokens",
+                &jwt,
+                Some(serde_json::json!({ "name": "CI", "scopes": ["sign"] })),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = router
+            .clone()
+            .oneshot(request(
+                "POST",
+                "/api/v1/me/tokens",
+                &jwt,
+                Some(serde_json::json!({ "name": "CI", "scopes": ["accounts:read"] })),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let created = response_json(response).await;
+        let pat = created["token"].as_str().expect("token").to_string();
+        let token_id = created["id"].as_str().expect("id").to_string();
+        assert_eq!(created["scopes"], serde_json::json!(["accounts:read"]));
+
+        let me_with_jwt = response_json(
+            router
+                .clone()
+                .oneshot(request("GET", "/api/v1/me", &jwt, None))
+                .await
+                .unwrap(),
+        )
+        .await;
+        let response = router
+            .clone()
+            .oneshot(request("GET", "/api/v1/me", &pat, None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response_json(response).await["account_id"],
+            me_with_jwt["account_id"]
+        );
+
+        // Limited to its scopes, and unable to manage tokens.
+        let response = router
+            .clone()
+            .oneshot(request(
+                "POST",
+                "/api/v1/accounts",
+                &pat,
+                Some(serde_json::json!({ "name": "bot", "is_bot": true })),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let response = router
+            .clone()
+            .oneshot(request("GET", "/api/v1/me/tokens", &pat, None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = router
+            .clone()
+            .oneshot(request("GET", "/api/v1/me/tokens", &jwt, None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let listed = response_json(response).await;
+        assert_eq!(listed["items"].as_array().map(Vec::len), Some(1));
+        assert_eq!(listed["items"][0]["id"], token_id.as_str());
+        assert!(listed["items"][0].get("token").is_none());
+        assert!(listed["items"][0]["last_used_at"].is_string());
+
+        let response = router
+            .clone()
+            .oneshot(request(
+                "DELETE",
+                &format!("/api/v1/me/tokens/{token_id}"),
+                &jwt,
+                None,
+            ))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        let response = router
+            .clone()
+            .oneshot(request("GET", "/api/v1/me", &pat, None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+}
@@ -0,0 +1,167 @@
+use crate::api::AdminWebhookApi;
+use crate::auth::{AuthClaims, OidcAuthInfo};
+use crate::error::ErrorStatus;
+use crate::handler::AppModule;
+use crate::schema::webhook::{
+    CreateWebhookSubscriptionRequest, GetWebhookDeliveriesQuery, WebhookDeliveriesResponse,
+    WebhookDeliveryResponse, WebhookSubscriptionResponse, WebhookSubscriptionsResponse,
+};
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::routing::{delete, get};
+use axum::{Extension, Json};
+
+pub trait AdminWebhookRouter {
+    fn route_admin_webhook(self) -> Self;
+}
+
+impl AdminWebhookRouter for axum::Router<AppModule> {
+    fn route_admin_webhook(self) -> Self {
+        self.route(
+            "/webhooks",
+            get(get_webhook_subscriptions).post(create_webhook_subscription),
+        )
+        .route(
+            "/webhooks/{webhook_id}",
+            delete(delete_webhook_subscription),
+        )
+        .route(
+            "/webhooks/{webhook_id}/deliveries",
+            get(get_webhook_deliveries),
+        )
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/webhooks",
+    description = "Register a webhook subscription. Deliveries are POSTed with an `X-Emumet-Webhook-Signature: sha256=<hex>` header, the HMAC-SHA256 of `\"{timestamp}.{body}\"` keyed by the secret. The secret is only returned here.",
+    request_body = CreateWebhookSubscriptionRequest,
+    responses(
+        (status = 201, description = "Webhook subscription created", body = WebhookSubscriptionResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 403, description = "Permission denied"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Webhook",
+)]
+pub(crate) async fn create_webhook_subscription(
+    Extension(claims): Extension<AuthClaims>,
+    State(api): State<AdminWebhookApi>,
+    Json(request): Json<CreateWebhookSubscriptionRequest>,
+) -> Result<(StatusCode, Json<WebhookSubscriptionResponse>), ErrorStatus> {
+    let auth_account_id = api
+        .resolve_auth_account_id(OidcAuthInfo::from(claims))
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    let subscription = api
+        .create_webhook_subscription(&auth_account_id, request.into_dto())
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    Ok((StatusCode::CREATED, Json(subscription.into())))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/webhooks",
+    description = "List webhook subscriptions. Secrets are never included.",
+    responses(
+        (status = 200, description = "Webhook subscriptions", body = WebhookSubscriptionsResponse),
+        (status = 403, description = "Permission denied"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Webhook",
+)]
+pub(crate) async fn get_webhook_subscriptions(
+    Extension(claims): Extension<AuthClaims>,
+    State(api): State<AdminWebhookApi>,
+) -> Result<Json<WebhookSubscriptionsResponse>, ErrorStatus> {
+    let auth_account_id = api
+        .resolve_auth_account_id(OidcAuthInfo::from(claims))
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    let subscriptions = api
+        .get_webhook_subscriptions(&auth_account_id)
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    Ok(Json(WebhookSubscriptionsResponse {
+        items: subscriptions.into_iter().map(Into::into).collect(),
+    }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/webhooks/{webhook_id}",
+    description = "Delete a webhook subscription together with its pending deliveries and delivery log.",
+    params(("webhook_id" = String, Path, description = "Webhook subscription ID")),
+    responses(
+        (status = 204, description = "Webhook subscription deleted"),
+        (status = 400, description = "Invalid request"),
+        (status = 403, description = "Permission denied"),
+        (status = 404, description = "Webhook subscription not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Webhook",
+)]
+pub(crate) async fn delete_webhook_subscription(
+    Extension(claims): Extension<AuthClaims>,
+    State(api): State<AdminWebhookApi>,
+    Path(webhook_id): Path<String>,
+) -> Result<StatusCode, ErrorStatus> {
+    let auth_account_id = api
+        .resolve_auth_account_id(OidcAuthInfo::from(claims))
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    api.delete_webhook_subscription(&auth_account_id, webhook_id)
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/webhooks/{webhook_id}/deliveries",
+    description = "Delivery log of a webhook subscription, newest first.",
+    params(
+        ("webhook_id" = String, Path, description = "Webhook subscription ID"),
+        ("limit" = Option<u32>, Query, description = "Page size (1-100, default 20)"),
+        ("cursor" = Option<String>, Query, description = "ID of the last delivery of the previous page"),
+    ),
+    responses(
+        (status = 200, description = "Webhook deliveries", body = WebhookDeliveriesResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 403, description = "Permission denied"),
+        (status = 404, description = "Webhook subscription not found"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Webhook",
+)]
+pub(crate) async fn get_webhook_deliveries(
+    Extension(claims): Extension<AuthClaims>,
+    State(api): State<AdminWebhookApi>,
+    Path(webhook_id): Path<String>,
+    Query(GetWebhookDeliveriesQuery { limit, cursor }): Query<GetWebhookDeliveriesQuery>,
+) -> Result<Json<WebhookDeliveriesResponse>, ErrorStatus> {
+    let auth_account_id = api
+        .resolve_auth_account_id(OidcAuthInfo::from(claims))
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    let deliveries = api
+        .get_webhook_deliveries(&auth_account_id, webhook_id, limit, cursor)
+        .await
+        .map_err(ErrorStatus::from)?;
+
+    Ok(Json(WebhookDeliveriesResponse {
+        items: deliveries
+            .into_iter()
+            .map(WebhookDeliveryResponse::from)
+            .collect(),
+    }))
+}
@@ -1,4 +1,8 @@
 pub mod account;
+pub mod custom_role;
+pub mod health;
 pub mod me;
 pub mod media;
 pub mod oauth2;
+pub mod personal_access_token;
+pub mod webhook;
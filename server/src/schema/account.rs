@@ -1,7 +1,10 @@
+use crate::schema::media::{ImageFocus, ImageRenditionResponse};
 use application::dto::account::{
-    AccountDetailDto, AccountFieldDto, CreateAccountDto, ModerationDto, UpdateAccountDto,
+    AccountDetailDto, AccountDto, AccountFieldDto, CreateAccountDto, InstanceRoleChangeDto,
+    InstanceRoleHolderDto, ModerationDto, UpdateAccountDto,
 };
-use kernel::prelude::entity::FieldAction;
+use kernel::interfaces::permission::InstanceRole;
+use kernel::prelude::entity::{FieldAction, InstanceRoleChangeAction};
 use serde::{Deserialize, Deserializer, Serialize};
 use time::OffsetDateTime;
 use utoipa::ToSchema;
@@ -46,6 +49,20 @@ pub struct UpdateAccountRequest {
     #[serde(default, deserialize_with = "deserialize_optional_nullable_string")]
     #[schema(nullable)]
     pub banner_url: Option<Option<String>>,
+    /// Alt text of the image in the icon slot after this update.
+    #[serde(default, deserialize_with = "deserialize_optional_nullable_string")]
+    #[schema(nullable)]
+    pub icon_description: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_optional_nullable_focus")]
+    #[schema(nullable)]
+    pub icon_focus: Option<Option<ImageFocus>>,
+    /// Alt text of the image in the banner slot after this update.
+    #[serde(default, deserialize_with = "deserialize_optional_nullable_string")]
+    #[schema(nullable)]
+    pub banner_description: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_optional_nullable_focus")]
+    #[schema(nullable)]
+    pub banner_focus: Option<Option<ImageFocus>>,
     #[serde(default, deserialize_with = "deserialize_optional_nullable_fields")]
     #[schema(nullable)]
     pub fields: Option<Option<Vec<AccountField>>>,
@@ -65,6 +82,10 @@ impl UpdateAccountRequest {
             summary: into_field_action(self.summary),
             icon_url: into_field_action(self.icon_url),
             banner_url: into_field_action(self.banner_url),
+            icon_description: into_field_action(self.icon_description),
+            icon_focus: into_field_action(self.icon_focus.map(|focus| focus.map(Into::into))),
+            banner_description: into_field_action(self.banner_description),
+            banner_focus: into_field_action(self.banner_focus.map(|focus| focus.map(Into::into))),
             fields,
         })
     }
@@ -96,6 +117,15 @@ where
     Option::<bool>::deserialize(deserializer).map(Some)
 }
 
+fn deserialize_optional_nullable_focus<'de, D>(
+    deserializer: D,
+) -> Result<Option<Option<ImageFocus>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<ImageFocus>::deserialize(deserializer).map(Some)
+}
+
 fn deserialize_optional_nullable_fields<'de, D>(
     deserializer: D,
 ) -> Result<Option<Option<Vec<AccountField>>>, D::Error>
@@ -140,6 +170,11 @@ pub struct AccountResponse {
     pub summary: Option<String>,
     pub icon_url: Option<String>,
     pub banner_url: Option<String>,
+    /// Icon as the 400x400 avatar rendition when one exists, or the animated
+    /// original with that rendition in `static_url`.
+    pub icon: Option<ImageRenditionResponse>,
+    /// Banner as the 1500x500 header rendition when one exists.
+    pub banner: Option<ImageRenditionResponse>,
     pub is_bot: bool,
     pub fields: Vec<AccountField>,
     #[serde(with = "time::serde::rfc3339")]
@@ -205,6 +240,114 @@ pub struct RelationListResponse {
     pub items: Vec<RelationResponse>,
 }
 
+/// A relation an auth account holds on a shared account.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccountMemberResponse {
+    /// The `account_id` of the member's `GET /api/v1/me`.
+    pub auth_account_id: String,
+    /// `owner`, `editor` or `signer`.
+    pub relation: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AccountMembersResponse {
+    pub items: Vec<AccountMemberResponse>,
+}
+
+pub fn instance_role_name(role: InstanceRole) -> &'static str {
+    match role {
+        InstanceRole::Admin => "admin",
+        InstanceRole::Moderator => "moderator",
+    }
+}
+
+/// An account linked to an instance role holder.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct InstanceRoleHolderAccountResponse {
+    pub id: String,
+    pub name: String,
+    pub moderation: Option<ModerationResponse>,
+}
+
+/// An auth account holding an instance role directly.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct InstanceRoleHolderResponse {
+    /// The `account_id` of the holder's `GET /api/v1/me`.
+    pub auth_account_id: String,
+    /// `admin` or `moderator`.
+    pub role: String,
+    pub accounts: Vec<InstanceRoleHolderAccountResponse>,
+}
+
+impl From<AccountDto> for InstanceRoleHolderAccountResponse {
+    fn from(dto: AccountDto) -> Self {
+        Self {
+            moderation: to_moderation_response(dto.moderation.as_ref()),
+            id: dto.nanoid,
+            name: dto.name,
+        }
+    }
+}
+
+impl From<InstanceRoleHolderDto> for InstanceRoleHolderResponse {
+    fn from(dto: InstanceRoleHolderDto) -> Self {
+        Self {
+            auth_account_id: dto.auth_account_id,
+            role: instance_role_name(dto.role).to_string(),
+            accounts: dto.accounts.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct InstanceRoleHoldersResponse {
+    pub items: Vec<InstanceRoleHolderResponse>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct GetInstanceRoleHistoryQuery {
+    pub limit: Option<u32>,
+    pub cursor: Option<String>,
+}
+
+/// A grant or revocation of an instance role.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct InstanceRoleChangeResponse {
+    pub id: String,
+    /// `admin` or `moderator`.
+    pub role: String,
+    /// `granted` or `revoked`.
+    pub action: String,
+    /// Auth account the role was granted to or revoked from.
+    pub subject_auth_account_id: String,
+    /// Auth account of the admin who made the change.
+    pub actor_auth_account_id: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+impl From<InstanceRoleChangeDto> for InstanceRoleChangeResponse {
+    fn from(dto: InstanceRoleChangeDto) -> Self {
+        Self {
+            id: dto.id,
+            role: instance_role_name(dto.role).to_string(),
+            action: match dto.action {
+                InstanceRoleChangeAction::Granted => "granted",
+                InstanceRoleChangeAction::Revoked => "revoked",
+            }
+            .to_string(),
+            subject_auth_account_id: dto.subject_auth_account_id,
+            actor_auth_account_id: dto.actor_auth_account_id,
+            created_at: dto.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct InstanceRoleHistoryResponse {
+    pub items: Vec<InstanceRoleChangeResponse>,
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct FollowAccountResponse {
@@ -240,6 +383,8 @@ pub fn account_dto_to_response(account: AccountDetailDto) -> AccountResponse {
         summary: account.summary,
         icon_url: account.icon_url,
         banner_url: account.banner_url,
+        icon: account.icon.map(Into::into),
+        banner: account.banner.map(Into::into),
         is_bot: account.is_bot,
         fields: account
             .fields
@@ -282,4 +427,21 @@ mod tests {
 
         assert!(matches!(dto.summary, FieldAction::Clear));
     }
+
+    #[test]
+    fn patch_request_carries_image_metadata() {
+        let request: UpdateAccountRequest = serde_json::from_str(
+            r#"{"icon_description":"A red fox","icon_focus":{"x":0.5,"y":-0.25},"banner_focus":null}"#,
+        )
+        .unwrap();
+
+        let dto = request.into_dto("account-id".to_string()).unwrap();
+
+        assert!(matches!(dto.icon_description, FieldAction::Set(value) if value == "A red fox"));
+        assert!(
+            matches!(dto.icon_focus, FieldAction::Set(focus) if focus.x == 0.5 && focus.y == -0.25)
+        );
+        assert!(matches!(dto.banner_description, FieldAction::Unchanged));
+        assert!(matches!(dto.banner_focus, FieldAction::Clear));
+    }
 }
@@ -0,0 +1,54 @@
+use application::dto::custom_role::CustomRoleDto;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use utoipa::ToSchema;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateCustomRoleRequest {
+    pub name: String,
+    /// Instance permissions bundled by the role, e.g. `manage_media` or
+    /// `view_audit_log`. `administrate` cannot be bundled.
+    pub permissions: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateCustomRoleRequest {
+    pub name: Option<String>,
+    /// Replaces the bundled permissions when present.
+    pub permissions: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CustomRoleResponse {
+    pub id: String,
+    pub name: String,
+    pub permissions: Vec<String>,
+    /// Auth account ids of the role's members.
+    pub members: Vec<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub updated_at: OffsetDateTime,
+}
+
+impl From<CustomRoleDto> for CustomRoleResponse {
+    fn from(dto: CustomRoleDto) -> Self {
+        Self {
+            id: dto.id,
+            name: dto.name,
+            permissions: dto
+                .permissions
+                .iter()
+                .map(|permission| permission.as_str().to_string())
+                .collect(),
+            members: dto.members,
+            created_at: dto.created_at,
+            updated_at: dto.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CustomRolesResponse {
+    pub items: Vec<CustomRoleResponse>,
+}
@@ -0,0 +1,43 @@
+use application::dto::projection::ProjectorHealthDto;
+use serde::Serialize;
+use time::OffsetDateTime;
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ProjectorHealthResponse {
+    pub name: String,
+    /// Last event log `seq` the projector has applied.
+    pub checkpoint: i64,
+    /// Highest committed `seq` in the projector's event log.
+    pub head_seq: i64,
+    /// Events not yet projected (`head_seq - checkpoint`).
+    pub lag: i64,
+    /// False when the latest poll of this server's worker failed.
+    pub healthy: bool,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub last_success_at: Option<OffsetDateTime>,
+    /// When the latest failed poll happened. The error itself is only
+    /// logged, since this endpoint is unauthenticated.
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub last_error_at: Option<OffsetDateTime>,
+}
+
+impl From<ProjectorHealthDto> for ProjectorHealthResponse {
+    fn from(dto: ProjectorHealthDto) -> Self {
+        Self {
+            name: dto.name,
+            checkpoint: dto.checkpoint,
+            head_seq: dto.head_seq,
+            lag: dto.lag,
+            healthy: dto.healthy,
+            last_success_at: dto.last_success_at,
+            last_error_at: dto.last_error_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ProjectionHealthResponse {
+    pub healthy: bool,
+    pub projectors: Vec<ProjectorHealthResponse>,
+}
@@ -1,4 +1,7 @@
+use crate::hydra::PreviousConsentSession;
+use crate::kratos::KratosSessionDetail;
 use serde::Serialize;
+use time::OffsetDateTime;
 use utoipa::ToSchema;
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -6,3 +9,83 @@ pub struct MeResponse {
     pub account_id: String,
     pub instance_roles: Vec<String>,
 }
+
+/// A Kratos login session of the signed-in identity.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LoginSessionResponse {
+    pub id: String,
+    /// Methods used to sign in, e.g. `password` or `totp`.
+    pub authentication_methods: Vec<String>,
+    pub devices: Vec<LoginSessionDeviceResponse>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub authenticated_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub expires_at: Option<OffsetDateTime>,
+}
+
+impl From<KratosSessionDetail> for LoginSessionResponse {
+    fn from(session: KratosSessionDetail) -> Self {
+        Self {
+            id: session.id,
+            authentication_methods: session
+                .authentication_methods
+                .into_iter()
+                .map(|method| method.method)
+                .collect(),
+            devices: session
+                .devices
+                .into_iter()
+                .map(|device| LoginSessionDeviceResponse {
+                    ip_address: device.ip_address,
+                    user_agent: device.user_agent,
+                    location: device.location,
+                })
+                .collect(),
+            authenticated_at: session.authenticated_at,
+            expires_at: session.expires_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LoginSessionDeviceResponse {
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub location: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LoginSessionsResponse {
+    pub items: Vec<LoginSessionResponse>,
+}
+
+/// An OAuth2 client the signed-in identity consented to.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ConsentResponse {
+    pub client_id: String,
+    pub client_name: Option<String>,
+    pub scopes: Vec<String>,
+    /// Whether the consent is reused instead of asking again.
+    pub remember: bool,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub granted_at: Option<OffsetDateTime>,
+}
+
+impl ConsentResponse {
+    /// `None` for consents whose client is gone.
+    pub fn from_session(session: PreviousConsentSession) -> Option<Self> {
+        let client = session.consent_request?.client?;
+        Some(Self {
+            client_id: client.client_id?,
+            client_name: client.client_name,
+            scopes: session.grant_scope,
+            remember: session.remember,
+            granted_at: session.handled_at,
+        })
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ConsentsResponse {
+    pub items: Vec<ConsentResponse>,
+}
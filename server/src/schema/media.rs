@@ -1,13 +1,111 @@
-use application::dto::media::UploadedImageDto;
-use serde::Serialize;
+use application::dto::media::{
+    BlockImageDto, CreateUploadSessionDto, ImageBlocklistEntryDto, ImageFocusDto,
+    ImageRenditionDto, ImageVariantDto, UploadSessionDto, UploadedImageDto,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use time::OffsetDateTime;
 use utoipa::ToSchema;
 
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateUploadSessionRequest {
+    /// `image/png`, `image/jpeg`, `image/webp` or `image/gif`.
+    pub content_type: String,
+    /// Exact size of the file that will be uploaded, at most 40 MiB.
+    pub byte_size: u64,
+    /// Alt text, up to 1500 characters.
+    pub description: Option<String>,
+    pub focus: Option<ImageFocus>,
+}
+
+impl CreateUploadSessionRequest {
+    pub fn into_dto(self) -> CreateUploadSessionDto {
+        CreateUploadSessionDto {
+            content_type: self.content_type,
+            byte_size: self.byte_size,
+            description: self.description,
+            focus: self.focus.map(Into::into),
+        }
+    }
+}
+
+/// Send the file as the body of a `method` request to `upload_url` with
+/// exactly these `headers`, then finalize the session.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UploadSessionResponse {
+    pub id: String,
+    pub upload_url: String,
+    pub method: String,
+    pub headers: BTreeMap<String, String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub expires_at: OffsetDateTime,
+}
+
+impl From<UploadSessionDto> for UploadSessionResponse {
+    fn from(dto: UploadSessionDto) -> Self {
+        Self {
+            id: dto.id,
+            upload_url: dto.upload_url,
+            method: dto.method,
+            headers: dto.headers.into_iter().collect(),
+            expires_at: dto.expires_at,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub struct UploadedImageResponse {
     pub id: String,
     pub url: String,
     pub hash: String,
     pub blur_hash: String,
+    pub variants: Vec<ImageVariantResponse>,
+    /// The stored image is an animated WebP; the `static` variant holds its
+    /// first frame.
+    pub animated: bool,
+    /// Alt text.
+    pub description: Option<String>,
+    pub focus: Option<ImageFocus>,
+    /// The upload matched a quarantine entry of the image blocklist. It
+    /// cannot be used as icon or banner until a moderator releases it.
+    pub quarantined: bool,
+}
+
+/// Focal point of an image, as in Mastodon's `focalPoint`: `x` from -1
+/// (left) to 1 (right), `y` from -1 (bottom) to 1 (top).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+pub struct ImageFocus {
+    pub x: f32,
+    pub y: f32,
+}
+
+/// A fixed-size rendition generated from the upload.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImageVariantResponse {
+    /// `avatar` (400x400), `header` (1500x500), `thumbnail` (fits 160x160)
+    /// or, for animations only, `static` (the full-size first frame).
+    /// Smaller uploads are never scaled up.
+    pub kind: String,
+    pub url: String,
+    pub media_type: String,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// The image to display in a profile slot. Size and media type are absent
+/// when the original is served because no rendition exists. Animated images
+/// are served as their animated WebP original, with the still rendition in
+/// `static_url`.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImageRenditionResponse {
+    pub url: String,
+    pub media_type: Option<String>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub static_url: Option<String>,
+    /// Alt text.
+    pub description: Option<String>,
+    pub focus: Option<ImageFocus>,
 }
 
 impl From<UploadedImageDto> for UploadedImageResponse {
@@ -17,6 +115,113 @@ impl From<UploadedImageDto> for UploadedImageResponse {
             url: dto.url,
             hash: dto.hash,
             blur_hash: dto.blur_hash,
+            variants: dto.variants.into_iter().map(Into::into).collect(),
+            animated: dto.animated,
+            description: dto.description,
+            focus: dto.focus.map(Into::into),
+            quarantined: dto.quarantined,
+        }
+    }
+}
+
+impl From<ImageFocusDto> for ImageFocus {
+    fn from(dto: ImageFocusDto) -> Self {
+        Self { x: dto.x, y: dto.y }
+    }
+}
+
+impl From<ImageFocus> for ImageFocusDto {
+    fn from(focus: ImageFocus) -> Self {
+        Self {
+            x: focus.x,
+            y: focus.y,
         }
     }
 }
+
+impl From<ImageVariantDto> for ImageVariantResponse {
+    fn from(dto: ImageVariantDto) -> Self {
+        Self {
+            kind: dto.kind,
+            url: dto.url,
+            media_type: dto.media_type,
+            width: dto.width,
+            height: dto.height,
+        }
+    }
+}
+
+impl From<ImageRenditionDto> for ImageRenditionResponse {
+    fn from(dto: ImageRenditionDto) -> Self {
+        Self {
+            url: dto.url,
+            media_type: dto.media_type,
+            width: dto.width,
+            height: dto.height,
+            static_url: dto.static_url,
+            description: dto.description,
+            focus: dto.focus.map(Into::into),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BlockImageRequest {
+    /// What happens to later uploads matching the image: `reject` or
+    /// `quarantine`.
+    pub action: String,
+    /// Shown to moderators only, up to 500 characters.
+    pub reason: String,
+}
+
+impl BlockImageRequest {
+    pub fn into_dto(self) -> BlockImageDto {
+        BlockImageDto {
+            action: self.action,
+            reason: self.reason,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImageBlocklistEntryResponse {
+    pub id: String,
+    /// SHA-256 of the blocked bytes.
+    pub hash: Option<String>,
+    /// 64-bit difference hash as 16 hex digits. Uploads within a few bits
+    /// of it match too.
+    pub perceptual_hash: Option<String>,
+    pub action: String,
+    pub reason: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+impl From<ImageBlocklistEntryDto> for ImageBlocklistEntryResponse {
+    fn from(dto: ImageBlocklistEntryDto) -> Self {
+        Self {
+            id: dto.id,
+            hash: dto.hash,
+            perceptual_hash: dto.perceptual_hash,
+            action: dto.action,
+            reason: dto.reason,
+            created_at: dto.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImageBlocklistResponse {
+    pub items: Vec<ImageBlocklistEntryResponse>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct GetQuarantinedImagesQuery {
+    pub limit: Option<u32>,
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct QuarantinedImagesResponse {
+    pub items: Vec<UploadedImageResponse>,
+}
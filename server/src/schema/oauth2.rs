@@ -1,6 +1,7 @@
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use axum::Json;
+use kernel::interfaces::permission::Scope;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
@@ -24,9 +25,31 @@ pub enum OAuth2Response {
         consent_challenge: String,
         client_name: Option<String>,
         requested_scope: Vec<String>,
+        /// What each requested Emumet scope allows, in the order requested.
+        /// Scopes such as `openid` are not listed.
+        scope_details: Vec<ScopeDetail>,
     },
 }
 
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ScopeDetail {
+    pub scope: String,
+    pub description: String,
+}
+
+impl ScopeDetail {
+    pub fn for_requested(requested_scope: &[String]) -> Vec<Self> {
+        requested_scope
+            .iter()
+            .filter_map(|scope| Scope::parse(scope))
+            .map(|scope| Self {
+                scope: scope.as_str().to_string(),
+                description: scope.description().to_string(),
+            })
+            .collect()
+    }
+}
+
 impl IntoResponse for OAuth2Response {
     fn into_response(self) -> axum::response::Response {
         match self {
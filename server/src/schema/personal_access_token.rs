@@ -0,0 +1,70 @@
+use application::dto::personal_access_token::{
+    CreatePersonalAccessTokenDto, PersonalAccessTokenDto,
+};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use utoipa::ToSchema;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreatePersonalAccessTokenRequest {
+    pub name: String,
+    /// Scopes such as `accounts:read` or `sign`; the calling access token
+    /// must carry each of them.
+    pub scopes: Vec<String>,
+    /// Account IDs the token is limited to; omitted allows every account
+    /// you have a relation on. Limited tokens cannot create accounts.
+    pub account_ids: Option<Vec<String>>,
+    /// Never expires when omitted.
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub expires_at: Option<OffsetDateTime>,
+}
+
+impl CreatePersonalAccessTokenRequest {
+    pub fn into_dto(self) -> CreatePersonalAccessTokenDto {
+        CreatePersonalAccessTokenDto {
+            name: self.name,
+            scopes: self.scopes,
+            account_ids: self.account_ids,
+            expires_at: self.expires_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PersonalAccessTokenResponse {
+    pub id: String,
+    pub name: String,
+    /// Only present in the create response. Use it as a bearer token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    pub scopes: Vec<String>,
+    /// Absent when the token is not limited to accounts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_ids: Option<Vec<String>>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub expires_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub last_used_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+impl From<PersonalAccessTokenDto> for PersonalAccessTokenResponse {
+    fn from(dto: PersonalAccessTokenDto) -> Self {
+        Self {
+            id: dto.id,
+            name: dto.name,
+            token: dto.token,
+            scopes: dto.scopes,
+            account_ids: dto.account_ids,
+            expires_at: dto.expires_at,
+            last_used_at: dto.last_used_at,
+            created_at: dto.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PersonalAccessTokensResponse {
+    pub items: Vec<PersonalAccessTokenResponse>,
+}
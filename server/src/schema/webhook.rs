@@ -0,0 +1,102 @@
+use application::dto::webhook::{
+    CreateWebhookSubscriptionDto, WebhookDeliveryDto, WebhookSubscriptionDto,
+};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use utoipa::ToSchema;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateWebhookSubscriptionRequest {
+    pub url: String,
+    /// Event types such as `account.suspended` or `profile.*`; empty or
+    /// omitted subscribes to every event.
+    #[serde(default)]
+    pub event_types: Vec<String>,
+    /// HMAC signing secret (at least 16 characters); generated when omitted.
+    pub secret: Option<String>,
+}
+
+impl CreateWebhookSubscriptionRequest {
+    pub fn into_dto(self) -> CreateWebhookSubscriptionDto {
+        CreateWebhookSubscriptionDto {
+            url: self.url,
+            event_types: self.event_types,
+            secret: self.secret,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct GetWebhookDeliveriesQuery {
+    pub limit: Option<u32>,
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookSubscriptionResponse {
+    pub id: String,
+    pub url: String,
+    pub event_types: Vec<String>,
+    /// Only present in the create response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+impl From<WebhookSubscriptionDto> for WebhookSubscriptionResponse {
+    fn from(dto: WebhookSubscriptionDto) -> Self {
+        Self {
+            id: dto.id,
+            url: dto.url,
+            event_types: dto.event_types,
+            secret: dto.secret,
+            created_at: dto.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookSubscriptionsResponse {
+    pub items: Vec<WebhookSubscriptionResponse>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookDeliveryResponse {
+    pub id: String,
+    pub event_id: String,
+    pub event_type: String,
+    /// `pending`, `delivered` or `failed`.
+    pub status: String,
+    pub attempts: i32,
+    #[serde(with = "time::serde::rfc3339")]
+    pub next_attempt_at: OffsetDateTime,
+    pub last_status_code: Option<i32>,
+    pub last_error: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub delivered_at: Option<OffsetDateTime>,
+}
+
+impl From<WebhookDeliveryDto> for WebhookDeliveryResponse {
+    fn from(dto: WebhookDeliveryDto) -> Self {
+        Self {
+            id: dto.id,
+            event_id: dto.event_id,
+            event_type: dto.event_type,
+            status: dto.status,
+            attempts: dto.attempts,
+            next_attempt_at: dto.next_attempt_at,
+            last_status_code: dto.last_status_code,
+            last_error: dto.last_error,
+            created_at: dto.created_at,
+            delivered_at: dto.delivered_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WebhookDeliveriesResponse {
+    pub items: Vec<WebhookDeliveryResponse>,
+}
@@ -0,0 +1,262 @@
+//! Prometheus recorder behind `GET /metrics` and the series owned by the
+//! server. The use cases record their own series (see
+//! `application::telemetry`) through the same global recorder.
+
+use application::telemetry::{INBOX_ACTIVITIES_TOTAL, OUTBOX_DELIVERIES_TOTAL};
+use axum::extract::{MatchedPath, Request};
+use axum::http::Method;
+use axum::middleware::Next;
+use axum::response::Response;
+use driver::database::PoolUsage;
+use metrics::{counter, describe_counter, describe_gauge, describe_histogram, gauge, histogram};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+pub const HTTP_REQUESTS_TOTAL: &str = "emumet_http_requests_total";
+pub const HTTP_REQUEST_DURATION_SECONDS: &str = "emumet_http_request_duration_seconds";
+pub const SIGNATURE_VERIFICATION_FAILURES_TOTAL: &str =
+    "emumet_signature_verification_failures_total";
+pub const PROJECTION_LAG: &str = "emumet_projection_lag";
+pub const PROJECTION_HEALTHY: &str = "emumet_projection_healthy";
+pub const DATABASE_POOL_CONNECTIONS: &str = "emumet_database_pool_connections";
+pub const DATABASE_POOL_MAX_CONNECTIONS: &str = "emumet_database_pool_max_connections";
+pub const JWKS_REFRESH_FAILURES_TOTAL: &str = "emumet_jwks_refresh_failures_total";
+
+const HTTP_DURATION_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Failure codes the HTTP signature verifier prefixes its messages with.
+const SIGNATURE_FAILURE_CODES: &[&str] = &[
+    "MissingSignature",
+    "MalformedSignature",
+    "UnsupportedSignature",
+    "StaleDate",
+    "DigestMismatch",
+    "KeyFetchFailed",
+    "InvalidSignature",
+];
+
+/// Rejections decided by the inbox route itself rather than the verifier.
+pub const HOST_MISMATCH: &str = "HostMismatch";
+pub const ACTOR_MISMATCH: &str = "ActorMismatch";
+const INBOX_REJECTION_CODES: &[&str] = &[HOST_MISMATCH, ACTOR_MISMATCH];
+
+static PROMETHEUS: OnceLock<PrometheusHandle> = OnceLock::new();
+
+/// Install the global recorder on first use. Later calls return the same
+/// handle, so every router built in one process renders the same registry.
+pub fn prometheus_handle() -> PrometheusHandle {
+    PROMETHEUS
+        .get_or_init(|| {
+            let handle = PrometheusBuilder::new()
+                .set_buckets_for_metric(
+                    Matcher::Full(HTTP_REQUEST_DURATION_SECONDS.to_string()),
+                    HTTP_DURATION_BUCKETS,
+                )
+                .expect("HTTP duration buckets are not empty")
+                .install_recorder()
+                .expect("no other metrics recorder is installed");
+            describe();
+            handle
+        })
+        .clone()
+}
+
+fn describe() {
+    describe_counter!(
+        HTTP_REQUESTS_TOTAL,
+        "HTTP requests by method, matched route and status"
+    );
+    describe_histogram!(
+        HTTP_REQUEST_DURATION_SECONDS,
+        metrics::Unit::Seconds,
+        "HTTP request latency by method and matched route"
+    );
+    describe_counter!(
+        INBOX_ACTIVITIES_TOTAL,
+        "ActivityPub inbox activities by type and outcome"
+    );
+    describe_counter!(
+        OUTBOX_DELIVERIES_TOTAL,
+        "ActivityPub deliveries by outcome and peer host"
+    );
+    describe_counter!(
+        SIGNATURE_VERIFICATION_FAILURES_TOTAL,
+        "Inbox requests rejected during HTTP signature verification, by reason"
+    );
+    describe_gauge!(
+        PROJECTION_LAG,
+        "Events in the log not yet applied by the projector"
+    );
+    describe_gauge!(
+        PROJECTION_HEALTHY,
+        "1 when the projector's latest poll on this server succeeded"
+    );
+    describe_gauge!(
+        DATABASE_POOL_CONNECTIONS,
+        "Open database connections by state"
+    );
+    describe_gauge!(
+        DATABASE_POOL_MAX_CONNECTIONS,
+        "Database connection pool capacity"
+    );
+    describe_counter!(
+        JWKS_REFRESH_FAILURES_TOTAL,
+        "Failed OIDC discovery or JWKS fetches, by stage"
+    );
+}
+
+/// Drain histogram samples in the background so they stay bounded between
+/// scrapes.
+pub fn spawn_upkeep(handle: PrometheusHandle, interval: Duration) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            handle.run_upkeep();
+        }
+    });
+}
+
+/// Count and time every request by its matched route template, so path
+/// parameters never become label values. Requests no route matched share
+/// the `unmatched` route label.
+pub async fn track_http_requests(request: Request, next: Next) -> Response {
+    let method = method_label(request.method());
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+    let started = Instant::now();
+    let response = next.run(request).await;
+    histogram!(
+        HTTP_REQUEST_DURATION_SECONDS,
+        "method" => method,
+        "route" => route.clone()
+    )
+    .record(started.elapsed().as_secs_f64());
+    counter!(
+        HTTP_REQUESTS_TOTAL,
+        "method" => method,
+        "route" => route,
+        "status" => response.status().as_u16().to_string()
+    )
+    .increment(1);
+    response
+}
+
+fn method_label(method: &Method) -> &'static str {
+    match *method {
+        Method::GET => "GET",
+        Method::POST => "POST",
+        Method::PUT => "PUT",
+        Method::PATCH => "PATCH",
+        Method::DELETE => "DELETE",
+        Method::HEAD => "HEAD",
+        Method::OPTIONS => "OPTIONS",
+        _ => "other",
+    }
+}
+
+/// Count an inbox request rejected before its activity was handled.
+/// `reason` is either a code chosen by the inbox route or a message of
+/// `SignatureVerificationResult::Invalid`, reduced to its failure code.
+pub fn record_signature_failure(reason: &str) {
+    counter!(
+        SIGNATURE_VERIFICATION_FAILURES_TOTAL,
+        "reason" => signature_failure_code(reason)
+    )
+    .increment(1);
+}
+
+fn signature_failure_code(reason: &str) -> &'static str {
+    let code = reason
+        .split_once(':')
+        .map_or(reason, |(code, _)| code)
+        .trim();
+    SIGNATURE_FAILURE_CODES
+        .iter()
+        .chain(INBOX_REJECTION_CODES)
+        .find(|known| **known == code)
+        .copied()
+        .unwrap_or("Other")
+}
+
+pub fn record_projection_lag(projector: &str, lag: i64, healthy: bool) {
+    gauge!(PROJECTION_LAG, "projector" => projector.to_string()).set(lag as f64);
+    gauge!(PROJECTION_HEALTHY, "projector" => projector.to_string()).set(if healthy {
+        1.0
+    } else {
+        0.0
+    });
+}
+
+pub fn record_pool_usage(usage: PoolUsage) {
+    let in_use = usage.size.saturating_sub(usage.idle);
+    gauge!(DATABASE_POOL_CONNECTIONS, "state" => "idle").set(usage.idle);
+    gauge!(DATABASE_POOL_CONNECTIONS, "state" => "in_use").set(in_use);
+    gauge!(DATABASE_POOL_MAX_CONNECTIONS).set(usage.max);
+}
+
+/// `stage` is `discovery` or `jwks`.
+pub fn record_jwks_refresh_failure(stage: &'static str) {
+    counter!(JWKS_REFRESH_FAILURES_TOTAL, "stage" => stage).increment(1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::routing::get;
+    use tower::ServiceExt;
+
+    #[test]
+    fn signature_failures_are_labelled_by_code() {
+        assert_eq!(
+            signature_failure_code("StaleDate: Date header is expired"),
+            "StaleDate"
+        );
+        assert_eq!(
+            signature_failure_code("KeyFetchFailed: SsrfBlocked: private address"),
+            "KeyFetchFailed"
+        );
+        assert_eq!(signature_failure_code(HOST_MISMATCH), HOST_MISMATCH);
+        assert_eq!(
+            signature_failure_code("publicKey field is not an object"),
+            "Other"
+        );
+    }
+
+    #[tokio::test]
+    async fn http_requests_are_labelled_by_route_template() {
+        let handle = prometheus_handle();
+        let router = axum::Router::new()
+            .route("/telemetry-test/{id}", get(|| async { "ok" }))
+            .layer(axum::middleware::from_fn(track_http_requests));
+        for id in ["a", "b"] {
+            let request = Request::builder()
+                .uri(format!("/telemetry-test/{id}"))
+                .body(Body::empty())
+                .unwrap();
+            router.clone().oneshot(request).await.unwrap();
+        }
+        let request = Request::builder()
+            .uri("/telemetry-test")
+            .body(Body::empty())
+            .unwrap();
+        router.oneshot(request).await.unwrap();
+
+        let rendered = handle.render();
+        assert!(rendered.contains(
+            r#"emumet_http_requests_total{method="GET",route="/telemetry-test/{id}",status="200"} 2"#
+        ));
+        assert!(rendered.contains(
+            r#"emumet_http_request_duration_seconds_bucket{method="GET",route="/telemetry-test/{id}",le="+Inf"} 2"#
+        ));
+        assert!(rendered.contains(r#"route="unmatched",status="404""#));
+        assert!(!rendered.contains("/telemetry-test/a"));
+    }
+}
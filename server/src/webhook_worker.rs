@@ -0,0 +1,110 @@
+use crate::handler::AppModule;
+use application::permission::without_scope_restriction;
+use application::webhook::{DeliverDueWebhooks, FanOutWebhookEvents, PruneWebhookDeliveries};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+/// How often finished deliveries past their retention are pruned.
+const WEBHOOK_PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Poll-driven webhook worker: fans the account, profile and metadata event
+/// logs out to subscriptions, then attempts the deliveries that are due.
+/// Hourly, it prunes finished deliveries older than `retention`.
+/// Stops on shutdown trigger.
+pub struct WebhookWorker {
+    module: Arc<AppModule>,
+    interval: Duration,
+    retention: time::Duration,
+    shutdown: watch::Receiver<bool>,
+}
+
+/// Cooperative shutdown handle for the worker.
+#[derive(Clone)]
+pub struct WebhookShutdown {
+    tx: watch::Sender<bool>,
+}
+
+impl WebhookShutdown {
+    pub fn trigger(&self) {
+        let _ = self.tx.send(true);
+    }
+}
+
+impl WebhookWorker {
+    pub fn spawn(
+        module: Arc<AppModule>,
+        interval: Duration,
+        retention: time::Duration,
+    ) -> (JoinHandle<()>, WebhookShutdown) {
+        let (tx, rx) = watch::channel(false);
+        let worker = Self {
+            module,
+            interval,
+            retention,
+            shutdown: rx,
+        };
+        let handle = tokio::spawn(without_scope_restriction(worker.run()));
+        (handle, WebhookShutdown { tx })
+    }
+
+    async fn run(mut self) {
+        let mut ticker = tokio::time::interval(self.interval);
+        let mut prune_ticker = tokio::time::interval(WEBHOOK_PRUNE_INTERVAL);
+        loop {
+            if *self.shutdown.borrow() {
+                break;
+            }
+            tokio::select! {
+                _ = ticker.tick() => {
+                    if let Err(error) = self.module.fan_out_account_events().await {
+                        tracing::error!(error = %error, "account webhook fan-out failed");
+                    }
+                    if let Err(error) = self.module.fan_out_profile_events().await {
+                        tracing::error!(error = %error, "profile webhook fan-out failed");
+                    }
+                    if let Err(error) = self.module.fan_out_metadata_events().await {
+                        tracing::error!(error = %error, "metadata webhook fan-out failed");
+                    }
+                    if let Err(error) = self.module.deliver_due_webhooks().await {
+                        tracing::error!(error = %error, "webhook delivery batch failed");
+                    }
+                }
+                _ = prune_ticker.tick() => {
+                    match self.module.prune_webhook_deliveries(self.retention).await {
+                        Ok(0) => {}
+                        Ok(pruned) => tracing::info!(pruned, "pruned finished webhook deliveries"),
+                        Err(error) => {
+                            tracing::error!(error = %error, "webhook delivery prune failed");
+                        }
+                    }
+                }
+                _ = self.shutdown.changed() => {
+                    if *self.shutdown.borrow() {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Parse `WEBHOOK_POLL_INTERVAL_MS` (default 1000ms).
+pub fn webhook_poll_interval_from_env() -> Duration {
+    let millis: u64 = std::env::var("WEBHOOK_POLL_INTERVAL_MS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(1000);
+    Duration::from_millis(millis)
+}
+
+/// Parse `WEBHOOK_DELIVERY_RETENTION_DAYS` (default 7 days).
+pub fn webhook_delivery_retention_from_env() -> time::Duration {
+    let days: i64 = std::env::var("WEBHOOK_DELIVERY_RETENTION_DAYS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .filter(|days| *days > 0)
+        .unwrap_or(7);
+    time::Duration::days(days)
+}
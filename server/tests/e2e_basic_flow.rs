@@ -190,18 +190,23 @@ async fn media_upload_flows_to_actor_icon_end_to_end() {
         PIXEL_PNG
     );
 
-    // When: the uploaded image is set as the account icon.
+    // When: the uploaded image is set as the account icon, with alt text.
     let patched = client
         .patch(format!(
             "http://localhost:8080/api/v1/accounts/{account_id}"
         ))
         .bearer_auth(&jwt)
-        .json(&serde_json::json!({"icon_url": url}))
+        .json(&serde_json::json!({
+            "icon_url": url,
+            "icon_description": "A red square",
+            "icon_focus": {"x": 0.0, "y": 0.5}
+        }))
         .send()
         .await
         .expect("icon patch failed");
 
-    // Then: the patch succeeds and the Actor document exposes the uploaded icon.
+    // Then: the patch succeeds and the Actor document exposes the uploaded
+    // icon with its alt text and focal point.
     assert_eq!(patched.status(), reqwest::StatusCode::OK);
     let actor = client
         .get(format!("http://localhost:8080/ap/accounts/{account_id}"))
@@ -212,4 +217,6 @@ async fn media_upload_flows_to_actor_icon_end_to_end() {
     assert_eq!(actor.status(), reqwest::StatusCode::OK);
     let actor: serde_json::Value = actor.json().await.expect("invalid actor response");
     assert_eq!(actor["icon"]["url"], url);
+    assert_eq!(actor["icon"]["name"], "A red square");
+    assert_eq!(actor["icon"]["focalPoint"], serde_json::json!([0.0, 0.5]));
 }
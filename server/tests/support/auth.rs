@@ -27,7 +27,7 @@ async fn ensure_hydra_client_exists() {
         "grant_types": ["authorization_code"],
         "response_types": ["code"],
         "redirect_uris": [REDIRECT_URI],
-        "scope": "openid offline",
+        "scope": "openid offline accounts:read accounts:write sign",
         "token_endpoint_auth_method": "client_secret_basic",
         "audience": ["account"],
         "skip_consent": true
@@ -160,7 +160,7 @@ async fn hydra_oauth2_flow(session_cookie: &str) -> String {
         .expect("failed to build reqwest client");
 
     let auth_url = format!(
-        "{HYDRA_PUBLIC}/oauth2/auth?client_id={CLIENT_ID}&response_type=code&scope=openid&redirect_uri={REDIRECT_URI}&audience=account&state=e2e-state"
+        "{HYDRA_PUBLIC}/oauth2/auth?client_id={CLIENT_ID}&response_type=code&scope=openid%20accounts:read%20accounts:write%20sign&redirect_uri={REDIRECT_URI}&audience=account&state=e2e-state"
     );
 
     let mut next_url = auth_url;